@@ -0,0 +1,22 @@
+-- Named party presets ("Forest team", "Boss team") that can be swapped in atomically.
+-- Member and bond references are intentionally not foreign keys: a dismissed unit or a removed
+-- bond should surface as a validation error on load instead of silently shrinking the preset.
+
+CREATE TABLE IF NOT EXISTS party_presets (
+    preset_id SERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_party_presets_user_name ON party_presets(user_id, LOWER(name));
+
+CREATE TABLE IF NOT EXISTS party_preset_members (
+    preset_id INT NOT NULL REFERENCES party_presets(preset_id) ON DELETE CASCADE,
+    slot INT NOT NULL,
+    player_unit_id INT NOT NULL,
+    -- Bond that was equipped on this member when the preset was saved (NULL = none).
+    bond_id INT NULL,
+    PRIMARY KEY (preset_id, slot)
+);
+CREATE INDEX IF NOT EXISTS idx_party_preset_members_unit ON party_preset_members(player_unit_id);
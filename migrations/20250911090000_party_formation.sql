@@ -0,0 +1,27 @@
+-- Party formation: front row units absorb enemy attacks first, back row units are only targeted
+-- once the front row has fallen. Ranged units fight well from the back row; melee units do not.
+
+DO $$ BEGIN
+    CREATE TYPE formation_row AS ENUM ('Front','Back');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+ALTER TABLE player_units ADD COLUMN IF NOT EXISTS formation_row formation_row NOT NULL DEFAULT 'Front';
+
+-- Attack style of the master unit (drives back row damage modifiers).
+ALTER TABLE units ADD COLUMN IF NOT EXISTS is_ranged BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE units SET is_ranged = TRUE WHERE name IN (
+    'Scout Ranger',
+    'Apprentice Mage',
+    'Battle Cleric',
+    'Arcane Trickster',
+    'War Drummer',
+    'Frost Warden',
+    'Storm Herald',
+    'Ember Drake',
+    'Temporal Sprite',
+    'Aether Serpent',
+    'Mythic Kitsune'
+);
+
+-- Presets remember where each member stood.
+ALTER TABLE party_preset_members ADD COLUMN IF NOT EXISTS formation_row formation_row NOT NULL DEFAULT 'Front';
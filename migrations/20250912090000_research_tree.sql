@@ -0,0 +1,9 @@
+-- Research tree: passive nodes bought with research points earned from completed species research.
+-- Node definitions live in code (saga::research::RESEARCH_TREE); only unlocks are persisted.
+
+CREATE TABLE IF NOT EXISTS player_research_nodes (
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    node_key TEXT NOT NULL,
+    unlocked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, node_key)
+);
@@ -0,0 +1,12 @@
+-- Contract negotiation: drafted contracts carry the currently offered terms and the number of
+-- counters used; recruited humans keep the agreed terms. NULL terms on a draft mean "opening
+-- demands" (drafts created before negotiation existed).
+
+ALTER TABLE drafted_human_contracts ADD COLUMN IF NOT EXISTS wage BIGINT NULL;
+ALTER TABLE drafted_human_contracts ADD COLUMN IF NOT EXISTS loot_share_pct INT NULL;
+ALTER TABLE drafted_human_contracts ADD COLUMN IF NOT EXISTS party_slot BOOLEAN NULL;
+ALTER TABLE drafted_human_contracts ADD COLUMN IF NOT EXISTS negotiation_rounds INT NOT NULL DEFAULT 0;
+
+ALTER TABLE player_units ADD COLUMN IF NOT EXISTS contract_wage BIGINT NULL;
+ALTER TABLE player_units ADD COLUMN IF NOT EXISTS contract_loot_share_pct INT NULL;
+ALTER TABLE player_units ADD COLUMN IF NOT EXISTS contract_party_slot BOOLEAN NOT NULL DEFAULT FALSE;
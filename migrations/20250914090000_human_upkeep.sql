@@ -0,0 +1,8 @@
+-- Human upkeep: morale per unit and the last UTC day wages were settled per player.
+
+ALTER TABLE player_units ADD COLUMN IF NOT EXISTS morale INT NOT NULL DEFAULT 100;
+DO $$ BEGIN
+    ALTER TABLE player_units ADD CONSTRAINT chk_player_units_morale CHECK (morale BETWEEN 0 AND 100);
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+ALTER TABLE player_saga_profile ADD COLUMN IF NOT EXISTS last_upkeep_date DATE NULL;
@@ -0,0 +1,65 @@
+-- Item registry: the items table becomes the source of truth for item definitions.
+-- The bot loads these rows into memory at startup (and on `/adminutil reloaditems`).
+
+ALTER TABLE items
+    ADD COLUMN IF NOT EXISTS item_key TEXT NULL,
+    ADD COLUMN IF NOT EXISTS emoji TEXT NOT NULL DEFAULT '❔',
+    ADD COLUMN IF NOT EXISTS category TEXT NOT NULL DEFAULT 'Resource',
+    ADD COLUMN IF NOT EXISTS rarity TEXT NOT NULL DEFAULT 'Common',
+    ADD COLUMN IF NOT EXISTS is_sellable BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS is_tradeable BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS buy_price BIGINT NULL,
+    ADD COLUMN IF NOT EXISTS sell_price BIGINT NULL,
+    ADD COLUMN IF NOT EXISTS aliases TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE items i SET
+    item_key = v.item_key,
+    name = v.name,
+    description = v.description,
+    emoji = v.emoji,
+    category = v.category,
+    rarity = v.rarity,
+    is_sellable = v.is_sellable,
+    is_tradeable = v.is_tradeable,
+    buy_price = v.buy_price,
+    sell_price = v.sell_price,
+    aliases = v.aliases
+FROM (VALUES
+ (1,'fish','Fish','A common fish. Good for selling in bulk.','🐟','Resource','Common',TRUE,TRUE,20,10,ARRAY[]::TEXT[]),
+ (2,'ore','Ore','A chunk of raw, unprocessed ore.','⛏️','Resource','Common',TRUE,TRUE,100,50,ARRAY[]::TEXT[]),
+ (3,'gem','Gem','A polished, valuable gemstone.','💎','Resource','Uncommon',TRUE,TRUE,500,250,ARRAY[]::TEXT[]),
+ (4,'goldenfish','Golden Fish','An incredibly rare and valuable fish. A true prize!','🐠','Special','Rare',TRUE,TRUE,NULL,1000,ARRAY['golden']::TEXT[]),
+ (5,'largegeode','Large Geode','A heavy, unassuming rock. Perhaps something valuable is inside?','🪨','Special','Rare',FALSE,TRUE,NULL,NULL,ARRAY['geode']::TEXT[]),
+ (6,'ancientrelic','Ancient Relic','A mysterious artifact from a forgotten era. Its value is immense.','🏺','Special','Legendary',TRUE,TRUE,NULL,10000,ARRAY['relic']::TEXT[]),
+ (7,'xpbooster','XP Booster','Doubles XP gain from working for one hour.','🚀','Consumable','Rare',FALSE,TRUE,2000,NULL,ARRAY['booster']::TEXT[]),
+ (8,'slimegel','Slime Gel','A sticky, gelatinous substance. Surprisingly useful in crafting.','🟢','Resource','Common',TRUE,TRUE,NULL,5,ARRAY['gel']::TEXT[]),
+ (9,'slimedata','Slime Research Data','Combat notes that could be used to tame a slime.','🔬','Special','Uncommon',FALSE,FALSE,NULL,NULL,ARRAY['data']::TEXT[]),
+ (10,'taminglure','Taming Lure','A lure used to attract and pacify wild or proud units for bonding.','🐾','Consumable','Uncommon',TRUE,TRUE,250,125,ARRAY['lure','contract']::TEXT[]),
+ (11,'healthpotion','Health Potion','A basic potion that restores a small amount of health.','🧪','Consumable','Uncommon',TRUE,TRUE,NULL,50,ARRAY['potion']::TEXT[]),
+ (12,'wolfdata','Wolf Research Data','Observations on wolf behavior, useful for taming.','📓','Special','Uncommon',FALSE,FALSE,NULL,NULL,ARRAY['wolfresearchdata']::TEXT[]),
+ (13,'boardata','Boar Research Data','Notes on boar aggression and patterns.','📕','Special','Uncommon',FALSE,FALSE,NULL,NULL,ARRAY['boarresearchdata']::TEXT[]),
+ (14,'forestcontract','Forest Contract Parchment','A blank contract ready to draft a local human recruit.','📜','Special','Rare',TRUE,TRUE,NULL,300,ARRAY['forestparchment']::TEXT[]),
+ (15,'frontiercontract','Frontier Contract Parchment','Higher grade contract for seasoned humans.','📜','Special','Rare',TRUE,TRUE,NULL,500,ARRAY['frontierparchment']::TEXT[]),
+ (16,'scholarnotes','Scholar Research Notes','Dense annotations that accelerate future discoveries.','📘','Special','Rare',FALSE,FALSE,NULL,NULL,ARRAY['researchnotes']::TEXT[]),
+ (17,'greaterhealthpotion','Greater Health Potion','Restores a large amount of health.','🧪','Consumable','Rare',TRUE,TRUE,NULL,150,ARRAY['greaterpotion']::TEXT[]),
+ (18,'staminadraft','Stamina Draft','Restores action stamina in the saga.','🥤','Consumable','Rare',TRUE,TRUE,NULL,120,ARRAY['draft']::TEXT[]),
+ (19,'focustonic','Focus Tonic','Slightly increases research drop rate for a short time.','🧴','Consumable','Rare',TRUE,TRUE,NULL,140,ARRAY['tonic']::TEXT[]),
+ (20,'beardata','Bear Research Data','Heavy scrawlings on bear movement and power.','📙','Special','Rare',FALSE,FALSE,NULL,NULL,ARRAY['bearresearchdata']::TEXT[]),
+ (21,'spiderdata','Spider Research Data','Sketched web patterns and venom potency notes.','🕷️','Special','Rare',FALSE,FALSE,NULL,NULL,ARRAY['spiderresearchdata']::TEXT[])
+) AS v(item_id, item_key, name, description, emoji, category, rarity, is_sellable, is_tradeable, buy_price, sell_price, aliases)
+WHERE i.item_id = v.item_id;
+
+-- Rows added outside the seed above fall back to a key derived from their name.
+UPDATE items SET item_key = lower(regexp_replace(name, '[^A-Za-z0-9]', '', 'g')) WHERE item_key IS NULL;
+ALTER TABLE items ALTER COLUMN item_key SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_items_item_key ON items(item_key);
+
+DO $$ BEGIN
+    ALTER TABLE items ADD CONSTRAINT chk_items_category CHECK (category IN ('Resource','Special','Consumable'));
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+DO $$ BEGIN
+    ALTER TABLE items ADD CONSTRAINT chk_items_rarity CHECK (rarity IN ('Common','Uncommon','Rare','Legendary'));
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+DO $$ BEGIN
+    ALTER TABLE items ADD CONSTRAINT chk_items_prices CHECK ((buy_price IS NULL OR buy_price > 0) AND (sell_price IS NULL OR sell_price >= 0));
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
@@ -0,0 +1,31 @@
+-- Player marketplace: fixed-price listings and timed auctions. Listed items are held in escrow
+-- (removed from the seller's inventory) until sold, cancelled or expired; the current high bid
+-- of an auction is likewise held from the bidder's balance.
+
+DO $$ BEGIN
+    CREATE TYPE market_listing_status AS ENUM ('Active','Sold','Cancelled','Expired');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+CREATE TABLE IF NOT EXISTS market_listings (
+    listing_id SERIAL PRIMARY KEY,
+    seller_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    item_id INT NOT NULL REFERENCES items(item_id) ON DELETE RESTRICT,
+    quantity BIGINT NOT NULL CHECK (quantity > 0),
+    -- Buyout price for fixed listings, starting bid for auctions.
+    price BIGINT NOT NULL CHECK (price > 0),
+    is_auction BOOLEAN NOT NULL DEFAULT FALSE,
+    high_bid BIGINT NULL,
+    high_bidder_id BIGINT NULL REFERENCES profiles(user_id) ON DELETE SET NULL,
+    status market_listing_status NOT NULL DEFAULT 'Active',
+    buyer_id BIGINT NULL,
+    sold_price BIGINT NULL,
+    tax_paid BIGINT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    closed_at TIMESTAMPTZ NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_listings_active ON market_listings(expires_at) WHERE status = 'Active';
+CREATE INDEX IF NOT EXISTS idx_market_listings_seller ON market_listings(seller_id, status);
+
+INSERT INTO bot_config (key, value) VALUES ('market_tax_pct', '5') ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Supply/demand pricing: a per-item price multiplier (basis points, 10000 = base price) that
+-- opens each day at the previous close, plus daily bought/sold volume used to move it.
+
+CREATE TABLE IF NOT EXISTS item_prices (
+    item_id INT PRIMARY KEY REFERENCES items(item_id) ON DELETE CASCADE,
+    open_bp BIGINT NOT NULL DEFAULT 10000 CHECK (open_bp > 0),
+    updated_on DATE NOT NULL DEFAULT CURRENT_DATE
+);
+
+CREATE TABLE IF NOT EXISTS item_market_volume (
+    item_id INT NOT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    bought BIGINT NOT NULL DEFAULT 0,
+    sold BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (item_id, day)
+);
+
+INSERT INTO item_prices (item_id) SELECT item_id FROM items ON CONFLICT (item_id) DO NOTHING;
+
+INSERT INTO bot_config (key, value) VALUES ('price_band_min_pct', '50') ON CONFLICT (key) DO NOTHING;
+INSERT INTO bot_config (key, value) VALUES ('price_band_max_pct', '200') ON CONFLICT (key) DO NOTHING;
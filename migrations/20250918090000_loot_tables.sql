@@ -0,0 +1,58 @@
+-- Data-defined loot tables for openable containers, with per-user pity counters and open history.
+
+CREATE TABLE IF NOT EXISTS loot_tables (
+    table_key TEXT PRIMARY KEY,
+    -- The inventory item consumed to open this table (NULL for tables only used by code).
+    container_item_id INT NULL UNIQUE REFERENCES items(item_id) ON DELETE CASCADE,
+    rolls INT NOT NULL DEFAULT 1 CHECK (rolls BETWEEN 1 AND 10),
+    pity_threshold INT NULL CHECK (pity_threshold IS NULL OR pity_threshold > 0)
+);
+
+CREATE TABLE IF NOT EXISTS loot_table_entries (
+    entry_id SERIAL PRIMARY KEY,
+    table_key TEXT NOT NULL REFERENCES loot_tables(table_key) ON DELETE CASCADE,
+    -- NULL drops coins.
+    item_id INT NULL REFERENCES items(item_id) ON DELETE CASCADE,
+    min_quantity BIGINT NOT NULL DEFAULT 1 CHECK (min_quantity > 0),
+    max_quantity BIGINT NOT NULL DEFAULT 1 CHECK (max_quantity >= min_quantity),
+    weight INT NOT NULL CHECK (weight >= 0),
+    is_rare BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE INDEX IF NOT EXISTS idx_loot_table_entries_table ON loot_table_entries(table_key);
+
+CREATE TABLE IF NOT EXISTS loot_pity (
+    user_id BIGINT NOT NULL,
+    table_key TEXT NOT NULL REFERENCES loot_tables(table_key) ON DELETE CASCADE,
+    misses INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, table_key)
+);
+
+CREATE TABLE IF NOT EXISTS loot_history (
+    history_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    table_key TEXT NOT NULL,
+    coins BIGINT NOT NULL DEFAULT 0,
+    item_ids INT[] NOT NULL DEFAULT '{}',
+    quantities BIGINT[] NOT NULL DEFAULT '{}',
+    got_rare BOOLEAN NOT NULL DEFAULT FALSE,
+    pity_used BOOLEAN NOT NULL DEFAULT FALSE,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_loot_history_user ON loot_history(user_id, opened_at DESC);
+
+-- Large Geode: two picks per open; a rare find is guaranteed after 20 opens without one.
+INSERT INTO loot_tables (table_key, container_item_id, rolls, pity_threshold)
+VALUES ('large_geode', 5, 2, 20)
+ON CONFLICT (table_key) DO NOTHING;
+
+INSERT INTO loot_table_entries (table_key, item_id, min_quantity, max_quantity, weight, is_rare)
+SELECT 'large_geode', v.item_id, v.min_q, v.max_q, v.weight, v.is_rare
+FROM (VALUES
+    (NULL::INT, 40::BIGINT, 120::BIGINT, 35, FALSE), -- coins
+    (2, 2, 5, 30, FALSE),                            -- Ore
+    (3, 1, 2, 20, FALSE),                            -- Gem
+    (11, 1, 2, 8, FALSE),                            -- Health Potion
+    (7, 1, 1, 5, TRUE),                              -- XP Booster
+    (6, 1, 1, 2, TRUE)                               -- Ancient Relic
+) AS v(item_id, min_q, max_q, weight, is_rare)
+WHERE NOT EXISTS (SELECT 1 FROM loot_table_entries WHERE table_key = 'large_geode');
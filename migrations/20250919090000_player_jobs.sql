@@ -0,0 +1,84 @@
+-- Data-defined jobs with per-player level, XP and cooldown, plus level-gated job perks.
+-- Replaces the fishing/mining/coding column pairs on profiles.
+
+CREATE TABLE IF NOT EXISTS jobs (
+    job_key TEXT PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    emoji TEXT NOT NULL DEFAULT '🛠️',
+    min_payout BIGINT NOT NULL CHECK (min_payout >= 0),
+    max_payout BIGINT NOT NULL CHECK (max_payout >= min_payout),
+    cooldown_secs INT NOT NULL CHECK (cooldown_secs > 0),
+    xp_gain BIGINT NOT NULL CHECK (xp_gain >= 0),
+    resource_item_id INT NOT NULL REFERENCES items(item_id),
+    resource_min BIGINT NOT NULL CHECK (resource_min > 0),
+    resource_max BIGINT NOT NULL CHECK (resource_max >= resource_min),
+    -- One extra resource per this many levels (0 = no level bonus).
+    resource_level_divisor INT NOT NULL DEFAULT 0 CHECK (resource_level_divisor >= 0),
+    rare_item_id INT NULL REFERENCES items(item_id),
+    -- Chance of the rare drop in basis points (10 000 = always).
+    rare_chance_bp INT NOT NULL DEFAULT 0 CHECK (rare_chance_bp BETWEEN 0 AND 10000),
+    sort_order INT NOT NULL DEFAULT 0
+);
+
+DO $$ BEGIN
+    CREATE TYPE job_perk_kind AS ENUM ('DoubleResource', 'BonusCoins', 'CooldownReduction', 'RareChance');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+CREATE TABLE IF NOT EXISTS job_perks (
+    perk_id SERIAL PRIMARY KEY,
+    job_key TEXT NOT NULL REFERENCES jobs(job_key) ON DELETE CASCADE,
+    required_level INT NOT NULL CHECK (required_level > 1),
+    kind job_perk_kind NOT NULL,
+    -- Percentage: chance for DoubleResource, bonus for BonusCoins/RareChance, reduction for CooldownReduction.
+    value_pct INT NOT NULL CHECK (value_pct BETWEEN 1 AND 100),
+    description TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_job_perks_job ON job_perks(job_key, required_level);
+
+CREATE TABLE IF NOT EXISTS player_jobs (
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    job_key TEXT NOT NULL REFERENCES jobs(job_key) ON DELETE CASCADE,
+    level INT NOT NULL DEFAULT 1 CHECK (level >= 1),
+    xp BIGINT NOT NULL DEFAULT 0 CHECK (xp >= 0),
+    last_worked_at TIMESTAMPTZ NULL,
+    PRIMARY KEY (user_id, job_key)
+);
+
+INSERT INTO jobs (job_key, display_name, emoji, min_payout, max_payout, cooldown_secs, xp_gain, resource_item_id, resource_min, resource_max, resource_level_divisor, rare_item_id, rare_chance_bp, sort_order) VALUES
+    ('fishing', 'Fishing', '🎣', 25, 75, 1800, 10, 1, 3, 8, 2, 4, 500, 1),
+    ('mining', 'Mining', '⛏️', 100, 300, 7200, 25, 2, 5, 15, 1, 5, 200, 2),
+    ('coding', 'Coding', '💻', 400, 800, 28800, 100, 3, 1, 3, 5, NULL, 0, 3)
+ON CONFLICT (job_key) DO NOTHING;
+
+INSERT INTO job_perks (job_key, required_level, kind, value_pct, description)
+SELECT v.job_key, v.required_level, v.kind::job_perk_kind, v.value_pct, v.description
+FROM (VALUES
+    ('fishing', 5, 'RareChance', 50, '+50% Golden Fish chance'),
+    ('fishing', 10, 'CooldownReduction', 20, '20% shorter cooldown'),
+    ('mining', 5, 'CooldownReduction', 10, '10% shorter cooldown'),
+    ('mining', 10, 'DoubleResource', 15, '15% chance of double ore'),
+    ('coding', 5, 'BonusCoins', 10, '+10% coins'),
+    ('coding', 10, 'DoubleResource', 10, '10% chance of double gems')
+) AS v(job_key, required_level, kind, value_pct, description)
+WHERE NOT EXISTS (SELECT 1 FROM job_perks p WHERE p.job_key = v.job_key AND p.required_level = v.required_level);
+
+-- Carry existing progress over. The old shared cooldown is not carried over.
+DO $$ BEGIN
+    IF EXISTS (SELECT 1 FROM information_schema.columns WHERE table_name = 'profiles' AND column_name = 'fishing_level') THEN
+        INSERT INTO player_jobs (user_id, job_key, level, xp)
+        SELECT user_id, 'fishing', fishing_level, fishing_xp FROM profiles WHERE fishing_level > 1 OR fishing_xp > 0
+        UNION ALL
+        SELECT user_id, 'mining', mining_level, mining_xp FROM profiles WHERE mining_level > 1 OR mining_xp > 0
+        UNION ALL
+        SELECT user_id, 'coding', coding_level, coding_xp FROM profiles WHERE coding_level > 1 OR coding_xp > 0
+        ON CONFLICT (user_id, job_key) DO NOTHING;
+    END IF;
+END $$;
+
+ALTER TABLE profiles
+    DROP COLUMN IF EXISTS fishing_xp,
+    DROP COLUMN IF EXISTS fishing_level,
+    DROP COLUMN IF EXISTS mining_xp,
+    DROP COLUMN IF EXISTS mining_level,
+    DROP COLUMN IF EXISTS coding_xp,
+    DROP COLUMN IF EXISTS coding_level;
@@ -0,0 +1,63 @@
+-- Crafting profession: timed crafts in a per-player queue, batch crafting, quality tiers and
+-- ingredient saving. Ingredients are taken when a craft is queued; outputs are granted when
+-- the background worker completes it.
+
+ALTER TABLE recipes
+    ADD COLUMN IF NOT EXISTS craft_secs INT NOT NULL DEFAULT 60 CHECK (craft_secs > 0),
+    ADD COLUMN IF NOT EXISTS xp_reward BIGINT NOT NULL DEFAULT 10 CHECK (xp_reward >= 0),
+    ADD COLUMN IF NOT EXISTS required_level INT NOT NULL DEFAULT 1 CHECK (required_level >= 1),
+    -- Output of a Masterwork craft (e.g. a Greater Health Potion from a Health Potion recipe).
+    -- NULL means a Masterwork craft yields double the normal output instead.
+    ADD COLUMN IF NOT EXISTS masterwork_item_id INT NULL REFERENCES items(item_id);
+
+CREATE TABLE IF NOT EXISTS crafting_profiles (
+    user_id BIGINT PRIMARY KEY REFERENCES profiles(user_id) ON DELETE CASCADE,
+    level INT NOT NULL DEFAULT 1 CHECK (level >= 1),
+    xp BIGINT NOT NULL DEFAULT 0 CHECK (xp >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS craft_queue (
+    craft_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    recipe_id INT NOT NULL REFERENCES recipes(recipe_id) ON DELETE CASCADE,
+    quantity INT NOT NULL CHECK (quantity BETWEEN 1 AND 10),
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    starts_at TIMESTAMPTZ NOT NULL,
+    completes_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ NULL,
+    -- Filled in on completion: crafts per quality tier and crafts whose ingredients were saved.
+    standard_count INT NOT NULL DEFAULT 0,
+    fine_count INT NOT NULL DEFAULT 0,
+    masterwork_count INT NOT NULL DEFAULT 0,
+    saved_count INT NOT NULL DEFAULT 0,
+    xp_gained BIGINT NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS idx_craft_queue_due ON craft_queue(completes_at) WHERE completed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_craft_queue_user ON craft_queue(user_id, completes_at);
+
+-- Starter recipes (only when the output is not craftable yet).
+INSERT INTO recipes (output_item_id, output_quantity, craft_secs, xp_reward, required_level, masterwork_item_id)
+SELECT v.output_item_id, v.output_quantity, v.craft_secs, v.xp_reward, v.required_level, v.masterwork_item_id
+FROM (VALUES
+    (11, 1, 60, 10, 1, 17),
+    (10, 1, 120, 15, 1, NULL::INT),
+    (18, 1, 300, 40, 5, NULL::INT),
+    (19, 1, 300, 40, 5, NULL::INT)
+) AS v(output_item_id, output_quantity, craft_secs, xp_reward, required_level, masterwork_item_id)
+WHERE NOT EXISTS (SELECT 1 FROM recipes r WHERE r.output_item_id = v.output_item_id);
+
+INSERT INTO recipe_ingredients (recipe_id, item_id, quantity)
+SELECT r.recipe_id, v.item_id, v.quantity
+FROM (VALUES
+    (11, 8, 3),
+    (11, 1, 1),
+    (10, 1, 2),
+    (10, 3, 1),
+    (18, 4, 1),
+    (18, 8, 2),
+    (19, 3, 2),
+    (19, 2, 3)
+) AS v(output_item_id, item_id, quantity)
+JOIN recipes r ON r.output_item_id = v.output_item_id
+WHERE NOT EXISTS (SELECT 1 FROM recipe_ingredients i WHERE i.recipe_id = r.recipe_id)
+ON CONFLICT (recipe_id, item_id) DO NOTHING;
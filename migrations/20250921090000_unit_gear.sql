@@ -0,0 +1,43 @@
+-- Unit gear: item-based weapon / armor / trinket slots on player units. Gear adds flat stats in
+-- battle, loses durability every battle and stops working when broken until repaired at the
+-- tavern.
+
+DO $$ BEGIN
+    CREATE TYPE gear_slot AS ENUM ('Weapon', 'Armor', 'Trinket');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+ALTER TABLE items DROP CONSTRAINT IF EXISTS chk_items_category;
+ALTER TABLE items ADD CONSTRAINT chk_items_category
+    CHECK (category IN ('Resource','Special','Consumable','Equipment'));
+
+INSERT INTO items (item_id, item_key, name, description, emoji, category, rarity, is_sellable, is_tradeable, buy_price, sell_price, aliases) VALUES
+ (22,'ironsword','Iron Sword','A dependable blade. Equip on a unit for extra attack.','🗡️','Equipment','Uncommon',TRUE,TRUE,NULL,60,ARRAY['sword']::TEXT[]),
+ (23,'leatherarmor','Leather Armor','Hardened leather that turns aside glancing blows.','🛡️','Equipment','Uncommon',TRUE,TRUE,NULL,60,ARRAY['armor']::TEXT[]),
+ (24,'luckycharm','Lucky Charm','A worn trinket that seems to help a little with everything.','🧿','Equipment','Rare',TRUE,TRUE,NULL,90,ARRAY['charm']::TEXT[])
+ON CONFLICT (item_id) DO NOTHING;
+
+-- Stats and durability of every equippable item.
+CREATE TABLE IF NOT EXISTS gear_stats (
+    item_id INT PRIMARY KEY REFERENCES items(item_id) ON DELETE CASCADE,
+    slot gear_slot NOT NULL,
+    attack INT NOT NULL DEFAULT 0,
+    defense INT NOT NULL DEFAULT 0,
+    health INT NOT NULL DEFAULT 0,
+    max_durability INT NOT NULL CHECK (max_durability > 0)
+);
+
+INSERT INTO gear_stats (item_id, slot, attack, defense, health, max_durability) VALUES
+ (22,'Weapon',4,0,0,30),
+ (23,'Armor',0,3,10,30),
+ (24,'Trinket',1,1,5,40)
+ON CONFLICT (item_id) DO NOTHING;
+
+-- One item per slot per unit. The item leaves the owner's inventory while equipped.
+CREATE TABLE IF NOT EXISTS player_unit_gear (
+    player_unit_id INT NOT NULL REFERENCES player_units(player_unit_id) ON DELETE CASCADE,
+    slot gear_slot NOT NULL,
+    item_id INT NOT NULL REFERENCES gear_stats(item_id) ON DELETE RESTRICT,
+    durability INT NOT NULL CHECK (durability >= 0),
+    equipped_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (player_unit_id, slot)
+);
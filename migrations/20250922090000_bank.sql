@@ -0,0 +1,54 @@
+-- Bank: a savings balance kept apart from the wallet (`profiles.balance`), daily interest capped
+-- by account rank, small installment loans, and a ledger of every bank-related balance change.
+-- Only the wallet can be spent on bets and purchases.
+
+DO $$ BEGIN
+    CREATE TYPE bank_entry_kind AS ENUM (
+        'Deposit', 'Withdrawal', 'Interest', 'LoanIssued', 'LoanPayment', 'LoanPenalty', 'LoanDefault'
+    );
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+DO $$ BEGIN
+    CREATE TYPE loan_status AS ENUM ('Active', 'Repaid', 'Defaulted');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+CREATE TABLE IF NOT EXISTS bank_accounts (
+    user_id BIGINT PRIMARY KEY REFERENCES profiles(user_id) ON DELETE CASCADE,
+    balance BIGINT NOT NULL DEFAULT 0 CHECK (balance >= 0),
+    -- Account rank grows with time in good standing since this moment; a loan default resets it.
+    rank_since TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Last UTC day interest was paid for.
+    interest_paid_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    credit_blocked_until TIMESTAMPTZ NULL,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS bank_loans (
+    loan_id SERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    principal BIGINT NOT NULL CHECK (principal > 0),
+    -- Principal plus the origination fee plus any missed-payment penalties.
+    total_due BIGINT NOT NULL CHECK (total_due >= principal),
+    repaid BIGINT NOT NULL DEFAULT 0 CHECK (repaid >= 0),
+    installment BIGINT NOT NULL CHECK (installment > 0),
+    next_due_at TIMESTAMPTZ NOT NULL,
+    missed_payments INT NOT NULL DEFAULT 0,
+    status loan_status NOT NULL DEFAULT 'Active',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ NULL
+);
+CREATE UNIQUE INDEX IF NOT EXISTS uq_bank_loans_active ON bank_loans(user_id) WHERE status = 'Active';
+CREATE INDEX IF NOT EXISTS idx_bank_loans_due ON bank_loans(next_due_at) WHERE status = 'Active';
+
+-- Append-only: one row per bank operation with its effect on the wallet and the bank balance.
+CREATE TABLE IF NOT EXISTS bank_ledger (
+    entry_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    kind bank_entry_kind NOT NULL,
+    wallet_delta BIGINT NOT NULL DEFAULT 0,
+    bank_delta BIGINT NOT NULL DEFAULT 0,
+    bank_balance BIGINT NOT NULL,
+    loan_id INT NULL REFERENCES bank_loans(loan_id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_bank_ledger_user ON bank_ledger(user_id, created_at DESC);
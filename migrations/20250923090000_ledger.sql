@@ -0,0 +1,43 @@
+-- Economic ledger: an append-only record of every change to a wallet (`profiles.balance`) or an
+-- inventory row, written in the same transaction as the change, with a reason code and an
+-- optional source reference such as `listing:42`. A nightly job checks that balances and
+-- inventories still equal their ledger sums.
+
+DO $$ BEGIN
+    CREATE TYPE ledger_reason AS ENUM (
+        'Opening', 'Work', 'Shop', 'Sell', 'Give', 'Trade', 'Market', 'Tavern', 'Recruit', 'Bond',
+        'Battle', 'Quest', 'Task', 'Contract', 'Crafting', 'Loot', 'Gear', 'Upkeep', 'Bank',
+        'Game', 'ItemUse'
+    );
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+-- No foreign keys: entries outlive the rows they describe.
+CREATE TABLE IF NOT EXISTS ledger (
+    entry_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    -- NULL for coins, otherwise the item whose quantity changed.
+    item_id INT NULL,
+    delta BIGINT NOT NULL,
+    -- Wallet balance or item quantity right after the change.
+    balance_after BIGINT NOT NULL,
+    reason ledger_reason NOT NULL,
+    source_ref TEXT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_ledger_user ON ledger(user_id, entry_id DESC);
+CREATE INDEX IF NOT EXISTS idx_ledger_user_item ON ledger(user_id, item_id);
+
+-- Opening entries so existing balances and inventories reconcile from day one.
+INSERT INTO ledger (user_id, item_id, delta, balance_after, reason)
+SELECT p.user_id, NULL, p.balance, p.balance, 'Opening'
+FROM profiles p
+WHERE p.balance <> 0
+  AND NOT EXISTS (SELECT 1 FROM ledger l WHERE l.user_id = p.user_id AND l.item_id IS NULL);
+
+INSERT INTO ledger (user_id, item_id, delta, balance_after, reason)
+SELECT i.user_id, i.item_id, i.quantity, i.quantity, 'Opening'
+FROM inventories i
+WHERE i.quantity <> 0
+  AND NOT EXISTS (
+      SELECT 1 FROM ledger l WHERE l.user_id = i.user_id AND l.item_id = i.item_id
+  );
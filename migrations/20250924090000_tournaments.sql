@@ -0,0 +1,39 @@
+-- Tournaments: a registration window where players pay a buy-in into a prize pool, then play
+-- Texas Hold'em or Blackjack with tournament chips across one or more tables until the prize
+-- pool is paid out to the top finishers. Finished entries keep their place and prize as history.
+
+ALTER TYPE ledger_reason ADD VALUE IF NOT EXISTS 'Tournament';
+
+DO $$ BEGIN
+    CREATE TYPE tournament_game AS ENUM ('Holdem', 'Blackjack');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+DO $$ BEGIN
+    CREATE TYPE tournament_status AS ENUM ('Registering', 'Running', 'Finished', 'Cancelled');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+CREATE TABLE IF NOT EXISTS tournaments (
+    tournament_id SERIAL PRIMARY KEY,
+    host_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    game tournament_game NOT NULL,
+    buy_in BIGINT NOT NULL CHECK (buy_in > 0),
+    -- Sum of buy-ins held in escrow until the tournament finishes or is cancelled.
+    prize_pool BIGINT NOT NULL DEFAULT 0 CHECK (prize_pool >= 0),
+    status tournament_status NOT NULL DEFAULT 'Registering',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ NULL,
+    finished_at TIMESTAMPTZ NULL
+);
+CREATE INDEX IF NOT EXISTS idx_tournaments_finished ON tournaments(finished_at DESC) WHERE status = 'Finished';
+
+CREATE TABLE IF NOT EXISTS tournament_entries (
+    tournament_id INT NOT NULL REFERENCES tournaments(tournament_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    -- 1 for the winner; NULL until the tournament finishes.
+    finish_position INT NULL,
+    prize BIGINT NOT NULL DEFAULT 0,
+    registered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tournament_id, user_id)
+);
+CREATE INDEX IF NOT EXISTS idx_tournament_entries_user ON tournament_entries(user_id);
@@ -0,0 +1,9 @@
+-- Chips bought in at a game table. Coins leave the wallet when a player sits down, the stack
+-- moves with each settled round, and whatever is left returns to the wallet on cash-out.
+CREATE TABLE IF NOT EXISTS table_stacks (
+    message_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    chips BIGINT NOT NULL CHECK (chips >= 0),
+    bought_in_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (message_id, user_id)
+);
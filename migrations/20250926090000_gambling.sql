@@ -0,0 +1,35 @@
+-- Gambling statistics per user and game, a per-day running net used for loss limits, and the
+-- limits and cooldowns players set on themselves.
+
+DO $$ BEGIN
+    CREATE TYPE gamble_game AS ENUM ('Blackjack', 'Poker', 'Holdem', 'Rps', 'Tavern');
+EXCEPTION WHEN duplicate_object THEN NULL; END $$;
+
+CREATE TABLE IF NOT EXISTS gambling_stats (
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    game gamble_game NOT NULL,
+    hands_played BIGINT NOT NULL DEFAULT 0,
+    hands_won BIGINT NOT NULL DEFAULT 0,
+    hands_lost BIGINT NOT NULL DEFAULT 0,
+    net BIGINT NOT NULL DEFAULT 0,
+    biggest_win BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, game)
+);
+
+-- Net result per UTC day across every game.
+CREATE TABLE IF NOT EXISTS gambling_daily (
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    net BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
+
+CREATE TABLE IF NOT EXISTS gambling_limits (
+    user_id BIGINT PRIMARY KEY REFERENCES profiles(user_id) ON DELETE CASCADE,
+    daily_loss_limit BIGINT NULL CHECK (daily_loss_limit > 0),
+    -- Raising or removing a limit only takes effect at `pending_from`; a NULL pending limit with
+    -- a `pending_from` set means the limit is being removed.
+    pending_loss_limit BIGINT NULL CHECK (pending_loss_limit > 0),
+    pending_from TIMESTAMPTZ NULL,
+    cooldown_until TIMESTAMPTZ NULL
+);
@@ -0,0 +1,36 @@
+-- Ranked Rock Paper Scissors: a rating per user per season, the matchmaking queue and the
+-- history of rated matches. Seasons are numbered from a fixed start date in code, so there is no
+-- seasons table.
+
+CREATE TABLE IF NOT EXISTS rps_ratings (
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    season INT NOT NULL,
+    rating INT NOT NULL,
+    peak INT NOT NULL,
+    wins INT NOT NULL DEFAULT 0,
+    losses INT NOT NULL DEFAULT 0,
+    brackets_won INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, season)
+);
+
+CREATE INDEX IF NOT EXISTS rps_ratings_season_rating_idx ON rps_ratings (season, rating DESC);
+
+-- Players waiting for a ranked opponent. The match is posted where the second player queued;
+-- the first is pointed to it in their own channel if that differs.
+CREATE TABLE IF NOT EXISTS rps_queue (
+    user_id BIGINT PRIMARY KEY REFERENCES profiles(user_id) ON DELETE CASCADE,
+    channel_id BIGINT NOT NULL,
+    rating INT NOT NULL,
+    queued_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS rps_matches (
+    match_id BIGSERIAL PRIMARY KEY,
+    season INT NOT NULL,
+    winner_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    loser_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    winner_delta INT NOT NULL,
+    loser_delta INT NOT NULL,
+    bracket BOOLEAN NOT NULL DEFAULT FALSE,
+    played_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
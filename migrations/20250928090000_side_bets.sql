@@ -0,0 +1,16 @@
+-- Spectators' side bets on a running game. The stake leaves the bettor's wallet when the bet is
+-- placed, at odds fixed from the players' ratings at that moment; the row is deleted when the
+-- round it was placed on is settled (or refunded), so only open bets live here.
+
+ALTER TYPE gamble_game ADD VALUE IF NOT EXISTS 'SideBet';
+
+CREATE TABLE IF NOT EXISTS side_bets (
+    message_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL REFERENCES profiles(user_id) ON DELETE CASCADE,
+    backed_id BIGINT NOT NULL,
+    stake BIGINT NOT NULL CHECK (stake > 0),
+    -- Decimal odds in hundredths: 185 pays 1.85x the stake.
+    odds INT NOT NULL CHECK (odds > 100),
+    placed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (message_id, user_id)
+);
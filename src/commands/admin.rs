@@ -1,6 +1,8 @@
+use crate::commands::economy::core::registry::registry;
 use crate::{AppState, database};
 use serenity::builder::{CreateCommand, CreateCommandOption, CreateEmbed, EditInteractionResponse};
 use serenity::model::application::{CommandInteraction, CommandOptionType};
+use serenity::model::id::UserId;
 use serenity::prelude::Context;
 
 // Lightweight admin utility to exercise maintenance helpers so they stay active.
@@ -14,6 +16,9 @@ pub fn register() -> CreateCommand {
     // /adminutil researchunit <unit_id>
     // /adminutil cachestats
     // /adminutil sagainit
+    // /adminutil reloaditems
+    // /adminutil ledger <user>
+    // /adminutil games
     CreateCommand::new("adminutil")
         .description("Maintenance utilities (owner-only)")
         .add_option(
@@ -82,6 +87,79 @@ pub fn register() -> CreateCommand {
             "sagainit",
             "Bootstrap base + saga profile and starter unit if absent",
         ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "reloaditems",
+            "Reload item definitions from the database",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "ledger",
+                "Show a user's recent coin and item movements and check them against balances",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "User to audit")
+                    .required(true),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "games",
+            "Show active game sessions and timeout counters",
+        ))
+}
+
+/// Ledger entries listed by `/adminutil ledger`.
+const LEDGER_VIEW_ENTRIES: i64 = 15;
+
+async fn ledger_notes(db: &sqlx::PgPool, user_id: UserId) -> Vec<String> {
+    let items = registry();
+    let mut notes = vec![format!("-- Ledger for <@{}> --", user_id)];
+    match database::ledger::recent_entries(db, user_id, LEDGER_VIEW_ENTRIES).await {
+        Ok(entries) if entries.is_empty() => notes.push("No ledger entries.".into()),
+        Ok(entries) => notes.extend(entries.iter().map(|e| {
+            let what = match e.item_id {
+                Some(item_id) => format!(
+                    "{:+} {} → {}",
+                    e.delta,
+                    items.name_of(item_id),
+                    e.balance_after
+                ),
+                None => format!("{:+} 💰 → {}", e.delta, e.balance_after),
+            };
+            let source = e
+                .source_ref
+                .as_deref()
+                .map(|r| format!(" ({})", r))
+                .unwrap_or_default();
+            format!(
+                "<t:{}:d> {} · {}{}",
+                e.created_at.timestamp(),
+                what,
+                e.reason.label(),
+                source
+            )
+        })),
+        Err(e) => notes.push(format!("Ledger query error: {e}")),
+    }
+    match database::ledger::find_mismatches(db, Some(user_id)).await {
+        Ok(mismatches) if mismatches.is_empty() => {
+            notes.push("✅ Wallet, inventory and bank match the ledger.".into())
+        }
+        Ok(mismatches) => notes.extend(mismatches.iter().map(|m| {
+            let account = match m.item_id {
+                Some(item_id) => items.name_of(item_id),
+                None => m.account.clone(),
+            };
+            format!(
+                "⚠️ {}: holds {} but the ledger sums to {}",
+                account, m.actual, m.expected
+            )
+        })),
+        Err(e) => notes.push(format!("Reconciliation query error: {e}")),
+    }
+    notes
 }
 
 pub async fn run_slash(ctx: &Context, interaction: &mut CommandInteraction) {
@@ -204,11 +282,37 @@ pub async fn run_slash(ctx: &Context, interaction: &mut CommandInteraction) {
                 }
                 tracing::info!(target="adminutil.sagainit", user_id=%interaction.user.id, "Completed saga initialization");
             }
+            ("reloaditems", _) => match database::items::reload_item_registry(db).await {
+                Ok((previous, loaded)) => {
+                    tracing::info!(
+                        target = "adminutil.reloaditems",
+                        previous,
+                        loaded,
+                        "Item registry reloaded"
+                    );
+                    notes.push(format!(
+                        "Item registry reloaded: {} item(s) (was {}).",
+                        loaded, previous
+                    ));
+                }
+                Err(e) => notes.push(format!("Item reload error (registry unchanged): {e}")),
+            },
+            ("ledger", Val::SubCommand(nested)) => {
+                if let Some(arg) = nested.iter().find(|o| o.name == "user")
+                    && let Val::User(user_id) = &arg.value
+                {
+                    notes.extend(ledger_notes(db, *user_id).await);
+                }
+            }
+            ("games", _) => {
+                let metrics = state.game_manager.metrics(std::time::Instant::now());
+                notes.extend(metrics.lines());
+            }
             _ => notes.push("Unknown subcommand.".into()),
         }
     }
     if notes.is_empty() {
-        notes.push("No subcommand provided. Available: markhuman, diaguser, bondtest, researchunit, cachestats, sagainit".into());
+        notes.push("No subcommand provided. Available: markhuman, diaguser, bondtest, researchunit, cachestats, sagainit, reloaditems, ledger, games".into());
     }
     embed = embed.description(notes.join("\n"));
     let builder = EditInteractionResponse::new().embed(embed);
@@ -1,34 +1,32 @@
 //! This is the main controller for the Blackjack game. It implements the `Game` trait
 //! and holds the core, non-async game logic.
 
+use super::rules::{HouseRules, INSURANCE_PAYS};
 use super::state::{BlackjackGame, GamePhase, Hand, HandStatus, Player};
 use crate::commands::games::card::Rank;
-use crate::commands::games::deck::Deck;
-use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::commands::games::lifecycle::{SessionClock, TABLE_LOBBY_TIMEOUT};
+use crate::commands::games::shoe::Shoe;
+use crate::commands::games::{Game, GamePayout, GameUpdate, TABLE_BUY_IN_BETS};
 use serenity::async_trait;
 use serenity::builder::{
     CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
 };
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
 use serenity::model::user::User;
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// How long the player to act has before their hand is stood automatically.
+const TURN_TIMEOUT: Duration = Duration::from_secs(60);
+
 // This is the main entry point from the game engine. It delegates all work
 // to the appropriate handlers and renderers.
 #[async_trait]
 impl Game for BlackjackGame {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
     async fn handle_interaction(
         &mut self,
         ctx: &Context,
@@ -38,23 +36,33 @@ impl Game for BlackjackGame {
         // Handle player turn timeout before processing any interaction.
         if self.phase == GamePhase::PlayerTurns {
             let elapsed = self.last_action_time.elapsed();
-            if elapsed > Duration::from_secs(60) {
-                let player = &mut self.players[self.current_player_index];
-                player.hands[self.current_hand_index].status = HandStatus::Stood;
-                player.has_passed_turn = false;
-                self.advance_turn();
-                self.inactivity_warned = false; // reset for next player
+            if elapsed > TURN_TIMEOUT {
+                self.stand_stalled_player();
+                if self.phase == GamePhase::GameOver {
+                    interaction.defer(&ctx.http).await.ok();
+                    return self.finish_round(db, interaction.message.id).await;
+                }
             } else if elapsed > Duration::from_secs(40) && !self.inactivity_warned {
                 self.inactivity_warned = true; // single warning in-channel log (embed log style not implemented)
             }
         }
 
+        // Players may cash out whenever no round is being played.
+        if interaction.data.custom_id == "bj_leave"
+            && matches!(
+                self.phase,
+                GamePhase::WaitingForPlayers | GamePhase::Betting | GamePhase::GameOver
+            )
+        {
+            return self.leave_table(ctx, interaction, db).await;
+        }
+
         match self.phase {
             GamePhase::WaitingForPlayers => self.handle_lobby(ctx, interaction, db).await,
             GamePhase::Betting => self.handle_betting(ctx, interaction, db).await,
             GamePhase::Insurance => self.handle_insurance(ctx, interaction, db).await,
             GamePhase::PlayerTurns => self.handle_player_turn(ctx, interaction, db).await,
-            GamePhase::GameOver => self.handle_game_over(ctx, interaction).await,
+            GamePhase::GameOver => self.handle_game_over(ctx, interaction, db).await,
             GamePhase::DealerTurn => {
                 self.send_ephemeral_response(
                     ctx,
@@ -108,12 +116,62 @@ impl Game for BlackjackGame {
         };
         (content, embed, components)
     }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.players.iter().map(|p| p.user.id).collect()
+    }
+
+    /// The lobby waits for the host to deal, then each player has `TURN_TIMEOUT` to act, and
+    /// the table as long to answer the insurance offer.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        match self.phase {
+            GamePhase::WaitingForPlayers => Some(clock.started + TABLE_LOBBY_TIMEOUT),
+            GamePhase::Insurance | GamePhase::PlayerTurns => {
+                Some(self.last_action_time + TURN_TIMEOUT)
+            }
+            _ => None,
+        }
+    }
+
+    async fn on_timeout(&mut self, db: &PgPool, message_id: MessageId) -> GameUpdate {
+        match self.phase {
+            GamePhase::WaitingForPlayers => {
+                println!(
+                    "[BJ] Lobby for game {} timed out and was removed.",
+                    message_id
+                );
+                let embed = CreateEmbed::new()
+                    .title("Blackjack Lobby Expired")
+                    .description("The game was not started by the host in time.")
+                    .color(0xFF0000); // Red
+                GameUpdate::Expired {
+                    content: "**Blackjack Lobby Expired**".to_string(),
+                    embed: Box::new(embed),
+                    components: vec![],
+                }
+            }
+            GamePhase::Insurance | GamePhase::PlayerTurns => {
+                if self.phase == GamePhase::Insurance {
+                    self.close_insurance();
+                } else {
+                    self.stand_stalled_player();
+                }
+                if self.phase == GamePhase::GameOver {
+                    self.finish_round(db, message_id).await
+                } else {
+                    GameUpdate::ReRender
+                }
+            }
+            _ => GameUpdate::NoOp,
+        }
+    }
 }
 
 // This block contains the core, non-async game logic and state manipulation.
 // These are the "rules" of the game.
 impl BlackjackGame {
     pub fn new(host: Arc<User>, min_bet: i64) -> Self {
+        let rules = HouseRules::default();
         Self {
             host_id: host.id.get(),
             players: vec![Player {
@@ -121,11 +179,14 @@ impl BlackjackGame {
                 hands: Vec::new(),
                 insurance: 0,
                 current_bet: min_bet,
+                chips: min_bet * TABLE_BUY_IN_BETS,
                 insurance_decision_made: false,
                 has_passed_turn: false,
             }],
             dealer_hand: Hand::new(0),
-            deck: Deck::new(),
+            shoe: Shoe::new(rules.decks as usize, rules.penetration),
+            rules,
+            reshuffled: false,
             phase: GamePhase::WaitingForPlayers,
             min_bet,
             pot: 0,
@@ -135,11 +196,30 @@ impl BlackjackGame {
             current_hand_index: 0,
             last_action_time: Instant::now(),
             inactivity_warned: false,
+            round_settled: true,
+            table_error: None,
         }
     }
 
-    pub fn is_in_lobby(&self) -> bool {
-        self.phase == GamePhase::WaitingForPlayers
+    /// Seats the table under `rules`, with a fresh shoe to match.
+    pub fn with_rules(mut self, rules: HouseRules) -> Self {
+        self.shoe = Shoe::new(rules.decks as usize, rules.penetration);
+        self.rules = rules;
+        self
+    }
+
+    /// Stands the hand of a player who let their turn run out and moves play on.
+    fn stand_stalled_player(&mut self) {
+        let player = &mut self.players[self.current_player_index];
+        player.hands[self.current_hand_index].status = HandStatus::Stood;
+        player.has_passed_turn = false;
+        self.advance_turn();
+        self.inactivity_warned = false; // reset for next player
+    }
+
+    /// Chips each player buys in for; friendly tables have no buy-in.
+    pub fn buy_in(&self) -> i64 {
+        self.min_bet * TABLE_BUY_IN_BETS
     }
 
     pub(super) fn start_game(&mut self) {
@@ -154,18 +234,26 @@ impl BlackjackGame {
         self.last_action_time = Instant::now();
     }
 
-    pub(super) fn deal_new_round(&mut self) {
-        self.deck = Deck::new();
-        self.deck.shuffle(); // Don't forget to shuffle!
+    /// Moves every hand on the table into the shoe's discard tray.
+    fn discard_hands(&mut self) {
+        let dealer = std::mem::replace(&mut self.dealer_hand, Hand::new(0));
+        self.shoe.discard(dealer.cards);
+        for player in self.players.iter_mut() {
+            for hand in player.hands.drain(..) {
+                self.shoe.discard(hand.cards);
+            }
+        }
+    }
 
-        // (✓) FIXED: Use the public method `cards_remaining` instead of the private field.
-        if self.deck.cards_remaining() < self.players.len() * 4 + 4 {
-            self.phase = GamePhase::GameOver;
-            // In a real scenario, you might want a specific message for this.
-            return;
+    /// Deals a round. If the cut card came out last round, the shoe is reshuffled first; the
+    /// dealer peeks for Blackjack and the round ends at once if they have it.
+    pub(super) fn deal_new_round(&mut self) {
+        self.discard_hands();
+        self.reshuffled = self.shoe.cut_card_reached();
+        if self.reshuffled {
+            self.shoe.reshuffle();
         }
 
-        self.dealer_hand = Hand::new(0);
         self.pot = 0;
         self.current_player_index = 0;
         self.current_hand_index = 0;
@@ -185,11 +273,11 @@ impl BlackjackGame {
 
         for _ in 0..2 {
             for player in self.players.iter_mut() {
-                if let Some(card) = self.deck.deal_one() {
+                if let Some(card) = self.shoe.deal_one() {
                     player.hands[0].add_card(card);
                 }
             }
-            if let Some(card) = self.deck.deal_one() {
+            if let Some(card) = self.shoe.deal_one() {
                 self.dealer_hand.add_card(card);
             }
         }
@@ -200,26 +288,55 @@ impl BlackjackGame {
             }
         }
 
-        if self
+        let ace_up = self
             .dealer_hand
             .cards
             .first()
-            .is_some_and(|c| c.rank == Rank::Ace)
-            && self.min_bet > 0
-        {
+            .is_some_and(|c| c.rank == Rank::Ace);
+        if ace_up && self.rules.insurance && self.min_bet > 0 {
             self.phase = GamePhase::Insurance;
+            // Hands dealt a Blackjack aren't offered insurance, so there may be nobody to ask.
+            if self.insurance_decided() {
+                self.close_insurance();
+            }
+        } else if self.dealer_has_blackjack() {
+            self.phase = GamePhase::GameOver;
         } else {
             self.phase = GamePhase::PlayerTurns;
-            self.find_next_hand();
+            if !self.find_next_hand() {
+                self.play_dealer_turn();
+            }
         }
         self.last_action_time = Instant::now();
     }
 
-    pub(super) fn reset_for_next_round(&mut self) {
-        // NOTE: Actual DB balance checks occur in handlers; here we only drop players who set their bet to 0 (can't afford min bet last round)
-        if self.min_bet > 0 {
-            self.players.retain(|p| p.current_bet >= self.min_bet);
+    /// Whether every player offered insurance has answered.
+    pub(super) fn insurance_decided(&self) -> bool {
+        self.players
+            .iter()
+            .all(|p| p.insurance_decision_made || p.hands[0].status == HandStatus::Blackjack)
+    }
+
+    /// Ends the insurance offer, with anyone who hasn't answered declining. The dealer then
+    /// checks for Blackjack and play moves on.
+    pub(super) fn close_insurance(&mut self) {
+        for player in self.players.iter_mut() {
+            player.insurance_decision_made = true;
+        }
+        if self.dealer_has_blackjack() {
+            self.phase = GamePhase::GameOver;
+        } else {
+            self.phase = GamePhase::PlayerTurns;
+            if !self.find_next_hand() {
+                self.play_dealer_turn();
+            }
         }
+        self.last_action_time = Instant::now();
+    }
+
+    /// Starts the next betting round. Players who can no longer cover the minimum bet must
+    /// have been cashed out and removed by the caller.
+    pub(super) fn reset_for_next_round(&mut self) {
         self.ready_players.clear();
         self.pot = 0;
         self.round += 1;
@@ -227,6 +344,7 @@ impl BlackjackGame {
             player.current_bet = self.min_bet;
             player.has_passed_turn = false;
         }
+        self.discard_hands();
         self.phase = GamePhase::Betting;
         self.last_action_time = Instant::now();
     }
@@ -286,8 +404,8 @@ impl BlackjackGame {
 
     pub(super) fn play_dealer_turn(&mut self) {
         self.phase = GamePhase::DealerTurn;
-        while self.dealer_hand.score() < 17 {
-            if let Some(card) = self.deck.deal_one() {
+        while self.rules.dealer_hits(&self.dealer_hand) {
+            if let Some(card) = self.shoe.deal_one() {
                 self.dealer_hand.add_card(card);
             } else {
                 break;
@@ -297,13 +415,17 @@ impl BlackjackGame {
         self.last_action_time = Instant::now();
     }
 
+    pub(super) fn dealer_has_blackjack(&self) -> bool {
+        self.dealer_hand.score() == 21 && self.dealer_hand.cards.len() == 2
+    }
+
     pub(super) fn calculate_payouts(&self) -> (String, Vec<GamePayout>) {
         if self.min_bet == 0 {
             return ("Friendly game, no payouts!".to_string(), Vec::new());
         }
         let dealer_score = self.dealer_hand.score();
         let dealer_busted = dealer_score > 21;
-        let dealer_has_bj = self.dealer_hand.score() == 21 && self.dealer_hand.cards.len() == 2;
+        let dealer_has_bj = self.dealer_has_blackjack();
         let mut overall_results = Vec::new();
         let mut payouts = HashMap::new();
 
@@ -313,11 +435,11 @@ impl BlackjackGame {
 
             if player.insurance > 0 {
                 if dealer_has_bj {
-                    total_winnings += player.insurance * 2;
+                    total_winnings += player.insurance * INSURANCE_PAYS;
                     player_results.push(format!(
                         "**<@{}>**: Insurance paid **💰{}**",
                         player.user.id,
-                        player.insurance * 2
+                        player.insurance * INSURANCE_PAYS
                     ));
                 } else {
                     total_winnings -= player.insurance;
@@ -341,7 +463,7 @@ impl BlackjackGame {
                         if dealer_has_bj {
                             ("Push".to_string(), 0)
                         } else {
-                            let winnings = (hand.bet * 3) / 2;
+                            let winnings = self.rules.blackjack_payout(hand.bet);
                             (format!("**Blackjack!** Wins 💰{}", winnings), winnings)
                         }
                     }
@@ -1,27 +1,17 @@
 //! Handles all `ComponentInteraction` events for the Blackjack game.
 
+use super::rules::MAX_HANDS;
 use super::state::{BlackjackGame, GamePhase, Hand, HandStatus, Player};
 use crate::commands::games::GameUpdate;
-use crate::commands::games::card::Rank;
+use crate::database::models::GambleGame;
+use crate::database::table_stacks;
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::MessageId;
 use serenity::prelude::Context;
 use sqlx::PgPool;
 use std::sync::Arc;
-
-// A robust helper to get a user's balance from the database.
-async fn get_balance(db: &PgPool, user_id: serenity::model::id::UserId) -> i64 {
-    match sqlx::query!(
-        "SELECT balance FROM profiles WHERE user_id = $1",
-        user_id.get() as i64
-    )
-    .fetch_optional(db)
-    .await
-    {
-        Ok(Some(record)) => record.balance,
-        _ => 0,
-    }
-}
+use std::time::Instant;
 
 // A utility to send silent, ephemeral error messages.
 async fn send_ephemeral_response(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
@@ -55,26 +45,24 @@ impl BlackjackGame {
                     .iter()
                     .any(|p| p.user.id == interaction.user.id)
                 {
-                    if self.min_bet > 0 {
-                        let balance = get_balance(db, interaction.user.id).await;
-                        if balance < self.min_bet {
-                            send_ephemeral_response(
-                                ctx,
-                                interaction,
-                                &format!(
-                                    "You cannot afford the minimum bet of **💰{}** to join.",
-                                    self.min_bet
-                                ),
-                            )
-                            .await;
-                            return GameUpdate::NoOp;
-                        }
+                    if self.min_bet > 0
+                        && let Err(e) = table_stacks::buy_in(
+                            db,
+                            interaction.message.id,
+                            interaction.user.id,
+                            self.buy_in(),
+                        )
+                        .await
+                    {
+                        send_ephemeral_response(ctx, interaction, &e).await;
+                        return GameUpdate::NoOp;
                     }
                     self.players.push(Player {
                         user: Arc::new(interaction.user.clone()),
                         hands: Vec::new(),
                         insurance: 0,
                         current_bet: self.min_bet,
+                        chips: self.buy_in(),
                         insurance_decision_made: false,
                         has_passed_turn: false,
                     });
@@ -89,6 +77,9 @@ impl BlackjackGame {
                 if interaction.user.id.get() == self.host_id {
                     self.start_game();
                     interaction.defer(&ctx.http).await.ok();
+                    if self.phase == GamePhase::GameOver {
+                        return self.finish_round(db, interaction.message.id).await;
+                    }
                     GameUpdate::ReRender
                 } else {
                     send_ephemeral_response(ctx, interaction, "Only the host can start the game.")
@@ -135,19 +126,23 @@ impl BlackjackGame {
             send_ephemeral_response(ctx, interaction, "You have already confirmed your bet.").await;
             return GameUpdate::NoOp;
         }
-        let balance = get_balance(db, player.user.id).await;
+        let chips = player.chips;
 
         match interaction.data.custom_id.as_str() {
-            "bj_bet_10" => player.current_bet = (player.current_bet + 10).min(balance),
-            "bj_bet_100" => player.current_bet = (player.current_bet + 100).min(balance),
-            "bj_bet_1000" => player.current_bet = (player.current_bet + 1000).min(balance),
-            "bj_bet_all_in" => player.current_bet = balance,
-            "bj_bet_clear" => player.current_bet = self.min_bet.min(balance),
+            "bj_bet_10" => player.current_bet = (player.current_bet + 10).min(chips),
+            "bj_bet_100" => player.current_bet = (player.current_bet + 100).min(chips),
+            "bj_bet_1000" => player.current_bet = (player.current_bet + 1000).min(chips),
+            "bj_bet_all_in" => player.current_bet = chips,
+            "bj_bet_clear" => player.current_bet = self.min_bet.min(chips),
             "bj_bet_confirm" => {
-                if player.current_bet > balance {
-                    send_ephemeral_response(ctx, interaction, "You cannot bet more than you have.")
-                        .await;
-                    player.current_bet = balance;
+                if player.current_bet > chips {
+                    send_ephemeral_response(
+                        ctx,
+                        interaction,
+                        "You cannot bet more chips than you have.",
+                    )
+                    .await;
+                    player.current_bet = chips;
                     return GameUpdate::ReRender;
                 }
                 if player.current_bet < self.min_bet {
@@ -170,6 +165,9 @@ impl BlackjackGame {
         interaction.defer(&ctx.http).await.ok();
         if self.ready_players.len() == self.players.len() {
             self.deal_new_round();
+            if self.phase == GamePhase::GameOver {
+                return self.finish_round(db, interaction.message.id).await;
+            }
         }
 
         GameUpdate::ReRender
@@ -181,6 +179,7 @@ impl BlackjackGame {
         interaction: &mut ComponentInteraction,
         db: &PgPool,
     ) -> GameUpdate {
+        let table = interaction.message.id;
         let player = match self
             .players
             .iter_mut()
@@ -202,8 +201,7 @@ impl BlackjackGame {
         match interaction.data.custom_id.as_str() {
             "bj_insure_yes" => {
                 let insurance_cost = player.hands[0].bet / 2;
-                let balance = get_balance(db, interaction.user.id).await;
-                if balance < player.hands[0].bet + insurance_cost {
+                if player.chips < player.hands[0].bet + insurance_cost {
                     send_ephemeral_response(
                         ctx,
                         interaction,
@@ -226,21 +224,11 @@ impl BlackjackGame {
         };
 
         interaction.defer(&ctx.http).await.ok();
-        let all_decided = self
-            .players
-            .iter()
-            .all(|p| p.insurance_decision_made || p.hands[0].status == HandStatus::Blackjack);
-        if all_decided {
-            if self.dealer_hand.score() == 21 && self.dealer_hand.cards.len() == 2 {
-                self.phase = GamePhase::GameOver;
-            } else {
-                self.phase = GamePhase::PlayerTurns;
-                self.find_next_hand();
-            }
+        if self.insurance_decided() {
+            self.close_insurance();
         }
         if self.phase == GamePhase::GameOver {
-            let (message, payouts) = self.calculate_payouts();
-            GameUpdate::GameOver { message, payouts }
+            self.finish_round(db, table).await
         } else {
             GameUpdate::ReRender
         }
@@ -258,13 +246,19 @@ impl BlackjackGame {
         }
         interaction.defer(&ctx.http).await.ok();
 
-        let balance = get_balance(db, interaction.user.id).await;
+        let table = interaction.message.id;
+        let rules = self.rules;
+        let current = &self.players[self.current_player_index];
+        let available = current.chips - current.insurance;
 
         match interaction.data.custom_id.as_str() {
             "bj_hit" => {
                 let player = &mut self.players[self.current_player_index];
                 let hand = &mut player.hands[self.current_hand_index];
-                if let Some(card) = self.deck.deal_one() {
+                if !rules.can_hit(hand) {
+                    return GameUpdate::NoOp;
+                }
+                if let Some(card) = self.shoe.deal_one() {
                     hand.add_card(card);
                 }
                 if hand.score() >= 21 {
@@ -292,8 +286,8 @@ impl BlackjackGame {
                 let player = &mut self.players[self.current_player_index];
                 let total_bet_so_far: i64 = player.hands.iter().map(|h| h.bet).sum();
                 let hand = &mut player.hands[self.current_hand_index];
-                if hand.can_double_down() {
-                    if balance < total_bet_so_far + hand.bet {
+                if rules.can_double(hand) {
+                    if available < total_bet_so_far + hand.bet {
                         send_ephemeral_response(
                             ctx,
                             interaction,
@@ -304,7 +298,7 @@ impl BlackjackGame {
                     }
                     self.pot += hand.bet;
                     hand.bet *= 2;
-                    if let Some(card) = self.deck.deal_one() {
+                    if let Some(card) = self.shoe.deal_one() {
                         hand.add_card(card);
                     }
                     hand.status = if hand.score() > 21 {
@@ -318,53 +312,51 @@ impl BlackjackGame {
             "bj_split" => {
                 let player = &mut self.players[self.current_player_index];
                 let total_bet_so_far: i64 = player.hands.iter().map(|h| h.bet).sum();
-                if player.hands[self.current_hand_index].can_split() {
-                    if balance < total_bet_so_far + self.min_bet {
-                        send_ephemeral_response(
-                            ctx,
-                            interaction,
-                            "You cannot afford to place a bet for a new hand.",
-                        )
-                        .await;
-                        return GameUpdate::NoOp;
+                let hand_count = player.hands.len();
+                let hand = &mut player.hands[self.current_hand_index];
+                if !rules.can_split(hand, hand_count) {
+                    return GameUpdate::NoOp;
+                }
+                if available < total_bet_so_far + hand.bet {
+                    send_ephemeral_response(
+                        ctx,
+                        interaction,
+                        "You cannot afford to place a bet for a new hand.",
+                    )
+                    .await;
+                    return GameUpdate::NoOp;
+                }
+                if let Some(split_card) = hand.cards.pop() {
+                    let mut new_hand = Hand::new(hand.bet);
+                    new_hand.add_card(split_card);
+                    hand.from_split = true;
+                    new_hand.from_split = true;
+                    if let Some(card) = self.shoe.deal_one() {
+                        hand.add_card(card);
                     }
-                    let hand = &mut player.hands[self.current_hand_index];
-                    if let Some(split_card) = hand.cards.pop() {
-                        let mut new_hand = Hand::new(self.min_bet);
-                        new_hand.add_card(split_card);
-                        if let Some(card) = self.deck.deal_one() {
-                            hand.add_card(card);
-                        }
-                        if let Some(card) = self.deck.deal_one() {
-                            new_hand.add_card(card);
-                        }
-                        self.pot += new_hand.bet;
-                        if hand.cards[0].rank == Rank::Ace {
-                            hand.status = HandStatus::Stood;
-                            new_hand.status = HandStatus::Stood;
-                        }
-                        if hand.score() == 21 {
-                            hand.status = HandStatus::Stood;
-                        }
-                        if new_hand.score() == 21 {
-                            new_hand.status = HandStatus::Stood;
-                        }
-                        player.hands.insert(self.current_hand_index + 1, new_hand);
-                        if player.hands[self.current_hand_index].status == HandStatus::Stood
-                            && player
-                                .hands
-                                .get(self.current_hand_index + 1)
-                                .is_some_and(|h| h.status == HandStatus::Stood)
+                    if let Some(card) = self.shoe.deal_one() {
+                        new_hand.add_card(card);
+                    }
+                    self.pot += new_hand.bet;
+                    // Split aces take one card each and stand, unless they can be split again.
+                    let may_resplit = hand_count + 1 < MAX_HANDS && rules.resplit_aces;
+                    for hand in [&mut *hand, &mut new_hand] {
+                        if hand.score() == 21
+                            || (hand.is_split_aces() && !(may_resplit && hand.can_split()))
                         {
-                            self.advance_turn();
+                            hand.status = HandStatus::Stood;
                         }
                     }
+                    player.hands.insert(self.current_hand_index + 1, new_hand);
+                    if player.hands[self.current_hand_index].status != HandStatus::Playing {
+                        self.advance_turn();
+                    }
                 }
             }
             "bj_surrender" => {
                 let hand =
                     &mut self.players[self.current_player_index].hands[self.current_hand_index];
-                if hand.can_surrender() {
+                if rules.can_surrender(hand) {
                     hand.status = HandStatus::Surrendered;
                     self.advance_turn();
                 }
@@ -372,8 +364,7 @@ impl BlackjackGame {
             _ => return GameUpdate::NoOp,
         }
         if self.phase == GamePhase::GameOver {
-            let (message, payouts) = self.calculate_payouts();
-            GameUpdate::GameOver { message, payouts }
+            self.finish_round(db, table).await
         } else {
             GameUpdate::ReRender
         }
@@ -383,17 +374,145 @@ impl BlackjackGame {
         &mut self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
+        db: &PgPool,
     ) -> GameUpdate {
-        if interaction.data.custom_id == "bj_next_round"
-            && interaction.user.id.get() == self.host_id
+        if interaction.data.custom_id != "bj_next_round"
+            || interaction.user.id.get() != self.host_id
         {
-            interaction.defer(&ctx.http).await.ok();
-            self.reset_for_next_round();
-            GameUpdate::ReRender
-        } else {
             send_ephemeral_response(ctx, interaction, "Only the host can start the next round.")
                 .await;
-            GameUpdate::NoOp
+            return GameUpdate::NoOp;
+        }
+        interaction.defer(&ctx.http).await.ok();
+        let table = interaction.message.id;
+        if !self.round_settled && !self.settle_stacks(db, table).await {
+            return GameUpdate::ReRender;
+        }
+        // Anyone who can no longer cover the minimum bet leaves with what they have left. A
+        // player whose cash-out fails keeps their seat until it goes through.
+        let (busted, staying): (Vec<_>, Vec<_>) = std::mem::take(&mut self.players)
+            .into_iter()
+            .partition(|p| p.chips < self.min_bet);
+        self.players = staying;
+        let mut failed = Vec::new();
+        for player in busted {
+            if let Err(e) = table_stacks::cash_out(db, table, player.user.id).await {
+                failed.push(format!("<@{}> ({})", player.user.id, e));
+                self.players.push(player);
+            }
+        }
+        if !failed.is_empty() {
+            self.table_error = Some(format!(
+                "⚠️ Could not cash out {}. Press Next Round to try again.",
+                failed.join(", ")
+            ));
+            return GameUpdate::ReRender;
         }
+        self.table_error = None;
+        if self.players.is_empty() {
+            return GameUpdate::GameOver {
+                message: "Every player has run out of chips.".to_string(),
+                payouts: vec![],
+            };
+        }
+        if !self.players.iter().any(|p| p.user.id.get() == self.host_id) {
+            self.host_id = self.players[0].user.id.get();
+        }
+        self.reset_for_next_round();
+        GameUpdate::ReRender
+    }
+
+    /// Settles a finished round. Friendly games end with it; tables playing for chips settle
+    /// the round against the players' stacks and wait for the host to deal the next one.
+    pub(super) async fn finish_round(&mut self, db: &PgPool, table: MessageId) -> GameUpdate {
+        if self.min_bet == 0 {
+            let (message, payouts) = self.calculate_payouts();
+            return GameUpdate::GameOver { message, payouts };
+        }
+        self.settle_stacks(db, table).await;
+        self.last_action_time = Instant::now();
+        GameUpdate::ReRender
+    }
+
+    /// Applies the finished round to the players' chip stacks. If that fails the table says so
+    /// and the next round waits until the host retries it successfully.
+    async fn settle_stacks(&mut self, db: &PgPool, table: MessageId) -> bool {
+        let (_, payouts) = self.calculate_payouts();
+        match table_stacks::settle(db, table, GambleGame::Blackjack, &payouts).await {
+            Ok(stacks) => {
+                for player in self.players.iter_mut() {
+                    if let Some(&(_, chips)) = stacks.iter().find(|(u, _)| *u == player.user.id) {
+                        player.chips = chips;
+                    }
+                }
+                self.round_settled = true;
+                self.table_error = None;
+                true
+            }
+            Err(e) => {
+                tracing::error!(table = %table, error = ?e, "failed to settle blackjack round");
+                self.round_settled = false;
+                self.table_error = Some(
+                    "⚠️ This round's chips could not be settled. Press Next Round to try again."
+                        .to_string(),
+                );
+                false
+            }
+        }
+    }
+
+    /// Cashes a player out between rounds. The table closes once everyone has left, and the
+    /// next player in line takes over as host if the host leaves.
+    pub(super) async fn leave_table(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        let Some(index) = self.players.iter().position(|p| p.user.id == user_id) else {
+            send_ephemeral_response(ctx, interaction, "You are not at this table.").await;
+            return GameUpdate::NoOp;
+        };
+        if !self.round_settled {
+            send_ephemeral_response(
+                ctx,
+                interaction,
+                "This round's chips haven't been settled yet. Wait for the host to retry.",
+            )
+            .await;
+            return GameUpdate::NoOp;
+        }
+        let farewell = if self.min_bet > 0 {
+            match table_stacks::cash_out(db, interaction.message.id, user_id).await {
+                Ok(chips) => format!("You left the table and cashed out **💰{}**.", chips),
+                Err(e) => {
+                    send_ephemeral_response(ctx, interaction, &e).await;
+                    return GameUpdate::NoOp;
+                }
+            }
+        } else {
+            "You left the table.".to_string()
+        };
+        send_ephemeral_response(ctx, interaction, &farewell).await;
+        self.players.remove(index);
+        self.ready_players.remove(&user_id);
+        if self.players.is_empty() {
+            self.phase = GamePhase::GameOver;
+            return GameUpdate::GameOver {
+                message: "Everyone left the table.".to_string(),
+                payouts: vec![],
+            };
+        }
+        if user_id.get() == self.host_id {
+            self.host_id = self.players[0].user.id.get();
+        }
+        if self.phase == GamePhase::Betting && self.ready_players.len() == self.players.len() {
+            self.deal_new_round();
+            if self.phase == GamePhase::GameOver {
+                return self.finish_round(db, interaction.message.id).await;
+            }
+        }
+        GameUpdate::ReRender
     }
 }
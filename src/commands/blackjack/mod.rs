@@ -4,6 +4,7 @@
 // The code for the game is now split across these files based on responsibility.
 pub mod game;
 pub mod handlers;
+pub mod rules;
 pub mod run;
 pub mod state;
 pub mod ui;
@@ -0,0 +1,207 @@
+//! House rules for a Blackjack table. Every table plays under a preset, "Vegas" or
+//! "Friendly", which the host can adjust rule by rule when opening it.
+
+use super::state::Hand;
+
+/// The most hands a player can hold after splitting.
+pub const MAX_HANDS: usize = 4;
+
+/// Insurance pays 2:1 when the dealer has Blackjack.
+pub const INSURANCE_PAYS: i64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HouseRules {
+    /// Decks in the shoe.
+    pub decks: u8,
+    /// How far into the shoe, in percent, the cut card is placed.
+    pub penetration: u8,
+    pub dealer_hits_soft_17: bool,
+    pub double_after_split: bool,
+    pub resplit_aces: bool,
+    /// Late surrender: give up half the bet instead of playing the first two cards.
+    pub surrender: bool,
+    /// Offer insurance when the dealer shows an Ace.
+    pub insurance: bool,
+    /// Blackjack payout as a ratio, e.g. `(3, 2)`.
+    pub blackjack_pays: (i64, i64),
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        Self::friendly()
+    }
+}
+
+impl HouseRules {
+    /// A six-deck shoe where the dealer hits soft 17, Blackjack pays 6:5 and surrender is off.
+    pub fn vegas() -> Self {
+        Self {
+            decks: 6,
+            penetration: 75,
+            dealer_hits_soft_17: true,
+            double_after_split: true,
+            resplit_aces: false,
+            surrender: false,
+            insurance: true,
+            blackjack_pays: (6, 5),
+        }
+    }
+
+    /// A two-deck shoe with player-friendly rules and a 3:2 Blackjack payout.
+    pub fn friendly() -> Self {
+        Self {
+            decks: 2,
+            penetration: 70,
+            dealer_hits_soft_17: false,
+            double_after_split: true,
+            resplit_aces: true,
+            surrender: true,
+            insurance: true,
+            blackjack_pays: (3, 2),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "vegas" => Some(Self::vegas()),
+            "friendly" => Some(Self::friendly()),
+            _ => None,
+        }
+    }
+
+    /// The preset these rules match, or "Custom" once the host has changed any of them.
+    pub fn name(&self) -> &'static str {
+        if *self == Self::vegas() {
+            "Vegas"
+        } else if *self == Self::friendly() {
+            "Friendly"
+        } else {
+            "Custom"
+        }
+    }
+
+    /// Applies a single rule flag such as `h17`, `nosurrender`, `6d` or `6:5`. Returns
+    /// `false` if the flag isn't recognised.
+    pub fn apply_flag(&mut self, flag: &str) -> bool {
+        let flag = flag.to_lowercase();
+        match flag.as_str() {
+            "h17" => self.dealer_hits_soft_17 = true,
+            "s17" => self.dealer_hits_soft_17 = false,
+            "das" => self.double_after_split = true,
+            "nodas" => self.double_after_split = false,
+            "rsa" => self.resplit_aces = true,
+            "norsa" => self.resplit_aces = false,
+            "surrender" => self.surrender = true,
+            "nosurrender" => self.surrender = false,
+            "insurance" => self.insurance = true,
+            "noinsurance" => self.insurance = false,
+            "3:2" => self.blackjack_pays = (3, 2),
+            "6:5" => self.blackjack_pays = (6, 5),
+            _ => {
+                let decks = flag
+                    .strip_suffix('d')
+                    .or_else(|| flag.strip_prefix("decks="))
+                    .and_then(|n| n.parse::<u8>().ok());
+                match decks {
+                    Some(n @ 1..=8) => self.decks = n,
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Builds rules from a preset name and rule flags, applied in order, e.g.
+    /// `vegas s17 8d`. Starts from the default preset.
+    pub fn from_args<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut rules = Self::default();
+        for arg in args {
+            if let Some(preset) = Self::preset(arg) {
+                rules = preset;
+            } else if !rules.apply_flag(arg) {
+                return Err(format!(
+                    "Unknown house rule `{}`. Use `vegas` or `friendly`, optionally followed by `h17`/`s17`, `das`/`nodas`, `rsa`/`norsa`, `surrender`/`nosurrender`, `insurance`/`noinsurance`, `3:2`/`6:5` or a deck count like `6d`.",
+                    arg
+                ));
+            }
+        }
+        Ok(rules)
+    }
+
+    /// One line listing every rule, for the table embeds.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            if self.decks == 1 {
+                "1 deck".to_string()
+            } else {
+                format!("{} decks", self.decks)
+            },
+            if self.dealer_hits_soft_17 {
+                "Dealer hits soft 17"
+            } else {
+                "Dealer stands on soft 17"
+            }
+            .to_string(),
+            if self.double_after_split {
+                "Double after split"
+            } else {
+                "No double after split"
+            }
+            .to_string(),
+            if self.resplit_aces {
+                "Resplit aces"
+            } else {
+                "No resplitting aces"
+            }
+            .to_string(),
+            if self.surrender {
+                "Late surrender"
+            } else {
+                "No surrender"
+            }
+            .to_string(),
+        ];
+        if self.insurance {
+            parts.push(format!("Insurance pays {}:1", INSURANCE_PAYS));
+        } else {
+            parts.push("No insurance".to_string());
+        }
+        parts.push(format!(
+            "Blackjack pays {}:{}",
+            self.blackjack_pays.0, self.blackjack_pays.1
+        ));
+        parts.join(" · ")
+    }
+
+    /// What a winning Blackjack pays on `bet`, rounded down.
+    pub fn blackjack_payout(&self, bet: i64) -> i64 {
+        bet * self.blackjack_pays.0 / self.blackjack_pays.1
+    }
+
+    /// Whether the dealer draws another card to `hand`.
+    pub fn dealer_hits(&self, hand: &Hand) -> bool {
+        let score = hand.score();
+        score < 17 || (score == 17 && self.dealer_hits_soft_17 && hand.is_soft())
+    }
+
+    /// Whether `hand` may be hit. Split aces take a single card each.
+    pub fn can_hit(&self, hand: &Hand) -> bool {
+        !hand.is_split_aces()
+    }
+
+    pub fn can_double(&self, hand: &Hand) -> bool {
+        hand.can_double_down()
+            && !hand.is_split_aces()
+            && (!hand.from_split || self.double_after_split)
+    }
+
+    /// Whether `hand` may be split again, given the player already holds `hands` hands.
+    pub fn can_split(&self, hand: &Hand, hands: usize) -> bool {
+        hand.can_split() && hands < MAX_HANDS && (!hand.is_split_aces() || self.resplit_aces)
+    }
+
+    /// Surrender is only offered on the opening two cards, before any split.
+    pub fn can_surrender(&self, hand: &Hand) -> bool {
+        self.surrender && hand.can_surrender() && !hand.from_split
+    }
+}
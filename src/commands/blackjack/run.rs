@@ -1,23 +1,48 @@
 //! This module contains the `run` functions for the Blackjack command.
 
+use super::rules::HouseRules;
 use super::state::BlackjackGame;
 use crate::AppState;
-use crate::commands::games::{Game, GameManager};
+use crate::commands::games::{Game, open_table};
+use crate::database::gambling;
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponse,
-    CreateInteractionResponseMessage, CreateMessage, EditMessage,
+    CreateInteractionResponseMessage, CreateMessage,
 };
 use serenity::model::application::{CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
+use serenity::model::id::UserId;
 use serenity::prelude::*;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
 use tracing::{instrument, warn};
 
-/// Registers the `/blackjack` slash command with an optional bet.
+/// The rule toggles `/blackjack` accepts, with the flags they set when true and false.
+const RULE_OPTIONS: [(&str, &str, &str, &str); 5] = [
+    ("dealer_hits_soft_17", "Dealer hits soft 17", "h17", "s17"),
+    (
+        "double_after_split",
+        "Allow doubling after a split",
+        "das",
+        "nodas",
+    ),
+    ("resplit_aces", "Allow resplitting aces", "rsa", "norsa"),
+    (
+        "surrender",
+        "Allow late surrender",
+        "surrender",
+        "nosurrender",
+    ),
+    (
+        "insurance",
+        "Offer insurance (pays 2:1)",
+        "insurance",
+        "noinsurance",
+    ),
+];
+
+/// Registers the `/blackjack` slash command with an optional bet and house rules.
 pub fn register() -> CreateCommand {
-    CreateCommand::new("blackjack")
+    let command = CreateCommand::new("blackjack")
         .description("Start a multiplayer game of Blackjack.")
         .add_option(
             CreateCommandOption::new(
@@ -28,6 +53,59 @@ pub fn register() -> CreateCommand {
             .required(false)
             .min_int_value(1),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "rules",
+                "Optional: The house rules preset. Defaults to friendly rules.",
+            )
+            .required(false)
+            .add_string_choice("Vegas", "vegas")
+            .add_string_choice("Friendly", "friendly"),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "decks",
+                "Optional: Decks in the shoe.",
+            )
+            .required(false)
+            .min_int_value(1)
+            .max_int_value(8),
+        );
+    RULE_OPTIONS
+        .iter()
+        .fold(command, |command, (name, description, _, _)| {
+            command.add_option(
+                CreateCommandOption::new(CommandOptionType::Boolean, *name, *description)
+                    .required(false),
+            )
+        })
+}
+
+/// Reads the house rules from the slash command's options as preset-then-flags.
+fn rules_from_options(interaction: &CommandInteraction) -> Result<HouseRules, String> {
+    let options = &interaction.data.options;
+    let mut args = Vec::new();
+    if let Some(preset) = options
+        .iter()
+        .find(|o| o.name == "rules")
+        .and_then(|o| o.value.as_str())
+    {
+        args.push(preset.to_string());
+    }
+    for option in options {
+        if option.name == "decks"
+            && let Some(decks) = option.value.as_i64()
+        {
+            args.push(format!("{}d", decks));
+        } else if let Some((_, _, on, off)) = RULE_OPTIONS.iter().find(|r| r.0 == option.name)
+            && let Some(enabled) = option.value.as_bool()
+        {
+            args.push(if enabled { on } else { off }.to_string());
+        }
+    }
+    HouseRules::from_args(args.iter().map(String::as_str))
 }
 
 /// Entry point for the `/blackjack` slash command.
@@ -37,8 +115,6 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         warn!(command = "blackjack_slash", "missing_app_state");
         return;
     };
-    let game_manager_lock = app_state.game_manager.clone();
-
     let response = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
     if interaction
         .create_response(&ctx.http, response)
@@ -63,7 +139,25 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         })
         .unwrap_or(0);
 
-    let blackjack_game = BlackjackGame::new(Arc::new(interaction.user.clone()), bet);
+    if bet > 0
+        && let Err(e) = gambling::check_can_play(&app_state.db, interaction.user.id).await
+    {
+        let builder = serenity::builder::EditInteractionResponse::new().content(e);
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    }
+
+    let rules = match rules_from_options(interaction) {
+        Ok(rules) => rules,
+        Err(e) => {
+            let builder = serenity::builder::EditInteractionResponse::new().content(e);
+            interaction.edit_response(&ctx.http, builder).await.ok();
+            return;
+        }
+    };
+
+    let blackjack_game =
+        BlackjackGame::new(Arc::new(interaction.user.clone()), bet).with_rules(rules);
     let (content, embed, components) = blackjack_game.render();
 
     let builder = serenity::builder::EditInteractionResponse::new()
@@ -72,11 +166,16 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .components(components);
 
     if let Ok(game_msg) = interaction.edit_response(&ctx.http, builder).await {
-        game_manager_lock
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(blackjack_game));
-        spawn_lobby_timeout_handler(ctx.clone(), game_manager_lock, game_msg);
+        let (host_id, buy_in) = (UserId::new(blackjack_game.host_id), blackjack_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(blackjack_game),
+        )
+        .await;
     }
 }
 
@@ -87,15 +186,29 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         warn!(command = "blackjack_prefix", "missing_app_state");
         return;
     };
-    let game_manager_lock = app_state.game_manager.clone();
-
-    // Bet is optional for prefix commands as well.
-    let bet = args
-        .iter()
-        .find_map(|arg| arg.parse::<i64>().ok())
+    // Bet is optional for prefix commands as well; everything else is a house rule.
+    let (bets, rule_args): (Vec<&str>, Vec<&str>) =
+        args.iter().partition(|arg| arg.parse::<i64>().is_ok());
+    let bet = bets
+        .first()
+        .and_then(|arg| arg.parse::<i64>().ok())
         .unwrap_or(0);
+    if bet > 0
+        && let Err(e) = gambling::check_can_play(&app_state.db, msg.author.id).await
+    {
+        msg.reply(ctx, e).await.ok();
+        return;
+    }
+
+    let rules = match HouseRules::from_args(rule_args) {
+        Ok(rules) => rules,
+        Err(e) => {
+            msg.reply(&ctx.http, e).await.ok();
+            return;
+        }
+    };
 
-    let blackjack_game = BlackjackGame::new(Arc::new(msg.author.clone()), bet);
+    let blackjack_game = BlackjackGame::new(Arc::new(msg.author.clone()), bet).with_rules(rules);
     let (content, embed, components) = blackjack_game.render();
 
     let builder = CreateMessage::new()
@@ -105,45 +218,15 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         .reference_message(msg);
 
     if let Ok(game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
-        game_manager_lock
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(blackjack_game));
-        spawn_lobby_timeout_handler(ctx.clone(), game_manager_lock, game_msg);
+        let (host_id, buy_in) = (UserId::new(blackjack_game.host_id), blackjack_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(blackjack_game),
+        )
+        .await;
     }
 }
-
-/// Spawns a task to handle the 2-minute lobby timeout, cleaning up inactive games.
-fn spawn_lobby_timeout_handler(
-    ctx: Context,
-    game_manager: Arc<RwLock<GameManager>>,
-    mut game_msg: Message,
-) {
-    tokio::spawn(async move {
-        tokio::time::sleep(Duration::from_secs(120)).await;
-        let mut manager = game_manager.write().await;
-
-        // (✓) FIXED: Collapsed the nested `if` statements into a single, more readable block.
-        if let Some(game_box) = manager.get_game_mut(&game_msg.id)
-            && let Some(bj_game) = game_box.as_any().downcast_ref::<BlackjackGame>()
-            && bj_game.is_in_lobby()
-        {
-            let embed = serenity::builder::CreateEmbed::new()
-                .title("Blackjack Lobby Expired")
-                .description("The game was not started by the host in time.")
-                .color(0xFF0000); // Red
-
-            let builder = EditMessage::new()
-                .content("**Blackjack Lobby Expired**")
-                .embed(embed)
-                .components(vec![]);
-
-            game_msg.edit(&ctx.http, builder).await.ok();
-            manager.remove_game(&game_msg.id);
-            println!(
-                "[BJ] Lobby for game {} timed out and was removed.",
-                game_msg.id
-            );
-        }
-    });
-}
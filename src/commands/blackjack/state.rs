@@ -1,7 +1,8 @@
 //! Defines all data structures (structs and enums) for the Blackjack game.
 
+use super::rules::HouseRules;
 use crate::commands::games::card::{Card, Rank};
-use crate::commands::games::deck::Deck;
+use crate::commands::games::shoe::Shoe;
 use serenity::model::id::UserId;
 use serenity::model::user::User;
 use std::collections::HashSet;
@@ -31,6 +32,8 @@ pub struct Hand {
     pub cards: Vec<Card>,
     pub bet: i64,
     pub status: HandStatus,
+    /// Set on both hands made by a split.
+    pub from_split: bool,
 }
 
 impl Hand {
@@ -39,6 +42,7 @@ impl Hand {
             cards: Vec::new(),
             bet,
             status: HandStatus::Playing,
+            from_split: false,
         }
     }
     pub fn add_card(&mut self, card: Card) {
@@ -59,6 +63,14 @@ impl Hand {
         }
         score
     }
+    /// Whether an Ace is currently counted as 11.
+    pub fn is_soft(&self) -> bool {
+        let hard: u8 = self.cards.iter().map(|c| c.rank.value().0).sum();
+        self.cards.iter().any(|c| c.rank == Rank::Ace) && hard + 10 <= 21
+    }
+    pub fn is_split_aces(&self) -> bool {
+        self.from_split && self.cards.first().is_some_and(|c| c.rank == Rank::Ace)
+    }
     pub fn can_split(&self) -> bool {
         self.cards.len() == 2 && self.cards[0].rank.value().0 == self.cards[1].rank.value().0
     }
@@ -91,6 +103,8 @@ pub struct Player {
     pub hands: Vec<Hand>,
     pub insurance: i64,
     pub current_bet: i64,
+    /// Chips bought in at the table; bets are settled against these, not the wallet.
+    pub chips: i64,
     pub insurance_decision_made: bool,
     pub has_passed_turn: bool, // (✓) ADDED: To track if a player has passed their turn.
 }
@@ -99,7 +113,10 @@ pub struct BlackjackGame {
     pub host_id: u64,
     pub players: Vec<Player>,
     pub dealer_hand: Hand,
-    pub deck: Deck,
+    pub shoe: Shoe,
+    pub rules: HouseRules,
+    /// Set when the shoe was reshuffled before the current round.
+    pub reshuffled: bool,
     pub phase: GamePhase,
     pub min_bet: i64,
     pub pot: i64,
@@ -109,4 +126,8 @@ pub struct BlackjackGame {
     pub current_hand_index: usize,
     pub last_action_time: Instant,
     pub inactivity_warned: bool,
+    /// False while the finished round's results still have to be applied to the chip stacks.
+    pub round_settled: bool,
+    /// Why the table could not settle the round or cash a player out, shown until it can.
+    pub table_error: Option<String>,
 }
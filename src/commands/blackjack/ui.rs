@@ -1,11 +1,34 @@
 //! Handles all rendering and UI logic for the Blackjack game.
 
+use super::rules::INSURANCE_PAYS;
 use super::state::{BlackjackGame, GamePhase, HandStatus};
 use crate::ui::buttons::Btn;
 use crate::ui::style::{COLOR_SAGA_MAP, COLOR_SAGA_TAVERN};
 use serenity::builder::{CreateActionRow, CreateEmbed, CreateEmbedFooter};
 
 impl BlackjackGame {
+    fn rules_field(&self) -> (String, String, bool) {
+        (
+            format!("📜 House Rules ({})", self.rules.name()),
+            self.rules.summary(),
+            false,
+        )
+    }
+
+    fn shoe_status(&self) -> String {
+        let reshuffled = if self.reshuffled {
+            " · 🔀 Fresh shuffle"
+        } else {
+            ""
+        };
+        format!(
+            "{} of {} cards left{}",
+            self.shoe.cards_remaining(),
+            self.shoe.size(),
+            reshuffled
+        )
+    }
+
     pub(super) fn render_lobby(&self) -> (CreateEmbed, Vec<CreateActionRow>) {
         let players_list = self
             .players
@@ -15,8 +38,10 @@ impl BlackjackGame {
             .join("\n");
         let desc = if self.min_bet > 0 {
             format!(
-                "<@{}> has started a Blackjack table with a minimum bet of **💰{}**!",
-                self.host_id, self.min_bet
+                "<@{}> has started a Blackjack table with a minimum bet of **💰{}**!\nJoining buys you in for **💰{}** in chips; leave the table to cash out.",
+                self.host_id,
+                self.min_bet,
+                self.buy_in()
             )
         } else {
             format!(
@@ -31,15 +56,19 @@ impl BlackjackGame {
                 "{}\n\n**Players ({}):**\n{}",
                 desc, player_count, players_list
             ))
+            .fields([self.rules_field()])
             .color(COLOR_SAGA_TAVERN)
             .footer(CreateEmbedFooter::new(
                 "Lobby expires in 2 minutes. Use Start when ready.",
             ));
-        let buttons = vec![
+        let mut buttons = vec![
             Btn::success("bj_join", "Join"),
             Btn::danger("bj_cancel", "Cancel (Host)"),
             Btn::primary("bj_start", "Start Game (Host)"),
         ];
+        if self.min_bet > 0 {
+            buttons.push(Btn::secondary("bj_leave", "Leave"));
+        }
         (embed, vec![CreateActionRow::Buttons(buttons)])
     }
 
@@ -54,8 +83,8 @@ impl BlackjackGame {
                     "🤔"
                 };
                 format!(
-                    "{} <@{}> — Bet: **💰{}**",
-                    status_icon, p.user.id, p.current_bet
+                    "{} <@{}> — Bet: **💰{}** · Chips: 💰{}",
+                    status_icon, p.user.id, p.current_bet, p.chips
                 )
             })
             .collect::<Vec<_>>()
@@ -67,6 +96,7 @@ impl BlackjackGame {
                 self.min_bet
             ))
             .field("Betting Status", betting_status, false)
+            .fields([self.rules_field()])
             .color(COLOR_SAGA_TAVERN)
             .footer(CreateEmbedFooter::new(
                 "Round starts when all players confirm (60s timeout).",
@@ -80,6 +110,7 @@ impl BlackjackGame {
             Btn::danger("bj_bet_all_in", "All In"),
             Btn::secondary("bj_bet_clear", "Reset Bet"),
             Btn::success("bj_bet_confirm", "Confirm Bet"),
+            Btn::secondary("bj_leave", "Cash Out & Leave"),
         ];
         (
             embed,
@@ -189,18 +220,30 @@ impl BlackjackGame {
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
+            let stack = if self.min_bet > 0 {
+                format!(" · 💰{}", player.chips)
+            } else {
+                String::new()
+            };
             embed = embed.field(
-                format!("{}👤 {}", turn_indicator, player.user.name),
+                format!("{}👤 {}{}", turn_indicator, player.user.name, stack),
                 hands_display,
                 true,
             );
         }
 
+        embed = embed
+            .fields([self.rules_field()])
+            .field("👞 Shoe", self.shoe_status(), false);
+
         if self.phase == GamePhase::Insurance {
             embed =
                 embed.description("The dealer is showing an Ace. **Place your insurance bets!**");
             components.push(CreateActionRow::Buttons(vec![
-                Btn::success("bj_insure_yes", "Insure (0.5x bet)"),
+                Btn::success(
+                    "bj_insure_yes",
+                    &format!("Insure (0.5x bet, pays {}:1)", INSURANCE_PAYS),
+                ),
                 Btn::danger("bj_insure_no", "No Insurance"),
             ]));
         } else {
@@ -211,25 +254,34 @@ impl BlackjackGame {
             );
             embed = embed.footer(CreateEmbedFooter::new(footer_text));
 
-            let mut buttons = vec![
-                Btn::success("bj_hit", "Hit"),
-                Btn::danger("bj_stand", "Stand"),
-                Btn::secondary("bj_pass", "Pass"), // (✓) ADDED: Pass button
-            ];
+            let current_player = &self.players[self.current_player_index];
+            let current_hand = &current_player.hands[self.current_hand_index];
+            let mut buttons = Vec::new();
+            if self.rules.can_hit(current_hand) {
+                buttons.push(Btn::success("bj_hit", "Hit"));
+            }
+            buttons.push(Btn::danger("bj_stand", "Stand"));
+            buttons.push(Btn::secondary("bj_pass", "Pass")); // (✓) ADDED: Pass button
+
+            components.push(CreateActionRow::Buttons(buttons));
 
-            let current_hand =
-                &self.players[self.current_player_index].hands[self.current_hand_index];
-            if current_hand.can_double_down() {
-                buttons.push(Btn::primary("bj_double", "Double"));
+            // Options the house rules allow on this hand go on their own row.
+            let mut options = Vec::new();
+            if self.rules.can_double(current_hand) {
+                options.push(Btn::primary("bj_double", "Double"));
             }
-            if current_hand.can_split() {
-                buttons.push(Btn::secondary("bj_split", "Split"));
+            if self
+                .rules
+                .can_split(current_hand, current_player.hands.len())
+            {
+                options.push(Btn::secondary("bj_split", "Split"));
             }
-            if current_hand.can_surrender() {
-                buttons.push(Btn::secondary("bj_surrender", "Surrender"));
+            if self.rules.can_surrender(current_hand) {
+                options.push(Btn::secondary("bj_surrender", "Surrender"));
+            }
+            if !options.is_empty() {
+                components.push(CreateActionRow::Buttons(options));
             }
-
-            components.push(CreateActionRow::Buttons(buttons));
         }
 
         (embed, components)
@@ -240,7 +292,18 @@ impl BlackjackGame {
             .title("♠️ Blackjack - Final Results ♦️")
             .color(COLOR_SAGA_MAP); // Green for success/completion
         let (results_str, _) = self.calculate_payouts();
-        embed = embed.description(format!("**--- Round Over ---**\n\n{}", results_str));
+        let error = self
+            .table_error
+            .as_ref()
+            .map(|e| format!("\n\n{}", e))
+            .unwrap_or_default();
+        embed = embed
+            .description(format!(
+                "**--- Round Over ---**\n\n{}{}",
+                results_str, error
+            ))
+            .fields([self.rules_field()])
+            .field("👞 Shoe", self.shoe_status(), false);
 
         let mut rows: Vec<CreateActionRow> = Vec::new();
         // Quick return to Tavern after the game ends
@@ -254,12 +317,19 @@ impl BlackjackGame {
         rows.push(crate::commands::saga::ui::global_nav_row("saga"));
 
         if self.min_bet > 0 {
-            rows.push(CreateActionRow::Buttons(vec![Btn::primary(
-                "bj_next_round",
-                "Next Round (Host)",
-            )]));
+            let stacks = self
+                .players
+                .iter()
+                .map(|p| format!("<@{}> 💰{}", p.user.id, p.chips))
+                .collect::<Vec<_>>()
+                .join("\n");
+            embed = embed.field("Chip Stacks", stacks, false);
+            rows.push(CreateActionRow::Buttons(vec![
+                Btn::primary("bj_next_round", "Next Round (Host)"),
+                Btn::secondary("bj_leave", "Cash Out & Leave"),
+            ]));
             embed = embed.footer(CreateEmbedFooter::new(
-                "The host has 60 seconds to start the next round.",
+                "Players who can't cover the minimum bet are cashed out before the next round.",
             ));
         }
 
@@ -1,3 +1,8 @@
+use crate::commands::economy::core::pricing::{
+    BAND_MAX_PCT_LIMIT, BAND_MIN_PCT_LIMIT, PRICE_BAND_MAX_CONFIG_KEY, PRICE_BAND_MIN_CONFIG_KEY,
+    PriceBand,
+};
+use crate::commands::economy::market::logic::{MARKET_TAX_CONFIG_KEY, MAX_MARKET_TAX_PCT};
 use crate::database::settings::set_config_value;
 use crate::model::AppState;
 use serenity::builder::{
@@ -32,6 +37,50 @@ pub fn register() -> CreateCommand {
                 .required(true),
             ),
         )
+        .add_option(
+            serenity::builder::CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "market_tax",
+                "Set the marketplace tax percentage taken from each sale",
+            )
+            .add_sub_option(
+                serenity::builder::CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "percent",
+                    "Tax percentage",
+                )
+                .required(true)
+                .min_int_value(0)
+                .max_int_value(MAX_MARKET_TAX_PCT as u64),
+            ),
+        )
+        .add_option(
+            serenity::builder::CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "price_band",
+                "Set how far dynamic prices may move from the base price",
+            )
+            .add_sub_option(
+                serenity::builder::CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "min_percent",
+                    "Lowest price as a percentage of base",
+                )
+                .required(true)
+                .min_int_value(BAND_MIN_PCT_LIMIT as u64)
+                .max_int_value(100),
+            )
+            .add_sub_option(
+                serenity::builder::CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "max_percent",
+                    "Highest price as a percentage of base",
+                )
+                .required(true)
+                .min_int_value(100)
+                .max_int_value(BAND_MAX_PCT_LIMIT as u64),
+            ),
+        )
         .add_option(serenity::builder::CreateCommandOption::new(
             CommandOptionType::SubCommand,
             "show",
@@ -73,133 +122,228 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
     let user_id = interaction.user.id.get();
     if let Some(sub) = interaction.data.options.first() {
         match &sub.value {
-            CommandDataOptionValue::SubCommand(nested) => {
-                match sub.name.as_str() {
-                    "starter_unit" => {
-                        if !is_admin(user_id) {
+            CommandDataOptionValue::SubCommand(nested) => match sub.name.as_str() {
+                "starter_unit" => {
+                    if !is_admin(user_id) {
+                        interaction
+                            .edit_response(
+                                &ctx.http,
+                                EditInteractionResponse::new()
+                                    .content("You are not permitted to set config."),
+                            )
+                            .await
+                            .ok();
+                        return;
+                    }
+                    if let Some(first) = nested.first()
+                        && let CommandDataOptionValue::Integer(val) = first.value
+                    {
+                        *app_state.starter_unit_id.write().await = val as i32;
+                        if let Err(e) =
+                            set_config_value(&app_state.db, "starter_unit_id", &val.to_string())
+                                .await
+                        {
+                            interaction
+                                .edit_response(
+                                    &ctx.http,
+                                    EditInteractionResponse::new().content(format!(
+                                        "Starter unit updated in memory but failed to persist: {}",
+                                        e
+                                    )),
+                                )
+                                .await
+                                .ok();
+                        } else {
                             interaction
                                 .edit_response(
                                     &ctx.http,
                                     EditInteractionResponse::new()
-                                        .content("You are not permitted to set config."),
+                                        .content(format!("Starter unit id set to {}", val)),
                                 )
                                 .await
                                 .ok();
-                            return;
                         }
-                        if let Some(first) = nested.first()
-                            && let CommandDataOptionValue::Integer(val) = first.value
+                        return;
+                    }
+                    interaction
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().content("Invalid unit id."),
+                        )
+                        .await
+                        .ok();
+                }
+                "market_tax" => {
+                    if !is_admin(user_id) {
+                        interaction
+                            .edit_response(
+                                &ctx.http,
+                                EditInteractionResponse::new()
+                                    .content("You are not permitted to set config."),
+                            )
+                            .await
+                            .ok();
+                        return;
+                    }
+                    let content = match nested.first().map(|o| &o.value) {
+                        Some(CommandDataOptionValue::Integer(pct))
+                            if (0..=MAX_MARKET_TAX_PCT).contains(pct) =>
                         {
-                            *app_state.starter_unit_id.write().await = val as i32;
-                            if let Err(e) =
-                                set_config_value(&app_state.db, "starter_unit_id", &val.to_string())
-                                    .await
+                            match set_config_value(
+                                &app_state.db,
+                                MARKET_TAX_CONFIG_KEY,
+                                &pct.to_string(),
+                            )
+                            .await
                             {
-                                interaction.edit_response(&ctx.http, EditInteractionResponse::new().content(format!("Starter unit updated in memory but failed to persist: {}", e))).await.ok();
-                            } else {
-                                interaction
-                                    .edit_response(
-                                        &ctx.http,
-                                        EditInteractionResponse::new()
-                                            .content(format!("Starter unit id set to {}", val)),
-                                    )
-                                    .await
-                                    .ok();
+                                Ok(_) => format!("Market tax set to {}%.", pct),
+                                Err(e) => format!("Failed to persist market tax: {}", e),
                             }
-                            return;
                         }
+                        _ => format!("Tax must be between 0 and {}%.", MAX_MARKET_TAX_PCT),
+                    };
+                    interaction
+                        .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+                        .await
+                        .ok();
+                }
+                "price_band" => {
+                    if !is_admin(user_id) {
                         interaction
                             .edit_response(
                                 &ctx.http,
-                                EditInteractionResponse::new().content("Invalid unit id."),
+                                EditInteractionResponse::new()
+                                    .content("You are not permitted to set config."),
                             )
                             .await
                             .ok();
+                        return;
                     }
-                    "show" => {
-                        let starter = *app_state.starter_unit_id.read().await;
+                    let int = |name: &str| {
+                        nested
+                            .iter()
+                            .find(|o| o.name == name)
+                            .and_then(|o| o.value.as_i64())
+                    };
+                    let content = match (int("min_percent"), int("max_percent")) {
+                        (Some(min), Some(max)) if PriceBand::new(min, max).is_some() => {
+                            let saved = match set_config_value(
+                                &app_state.db,
+                                PRICE_BAND_MIN_CONFIG_KEY,
+                                &min.to_string(),
+                            )
+                            .await
+                            {
+                                Ok(_) => {
+                                    set_config_value(
+                                        &app_state.db,
+                                        PRICE_BAND_MAX_CONFIG_KEY,
+                                        &max.to_string(),
+                                    )
+                                    .await
+                                }
+                                Err(e) => Err(e),
+                            };
+                            match saved {
+                                Ok(_) => format!("Price band set to {}%–{}% of base.", min, max),
+                                Err(e) => format!("Failed to persist price band: {}", e),
+                            }
+                        }
+                        _ => format!(
+                            "The band must run from {}–100% up to 100–{}% of base.",
+                            BAND_MIN_PCT_LIMIT, BAND_MAX_PCT_LIMIT
+                        ),
+                    };
+                    interaction
+                        .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+                        .await
+                        .ok();
+                }
+                "show" => {
+                    let starter = *app_state.starter_unit_id.read().await;
+                    let tax = crate::database::market::get_market_tax_pct(&app_state.db).await;
+                    let band = crate::database::pricing::get_price_band(&app_state.db).await;
+                    interaction
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().content(format!(
+                                "Current starter_unit_id: {}\nMarket tax: {}%\nPrice band: {}%–{}%",
+                                starter, tax, band.min_pct, band.max_pct
+                            )),
+                        )
+                        .await
+                        .ok();
+                }
+                "bonds" => {
+                    if !is_admin(user_id) {
                         interaction
                             .edit_response(
                                 &ctx.http,
-                                EditInteractionResponse::new()
-                                    .content(format!("Current starter_unit_id: {}", starter)),
+                                EditInteractionResponse::new().content("Not permitted."),
                             )
                             .await
                             .ok();
+                        return;
                     }
-                    "bonds" => {
-                        if !is_admin(user_id) {
+                    match crate::database::units::list_active_bonds_detailed(
+                        &app_state.db,
+                        interaction.user.id,
+                    )
+                    .await
+                    {
+                        Ok(rows) if !rows.is_empty() => {
+                            use chrono::Utc;
+                            let mut out = String::from("Active Bonds:\n");
+                            for r in rows {
+                                let age = Utc::now() - r.created_at;
+                                let mins = age.num_minutes();
+                                out.push_str(&format!(
+                                    "[#{}] Host {} <- Equipped {} • {}m • equipped:{}\n",
+                                    r.bond_id,
+                                    r.host_player_unit_id,
+                                    r.equipped_player_unit_id,
+                                    mins,
+                                    r.is_equipped
+                                ));
+                            }
                             interaction
                                 .edit_response(
                                     &ctx.http,
-                                    EditInteractionResponse::new().content("Not permitted."),
+                                    EditInteractionResponse::new().content(out),
                                 )
                                 .await
                                 .ok();
-                            return;
                         }
-                        match crate::database::units::list_active_bonds_detailed(
-                            &app_state.db,
-                            interaction.user.id,
-                        )
-                        .await
-                        {
-                            Ok(rows) if !rows.is_empty() => {
-                                use chrono::Utc;
-                                let mut out = String::from("Active Bonds:\n");
-                                for r in rows {
-                                    let age = Utc::now() - r.created_at;
-                                    let mins = age.num_minutes();
-                                    out.push_str(&format!(
-                                        "[#{}] Host {} <- Equipped {} • {}m • equipped:{}\n",
-                                        r.bond_id,
-                                        r.host_player_unit_id,
-                                        r.equipped_player_unit_id,
-                                        mins,
-                                        r.is_equipped
-                                    ));
-                                }
-                                interaction
-                                    .edit_response(
-                                        &ctx.http,
-                                        EditInteractionResponse::new().content(out),
-                                    )
-                                    .await
-                                    .ok();
-                            }
-                            Ok(_) => {
-                                interaction
-                                    .edit_response(
-                                        &ctx.http,
-                                        EditInteractionResponse::new().content("No active bonds."),
-                                    )
-                                    .await
-                                    .ok();
-                            }
-                            Err(_) => {
-                                interaction
-                                    .edit_response(
-                                        &ctx.http,
-                                        EditInteractionResponse::new()
-                                            .content("Failed to load bonds."),
-                                    )
-                                    .await
-                                    .ok();
-                            }
+                        Ok(_) => {
+                            interaction
+                                .edit_response(
+                                    &ctx.http,
+                                    EditInteractionResponse::new().content("No active bonds."),
+                                )
+                                .await
+                                .ok();
+                        }
+                        Err(_) => {
+                            interaction
+                                .edit_response(
+                                    &ctx.http,
+                                    EditInteractionResponse::new().content("Failed to load bonds."),
+                                )
+                                .await
+                                .ok();
                         }
-                    }
-                    _ => {
-                        interaction
-                            .edit_response(
-                                &ctx.http,
-                                EditInteractionResponse::new()
-                                    .content("Unknown config subcommand."),
-                            )
-                            .await
-                            .ok();
                     }
                 }
-            }
+                _ => {
+                    interaction
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().content("Unknown config subcommand."),
+                        )
+                        .await
+                        .ok();
+                }
+            },
             _ => {
                 interaction
                     .edit_response(
@@ -87,7 +87,7 @@ pub fn build_contracts_embed(
             ));
         }
         lines.push(
-            "Select a Ready human in Draft menu to create a contract; then Accept menu to recruit."
+            "Select a Ready human in Draft menu to create a contract; negotiate their demands, then Accept menu to recruit."
                 .to_string(),
         );
     }
@@ -98,10 +98,15 @@ pub fn build_contracts_embed(
         for d in drafted.iter().take(5) {
             // cap display
             let age = relative_time(d.drafted_at);
+            let terms = match (d.wage, d.loot_share_pct) {
+                (Some(wage), Some(share)) => format!(" — {}c/day, {}% loot", wage, share),
+                _ => String::new(),
+            };
             lines.push(format!(
-                "Unit {:>3} drafted {}{}",
+                "Unit {:>3} drafted {}{}{}",
                 d.unit_id,
                 age,
+                terms,
                 if drafted.len() > 5 && d == drafted.last().unwrap() {
                     " (more...)"
                 } else {
@@ -188,10 +193,19 @@ pub fn build_contracts_embed(
         rows.push(CreateActionRow::SelectMenu(
             CreateSelectMenu::new(
                 "contracts_accept_select",
-                CreateSelectMenuKind::String { options: opts },
+                CreateSelectMenuKind::String {
+                    options: opts.clone(),
+                },
             )
             .placeholder("Accept contract for..."),
         ));
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "contracts_negotiate_select",
+                CreateSelectMenuKind::String { options: opts },
+            )
+            .placeholder("Negotiate terms with..."),
+        ));
     }
     // Refresh + pagination + Play buttons row
     let mut nav_buttons = vec![Btn::secondary("contracts_refresh", "🔄 Refresh")];
@@ -217,6 +231,66 @@ pub fn build_contracts_embed(
     }
 }
 
+/// Negotiation view for one drafted human: current demands, leverage and counter buttons.
+pub fn build_negotiation_view(
+    state: &crate::database::human::NegotiationState,
+    notice: Option<&str>,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    use crate::saga::negotiation::Counter;
+    let mut desc = format!(
+        "**{}** is ready to sign, but has demands.\nCounter their offer or sign as-is.",
+        state.unit_name
+    );
+    if let Some(n) = notice {
+        desc = format!("{}\n\n{}", n, desc);
+    }
+    let embed = CreateEmbed::new()
+        .title(format!("Negotiation — {}", state.unit_name))
+        .description(desc)
+        .field("Current Terms", state.current.summary(), false)
+        .field("Opening Demands", state.opening.summary(), false)
+        .field(
+            "Leverage",
+            format!(
+                "Fame tier {} · {} extra defeat(s) → flexibility {}%",
+                state.fame_tier, state.extra_defeats, state.flexibility
+            ),
+            true,
+        )
+        .field(
+            "Counters Left",
+            format!(
+                "{}/{}",
+                state.rounds_left(),
+                crate::saga::negotiation::MAX_NEGOTIATION_ROUNDS
+            ),
+            true,
+        );
+    let counters = Counter::ALL
+        .iter()
+        .map(|c| {
+            let disabled =
+                state.rounds_left() == 0 || (*c == Counter::WaiveSlot && !state.current.party_slot);
+            Btn::primary(
+                &format!("contracts_counter_{}_{}", state.unit_id, c.key()),
+                c.label(),
+            )
+            .disabled(disabled)
+        })
+        .collect();
+    let rows = vec![
+        CreateActionRow::Buttons(counters),
+        CreateActionRow::Buttons(vec![
+            Btn::success(
+                &format!("contracts_sign_{}", state.unit_id),
+                "✍️ Sign Contract",
+            ),
+            Btn::secondary("contracts_refresh", "↩ Contracts"),
+        ]),
+    ];
+    (embed, rows)
+}
+
 fn relative_time(ts: DateTime<Utc>) -> String {
     let now = Utc::now();
     let secs = (now - ts).num_seconds();
@@ -1,4 +1,5 @@
 //! Implements the `/craft` command for creating new items.
 
+pub mod profession;
 pub mod run;
 pub mod ui;
@@ -0,0 +1,134 @@
+//! The crafting profession: level-based craft speed, quality tiers and ingredient saving.
+//!
+//! Crafts are rolled one unit at a time when a queued batch completes, using the crafter's level
+//! at that moment. Each unit lands in a quality tier; a Masterwork unit yields the recipe's
+//! masterwork item when it has one (a Greater Health Potion instead of a Health Potion), otherwise
+//! double output. Independently, each unit may save its ingredients, which are refunded.
+
+use crate::database::models::Recipe;
+use chrono::Duration;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Most units in one queued craft ("craft x10").
+pub const MAX_BATCH: i32 = 10;
+/// Most unfinished crafts a player may have queued at once.
+pub const MAX_QUEUE: i64 = 3;
+/// Craft time shrinks by this much per level above 1, down to `MIN_DURATION_PCT`.
+pub const SPEED_PCT_PER_LEVEL: i64 = 2;
+pub const MIN_DURATION_PCT: i64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QualityTier {
+    Standard,
+    Fine,
+    Masterwork,
+}
+
+impl QualityTier {
+    pub fn label(self) -> &'static str {
+        match self {
+            QualityTier::Standard => "Standard",
+            QualityTier::Fine => "Fine",
+            QualityTier::Masterwork => "Masterwork",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            QualityTier::Standard => "⚪",
+            QualityTier::Fine => "🔵",
+            QualityTier::Masterwork => "🟣",
+        }
+    }
+}
+
+/// Chance (percent) that a unit comes out Fine or better.
+pub fn fine_chance_pct(level: i32) -> i64 {
+    (level as i64 * 3).min(45)
+}
+
+/// Chance (percent) that a unit comes out Masterwork. Unlocks at level 5.
+pub fn masterwork_chance_pct(level: i32) -> i64 {
+    ((level as i64 - 4) * 2).clamp(0, 20)
+}
+
+/// Chance (percent) that a unit's ingredients are refunded.
+pub fn save_chance_pct(level: i32) -> i64 {
+    ((level as i64 - 1) * 2).clamp(0, 25)
+}
+
+/// Time to craft `quantity` units of a recipe at `level`.
+pub fn craft_duration(craft_secs: i32, level: i32, quantity: i32) -> Duration {
+    let pct = (100 - (level as i64 - 1) * SPEED_PCT_PER_LEVEL).max(MIN_DURATION_PCT);
+    Duration::seconds((craft_secs.max(1) as i64 * quantity.max(1) as i64 * pct / 100).max(1))
+}
+
+pub fn roll_quality<R: Rng + ?Sized>(level: i32, rng: &mut R) -> QualityTier {
+    let roll = rng.random_range(0..100);
+    if roll < masterwork_chance_pct(level) {
+        QualityTier::Masterwork
+    } else if roll < fine_chance_pct(level).max(masterwork_chance_pct(level)) {
+        QualityTier::Fine
+    } else {
+        QualityTier::Standard
+    }
+}
+
+/// The rolled result of a completed batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CraftOutcome {
+    pub standard: i32,
+    pub fine: i32,
+    pub masterwork: i32,
+    /// Units whose ingredients were saved.
+    pub saved: i32,
+}
+
+impl CraftOutcome {
+    pub fn total(&self) -> i32 {
+        self.standard + self.fine + self.masterwork
+    }
+
+    pub fn count(&self, tier: QualityTier) -> i32 {
+        match tier {
+            QualityTier::Standard => self.standard,
+            QualityTier::Fine => self.fine,
+            QualityTier::Masterwork => self.masterwork,
+        }
+    }
+
+    /// Items granted for this outcome. Fine units add one extra output each.
+    pub fn outputs(&self, recipe: &Recipe) -> BTreeMap<i32, i64> {
+        let per_unit = recipe.output_quantity.max(1) as i64;
+        let mut outputs = BTreeMap::new();
+        let base = self.standard as i64 * per_unit + self.fine as i64 * (per_unit + 1);
+        if base > 0 {
+            *outputs.entry(recipe.output_item_id).or_insert(0) += base;
+        }
+        if self.masterwork > 0 {
+            let (item, quantity) = match recipe.masterwork_item_id {
+                Some(item) => (item, self.masterwork as i64 * per_unit),
+                None => (recipe.output_item_id, self.masterwork as i64 * per_unit * 2),
+            };
+            *outputs.entry(item).or_insert(0) += quantity;
+        }
+        outputs
+    }
+}
+
+/// Rolls quality and ingredient saving for `quantity` units at `level`.
+pub fn roll_batch<R: Rng + ?Sized>(level: i32, quantity: i32, rng: &mut R) -> CraftOutcome {
+    let mut outcome = CraftOutcome::default();
+    for _ in 0..quantity.max(0) {
+        match roll_quality(level, rng) {
+            QualityTier::Standard => outcome.standard += 1,
+            QualityTier::Fine => outcome.fine += 1,
+            QualityTier::Masterwork => outcome.masterwork += 1,
+        }
+        if rng.random_range(0..100) < save_chance_pct(level) {
+            outcome.saved += 1;
+        }
+    }
+    outcome
+}
@@ -1,61 +1,86 @@
 //! Implements the run logic for the `/craft` command.
 
-use super::ui::{RecipeInfo, create_crafting_menu};
-use crate::commands::economy::core::item::Item;
+use super::profession::MAX_BATCH;
+use super::ui::{CraftQueueInfo, RecipeInfo, create_crafting_menu};
+use crate::commands::economy::core::registry::registry;
+use crate::commands::economy::work::ui::format_duration;
 use crate::{AppState, database};
+use chrono::Utc;
 use serenity::builder::{
-    CreateCommand, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
-    EditInteractionResponse,
+    CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse,
 };
-use serenity::model::application::CommandInteraction;
+use serenity::model::application::{CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
+use serenity::model::id::UserId;
 use serenity::prelude::*;
+use sqlx::PgPool;
 use std::collections::HashMap;
 
-pub fn register() -> CreateCommand {
-    CreateCommand::new("craft").description("Craft new items from materials in your inventory.")
-}
+/// Completed crafts listed under "Recently Finished".
+const RECENT_CRAFTS: i64 = 3;
 
-pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
-    interaction
-        .create_response(
-            &ctx.http,
-            CreateInteractionResponse::Defer(
-                CreateInteractionResponseMessage::new().ephemeral(true),
-            ),
+pub fn register() -> CreateCommand {
+    CreateCommand::new("craft")
+        .description("Craft new items from materials in your inventory.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "item",
+                "Queue a craft directly, e.g. Health Potion. Leave empty for the workshop.",
+            )
+            .required(false),
         )
-        .await
-        .ok();
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "count", "How many to craft")
+                .required(false)
+                .min_int_value(1)
+                .max_int_value(MAX_BATCH as u64),
+        )
+}
 
-    let Some(app_state) = AppState::from_ctx(ctx).await else {
-        return;
-    };
-    let pool = app_state.db.clone();
+/// Builds the workshop view: recipes, the player's queue and recently finished crafts. Due crafts
+/// are completed first so the view never shows a finished craft as pending.
+pub async fn build_craft_view(
+    pool: &PgPool,
+    user_id: UserId,
+    selected: Option<i32>,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    if let Err(e) = database::crafting::complete_due_crafts(pool).await {
+        tracing::warn!(target: "crafting", error = %e, "Lazy craft completion failed");
+    }
 
     // 1. Fetch all recipes and ingredients from the database.
-    let all_recipes = database::crafting::get_all_recipes(&pool)
+    let all_recipes = database::crafting::get_all_recipes(pool)
         .await
         .unwrap_or_default();
     let mut all_ingredients = HashMap::new();
     for recipe in &all_recipes {
         if let Ok(ingredients) =
-            database::crafting::get_ingredients_for_recipe(&pool, recipe.recipe_id).await
+            database::crafting::get_ingredients_for_recipe(pool, recipe.recipe_id).await
         {
             all_ingredients.insert(recipe.recipe_id, ingredients);
         }
     }
 
-    // 2. Fetch the player's inventory.
-    let inventory = database::economy::get_inventory(&pool, interaction.user.id)
+    // 2. Fetch the player's inventory, crafting level and queue.
+    let inventory = database::economy::get_inventory(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let skill = database::crafting::get_crafting_skill(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let (pending, recent) = database::crafting::get_craft_queue(pool, user_id, RECENT_CRAFTS)
         .await
         .unwrap_or_default();
 
     // 3. Combine the data into the format the UI expects.
+    let items = registry();
     let recipe_infos: Vec<_> = all_recipes
         .iter()
         .filter_map(|recipe| {
             if let Some(ingredients) = all_ingredients.get(&recipe.recipe_id)
-                && let Some(output_item) = Item::from_i32(recipe.output_item_id)
+                && let Some(output_item) = items.get(recipe.output_item_id).cloned()
             {
                 return Some(RecipeInfo {
                     recipe,
@@ -67,57 +92,113 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         })
         .collect();
 
-    // 4. Generate and send the UI.
-    let (embed, components) = create_crafting_menu(&recipe_infos, &inventory);
-    let builder = EditInteractionResponse::new()
-        .embed(embed)
-        .components(components);
+    let queue = CraftQueueInfo {
+        skill,
+        pending: &pending,
+        recent: &recent,
+        now: Utc::now(),
+    };
+    create_crafting_menu(&recipe_infos, &inventory, &queue, selected)
+}
 
-    interaction.edit_response(&ctx.http, builder).await.ok();
+/// Queues a craft and returns the message to show the player.
+pub async fn queue_and_describe(
+    pool: &PgPool,
+    user_id: UserId,
+    recipe_id: i32,
+    quantity: i32,
+) -> String {
+    match database::crafting::queue_craft(pool, user_id, recipe_id, quantity).await {
+        Ok(craft) => format!(
+            "⚒️ Queued **{}x {}**. Ready in **{}**.",
+            craft.quantity,
+            registry().name_of(craft.output_item_id),
+            format_duration(craft.completes_at - Utc::now())
+        ),
+        Err(e) => format!("❌ Crafting failed: {}", e),
+    }
+}
+
+/// Resolves an item name to its recipe and queues `quantity` of it.
+async fn queue_by_name(pool: &PgPool, user_id: UserId, name: &str, quantity: i32) -> String {
+    let Some(item) = registry().resolve(name).cloned() else {
+        return format!("❌ '{}' is not a known item.", name);
+    };
+    match database::crafting::find_recipe_for_output(pool, item.id).await {
+        Ok(Some(recipe)) => queue_and_describe(pool, user_id, recipe.recipe_id, quantity).await,
+        Ok(None) => format!("❌ **{}** cannot be crafted.", item.name),
+        Err(_) => "❌ Could not load recipes.".to_string(),
+    }
 }
 
-pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Defer(
+                CreateInteractionResponseMessage::new().ephemeral(true),
+            ),
+        )
+        .await
+        .ok();
+
     let Some(app_state) = AppState::from_ctx(ctx).await else {
         return;
     };
     let pool = app_state.db.clone();
+    let user_id = interaction.user.id;
+    let options = &interaction.data.options;
+    let item = options
+        .iter()
+        .find(|o| o.name == "item")
+        .and_then(|o| o.value.as_str());
+    let count = options
+        .iter()
+        .find(|o| o.name == "count")
+        .and_then(|o| o.value.as_i64())
+        .unwrap_or(1) as i32;
 
-    let all_recipes = database::crafting::get_all_recipes(&pool)
-        .await
-        .unwrap_or_default();
-    let mut all_ingredients = HashMap::new();
-    for recipe in &all_recipes {
-        if let Ok(ingredients) =
-            database::crafting::get_ingredients_for_recipe(&pool, recipe.recipe_id).await
-        {
-            all_ingredients.insert(recipe.recipe_id, ingredients);
-        }
+    let notice = match item {
+        Some(item) => Some(queue_by_name(&pool, user_id, item, count).await),
+        None => None,
+    };
+    let (embed, components) = build_craft_view(&pool, user_id, None).await;
+    let mut builder = EditInteractionResponse::new()
+        .embed(embed)
+        .components(components);
+    if let Some(notice) = notice {
+        builder = builder.content(notice);
     }
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
 
-    let inventory = database::economy::get_inventory(&pool, msg.author.id)
-        .await
-        .unwrap_or_default();
+/// `$craft`, or `$craft <item> [count]` where the count may be written `x10`.
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let pool = app_state.db.clone();
 
-    let recipe_infos: Vec<_> = all_recipes
-        .iter()
-        .filter_map(|recipe| {
-            if let Some(ingredients) = all_ingredients.get(&recipe.recipe_id)
-                && let Some(output_item) = Item::from_i32(recipe.output_item_id)
-            {
-                return Some(RecipeInfo {
-                    recipe,
-                    ingredients,
-                    output_item,
-                });
-            }
-            None
-        })
-        .collect();
+    let notice = if args.is_empty() {
+        None
+    } else {
+        let count = args
+            .last()
+            .and_then(|a| a.strip_prefix(['x', 'X']).unwrap_or(a).parse::<i32>().ok());
+        let (item_args, count) = match count {
+            Some(n) if args.len() > 1 => (&args[..args.len() - 1], n),
+            _ => (&args[..], 1),
+        };
+        Some(queue_by_name(&pool, msg.author.id, &item_args.join(" "), count).await)
+    };
 
-    let (embed, components) = create_crafting_menu(&recipe_infos, &inventory);
-    let builder = CreateMessage::new()
+    let (embed, components) = build_craft_view(&pool, msg.author.id, None).await;
+    let mut builder = CreateMessage::new()
         .embed(embed)
         .components(components)
         .reference_message(msg);
+    if let Some(notice) = notice {
+        builder = builder.content(notice);
+    }
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
@@ -1,32 +1,155 @@
 //! Handles the UI creation for the `/craft` command.
 
-use crate::commands::economy::core::item::Item;
-use crate::database::models::{InventoryItem, Recipe, RecipeIngredient};
+use super::profession::{
+    CraftOutcome, MAX_BATCH, MAX_QUEUE, QualityTier, craft_duration, fine_chance_pct,
+    masterwork_chance_pct, save_chance_pct,
+};
+use crate::commands::economy::core::profile::xp_for_level;
+use crate::commands::economy::core::registry::{ItemDef, registry};
+use crate::commands::economy::work::ui::format_duration;
+use crate::database::models::{
+    CraftingSkill, InventoryItem, QueuedCraft, Recipe, RecipeIngredient,
+};
+use chrono::{DateTime, Utc};
 use serenity::builder::{
-    CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind,
-    CreateSelectMenuOption,
+    CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption,
 };
+use serenity::model::application::ButtonStyle;
 use std::collections::HashMap;
 
+/// Batch sizes offered as buttons once a recipe is selected.
+pub const BATCH_BUTTONS: [i32; 3] = [1, 5, MAX_BATCH];
+
 /// A helper struct to hold all the data needed to render a recipe.
 pub struct RecipeInfo<'a> {
     pub recipe: &'a Recipe,
     pub ingredients: &'a [RecipeIngredient],
-    pub output_item: Item,
+    pub output_item: ItemDef,
+}
+
+/// The player's crafting level and queue.
+pub struct CraftQueueInfo<'a> {
+    pub skill: CraftingSkill,
+    pub pending: &'a [QueuedCraft],
+    pub recent: &'a [QueuedCraft],
+    pub now: DateTime<Utc>,
+}
+
+/// How many units of a recipe the inventory can pay for.
+fn max_affordable(ingredients: &[RecipeIngredient], inventory: &HashMap<i32, i64>) -> i64 {
+    ingredients
+        .iter()
+        .map(|ing| inventory.get(&ing.item_id).copied().unwrap_or(0) / (ing.quantity.max(1) as i64))
+        .min()
+        .unwrap_or(0)
+}
+
+fn outcome_of(craft: &QueuedCraft) -> CraftOutcome {
+    CraftOutcome {
+        standard: craft.standard_count,
+        fine: craft.fine_count,
+        masterwork: craft.masterwork_count,
+        saved: craft.saved_count,
+    }
 }
 
 /// Creates the main embed and components for the crafting menu.
 pub fn create_crafting_menu(
     recipes: &[RecipeInfo],
     inventory: &[InventoryItem],
+    queue: &CraftQueueInfo,
+    selected: Option<i32>,
 ) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let level = queue.skill.level;
+    let xp_needed = xp_for_level(level + 1);
     let mut embed = CreateEmbed::new()
         .title("Crafting Workshop")
-        .description("Combine materials to create powerful new items.")
+        .description(format!(
+            "Combine materials to create powerful new items.\n**Crafting Level {}** (`{}/{}` XP) · 🔵 Fine `{}%` · 🟣 Masterwork `{}%` · ♻️ Save `{}%`",
+            level,
+            queue.skill.xp,
+            xp_needed,
+            fine_chance_pct(level),
+            masterwork_chance_pct(level),
+            save_chance_pct(level)
+        ))
         .color(0x964B00); // Brown
 
+    let items = registry();
+    let queue_full = queue.pending.len() as i64 >= MAX_QUEUE;
+    let queue_text = if queue.pending.is_empty() {
+        "_Nothing in progress._".to_string()
+    } else {
+        queue
+            .pending
+            .iter()
+            .enumerate()
+            .map(|(i, craft)| {
+                let remaining = (craft.completes_at - queue.now).max(chrono::Duration::zero());
+                let state = if i == 0 || craft.starts_at <= queue.now {
+                    "⚒️"
+                } else {
+                    "⏸️"
+                };
+                format!(
+                    "{} `{}x` {} — ready in **{}**",
+                    state,
+                    craft.quantity,
+                    items.name_of(craft.output_item_id),
+                    format_duration(remaining)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    embed = embed.field(
+        format!("🛠️ Queue ({}/{})", queue.pending.len(), MAX_QUEUE),
+        queue_text,
+        false,
+    );
+
+    if !queue.recent.is_empty() {
+        let recent_text = queue
+            .recent
+            .iter()
+            .map(|craft| {
+                let outcome = outcome_of(craft);
+                let tiers = [
+                    QualityTier::Standard,
+                    QualityTier::Fine,
+                    QualityTier::Masterwork,
+                ]
+                .iter()
+                .filter(|t| outcome.count(**t) > 0)
+                .map(|t| format!("{} {} {}", t.emoji(), outcome.count(*t), t.label()))
+                .collect::<Vec<_>>()
+                .join(" ");
+                let saved = if outcome.saved > 0 {
+                    format!(" · ♻️ {} saved", outcome.saved)
+                } else {
+                    String::new()
+                };
+                format!(
+                    "`{}x` {}: {}{} · +{} XP",
+                    craft.quantity,
+                    items.name_of(craft.output_item_id),
+                    tiers,
+                    saved,
+                    craft.xp_gained
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("📦 Recently Finished", recent_text, false);
+    }
+
     if recipes.is_empty() {
-        embed = embed.description("There are no crafting recipes available at the moment.");
+        embed = embed.field(
+            "Recipes",
+            "There are no crafting recipes available at the moment.",
+            false,
+        );
         return (embed, vec![]);
     }
 
@@ -34,56 +157,80 @@ pub fn create_crafting_menu(
     let inventory_map: HashMap<i32, i64> = inventory
         .iter()
         .filter_map(|inv_item| {
-            if let Ok(item_enum) = inv_item.name.parse::<Item>() {
-                Some((item_enum as i32, inv_item.quantity))
-            } else {
-                None
-            }
+            items
+                .resolve(&inv_item.name)
+                .map(|def| (def.id, inv_item.quantity))
         })
         .collect();
 
     let mut select_options = Vec::new();
+    let mut selected_affordable = None;
 
     for recipe_info in recipes {
+        let recipe = recipe_info.recipe;
         let ingredients_str = recipe_info
             .ingredients
             .iter()
             .filter_map(|ing| {
-                let required_item = Item::from_i32(ing.item_id)?; // skip invalid silently
+                let required_item = items.get(ing.item_id)?; // skip invalid silently
                 let owned_qty = inventory_map.get(&ing.item_id).copied().unwrap_or(0);
                 Some(format!(
                     "`{}/{}` {}",
-                    owned_qty,
-                    ing.quantity,
-                    required_item.display_name()
+                    owned_qty, ing.quantity, required_item.name
                 ))
             })
             .collect::<Vec<_>>()
             .join(", ");
 
-        let can_craft = recipe_info.ingredients.iter().all(|ing| {
-            inventory_map.get(&ing.item_id).copied().unwrap_or(0) >= ing.quantity as i64
-        });
-
-        let craft_status_emoji = if can_craft { "✅" } else { "❌" };
+        let unlocked = level >= recipe.required_level;
+        let affordable = max_affordable(recipe_info.ingredients, &inventory_map);
+        let can_craft = unlocked && affordable > 0;
+        let craft_status_emoji = if !unlocked {
+            "🔒"
+        } else if can_craft {
+            "✅"
+        } else {
+            "❌"
+        };
+        let masterwork = recipe
+            .masterwork_item_id
+            .map(|id| format!("\n🟣 Masterwork: {}", items.name_of(id)))
+            .unwrap_or_default();
+        let requirement = if unlocked {
+            String::new()
+        } else {
+            format!(" · Lv {}", recipe.required_level)
+        };
 
         embed = embed.field(
             format!(
                 "{} {} {}",
-                craft_status_emoji,
-                recipe_info.output_item.emoji(),
-                recipe_info.output_item.display_name()
+                craft_status_emoji, recipe_info.output_item.emoji, recipe_info.output_item.name
+            ),
+            format!(
+                "Requires: {}\n⏱️ {} each{}{}",
+                ingredients_str,
+                format_duration(craft_duration(recipe.craft_secs, level, 1)),
+                requirement,
+                masterwork
             ),
-            format!("Requires: {}", ingredients_str),
             false,
         );
 
-        // Only add recipes the player can afford to the dropdown menu.
+        // Only add recipes the player can craft to the dropdown menu.
         if can_craft {
-            select_options.push(CreateSelectMenuOption::new(
-                recipe_info.output_item.display_name(),
-                recipe_info.recipe.recipe_id.to_string(),
-            ));
+            let is_selected = selected == Some(recipe.recipe_id);
+            if is_selected {
+                selected_affordable = Some((recipe.recipe_id, affordable));
+            }
+            select_options.push(
+                CreateSelectMenuOption::new(
+                    recipe_info.output_item.name.as_str(),
+                    recipe.recipe_id.to_string(),
+                )
+                .description(format!("You can craft {}", affordable))
+                .default_selection(is_selected),
+            );
         }
     }
 
@@ -104,5 +251,30 @@ pub fn create_crafting_menu(
         ));
     }
 
+    let mut buttons = Vec::new();
+    if let Some((recipe_id, affordable)) = selected_affordable {
+        for batch in BATCH_BUTTONS {
+            buttons.push(
+                CreateButton::new(format!("craft_make_{}_{}", recipe_id, batch))
+                    .label(format!("Craft x{}", batch))
+                    .style(ButtonStyle::Success)
+                    .disabled(queue_full || affordable < batch as i64),
+            );
+        }
+    }
+    buttons.push(
+        CreateButton::new("craft_refresh")
+            .label("Refresh")
+            .emoji('🔄')
+            .style(ButtonStyle::Secondary),
+    );
+    components.push(CreateActionRow::Buttons(buttons));
+
+    if queue_full {
+        embed = embed.footer(CreateEmbedFooter::new(
+            "Your queue is full. New crafts can be queued when one finishes.",
+        ));
+    }
+
     (embed, components)
 }
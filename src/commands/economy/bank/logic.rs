@@ -0,0 +1,173 @@
+//! Pure bank rules: account ranks, daily interest, loan terms and installment collection.
+//!
+//! The bank balance earns interest once per UTC day, up to a daily cap set by the account rank.
+//! Rank grows with time in good standing. A loan is repaid in fixed daily installments taken
+//! from the wallet first, then the bank balance; a missed installment adds a penalty, and too many
+//! missed installments default the loan: the bank seizes what it can, resets the account's rank
+//! and blocks new loans for a while.
+
+use chrono::Duration;
+
+/// Days of interest paid at most when a player returns after a long absence.
+pub const MAX_INTEREST_CATCHUP_DAYS: i64 = 7;
+/// Origination fee added to every loan's principal.
+pub const LOAN_FEE_PCT: i64 = 10;
+pub const LOAN_INSTALLMENTS: i64 = 4;
+pub const LOAN_INSTALLMENT_HOURS: i64 = 24;
+pub const MIN_LOAN: i64 = 100;
+/// Penalty (percent of the installment) added to the debt for each missed installment.
+pub const MISSED_PAYMENT_PENALTY_PCT: i64 = 10;
+/// Missed installments after which a loan defaults.
+pub const MAX_MISSED_PAYMENTS: i32 = 3;
+pub const DEFAULT_CREDIT_BLOCK_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BankRank {
+    Copper,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl BankRank {
+    pub const ALL: [BankRank; 4] = [
+        BankRank::Copper,
+        BankRank::Silver,
+        BankRank::Gold,
+        BankRank::Platinum,
+    ];
+
+    /// Rank earned after `days` in good standing.
+    pub fn for_days(days: i64) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|r| days >= r.min_days())
+            .unwrap_or(BankRank::Copper)
+    }
+
+    pub fn min_days(self) -> i64 {
+        match self {
+            BankRank::Copper => 0,
+            BankRank::Silver => 7,
+            BankRank::Gold => 30,
+            BankRank::Platinum => 90,
+        }
+    }
+
+    pub fn next(self) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| *r > self)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BankRank::Copper => "Copper",
+            BankRank::Silver => "Silver",
+            BankRank::Gold => "Gold",
+            BankRank::Platinum => "Platinum",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            BankRank::Copper => "🟤",
+            BankRank::Silver => "⚪",
+            BankRank::Gold => "🟡",
+            BankRank::Platinum => "💠",
+        }
+    }
+
+    /// Daily interest in basis points of the bank balance.
+    pub fn interest_bp(self) -> i64 {
+        match self {
+            BankRank::Copper => 10,
+            BankRank::Silver => 15,
+            BankRank::Gold => 20,
+            BankRank::Platinum => 25,
+        }
+    }
+
+    /// Most interest paid for a single day.
+    pub fn daily_interest_cap(self) -> i64 {
+        match self {
+            BankRank::Copper => 50,
+            BankRank::Silver => 150,
+            BankRank::Gold => 400,
+            BankRank::Platinum => 1_000,
+        }
+    }
+
+    /// Largest loan principal on offer.
+    pub fn max_loan(self) -> i64 {
+        match self {
+            BankRank::Copper => 1_000,
+            BankRank::Silver => 3_000,
+            BankRank::Gold => 10_000,
+            BankRank::Platinum => 25_000,
+        }
+    }
+}
+
+/// Interest for one day on `balance`.
+pub fn daily_interest(balance: i64, rank: BankRank) -> i64 {
+    (balance.max(0) * rank.interest_bp() / 10_000).min(rank.daily_interest_cap())
+}
+
+/// Interest for `days` consecutive days (compounding, at most `MAX_INTEREST_CATCHUP_DAYS`).
+pub fn accrued_interest(balance: i64, rank: BankRank, days: i64) -> i64 {
+    let mut total = 0;
+    for _ in 0..days.clamp(0, MAX_INTEREST_CATCHUP_DAYS) {
+        total += daily_interest(balance + total, rank);
+    }
+    total
+}
+
+/// Repayment terms for a new loan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoanTerms {
+    pub principal: i64,
+    pub total_due: i64,
+    pub installment: i64,
+}
+
+impl LoanTerms {
+    pub fn for_principal(principal: i64) -> Self {
+        let total_due = principal + principal * LOAN_FEE_PCT / 100;
+        Self {
+            principal,
+            total_due,
+            installment: (total_due + LOAN_INSTALLMENTS - 1) / LOAN_INSTALLMENTS,
+        }
+    }
+}
+
+pub fn installment_interval() -> Duration {
+    Duration::hours(LOAN_INSTALLMENT_HOURS)
+}
+
+pub fn missed_payment_penalty(installment: i64) -> i64 {
+    (installment * MISSED_PAYMENT_PENALTY_PCT / 100).max(1)
+}
+
+/// Splits a payment of `amount` into (from wallet, from bank), or `None` when the player cannot
+/// cover it.
+pub fn split_payment(amount: i64, wallet: i64, bank: i64) -> Option<(i64, i64)> {
+    let from_wallet = amount.min(wallet.max(0));
+    let from_bank = amount - from_wallet;
+    (from_bank <= bank.max(0)).then_some((from_wallet, from_bank))
+}
+
+/// Parses an amount like `500` or `1,000`; `all`/`max` parse to `None`, meaning "as much as
+/// possible".
+pub fn parse_amount(raw: &str) -> Result<Option<i64>, String> {
+    let raw = raw.trim().to_lowercase();
+    if raw == "all" || raw == "max" {
+        return Ok(None);
+    }
+    raw.replace(',', "")
+        .parse::<i64>()
+        .ok()
+        .filter(|v| *v > 0)
+        .map(Some)
+        .ok_or_else(|| format!("'{}' is not a valid amount.", raw))
+}
@@ -0,0 +1,5 @@
+//! Implements `/bank`: savings kept out of the wallet, daily interest and installment loans.
+
+pub mod logic;
+pub mod run;
+pub mod ui;
@@ -0,0 +1,207 @@
+//! Handles the command logic for `/bank` and `$bank`.
+
+use super::logic::{MIN_LOAN, parse_amount};
+use super::ui::create_bank_embed;
+use crate::AppState;
+use crate::database::bank::{self, account_rank};
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage, EditInteractionResponse,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::id::UserId;
+use serenity::prelude::*;
+use sqlx::PgPool;
+
+/// Ledger entries shown on the statement.
+const STATEMENT_ENTRIES: i64 = 5;
+
+fn amount_option(description: &str, required: bool) -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "amount", description).required(required)
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("bank")
+        .description("Save coins, earn daily interest and take out loans.")
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "view",
+            "Show your bank statement",
+        ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "deposit",
+                "Move coins from your wallet into the bank",
+            )
+            .add_sub_option(amount_option("Amount, or 'all'", true)),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "withdraw",
+                "Move coins from the bank into your wallet",
+            )
+            .add_sub_option(amount_option("Amount, or 'all'", true)),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "loan", "Borrow coins")
+                .add_sub_option(amount_option("Amount, or 'max'", true)),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "repay",
+                "Pay off your loan from your wallet",
+            )
+            .add_sub_option(amount_option("Amount (defaults to the whole debt)", false)),
+        )
+}
+
+/// A parsed `/bank` or `$bank` request. `None` amounts mean "as much as possible".
+enum BankAction {
+    View,
+    Deposit(Option<i64>),
+    Withdraw(Option<i64>),
+    Loan(Option<i64>),
+    Repay(Option<i64>),
+}
+
+/// Builds the statement embed, with any settlement notices (interest, collected installments)
+/// shown above it.
+async fn build_statement(db: &PgPool, user_id: UserId) -> Result<(String, CreateEmbed), String> {
+    let statement = bank::get_statement(db, user_id, STATEMENT_ENTRIES)
+        .await
+        .map_err(|_| "Could not load your bank account.".to_string())?;
+    Ok((statement.notices.join("\n"), create_bank_embed(&statement)))
+}
+
+/// Runs a write action and returns the confirmation or error line.
+async fn run_action(db: &PgPool, user_id: UserId, action: BankAction) -> String {
+    let result = match action {
+        BankAction::View => return String::new(),
+        BankAction::Deposit(amount) => {
+            bank::deposit(db, user_id, amount)
+                .await
+                .map(|(amount, balance)| {
+                    format!("🏦 Deposited 💰{}. Bank balance: 💰{}.", amount, balance)
+                })
+        }
+        BankAction::Withdraw(amount) => {
+            bank::withdraw(db, user_id, amount)
+                .await
+                .map(|(amount, balance)| {
+                    format!("👛 Withdrew 💰{}. Bank balance: 💰{}.", amount, balance)
+                })
+        }
+        BankAction::Loan(amount) => {
+            let principal = match amount {
+                Some(amount) => amount,
+                None => match bank::get_statement(db, user_id, 0).await {
+                    Ok(statement) => account_rank(&statement.account).max_loan(),
+                    Err(_) => return "Could not load your bank account.".to_string(),
+                },
+            };
+            bank::take_loan(db, user_id, principal).await.map(|loan| {
+                format!(
+                    "🤝 Borrowed 💰{}. You owe 💰{} in installments of 💰{}; the first is due <t:{}:R>.",
+                    loan.principal,
+                    loan.total_due,
+                    loan.installment,
+                    loan.next_due_at.timestamp()
+                )
+            })
+        }
+        BankAction::Repay(amount) => {
+            bank::repay_loan(db, user_id, amount)
+                .await
+                .map(|(paid, remaining)| {
+                    if remaining == 0 {
+                        format!("🎉 Paid 💰{} and cleared your loan.", paid)
+                    } else {
+                        format!("💳 Paid 💰{}. Still owed: 💰{}.", paid, remaining)
+                    }
+                })
+        }
+    };
+    result.unwrap_or_else(|e| e)
+}
+
+fn parse_slash(interaction: &CommandInteraction) -> Result<BankAction, String> {
+    let Some(sub) = interaction.data.options.first() else {
+        return Ok(BankAction::View);
+    };
+    let CommandDataOptionValue::SubCommand(nested) = &sub.value else {
+        return Err("Invalid bank command.".to_string());
+    };
+    let amount = nested
+        .iter()
+        .find(|o| o.name == "amount")
+        .and_then(|o| o.value.as_str());
+    parse_action(sub.name.as_str(), amount)
+}
+
+/// `$bank [view] | deposit <amount|all> | withdraw <amount|all> | loan <amount|max> | repay [amount]`
+fn parse_action(sub: &str, amount: Option<&str>) -> Result<BankAction, String> {
+    let required = |amount: Option<&str>| match amount {
+        Some(raw) => parse_amount(raw),
+        None => Err(format!("Please give an amount to {}.", sub)),
+    };
+    match sub {
+        "view" => Ok(BankAction::View),
+        "deposit" => Ok(BankAction::Deposit(required(amount)?)),
+        "withdraw" => Ok(BankAction::Withdraw(required(amount)?)),
+        "loan" => match required(amount)? {
+            Some(principal) if principal < MIN_LOAN => {
+                Err(format!("The smallest loan is 💰{}.", MIN_LOAN))
+            }
+            principal => Ok(BankAction::Loan(principal)),
+        },
+        "repay" => Ok(BankAction::Repay(match amount {
+            Some(raw) => parse_amount(raw)?,
+            None => None,
+        })),
+        _ => Err(
+            "Usage: `$bank`, `$bank deposit <amount|all>`, `$bank withdraw <amount|all>`, `$bank loan <amount|max>`, `$bank repay [amount]`"
+                .to_string(),
+        ),
+    }
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction.defer_ephemeral(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let builder = match parse_slash(interaction) {
+        Ok(BankAction::View) => match build_statement(db, interaction.user.id).await {
+            Ok((notices, embed)) => EditInteractionResponse::new().content(notices).embed(embed),
+            Err(e) => EditInteractionResponse::new().content(e),
+        },
+        Ok(action) => EditInteractionResponse::new()
+            .content(run_action(db, interaction.user.id, action).await),
+        Err(e) => EditInteractionResponse::new().content(e),
+    };
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let sub = args.first().map(|s| s.to_lowercase());
+    let builder = match parse_action(sub.as_deref().unwrap_or("view"), args.get(1).copied()) {
+        Ok(BankAction::View) => match build_statement(db, msg.author.id).await {
+            Ok((notices, embed)) => CreateMessage::new().content(notices).embed(embed),
+            Err(e) => CreateMessage::new().content(e),
+        },
+        Ok(action) => CreateMessage::new().content(run_action(db, msg.author.id, action).await),
+        Err(e) => CreateMessage::new().content(e),
+    };
+    msg.channel_id
+        .send_message(&ctx.http, builder.reference_message(msg))
+        .await
+        .ok();
+}
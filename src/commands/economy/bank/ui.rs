@@ -0,0 +1,116 @@
+//! Handles embed creation for the `/bank` command.
+
+use super::logic::{
+    BankRank, LOAN_FEE_PCT, LOAN_INSTALLMENTS, MAX_MISSED_PAYMENTS, daily_interest,
+};
+use crate::database::bank::{BankStatement, account_rank};
+use crate::database::models::BankEntryKind;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+fn entry_label(kind: BankEntryKind) -> &'static str {
+    match kind {
+        BankEntryKind::Deposit => "📥 Deposit",
+        BankEntryKind::Withdrawal => "📤 Withdrawal",
+        BankEntryKind::Interest => "📈 Interest",
+        BankEntryKind::LoanIssued => "🤝 Loan issued",
+        BankEntryKind::LoanPayment => "💳 Loan payment",
+        BankEntryKind::LoanPenalty => "⚠️ Missed payment",
+        BankEntryKind::LoanDefault => "🚫 Loan default",
+    }
+}
+
+fn signed(amount: i64) -> String {
+    if amount > 0 {
+        format!("+{}", amount)
+    } else {
+        amount.to_string()
+    }
+}
+
+pub fn create_bank_embed(statement: &BankStatement) -> CreateEmbed {
+    let account = &statement.account;
+    let rank = account_rank(account);
+    let next_rank = match rank.next() {
+        Some(next) => format!(
+            "\nNext rank: {} {} after {} days in good standing.",
+            next.emoji(),
+            next.label(),
+            next.min_days()
+        ),
+        None => String::new(),
+    };
+    let mut embed = CreateEmbed::new()
+        .title("🏦 Bank")
+        .description(format!(
+            "Coins in the bank are safe from bets and purchases; move them to your wallet to spend them.\n{} **{}** account · {:.2}% daily interest (max 💰{} per day){}",
+            rank.emoji(),
+            rank.label(),
+            rank.interest_bp() as f64 / 100.0,
+            rank.daily_interest_cap(),
+            next_rank
+        ))
+        .field("👛 Wallet", format!("💰{}", statement.wallet), true)
+        .field("🏦 Bank", format!("💰{}", account.balance), true)
+        .field(
+            "📈 Next Interest",
+            format!("💰{}", daily_interest(account.balance, rank)),
+            true,
+        )
+        .color(0xF1C40F);
+
+    let loan_text = match &statement.loan {
+        Some(loan) => format!(
+            "Owed: **💰{}** of 💰{} · installment 💰{} due <t:{}:R>\nMissed payments: {}/{}",
+            loan.outstanding(),
+            loan.total_due,
+            loan.installment.min(loan.outstanding()),
+            loan.next_due_at.timestamp(),
+            loan.missed_payments,
+            MAX_MISSED_PAYMENTS
+        ),
+        None => match account.credit_blocked_until {
+            Some(until) if until > chrono::Utc::now() => format!(
+                "The bank will not lend to you until <t:{}:R>.",
+                until.timestamp()
+            ),
+            _ => format!(
+                "Borrow up to 💰{} with `/bank loan`. Repay {}% extra over {} daily installments, taken from your wallet (then your bank) when due.",
+                rank.max_loan(),
+                LOAN_FEE_PCT,
+                LOAN_INSTALLMENTS
+            ),
+        },
+    };
+    embed = embed.field("🤝 Loan", loan_text, false);
+
+    if !statement.recent.is_empty() {
+        let history = statement
+            .recent
+            .iter()
+            .map(|e| {
+                let mut parts = Vec::new();
+                if e.wallet_delta != 0 {
+                    parts.push(format!("wallet {}", signed(e.wallet_delta)));
+                }
+                if e.bank_delta != 0 {
+                    parts.push(format!("bank {}", signed(e.bank_delta)));
+                }
+                format!(
+                    "<t:{}:d> {} {} → 💰{}",
+                    e.created_at.timestamp(),
+                    entry_label(e.kind),
+                    parts.join(", "),
+                    e.bank_balance
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("📒 Recent Activity", history, false);
+    }
+    let ranks = BankRank::ALL
+        .iter()
+        .map(|r| format!("{} {}d", r.emoji(), r.min_days()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    embed.footer(CreateEmbedFooter::new(format!("Ranks: {}", ranks)))
+}
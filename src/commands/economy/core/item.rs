@@ -29,6 +29,17 @@ impl Rarity {
             Rarity::Legendary => 0x9B59B6,
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [
+            Rarity::Common,
+            Rarity::Uncommon,
+            Rarity::Rare,
+            Rarity::Legendary,
+        ]
+        .into_iter()
+        .find(|r| r.as_str().eq_ignore_ascii_case(s))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -36,6 +47,29 @@ pub enum ItemCategory {
     Resource,
     Special,
     Consumable,
+    Equipment,
+}
+
+impl ItemCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemCategory::Resource => "Resource",
+            ItemCategory::Special => "Special",
+            ItemCategory::Consumable => "Consumable",
+            ItemCategory::Equipment => "Equipment",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [
+            ItemCategory::Resource,
+            ItemCategory::Special,
+            ItemCategory::Consumable,
+            ItemCategory::Equipment,
+        ]
+        .into_iter()
+        .find(|c| c.as_str().eq_ignore_ascii_case(s))
+    }
 }
 
 pub struct ItemProperties {
@@ -74,9 +108,41 @@ pub enum Item {
     FocusTonic = 19,
     BearResearchData = 20,
     SpiderResearchData = 21,
+    IronSword = 22,
+    LeatherArmor = 23,
+    LuckyCharm = 24,
 }
 
 impl Item {
+    /// Every item known to the code. Used to build the fallback registry when the `items`
+    /// table cannot be loaded.
+    pub const ALL: [Item; 24] = [
+        Item::Fish,
+        Item::Ore,
+        Item::Gem,
+        Item::GoldenFish,
+        Item::LargeGeode,
+        Item::AncientRelic,
+        Item::XpBooster,
+        Item::SlimeGel,
+        Item::SlimeResearchData,
+        Item::TamingLure,
+        Item::HealthPotion,
+        Item::WolfResearchData,
+        Item::BoarResearchData,
+        Item::ForestContractParchment,
+        Item::FrontierContractParchment,
+        Item::ScholarResearchNotes,
+        Item::GreaterHealthPotion,
+        Item::StaminaDraft,
+        Item::FocusTonic,
+        Item::BearResearchData,
+        Item::SpiderResearchData,
+        Item::IronSword,
+        Item::LeatherArmor,
+        Item::LuckyCharm,
+    ];
+
     // (✓) NEW: Add the id() method to resolve compiler errors.
     pub fn id(&self) -> i32 {
         *self as i32
@@ -315,43 +381,71 @@ impl Item {
                 buy_price: None,
                 sell_price: None,
             },
+            Item::IronSword => ItemProperties {
+                display_name: "Iron Sword",
+                description: "A dependable blade. Equip on a unit for extra attack.",
+                emoji: "🗡️",
+                category: ItemCategory::Equipment,
+                rarity: Rarity::Uncommon,
+                is_sellable: true,
+                is_tradeable: true,
+                buy_price: None,
+                sell_price: Some(60),
+            },
+            Item::LeatherArmor => ItemProperties {
+                display_name: "Leather Armor",
+                description: "Hardened leather that turns aside glancing blows.",
+                emoji: "🛡️",
+                category: ItemCategory::Equipment,
+                rarity: Rarity::Uncommon,
+                is_sellable: true,
+                is_tradeable: true,
+                buy_price: None,
+                sell_price: Some(60),
+            },
+            Item::LuckyCharm => ItemProperties {
+                display_name: "Lucky Charm",
+                description: "A worn trinket that seems to help a little with everything.",
+                emoji: "🧿",
+                category: ItemCategory::Equipment,
+                rarity: Rarity::Rare,
+                is_sellable: true,
+                is_tradeable: true,
+                buy_price: None,
+                sell_price: Some(90),
+            },
         }
     }
 
-    pub fn get_all_purchasable() -> Vec<Item> {
-        vec![
-            Item::Fish,
-            Item::Ore,
-            Item::Gem,
-            Item::XpBooster,
-            Item::TamingLure,
-        ]
+    pub fn from_i32(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| item.id() == id)
     }
 
-    pub fn from_i32(id: i32) -> Option<Self> {
-        match id {
-            1 => Some(Item::Fish),
-            2 => Some(Item::Ore),
-            3 => Some(Item::Gem),
-            4 => Some(Item::GoldenFish),
-            5 => Some(Item::LargeGeode),
-            6 => Some(Item::AncientRelic),
-            7 => Some(Item::XpBooster),
-            8 => Some(Item::SlimeGel),
-            9 => Some(Item::SlimeResearchData),
-            10 => Some(Item::TamingLure),
-            11 => Some(Item::HealthPotion),
-            12 => Some(Item::WolfResearchData),
-            13 => Some(Item::BoarResearchData),
-            14 => Some(Item::ForestContractParchment),
-            15 => Some(Item::FrontierContractParchment),
-            16 => Some(Item::ScholarResearchNotes),
-            17 => Some(Item::GreaterHealthPotion),
-            18 => Some(Item::StaminaDraft),
-            19 => Some(Item::FocusTonic),
-            20 => Some(Item::BearResearchData),
-            21 => Some(Item::SpiderResearchData),
-            _ => None,
+    /// Extra names accepted for this item besides its key (see `Display`).
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Item::Fish | Item::Ore | Item::Gem => &[],
+            Item::GoldenFish => &["golden"],
+            Item::LargeGeode => &["geode"],
+            Item::AncientRelic => &["relic"],
+            Item::XpBooster => &["booster"],
+            Item::SlimeGel => &["gel"],
+            Item::SlimeResearchData => &["data"],
+            Item::TamingLure => &["lure", "contract"],
+            Item::HealthPotion => &["potion"],
+            Item::WolfResearchData => &["wolfresearchdata"],
+            Item::BoarResearchData => &["boarresearchdata"],
+            Item::ForestContractParchment => &["forestparchment"],
+            Item::FrontierContractParchment => &["frontierparchment"],
+            Item::ScholarResearchNotes => &["researchnotes"],
+            Item::GreaterHealthPotion => &["greaterpotion"],
+            Item::StaminaDraft => &["draft"],
+            Item::FocusTonic => &["tonic"],
+            Item::BearResearchData => &["bearresearchdata"],
+            Item::SpiderResearchData => &["spiderresearchdata"],
+            Item::IronSword => &["sword"],
+            Item::LeatherArmor => &["armor"],
+            Item::LuckyCharm => &["charm"],
         }
     }
 
@@ -387,30 +481,17 @@ impl Item {
 impl FromStr for Item {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "fish" => Ok(Item::Fish),
-            "ore" => Ok(Item::Ore),
-            "gem" => Ok(Item::Gem),
-            "goldenfish" | "golden" => Ok(Item::GoldenFish),
-            "largegeode" | "geode" => Ok(Item::LargeGeode),
-            "ancientrelic" | "relic" => Ok(Item::AncientRelic),
-            "xpbooster" | "booster" => Ok(Item::XpBooster),
-            "slimegel" | "gel" => Ok(Item::SlimeGel),
-            "slimedata" | "data" => Ok(Item::SlimeResearchData),
-            "taminglure" | "lure" | "contract" => Ok(Item::TamingLure),
-            "healthpotion" | "potion" => Ok(Item::HealthPotion),
-            "wolfresearchdata" | "wolfdata" => Ok(Item::WolfResearchData),
-            "boarresearchdata" | "boardata" => Ok(Item::BoarResearchData),
-            "forestcontract" | "forestparchment" => Ok(Item::ForestContractParchment),
-            "frontiercontract" | "frontierparchment" => Ok(Item::FrontierContractParchment),
-            "scholarnotes" | "researchnotes" => Ok(Item::ScholarResearchNotes),
-            "greaterpotion" | "greaterhealthpotion" => Ok(Item::GreaterHealthPotion),
-            "staminadraft" | "draft" => Ok(Item::StaminaDraft),
-            "focustonic" | "tonic" => Ok(Item::FocusTonic),
-            "beardata" | "bearresearchdata" => Ok(Item::BearResearchData),
-            "spiderdata" | "spiderresearchdata" => Ok(Item::SpiderResearchData),
-            _ => Err(()),
-        }
+        let s = s.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|item| item.to_string() == s || item.aliases().contains(&s.as_str()))
+            .ok_or(())
+    }
+}
+
+impl From<Item> for i32 {
+    fn from(item: Item) -> i32 {
+        item.id()
     }
 }
 
@@ -441,6 +522,9 @@ impl fmt::Display for Item {
                 Item::FocusTonic => "focustonic",
                 Item::BearResearchData => "beardata",
                 Item::SpiderResearchData => "spiderdata",
+                Item::IronSword => "ironsword",
+                Item::LeatherArmor => "leatherarmor",
+                Item::LuckyCharm => "luckycharm",
             }
         )
     }
@@ -0,0 +1,162 @@
+//! The loot-table engine.
+//!
+//! A table is a list of entries that each drop an item (or coins when `item_id` is `None`).
+//! Weighted tables pick `rolls` entries in proportion to their weight and back rare entries with a
+//! pity counter. Independent tables give every entry its own chance (the weight, in basis points
+//! of `CHANCE_SCALE`), which is how map-node drops work. Container tables live in the database;
+//! node rewards are converted on the fly.
+
+use rand::Rng;
+
+/// Denominator for independent-mode weights: a weight of 2 500 is a 25% chance.
+pub const CHANCE_SCALE: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LootMode {
+    Weighted,
+    Independent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootEntry {
+    /// `None` drops coins.
+    pub item_id: Option<i32>,
+    pub min_quantity: i64,
+    pub max_quantity: i64,
+    pub weight: u32,
+    pub is_rare: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTable {
+    pub key: String,
+    pub mode: LootMode,
+    /// Entries picked per open (weighted tables only).
+    pub rolls: u32,
+    /// After this many opens in a row without a rare drop, the next open guarantees one.
+    pub pity_threshold: Option<u32>,
+    pub entries: Vec<LootEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LootDrop {
+    pub item_id: Option<i32>,
+    pub quantity: i64,
+    pub is_rare: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LootRoll {
+    pub drops: Vec<LootDrop>,
+    /// The pity counter forced a rare drop on this open.
+    pub pity_used: bool,
+}
+
+impl LootRoll {
+    pub fn got_rare(&self) -> bool {
+        self.drops.iter().any(|d| d.is_rare)
+    }
+
+    pub fn coins(&self) -> i64 {
+        self.drops
+            .iter()
+            .filter(|d| d.item_id.is_none())
+            .map(|d| d.quantity)
+            .sum()
+    }
+}
+
+impl LootTable {
+    /// Independent-chance table, e.g. built from a map node's reward rows.
+    pub fn independent(key: &str, entries: Vec<LootEntry>) -> Self {
+        Self {
+            key: key.to_string(),
+            mode: LootMode::Independent,
+            rolls: 1,
+            pity_threshold: None,
+            entries,
+        }
+    }
+
+    pub fn has_rares(&self) -> bool {
+        self.entries.iter().any(|e| e.is_rare && e.weight > 0)
+    }
+
+    /// Whether the next open is guaranteed a rare drop after `misses` opens without one.
+    pub fn pity_due(&self, misses: u32) -> bool {
+        self.has_rares()
+            && self
+                .pity_threshold
+                .is_some_and(|t| t > 0 && misses + 1 >= t)
+    }
+
+    /// Opens remaining until the pity guarantee kicks in (1 = the next open).
+    pub fn opens_until_pity(&self, misses: u32) -> Option<u32> {
+        self.pity_threshold
+            .filter(|_| self.has_rares())
+            .map(|t| t.saturating_sub(misses).max(1))
+    }
+
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R, misses: u32) -> LootRoll {
+        match self.mode {
+            LootMode::Independent => {
+                let mut roll = LootRoll::default();
+                for entry in &self.entries {
+                    if rng.random_range(0..CHANCE_SCALE) < entry.weight {
+                        roll.drops.push(drop_for(entry, rng));
+                    }
+                }
+                roll
+            }
+            LootMode::Weighted => {
+                let mut roll = LootRoll::default();
+                for i in 0..self.rolls.max(1) {
+                    let force_rare = i == 0 && self.pity_due(misses);
+                    let pool: Vec<&LootEntry> = self
+                        .entries
+                        .iter()
+                        .filter(|e| e.weight > 0 && (!force_rare || e.is_rare))
+                        .collect();
+                    if let Some(entry) = pick_weighted(&pool, rng) {
+                        roll.pity_used |= force_rare;
+                        roll.drops.push(drop_for(entry, rng));
+                    }
+                }
+                roll
+            }
+        }
+    }
+}
+
+fn pick_weighted<'a, R: Rng + ?Sized>(
+    entries: &[&'a LootEntry],
+    rng: &mut R,
+) -> Option<&'a LootEntry> {
+    let total: u64 = entries.iter().map(|e| e.weight as u64).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut ticket = rng.random_range(0..total);
+    for entry in entries {
+        if ticket < entry.weight as u64 {
+            return Some(entry);
+        }
+        ticket -= entry.weight as u64;
+    }
+    None
+}
+
+fn drop_for<R: Rng + ?Sized>(entry: &LootEntry, rng: &mut R) -> LootDrop {
+    let min = entry.min_quantity.max(1);
+    let max = entry.max_quantity.max(min);
+    LootDrop {
+        item_id: entry.item_id,
+        quantity: rng.random_range(min..=max),
+        is_rare: entry.is_rare,
+    }
+}
+
+/// Pity counter after an open: reset by any rare drop, otherwise one more miss.
+pub fn next_pity_misses(misses: u32, roll: &LootRoll) -> u32 {
+    if roll.got_rare() { 0 } else { misses + 1 }
+}
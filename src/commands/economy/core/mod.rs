@@ -1,4 +1,7 @@
 //! The core "engine" of the economy system.
 
 pub mod item;
+pub mod loot;
+pub mod pricing;
 pub mod profile;
+pub mod registry;
@@ -0,0 +1,157 @@
+//! Supply and demand pricing.
+//!
+//! Every item carries a price multiplier in basis points (10 000 = the base price from the
+//! registry or the Tavern list). Each day opens at the previous day's close. During the day the
+//! live multiplier is the open shifted by that day's buy/sell pressure, and at the day roll the
+//! close drifts part of the way back toward the base price. Every multiplier stays inside the
+//! configured price band.
+
+/// Multiplier for the unmodified base price.
+pub const BASE_BP: i64 = 10_000;
+/// The most one day of one-sided volume can move the multiplier.
+pub const MAX_DAILY_MOVE_BP: i64 = 2_000;
+/// Volume that counts as "normal" for a day. The pressure from a day's trades is
+/// `(bought - sold) / (bought + sold + LIQUIDITY_UNITS)`, so small trades barely move the price.
+pub const LIQUIDITY_UNITS: i64 = 50;
+/// Share of the distance back to the base price recovered at each day roll.
+pub const REVERSION_PCT: i64 = 10;
+/// Days replayed when rolling a price that has not been touched for a long time.
+pub const MAX_ROLL_DAYS: i64 = 30;
+/// Live-vs-open change (in basis points) below which the trend counts as flat.
+pub const TREND_THRESHOLD_BP: i64 = 100;
+
+pub const PRICE_BAND_MIN_CONFIG_KEY: &str = "price_band_min_pct";
+pub const PRICE_BAND_MAX_CONFIG_KEY: &str = "price_band_max_pct";
+pub const DEFAULT_BAND_MIN_PCT: i64 = 50;
+pub const DEFAULT_BAND_MAX_PCT: i64 = 200;
+/// Hard limits for what admins may configure.
+pub const BAND_MIN_PCT_LIMIT: i64 = 10;
+pub const BAND_MAX_PCT_LIMIT: i64 = 500;
+
+/// Lowest and highest multiplier, as percentages of the base price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceBand {
+    pub min_pct: i64,
+    pub max_pct: i64,
+}
+
+impl Default for PriceBand {
+    fn default() -> Self {
+        Self {
+            min_pct: DEFAULT_BAND_MIN_PCT,
+            max_pct: DEFAULT_BAND_MAX_PCT,
+        }
+    }
+}
+
+impl PriceBand {
+    /// Validates a band; `None` when it is inverted, excludes the base price or leaves the limits.
+    pub fn new(min_pct: i64, max_pct: i64) -> Option<Self> {
+        ((BAND_MIN_PCT_LIMIT..=100).contains(&min_pct)
+            && (100..=BAND_MAX_PCT_LIMIT).contains(&max_pct))
+        .then_some(Self { min_pct, max_pct })
+    }
+
+    /// Band from raw config values, falling back to the default for anything invalid.
+    pub fn parse(min: Option<&str>, max: Option<&str>) -> Self {
+        let num = |v: Option<&str>| v.and_then(|s| s.trim().parse::<i64>().ok());
+        let default = Self::default();
+        Self::new(
+            num(min).unwrap_or(default.min_pct),
+            num(max).unwrap_or(default.max_pct),
+        )
+        .unwrap_or(default)
+    }
+
+    pub fn clamp(&self, bp: i64) -> i64 {
+        bp.clamp(self.min_pct * 100, self.max_pct * 100)
+    }
+}
+
+/// Live multiplier: the day's open shifted by the day's buy/sell pressure.
+pub fn live_multiplier(open_bp: i64, bought: i64, sold: i64, band: PriceBand) -> i64 {
+    let (bought, sold) = (bought.max(0), sold.max(0));
+    let shift = MAX_DAILY_MOVE_BP * (bought - sold) / (bought + sold + LIQUIDITY_UNITS);
+    band.clamp(open_bp + shift)
+}
+
+/// Next day's open: the day's live close, partly reverted toward the base price.
+pub fn close_multiplier(open_bp: i64, bought: i64, sold: i64, band: PriceBand) -> i64 {
+    let live = live_multiplier(open_bp, bought, sold, band);
+    band.clamp(live + (BASE_BP - live) * REVERSION_PCT / 100)
+}
+
+/// Scales a base price by a multiplier. A priced item never drops below one coin.
+pub fn apply_multiplier(base: i64, bp: i64) -> i64 {
+    if base <= 0 {
+        return 0;
+    }
+    (base * bp / BASE_BP).max(1)
+}
+
+/// Total for `quantity` units traded while the multiplier moves from `before_bp` to `after_bp`.
+/// Charging the average of both ends means a large order pays for the move it causes.
+pub fn quote_total(base: i64, before_bp: i64, after_bp: i64, quantity: i64) -> i64 {
+    let before = apply_multiplier(base, before_bp);
+    let after = apply_multiplier(base, after_bp);
+    (before + after) * quantity.max(0) / 2
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Up,
+    Down,
+    Flat,
+}
+
+impl Trend {
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Trend::Up => "▲",
+            Trend::Down => "▼",
+            Trend::Flat => "▬",
+        }
+    }
+}
+
+/// An item's multiplier at the start of today and right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceState {
+    pub open_bp: i64,
+    pub live_bp: i64,
+}
+
+impl Default for PriceState {
+    fn default() -> Self {
+        Self {
+            open_bp: BASE_BP,
+            live_bp: BASE_BP,
+        }
+    }
+}
+
+impl PriceState {
+    pub fn trend(&self) -> Trend {
+        let delta = self.live_bp - self.open_bp;
+        if delta >= TREND_THRESHOLD_BP {
+            Trend::Up
+        } else if delta <= -TREND_THRESHOLD_BP {
+            Trend::Down
+        } else {
+            Trend::Flat
+        }
+    }
+
+    /// Arrow plus today's percentage move, e.g. `▲ +12%`.
+    pub fn trend_label(&self) -> String {
+        let pct = (self.live_bp - self.open_bp) * 100 / self.open_bp.max(1);
+        match self.trend() {
+            Trend::Flat => Trend::Flat.arrow().to_string(),
+            trend => format!("{} {:+}%", trend.arrow(), pct),
+        }
+    }
+
+    pub fn price(&self, base: i64) -> i64 {
+        apply_multiplier(base, self.live_bp)
+    }
+}
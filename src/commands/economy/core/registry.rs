@@ -0,0 +1,155 @@
+//! The in-memory item registry.
+//!
+//! Item definitions live in the `items` table and are loaded into a process-wide registry at
+//! startup (`/adminutil reloaditems` swaps in a fresh copy without a restart). Shop, sell, give,
+//! crafting and node rewards look items up here by id or name, so adding an item only needs a
+//! row in the table. The `Item` enum remains as named handles for ids the game logic refers to
+//! directly and as the fallback content when the table cannot be read.
+
+use super::item::{Item, ItemCategory, Rarity};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDef {
+    pub id: i32,
+    /// Canonical lookup key (e.g. `goldenfish`), also used as select-menu value.
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    pub emoji: String,
+    pub category: ItemCategory,
+    pub rarity: Rarity,
+    pub is_sellable: bool,
+    pub is_tradeable: bool,
+    pub buy_price: Option<i64>,
+    pub sell_price: Option<i64>,
+    pub aliases: Vec<String>,
+}
+
+impl ItemDef {
+    /// Definition compiled into the binary for a well-known item.
+    pub fn builtin(item: Item) -> Self {
+        let props = item.properties();
+        Self {
+            id: item.id(),
+            key: item.to_string(),
+            name: props.display_name.to_string(),
+            description: props.description.to_string(),
+            emoji: props.emoji.to_string(),
+            category: props.category,
+            rarity: props.rarity,
+            is_sellable: props.is_sellable,
+            is_tradeable: props.is_tradeable,
+            buy_price: props.buy_price,
+            sell_price: props.sell_price,
+            aliases: item.aliases().iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    pub fn is_purchasable(&self) -> bool {
+        self.buy_price.is_some()
+    }
+}
+
+/// Lower-cases and drops everything but letters and digits, so `Golden Fish`, `golden_fish`
+/// and `goldenfish` all resolve to the same item.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct ItemRegistry {
+    items: Vec<ItemDef>,
+    by_id: HashMap<i32, usize>,
+    by_name: HashMap<String, usize>,
+}
+
+impl ItemRegistry {
+    /// Builds a registry ordered by item id. Keys win over display names, which win over
+    /// aliases; the first item to claim a name keeps it.
+    pub fn new(mut items: Vec<ItemDef>) -> Self {
+        items.sort_by_key(|d| d.id);
+        items.dedup_by_key(|d| d.id);
+        let by_id = items.iter().enumerate().map(|(i, d)| (d.id, i)).collect();
+        let mut by_name = HashMap::new();
+        for pass in 0..3 {
+            for (idx, def) in items.iter().enumerate() {
+                let names: Vec<&str> = match pass {
+                    0 => vec![def.key.as_str()],
+                    1 => vec![def.name.as_str()],
+                    _ => def.aliases.iter().map(String::as_str).collect(),
+                };
+                for name in names {
+                    by_name.entry(normalize(name)).or_insert(idx);
+                }
+            }
+        }
+        Self {
+            items,
+            by_id,
+            by_name,
+        }
+    }
+
+    /// Registry built from the compiled-in `Item` definitions.
+    pub fn builtin() -> Self {
+        Self::new(Item::ALL.into_iter().map(ItemDef::builtin).collect())
+    }
+
+    pub fn get(&self, id: i32) -> Option<&ItemDef> {
+        self.by_id.get(&id).map(|&i| &self.items[i])
+    }
+
+    /// Finds an item by key, display name or alias (case, spaces and punctuation ignored).
+    pub fn resolve(&self, name: &str) -> Option<&ItemDef> {
+        self.by_name.get(&normalize(name)).map(|&i| &self.items[i])
+    }
+
+    /// Display name for an id, falling back to `Item #id` for unknown ids.
+    pub fn name_of(&self, id: i32) -> String {
+        self.get(id)
+            .map(|d| d.name.clone())
+            .unwrap_or_else(|| format!("Item #{}", id))
+    }
+
+    /// Items the shop sells in the given category, in id order.
+    pub fn purchasable(&self, category: ItemCategory) -> Vec<&ItemDef> {
+        self.items
+            .iter()
+            .filter(|d| d.category == category && d.is_purchasable())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+static REGISTRY: LazyLock<RwLock<Arc<ItemRegistry>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(ItemRegistry::builtin())));
+
+/// Current registry snapshot. Cheap to call; holders keep their snapshot across a reload.
+pub fn registry() -> Arc<ItemRegistry> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Replaces the process-wide registry, returning the previous item count.
+pub fn install(registry: ItemRegistry) -> usize {
+    let mut guard = REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let previous = guard.len();
+    *guard = Arc::new(registry);
+    previous
+}
@@ -0,0 +1,125 @@
+//! Pure gambling rules: totals across games, self-set loss limits and cooldowns.
+//!
+//! A daily loss limit blocks sitting down at a table (or accepting a bet) once the player's net
+//! result for the UTC day reaches minus the limit. Tightening a limit applies at once; raising or
+//! removing one only takes effect a day later, so it can't be undone in the heat of a session.
+//! A cooldown blocks gambling outright until it runs out and can't be shortened.
+
+use crate::database::models::{GamblingLimits, GamblingStat};
+use chrono::{DateTime, Duration, Utc};
+
+/// Hours before a raised or removed loss limit takes effect.
+pub const LIMIT_RAISE_DELAY_HOURS: i64 = 24;
+pub const MAX_COOLDOWN_DAYS: i64 = 30;
+
+/// When a requested loss-limit change applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitChange {
+    Now,
+    Delayed,
+}
+
+/// Stats summed over every game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GamblingTotals {
+    pub hands_played: i64,
+    pub hands_won: i64,
+    pub hands_lost: i64,
+    pub net: i64,
+    pub biggest_win: i64,
+}
+
+pub fn totals(stats: &[GamblingStat]) -> GamblingTotals {
+    stats
+        .iter()
+        .fold(GamblingTotals::default(), |acc, s| GamblingTotals {
+            hands_played: acc.hands_played + s.hands_played,
+            hands_won: acc.hands_won + s.hands_won,
+            hands_lost: acc.hands_lost + s.hands_lost,
+            net: acc.net + s.net,
+            biggest_win: acc.biggest_win.max(s.biggest_win),
+        })
+}
+
+/// The loss limit in force at `now`, taking a queued change into account once it is due.
+pub fn effective_loss_limit(limits: &GamblingLimits, now: DateTime<Utc>) -> Option<i64> {
+    match limits.pending_from {
+        Some(from) if from <= now => limits.pending_loss_limit,
+        _ => limits.daily_loss_limit,
+    }
+}
+
+/// Whether moving from `current` to `requested` tightens the limit (applied now) or loosens it
+/// (applied after `LIMIT_RAISE_DELAY_HOURS`). `None` means no limit.
+pub fn limit_change(current: Option<i64>, requested: Option<i64>) -> LimitChange {
+    match (current, requested) {
+        (None, _) => LimitChange::Now,
+        (Some(current), Some(requested)) if requested <= current => LimitChange::Now,
+        _ => LimitChange::Delayed,
+    }
+}
+
+/// Checks a player's limits before they sit down to gamble. `net_today` is their net result for
+/// the current day.
+pub fn check_play(
+    limits: &GamblingLimits,
+    net_today: i64,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    if let Some(until) = limits.cooldown_until.filter(|until| *until > now) {
+        return Err(format!(
+            "You're on a gambling cooldown until <t:{}:f>.",
+            until.timestamp()
+        ));
+    }
+    if let Some(limit) = effective_loss_limit(limits, now)
+        && -net_today >= limit
+    {
+        return Err(format!(
+            "You've reached your daily loss limit of 💰{}. Come back tomorrow.",
+            limit
+        ));
+    }
+    Ok(())
+}
+
+/// Parses a loss limit: a positive amount, or `off` to remove it.
+pub fn parse_limit(raw: &str) -> Result<Option<i64>, String> {
+    let raw = raw.trim().to_lowercase();
+    if raw == "off" || raw == "none" {
+        return Ok(None);
+    }
+    raw.replace(',', "")
+        .parse::<i64>()
+        .ok()
+        .filter(|v| *v > 0)
+        .map(Some)
+        .ok_or_else(|| {
+            format!(
+                "'{}' is not a valid loss limit. Use an amount or `off`.",
+                raw
+            )
+        })
+}
+
+/// Parses a cooldown such as `12h` or `3d`; a bare number is hours.
+pub fn parse_cooldown(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim().to_lowercase();
+    let (number, unit) = match raw.strip_suffix('d') {
+        Some(days) => (days, Duration::days(1)),
+        None => (raw.strip_suffix('h').unwrap_or(&raw), Duration::hours(1)),
+    };
+    let length = number
+        .parse::<i32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .map(|n| unit * n)
+        .ok_or_else(|| format!("'{}' is not a valid cooldown. Try `12h` or `3d`.", raw))?;
+    if length > Duration::days(MAX_COOLDOWN_DAYS) {
+        return Err(format!(
+            "Cooldowns can last at most {} days.",
+            MAX_COOLDOWN_DAYS
+        ));
+    }
+    Ok(length)
+}
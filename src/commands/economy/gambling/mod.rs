@@ -0,0 +1,6 @@
+//! Implements `/gambling`: win/loss statistics across the casino games, plus the daily loss
+//! limits and cooldowns players can set on themselves.
+
+pub mod logic;
+pub mod run;
+pub mod ui;
@@ -0,0 +1,216 @@
+//! Handles the command logic for `/gambling` and `$gambling`.
+
+use super::logic::{LIMIT_RAISE_DELAY_HOURS, LimitChange, parse_cooldown, parse_limit};
+use super::ui::{OwnLimits, create_stats_embed};
+use crate::AppState;
+use crate::database::gambling;
+use chrono::Duration;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage, EditInteractionResponse,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::user::User;
+use serenity::prelude::*;
+use sqlx::PgPool;
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("gambling")
+        .description("Gambling stats, loss limits and cooldowns.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "stats",
+                "Show wins and losses across the casino games",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "Whose stats to show")
+                    .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "limit",
+                "Set a daily loss limit",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "amount",
+                    "Most you can lose per day, or 'off'",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "cooldown",
+                "Take a break from gambling",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "length",
+                    "How long, e.g. 12h or 3d",
+                )
+                .required(true),
+            ),
+        )
+}
+
+/// A parsed `/gambling` or `$gambling` request.
+enum GamblingAction {
+    Stats,
+    Limit(Option<i64>),
+    Cooldown(Duration),
+}
+
+const USAGE: &str = "Usage: `$gambling [stats] [@user]`, `$gambling limit <amount|off>`, `$gambling cooldown <12h|3d>`";
+
+/// Builds the stats embed. Limits are only shown on your own stats.
+async fn build_stats(
+    db: &PgPool,
+    viewer: &User,
+    target: Option<User>,
+) -> Result<CreateEmbed, String> {
+    let target = target.unwrap_or_else(|| viewer.clone());
+    let stats = gambling::get_stats(db, target.id)
+        .await
+        .map_err(|_| "Could not load gambling stats.".to_string())?;
+    let own = if target.id == viewer.id {
+        let limits = gambling::get_limits(db, viewer.id)
+            .await
+            .map_err(|_| "Could not load your gambling limits.".to_string())?;
+        let net_today = gambling::net_today(db, viewer.id)
+            .await
+            .map_err(|_| "Could not load your gambling limits.".to_string())?;
+        Some(OwnLimits { limits, net_today })
+    } else {
+        None
+    };
+    Ok(create_stats_embed(&target.name, &stats, own.as_ref()))
+}
+
+/// Runs a limit or cooldown change and returns the confirmation or error line.
+async fn run_action(db: &PgPool, user: &User, action: GamblingAction) -> String {
+    let result = match action {
+        GamblingAction::Stats => return String::new(),
+        GamblingAction::Limit(limit) => {
+            gambling::set_loss_limit(db, user.id, limit)
+                .await
+                .map(|change| match (change, limit) {
+                    (LimitChange::Now, Some(limit)) => {
+                        format!("🛑 Your daily loss limit is now 💰{}.", limit)
+                    }
+                    (LimitChange::Now, None) => {
+                        "Your daily loss limit has been removed.".to_string()
+                    }
+                    (LimitChange::Delayed, Some(limit)) => format!(
+                        "⏳ Your daily loss limit will rise to 💰{} in {} hours.",
+                        limit, LIMIT_RAISE_DELAY_HOURS
+                    ),
+                    (LimitChange::Delayed, None) => format!(
+                        "⏳ Your daily loss limit will be removed in {} hours.",
+                        LIMIT_RAISE_DELAY_HOURS
+                    ),
+                })
+        }
+        GamblingAction::Cooldown(length) => gambling::start_cooldown(db, user.id, length)
+            .await
+            .map(|until| {
+                format!(
+                    "⏸️ You're taking a break from gambling until <t:{}:f>.",
+                    until.timestamp()
+                )
+            }),
+    };
+    result.unwrap_or_else(|e| e)
+}
+
+/// The `user` option of `/gambling stats`, if given.
+fn slash_target(interaction: &CommandInteraction) -> Option<User> {
+    let CommandDataOptionValue::SubCommand(nested) = &interaction.data.options.first()?.value
+    else {
+        return None;
+    };
+    let id = nested
+        .iter()
+        .find(|o| o.name == "user")?
+        .value
+        .as_user_id()?;
+    interaction.data.resolved.users.get(&id).cloned()
+}
+
+fn parse_slash(interaction: &CommandInteraction) -> Result<GamblingAction, String> {
+    let Some(sub) = interaction.data.options.first() else {
+        return Ok(GamblingAction::Stats);
+    };
+    let CommandDataOptionValue::SubCommand(nested) = &sub.value else {
+        return Err("Invalid gambling command.".to_string());
+    };
+    let value = |name: &str| nested.iter().find(|o| o.name == name).map(|o| &o.value);
+    match sub.name.as_str() {
+        "stats" => Ok(GamblingAction::Stats),
+        "limit" => parse_action("limit", value("amount").and_then(|v| v.as_str())),
+        "cooldown" => parse_action("cooldown", value("length").and_then(|v| v.as_str())),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// `$gambling [stats] | limit <amount|off> | cooldown <length>`
+fn parse_action(sub: &str, arg: Option<&str>) -> Result<GamblingAction, String> {
+    match (sub, arg) {
+        ("stats", _) => Ok(GamblingAction::Stats),
+        ("limit", Some(raw)) => Ok(GamblingAction::Limit(parse_limit(raw)?)),
+        ("cooldown", Some(raw)) => Ok(GamblingAction::Cooldown(parse_cooldown(raw)?)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction.defer_ephemeral(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let builder = match parse_slash(interaction) {
+        Ok(GamblingAction::Stats) => {
+            match build_stats(db, &interaction.user, slash_target(interaction)).await {
+                Ok(embed) => EditInteractionResponse::new().embed(embed),
+                Err(e) => EditInteractionResponse::new().content(e),
+            }
+        }
+        Ok(action) => {
+            EditInteractionResponse::new().content(run_action(db, &interaction.user, action).await)
+        }
+        Err(e) => EditInteractionResponse::new().content(e),
+    };
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let sub = args
+        .first()
+        .filter(|arg| !arg.starts_with("<@"))
+        .map(|s| s.to_lowercase());
+    let builder = match parse_action(sub.as_deref().unwrap_or("stats"), args.get(1).copied()) {
+        Ok(GamblingAction::Stats) => {
+            match build_stats(db, &msg.author, msg.mentions.first().cloned()).await {
+                Ok(embed) => CreateMessage::new().embed(embed),
+                Err(e) => CreateMessage::new().content(e),
+            }
+        }
+        Ok(action) => CreateMessage::new().content(run_action(db, &msg.author, action).await),
+        Err(e) => CreateMessage::new().content(e),
+    };
+    msg.channel_id
+        .send_message(&ctx.http, builder.reference_message(msg))
+        .await
+        .ok();
+}
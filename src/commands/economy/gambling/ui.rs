@@ -0,0 +1,96 @@
+//! Handles embed creation for the `/gambling` command.
+
+use super::logic::{effective_loss_limit, totals};
+use crate::database::models::{GambleGame, GamblingLimits, GamblingStat};
+use chrono::Utc;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+fn game_label(game: GambleGame) -> &'static str {
+    match game {
+        GambleGame::Blackjack => "🃏 Blackjack",
+        GambleGame::Poker => "♦️ Poker",
+        GambleGame::Holdem => "♠️ Hold'em",
+        GambleGame::Rps => "✊ Rock Paper Scissors",
+        GambleGame::Tavern => "🍺 Tavern Games",
+        GambleGame::SideBet => "🎲 Side Bets",
+    }
+}
+
+fn signed(amount: i64) -> String {
+    if amount > 0 {
+        format!("+{}", amount)
+    } else {
+        amount.to_string()
+    }
+}
+
+/// Your own limits, shown only when viewing your own stats.
+pub struct OwnLimits {
+    pub limits: GamblingLimits,
+    pub net_today: i64,
+}
+
+pub fn create_stats_embed(
+    name: &str,
+    stats: &[GamblingStat],
+    own: Option<&OwnLimits>,
+) -> CreateEmbed {
+    let total = totals(stats);
+    let mut embed = CreateEmbed::new()
+        .title(format!("🎲 Gambling Stats — {}", name))
+        .color(0x9B59B6);
+    if stats.is_empty() {
+        embed = embed.description("No hands played yet.");
+    } else {
+        embed = embed
+            .field("Hands", total.hands_played.to_string(), true)
+            .field(
+                "Won / Lost",
+                format!("{} / {}", total.hands_won, total.hands_lost),
+                true,
+            )
+            .field("Net", format!("💰{}", signed(total.net)), true)
+            .field("Biggest Win", format!("💰{}", total.biggest_win), true);
+        let breakdown = stats
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} — {} hands ({}W/{}L) · net 💰{} · best 💰{}",
+                    game_label(s.game),
+                    s.hands_played,
+                    s.hands_won,
+                    s.hands_lost,
+                    signed(s.net),
+                    s.biggest_win
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("By Game", breakdown, false);
+    }
+
+    if let Some(own) = own {
+        let now = Utc::now();
+        let mut lines = vec![format!("Today: 💰{}", signed(own.net_today))];
+        match effective_loss_limit(&own.limits, now) {
+            Some(limit) => lines.push(format!("Daily loss limit: 💰{}", limit)),
+            None => lines.push("Daily loss limit: none".to_string()),
+        }
+        if let Some(from) = own.limits.pending_from.filter(|from| *from > now) {
+            let change = match own.limits.pending_loss_limit {
+                Some(limit) => format!("💰{}", limit),
+                None => "no limit".to_string(),
+            };
+            lines.push(format!("Changes to {} <t:{}:R>", change, from.timestamp()));
+        }
+        if let Some(until) = own.limits.cooldown_until.filter(|until| *until > now) {
+            lines.push(format!("⏸️ Cooldown until <t:{}:f>", until.timestamp()));
+        }
+        embed = embed
+            .field("Limits", lines.join("\n"), false)
+            .footer(CreateEmbedFooter::new(
+                "Set a limit with /gambling limit or take a break with /gambling cooldown.",
+            ));
+    }
+    embed
+}
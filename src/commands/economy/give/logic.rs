@@ -1,7 +1,8 @@
 //! Contains the core logic for the `/give` command.
 
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::ItemDef;
 use crate::database;
+use crate::database::models::LedgerReason;
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
 use sqlx::PgPool;
@@ -10,7 +11,7 @@ pub async fn give_item(
     pool: &PgPool,
     giver: &User,
     receiver: &User,
-    item: Item,
+    item: &ItemDef,
     quantity: i64,
 ) -> CreateEmbed {
     if giver.id == receiver.id {
@@ -32,10 +33,9 @@ pub async fn give_item(
             .color(0xFF0000);
     }
 
-    let properties = item.properties();
     // (✓) ALIVE: The `is_tradeable` flag is now being used for game logic.
-    if !properties.is_tradeable {
-        let err_msg = format!("The item '{}' cannot be traded.", properties.display_name);
+    if !item.is_tradeable {
+        let err_msg = format!("The item '{}' cannot be traded.", item.name);
         return CreateEmbed::new()
             .title("Trade Error")
             .description(err_msg)
@@ -53,13 +53,13 @@ pub async fn give_item(
     };
 
     // Check if the giver has enough items to give.
-    match database::economy::get_inventory_item(&mut tx, giver.id, item).await {
+    match database::economy::get_inventory_item(&mut tx, giver.id, item.id).await {
         Ok(Some(item_in_inv)) if item_in_inv.quantity >= quantity => (),
         _ => {
             tx.rollback().await.ok();
             let err_msg = format!(
                 "You do not have enough **{}** to give. You need `{}` but only have the required amount.",
-                properties.display_name, quantity
+                item.name, quantity
             );
             return CreateEmbed::new()
                 .title("Not Enough Items")
@@ -69,12 +69,24 @@ pub async fn give_item(
     };
 
     // Perform the transaction.
-    if database::economy::add_to_inventory(&mut tx, giver.id, item, -quantity)
+    if database::economy::add_to_inventory(
+        &mut tx,
+        giver.id,
+        item.id,
+        -quantity,
+        LedgerReason::Give.with_ref("user", receiver.id),
+    )
+    .await
+    .is_err()
+        || database::economy::add_to_inventory(
+            &mut tx,
+            receiver.id,
+            item.id,
+            quantity,
+            LedgerReason::Give.with_ref("user", giver.id),
+        )
         .await
         .is_err()
-        || database::economy::add_to_inventory(&mut tx, receiver.id, item, quantity)
-            .await
-            .is_err()
     {
         tx.rollback().await.ok();
         return CreateEmbed::new()
@@ -94,7 +106,7 @@ pub async fn give_item(
         .title("Trade Successful!")
         .description(format!(
             "You gave **`{}` {}** to **{}**.",
-            quantity, properties.display_name, receiver.name
+            quantity, item.name, receiver.name
         ))
         .color(0x00FF00)
 }
@@ -2,14 +2,13 @@
 
 use super::logic::give_item;
 use crate::AppState;
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, CreateMessage,
 };
 use serenity::model::application::{CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
 use serenity::prelude::*;
-use std::str::FromStr;
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("give")
@@ -26,16 +25,9 @@ pub fn register() -> CreateCommand {
             CreateCommandOption::new(
                 CommandOptionType::String,
                 "item",
-                "The item you want to give",
+                "The item you want to give (e.g. fish, geode, xp booster)",
             )
-            .required(true)
-            .add_string_choice("Fish", "fish")
-            .add_string_choice("Ore", "ore")
-            .add_string_choice("Gem", "gem")
-            .add_string_choice("Golden Fish", "goldenfish")
-            .add_string_choice("Large Geode", "largegeode")
-            .add_string_choice("Ancient Relic", "ancientrelic")
-            .add_string_choice("XP Booster", "xpbooster"),
+            .required(true),
         )
         .add_option(
             CreateCommandOption::new(
@@ -100,7 +92,7 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .and_then(|opt| opt.value.as_i64())
         .unwrap_or(1);
 
-    let Ok(item) = Item::from_str(item_str) else {
+    let Some(item) = registry().resolve(item_str).cloned() else {
         interaction
             .edit_response(
                 &ctx.http,
@@ -111,7 +103,7 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         return;
     };
 
-    let embed = give_item(&pool, &interaction.user, &receiver_user, item, quantity).await;
+    let embed = give_item(&pool, &interaction.user, &receiver_user, &item, quantity).await;
     let builder = CreateInteractionResponseFollowup::new().embed(embed);
     interaction.create_followup(&ctx.http, builder).await.ok();
 }
@@ -148,19 +140,16 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         }
     };
 
-    let item = match Item::from_str(item_name) {
-        Ok(item) => item,
-        Err(_) => {
-            msg.reply(ctx, &format!("'{}' is not a valid item.", item_name))
-                .await
-                .ok();
-            return;
-        }
+    let Some(item) = registry().resolve(item_name).cloned() else {
+        msg.reply(ctx, &format!("'{}' is not a valid item.", item_name))
+            .await
+            .ok();
+        return;
     };
 
     let quantity = args.get(2).and_then(|q| q.parse::<i64>().ok()).unwrap_or(1);
 
-    let embed = give_item(&pool, &msg.author, receiver, item, quantity).await;
+    let embed = give_item(&pool, &msg.author, receiver, &item, quantity).await;
     let builder = CreateMessage::new().embed(embed).reference_message(msg);
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
@@ -1,10 +1,10 @@
 //! Handles all UI and embed creation for the `/inventory` command.
 
-use crate::commands::economy::core::item::{Item, Rarity};
+use crate::commands::economy::core::item::Rarity;
+use crate::commands::economy::core::registry::registry;
 use crate::database;
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
-use std::str::FromStr;
 
 pub fn create_inventory_embed(
     user: &User,
@@ -23,24 +23,24 @@ pub fn create_inventory_embed(
             } else {
                 // (✓) ALIVE: The Rarity::color() method is now used here.
                 // Find the highest rarity item in the inventory to set the embed color.
+                let items = registry();
                 let highest_rarity = inventory
                     .iter()
-                    .filter_map(|db_item| Item::from_str(&db_item.name).ok())
-                    .map(|item_enum| item_enum.properties().rarity)
+                    .filter_map(|db_item| items.resolve(&db_item.name))
+                    .map(|def| def.rarity)
                     .max()
                     .unwrap_or(Rarity::Common);
 
                 let items_list = inventory
                     .iter()
                     .map(|db_item| {
-                        if let Ok(item_enum) = Item::from_str(&db_item.name) {
-                            let props = item_enum.properties();
+                        if let Some(def) = items.resolve(&db_item.name) {
                             format!(
                                 "{} **{}** `x{}`\n*{} Rarity*",
-                                props.emoji,
+                                def.emoji,
                                 db_item.name,
                                 db_item.quantity,
-                                props.rarity.as_str()
+                                def.rarity.as_str()
                             )
                         } else {
                             format!(
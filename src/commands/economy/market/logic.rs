@@ -0,0 +1,44 @@
+//! Pure marketplace rules: tax, bid increments, listing limits and durations.
+
+/// `bot_config` key holding the market tax percentage (see `/config market_tax`).
+pub const MARKET_TAX_CONFIG_KEY: &str = "market_tax_pct";
+pub const DEFAULT_MARKET_TAX_PCT: i64 = 5;
+pub const MAX_MARKET_TAX_PCT: i64 = 50;
+/// How long a fixed-price listing stays up before the items are returned.
+pub const LISTING_DURATION_HOURS: i64 = 72;
+pub const MIN_AUCTION_HOURS: i64 = 1;
+pub const MAX_AUCTION_HOURS: i64 = 72;
+/// Each new bid must beat the current one by at least this many percent (minimum 1 coin).
+pub const MIN_BID_INCREMENT_PCT: i64 = 5;
+pub const MAX_ACTIVE_LISTINGS: i64 = 10;
+pub const LISTINGS_PER_PAGE: i64 = 5;
+
+/// Coins removed from the economy when a sale of `amount` settles.
+pub fn market_tax(amount: i64, tax_pct: i64) -> i64 {
+    amount.max(0) * tax_pct.clamp(0, MAX_MARKET_TAX_PCT) / 100
+}
+
+/// What the seller receives for a sale of `amount`.
+pub fn seller_proceeds(amount: i64, tax_pct: i64) -> i64 {
+    amount - market_tax(amount, tax_pct)
+}
+
+/// Lowest acceptable bid given the starting price and the current high bid.
+pub fn min_next_bid(start_price: i64, high_bid: Option<i64>) -> i64 {
+    match high_bid {
+        None => start_price,
+        Some(bid) => bid + (bid * MIN_BID_INCREMENT_PCT / 100).max(1),
+    }
+}
+
+/// Parses a configured tax value, falling back to the default when missing or invalid.
+pub fn parse_tax_pct(raw: Option<&str>) -> i64 {
+    raw.and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|p| (0..=MAX_MARKET_TAX_PCT).contains(p))
+        .unwrap_or(DEFAULT_MARKET_TAX_PCT)
+}
+
+/// Number of browse pages for `total` listings (at least one, so an empty market still renders).
+pub fn page_count(total: i64) -> i64 {
+    ((total.max(0) + LISTINGS_PER_PAGE - 1) / LISTINGS_PER_PAGE).max(1)
+}
@@ -0,0 +1,5 @@
+//! Implements the `/market` player marketplace (fixed-price listings and timed auctions).
+
+pub mod logic;
+pub mod run;
+pub mod ui;
@@ -0,0 +1,292 @@
+//! Handles the command logic for `/market` and `$market`.
+
+use super::logic::{MAX_AUCTION_HOURS, MIN_AUCTION_HOURS, page_count};
+use super::ui::create_browse_view;
+use crate::AppState;
+use crate::commands::economy::core::registry::registry;
+use crate::database::market::{self, PurchaseOutcome};
+use serenity::builder::{
+    CreateActionRow, CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage,
+    EditInteractionResponse,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::id::UserId;
+use serenity::prelude::*;
+use sqlx::PgPool;
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("market")
+        .description("Buy and sell items with other players.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "browse",
+                "Browse active listings",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "page", "Page number")
+                    .required(false)
+                    .min_int_value(1),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "sell",
+                "List a tradeable item for sale or auction",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "item", "Item to list")
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "quantity", "How many")
+                    .required(true)
+                    .min_int_value(1),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "price",
+                    "Buyout price, or starting bid for auctions",
+                )
+                .required(true)
+                .min_int_value(1),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "auction_hours",
+                    "Run as a timed auction for this many hours",
+                )
+                .required(false)
+                .min_int_value(MIN_AUCTION_HOURS as u64)
+                .max_int_value(MAX_AUCTION_HOURS as u64),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "buy",
+                "Buy a listing or bid on an auction",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "listing", "Listing number")
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "bid",
+                    "Bid amount (auctions only; defaults to the minimum bid)",
+                )
+                .required(false)
+                .min_int_value(1),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "cancel",
+                "Cancel one of your listings and get the items back",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "listing", "Listing number")
+                    .required(true),
+            ),
+        )
+}
+
+/// A parsed `/market` or `$market` request.
+enum MarketAction {
+    Browse(i64),
+    Sell {
+        item: String,
+        quantity: i64,
+        price: i64,
+        auction_hours: Option<i64>,
+    },
+    Buy {
+        listing_id: i32,
+        bid: Option<i64>,
+    },
+    Cancel(i32),
+}
+
+/// Settles expired listings, then renders the requested browse page (clamped to the last page).
+pub async fn build_browse_view(db: &PgPool, page: i64) -> (CreateEmbed, Vec<CreateActionRow>) {
+    if let Err(e) = market::settle_expired_listings(db).await {
+        tracing::warn!(target: "market", error = %e, "Failed to settle expired listings");
+    }
+    let total = market::count_active_listings(db).await.unwrap_or(0);
+    let page = page.clamp(0, page_count(total) - 1);
+    let listings = market::list_active_listings(db, page)
+        .await
+        .unwrap_or_default();
+    let tax_pct = market::get_market_tax_pct(db).await;
+    create_browse_view(&listings, page, total, tax_pct)
+}
+
+/// Runs a write action and returns the confirmation or error line.
+async fn run_action(db: &PgPool, user_id: UserId, action: MarketAction) -> String {
+    if let Err(e) = market::settle_expired_listings(db).await {
+        tracing::warn!(target: "market", error = %e, "Failed to settle expired listings");
+    }
+    match action {
+        MarketAction::Browse(_) => String::new(),
+        MarketAction::Sell {
+            item,
+            quantity,
+            price,
+            auction_hours,
+        } => {
+            let Some(def) = registry().resolve(&item).cloned() else {
+                return format!("'{}' is not a known item.", item);
+            };
+            match market::create_listing(db, user_id, &def, quantity, price, auction_hours).await {
+                Ok(id) => match auction_hours {
+                    Some(hours) => format!(
+                        "🔨 Listed `{}` **{}** as auction **#{}** (starting bid 💰{}, {}h).",
+                        quantity, def.name, id, price, hours
+                    ),
+                    None => format!(
+                        "🏷️ Listed `{}` **{}** as **#{}** for 💰{}.",
+                        quantity, def.name, id, price
+                    ),
+                },
+                Err(e) => e,
+            }
+        }
+        MarketAction::Buy { listing_id, bid } => {
+            match market::buy_listing(db, user_id, listing_id, bid).await {
+                Ok(PurchaseOutcome::Bought {
+                    item_name,
+                    quantity,
+                    price,
+                }) => format!(
+                    "✅ You bought `{}` **{}** for 💰{}.",
+                    quantity, item_name, price
+                ),
+                Ok(PurchaseOutcome::BidPlaced {
+                    item_name,
+                    amount,
+                    expires_at,
+                }) => format!(
+                    "🔨 You are the top bidder on **{}** with 💰{} (held until the auction ends <t:{}:R>).",
+                    item_name,
+                    amount,
+                    expires_at.timestamp()
+                ),
+                Err(e) => e,
+            }
+        }
+        MarketAction::Cancel(listing_id) => {
+            match market::cancel_listing(db, user_id, listing_id).await {
+                Ok(listing) => format!(
+                    "↩️ Cancelled listing #{}; `{}` **{}** returned to your inventory.",
+                    listing.listing_id,
+                    listing.quantity,
+                    registry().name_of(listing.item_id)
+                ),
+                Err(e) => e,
+            }
+        }
+    }
+}
+
+fn parse_slash(interaction: &CommandInteraction) -> Option<MarketAction> {
+    let sub = interaction.data.options.first()?;
+    let CommandDataOptionValue::SubCommand(nested) = &sub.value else {
+        return None;
+    };
+    let int = |name: &str| {
+        nested
+            .iter()
+            .find(|o| o.name == name)
+            .and_then(|o| o.value.as_i64())
+    };
+    match sub.name.as_str() {
+        "browse" => Some(MarketAction::Browse(int("page").unwrap_or(1) - 1)),
+        "sell" => Some(MarketAction::Sell {
+            item: nested
+                .iter()
+                .find(|o| o.name == "item")
+                .and_then(|o| o.value.as_str())?
+                .to_string(),
+            quantity: int("quantity")?,
+            price: int("price")?,
+            auction_hours: int("auction_hours"),
+        }),
+        "buy" => Some(MarketAction::Buy {
+            listing_id: int("listing")? as i32,
+            bid: int("bid"),
+        }),
+        "cancel" => Some(MarketAction::Cancel(int("listing")? as i32)),
+        _ => None,
+    }
+}
+
+/// `$market [browse [page]] | sell <item> <qty> <price> [auction_hours] | buy <id> [bid] | cancel <id>`
+fn parse_prefix(args: &[&str]) -> Option<MarketAction> {
+    let num = |i: usize| {
+        args.get(i)
+            .and_then(|v| v.trim_start_matches('#').parse::<i64>().ok())
+    };
+    match args.first().map(|s| s.to_lowercase()).as_deref() {
+        None | Some("browse") => Some(MarketAction::Browse(num(1).unwrap_or(1) - 1)),
+        Some("sell") => Some(MarketAction::Sell {
+            item: args.get(1)?.to_string(),
+            quantity: num(2)?,
+            price: num(3)?,
+            auction_hours: num(4),
+        }),
+        Some("buy") => Some(MarketAction::Buy {
+            listing_id: num(1)? as i32,
+            bid: num(2),
+        }),
+        Some("cancel") => Some(MarketAction::Cancel(num(1)? as i32)),
+        _ => None,
+    }
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction.defer_ephemeral(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let builder = match parse_slash(interaction) {
+        Some(MarketAction::Browse(page)) => {
+            let (embed, components) = build_browse_view(db, page).await;
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+        }
+        Some(action) => EditInteractionResponse::new()
+            .content(run_action(db, interaction.user.id, action).await),
+        None => EditInteractionResponse::new().content("Invalid market command."),
+    };
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let builder = match parse_prefix(&args) {
+        Some(MarketAction::Browse(page)) => {
+            let (embed, components) = build_browse_view(db, page).await;
+            CreateMessage::new().embed(embed).components(components)
+        }
+        Some(action) => CreateMessage::new().content(run_action(db, msg.author.id, action).await),
+        None => CreateMessage::new().content(
+            "Usage: `$market browse [page]`, `$market sell <item> <qty> <price> [auction_hours]`, `$market buy <listing> [bid]`, `$market cancel <listing>`",
+        ),
+    };
+    msg.channel_id
+        .send_message(&ctx.http, builder.reference_message(msg))
+        .await
+        .ok();
+}
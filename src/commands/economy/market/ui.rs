@@ -0,0 +1,71 @@
+//! Handles embed and component creation for the `/market` command.
+
+use super::logic::page_count;
+use crate::commands::economy::core::registry::registry;
+use crate::database::market::MarketListing;
+use crate::ui::buttons::Btn;
+use serenity::builder::{CreateActionRow, CreateEmbed, CreateEmbedFooter};
+
+/// Browse view for one page of active listings. `page` is 0-based.
+pub fn create_browse_view(
+    listings: &[MarketListing],
+    page: i64,
+    total: i64,
+    tax_pct: i64,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let pages = page_count(total);
+    let items = registry();
+    let mut embed = CreateEmbed::new()
+        .title("🏪 Marketplace")
+        .description(format!(
+            "Buy with `/market buy <listing>`; auctions take an optional `bid`. Sellers pay a **{}%** market tax on each sale.",
+            tax_pct
+        ))
+        .color(0xE67E22)
+        .footer(CreateEmbedFooter::new(format!(
+            "Page {}/{} · {} active listing(s)",
+            page + 1,
+            pages,
+            total
+        )));
+    if listings.is_empty() {
+        embed = embed.field("No listings", "List an item with `/market sell`.", false);
+    }
+    for listing in listings {
+        let (emoji, name) = items
+            .get(listing.item_id)
+            .map(|d| (d.emoji.clone(), d.name.clone()))
+            .unwrap_or_else(|| ("❔".to_string(), items.name_of(listing.item_id)));
+        let price_line = if listing.is_auction {
+            match listing.high_bid {
+                Some(bid) => format!(
+                    "🔨 Auction · top bid **💰{}** · next bid ≥ 💰{}",
+                    bid,
+                    listing.min_next_bid()
+                ),
+                None => format!("🔨 Auction · starting bid **💰{}**", listing.price),
+            }
+        } else {
+            format!("💰 **{}** buyout", listing.price)
+        };
+        embed = embed.field(
+            format!(
+                "#{} · {} {} x{}",
+                listing.listing_id, emoji, name, listing.quantity
+            ),
+            format!(
+                "{}\nSeller <@{}> · ends <t:{}:R>",
+                price_line,
+                listing.seller_id,
+                listing.expires_at.timestamp()
+            ),
+            false,
+        );
+    }
+    let nav = CreateActionRow::Buttons(vec![
+        Btn::secondary(&format!("market_page_{}", page - 1), "◀ Previous").disabled(page == 0),
+        Btn::secondary(&format!("market_page_{}", page), "🔄 Refresh"),
+        Btn::secondary(&format!("market_page_{}", page + 1), "Next ▶").disabled(page + 1 >= pages),
+    ]);
+    (embed, vec![nav])
+}
@@ -1,9 +1,13 @@
 //! This module contains all commands related to the server economy.
 
+pub mod bank;
 pub mod core;
+pub mod gambling;
 pub mod give;
 pub mod inventory;
+pub mod market;
 pub mod profile;
 pub mod sell;
 pub mod shop;
+pub mod trade;
 pub mod work;
@@ -44,8 +44,19 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
     let saga_profile = services::saga::get_saga_profile(&app_state, user_to_fetch.id, false)
         .await
         .ok_or_else(|| sqlx::Error::RowNotFound);
+    let jobs = database::jobs::list_jobs(&pool).await.unwrap_or_default();
+    let job_progress = database::jobs::get_player_jobs(&pool, user_to_fetch.id)
+        .await
+        .unwrap_or_default();
 
-    let embed = create_profile_embed(&user_to_fetch, profile, inventory, saga_profile);
+    let embed = create_profile_embed(
+        &user_to_fetch,
+        profile,
+        inventory,
+        saga_profile,
+        &jobs,
+        &job_progress,
+    );
     let builder = CreateInteractionResponseFollowup::new().embed(embed);
     interaction.create_followup(&ctx.http, builder).await.ok();
 }
@@ -68,8 +79,19 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
     let saga_profile = services::saga::get_saga_profile(&app_state, user_to_fetch.id, false)
         .await
         .ok_or_else(|| sqlx::Error::RowNotFound);
+    let jobs = database::jobs::list_jobs(&pool).await.unwrap_or_default();
+    let job_progress = database::jobs::get_player_jobs(&pool, user_to_fetch.id)
+        .await
+        .unwrap_or_default();
 
-    let embed = create_profile_embed(&user_to_fetch, profile, inventory, saga_profile);
+    let embed = create_profile_embed(
+        &user_to_fetch,
+        profile,
+        inventory,
+        saga_profile,
+        &jobs,
+        &job_progress,
+    );
     let builder = CreateMessage::new().embed(embed).reference_message(msg);
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
@@ -1,16 +1,20 @@
 //! Handles all UI and embed creation for the `/profile` command.
 
 use crate::commands::economy::core;
+use crate::commands::economy::work::jobs::{JobDef, JobProgress};
 use crate::database;
 use crate::database::models::SagaProfile;
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
+use std::collections::HashMap;
 
 pub fn create_profile_embed(
     user: &User,
     profile_result: Result<database::models::Profile, sqlx::Error>,
     inventory_result: Result<Vec<database::models::InventoryItem>, sqlx::Error>,
     saga_result: Result<SagaProfile, sqlx::Error>,
+    jobs: &[JobDef],
+    job_progress: &HashMap<String, JobProgress>,
 ) -> CreateEmbed {
     fn xp_bar(current: i64, needed: i64) -> String {
         let total_raw = if needed <= 0 { 1 } else { needed };
@@ -75,44 +79,21 @@ pub fn create_profile_embed(
 
             embed = embed.field("\u{200B}", "\u{200B}", false); // Full-width spacer
 
-            let fishing_xp_needed = core::profile::xp_for_level(profile.fishing_level);
-            embed = embed.field(
-                "🎣 Fishing",
-                format!(
-                    "Level {} ({} `{}/{}`)",
-                    profile.fishing_level,
-                    xp_bar(profile.fishing_xp, fishing_xp_needed),
-                    profile.fishing_xp,
-                    fishing_xp_needed
-                ),
-                true,
-            );
-
-            let mining_xp_needed = core::profile::xp_for_level(profile.mining_level);
-            embed = embed.field(
-                "⛏️ Mining",
-                format!(
-                    "Level {} ({} `{}/{}`)",
-                    profile.mining_level,
-                    xp_bar(profile.mining_xp, mining_xp_needed),
-                    profile.mining_xp,
-                    mining_xp_needed
-                ),
-                true,
-            );
-
-            let coding_xp_needed = core::profile::xp_for_level(profile.coding_level);
-            embed = embed.field(
-                "💻 Coding",
-                format!(
-                    "Level {} ({} `{}/{}`)",
-                    profile.coding_level,
-                    xp_bar(profile.coding_xp, coding_xp_needed),
-                    profile.coding_xp,
-                    coding_xp_needed
-                ),
-                true,
-            );
+            for job in jobs {
+                let progress = job_progress.get(&job.key).copied().unwrap_or_default();
+                let xp_needed = core::profile::xp_for_level(progress.level + 1);
+                embed = embed.field(
+                    format!("{} {}", job.emoji, job.display_name),
+                    format!(
+                        "Level {} ({} `{}/{}`)",
+                        progress.level,
+                        xp_bar(progress.xp, xp_needed),
+                        progress.xp,
+                        xp_needed
+                    ),
+                    true,
+                );
+            }
         }
         Err(e) => {
             println!("[PROFILE CMD] Database error: {:?}", e);
@@ -1,8 +1,10 @@
 //! Contains the core logic for the `sell` command.
 
 use super::ui;
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::pricing::quote_total;
+use crate::commands::economy::core::registry::ItemDef;
 use crate::database;
+use crate::database::models::LedgerReason;
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
 use sqlx::PgPool;
@@ -10,32 +12,27 @@ use sqlx::PgPool;
 pub async fn sell_items(
     pool: &PgPool,
     user: &User,
-    item: Item,
+    item: &ItemDef,
     quantity: Option<i64>,
 ) -> CreateEmbed {
-    let properties = item.properties();
-    if !properties.is_sellable {
-        return ui::create_error_embed(&format!(
-            "The item '{}' cannot be sold.",
-            properties.display_name
-        ));
+    if !item.is_sellable {
+        return ui::create_error_embed(&format!("The item '{}' cannot be sold.", item.name));
     }
-    // (✓) MODIFIED: Use the convenience wrapper method.
-    let sell_price = item.sell_price().unwrap_or(0);
+    let base_price = item.sell_price.unwrap_or(0);
+    let band = database::pricing::get_price_band(pool).await;
 
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
         Err(_) => return ui::create_error_embed("Could not start database transaction."),
     };
 
-    let inventory_item = match database::economy::get_inventory_item(&mut tx, user.id, item).await {
+    let inventory_item = match database::economy::get_inventory_item(&mut tx, user.id, item.id)
+        .await
+    {
         Ok(Some(item)) => item,
         _ => {
             tx.rollback().await.ok();
-            return ui::create_error_embed(&format!(
-                "You do not have any {} to sell.",
-                properties.display_name
-            ));
+            return ui::create_error_embed(&format!("You do not have any {} to sell.", item.name));
         }
     };
 
@@ -45,22 +42,43 @@ pub async fn sell_items(
         tx.rollback().await.ok();
         return ui::create_error_embed(&format!(
             "You only have `{}` {} to sell.",
-            inventory_item.quantity, properties.display_name
+            inventory_item.quantity, item.name
         ));
     }
 
-    let total_sale_price = sell_price * amount_to_sell;
+    // Selling pushes the price down; the whole batch is paid at the average of the price before
+    // and after the sale.
+    let (before, after) =
+        match database::pricing::record_trade(&mut tx, item.id, 0, amount_to_sell, band).await {
+            Ok(states) => states,
+            Err(_) => {
+                tx.rollback().await.ok();
+                return ui::create_error_embed("Could not look up the current market price.");
+            }
+        };
+    let total_sale_price = quote_total(base_price, before.live_bp, after.live_bp, amount_to_sell);
 
-    if database::economy::add_to_inventory(&mut tx, user.id, item, -amount_to_sell)
-        .await
-        .is_err()
+    if database::economy::add_to_inventory(
+        &mut tx,
+        user.id,
+        item.id,
+        -amount_to_sell,
+        LedgerReason::Sell,
+    )
+    .await
+    .is_err()
     {
         tx.rollback().await.ok();
         return ui::create_error_embed("Failed to remove items from your inventory.");
     }
-    if database::economy::add_balance(&mut tx, user.id, total_sale_price)
-        .await
-        .is_err()
+    if database::economy::add_balance(
+        &mut tx,
+        user.id,
+        total_sale_price,
+        LedgerReason::Sell.with_ref("item", item.id),
+    )
+    .await
+    .is_err()
     {
         tx.rollback().await.ok();
         return ui::create_error_embed("Failed to add coins to your balance.");
@@ -70,5 +88,11 @@ pub async fn sell_items(
         return ui::create_error_embed("Failed to commit the transaction.");
     }
 
-    ui::create_success_embed(properties.display_name, amount_to_sell, total_sale_price)
+    ui::create_success_embed(
+        &item.name,
+        amount_to_sell,
+        total_sale_price,
+        after.price(base_price),
+        &after.trend_label(),
+    )
 }
@@ -2,7 +2,7 @@
 
 use super::logic::sell_items;
 use crate::AppState;
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponseFollowup, CreateMessage,
 };
@@ -17,13 +17,9 @@ pub fn register() -> CreateCommand {
             CreateCommandOption::new(
                 CommandOptionType::String,
                 "item",
-                "The item you want to sell.",
+                "The item you want to sell (e.g. fish, gem, golden fish).",
             )
-            .required(true)
-            .add_string_choice("Fish", "fish")
-            .add_string_choice("Ore", "ore")
-            .add_string_choice("Gem", "gem")
-            .add_string_choice("Golden Fish", "goldenfish"),
+            .required(true),
         )
         .add_option(
             CreateCommandOption::new(
@@ -50,15 +46,16 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .find(|opt| opt.name == "item")
         .and_then(|opt| opt.value.as_str())
         .unwrap_or_default();
-    let item = match item_str {
-        "fish" => Item::Fish,
-        "ore" => Item::Ore,
-        "gem" => Item::Gem,
-        "goldenfish" => Item::GoldenFish,
-        _ => {
-            // This case is unreachable due to Discord's command choices
-            return;
-        }
+    let Some(item) = registry().resolve(item_str).cloned() else {
+        interaction
+            .edit_response(
+                &ctx.http,
+                serenity::builder::EditInteractionResponse::new()
+                    .content(format!("'{}' is not a known item.", item_str)),
+            )
+            .await
+            .ok();
+        return;
     };
 
     let quantity = interaction
@@ -68,7 +65,7 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .find(|opt| opt.name == "quantity")
         .and_then(|opt| opt.value.as_i64());
 
-    let embed = sell_items(&pool, &interaction.user, item, quantity).await;
+    let embed = sell_items(&pool, &interaction.user, &item, quantity).await;
     let builder = CreateInteractionResponseFollowup::new().embed(embed);
     interaction.create_followup(&ctx.http, builder).await.ok();
 }
@@ -92,23 +89,17 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         }
     };
 
-    let item = match item_name.to_lowercase().as_str() {
-        "fish" => Item::Fish,
-        "ore" => Item::Ore,
-        "gem" => Item::Gem,
-        "goldenfish" | "golden" => Item::GoldenFish,
-        _ => {
-            msg.reply(ctx, &format!("'{}' is not a sellable item.", item_name))
-                .await
-                .ok();
-            return;
-        }
+    let Some(item) = registry().resolve(item_name).cloned() else {
+        msg.reply(ctx, &format!("'{}' is not a known item.", item_name))
+            .await
+            .ok();
+        return;
     };
 
     // Parse the second argument for quantity, if it exists and is a valid number.
     let quantity = args.get(1).and_then(|q| q.parse::<i64>().ok());
 
-    let embed = sell_items(&pool, &msg.author, item, quantity).await;
+    let embed = sell_items(&pool, &msg.author, &item, quantity).await;
     let builder = CreateMessage::new().embed(embed).reference_message(msg);
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
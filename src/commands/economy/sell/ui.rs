@@ -11,13 +11,24 @@ pub fn create_error_embed(error_message: &str) -> CreateEmbed {
         .color(Colour::RED)
 }
 
-/// Creates a standardized success embed for a successful sale.
-pub fn create_success_embed(item_name: &str, quantity_sold: i64, total_price: i64) -> CreateEmbed {
+/// Creates a standardized success embed for a successful sale, with the item's new market price.
+pub fn create_success_embed(
+    item_name: &str,
+    quantity_sold: i64,
+    total_price: i64,
+    unit_price: i64,
+    trend: &str,
+) -> CreateEmbed {
     CreateEmbed::new()
         .title("Sale Successful!")
         .description(format!(
             "You sold **`{}` {}** for a total of **💰`{}`** coins.",
             quantity_sold, item_name, total_price
         ))
+        .field(
+            "Market Price",
+            format!("💰{} each · {} today", unit_price, trend),
+            false,
+        )
         .color(Colour::GOLD)
 }
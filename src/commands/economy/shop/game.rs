@@ -2,7 +2,9 @@
 
 use super::logic::buy_item;
 use super::state::ShopSession;
-use crate::commands::economy::core::item::{Item, ItemCategory};
+use crate::commands::economy::core::item::ItemCategory;
+use crate::commands::economy::core::registry::registry;
+use crate::commands::games::lifecycle::SessionClock;
 use crate::commands::games::{Game, GameUpdate};
 use serenity::async_trait;
 use serenity::builder::{
@@ -10,10 +12,13 @@ use serenity::builder::{
     CreateInteractionResponseMessage,
 };
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
-use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How long a shop session stays open without a click.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(120);
 
 pub struct ShopGame {
     pub session: ShopSession,
@@ -21,18 +26,32 @@ pub struct ShopGame {
 
 #[async_trait]
 impl Game for ShopGame {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
     fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
         let (embed, components) = self.session.render_shop();
         ("".to_string(), embed, components)
     }
 
+    fn participants(&self) -> Vec<UserId> {
+        vec![UserId::new(self.session.user_id)]
+    }
+
+    /// The session closes after `SESSION_TIMEOUT` without a click.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        Some(clock.last_active + SESSION_TIMEOUT)
+    }
+
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        let embed = CreateEmbed::new()
+            .title("Shop Session Expired")
+            .description("Your shop session has timed out due to inactivity.")
+            .color(0xFF0000); // Red
+        GameUpdate::Expired {
+            content: String::new(),
+            embed: Box::new(embed),
+            components: vec![],
+        }
+    }
+
     async fn handle_interaction(
         &mut self,
         ctx: &Context,
@@ -52,6 +71,7 @@ impl Game for ShopGame {
         if interaction.data.custom_id != "shop_buy" {
             interaction.defer(&ctx.http).await.ok();
         }
+        self.refresh_prices(db).await;
 
         match interaction.data.custom_id.as_str() {
             // (✓) ADDED: Handlers for the new category buttons.
@@ -89,19 +109,41 @@ impl Game for ShopGame {
                     return GameUpdate::NoOp;
                 };
 
-                let item = match Item::from_str(selected_item_str) {
-                    Ok(item) => item,
-                    Err(_) => return GameUpdate::NoOp,
+                let Some(item) = registry().resolve(selected_item_str).cloned() else {
+                    return GameUpdate::NoOp;
                 };
 
-                let embed = buy_item(db, &interaction.user, item, 1).await;
+                let embed = buy_item(db, &interaction.user, &item, 1).await;
                 let builder = CreateInteractionResponseFollowup::new().embed(embed);
                 interaction.create_followup(&ctx.http, builder).await.ok();
 
                 // (✓) MODIFIED: The shop session no longer ends after one purchase.
-                GameUpdate::NoOp
+                // Re-render so the listed price reflects the purchase.
+                self.refresh_prices(db).await;
+                GameUpdate::ReRender
             }
             _ => GameUpdate::NoOp,
         }
     }
 }
+
+impl ShopGame {
+    /// Reloads live prices for every item the shop sells.
+    pub async fn refresh_prices(&mut self, db: &PgPool) {
+        let ids: Vec<i32> = [
+            ItemCategory::Resource,
+            ItemCategory::Special,
+            ItemCategory::Consumable,
+        ]
+        .into_iter()
+        .flat_map(|c| {
+            registry()
+                .purchasable(c)
+                .into_iter()
+                .map(|d| d.id)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+        self.session.prices = crate::database::pricing::get_price_states(db, &ids).await;
+    }
+}
@@ -1,20 +1,21 @@
 //! Contains the core logic for the `/shop` command.
 
 use super::ui;
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::pricing::quote_total;
+use crate::commands::economy::core::registry::ItemDef;
 use crate::database;
+use crate::database::models::LedgerReason;
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
 use sqlx::PgPool;
 
-pub async fn buy_item(pool: &PgPool, user: &User, item: Item, quantity: i64) -> CreateEmbed {
-    let properties = item.properties();
-    let buy_price = match properties.buy_price {
+pub async fn buy_item(pool: &PgPool, user: &User, item: &ItemDef, quantity: i64) -> CreateEmbed {
+    let buy_price = match item.buy_price {
         Some(price) => price,
         None => {
             return ui::create_error_embed(&format!(
                 "'{}' cannot be bought from the shop.",
-                properties.display_name
+                item.name
             ));
         }
     };
@@ -23,13 +24,23 @@ pub async fn buy_item(pool: &PgPool, user: &User, item: Item, quantity: i64) ->
         return ui::create_error_embed("You must buy at least one item.");
     }
 
-    let total_cost = buy_price * quantity;
-
+    let band = database::pricing::get_price_band(pool).await;
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
         Err(_) => return ui::create_error_embed("Could not start database transaction."),
     };
 
+    // Buying pushes the price up; the order is charged the average of the price before and after.
+    let (before, after) =
+        match database::pricing::record_trade(&mut tx, item.id, quantity, 0, band).await {
+            Ok(states) => states,
+            Err(_) => {
+                tx.rollback().await.ok();
+                return ui::create_error_embed("Could not look up the current market price.");
+            }
+        };
+    let total_cost = quote_total(buy_price, before.live_bp, after.live_bp, quantity);
+
     // (✓) FIXED: Dereference the transaction `tx` to get an executor `&mut *tx`.
     // This passes the underlying connection to the function, which satisfies the trait bound.
     let profile = match database::economy::get_or_create_profile(pool, user.id).await {
@@ -48,15 +59,20 @@ pub async fn buy_item(pool: &PgPool, user: &User, item: Item, quantity: i64) ->
         ));
     }
 
-    if database::economy::add_balance(&mut tx, user.id, -total_cost)
-        .await
-        .is_err()
+    if database::economy::add_balance(
+        &mut tx,
+        user.id,
+        -total_cost,
+        LedgerReason::Shop.with_ref("item", item.id),
+    )
+    .await
+    .is_err()
     {
         tx.rollback().await.ok();
         return ui::create_error_embed("Failed to deduct coins from your balance.");
     }
 
-    if database::economy::add_to_inventory(&mut tx, user.id, item, quantity)
+    if database::economy::add_to_inventory(&mut tx, user.id, item.id, quantity, LedgerReason::Shop)
         .await
         .is_err()
     {
@@ -68,5 +84,5 @@ pub async fn buy_item(pool: &PgPool, user: &User, item: Item, quantity: i64) ->
         return ui::create_error_embed("Failed to commit the transaction to the database.");
     }
 
-    ui::create_success_embed(properties.display_name, quantity, total_cost)
+    ui::create_success_embed(&item.name, quantity, total_cost)
 }
@@ -1,9 +1,13 @@
 //! Defines the state for an active shop session.
 
 use crate::commands::economy::core::item::ItemCategory;
+use crate::commands::economy::core::pricing::PriceState;
+use std::collections::HashMap;
 
 pub struct ShopSession {
     pub user_id: u64,
     pub current_category: ItemCategory,
     pub current_page: usize,
+    /// Live prices by item id, refreshed on every interaction.
+    pub prices: HashMap<i32, PriceState>,
 }
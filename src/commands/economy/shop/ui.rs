@@ -1,10 +1,12 @@
 //! Handles all UI and embed creation for the `/shop` command.
 
 use super::state::ShopSession;
-use crate::commands::economy::core::item::{Item, ItemCategory};
+use crate::commands::economy::core::item::ItemCategory;
+use crate::commands::economy::core::registry::registry;
 use crate::ui::buttons::Btn;
 use serenity::builder::{
-    CreateActionRow, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption,
 };
 // (✓) REMOVED: Unused import of FromStr.
 
@@ -13,13 +15,8 @@ const ITEMS_PER_PAGE: usize = 5;
 impl ShopSession {
     // ... render_shop function remains the same ...
     pub(super) fn render_shop(&self) -> (CreateEmbed, Vec<CreateActionRow>) {
-        let items_to_display: Vec<Item> = Item::get_all_purchasable()
-            .into_iter()
-            .filter(|item| {
-                item.properties().category == self.current_category
-                    && item.properties().buy_price.is_some()
-            })
-            .collect();
+        let registry = registry();
+        let items_to_display = registry.purchasable(self.current_category);
 
         let start = self.current_page * ITEMS_PER_PAGE;
         let end = (start + ITEMS_PER_PAGE).min(items_to_display.len());
@@ -29,9 +26,16 @@ impl ShopSession {
             ItemCategory::Resource => "🛒 Shop - Resources",
             ItemCategory::Special => "✨ Shop - Special Items",
             ItemCategory::Consumable => "🧪 Shop - Consumables",
+            ItemCategory::Equipment => "🗡️ Shop - Equipment",
         };
 
-        let mut embed = CreateEmbed::new().title(title).color(0x5865F2);
+        let mut embed =
+            CreateEmbed::new()
+                .title(title)
+                .color(0x5865F2)
+                .footer(CreateEmbedFooter::new(
+                    "Prices follow supply and demand: ▲ rising / ▼ falling today.",
+                ));
 
         if page_items.is_empty() {
             embed = embed.description("There are no items in this category.");
@@ -39,17 +43,18 @@ impl ShopSession {
             let item_list = page_items
                 .iter()
                 .map(|item| {
-                    let props = item.properties();
-                    if let Some(price) = props.buy_price {
+                    if let Some(base) = item.buy_price {
+                        let state = self.prices.get(&item.id).copied().unwrap_or_default();
                         format!(
-                            "**{} {}** - **💰{}**\n*{}*",
-                            props.emoji, props.display_name, price, props.description
+                            "**{} {}** - **💰{}** {}\n*{}*",
+                            item.emoji,
+                            item.name,
+                            state.price(base),
+                            state.trend_label(),
+                            item.description
                         )
                     } else {
-                        format!(
-                            "**{} {}**\n*{}*",
-                            props.emoji, props.display_name, props.description
-                        )
+                        format!("**{} {}**\n*{}*", item.emoji, item.name, item.description)
                     }
                 })
                 .collect::<Vec<_>>()
@@ -80,10 +85,8 @@ impl ShopSession {
 
         let mut options = Vec::new();
         for item in page_items {
-            let props = item.properties();
-            let value = item.to_string();
-            let mut option = CreateSelectMenuOption::new(props.display_name, value);
-            if let Some(emoji) = props.emoji.chars().next() {
+            let mut option = CreateSelectMenuOption::new(item.name.as_str(), item.key.as_str());
+            if let Some(emoji) = item.emoji.chars().next() {
                 option = option.emoji(emoji);
             }
             options.push(option);
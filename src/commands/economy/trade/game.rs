@@ -0,0 +1,151 @@
+//! Implements the `Game` trait for a two-sided trade window.
+
+use super::state::{TRADE_TIMEOUT_SECS, TradeSession, TradeStatus};
+use crate::commands::economy::core::registry::registry;
+use crate::commands::games::lifecycle::SessionClock;
+use crate::commands::games::{Game, GameUpdate};
+use crate::database;
+use serenity::async_trait;
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::model::application::{ComponentInteraction, ComponentInteractionDataKind};
+use serenity::model::id::{MessageId, UserId};
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::time::{Duration, Instant};
+
+pub struct TradeGame {
+    pub session: TradeSession,
+}
+
+#[async_trait]
+impl Game for TradeGame {
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        self.session.render_window()
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.session.offers.iter().map(|o| o.user_id).collect()
+    }
+
+    /// The window closes `TRADE_TIMEOUT_SECS` after it opened, whether or not anyone is busy.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        Some(clock.started + Duration::from_secs(TRADE_TIMEOUT_SECS))
+    }
+
+    /// Nothing is held in escrow, so expiring simply discards both offers.
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        self.session.status = TradeStatus::Expired;
+        GameUpdate::GameOver {
+            message: "Trade window expired.".to_string(),
+            payouts: vec![],
+        }
+    }
+
+    async fn handle_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        let Some(side) = self.session.side_of(user_id) else {
+            send_ephemeral(ctx, interaction, "You are not part of this trade.").await;
+            return GameUpdate::NoOp;
+        };
+        let parts: Vec<&str> = interaction.data.custom_id.split('_').collect();
+
+        let result = match parts.get(1).copied().unwrap_or("") {
+            "item" => {
+                // Each side has its own menu; only its owner may use it.
+                if parts.get(2).and_then(|s| s.parse::<usize>().ok()) != Some(side) {
+                    send_ephemeral(ctx, interaction, "Use the menu with your own name on it.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                let ComponentInteractionDataKind::StringSelect { values } = &interaction.data.kind
+                else {
+                    return GameUpdate::NoOp;
+                };
+                let Some(def) = values
+                    .first()
+                    .and_then(|v| v.parse::<i32>().ok())
+                    .and_then(|id| registry().get(id).cloned())
+                else {
+                    return GameUpdate::NoOp;
+                };
+                self.refresh_holdings(db, side).await;
+                self.session.add_item(user_id, &def, 1)
+            }
+            "coins" => {
+                let amount = parts
+                    .get(2)
+                    .and_then(|s| s.parse::<i64>().ok())
+                    .unwrap_or(0);
+                self.refresh_holdings(db, side).await;
+                self.session.add_coins(user_id, amount)
+            }
+            "clear" => self.session.clear(user_id),
+            "cancel" => {
+                interaction.defer(&ctx.http).await.ok();
+                self.session.status = TradeStatus::Cancelled {
+                    by: self.session.offers[side].name.clone(),
+                };
+                return GameUpdate::GameOver {
+                    message: "Trade cancelled.".to_string(),
+                    payouts: vec![],
+                };
+            }
+            "confirm" => match self.session.confirm(user_id) {
+                Ok(true) => {
+                    interaction.defer(&ctx.http).await.ok();
+                    self.session.status =
+                        match database::trade::settle_trade(db, &self.session.offers).await {
+                            Ok(()) => TradeStatus::Completed,
+                            Err(e) => TradeStatus::Failed(e),
+                        };
+                    // Items and coins were moved by the settlement itself, not via payouts.
+                    return GameUpdate::GameOver {
+                        message: "Trade settled.".to_string(),
+                        payouts: vec![],
+                    };
+                }
+                Ok(false) => Ok(()),
+                Err(e) => Err(e),
+            },
+            _ => return GameUpdate::NoOp,
+        };
+
+        match result {
+            Ok(()) => {
+                interaction.defer(&ctx.http).await.ok();
+                GameUpdate::ReRender
+            }
+            Err(e) => {
+                send_ephemeral(ctx, interaction, &e).await;
+                GameUpdate::NoOp
+            }
+        }
+    }
+}
+
+impl TradeGame {
+    /// Reloads what a side owns so the menu and pre-checks reflect spending elsewhere.
+    async fn refresh_holdings(&mut self, db: &PgPool, side: usize) {
+        match database::trade::get_holdings(db, self.session.offers[side].user_id).await {
+            Ok(holdings) => self.session.holdings[side] = holdings,
+            Err(e) => tracing::warn!(target: "trade", error = %e, "Failed to load trade holdings"),
+        }
+    }
+}
+
+async fn send_ephemeral(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
+    let response = CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true);
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await
+        .ok();
+}
@@ -0,0 +1,7 @@
+//! Implements the `/trade` command: a shared window where two players build offers and both
+//! must confirm before anything changes hands.
+
+pub mod game;
+pub mod run;
+pub mod state;
+pub mod ui;
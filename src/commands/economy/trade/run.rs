@@ -0,0 +1,125 @@
+//! Handles the command logic for `/trade` and `$trade`.
+
+use super::game::TradeGame;
+use super::state::TradeSession;
+use crate::AppState;
+use crate::commands::games::Game;
+use crate::database;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateMessage, EditInteractionResponse, EditMessage,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::user::User;
+use serenity::prelude::*;
+use sqlx::PgPool;
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("trade")
+        .description("Open a trade window to swap items and coins with another player.")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::User, "user", "Who to trade with")
+                .required(true),
+        )
+}
+
+fn validate_partner(initiator: &User, partner: &User) -> Result<(), &'static str> {
+    if partner.bot {
+        return Err("You cannot trade with a bot.");
+    }
+    if partner.id == initiator.id {
+        return Err("You cannot trade with yourself.");
+    }
+    Ok(())
+}
+
+async fn new_game(db: &PgPool, initiator: &User, partner: &User) -> TradeGame {
+    let mut session = TradeSession::new(
+        (initiator.id, initiator.name.clone()),
+        (partner.id, partner.name.clone()),
+    );
+    for (side, user) in [initiator, partner].into_iter().enumerate() {
+        if let Ok(holdings) = database::trade::get_holdings(db, user.id).await {
+            session.holdings[side] = holdings;
+        }
+    }
+    TradeGame { session }
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction.defer(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let partner = interaction.data.options.iter().find_map(|o| match o.value {
+        CommandDataOptionValue::User(id) if o.name == "user" => {
+            interaction.data.resolved.users.get(&id).cloned()
+        }
+        _ => None,
+    });
+    let Some(partner) = partner else {
+        let builder = EditInteractionResponse::new().content("A valid trade partner is required.");
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    };
+    if let Err(e) = validate_partner(&interaction.user, &partner) {
+        let builder = EditInteractionResponse::new().content(e);
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    }
+
+    let game = new_game(&app_state.db, &interaction.user, &partner).await;
+    let (content, embed, components) = game.render();
+    let builder = EditInteractionResponse::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+    if let Ok(mut game_msg) = interaction.edit_response(&ctx.http, builder).await {
+        let started =
+            app_state
+                .game_manager
+                .start_game(game_msg.channel_id, game_msg.id, Box::new(game));
+        if let Err(e) = started {
+            let builder = EditMessage::new()
+                .content(e)
+                .embeds(vec![])
+                .components(vec![]);
+            game_msg.edit(&ctx.http, builder).await.ok();
+        }
+    }
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let Some(partner) = msg.mentions.first() else {
+        msg.reply(ctx, "Usage: `$trade @user`").await.ok();
+        return;
+    };
+    if let Err(e) = validate_partner(&msg.author, partner) {
+        msg.reply(ctx, e).await.ok();
+        return;
+    }
+
+    let game = new_game(&app_state.db, &msg.author, partner).await;
+    let (content, embed, components) = game.render();
+    let builder = CreateMessage::new()
+        .content(content)
+        .embed(embed)
+        .components(components)
+        .reference_message(msg);
+    if let Ok(mut game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
+        let started =
+            app_state
+                .game_manager
+                .start_game(game_msg.channel_id, game_msg.id, Box::new(game));
+        if let Err(e) = started {
+            let builder = EditMessage::new()
+                .content(e)
+                .embeds(vec![])
+                .components(vec![]);
+            game_msg.edit(&ctx.http, builder).await.ok();
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Pure state for a two-sided trade window. No Discord or database access lives here so the
+//! confirmation rules can be tested directly.
+
+use crate::commands::economy::core::registry::ItemDef;
+use serenity::model::id::UserId;
+use std::collections::BTreeMap;
+
+/// Coin amounts offered by the `+coins` buttons.
+pub const COIN_STEPS: [i64; 3] = [100, 1_000, 10_000];
+/// Seconds before an unfinished trade window expires.
+pub const TRADE_TIMEOUT_SECS: u64 = 300;
+
+/// What a player currently owns, refreshed from the database before each change to their offer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Holdings {
+    pub balance: i64,
+    pub items: BTreeMap<i32, i64>,
+}
+
+/// One side of the trade: what this player gives away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeOffer {
+    pub user_id: UserId,
+    pub name: String,
+    pub coins: i64,
+    /// item id -> quantity
+    pub items: BTreeMap<i32, i64>,
+    pub confirmed: bool,
+}
+
+impl TradeOffer {
+    fn new(user_id: UserId, name: String) -> Self {
+        Self {
+            user_id,
+            name,
+            coins: 0,
+            items: BTreeMap::new(),
+            confirmed: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coins == 0 && self.items.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeStatus {
+    Open,
+    Completed,
+    Cancelled { by: String },
+    Failed(String),
+    Expired,
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeSession {
+    /// `[initiator, partner]`
+    pub offers: [TradeOffer; 2],
+    pub holdings: [Holdings; 2],
+    pub status: TradeStatus,
+}
+
+impl TradeSession {
+    pub fn new(initiator: (UserId, String), partner: (UserId, String)) -> Self {
+        Self {
+            offers: [
+                TradeOffer::new(initiator.0, initiator.1),
+                TradeOffer::new(partner.0, partner.1),
+            ],
+            holdings: [Holdings::default(), Holdings::default()],
+            status: TradeStatus::Open,
+        }
+    }
+
+    /// Index of the user's side, or `None` for outsiders.
+    pub fn side_of(&self, user_id: UserId) -> Option<usize> {
+        self.offers.iter().position(|o| o.user_id == user_id)
+    }
+
+    fn side(&self, user_id: UserId) -> Result<usize, String> {
+        if self.status != TradeStatus::Open {
+            return Err("This trade is already closed.".to_string());
+        }
+        self.side_of(user_id)
+            .ok_or_else(|| "You are not part of this trade.".to_string())
+    }
+
+    /// Any change to either offer withdraws both confirmations, so nobody can swap an item out
+    /// after the other side has accepted.
+    fn reset_confirmations(&mut self) {
+        for offer in &mut self.offers {
+            offer.confirmed = false;
+        }
+    }
+
+    pub fn add_item(
+        &mut self,
+        user_id: UserId,
+        item: &ItemDef,
+        quantity: i64,
+    ) -> Result<(), String> {
+        let side = self.side(user_id)?;
+        if quantity <= 0 {
+            return Err("Quantity must be positive.".to_string());
+        }
+        if !item.is_tradeable {
+            return Err(format!("**{}** cannot be traded.", item.name));
+        }
+        let held = self.holdings[side]
+            .items
+            .get(&item.id)
+            .copied()
+            .unwrap_or(0);
+        let offered = self.offers[side].items.get(&item.id).copied().unwrap_or(0);
+        if offered + quantity > held {
+            return Err(format!(
+                "You only have `{}` **{}** (already offering `{}`).",
+                held, item.name, offered
+            ));
+        }
+        *self.offers[side].items.entry(item.id).or_insert(0) += quantity;
+        self.reset_confirmations();
+        Ok(())
+    }
+
+    pub fn add_coins(&mut self, user_id: UserId, amount: i64) -> Result<(), String> {
+        let side = self.side(user_id)?;
+        if amount <= 0 {
+            return Err("Amount must be positive.".to_string());
+        }
+        let offered = self.offers[side].coins;
+        if offered + amount > self.holdings[side].balance {
+            return Err(format!(
+                "You only have 💰{} (already offering 💰{}).",
+                self.holdings[side].balance, offered
+            ));
+        }
+        self.offers[side].coins += amount;
+        self.reset_confirmations();
+        Ok(())
+    }
+
+    /// Withdraws everything the user put on the table.
+    pub fn clear(&mut self, user_id: UserId) -> Result<(), String> {
+        let side = self.side(user_id)?;
+        self.offers[side].coins = 0;
+        self.offers[side].items.clear();
+        self.reset_confirmations();
+        Ok(())
+    }
+
+    /// Marks the user's side as confirmed. Returns `true` once both sides have confirmed.
+    pub fn confirm(&mut self, user_id: UserId) -> Result<bool, String> {
+        let side = self.side(user_id)?;
+        if self.offers.iter().all(TradeOffer::is_empty) {
+            return Err("Add something to the trade before confirming.".to_string());
+        }
+        self.offers[side].confirmed = true;
+        Ok(self.both_confirmed())
+    }
+
+    pub fn both_confirmed(&self) -> bool {
+        self.offers.iter().all(|o| o.confirmed)
+    }
+}
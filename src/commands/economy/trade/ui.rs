@@ -0,0 +1,141 @@
+//! Handles embed and component creation for the trade window.
+
+use super::state::{COIN_STEPS, TradeOffer, TradeSession, TradeStatus};
+use crate::commands::economy::core::registry::registry;
+use crate::ui::buttons::Btn;
+use crate::ui::style::COLOR_ALERT;
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption,
+};
+
+const COLOR_TRADE: u32 = 0x1ABC9C;
+const COLOR_TRADE_DONE: u32 = 0x2ECC71;
+/// Discord caps select menus at 25 options.
+const MAX_ITEM_OPTIONS: usize = 25;
+
+fn describe_offer(offer: &TradeOffer) -> String {
+    let items = registry();
+    let mut lines: Vec<String> = offer
+        .items
+        .iter()
+        .map(|(&id, qty)| {
+            let emoji = items.get(id).map(|d| d.emoji.clone()).unwrap_or_default();
+            format!("{} {} x{}", emoji, items.name_of(id), qty)
+        })
+        .collect();
+    if offer.coins > 0 {
+        lines.insert(0, format!("💰 {}", offer.coins));
+    }
+    if lines.is_empty() {
+        "*Nothing yet*".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+impl TradeSession {
+    /// Select menu listing the tradeable items a side still has available to add.
+    fn item_menu(&self, side: usize) -> Option<CreateActionRow> {
+        let items = registry();
+        let offer = &self.offers[side];
+        let options: Vec<CreateSelectMenuOption> = self.holdings[side]
+            .items
+            .iter()
+            .filter_map(|(&id, &held)| {
+                let def = items.get(id).filter(|d| d.is_tradeable)?;
+                let left = held - offer.items.get(&id).copied().unwrap_or(0);
+                (left > 0).then(|| {
+                    CreateSelectMenuOption::new(
+                        format!("{} (x{} left)", def.name, left),
+                        id.to_string(),
+                    )
+                    .emoji(serenity::model::channel::ReactionType::Unicode(
+                        def.emoji.clone(),
+                    ))
+                })
+            })
+            .take(MAX_ITEM_OPTIONS)
+            .collect();
+        if options.is_empty() {
+            return None;
+        }
+        let menu = CreateSelectMenu::new(
+            format!("trade_item_{}", side),
+            CreateSelectMenuKind::String { options },
+        )
+        .placeholder(format!("{}: add one item...", offer.name));
+        Some(CreateActionRow::SelectMenu(menu))
+    }
+
+    pub(super) fn render_window(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let [a, b] = &self.offers;
+        let mark = |o: &TradeOffer| if o.confirmed { "✅" } else { "⏳" };
+        let mut embed = CreateEmbed::new()
+            .title("🤝 Trade Window")
+            .field(
+                format!("{} {} gives", mark(a), a.name),
+                describe_offer(a),
+                true,
+            )
+            .field(
+                format!("{} {} gives", mark(b), b.name),
+                describe_offer(b),
+                true,
+            );
+        let mut components = Vec::new();
+        match &self.status {
+            TradeStatus::Open => {
+                embed = embed
+                    .description(
+                        "Add items and coins below, then both press **Confirm**. Any change to either side resets both confirmations.",
+                    )
+                    .color(COLOR_TRADE)
+                    .footer(CreateEmbedFooter::new(
+                        "Items and coins only move when both players have confirmed.",
+                    ));
+                components.extend((0..2).filter_map(|side| self.item_menu(side)));
+                let mut coin_row: Vec<_> = COIN_STEPS
+                    .iter()
+                    .map(|step| {
+                        Btn::secondary(&format!("trade_coins_{}", step), &format!("+💰{}", step))
+                    })
+                    .collect();
+                coin_row.push(Btn::secondary("trade_clear", "🧹 Clear My Offer"));
+                components.push(CreateActionRow::Buttons(coin_row));
+                components.push(CreateActionRow::Buttons(vec![
+                    Btn::success("trade_confirm", "✅ Confirm"),
+                    Btn::danger("trade_cancel", "❌ Cancel"),
+                ]));
+            }
+            TradeStatus::Completed => {
+                embed = embed
+                    .description("Trade complete. Both offers have been exchanged.")
+                    .color(COLOR_TRADE_DONE);
+            }
+            TradeStatus::Cancelled { by } => {
+                embed = embed
+                    .description(format!(
+                        "Trade cancelled by **{}**. Nothing was exchanged.",
+                        by
+                    ))
+                    .color(COLOR_ALERT);
+            }
+            TradeStatus::Failed(reason) => {
+                embed = embed
+                    .description(format!("Trade failed: {}\nNothing was exchanged.", reason))
+                    .color(COLOR_ALERT);
+            }
+            TradeStatus::Expired => {
+                embed = embed
+                    .description("This trade window expired. Nothing was exchanged.")
+                    .color(COLOR_ALERT);
+            }
+        }
+        (
+            format!("<@{}> ⇄ <@{}>", a.user_id, b.user_id),
+            embed,
+            components,
+        )
+    }
+}
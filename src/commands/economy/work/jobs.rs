@@ -1,51 +1,193 @@
-//! Defines all available jobs and their unique properties.
+//! Job definitions, per-player job progress and level-gated perks.
+//!
+//! Jobs are rows in the `jobs` table, so a new job needs no code or schema change. Each player
+//! has their own level, XP and cooldown per job (`player_jobs`), and every job has a perk tree in
+//! `job_perks` whose perks switch on once the player reaches the perk's level.
 
-use crate::commands::economy::core::item::Item;
-use chrono::Duration;
-// (✓) FIXED: Use the correct imports for your version of the `rand` crate.
-use rand::{Rng, rng};
+use crate::database::models::{JobPerkKind, WorkRewards};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
 
-pub struct Job {
-    pub name: &'static str,
-    pub display_name: &'static str,
+/// Denominator for `JobDef::rare_chance_bp`.
+pub const CHANCE_SCALE: u32 = 10_000;
+/// Cooldown reductions from perks stack up to this percentage.
+pub const MAX_COOLDOWN_REDUCTION_PCT: i64 = 50;
+/// Daily streak bonus per streak day, capped at `MAX_STREAK_BONUS_PCT`.
+pub const STREAK_BONUS_PCT_PER_DAY: i64 = 1;
+pub const MAX_STREAK_BONUS_PCT: i64 = 25;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobPerk {
+    pub required_level: i32,
+    pub kind: JobPerkKind,
+    pub value_pct: i32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobDef {
+    pub key: String,
+    pub display_name: String,
+    pub emoji: String,
     pub min_payout: i64,
     pub max_payout: i64,
     pub cooldown: Duration,
     pub xp_gain: i64,
-    pub resource: fn(level: i32) -> (Item, i64),
-    pub rare_reward: Option<(Item, f64)>,
-}
-
-pub const JOBS: &[Job] = &[
-    Job {
-        name: "fishing",
-        display_name: "Fishing",
-        min_payout: 25,
-        max_payout: 75,
-        cooldown: Duration::minutes(30),
-        xp_gain: 10,
-        // (✓) FINAL FIX: Use the correct `rng()` and `random_range()` for your crate version.
-        resource: |level| (Item::Fish, rng().random_range(3..=8) + (level as i64 / 2)),
-        rare_reward: Some((Item::GoldenFish, 0.05)),
-    },
-    Job {
-        name: "mining",
-        display_name: "Mining",
-        min_payout: 100,
-        max_payout: 300,
-        cooldown: Duration::hours(2),
-        xp_gain: 25,
-        resource: |level| (Item::Ore, rng().random_range(5..=15) + (level as i64)),
-        rare_reward: Some((Item::LargeGeode, 0.02)),
-    },
-    Job {
-        name: "coding",
-        display_name: "Coding",
-        min_payout: 400,
-        max_payout: 800,
-        cooldown: Duration::hours(8),
-        xp_gain: 100,
-        resource: |level| (Item::Gem, rng().random_range(1..=3) + (level as i64 / 5)),
-        rare_reward: None,
-    },
-];
+    pub resource_item_id: i32,
+    pub resource_min: i64,
+    pub resource_max: i64,
+    /// One extra resource per this many levels (0 = no level bonus).
+    pub resource_level_divisor: i32,
+    pub rare_item_id: Option<i32>,
+    pub rare_chance_bp: u32,
+    /// Ordered by required level.
+    pub perks: Vec<JobPerk>,
+}
+
+/// The combined effect of every perk a player has unlocked for one job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PerkEffects {
+    pub double_resource_pct: i64,
+    pub bonus_coins_pct: i64,
+    pub cooldown_reduction_pct: i64,
+    pub rare_chance_bonus_pct: i64,
+}
+
+impl PerkEffects {
+    pub fn at_level(perks: &[JobPerk], level: i32) -> Self {
+        let mut effects = Self::default();
+        for perk in perks.iter().filter(|p| p.required_level <= level) {
+            let value = perk.value_pct as i64;
+            match perk.kind {
+                JobPerkKind::DoubleResource => effects.double_resource_pct += value,
+                JobPerkKind::BonusCoins => effects.bonus_coins_pct += value,
+                JobPerkKind::CooldownReduction => effects.cooldown_reduction_pct += value,
+                JobPerkKind::RareChance => effects.rare_chance_bonus_pct += value,
+            }
+        }
+        effects.double_resource_pct = effects.double_resource_pct.min(100);
+        effects.cooldown_reduction_pct = effects
+            .cooldown_reduction_pct
+            .min(MAX_COOLDOWN_REDUCTION_PCT);
+        effects
+    }
+}
+
+impl JobDef {
+    pub fn effects_at(&self, level: i32) -> PerkEffects {
+        PerkEffects::at_level(&self.perks, level)
+    }
+
+    /// The cooldown after working this job at `level`, with perk reductions applied.
+    pub fn cooldown_at(&self, level: i32) -> Duration {
+        let reduction = self.effects_at(level).cooldown_reduction_pct;
+        Duration::seconds(self.cooldown.num_seconds() * (100 - reduction) / 100)
+    }
+
+    /// Rare-drop chance at `level`, in basis points.
+    pub fn rare_chance_at(&self, level: i32) -> u32 {
+        let bonus = self.effects_at(level).rare_chance_bonus_pct as u32;
+        (self.rare_chance_bp * (100 + bonus) / 100).min(CHANCE_SCALE)
+    }
+
+    /// Perks that unlock when going from `old_level` to `new_level`.
+    pub fn perks_unlocked(&self, old_level: i32, new_level: i32) -> Vec<&JobPerk> {
+        self.perks
+            .iter()
+            .filter(|p| p.required_level > old_level && p.required_level <= new_level)
+            .collect()
+    }
+
+    /// The next perk still locked at `level`.
+    pub fn next_perk(&self, level: i32) -> Option<&JobPerk> {
+        self.perks.iter().find(|p| p.required_level > level)
+    }
+
+    fn level_bonus(&self, level: i32) -> i64 {
+        if self.resource_level_divisor > 0 {
+            (level / self.resource_level_divisor) as i64
+        } else {
+            0
+        }
+    }
+}
+
+/// A player's progress in one job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobProgress {
+    pub level: i32,
+    pub xp: i64,
+    pub last_worked_at: Option<DateTime<Utc>>,
+}
+
+impl Default for JobProgress {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            xp: 0,
+            last_worked_at: None,
+        }
+    }
+}
+
+impl JobProgress {
+    /// Time left on this job's cooldown, or `None` when the job can be worked.
+    pub fn cooldown_remaining(&self, job: &JobDef, now: DateTime<Utc>) -> Option<Duration> {
+        let ready_at = self.last_worked_at? + job.cooldown_at(self.level);
+        (now < ready_at).then(|| ready_at - now)
+    }
+}
+
+/// The outcome of one work session, before it is saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkRoll {
+    pub base_coins: i64,
+    pub streak_bonus: i64,
+    pub perk_bonus: i64,
+    pub resource: i64,
+    pub resource_doubled: bool,
+    pub rare_drop: Option<i32>,
+}
+
+impl WorkRoll {
+    pub fn rewards(&self, job: &JobDef) -> WorkRewards {
+        let mut items = vec![(job.resource_item_id, self.resource)];
+        if let Some(rare) = self.rare_drop {
+            items.push((rare, 1));
+        }
+        WorkRewards {
+            coins: self.base_coins + self.streak_bonus + self.perk_bonus,
+            xp: job.xp_gain,
+            items,
+        }
+    }
+}
+
+/// Rolls the rewards for working `job` at `level` with the given daily `streak`.
+pub fn roll_work<R: Rng + ?Sized>(job: &JobDef, level: i32, streak: i32, rng: &mut R) -> WorkRoll {
+    let effects = job.effects_at(level);
+    let base_coins = rng.random_range(job.min_payout..=job.max_payout.max(job.min_payout));
+    let streak_pct = if streak > 1 {
+        (streak as i64 * STREAK_BONUS_PCT_PER_DAY).min(MAX_STREAK_BONUS_PCT)
+    } else {
+        0
+    };
+    let mut resource = rng.random_range(job.resource_min..=job.resource_max.max(job.resource_min))
+        + job.level_bonus(level);
+    let resource_doubled =
+        effects.double_resource_pct > 0 && rng.random_range(0..100) < effects.double_resource_pct;
+    if resource_doubled {
+        resource *= 2;
+    }
+    let rare_drop = job
+        .rare_item_id
+        .filter(|_| rng.random_range(0..CHANCE_SCALE) < job.rare_chance_at(level));
+    WorkRoll {
+        base_coins,
+        streak_bonus: base_coins * streak_pct / 100,
+        perk_bonus: base_coins * effects.bonus_coins_pct / 100,
+        resource,
+        resource_doubled,
+        rare_drop,
+    }
+}
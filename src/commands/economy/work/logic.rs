@@ -1,24 +1,45 @@
 //! Contains the core logic for the `work` command.
 
-use super::jobs::{JOBS, Job};
+use super::jobs::{JobDef, WorkRoll, roll_work};
 use super::ui;
 use crate::commands::economy::core;
+use crate::commands::economy::core::registry::registry;
 use crate::database;
 use chrono::Utc;
-use rand::{Rng, rng};
 use serenity::builder::CreateEmbed;
 use serenity::model::user::User;
 use sqlx::PgPool;
 
+/// The job board shown when no job is given: every job with the user's level and cooldown.
+pub async fn job_board(pool: &PgPool, user: &User) -> CreateEmbed {
+    let jobs = match database::jobs::list_jobs(pool).await {
+        Ok(jobs) => jobs,
+        Err(_) => return ui::create_error_embed("Could not load the job list."),
+    };
+    let progress = database::jobs::get_player_jobs(pool, user.id)
+        .await
+        .unwrap_or_default();
+    ui::create_job_board_embed(&jobs, &progress, Utc::now())
+}
+
 /// The shared core logic for the work command.
 pub async fn perform_work(pool: &PgPool, user: &User, job_name: &str) -> CreateEmbed {
-    let chosen_job = match JOBS.iter().find(|j| j.name == job_name) {
-        Some(job) => job,
-        None => {
-            return ui::create_error_embed(
-                "That's not a valid job! Try `fishing`, `mining`, or `coding`.",
-            );
+    let job = match database::jobs::find_job(pool, job_name).await {
+        Ok(Some(job)) => job,
+        Ok(None) => {
+            let keys = database::jobs::list_jobs(pool)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .map(|j| format!("`{}`", j.key))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return ui::create_error_embed(&format!(
+                "That's not a valid job! Try one of: {}.",
+                keys
+            ));
         }
+        Err(_) => return ui::create_error_embed("Could not load the job list."),
     };
 
     let mut profile = match database::economy::get_or_create_profile(pool, user.id).await {
@@ -26,46 +47,35 @@ pub async fn perform_work(pool: &PgPool, user: &User, job_name: &str) -> CreateE
         Err(_) => return ui::create_error_embed("Could not fetch your profile."),
     };
 
-    if let Some(last_work) = profile.last_work {
-        let next_work_time = last_work + chosen_job.cooldown;
-        if Utc::now() < next_work_time {
-            return ui::create_cooldown_embed(next_work_time - Utc::now());
-        }
-    }
-
-    let streak = core::profile::check_and_update_streak(&mut profile);
-
-    let (current_level, current_xp) = match chosen_job.name {
-        "fishing" => (profile.fishing_level, profile.fishing_xp),
-        "mining" => (profile.mining_level, profile.mining_xp),
-        "coding" => (profile.coding_level, profile.coding_xp),
-        _ => (1, 0),
-    };
-
-    let (rewards, reward_lines, streak_bonus) =
-        calculate_rewards(current_level, chosen_job, streak);
-
-    let (new_level, new_xp, level_up_info) =
-        core::profile::handle_leveling(current_level, current_xp, rewards.xp);
-
-    let _progression_update = if level_up_info.is_some() || rewards.xp > 0 {
-        Some(database::models::ProgressionUpdate {
-            job_name: chosen_job.name.to_string(),
-            new_level,
-            new_xp,
-        })
-    } else {
-        None
-    };
-
     // --- Main Transaction for Critical Data ---
-    {
+    let (roll, old_level, progress, level_up_info) = {
         let mut tx = match pool.begin().await {
             Ok(tx) => tx,
             Err(_) => return ui::create_error_embed("Failed to start database transaction."),
         };
 
-        if database::economy::update_work_stats(&mut tx, user.id, &rewards, chosen_job.name)
+        // The job row stays locked until commit, so a double-click cannot work twice.
+        let mut progress = match database::jobs::lock_job_progress(&mut tx, user.id, &job.key).await
+        {
+            Ok(progress) => progress,
+            Err(_) => return ui::create_error_embed("Could not fetch your job progress."),
+        };
+        if let Some(remaining) = progress.cooldown_remaining(&job, Utc::now()) {
+            tx.rollback().await.ok();
+            return ui::create_cooldown_embed(&job, remaining);
+        }
+
+        let streak = core::profile::check_and_update_streak(&mut profile);
+        let roll = roll_work(&job, progress.level, streak, &mut rand::rng());
+        let rewards = roll.rewards(&job);
+
+        let old_level = progress.level;
+        let (new_level, new_xp, level_up_info) =
+            core::profile::handle_leveling(progress.level, progress.xp, rewards.xp);
+        progress.level = new_level;
+        progress.xp = new_xp;
+
+        if database::jobs::complete_work(&mut tx, user.id, &job.key, &rewards, progress, streak)
             .await
             .is_err()
         {
@@ -76,7 +86,8 @@ pub async fn perform_work(pool: &PgPool, user: &User, job_name: &str) -> CreateE
         if tx.commit().await.is_err() {
             return ui::create_error_embed("Failed to commit your rewards to the database.");
         }
-    }
+        (roll, old_level, progress, level_up_info)
+    };
     // --- End of Transaction ---
 
     // (✓) FIXED: Update tasks *after* the main transaction is committed.
@@ -86,65 +97,52 @@ pub async fn perform_work(pool: &PgPool, user: &User, job_name: &str) -> CreateE
         .await
         .ok();
 
-    for (item, quantity) in &rewards.items {
+    for (item_id, quantity) in &roll.rewards(&job).items {
         database::tasks::update_task_progress(
             pool,
             user.id,
-            &format!("GatherItem:{}", item.id()), // e.g., "GatherItem:1"
+            &format!("GatherItem:{}", item_id), // e.g., "GatherItem:1"
             *quantity as i32,
         )
         .await
         .ok();
     }
 
+    let unlocked = job.perks_unlocked(old_level, progress.level);
     ui::create_success_embed(
-        chosen_job,
-        &rewards,
-        reward_lines,
-        streak_bonus,
+        &job,
+        reward_lines(&job, &roll),
+        roll.streak_bonus,
         level_up_info,
+        progress.xp,
+        &unlocked,
+        job.cooldown_at(progress.level),
     )
 }
 
-fn calculate_rewards(
-    current_level: i32,
-    job: &Job,
-    streak: i32,
-) -> (database::models::WorkRewards, Vec<String>, i64) {
-    let mut rng = rng();
-    let base_coins = rng.random_range(job.min_payout..=job.max_payout);
-    let streak_bonus = if streak > 1 {
-        (base_coins as f64 * (streak as f64 * 0.01).min(0.25)).round() as i64
-    } else {
-        0
-    };
-
-    let mut rewards = database::models::WorkRewards {
-        coins: base_coins + streak_bonus,
-        xp: job.xp_gain,
-        items: Vec::new(),
-    };
-
-    let mut reward_lines = vec![format!("💰 You earned `{}` coins.", base_coins)];
-
-    let (item, amount) = (job.resource)(current_level);
-    reward_lines.push(format!(
-        "{} You found `{}` {}.",
-        item.emoji(),
-        amount,
-        item.display_name()
+fn reward_lines(job: &JobDef, roll: &WorkRoll) -> Vec<String> {
+    let items = registry();
+    let mut lines = vec![format!("💰 You earned `{}` coins.", roll.base_coins)];
+    if roll.perk_bonus > 0 {
+        lines.push(format!("✨ Perk bonus: `+{}` coins.", roll.perk_bonus));
+    }
+    let resource = items.get(job.resource_item_id);
+    lines.push(format!(
+        "{} You found `{}` {}.{}",
+        resource.map(|i| i.emoji.as_str()).unwrap_or("📦"),
+        roll.resource,
+        items.name_of(job.resource_item_id),
+        if roll.resource_doubled {
+            " **Double haul!**"
+        } else {
+            ""
+        }
     ));
-    rewards.items.push((item, amount));
-
-    if let Some((rare_item, chance)) = &job.rare_reward
-        && rng.random_bool(*chance)
-    {
-        rewards.items.push((*rare_item, 1));
-        reward_lines.push(format!(
+    if let Some(rare) = roll.rare_drop {
+        lines.push(format!(
             "🌟 **RARE DROP!** You found a **{}**!",
-            rare_item.display_name()
+            items.name_of(rare)
         ));
     }
-
-    (rewards, reward_lines, streak_bonus)
+    lines
 }
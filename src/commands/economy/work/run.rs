@@ -1,6 +1,6 @@
 //! This module implements the `work` command, supporting both prefix and slash commands.
 
-use super::logic::perform_work;
+use super::logic::{job_board, perform_work};
 use crate::AppState;
 use serenity::builder::{CreateCommand, CreateCommandOption};
 use serenity::builder::{CreateInteractionResponseFollowup, CreateMessage};
@@ -13,11 +13,12 @@ pub fn register() -> CreateCommand {
     CreateCommand::new("work")
         .description("Work a job to earn coins and resources.")
         .add_option(
-            CreateCommandOption::new(CommandOptionType::String, "job", "The job to perform.")
-                .required(true)
-                .add_string_choice("Fishing", "fishing")
-                .add_string_choice("Mining", "mining")
-                .add_string_choice("Coding", "coding"),
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "job",
+                "The job to perform, e.g. fishing. Leave empty to see the job board.",
+            )
+            .required(false),
         )
 }
 
@@ -33,10 +34,12 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .options
         .iter()
         .find(|opt| opt.name == "job")
-        .and_then(|opt| opt.value.as_str())
-        .unwrap_or("fishing");
+        .and_then(|opt| opt.value.as_str());
 
-    let embed = perform_work(&pool, &interaction.user, job_name).await;
+    let embed = match job_name {
+        Some(job_name) => perform_work(&pool, &interaction.user, job_name).await,
+        None => job_board(&pool, &interaction.user).await,
+    };
     let builder = CreateInteractionResponseFollowup::new().embed(embed);
     interaction.create_followup(&ctx.http, builder).await.ok();
 }
@@ -46,9 +49,10 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         return;
     };
     let pool = app_state.db.clone();
-    let job_name = args.first().cloned().unwrap_or("fishing");
-
-    let embed = perform_work(&pool, &msg.author, job_name).await;
+    let embed = match args.first() {
+        Some(job_name) => perform_work(&pool, &msg.author, job_name).await,
+        None => job_board(&pool, &msg.author).await,
+    };
     let builder = CreateMessage::new().embed(embed).reference_message(msg);
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
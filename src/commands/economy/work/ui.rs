@@ -1,20 +1,26 @@
 //! Handles all UI and embed creation for the `work` command.
 
-use super::jobs::Job;
-use crate::database::models::WorkRewards;
-use chrono::Duration;
-use serenity::builder::CreateEmbed;
+use super::jobs::{JobDef, JobPerk, JobProgress};
+use crate::commands::economy::core::profile::xp_for_level;
+use chrono::{DateTime, Duration, Utc};
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+use std::collections::HashMap;
 
 /// Creates the embed for a successful work session.
 pub fn create_success_embed(
-    job: &Job,
-    rewards: &WorkRewards,
+    job: &JobDef,
     reward_lines: Vec<String>,
     streak_bonus: i64,
     level_up_info: Option<(i32, i64)>,
+    current_xp: i64,
+    unlocked_perks: &[&JobPerk],
+    next_cooldown: Duration,
 ) -> CreateEmbed {
     let mut embed = CreateEmbed::new()
-        .title(format!("Work Complete: {}!", job.display_name))
+        .title(format!(
+            "Work Complete: {} {}!",
+            job.emoji, job.display_name
+        ))
         .color(0x00FF00); // Green
 
     if streak_bonus > 0 {
@@ -24,32 +30,78 @@ pub fn create_success_embed(
     embed = embed.field("Rewards", reward_lines.join("\n"), false);
 
     if let Some((new_level, xp_needed)) = level_up_info {
-        // This XP would be the remainder after leveling up. For now, we'll show it as full.
-        let xp_bar = create_xp_bar(rewards.xp, xp_needed);
-        embed = embed.field(
-            "🎉 Level Up! 🎉", // Field name must be a String or &str
-            format!(
-                "Congratulations! You've reached **Level {}** in {}!\n{}",
-                new_level, job.display_name, xp_bar
-            ),
-            false,
+        let mut text = format!(
+            "Congratulations! You've reached **Level {}** in {}!\n{}",
+            new_level,
+            job.display_name,
+            create_xp_bar(current_xp, xp_needed)
         );
+        for perk in unlocked_perks {
+            text.push_str(&format!("\n🔓 **Perk unlocked:** {}", perk.description));
+        }
+        embed = embed.field("🎉 Level Up! 🎉", text, false);
     }
 
-    embed
+    embed.footer(CreateEmbedFooter::new(format!(
+        "You can work as {} again in {}.",
+        job.display_name,
+        format_duration(next_cooldown)
+    )))
 }
 
-/// Creates the embed for when a user is on cooldown.
-pub fn create_cooldown_embed(remaining: Duration) -> CreateEmbed {
+/// Creates the embed for when a user is on cooldown for one job.
+pub fn create_cooldown_embed(job: &JobDef, remaining: Duration) -> CreateEmbed {
     CreateEmbed::new()
         .title("On Cooldown")
         .description(format!(
-            "You can work again in **{}**.",
+            "You can work as {} {} again in **{}**. Other jobs have their own cooldowns.",
+            job.emoji,
+            job.display_name,
             format_duration(remaining)
         ))
         .color(0xFF0000) // Red
 }
 
+/// Creates the job board: every job with the user's level, next perk and cooldown.
+pub fn create_job_board_embed(
+    jobs: &[JobDef],
+    progress: &HashMap<String, JobProgress>,
+    now: DateTime<Utc>,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("Job Board")
+        .description(
+            "Use `/work <job>` to start a shift. Each job levels up and cools down on its own.",
+        )
+        .color(0x3498DB);
+    for job in jobs {
+        let progress = progress.get(&job.key).copied().unwrap_or_default();
+        let status = match progress.cooldown_remaining(job, now) {
+            Some(remaining) => format!("⏳ Ready in {}", format_duration(remaining)),
+            None => "✅ Ready".to_string(),
+        };
+        let next_perk = match job.next_perk(progress.level) {
+            Some(perk) => format!(
+                "Next perk (Lv {}): {}",
+                perk.required_level, perk.description
+            ),
+            None => "All perks unlocked".to_string(),
+        };
+        embed = embed.field(
+            format!("{} {} (`{}`)", job.emoji, job.display_name, job.key),
+            format!(
+                "Level {} {}\n{}\n{}",
+                progress.level,
+                create_xp_bar(progress.xp, xp_for_level(progress.level + 1)),
+                status,
+                next_perk
+            ),
+            true,
+        );
+    }
+    embed
+}
+
 /// Creates a generic error embed.
 pub fn create_error_embed(error_message: &str) -> CreateEmbed {
     CreateEmbed::new()
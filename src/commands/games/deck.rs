@@ -76,4 +76,9 @@ impl Deck {
     pub fn cards_remaining(&self) -> usize {
         self.cards.len()
     }
+
+    /// Consumes the deck, returning the cards left in it.
+    pub fn into_cards(self) -> Vec<Card> {
+        self.cards
+    }
 }
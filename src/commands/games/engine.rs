@@ -2,14 +2,33 @@
 //! It defines the `Game` trait that all games must implement, and the
 //! `GameManager` which tracks and routes interactions for all active games.
 
+use super::lifecycle::{
+    IDLE_LIMIT, MAX_GAMES_PER_USER, SessionClock, SessionMetrics, SessionTotals,
+};
+use super::{side_bets, spectate};
+use crate::AppState;
+use crate::database::models::{GambleGame, LedgerReason};
+use crate::database::{gambling, ledger, side_bets as side_bet_book, table_stacks};
+use crate::ui::style::EMOJI_COIN;
 use serenity::async_trait;
-use serenity::builder::{CreateActionRow, CreateEmbed, EditMessage};
-use serenity::model::application::ComponentInteraction;
-use serenity::model::id::{MessageId, UserId};
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage, EditMessage,
+};
+use serenity::http::Http;
+use serenity::model::application::{ComponentInteraction, ComponentInteractionDataKind};
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, MessageId, UserId};
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+
+/// Tables that play for coins seat each player with a stack of this many minimum bets, bought
+/// in from their wallet when they join (see `database::table_stacks`).
+pub const TABLE_BUY_IN_BETS: i64 = 20;
 
 /// Represents a single player's win or loss.
 #[derive(Debug, Clone)]
@@ -25,14 +44,18 @@ pub enum GameUpdate {
         message: String,
         payouts: Vec<GamePayout>,
     },
+    /// The game closed without being played out, such as a lobby nobody started. Its message is
+    /// replaced with this view and nothing is paid.
+    Expired {
+        content: String,
+        embed: Box<CreateEmbed>,
+        components: Vec<CreateActionRow>,
+    },
     NoOp,
 }
 
 #[async_trait]
 pub trait Game: Send + Sync {
-    fn as_any(&self) -> &dyn Any;
-    fn as_any_mut(&mut self) -> &mut dyn Any; // actively used via GameManager::get_game_mut_typed
-
     /// (✓) MODIFIED: The handler now receives the database pool for real-time checks.
     async fn handle_interaction(
         &mut self,
@@ -42,122 +65,771 @@ pub trait Game: Send + Sync {
     ) -> GameUpdate;
 
     fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>);
+
+    /// The gambling stats this game's payouts count towards. `None` for games played without
+    /// stakes.
+    fn gamble_game(&self) -> Option<GambleGame> {
+        None
+    }
+
+    /// The players spectators can back with a side bet right now. Empty while betting is closed.
+    fn side_bet_players(&self) -> Vec<UserId> {
+        Vec::new()
+    }
+
+    /// Whether the odds on `side_bet_players` come from their RPS ladder ratings. Otherwise
+    /// every player is offered at the same odds.
+    fn side_bets_rated(&self) -> bool {
+        false
+    }
+
+    /// The results of a round that finished without ending the game, such as a tournament hand.
+    /// Taken once, so the side bets placed on that round can be settled.
+    fn take_round_outcome(&mut self) -> Option<Vec<GamePayout>> {
+        None
+    }
+
+    /// The players a side bet had to back to win, given a round's results. By default whoever
+    /// came out furthest ahead; nobody winning makes the round void.
+    fn side_bet_winners(&self, payouts: &[GamePayout]) -> Vec<UserId> {
+        side_bets::top_earners(payouts)
+    }
+
+    /// Everyone playing, so the manager can cap how many games each player has open.
+    fn participants(&self) -> Vec<UserId> {
+        Vec::new()
+    }
+
+    /// When this game's own clock runs out (an unstarted lobby, an unanswered challenge, a
+    /// stalled turn), given when the session started and last saw play. `None` while no clock
+    /// is running.
+    fn deadline(&self, _clock: &SessionClock) -> Option<Instant> {
+        None
+    }
+
+    /// Called by the sweeper once `deadline` has passed. It must end the game or move its
+    /// deadline on, or it is called again on the next sweep.
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        GameUpdate::NoOp
+    }
+
+    /// Called before the sweeper closes a game nobody has played for `IDLE_LIMIT`, to void a
+    /// round or let go of anything held outside the manager. Returns whatever is owed to the
+    /// players; table stacks and side bets are refunded either way.
+    async fn on_abandon(&mut self, _db: &PgPool, _message_id: MessageId) -> Vec<GamePayout> {
+        Vec::new()
+    }
+}
+
+/// How many shards the manager spreads its games across. A shard's map is only locked to look a
+/// game up or to add or drop one, never across an await.
+const SHARDS: usize = 16;
+
+/// A tracked game, where it was posted, and its clock.
+struct Session {
+    game: Box<dyn Game>,
+    channel_id: ChannelId,
+    clock: SessionClock,
+    /// Set once the game has been settled, for anyone who was waiting on its lock.
+    closed: bool,
+}
+
+/// What the manager knows about a session without waiting for its game, so the cap, the sweeper
+/// and the metrics never queue behind a game that is busy.
+#[derive(Clone)]
+struct Summary {
+    players: Vec<UserId>,
+    deadline: Option<Instant>,
+    clock: SessionClock,
+}
+
+impl Summary {
+    fn of(session: &Session) -> Self {
+        Self {
+            players: session.game.participants(),
+            deadline: session.game.deadline(&session.clock),
+            clock: session.clock,
+        }
+    }
+}
+
+/// One game behind its own lock, with the summary it leaves behind each time it is let go.
+struct Slot {
+    session: tokio::sync::Mutex<Session>,
+    summary: Mutex<Summary>,
+}
+
+impl Slot {
+    fn summary(&self) -> Summary {
+        self.summary
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// A handle on one tracked game. Locking it waits only for that game, so interactions on
+/// different games run side by side.
+#[derive(Clone)]
+pub struct GameHandle {
+    slot: Arc<Slot>,
 }
 
+impl GameHandle {
+    /// Waits for the game. `None` if it was settled in the meantime.
+    pub async fn lock(&self) -> Option<GameGuard<'_>> {
+        let session = self.slot.session.lock().await;
+        self.guard(session)
+    }
+
+    /// Takes the game only if nobody is playing it right now.
+    fn try_lock(&self) -> Option<GameGuard<'_>> {
+        let session = self.slot.session.try_lock().ok()?;
+        self.guard(session)
+    }
+
+    fn guard<'a>(&'a self, session: tokio::sync::MutexGuard<'a, Session>) -> Option<GameGuard<'a>> {
+        if session.closed {
+            return None;
+        }
+        Some(GameGuard {
+            slot: &self.slot,
+            session,
+        })
+    }
+}
+
+/// Exclusive access to a tracked game. Dropping it refreshes the players and deadline the
+/// manager sees.
+pub struct GameGuard<'a> {
+    slot: &'a Slot,
+    session: tokio::sync::MutexGuard<'a, Session>,
+}
+
+impl Deref for GameGuard<'_> {
+    type Target = Box<dyn Game>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.session.game
+    }
+}
+
+impl DerefMut for GameGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.session.game
+    }
+}
+
+impl Drop for GameGuard<'_> {
+    fn drop(&mut self) {
+        *self
+            .slot
+            .summary
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Summary::of(&self.session);
+    }
+}
+
+/// Tracks every open game. Each game has its own lock, so a slow button press (a battle
+/// animating, a payout waiting on the database) only holds up the game it was pressed on.
 pub struct GameManager {
-    active_games: HashMap<MessageId, Box<dyn Game>>,
+    shards: Vec<RwLock<HashMap<MessageId, Arc<Slot>>>>,
+    /// Games with open side bets, so rounds nobody bet on settle without a database round trip.
+    betting: Mutex<HashSet<MessageId>>,
+    /// Also held while a game is admitted, so two games starting at once cannot both slip under
+    /// a player's cap.
+    totals: Mutex<SessionTotals>,
 }
 
 impl GameManager {
     pub fn new() -> Self {
         Self {
-            active_games: HashMap::new(),
+            shards: (0..SHARDS).map(|_| RwLock::default()).collect(),
+            betting: Mutex::default(),
+            totals: Mutex::default(),
         }
     }
 
-    pub fn start_game(&mut self, message_id: MessageId, game: Box<dyn Game>) {
-        self.active_games.insert(message_id, game);
+    fn shard(&self, message_id: &MessageId) -> &RwLock<HashMap<MessageId, Arc<Slot>>> {
+        &self.shards[message_id.get() as usize % SHARDS]
     }
 
-    pub fn get_game_mut(&mut self, message_id: &MessageId) -> Option<&mut Box<dyn Game>> {
-        self.active_games.get_mut(message_id)
+    fn totals(&self) -> MutexGuard<'_, SessionTotals> {
+        self.totals.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
-    pub fn remove_game(&mut self, message_id: &MessageId) {
-        self.active_games.remove(message_id);
+    /// Every tracked game's summary. Each shard is locked only long enough to copy it.
+    fn summaries(&self) -> Vec<(MessageId, Summary)> {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                let shard = shard.read().unwrap_or_else(PoisonError::into_inner);
+                shard
+                    .iter()
+                    .map(|(&id, slot)| (id, slot.summary()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
     }
 
-    /// Attempt to downcast the stored game to a concrete mutable type.
-    pub fn get_game_mut_typed<T: Game + 'static>(
-        &mut self,
-        message_id: &MessageId,
-    ) -> Option<&mut T> {
-        self.active_games
-            .get_mut(message_id)?
-            .as_any_mut()
-            .downcast_mut::<T>()
+    /// Starts tracking a game a player opened, unless one of its participants already has
+    /// `MAX_GAMES_PER_USER` games open.
+    pub fn start_game(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        game: Box<dyn Game>,
+    ) -> Result<(), String> {
+        let mut totals = self.totals();
+        if let Some(busy) = game
+            .participants()
+            .into_iter()
+            .find(|&user_id| self.games_of(user_id) >= MAX_GAMES_PER_USER)
+        {
+            totals.refused += 1;
+            return Err(format!(
+                "<@{}> already has {} games open. Finish one before starting another.",
+                busy, MAX_GAMES_PER_USER
+            ));
+        }
+        totals.started += 1;
+        self.insert(channel_id, message_id, game);
+        Ok(())
+    }
+
+    /// Starts tracking a game the bot scheduled itself, such as a bracket match or a tournament
+    /// table. It counts toward its players' cap but is never refused, since they committed to it
+    /// when they signed up.
+    pub fn start_scheduled_game(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        game: Box<dyn Game>,
+    ) {
+        self.totals().started += 1;
+        self.insert(channel_id, message_id, game);
+    }
+
+    fn insert(&self, channel_id: ChannelId, message_id: MessageId, game: Box<dyn Game>) {
+        let session = Session {
+            game,
+            channel_id,
+            clock: SessionClock::new(Instant::now()),
+            closed: false,
+        };
+        let slot = Arc::new(Slot {
+            summary: Mutex::new(Summary::of(&session)),
+            session: tokio::sync::Mutex::new(session),
+        });
+        self.shard(&message_id)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(message_id, slot);
+    }
+
+    /// The game posted as `message_id`, if it is still being tracked.
+    pub fn game(&self, message_id: &MessageId) -> Option<GameHandle> {
+        let shard = self
+            .shard(message_id)
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        let slot = shard.get(message_id)?.clone();
+        Some(GameHandle { slot })
+    }
+
+    /// How many open games `user_id` is playing in.
+    pub fn games_of(&self, user_id: UserId) -> usize {
+        self.summaries()
+            .iter()
+            .filter(|(_, s)| s.players.contains(&user_id))
+            .count()
+    }
+
+    pub fn remove_game(&self, message_id: &MessageId) {
+        self.shard(message_id)
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(message_id);
+    }
+
+    /// The games whose own clock has run out by `now`.
+    pub fn timed_out(&self, now: Instant) -> Vec<MessageId> {
+        self.summaries()
+            .into_iter()
+            .filter(|(_, s)| s.deadline.is_some_and(|d| d <= now))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// The games nobody has played for `IDLE_LIMIT` by `now`.
+    pub fn idle(&self, now: Instant) -> Vec<MessageId> {
+        self.summaries()
+            .into_iter()
+            .filter(|(_, s)| s.clock.is_idle(now))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// A snapshot of the open games and what has happened to sessions since startup.
+    pub fn metrics(&self, now: Instant) -> SessionMetrics {
+        let summaries = self.summaries();
+        let mut per_player: HashMap<UserId, usize> = HashMap::new();
+        for (_, summary) in &summaries {
+            for &user_id in &summary.players {
+                *per_player.entry(user_id).or_default() += 1;
+            }
+        }
+        SessionMetrics {
+            active: summaries.len(),
+            players: per_player.len(),
+            busiest: per_player
+                .into_iter()
+                .max_by_key(|&(user_id, count)| (count, std::cmp::Reverse(user_id))),
+            oldest: summaries
+                .iter()
+                .map(|(_, s)| now.saturating_duration_since(s.clock.started))
+                .max(),
+            quiet: summaries
+                .iter()
+                .filter(|(_, s)| {
+                    now.saturating_duration_since(s.clock.last_active) > Duration::from_secs(60)
+                })
+                .count(),
+            totals: *self.totals(),
+        }
+    }
+
+    /// One pass of the sweeper: games whose clock has run out get their `on_timeout`, then games
+    /// nobody has played for `IDLE_LIMIT` are closed with their stakes refunded. A game someone
+    /// is playing right now is left for the next pass rather than waited on.
+    pub async fn sweep(&self, http: &Http, db: &PgPool) {
+        self.sweep_at(http, db, Instant::now()).await;
+    }
+
+    /// A pass of the sweeper as if it ran at `now`.
+    pub async fn sweep_at(&self, http: &Http, db: &PgPool, now: Instant) {
+        for message_id in self.timed_out(now) {
+            let Some(handle) = self.game(&message_id) else {
+                continue;
+            };
+            let Some(mut game) = handle.try_lock() else {
+                continue;
+            };
+            let session = &mut *game.session;
+            if session
+                .game
+                .deadline(&session.clock)
+                .is_none_or(|d| d > now)
+            {
+                continue;
+            }
+            let update = session.game.on_timeout(db, message_id).await;
+            session.clock.touch(now);
+            self.totals().timed_out += 1;
+            self.settle_round(http, db, session, message_id).await;
+            self.apply_update(http, db, session, message_id, update)
+                .await;
+        }
+        for message_id in self.idle(now) {
+            let Some(handle) = self.game(&message_id) else {
+                continue;
+            };
+            let Some(mut game) = handle.try_lock() else {
+                continue;
+            };
+            if game.session.clock.is_idle(now) {
+                self.abandon(http, db, &mut game.session, message_id).await;
+            }
+        }
+    }
+
+    /// Closes a game nobody is playing any more, paying out whatever its `on_abandon` says it
+    /// owes.
+    async fn abandon(
+        &self,
+        http: &Http,
+        db: &PgPool,
+        session: &mut Session,
+        message_id: MessageId,
+    ) {
+        let payouts = session.game.on_abandon(db, message_id).await;
+        let (_, embed, _) = session.game.render();
+        self.totals().abandoned += 1;
+        let notice = format!(
+            "⌛ This game was closed after {} minutes without play.",
+            IDLE_LIMIT.as_secs() / 60
+        );
+        let view = (notice.clone(), embed, vec![]);
+        self.close(http, db, session, message_id, &notice, payouts, Some(view))
+            .await;
     }
 
     pub async fn on_interaction(
-        &mut self,
+        &self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
         db: &PgPool,
     ) {
-        // Touch typed accessor generically to keep it live (no-op if mismatch)
-        if let Some(_unused) = self
-            .get_game_mut_typed::<crate::commands::blackjack::state::BlackjackGame>(
-                &interaction.message.id,
-            )
-        {
-            let _ = &_unused; // keep mutable access path exercised
-        }
-        if let Some(game) = self.get_game_mut(&interaction.message.id) {
-            // (✓) MODIFIED: Pass the database pool down to the game's handler.
-            match game.handle_interaction(ctx, interaction, db).await {
-                GameUpdate::ReRender => {
-                    let (content, embed, components) = game.render();
-                    let builder = EditMessage::new()
-                        .content(content)
-                        .embed(embed)
-                        .components(components);
-                    if let Err(e) = interaction.message.edit(&ctx.http, builder).await {
-                        println!("[GAME MANAGER] Error editing game message: {:?}", e);
-                    }
+        if interaction.data.custom_id.starts_with("spectate_") {
+            self.on_spectate(ctx, interaction, db).await;
+            return;
+        }
+        let message_id = interaction.message.id;
+        let user_id = interaction.user.id;
+        let Some(handle) = self.game(&message_id) else {
+            return;
+        };
+        // Anyone not yet playing is most likely joining, which the cap has to allow for.
+        let joining = !handle.slot.summary().players.contains(&user_id);
+        if joining && self.games_of(user_id) >= MAX_GAMES_PER_USER {
+            let response = CreateInteractionResponseMessage::new()
+                .content(format!(
+                    "You already have {} games open. Finish one before joining another.",
+                    MAX_GAMES_PER_USER
+                ))
+                .ephemeral(true);
+            interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await
+                .ok();
+            return;
+        }
+        let Some(mut game) = handle.lock().await else {
+            return;
+        };
+        let session = &mut *game.session;
+        // (✓) MODIFIED: Pass the database pool down to the game's handler.
+        let update = session.game.handle_interaction(ctx, interaction, db).await;
+        session.clock.touch(Instant::now());
+        self.settle_round(&ctx.http, db, session, message_id).await;
+        self.apply_update(&ctx.http, db, session, message_id, update)
+            .await;
+    }
+
+    /// Settles the side bets on a round that finished without ending the game, if one did.
+    async fn settle_round(
+        &self,
+        http: &Http,
+        db: &PgPool,
+        session: &mut Session,
+        message_id: MessageId,
+    ) {
+        let game = &mut session.game;
+        let round_winners = game
+            .take_round_outcome()
+            .map(|outcome| game.side_bet_winners(&outcome));
+        if let Some(winners) = round_winners {
+            self.settle_side_bets(http, db, session.channel_id, message_id, &winners)
+                .await;
+        }
+    }
+
+    /// Shows or settles what a game reported after an interaction or a timeout.
+    async fn apply_update(
+        &self,
+        http: &Http,
+        db: &PgPool,
+        session: &mut Session,
+        message_id: MessageId,
+        update: GameUpdate,
+    ) {
+        match update {
+            GameUpdate::ReRender => {
+                let (content, embed, components) = session.game.render();
+                let builder = EditMessage::new()
+                    .content(content)
+                    .embed(embed)
+                    .components(components);
+                if let Err(e) = session
+                    .channel_id
+                    .edit_message(http, message_id, builder)
+                    .await
+                {
+                    println!("[GAME MANAGER] Error editing game message: {:?}", e);
                 }
-                GameUpdate::GameOver { message, payouts } => {
-                    println!("[GAME MANAGER] Game over: {}", message);
-
-                    if !payouts.is_empty() {
-                        let mut tx = match db.begin().await {
-                            Ok(tx) => tx,
-                            Err(e) => {
-                                println!("[DB] Failed to begin transaction: {:?}", e);
-                                return;
-                            }
-                        };
-
-                        for payout in &payouts {
-                            if payout.amount == 0 {
-                                continue;
-                            }
-                            if let Err(e) = sqlx::query!(
-                                "UPDATE profiles SET balance = balance + $1 WHERE user_id = $2",
-                                payout.amount,
-                                payout.user_id.get() as i64
-                            )
-                            .execute(&mut *tx)
-                            .await
-                            {
-                                println!(
-                                    "[DB] Failed to process payout for {}: {:?}. Rolling back.",
-                                    payout.user_id, e
-                                );
-                                tx.rollback().await.ok();
-                                return;
-                            }
-                        }
-
-                        if let Err(e) = tx.commit().await {
-                            println!("[DB] Failed to commit transaction: {:?}", e);
-                        } else {
-                            println!("[DB] Successfully processed {} payouts.", payouts.len());
-                        }
-                    }
+            }
+            GameUpdate::GameOver { message, payouts } => {
+                self.close(http, db, session, message_id, &message, payouts, None)
+                    .await;
+            }
+            GameUpdate::Expired {
+                content,
+                embed,
+                components,
+            } => {
+                let view = (content, *embed, components);
+                self.close(http, db, session, message_id, "Expired", vec![], Some(view))
+                    .await;
+            }
+            GameUpdate::NoOp => {}
+        }
+    }
+
+    /// Settles a finished game: applies its payouts, records gambling stats, cashes out any table
+    /// stacks, renders the final state without components (or `view`, when one is given) and
+    /// stops tracking it. Used for games that end on a button press as well as on a timer.
+    #[allow(clippy::too_many_arguments)]
+    async fn close(
+        &self,
+        http: &Http,
+        db: &PgPool,
+        session: &mut Session,
+        message_id: MessageId,
+        message: &str,
+        payouts: Vec<GamePayout>,
+        view: Option<(String, CreateEmbed, Vec<CreateActionRow>)>,
+    ) {
+        println!("[GAME MANAGER] Game over: {}", message);
+        let channel_id = session.channel_id;
+        let game = &session.game;
+        let winners = game.side_bet_winners(&payouts);
+        self.settle_side_bets(http, db, channel_id, message_id, &winners)
+            .await;
 
-                    let (content, embed, _) = game.render();
-                    let builder = EditMessage::new()
-                        .content(content)
-                        .embed(embed)
-                        .components(vec![]);
-                    if let Err(e) = interaction.message.edit(&ctx.http, builder).await {
-                        println!("[GAME MANAGER] Error editing final message: {:?}", e);
+        // A failed payout still closes the game, so it can't be left open for the idle sweep;
+        // the final message says nothing was paid.
+        let mut notice = None;
+        if !payouts.is_empty() {
+            match pay_out(db, message_id, &payouts).await {
+                Ok(()) => {
+                    println!("[DB] Successfully processed {} payouts.", payouts.len());
+                    if let Some(kind) = game.gamble_game()
+                        && let Err(e) = gambling::record(db, kind, &payouts).await
+                    {
+                        println!("[DB] Failed to record gambling stats: {:?}", e);
                     }
-                    self.remove_game(&interaction.message.id);
                 }
-                GameUpdate::NoOp => {}
+                Err(e) => {
+                    println!("[DB] Failed to process payouts: {:?}. Rolled back.", e);
+                    notice = Some(
+                        "⚠️ This game's winnings could not be paid out, so no balances were changed.",
+                    );
+                }
             }
         }
+
+        // Anyone still holding chips at the table gets them back as it closes.
+        if let Err(e) = table_stacks::cash_out_table(db, message_id).await {
+            println!("[DB] Failed to cash out table stacks: {:?}", e);
+        }
+
+        let (mut content, embed, components) = view.unwrap_or_else(|| {
+            let (content, embed, _) = game.render();
+            (content, embed, vec![])
+        });
+        if let Some(notice) = notice {
+            content = format!("{}\n{}", content, notice).trim_start().to_string();
+        }
+        let builder = EditMessage::new()
+            .content(content)
+            .embed(embed)
+            .components(components);
+        if let Err(e) = channel_id.edit_message(http, message_id, builder).await {
+            println!("[GAME MANAGER] Error editing final message: {:?}", e);
+        }
+        session.closed = true;
+        self.remove_game(&message_id);
+        // Bets that still couldn't be settled stay held until the startup refund returns them.
+        self.betting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&message_id);
+    }
+
+    /// Settles the open side bets on a game's round and posts how they went. With no winners
+    /// the round is void and every stake is refunded.
+    async fn settle_side_bets(
+        &self,
+        http: &Http,
+        db: &PgPool,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        winners: &[UserId],
+    ) {
+        let has_bets = self
+            .betting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&message_id);
+        if !has_bets {
+            return;
+        }
+        // The game stays marked as taking bets until they settle, so a failed settle is tried
+        // again when the next round or the game ends.
+        let settled = match side_bet_book::settle(db, message_id, winners).await {
+            Ok(settled) => settled,
+            Err(e) => {
+                println!("[DB] Failed to settle side bets: {:?}", e);
+                return;
+            }
+        };
+        self.betting
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&message_id);
+        if settled.is_empty() {
+            return;
+        }
+        let lines = settled
+            .iter()
+            .map(|bet| {
+                let result = if winners.is_empty() {
+                    format!("refunded {}{}", EMOJI_COIN, bet.returned)
+                } else if bet.returned > 0 {
+                    format!("won {}{}", EMOJI_COIN, bet.returned)
+                } else {
+                    format!("lost {}{}", EMOJI_COIN, bet.stake)
+                };
+                format!("<@{}> backed <@{}>: {}", bet.user_id, bet.backed_id, result)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let builder = CreateMessage::new()
+            .content(format!("🎲 **Side bets settled**\n{}", lines))
+            .reference_message((channel_id, message_id));
+        if let Err(e) = channel_id.send_message(http, builder).await {
+            println!("[GAME MANAGER] Error posting side bet results: {:?}", e);
+        }
+    }
+
+    /// Handles the spectator view: opening it from a game, refreshing it, and placing side bets.
+    async fn on_spectate(
+        &self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) {
+        let custom_id = interaction.data.custom_id.clone();
+        let viewer = interaction.user.id;
+        let (target, bet_choice) = if custom_id == spectate::SPECTATE_VIEW {
+            (Some(interaction.message.id), None)
+        } else if let Some(id) = custom_id.strip_prefix("spectate_refresh_") {
+            (id.parse::<u64>().ok().map(MessageId::new), None)
+        } else if let Some(id) = custom_id.strip_prefix("spectate_bet_") {
+            let choice = match &interaction.data.kind {
+                ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
+                _ => None,
+            };
+            (id.parse::<u64>().ok().map(MessageId::new), choice)
+        } else {
+            (None, None)
+        };
+        let opening = custom_id == spectate::SPECTATE_VIEW;
+        let handle = target.and_then(|id| Some((id, self.game(&id)?)));
+        let snapshot = match &handle {
+            Some((_, handle)) => handle
+                .lock()
+                .await
+                .map(|game| spectate::Snapshot::of(&**game)),
+            None => None,
+        };
+        let (Some((message_id, handle)), Some(snapshot)) = (handle, snapshot) else {
+            let response = CreateInteractionResponseMessage::new()
+                .content("That game has finished.")
+                .components(vec![])
+                .ephemeral(true);
+            let response = if opening {
+                CreateInteractionResponse::Message(response)
+            } else {
+                CreateInteractionResponse::UpdateMessage(response)
+            };
+            interaction.create_response(&ctx.http, response).await.ok();
+            return;
+        };
+
+        // Pricing looks up ratings and names, so it runs without the game's lock.
+        let runners = spectate::market(ctx, db, &snapshot).await;
+        let mut note = String::new();
+        if let Some(choice) = bet_choice {
+            // The bet is only taken if betting is still open on the same players, checked and
+            // recorded under the lock so a round can't settle in between.
+            let game = handle.lock().await;
+            let open = game
+                .as_ref()
+                .is_some_and(|game| game.side_bet_players() == snapshot.players);
+            let placed = if open {
+                spectate::place_bet(db, &runners, message_id, viewer, &choice).await
+            } else {
+                Err("Betting is closed for this round.".to_string())
+            };
+            note = match placed {
+                Ok(placed) => {
+                    self.betting
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .insert(message_id);
+                    placed
+                }
+                Err(e) => format!("⚠️ {}", e),
+            };
+        }
+        let (embed, components) = spectate::view(db, snapshot, &runners, message_id, viewer).await;
+        let response = CreateInteractionResponseMessage::new()
+            .content(note)
+            .embed(embed)
+            .components(components)
+            .ephemeral(true);
+        let response = if opening {
+            CreateInteractionResponse::Message(response)
+        } else {
+            CreateInteractionResponse::UpdateMessage(response)
+        };
+        if let Err(e) = interaction.create_response(&ctx.http, response).await {
+            println!("[GAME MANAGER] Error showing spectator view: {:?}", e);
+        }
+    }
+}
+
+/// Replaces a posted game's message with why it could not start, such as `start_game` refusing
+/// it, and returns any table stacks already bought in for it.
+pub async fn refuse_game(
+    ctx: &Context,
+    db: &PgPool,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    reason: &str,
+) {
+    if let Err(e) = table_stacks::cash_out_table(db, message_id).await {
+        println!("[DB] Failed to cash out table stacks: {:?}", e);
+    }
+    let builder = EditMessage::new()
+        .content(reason)
+        .embeds(vec![])
+        .components(vec![]);
+    if let Err(e) = channel_id
+        .edit_message(&ctx.http, message_id, builder)
+        .await
+    {
+        println!("[GAME MANAGER] Error editing refused game: {:?}", e);
+    }
+}
+
+/// Buys the host in and registers a posted table lobby with the game manager. If the host can't
+/// cover the buy-in, the lobby is closed instead; if the manager refuses the table, its message
+/// says why and the buy-in is returned.
+pub async fn open_table(
+    ctx: &Context,
+    app_state: &AppState,
+    mut game_msg: Message,
+    host_id: UserId,
+    buy_in: i64,
+    game: Box<dyn Game>,
+) {
+    if buy_in > 0
+        && let Err(e) = table_stacks::buy_in(&app_state.db, game_msg.id, host_id, buy_in).await
+    {
+        let builder = EditMessage::new()
+            .content(e)
+            .embeds(vec![])
+            .components(vec![]);
+        game_msg.edit(&ctx.http, builder).await.ok();
+        return;
+    }
+    let started = app_state
+        .game_manager
+        .start_game(game_msg.channel_id, game_msg.id, game);
+    if let Err(e) = started {
+        refuse_game(ctx, &app_state.db, game_msg.channel_id, game_msg.id, &e).await;
     }
 }
 
@@ -166,3 +838,33 @@ impl Default for GameManager {
         Self::new()
     }
 }
+
+/// Applies a finished game's payouts to the players' balances in one transaction, so either
+/// every payout lands or none do.
+async fn pay_out(
+    db: &PgPool,
+    message_id: MessageId,
+    payouts: &[GamePayout],
+) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let source = LedgerReason::Game.with_ref("message", message_id);
+    for payout in payouts.iter().filter(|p| p.amount != 0) {
+        let balance = sqlx::query_scalar!(
+            "UPDATE profiles SET balance = balance + $1 WHERE user_id = $2 RETURNING balance",
+            payout.amount,
+            payout.user_id.get() as i64
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        ledger::record(
+            &mut tx,
+            payout.user_id,
+            None,
+            payout.amount,
+            balance,
+            &source,
+        )
+        .await?;
+    }
+    tx.commit().await
+}
@@ -0,0 +1,120 @@
+//! Session lifecycle for the `GameManager`. Instead of every game spawning its own timer task,
+//! one sweeper checks each tracked game's clock: games whose `deadline` has passed get their
+//! `on_timeout`, and games nobody has played for `IDLE_LIMIT` are abandoned with their stakes
+//! refunded. Players may only have `MAX_GAMES_PER_USER` games open at once.
+
+use crate::AppState;
+use serenity::model::id::UserId;
+use serenity::prelude::Context;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+
+/// How many games a player may have open at once.
+pub const MAX_GAMES_PER_USER: usize = 3;
+/// A game nobody has played for this long is closed and its stakes refunded.
+pub const IDLE_LIMIT: Duration = Duration::from_secs(15 * 60);
+/// How often the sweeper checks the games' clocks.
+pub const SWEEP_INTERVAL: Duration = Duration::from_secs(2);
+/// How long a table's lobby waits for its host to deal the first hand.
+pub const TABLE_LOBBY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// When a session was started and when it last saw play, so games can time themselves without
+/// keeping a clock of their own.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionClock {
+    pub started: Instant,
+    pub last_active: Instant,
+}
+
+impl SessionClock {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            started: now,
+            last_active: now,
+        }
+    }
+
+    pub fn touch(&mut self, now: Instant) {
+        self.last_active = now;
+    }
+
+    pub fn is_idle(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_active) >= IDLE_LIMIT
+    }
+}
+
+/// Running counts of what has happened to sessions since the bot started.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionTotals {
+    pub started: u64,
+    /// Games refused because a player already had `MAX_GAMES_PER_USER` open.
+    pub refused: u64,
+    pub timed_out: u64,
+    pub abandoned: u64,
+}
+
+/// A snapshot of the games being tracked.
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetrics {
+    pub active: usize,
+    /// Distinct players across every open game.
+    pub players: usize,
+    /// The player with the most games open, and how many.
+    pub busiest: Option<(UserId, usize)>,
+    /// How long the oldest open game has been running.
+    pub oldest: Option<Duration>,
+    /// Open games nobody has played for over a minute.
+    pub quiet: usize,
+    pub totals: SessionTotals,
+}
+
+impl SessionMetrics {
+    /// One line per figure, for `/adminutil games`.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Active games: {} with {} player(s), {} quiet for over a minute",
+            self.active, self.players, self.quiet
+        )];
+        if let Some((user_id, count)) = self.busiest {
+            lines.push(format!(
+                "Busiest player: <@{}> in {} game(s)",
+                user_id, count
+            ));
+        }
+        if let Some(oldest) = self.oldest {
+            lines.push(format!(
+                "Oldest game: {}m {}s",
+                oldest.as_secs() / 60,
+                oldest.as_secs() % 60
+            ));
+        }
+        let t = self.totals;
+        lines.push(format!(
+            "Since startup: {} started, {} refused at the cap, {} timeouts, {} abandoned",
+            t.started, t.refused, t.timed_out, t.abandoned
+        ));
+        lines
+    }
+}
+
+static SWEEPER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Starts the sweeper. `ready` fires again on every reconnect, so only the first call spawns it.
+pub fn spawn_sweeper(ctx: &Context) {
+    if SWEEPER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SWEEP_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let Some(app_state) = AppState::from_ctx(&ctx).await else {
+                continue;
+            };
+            app_state.game_manager.sweep(&ctx.http, &app_state.db).await;
+        }
+    });
+}
@@ -8,10 +8,16 @@
 pub mod card;
 pub mod deck;
 pub mod engine;
+pub mod lifecycle;
+pub mod shoe;
+pub mod side_bets;
+pub mod spectate;
 
 // 2. Publicly re-export the most important components from the engine.
 //    This allows other parts of the code to write `use crate::commands::games::Game;`
 //    instead of the more verbose `use crate::commands::games::engine::Game;`.
 //    The `unused_imports` warning from clippy on this line is expected and can be ignored,
 //    as the purpose of this file is to export these items for external use.
-pub use engine::{Game, GameManager, GamePayout, GameUpdate};
+pub use engine::{
+    Game, GameManager, GamePayout, GameUpdate, TABLE_BUY_IN_BETS, open_table, refuse_game,
+};
@@ -0,0 +1,76 @@
+//! A multi-deck dealing shoe with a cut card.
+//!
+//! The cut card sits at a fixed penetration into the shoe. Reaching it never interrupts a
+//! round: the dealer finishes the hand and the table reshuffles before the next deal, so the
+//! shuffle point can't be steered by the cards still to come.
+
+use super::card::Card;
+use super::deck::Deck;
+use rand::seq::SliceRandom;
+
+pub struct Shoe {
+    cards: Vec<Card>,
+    discards: Vec<Card>,
+    decks: usize,
+    penetration: u8,
+    dealt: usize,
+}
+
+impl Shoe {
+    /// Builds and shuffles a shoe of `decks` decks whose cut card sits `penetration` percent
+    /// of the way in.
+    pub fn new(decks: usize, penetration: u8) -> Self {
+        let mut shoe = Self {
+            cards: Vec::new(),
+            discards: Vec::new(),
+            decks: decks.max(1),
+            penetration: penetration.clamp(1, 100),
+            dealt: 0,
+        };
+        shoe.reshuffle();
+        shoe
+    }
+
+    /// Gathers every card back into the shoe and shuffles it. Only call this between rounds,
+    /// once all hands have been discarded.
+    pub fn reshuffle(&mut self) {
+        self.cards = (0..self.decks)
+            .flat_map(|_| Deck::new().into_cards())
+            .collect();
+        self.cards.shuffle(&mut rand::rng());
+        self.discards.clear();
+        self.dealt = 0;
+    }
+
+    /// Deals one card. If the shoe runs dry mid-round, the discard tray is shuffled back in;
+    /// cards still on the table are never part of it.
+    pub fn deal_one(&mut self) -> Option<Card> {
+        if self.cards.is_empty() {
+            self.cards = std::mem::take(&mut self.discards);
+            self.cards.shuffle(&mut rand::rng());
+        }
+        let card = self.cards.pop()?;
+        self.dealt += 1;
+        Some(card)
+    }
+
+    /// Puts finished hands in the discard tray.
+    pub fn discard(&mut self, cards: impl IntoIterator<Item = Card>) {
+        self.discards.extend(cards);
+    }
+
+    /// Whether the cut card has come out, meaning the shoe is due a reshuffle before the
+    /// next round.
+    pub fn cut_card_reached(&self) -> bool {
+        self.dealt >= self.size() * self.penetration as usize / 100
+    }
+
+    /// The number of cards the shoe holds when full.
+    pub fn size(&self) -> usize {
+        self.decks * 52
+    }
+
+    pub fn cards_remaining(&self) -> usize {
+        self.cards.len()
+    }
+}
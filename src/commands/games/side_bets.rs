@@ -0,0 +1,67 @@
+//! Pure side-bet rules: the odds spectators are offered on each player and how a finished round
+//! picks the winning side. Nothing here touches Discord or the database.
+
+use super::GamePayout;
+use serenity::model::id::UserId;
+
+/// The stakes a spectator can put on a player.
+pub const STAKES: [i64; 3] = [50, 200, 1000];
+/// The house keeps this share of every fair price.
+pub const HOUSE_EDGE_PCT: f64 = 5.0;
+/// Odds are quoted in hundredths (185 pays 1.85× the stake) and kept within these bounds.
+pub const MIN_ODDS: i64 = 105;
+pub const MAX_ODDS: i64 = 1000;
+
+/// Each player's chance of winning from their ratings, Elo style: a 400 point lead makes a
+/// player ten times as likely to win as the player behind.
+pub fn win_chances(ratings: &[i32]) -> Vec<f64> {
+    let strengths: Vec<f64> = ratings
+        .iter()
+        .map(|&r| 10f64.powf(r as f64 / 400.0))
+        .collect();
+    let total: f64 = strengths.iter().sum();
+    strengths.iter().map(|s| s / total).collect()
+}
+
+/// The odds offered on a player with this chance of winning, less the house edge.
+pub fn odds_for(chance: f64) -> i64 {
+    if chance <= 0.0 {
+        return MAX_ODDS;
+    }
+    let fair = 100.0 / chance;
+    ((fair * (100.0 - HOUSE_EDGE_PCT) / 100.0).floor() as i64).clamp(MIN_ODDS, MAX_ODDS)
+}
+
+/// Odds for every player, in the order of `ratings`.
+pub fn odds_from_ratings(ratings: &[i32]) -> Vec<i64> {
+    win_chances(ratings).into_iter().map(odds_for).collect()
+}
+
+/// Odds for `players` who all have the same chance, such as the seats of a Hold'em hand before
+/// anyone has seen a card.
+pub fn even_odds(players: usize) -> Vec<i64> {
+    if players == 0 {
+        return Vec::new();
+    }
+    vec![odds_for(1.0 / players as f64); players]
+}
+
+/// What a winning bet returns, stake included.
+pub fn winnings(stake: i64, odds: i64) -> i64 {
+    stake * odds / 100
+}
+
+/// Whoever came out furthest ahead in a round's payouts. Nobody wins when no one finished up.
+pub fn top_earners(payouts: &[GamePayout]) -> Vec<UserId> {
+    let Some(best) = payouts.iter().map(|p| p.amount).max() else {
+        return Vec::new();
+    };
+    if best <= 0 {
+        return Vec::new();
+    }
+    payouts
+        .iter()
+        .filter(|p| p.amount == best)
+        .map(|p| p.user_id)
+        .collect()
+}
@@ -0,0 +1,214 @@
+//! Spectating and side bets. Anyone who isn't playing can open a read-only view of a running game
+//! from its 👁 Spectate button; the view is ephemeral, refreshes on demand, and while the game has
+//! betting open it offers side bets on its players. RPS duels are priced from the players' ladder
+//! ratings and tournament hands at even odds.
+//!
+//! The view is built from a `Snapshot` of the game, so pricing and rendering never hold the
+//! game's lock.
+//!
+//! Custom ids: `spectate_view` sits on the game's own message, while the buttons on the
+//! ephemeral view carry the game's message id (`spectate_refresh_<id>`, `spectate_bet_<id>`).
+
+use super::Game;
+use super::side_bets::{STAKES, even_odds, odds_from_ratings, winnings};
+use crate::commands::rps::ladder::{STARTING_RATING, season_at};
+use crate::database::{rps_ladder, side_bets};
+use crate::ui::buttons::Btn;
+use crate::ui::style::EMOJI_COIN;
+use chrono::Utc;
+use serenity::builder::{
+    CreateActionRow, CreateButton, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption,
+};
+use serenity::model::id::{MessageId, UserId};
+use serenity::prelude::Context;
+use sqlx::PgPool;
+
+/// The id of the Spectate button games put on their own message.
+pub const SPECTATE_VIEW: &str = "spectate_view";
+
+pub fn spectate_button() -> CreateButton {
+    Btn::secondary(SPECTATE_VIEW, "👁 Spectate")
+}
+
+/// A player spectators can back, with the odds on offer.
+pub struct Runner {
+    pub user_id: UserId,
+    pub name: String,
+    pub odds: i64,
+}
+
+/// What the spectator view needs from a game, taken while holding its lock.
+pub struct Snapshot {
+    pub embed: CreateEmbed,
+    /// The players open for side bets. Empty while betting is closed.
+    pub players: Vec<UserId>,
+    pub rated: bool,
+}
+
+impl Snapshot {
+    pub fn of(game: &dyn Game) -> Self {
+        let (_, embed, _) = game.render();
+        Self {
+            embed,
+            players: game.side_bet_players(),
+            rated: game.side_bets_rated(),
+        }
+    }
+}
+
+/// The players open for side bets, priced from their current ladder ratings or at even odds.
+/// Empty while betting is closed.
+pub async fn market(ctx: &Context, db: &PgPool, snapshot: &Snapshot) -> Vec<Runner> {
+    let players = &snapshot.players;
+    if players.len() < 2 {
+        return Vec::new();
+    }
+    let odds = if snapshot.rated {
+        let season = season_at(Utc::now());
+        let mut ratings = Vec::with_capacity(players.len());
+        for &user_id in players {
+            let rating = rps_ladder::get_rating(db, user_id, season)
+                .await
+                .map_or(STARTING_RATING, |r| r.rating);
+            ratings.push(rating);
+        }
+        odds_from_ratings(&ratings)
+    } else {
+        even_odds(players.len())
+    };
+    let mut runners = Vec::with_capacity(players.len());
+    for (&user_id, odds) in players.iter().zip(odds) {
+        let name = match user_id.to_user(ctx).await {
+            Ok(user) => user.global_name.unwrap_or(user.name),
+            Err(_) => user_id.to_string(),
+        };
+        runners.push(Runner {
+            user_id,
+            name,
+            odds,
+        });
+    }
+    runners
+}
+
+fn odds_label(odds: i64) -> String {
+    format!("{}.{:02}×", odds / 100, odds % 100)
+}
+
+/// The spectator's view of a game: its live embed without the players' controls, the side-bet
+/// book, and (while betting is open) a menu to place a bet on one of `runners`.
+pub async fn view(
+    db: &PgPool,
+    snapshot: Snapshot,
+    runners: &[Runner],
+    message_id: MessageId,
+    viewer: UserId,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let mut embed = snapshot.embed;
+    let bets = side_bets::open_bets(db, message_id)
+        .await
+        .unwrap_or_default();
+    let mut rows = Vec::new();
+
+    if !runners.is_empty() || !bets.is_empty() {
+        let mut lines: Vec<String> = runners
+            .iter()
+            .map(|r| {
+                let backed: i64 = bets
+                    .iter()
+                    .filter(|b| b.backed_id as u64 == r.user_id.get())
+                    .map(|b| b.stake)
+                    .sum();
+                format!(
+                    "<@{}> pays **{}** · {}{} backed",
+                    r.user_id,
+                    odds_label(r.odds),
+                    EMOJI_COIN,
+                    backed
+                )
+            })
+            .collect();
+        if runners.is_empty() {
+            lines.push("Betting is closed for this round.".to_string());
+        }
+        if let Some(mine) = bets.iter().find(|b| b.user_id as u64 == viewer.get()) {
+            lines.push(format!(
+                "Your bet: {}{} on <@{}> at {} (returns {}{})",
+                EMOJI_COIN,
+                mine.stake,
+                mine.backed_id,
+                odds_label(mine.odds as i64),
+                EMOJI_COIN,
+                winnings(mine.stake, mine.odds as i64)
+            ));
+        }
+        embed = embed.field("🎲 Side Bets", lines.join("\n"), false);
+    }
+
+    let viewer_playing = runners.iter().any(|r| r.user_id == viewer);
+    if !runners.is_empty() && !viewer_playing {
+        let options = runners
+            .iter()
+            .flat_map(|r| {
+                STAKES.iter().map(move |&stake| {
+                    CreateSelectMenuOption::new(
+                        format!(
+                            "{} · {}{} (returns {})",
+                            r.name,
+                            EMOJI_COIN,
+                            stake,
+                            winnings(stake, r.odds)
+                        ),
+                        format!("{}_{}", r.user_id, stake),
+                    )
+                })
+            })
+            .collect();
+        let menu = CreateSelectMenu::new(
+            format!("spectate_bet_{}", message_id),
+            CreateSelectMenuKind::String { options },
+        )
+        .placeholder("Place a side bet...");
+        rows.push(CreateActionRow::SelectMenu(menu));
+    }
+    rows.push(CreateActionRow::Buttons(vec![Btn::secondary(
+        &format!("spectate_refresh_{}", message_id),
+        "🔄 Refresh",
+    )]));
+    (embed, rows)
+}
+
+/// Places a side bet from the spectator menu. `choice` is the menu value, `<player>_<stake>`.
+/// The odds come from `runners`, priced again now rather than trusted from the menu.
+pub async fn place_bet(
+    db: &PgPool,
+    runners: &[Runner],
+    message_id: MessageId,
+    bettor: UserId,
+    choice: &str,
+) -> Result<String, String> {
+    let (backed, stake) = choice
+        .split_once('_')
+        .and_then(|(u, s)| Some((u.parse::<u64>().ok()?, s.parse::<i64>().ok()?)))
+        .filter(|(_, stake)| STAKES.contains(stake))
+        .ok_or("That bet isn't on offer.")?;
+    if runners.is_empty() {
+        return Err("Betting is closed for this round.".into());
+    }
+    if runners.iter().any(|r| r.user_id == bettor) {
+        return Err("Players can't bet on their own game.".into());
+    }
+    let runner = runners
+        .iter()
+        .find(|r| r.user_id.get() == backed)
+        .ok_or("That player isn't open for bets.")?;
+    side_bets::place(db, message_id, bettor, runner.user_id, stake, runner.odds).await?;
+    Ok(format!(
+        "🎲 You bet {}{} on {} at {}.",
+        EMOJI_COIN,
+        stake,
+        runner.name,
+        odds_label(runner.odds)
+    ))
+}
@@ -108,8 +108,8 @@ const COMMANDS: &[CommandInfo] = &[
     CommandInfo {
         name: "work",
         description: "Work a job to earn coins and resources.",
-        usage: &["work <job>", "w <job>"],
-        details: "Perform a job to earn rewards and XP. **Jobs:** `fishing`, `mining`, `coding`.",
+        usage: &["work <job>", "w <job>", "work"],
+        details: "Perform a job to earn rewards and XP. **Jobs:** `fishing`, `mining`, `coding`. Every job has its own level and cooldown, and unlocks perks as it levels (e.g. Mining 10: chance of double ore). Run `work` on its own for the job board.",
         category: CommandCategory::Economy,
     },
     CommandInfo {
@@ -123,14 +123,14 @@ const COMMANDS: &[CommandInfo] = &[
         name: "sell",
         description: "Sell items from your inventory.",
         usage: &["sell <item> [quantity]"],
-        details: "Sell items you've collected to earn coins. Sells the whole stack if quantity is omitted.",
+        details: "Sell items you've collected to earn coins. Sells the whole stack if quantity is omitted. Prices follow supply and demand, so dumping a large stack lowers the price you get.",
         category: CommandCategory::Economy,
     },
     CommandInfo {
         name: "shop",
         description: "Buy items from the bot.",
         usage: &["shop"],
-        details: "Opens an interactive shop menu to purchase items.",
+        details: "Opens an interactive shop menu to purchase items. Prices move with daily supply and demand; ▲/▼ show today's trend.",
         category: CommandCategory::Economy,
     },
     CommandInfo {
@@ -140,11 +140,54 @@ const COMMANDS: &[CommandInfo] = &[
         details: "Transfer an item from your inventory to another user.",
         category: CommandCategory::Economy,
     },
+    CommandInfo {
+        name: "market",
+        description: "Trade items with other players.",
+        usage: &[
+            "market browse [page]",
+            "market sell <item> <qty> <price> [auction_hours]",
+            "market buy <listing> [bid]",
+            "market cancel <listing>",
+        ],
+        details: "List tradeable items at a fixed price or as a timed auction. Listed items are held in escrow until sold, cancelled or expired (fixed listings last 72h), and auction bids are held from your balance until you are outbid. A market tax is taken from each sale.",
+        category: CommandCategory::Economy,
+    },
+    CommandInfo {
+        name: "bank",
+        description: "Save coins, earn interest and borrow.",
+        usage: &[
+            "bank",
+            "bank deposit <amount|all>",
+            "bank withdraw <amount|all>",
+            "bank loan <amount|max>",
+            "bank repay [amount]",
+        ],
+        details: "Coins in the bank earn daily interest but cannot be used for bets or purchases until withdrawn. Interest and the largest loan on offer grow with your account rank, which rises the longer you stay in good standing. Loan installments are collected daily from your wallet, then your bank; missed installments add penalties and three misses default the loan, seizing your bank balance and resetting your rank.",
+        category: CommandCategory::Economy,
+    },
+    CommandInfo {
+        name: "gambling",
+        description: "Gambling stats, loss limits and cooldowns.",
+        usage: &[
+            "gambling [@user]",
+            "gambling limit <amount|off>",
+            "gambling cooldown <12h|3d>",
+        ],
+        details: "Shows hands played, net result, biggest win and a per-game breakdown across blackjack, poker, Hold'em, RPS bets and tavern games. A daily loss limit stops you joining tables or betting once you've lost that much in a day; lowering it applies at once, while raising or removing it takes 24 hours. A cooldown blocks gambling entirely for up to 30 days and can't be shortened.",
+        category: CommandCategory::Economy,
+    },
+    CommandInfo {
+        name: "trade",
+        description: "Swap items and coins directly with another player.",
+        usage: &["trade @user"],
+        details: "Opens a shared trade window. Both players add items and coins to their side, then both must confirm; any change resets both confirmations. The swap happens all at once, so neither side can back out halfway. Windows expire after 5 minutes.",
+        category: CommandCategory::Economy,
+    },
     CommandInfo {
         name: "craft",
         description: "Craft new items from materials.",
-        usage: &["craft", "c"],
-        details: "Opens the crafting menu to create new items from resources.",
+        usage: &["craft", "c", "craft <item> [x10]"],
+        details: "Opens the crafting workshop with your queue and remaining times. Crafts take time and finish in the background; queue up to 3 batches of up to 10. Crafting levels up with use, speeding crafts and adding chances for Fine or Masterwork quality and for saving ingredients.",
         category: CommandCategory::Economy,
     },
     // Saga Commands
@@ -173,14 +216,14 @@ const COMMANDS: &[CommandInfo] = &[
         name: "contracts",
         description: "Manage human encounter contracts.",
         usage: &["contracts"],
-        details: "Shows human encounter progress, lets you draft contracts for ready humans and accept drafted contracts to recruit them.",
+        details: "Shows human encounter progress, lets you draft contracts for ready humans, negotiate their demands (daily wage, loot share, party slot) and accept drafted contracts to recruit them. Your tavern fame tier and extra defeats decide how far they bend.",
         category: CommandCategory::Saga,
     },
     CommandInfo {
         name: "research",
         description: "View or advance unit research bonuses.",
         usage: &["research"],
-        details: "Opens the research interface to see passive bonuses unlocked by collecting research data drops from battles.",
+        details: "Shows species research progress and the research tree. Completing research on a creature family opens its branch; completed species award research points to spend on passive nodes (pet damage, cheaper Taming Lures, research drop chance). Nodes list their prerequisites.",
         category: CommandCategory::Saga,
     },
     CommandInfo {
@@ -199,17 +242,23 @@ const COMMANDS: &[CommandInfo] = &[
     },
     CommandInfo {
         name: "open",
-        description: "Open loot or reward crates (if available).",
-        usage: &["open <crate>"],
-        details: "Opens a crate or reward container from your inventory and applies its contents.",
+        description: "Open geodes and other containers.",
+        usage: &["open <item> [count]", "open history"],
+        details: "Opens containers such as the Large Geode and rolls their loot table. Rare finds are guaranteed after a run of bad luck (pity), and `open history` lists your recent opens and pity progress.",
         category: CommandCategory::Saga,
     },
     // (✓) NEW: Added Quest Log to the help menu.
     CommandInfo {
         name: "party",
         description: "Manage your active party and army.",
-        usage: &["party", "army"],
-        details: "View all the units you own and set your active 5-member combat party.",
+        usage: &[
+            "party",
+            "army",
+            "party save <name>",
+            "party load <name>",
+            "party presets",
+        ],
+        details: "View all the units you own and set your active 5-member combat party. Save named presets (members + bonds) and swap them in atomically. Hired humans draw a daily wage and have morale; check both (and send gifts) from the Upkeep button. The Gear button equips weapons, armor and trinkets on your units; gear wears down in battle and is repaired at the Tavern's Small Arms shop.",
         category: CommandCategory::Saga,
     },
     CommandInfo {
@@ -231,21 +280,63 @@ const COMMANDS: &[CommandInfo] = &[
         name: "rps",
         description: "Challenge a user to Rock, Paper, Scissors.",
         usage: &["rps @user"],
-        details: "Starts a game of Rock, Paper, Scissors against another user.",
+        details: "Starts a game of Rock, Paper, Scissors against another user. Anyone else can press 👁 Spectate for a live view and, until the first round is played, back either player with a side bet at odds set by their ladder ratings.",
+        category: CommandCategory::Games,
+    },
+    CommandInfo {
+        name: "ladder",
+        description: "Ranked Rock Paper Scissors.",
+        usage: &[
+            "ladder [@user]",
+            "ladder queue",
+            "ladder leave",
+            "ladder bracket",
+        ],
+        details: "Queue to be paired with someone near your rating for a rated best-of-three; the accepted rating gap widens the longer you wait. Ratings use Elo and move faster for your first 10 games of a season. Seasons last 28 days, and each new season starts you halfway back toward 1200. Brackets are single elimination, seeded by rating, and every match is rated. The RPS Ladder leaderboard shows the current season.",
+        category: CommandCategory::Games,
+    },
+    CommandInfo {
+        name: "contest",
+        description: "Arm wrestling or darts in the tavern.",
+        usage: &[
+            "contest arm [bet] [unit]",
+            "contest darts @user [bet] [unit]",
+        ],
+        details: "Send a party member against the house champion, or challenge another player's unit. Arm wrestling is a 20 second duel: Push, Surge or Brace to drive the arm across while managing stamina, and whoever has it on their side when time runs out wins. Darts is six throws each at Bull, Treble 20 or Single 20; your aim drifts with every dart until you steady it. Strength (attack) and dexterity (defense) decide how hard you push and how tightly you throw. Beating the house in a free game pays a 25 coin purse; wagers go to the winner.",
         category: CommandCategory::Games,
     },
     CommandInfo {
         name: "blackjack",
         description: "Play a game of Blackjack.",
-        usage: &["blackjack <bet>", "bj <bet>"],
-        details: "Starts a game of Blackjack against the house. Try to get as close to 21 as possible without going over.",
+        usage: &[
+            "blackjack <bet> [vegas|friendly] [rules...]",
+            "bj <bet> [vegas|friendly] [rules...]",
+        ],
+        details: "Starts a game of Blackjack against the house. Try to get as close to 21 as possible without going over. With a minimum bet, joining buys you in for 20 bets in chips; rounds are settled against your chips and you cash out when you leave the table. Tables deal from a multi-deck shoe under Vegas or Friendly house rules (Friendly by default); tweak them with `h17`/`s17`, `das`/`nodas`, `rsa`/`norsa`, `surrender`/`nosurrender`, `insurance`/`noinsurance`, `3:2`/`6:5` or a deck count like `6d`.",
         category: CommandCategory::Games,
     },
     CommandInfo {
         name: "poker",
         description: "Play Five Card Draw poker.",
         usage: &["poker <bet>", "pk <bet>"],
-        details: "Starts a game of Five Card Draw poker against the dealer.",
+        details: "Starts a game of Five Card Draw poker against the dealer. Joining buys you in for 20 antes in chips; rounds are settled against your chips and you cash out when you leave the table.",
+        category: CommandCategory::Games,
+    },
+    CommandInfo {
+        name: "holdem",
+        description: "Play Texas Hold'em against other players.",
+        usage: &["holdem <big_blind>", "th <big_blind>"],
+        details: "Opens a Texas Hold'em table. Players buy in for 100 big blinds in chips and bet through the pre-flop, flop, turn and river; all-in players are covered by side pots. Use **My Cards** to see your hole cards privately.",
+        category: CommandCategory::Games,
+    },
+    CommandInfo {
+        name: "tournament",
+        description: "Run Hold'em and blackjack tournaments.",
+        usage: &[
+            "tournament create <holdem|blackjack> <buy_in>",
+            "tournament history",
+        ],
+        details: "Opens five minutes of registration for a tournament. Every buy-in goes into the prize pool and each player gets 1,000 chips; blinds or bets rise every five minutes and tables are rebalanced as players bust. The top finishers split the pool. Spectators can bet on a Hold'em hand until the flop.",
         category: CommandCategory::Games,
     },
     // Admin Commands
@@ -263,15 +354,21 @@ const COMMANDS: &[CommandInfo] = &[
             "adminutil cachestats",
             "adminutil markhuman <unit_id>",
             "adminutil bondhost <id> bondequip <id>",
+            "adminutil reloaditems",
+            "adminutil ledger <user>",
         ],
-        details: "Provides maintenance helpers: view global cache hit/miss counters, mark units as Human, perform a raw bond test pair, and inspect research progress.",
+        details: "Provides maintenance helpers: view global cache hit/miss counters, mark units as Human, perform a raw bond test pair, inspect research progress, reload item definitions from the database without a restart, and audit a user's recent coin and item movements against their balances.",
         category: CommandCategory::Admin,
     },
     CommandInfo {
         name: "config",
         description: "Bot runtime configuration (admin only).",
-        usage: &["config starter <unit_id>"],
-        details: "Adjusts live bot configuration values such as the starter unit id used in the saga tutorial.",
+        usage: &[
+            "config starter <unit_id>",
+            "config market_tax <percent>",
+            "config price_band <min_percent> <max_percent>",
+        ],
+        details: "Adjusts live bot configuration values such as the starter unit id used in the saga tutorial, the marketplace tax and the band dynamic item prices may move within.",
         category: CommandCategory::Admin,
     },
 ];
@@ -0,0 +1,315 @@
+//! The controller for a Texas Hold'em table. It implements the `Game` trait, deals one
+//! `Table` per hand with the button moving a seat each time, and settles every finished hand
+//! against the players' bought-in stacks. The table stays open until fewer than two players
+//! have chips left.
+
+use super::table::{BUY_IN_BB, HoldemAction, MIN_PLAYERS, PotResult, Table};
+use crate::commands::games::deck::Deck;
+use crate::commands::games::lifecycle::{SessionClock, TABLE_LOBBY_TIMEOUT};
+use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::database::models::GambleGame;
+use crate::database::table_stacks;
+use serenity::async_trait;
+use serenity::builder::{CreateActionRow, CreateEmbed};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
+use serenity::model::user::User;
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long the player to act has before they are checked or folded automatically.
+pub const TURN_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldemPhase {
+    Lobby,
+    Playing,
+    /// A hand is over and the table waits for the host to deal the next one.
+    HandOver,
+    Finished,
+}
+
+pub struct HoldemGame {
+    pub host_id: UserId,
+    /// Everyone seated, in seat order.
+    pub players: Vec<Arc<User>>,
+    pub big_blind: i64,
+    pub phase: HoldemPhase,
+    pub table: Option<Table>,
+    /// Each seated player's chips between hands, as the table's stacks last settled.
+    pub stacks: Vec<(UserId, i64)>,
+    pub hands_played: usize,
+    /// The summary of the last hand, or why the table closed.
+    pub summary: Option<String>,
+    /// False while the last hand's results still have to be applied to the chip stacks.
+    pub hand_settled: bool,
+    /// Why the table could not settle the hand or cash a player out, shown until it can.
+    pub table_error: Option<String>,
+    pub last_action_time: Instant,
+}
+
+#[async_trait]
+impl Game for HoldemGame {
+    async fn handle_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        // A stalled player is checked or folded before anyone else's click is processed.
+        if self.phase == HoldemPhase::Playing && self.last_action_time.elapsed() > TURN_TIMEOUT {
+            self.apply_timeout();
+            if let Some(update) = self.finish_if_over(db, interaction.message.id).await {
+                interaction.defer(&ctx.http).await.ok();
+                return update;
+            }
+        }
+
+        match self.phase {
+            HoldemPhase::Lobby => self.handle_lobby(ctx, interaction, db).await,
+            HoldemPhase::Playing => self.handle_action(ctx, interaction, db).await,
+            HoldemPhase::HandOver => self.handle_hand_over(ctx, interaction, db).await,
+            HoldemPhase::Finished => GameUpdate::NoOp,
+        }
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let content = match self.phase {
+            HoldemPhase::Lobby => "A new Texas Hold'em table has been opened!".to_string(),
+            _ => self
+                .players
+                .iter()
+                .map(|p| format!("<@{}>", p.id))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let (embed, components) = match self.phase {
+            HoldemPhase::Lobby => self.render_lobby(),
+            _ => self.render_table(),
+        };
+        (content, embed, components)
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.players.iter().map(|p| p.id).collect()
+    }
+
+    /// The lobby waits for the host to deal, then each player has `TURN_TIMEOUT` to act and
+    /// the host as long to deal the next hand.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        match self.phase {
+            HoldemPhase::Lobby => Some(clock.started + TABLE_LOBBY_TIMEOUT),
+            HoldemPhase::Playing | HoldemPhase::HandOver => {
+                Some(self.last_action_time + TURN_TIMEOUT)
+            }
+            HoldemPhase::Finished => None,
+        }
+    }
+
+    async fn on_timeout(&mut self, db: &PgPool, message_id: MessageId) -> GameUpdate {
+        match self.phase {
+            HoldemPhase::Lobby => {
+                let embed = CreateEmbed::new()
+                    .title("Hold'em Lobby Expired")
+                    .description("The game was not started by the host in time.")
+                    .color(0xFF0000); // Red
+                GameUpdate::Expired {
+                    content: "**Hold'em Lobby Expired**".to_string(),
+                    embed: Box::new(embed),
+                    components: vec![],
+                }
+            }
+            HoldemPhase::Playing => {
+                self.apply_timeout();
+                self.finish_if_over(db, message_id)
+                    .await
+                    .unwrap_or(GameUpdate::ReRender)
+            }
+            // Closing the table cashes out the stacks as they last settled, so a hand that
+            // never settled is void.
+            HoldemPhase::HandOver => {
+                let reason = if self.hand_settled {
+                    "The table closed because the next hand wasn't dealt in time."
+                } else {
+                    "The table closed before the last hand could be settled, so it was voided and every stack returned."
+                };
+                self.cancel(reason)
+            }
+            HoldemPhase::Finished => GameUpdate::NoOp,
+        }
+    }
+}
+
+impl HoldemGame {
+    pub fn new(host: Arc<User>, big_blind: i64) -> Self {
+        Self {
+            host_id: host.id,
+            players: vec![host],
+            big_blind,
+            phase: HoldemPhase::Lobby,
+            table: None,
+            stacks: Vec::new(),
+            hands_played: 0,
+            summary: None,
+            hand_settled: true,
+            table_error: None,
+            last_action_time: Instant::now(),
+        }
+    }
+
+    /// Chips each player buys in for when they join.
+    pub fn buy_in(&self) -> i64 {
+        self.big_blind * BUY_IN_BB
+    }
+
+    /// Shuffles a fresh deck and deals a hand to everyone with chips. The seats rotate each
+    /// hand so the button and the blinds move around the table.
+    pub fn start_hand(&mut self) {
+        let mut roster: Vec<(UserId, i64)> = self
+            .stacks
+            .iter()
+            .copied()
+            .filter(|&(_, chips)| chips > 0)
+            .collect();
+        let shift = self.hands_played % roster.len().max(1);
+        roster.rotate_left(shift);
+        let mut deck = Deck::new();
+        deck.shuffle();
+        self.table = Some(Table::new(roster, self.big_blind, deck));
+        self.hands_played += 1;
+        self.phase = HoldemPhase::Playing;
+        self.last_action_time = Instant::now();
+    }
+
+    pub(super) fn apply_action(
+        &mut self,
+        user_id: UserId,
+        action: HoldemAction,
+    ) -> Result<(), String> {
+        let table = self.table.as_mut().ok_or("The hand hasn't started yet.")?;
+        table.apply(user_id, action)?;
+        self.last_action_time = Instant::now();
+        Ok(())
+    }
+
+    fn apply_timeout(&mut self) {
+        let Some(table) = self.table.as_mut() else {
+            return;
+        };
+        if let Some(seat) = table.current_seat() {
+            let user_id = seat.user_id;
+            let action = table.timeout_action();
+            table.apply(user_id, action).ok();
+        }
+        self.last_action_time = Instant::now();
+    }
+
+    /// Settles the hand once the table reaches the showdown. The table then waits for the
+    /// next hand, or closes if fewer than two players have chips left.
+    pub(super) async fn finish_if_over(
+        &mut self,
+        db: &PgPool,
+        message_id: MessageId,
+    ) -> Option<GameUpdate> {
+        self.table.as_ref().filter(|t| t.is_finished())?;
+        self.summary = None;
+        self.phase = HoldemPhase::HandOver;
+        self.last_action_time = Instant::now();
+        if !self.settle_hand(db, message_id).await {
+            return Some(GameUpdate::ReRender);
+        }
+        Some(self.close_if_short())
+    }
+
+    /// Applies the finished hand to the players' chip stacks. If that fails the table says so
+    /// and the next hand waits until the host retries it successfully.
+    pub(super) async fn settle_hand(&mut self, db: &PgPool, message_id: MessageId) -> bool {
+        let Some(table) = &self.table else {
+            return true;
+        };
+        let (pots, net) = table.settle();
+        let results: Vec<GamePayout> = net
+            .into_iter()
+            .map(|(user_id, amount)| GamePayout { user_id, amount })
+            .collect();
+        match table_stacks::settle(db, message_id, GambleGame::Holdem, &results).await {
+            Ok(stacks) => {
+                for (user_id, chips) in self.stacks.iter_mut() {
+                    if let Some(&(_, settled)) = stacks.iter().find(|(u, _)| u == user_id) {
+                        *chips = settled;
+                    }
+                }
+                self.summary = Some(pot_lines(&pots));
+                self.hand_settled = true;
+                self.table_error = None;
+                true
+            }
+            Err(e) => {
+                tracing::error!(table = %message_id, error = ?e, "failed to settle hold'em hand");
+                self.hand_settled = false;
+                self.table_error = Some(
+                    "⚠️ This hand's chips could not be settled. Press Next Hand to try again."
+                        .to_string(),
+                );
+                false
+            }
+        }
+    }
+
+    pub(super) fn players_with_chips(&self) -> usize {
+        self.stacks.iter().filter(|&&(_, chips)| chips > 0).count()
+    }
+
+    /// Closes the table once fewer than two players have chips to play with; otherwise it
+    /// waits for the next hand.
+    pub(super) fn close_if_short(&mut self) -> GameUpdate {
+        if self.players_with_chips() >= MIN_PLAYERS {
+            return GameUpdate::ReRender;
+        }
+        let summary = self.summary.take().unwrap_or_default();
+        let reason = format!(
+            "{}\n\nFewer than {} players have chips left, so the table has closed.",
+            summary, MIN_PLAYERS
+        );
+        self.cancel(reason.trim_start())
+    }
+
+    /// Closes the table; the game manager cashes out whatever stacks are left.
+    pub(super) fn cancel(&mut self, reason: &str) -> GameUpdate {
+        self.phase = HoldemPhase::Finished;
+        self.summary = Some(reason.to_string());
+        GameUpdate::GameOver {
+            message: reason.to_string(),
+            payouts: vec![],
+        }
+    }
+}
+
+/// One line per pot naming who won it and with what.
+fn pot_lines(pots: &[PotResult]) -> String {
+    pots.iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let pot_name = if i == 0 {
+                "Main pot".to_string()
+            } else {
+                format!("Side pot {}", i)
+            };
+            let winners = result
+                .winners
+                .iter()
+                .map(|u| format!("<@{}>", u))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let hand = result
+                .hand
+                .as_ref()
+                .map(|h| format!(" with **{}**", super::ui::hand_name(h)))
+                .unwrap_or_default();
+            format!("{} (💰{}): {}{}", pot_name, result.amount, winners, hand)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
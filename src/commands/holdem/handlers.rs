@@ -0,0 +1,229 @@
+//! Handles all `ComponentInteraction` events for the Texas Hold'em game.
+
+use super::game::{HoldemGame, HoldemPhase};
+use super::table::{MAX_SEATS, MIN_PLAYERS};
+use crate::commands::games::GameUpdate;
+use crate::database::table_stacks;
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::UserId;
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+async fn send_ephemeral_response(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
+    let builder = CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true);
+    let response = CreateInteractionResponse::Message(builder);
+    interaction.create_response(&ctx.http, response).await.ok();
+}
+
+impl HoldemGame {
+    pub(super) async fn handle_lobby(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        match interaction.data.custom_id.as_str() {
+            "holdem_join" => {
+                if self.players.iter().any(|p| p.id == user_id) {
+                    send_ephemeral_response(ctx, interaction, "You have already joined.").await;
+                    return GameUpdate::NoOp;
+                }
+                if self.players.len() >= MAX_SEATS {
+                    send_ephemeral_response(ctx, interaction, "Sorry, this table is full.").await;
+                    return GameUpdate::NoOp;
+                }
+                if let Err(e) =
+                    table_stacks::buy_in(db, interaction.message.id, user_id, self.buy_in()).await
+                {
+                    send_ephemeral_response(ctx, interaction, &e).await;
+                    return GameUpdate::NoOp;
+                }
+                self.players.push(Arc::new(interaction.user.clone()));
+                interaction.defer(&ctx.http).await.ok();
+                GameUpdate::ReRender
+            }
+            "holdem_start" => {
+                if user_id != self.host_id {
+                    send_ephemeral_response(ctx, interaction, "Only the host can start the game.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                if self.players.len() < MIN_PLAYERS {
+                    send_ephemeral_response(
+                        ctx,
+                        interaction,
+                        &format!("At least {} players are needed to start.", MIN_PLAYERS),
+                    )
+                    .await;
+                    return GameUpdate::NoOp;
+                }
+                self.stacks = self.players.iter().map(|p| (p.id, self.buy_in())).collect();
+                self.start_hand();
+                interaction.defer(&ctx.http).await.ok();
+                self.finish_if_over(db, interaction.message.id)
+                    .await
+                    .unwrap_or(GameUpdate::ReRender)
+            }
+            "holdem_leave" => self.leave_table(ctx, interaction, db).await,
+            "holdem_cancel" => {
+                if user_id != self.host_id {
+                    send_ephemeral_response(ctx, interaction, "Only the host can cancel the game.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                interaction.defer(&ctx.http).await.ok();
+                self.cancel("Game cancelled by host.")
+            }
+            _ => GameUpdate::NoOp,
+        }
+    }
+
+    pub(super) async fn handle_action(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        let custom_id = interaction.data.custom_id.as_str();
+        if custom_id == "holdem_cards" {
+            let content = self.hole_cards_text(user_id);
+            send_ephemeral_response(ctx, interaction, &content).await;
+            return GameUpdate::NoOp;
+        }
+        let Some(action) = custom_id
+            .strip_prefix("holdem_")
+            .and_then(super::ui::action_for)
+        else {
+            return GameUpdate::NoOp;
+        };
+        if let Err(e) = self.apply_action(user_id, action) {
+            send_ephemeral_response(ctx, interaction, &e).await;
+            return GameUpdate::NoOp;
+        }
+        interaction.defer(&ctx.http).await.ok();
+        self.finish_if_over(db, interaction.message.id)
+            .await
+            .unwrap_or(GameUpdate::ReRender)
+    }
+
+    pub(super) async fn handle_hand_over(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        match interaction.data.custom_id.as_str() {
+            "holdem_next" => self.next_hand(ctx, interaction, db).await,
+            "holdem_leave" => self.leave_table(ctx, interaction, db).await,
+            _ => GameUpdate::NoOp,
+        }
+    }
+
+    /// Deals the next hand. Anyone who has run out of chips leaves first; a player whose
+    /// cash-out fails keeps their seat until it goes through.
+    async fn next_hand(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        if interaction.user.id != self.host_id {
+            send_ephemeral_response(ctx, interaction, "Only the host can deal the next hand.")
+                .await;
+            return GameUpdate::NoOp;
+        }
+        interaction.defer(&ctx.http).await.ok();
+        let table = interaction.message.id;
+        if !self.hand_settled && !self.settle_hand(db, table).await {
+            return GameUpdate::ReRender;
+        }
+        let busted: Vec<UserId> = self
+            .stacks
+            .iter()
+            .filter(|&&(_, chips)| chips == 0)
+            .map(|&(user_id, _)| user_id)
+            .collect();
+        let mut failed = Vec::new();
+        for user_id in busted {
+            match table_stacks::cash_out(db, table, user_id).await {
+                Ok(_) => self.unseat(user_id),
+                Err(e) => failed.push(format!("<@{}> ({})", user_id, e)),
+            }
+        }
+        if !failed.is_empty() {
+            self.table_error = Some(format!(
+                "⚠️ Could not cash out {}. Press Next Hand to try again.",
+                failed.join(", ")
+            ));
+            return GameUpdate::ReRender;
+        }
+        self.table_error = None;
+        if self.players_with_chips() < MIN_PLAYERS {
+            return self.close_if_short();
+        }
+        self.start_hand();
+        self.finish_if_over(db, table)
+            .await
+            .unwrap_or(GameUpdate::ReRender)
+    }
+
+    /// Cashes a player out before the first hand or between hands. The table closes once
+    /// fewer than two players are left to play, and the next player in line takes over as
+    /// host if the host leaves.
+    async fn leave_table(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        if !self.players.iter().any(|p| p.id == user_id) {
+            send_ephemeral_response(ctx, interaction, "You are not at this table.").await;
+            return GameUpdate::NoOp;
+        }
+        if !self.hand_settled {
+            send_ephemeral_response(
+                ctx,
+                interaction,
+                "This hand's chips haven't been settled yet. Wait for the host to retry.",
+            )
+            .await;
+            return GameUpdate::NoOp;
+        }
+        match table_stacks::cash_out(db, interaction.message.id, user_id).await {
+            Ok(chips) => {
+                let farewell = format!("You left the table and cashed out **💰{}**.", chips);
+                send_ephemeral_response(ctx, interaction, &farewell).await;
+            }
+            Err(e) => {
+                send_ephemeral_response(ctx, interaction, &e).await;
+                return GameUpdate::NoOp;
+            }
+        }
+        self.unseat(user_id);
+        if self.players.is_empty() {
+            return self.cancel("Everyone left the table.");
+        }
+        if self.phase == HoldemPhase::HandOver {
+            return self.close_if_short();
+        }
+        GameUpdate::ReRender
+    }
+
+    /// Removes a player who has cashed out, handing the host role on if they held it.
+    fn unseat(&mut self, user_id: UserId) {
+        self.players.retain(|p| p.id != user_id);
+        self.stacks.retain(|&(u, _)| u != user_id);
+        if user_id == self.host_id
+            && let Some(next) = self.players.first()
+        {
+            self.host_id = next.id;
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! This module contains the implementation for multiplayer Texas Hold'em.
+//!
+//! Players play against each other rather than the house: the table engine in `table` runs
+//! the blinds, betting rounds and side pots, while `game` adapts it to the `Game` trait.
+
+pub mod game;
+pub mod handlers;
+pub mod run;
+pub mod table;
+pub mod ui;
@@ -0,0 +1,131 @@
+//! This module contains the `run` functions for the Texas Hold'em command.
+
+use super::game::HoldemGame;
+use crate::AppState;
+use crate::commands::games::{Game, open_table};
+use crate::database::gambling;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage,
+};
+use serenity::model::application::{CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::prelude::*;
+use std::sync::Arc;
+use tracing::{instrument, warn};
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("holdem")
+        .description("Open a Texas Hold'em table where players bet against each other.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "big_blind",
+                "The big blind; the small blind is half of it.",
+            )
+            .required(true)
+            .min_int_value(1),
+        )
+}
+
+#[instrument(level = "info", skip(ctx, interaction), fields(user_id = interaction.user.id.get()))]
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        warn!(command = "holdem_slash", "missing_app_state");
+        return;
+    };
+    let response = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
+    if interaction
+        .create_response(&ctx.http, response)
+        .await
+        .is_err()
+    {
+        println!("[HOLDEM] Failed to defer slash command response.");
+        return;
+    }
+
+    let big_blind = interaction
+        .data
+        .options
+        .iter()
+        .find_map(|opt| {
+            if opt.name == "big_blind" {
+                opt.value.as_i64()
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0); // This is safe because the option is required.
+
+    if let Err(e) = gambling::check_can_play(&app_state.db, interaction.user.id).await {
+        let builder = serenity::builder::EditInteractionResponse::new().content(e);
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    }
+
+    let holdem_game = HoldemGame::new(Arc::new(interaction.user.clone()), big_blind);
+    let (content, embed, components) = holdem_game.render();
+    let builder = serenity::builder::EditInteractionResponse::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+
+    if let Ok(game_msg) = interaction.edit_response(&ctx.http, builder).await {
+        let (host_id, buy_in) = (holdem_game.host_id, holdem_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(holdem_game),
+        )
+        .await;
+    }
+}
+
+#[instrument(level = "info", skip(ctx, msg, args), fields(user_id = msg.author.id.get()))]
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        warn!(command = "holdem_prefix", "missing_app_state");
+        return;
+    };
+    let big_blind = match args.first().and_then(|arg| arg.parse::<i64>().ok()) {
+        Some(bet) if bet > 0 => bet,
+        _ => {
+            msg.reply(
+                ctx,
+                "You must specify a valid big blind to open a Hold'em table.",
+            )
+            .await
+            .ok();
+            return;
+        }
+    };
+
+    if let Err(e) = gambling::check_can_play(&app_state.db, msg.author.id).await {
+        msg.reply(ctx, e).await.ok();
+        return;
+    }
+
+    let holdem_game = HoldemGame::new(Arc::new(msg.author.clone()), big_blind);
+    let (content, embed, components) = holdem_game.render();
+    let builder = CreateMessage::new()
+        .content(content)
+        .embed(embed)
+        .components(components)
+        .reference_message(msg);
+
+    if let Ok(game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
+        let (host_id, buy_in) = (holdem_game.host_id, holdem_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(holdem_game),
+        )
+        .await;
+    }
+}
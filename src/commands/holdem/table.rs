@@ -0,0 +1,484 @@
+//! Texas Hold'em table rules: blinds, betting rounds, side pots and the showdown.
+//!
+//! The table only knows players by `UserId`, so the betting logic can be driven directly
+//! without Discord. A hand runs pre-flop → flop → turn → river; once a betting round is
+//! settled the next street is dealt, and when at most one player can still bet the remaining
+//! board is run out straight to the showdown.
+
+use crate::commands::games::card::Card;
+use crate::commands::games::deck::Deck;
+use crate::commands::poker::hand_eval::{BestHand, evaluate_best_hand};
+use serenity::model::id::UserId;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_SEATS: usize = 8;
+/// Chips each player buys in for, in big blinds.
+pub const BUY_IN_BB: i64 = 100;
+/// Action log lines kept for the table view.
+const LOG_LINES: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+impl Street {
+    pub fn label(self) -> &'static str {
+        match self {
+            Street::PreFlop => "Pre-flop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+            Street::Showdown => "Showdown",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Street::PreFlop => Street::Flop,
+            Street::Flop => Street::Turn,
+            Street::Turn => Street::River,
+            Street::River | Street::Showdown => Street::Showdown,
+        }
+    }
+
+    /// Board cards dealt when this street starts.
+    fn cards_dealt(self) -> usize {
+        match self {
+            Street::Flop => 3,
+            Street::Turn | Street::River => 1,
+            Street::PreFlop | Street::Showdown => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoldemAction {
+    Fold,
+    /// Checks when nothing is owed, otherwise calls (all-in if the stack is short).
+    CheckCall,
+    /// Raises by the minimum legal amount.
+    MinRaise,
+    /// Raises by the size of the pot after calling.
+    PotRaise,
+    AllIn,
+}
+
+#[derive(Debug, Clone)]
+pub struct Seat {
+    pub user_id: UserId,
+    pub stack: i64,
+    /// Chips put in during the current street.
+    pub street_bet: i64,
+    /// Chips put in over the whole hand.
+    pub contributed: i64,
+    pub hole: Vec<Card>,
+    pub folded: bool,
+    /// Whether the player has acted since the last raise on this street.
+    pub acted: bool,
+}
+
+impl Seat {
+    pub fn is_all_in(&self) -> bool {
+        !self.folded && self.stack == 0
+    }
+
+    fn can_bet(&self) -> bool {
+        !self.folded && self.stack > 0
+    }
+}
+
+/// A main or side pot and the players who can win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: i64,
+    pub eligible: Vec<UserId>,
+}
+
+/// How one pot was awarded.
+#[derive(Debug, Clone)]
+pub struct PotResult {
+    pub amount: i64,
+    pub winners: Vec<UserId>,
+    /// The winning hand, or `None` when everyone else folded.
+    pub hand: Option<BestHand>,
+}
+
+/// Splits everything put in this hand into a main pot and side pots. A player who is all-in
+/// for less than others can only win the part of each opponent's bet they matched.
+pub fn side_pots(seats: &[Seat]) -> Vec<Pot> {
+    let mut levels: Vec<i64> = seats
+        .iter()
+        .filter(|s| !s.folded)
+        .map(|s| s.contributed)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous = 0;
+    for &level in &levels {
+        let amount: i64 = seats
+            .iter()
+            .map(|s| s.contributed.min(level) - s.contributed.min(previous))
+            .sum();
+        let eligible: Vec<UserId> = seats
+            .iter()
+            .filter(|s| !s.folded && s.contributed >= level)
+            .map(|s| s.user_id)
+            .collect();
+        previous = level;
+        match pots.last_mut() {
+            Some(last) if last.eligible == eligible => last.amount += amount,
+            _ if amount > 0 => pots.push(Pot { amount, eligible }),
+            _ => {}
+        }
+    }
+    // Folded chips above the highest live bet still belong in the last pot.
+    let leftover: i64 = seats
+        .iter()
+        .map(|s| s.contributed - s.contributed.min(previous))
+        .sum();
+    if let Some(last) = pots.last_mut() {
+        last.amount += leftover;
+    }
+    pots
+}
+
+pub struct Table {
+    pub seats: Vec<Seat>,
+    pub board: Vec<Card>,
+    pub street: Street,
+    pub big_blind: i64,
+    /// Highest street bet any player has made on this street.
+    pub current_bet: i64,
+    /// Smallest legal raise increment.
+    pub min_raise: i64,
+    pub to_act: usize,
+    pub button: usize,
+    pub log: Vec<String>,
+    deck: Deck,
+}
+
+impl Table {
+    /// Seats the players (with their stacks) in order, deals hole cards and posts the blinds.
+    /// The first player holds the button.
+    pub fn new(players: Vec<(UserId, i64)>, big_blind: i64, mut deck: Deck) -> Self {
+        let seats = players
+            .into_iter()
+            .map(|(user_id, stack)| Seat {
+                user_id,
+                stack,
+                street_bet: 0,
+                contributed: 0,
+                hole: deck.deal(2).unwrap_or_default(),
+                folded: false,
+                acted: false,
+            })
+            .collect::<Vec<_>>();
+        let mut table = Self {
+            seats,
+            board: Vec::new(),
+            street: Street::PreFlop,
+            big_blind,
+            current_bet: 0,
+            min_raise: big_blind,
+            to_act: 0,
+            button: 0,
+            log: Vec::new(),
+            deck,
+        };
+        // Heads-up the button posts the small blind and acts first before the flop.
+        let (sb, bb) = if table.seats.len() == 2 {
+            (0, 1)
+        } else {
+            (1, 2 % table.seats.len())
+        };
+        let small_blind = (big_blind / 2).max(1);
+        table.put(sb, small_blind);
+        table.put(bb, big_blind);
+        table.current_bet = table.seats.iter().map(|s| s.street_bet).max().unwrap_or(0);
+        table.push_log(format!(
+            "<@{}> posts the small blind ({}), <@{}> the big blind ({}).",
+            table.seats[sb].user_id,
+            table.seats[sb].street_bet,
+            table.seats[bb].user_id,
+            table.seats[bb].street_bet
+        ));
+        table.to_act = bb;
+        table.advance();
+        table
+    }
+
+    pub fn pot_total(&self) -> i64 {
+        self.seats.iter().map(|s| s.contributed).sum()
+    }
+
+    pub fn seat_of(&self, user_id: UserId) -> Option<&Seat> {
+        self.seats.iter().find(|s| s.user_id == user_id)
+    }
+
+    pub fn current_seat(&self) -> Option<&Seat> {
+        (self.street != Street::Showdown).then(|| &self.seats[self.to_act])
+    }
+
+    /// Chips the player to act needs to put in to call.
+    pub fn to_call(&self) -> i64 {
+        self.current_seat()
+            .map_or(0, |s| (self.current_bet - s.street_bet).min(s.stack))
+    }
+
+    /// The total street bet a minimum raise makes, capped at the player's stack.
+    pub fn min_raise_to(&self) -> i64 {
+        self.capped(self.current_bet + self.min_raise)
+    }
+
+    /// The total street bet a pot-sized raise makes: call, then raise by the pot after calling.
+    pub fn pot_raise_to(&self) -> i64 {
+        let owed = self
+            .current_seat()
+            .map_or(0, |s| self.current_bet - s.street_bet);
+        self.capped(self.current_bet + self.pot_total() + owed)
+    }
+
+    fn capped(&self, target: i64) -> i64 {
+        self.current_seat()
+            .map_or(target, |s| target.min(s.street_bet + s.stack))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.street == Street::Showdown
+    }
+
+    /// The action taken for a player who ran out of time: check when free, otherwise fold.
+    pub fn timeout_action(&self) -> HoldemAction {
+        if self.to_call() == 0 {
+            HoldemAction::CheckCall
+        } else {
+            HoldemAction::Fold
+        }
+    }
+
+    /// Applies `action` for `user_id`, who must be the player to act.
+    pub fn apply(&mut self, user_id: UserId, action: HoldemAction) -> Result<(), String> {
+        let Some(seat) = self.current_seat() else {
+            return Err("This hand is over.".into());
+        };
+        if seat.user_id != user_id {
+            return Err("It's not your turn.".into());
+        }
+        let idx = self.to_act;
+        let to_call = self.current_bet - seat.street_bet;
+        let stack = seat.stack;
+        let max_total = seat.street_bet + stack;
+        match action {
+            HoldemAction::Fold => {
+                self.seats[idx].folded = true;
+                self.push_log(format!("<@{}> folds.", user_id));
+            }
+            HoldemAction::CheckCall if to_call == 0 => {
+                self.push_log(format!("<@{}> checks.", user_id));
+            }
+            HoldemAction::CheckCall => {
+                let paid = self.put(idx, to_call);
+                self.push_log(format!(
+                    "<@{}> calls {}{}.",
+                    user_id,
+                    paid,
+                    self.all_in_suffix(idx)
+                ));
+            }
+            HoldemAction::MinRaise | HoldemAction::PotRaise if stack <= to_call => {
+                return Err("You don't have enough chips to raise. Call or go all-in.".into());
+            }
+            HoldemAction::MinRaise => self.raise_to(idx, self.min_raise_to()),
+            HoldemAction::PotRaise => self.raise_to(idx, self.pot_raise_to()),
+            HoldemAction::AllIn if max_total <= self.current_bet => {
+                let paid = self.put(idx, stack);
+                self.push_log(format!("<@{}> calls {} and is all-in.", user_id, paid));
+            }
+            HoldemAction::AllIn => self.raise_to(idx, max_total),
+        }
+        self.seats[idx].acted = true;
+        self.advance();
+        Ok(())
+    }
+
+    /// Awards every pot and returns each player's net result for the hand.
+    pub fn settle(&self) -> (Vec<PotResult>, Vec<(UserId, i64)>) {
+        let live: Vec<&Seat> = self.seats.iter().filter(|s| !s.folded).collect();
+        let results = if let [winner] = live.as_slice() {
+            vec![PotResult {
+                amount: self.pot_total(),
+                winners: vec![winner.user_id],
+                hand: None,
+            }]
+        } else {
+            side_pots(&self.seats)
+                .into_iter()
+                .map(|pot| self.award(pot))
+                .collect()
+        };
+        let net = self
+            .seats
+            .iter()
+            .map(|seat| {
+                let won: i64 = results
+                    .iter()
+                    .map(|r| share_of(r, seat.user_id, &self.seats, self.button))
+                    .sum();
+                (seat.user_id, won - seat.contributed)
+            })
+            .collect();
+        (results, net)
+    }
+
+    /// The best hand `user_id` can make with the current board.
+    pub fn best_hand(&self, user_id: UserId) -> Option<BestHand> {
+        let seat = self.seat_of(user_id)?;
+        let cards: Vec<Card> = seat.hole.iter().chain(self.board.iter()).copied().collect();
+        (cards.len() >= 5).then(|| evaluate_best_hand(&cards))
+    }
+
+    fn award(&self, pot: Pot) -> PotResult {
+        let hands: Vec<(UserId, BestHand)> = pot
+            .eligible
+            .iter()
+            .filter_map(|&u| self.best_hand(u).map(|h| (u, h)))
+            .collect();
+        let best = hands.iter().map(|(_, h)| h).max().cloned();
+        let winners = hands
+            .iter()
+            .filter(|(_, h)| Some(h) == best.as_ref())
+            .map(|(u, _)| *u)
+            .collect();
+        PotResult {
+            amount: pot.amount,
+            winners,
+            hand: best,
+        }
+    }
+
+    /// Moves up to `amount` from a seat's stack into the pot and returns what was paid.
+    fn put(&mut self, idx: usize, amount: i64) -> i64 {
+        let seat = &mut self.seats[idx];
+        let paid = amount.min(seat.stack).max(0);
+        seat.stack -= paid;
+        seat.street_bet += paid;
+        seat.contributed += paid;
+        paid
+    }
+
+    fn raise_to(&mut self, idx: usize, target: i64) {
+        let seat = &self.seats[idx];
+        let target = target.min(seat.street_bet + seat.stack);
+        let increment = target - self.current_bet;
+        // A short all-in still reopens the action here, so everyone gets to respond to it.
+        if increment >= self.min_raise {
+            self.min_raise = increment;
+        }
+        self.current_bet = target;
+        let owed = target - self.seats[idx].street_bet;
+        self.put(idx, owed);
+        for (i, other) in self.seats.iter_mut().enumerate() {
+            if i != idx {
+                other.acted = false;
+            }
+        }
+        let user_id = self.seats[idx].user_id;
+        let line = format!(
+            "<@{}> raises to {}{}.",
+            user_id,
+            target,
+            self.all_in_suffix(idx)
+        );
+        self.push_log(line);
+    }
+
+    fn all_in_suffix(&self, idx: usize) -> &'static str {
+        if self.seats[idx].is_all_in() {
+            " and is all-in"
+        } else {
+            ""
+        }
+    }
+
+    fn needs_action(&self, idx: usize) -> bool {
+        let seat = &self.seats[idx];
+        if !seat.can_bet() {
+            return false;
+        }
+        let bettors = self.seats.iter().filter(|s| s.can_bet()).count();
+        if bettors <= 1 && seat.street_bet >= self.current_bet {
+            // Nobody left to bet against.
+            return false;
+        }
+        !seat.acted || seat.street_bet < self.current_bet
+    }
+
+    /// The next seat after `from` that still has to act on this street.
+    fn next_actor(&self, from: usize) -> Option<usize> {
+        let n = self.seats.len();
+        (1..=n)
+            .map(|step| (from + step) % n)
+            .find(|&i| self.needs_action(i))
+    }
+
+    /// Moves the action on, dealing new streets until someone has to act or the hand ends.
+    fn advance(&mut self) {
+        loop {
+            if self.seats.iter().filter(|s| !s.folded).count() <= 1 {
+                self.street = Street::Showdown;
+                return;
+            }
+            if let Some(next) = self.next_actor(self.to_act) {
+                self.to_act = next;
+                return;
+            }
+            self.street = self.street.next();
+            if self.street == Street::Showdown {
+                return;
+            }
+            for seat in &mut self.seats {
+                seat.street_bet = 0;
+                seat.acted = false;
+            }
+            self.current_bet = 0;
+            self.min_raise = self.big_blind;
+            // Burn one, then deal the street.
+            self.deck.deal_one();
+            let cards = self
+                .deck
+                .deal(self.street.cards_dealt())
+                .unwrap_or_default();
+            self.board.extend(cards);
+            // After the flop the first player left of the button acts first.
+            self.to_act = self.button;
+        }
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
+
+/// Chips `user_id` takes from a pot. Odd chips from a split go to the winners closest to the
+/// left of the button.
+fn share_of(result: &PotResult, user_id: UserId, seats: &[Seat], button: usize) -> i64 {
+    let Some(position) = result.winners.iter().position(|&u| u == user_id) else {
+        return 0;
+    };
+    let n = result.winners.len() as i64;
+    let mut order: Vec<usize> = (0..result.winners.len()).collect();
+    let seat_index = |u: UserId| seats.iter().position(|s| s.user_id == u).unwrap_or(0);
+    order
+        .sort_by_key(|&w| (seat_index(result.winners[w]) + seats.len() - button - 1) % seats.len());
+    let rank = order.iter().position(|&w| w == position).unwrap_or(0) as i64;
+    result.amount / n + i64::from(rank < result.amount % n)
+}
@@ -0,0 +1,288 @@
+//! Handles all rendering and UI logic for the Texas Hold'em game.
+
+use super::game::{HoldemGame, HoldemPhase};
+use super::table::{HoldemAction, MAX_SEATS, Table};
+use crate::commands::games::card::Card;
+use crate::commands::games::spectate::spectate_button;
+use crate::commands::poker::hand_eval::BestHand;
+use crate::commands::poker::state::HandRank;
+use crate::ui::buttons::Btn;
+use crate::ui::style::{COLOR_SAGA_MAP, COLOR_SAGA_TAVERN};
+use serenity::builder::{CreateActionRow, CreateEmbed, CreateEmbedFooter};
+use serenity::model::id::UserId;
+
+fn rank_name(rank: u8) -> &'static str {
+    match rank {
+        2 => "Twos",
+        3 => "Threes",
+        4 => "Fours",
+        5 => "Fives",
+        6 => "Sixes",
+        7 => "Sevens",
+        8 => "Eights",
+        9 => "Nines",
+        10 => "Tens",
+        11 => "Jacks",
+        12 => "Queens",
+        13 => "Kings",
+        _ => "Aces",
+    }
+}
+
+fn high_name(rank: u8) -> &'static str {
+    match rank {
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        14 => "Ace",
+        _ => "",
+    }
+}
+
+fn high_label(rank: u8) -> String {
+    match high_name(rank) {
+        "" => rank.to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// A readable name for a hand, e.g. "Two Pair, Kings and Fives".
+pub fn hand_name(hand: &BestHand) -> String {
+    match hand.rank {
+        HandRank::HighCard(r) => format!("{} High", high_label(r)),
+        HandRank::OnePair(r) => format!("Pair of {}", rank_name(r)),
+        HandRank::TwoPair(a, b) => format!("Two Pair, {} and {}", rank_name(a), rank_name(b)),
+        HandRank::ThreeOfAKind(r) => format!("Three {}", rank_name(r)),
+        HandRank::Straight(r) => format!("{}-high Straight", high_label(r)),
+        HandRank::Flush(r) => format!("{}-high Flush", high_label(r)),
+        HandRank::FullHouse(a, b) => format!("{} full of {}", rank_name(a), rank_name(b)),
+        HandRank::FourOfAKind(r) => format!("Four {}", rank_name(r)),
+        HandRank::StraightFlush(r) => format!("{}-high Straight Flush", high_label(r)),
+        HandRank::RoyalFlush => "Royal Flush".to_string(),
+    }
+}
+
+/// The betting buttons for the player to act. `prefix` is the custom id family, so tournament
+/// tables can reuse them; `action_for` maps the ids back.
+pub fn action_rows(table: &Table, prefix: &str) -> Vec<CreateActionRow> {
+    let Some(seat) = table.current_seat() else {
+        return Vec::new();
+    };
+    let to_call = table.to_call();
+    let check_label = if to_call == 0 {
+        "Check".to_string()
+    } else {
+        format!("Call {}", to_call)
+    };
+    let id = |action: &str| format!("{}_{}", prefix, action);
+    vec![
+        CreateActionRow::Buttons(vec![
+            Btn::secondary(&id("cards"), "🂠 My Cards"),
+            Btn::danger(&id("fold"), "Fold"),
+            Btn::success(&id("check"), &check_label),
+            spectate_button(),
+        ]),
+        CreateActionRow::Buttons(vec![
+            Btn::primary(
+                &id("raise_min"),
+                &format!("Raise to {}", table.min_raise_to()),
+            ),
+            Btn::primary(&id("raise_pot"), &format!("Pot ({})", table.pot_raise_to())),
+            Btn::danger(
+                &id("allin"),
+                &format!("All-in ({})", seat.street_bet + seat.stack),
+            ),
+        ]),
+    ]
+}
+
+/// The betting action for a button id with its family prefix stripped (e.g. `raise_min`).
+pub fn action_for(action: &str) -> Option<HoldemAction> {
+    match action {
+        "fold" => Some(HoldemAction::Fold),
+        "check" => Some(HoldemAction::CheckCall),
+        "raise_min" => Some(HoldemAction::MinRaise),
+        "raise_pot" => Some(HoldemAction::PotRaise),
+        "allin" => Some(HoldemAction::AllIn),
+        _ => None,
+    }
+}
+
+/// The private hole-card reveal shown to a seated player.
+pub fn hole_cards_text(table: &Table, user_id: UserId) -> String {
+    let Some(seat) = table.seat_of(user_id) else {
+        return "You aren't seated at this table.".to_string();
+    };
+    let mut text = format!("Your hole cards: **[ {} ]**", cards_text(&seat.hole));
+    if let Some(best) = table.best_hand(user_id) {
+        text.push_str(&format!(
+            "\nBest hand: **{}** ({})",
+            hand_name(&best),
+            cards_text(&best.cards)
+        ));
+    }
+    text
+}
+
+pub fn cards_text(cards: &[Card]) -> String {
+    if cards.is_empty() {
+        return "—".to_string();
+    }
+    cards
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl HoldemGame {
+    /// Embed field names don't render mentions, so seats are labelled by username.
+    fn player_name(&self, user_id: UserId) -> String {
+        self.players
+            .iter()
+            .find(|p| p.id == user_id)
+            .map_or_else(|| user_id.to_string(), |p| p.name.clone())
+    }
+
+    pub(super) fn hole_cards_text(&self, user_id: UserId) -> String {
+        match &self.table {
+            Some(table) => hole_cards_text(table, user_id),
+            None => "The hand hasn't been dealt yet.".to_string(),
+        }
+    }
+
+    pub(super) fn render_lobby(&self) -> (CreateEmbed, Vec<CreateActionRow>) {
+        let players_list = self
+            .players
+            .iter()
+            .map(|p| format!("<@{}>", p.id))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let embed = CreateEmbed::new()
+            .title("♠️ Texas Hold'em Lobby ♥️")
+            .description(format!(
+                "<@{}> has opened a Hold'em table with blinds of **💰{}/{}**.\nJoining buys you in for **💰{}** in chips; the table deals hand after hand, and whatever you hold when you leave goes back to your wallet.\n\n**Players ({}):**\n{}",
+                self.host_id,
+                (self.big_blind / 2).max(1),
+                self.big_blind,
+                self.buy_in(),
+                self.players.len(),
+                players_list
+            ))
+            .color(COLOR_SAGA_TAVERN)
+            .footer(CreateEmbedFooter::new(format!(
+                "Lobby expires in 2 minutes. {} players max.",
+                MAX_SEATS
+            )));
+        let buttons = vec![
+            Btn::success("holdem_join", "Join"),
+            Btn::danger("holdem_cancel", "Cancel (Host)"),
+            Btn::primary("holdem_start", "Start Game (Host)"),
+            Btn::secondary("holdem_leave", "Leave"),
+        ];
+        (embed, vec![CreateActionRow::Buttons(buttons)])
+    }
+
+    pub(super) fn render_table(&self) -> (CreateEmbed, Vec<CreateActionRow>) {
+        // Between hands the table shows the last hand's results, like a closed table does.
+        let finished = self.phase != HoldemPhase::Playing;
+        let Some(table) = &self.table else {
+            let embed = CreateEmbed::new()
+                .title("♠️ Texas Hold'em ♥️")
+                .description(self.summary.clone().unwrap_or_default())
+                .color(COLOR_SAGA_MAP);
+            return (embed, vec![]);
+        };
+
+        let title = if self.phase == HoldemPhase::HandOver {
+            format!("♠️ Texas Hold'em - Hand {} Results ♦️", self.hands_played)
+        } else if finished {
+            "♠️ Texas Hold'em - Results ♦️".to_string()
+        } else {
+            format!("♠️ Texas Hold'em - {} ♣️", table.street.label())
+        };
+        let color = if finished {
+            COLOR_SAGA_MAP
+        } else {
+            COLOR_SAGA_TAVERN
+        };
+        let mut embed = CreateEmbed::new()
+            .title(title)
+            .color(color)
+            .field("Board", format!("[ {} ]", cards_text(&table.board)), false)
+            .field("Pot", format!("💰{}", table.pot_total()), true);
+
+        // Hole cards are only shown to everyone if the hand went to a real showdown.
+        let showdown = finished
+            && table.board.len() == 5
+            && table.seats.iter().filter(|s| !s.folded).count() > 1;
+        for (idx, seat) in table.seats.iter().enumerate() {
+            let marker = if !finished && idx == table.to_act {
+                "▶️ "
+            } else {
+                ""
+            };
+            let dealer = if idx == table.button { " (D)" } else { "" };
+            let status = if seat.folded {
+                "**Folded**".to_string()
+            } else if seat.is_all_in() {
+                "**All-in**".to_string()
+            } else {
+                format!("Stack: `💰{}`", seat.stack)
+            };
+            let mut value = format!("{}\nBet: `💰{}`", status, seat.street_bet);
+            if showdown && !seat.folded {
+                value.push_str(&format!("\n[ {} ]", cards_text(&seat.hole)));
+                if let Some(best) = table.best_hand(seat.user_id) {
+                    value.push_str(&format!("\n`{}`", hand_name(&best)));
+                }
+            }
+            embed = embed.field(
+                format!("{}👤 {}{}", marker, self.player_name(seat.user_id), dealer),
+                value,
+                true,
+            );
+        }
+
+        if !table.log.is_empty() {
+            embed = embed.field("Action", table.log.join("\n"), false);
+        }
+
+        let mut components = Vec::new();
+        if self.phase == HoldemPhase::HandOver {
+            let mut description = self.summary.clone().unwrap_or_default();
+            if let Some(error) = &self.table_error {
+                description = format!("{}\n\n{}", description, error);
+            }
+            embed = embed
+                .description(description.trim_start().to_string())
+                .footer(CreateEmbedFooter::new(format!(
+                    "The table closes if <@{}> doesn't deal the next hand within 60 seconds.",
+                    self.host_id
+                )));
+            components.push(CreateActionRow::Buttons(vec![
+                Btn::primary("holdem_next", "Next Hand (Host)"),
+                Btn::secondary("holdem_leave", "Cash Out & Leave"),
+                spectate_button(),
+            ]));
+        } else if finished {
+            embed = embed.description(format!(
+                "**Final Results**\n\n{}",
+                self.summary.clone().unwrap_or_default()
+            ));
+            components.push(CreateActionRow::Buttons(vec![Btn::secondary(
+                crate::interactions::ids::SAGA_TAVERN_HOME,
+                "🏰 Tavern",
+            )]));
+            components.push(crate::commands::saga::ui::global_nav_row("saga"));
+        } else if let Some(seat) = table.current_seat() {
+            embed = embed.footer(CreateEmbedFooter::new(format!(
+                "Current bet: {}. Players who don't act within 60 seconds check or fold.",
+                table.current_bet
+            )));
+            embed = embed.description(format!("It's <@{}>'s turn.", seat.user_id));
+            components.extend(action_rows(table, "holdem"));
+        }
+        (embed, components)
+    }
+}
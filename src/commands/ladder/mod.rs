@@ -0,0 +1,6 @@
+//! Implements `/ladder`: the ranked Rock Paper Scissors ladder. Players queue for a rated match
+//! against someone near their rating, check their seasonal rating, or open a bracket. The rating
+//! rules live in `commands::rps::ladder`.
+
+pub mod run;
+pub mod ui;
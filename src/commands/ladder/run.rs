@@ -0,0 +1,273 @@
+//! Handles the command logic for `/ladder` and `$ladder`.
+
+use super::ui::create_rating_embed;
+use crate::AppState;
+use crate::commands::games::Game;
+use crate::commands::rps::game::RpsGame;
+use crate::commands::rps::ladder::{QUEUE_TIMEOUT_MINUTES, season_at};
+use crate::commands::rps::lobby::BracketLobby;
+use crate::commands::rps::state::{GameState, MatchKind};
+use crate::database::models::RpsQueueEntry;
+use crate::database::rps_ladder::{self, QueueOutcome};
+use chrono::Utc;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage, EditInteractionResponse,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, UserId};
+use serenity::model::user::User;
+use serenity::prelude::*;
+use std::sync::Arc;
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("ladder")
+        .description("Ranked Rock Paper Scissors.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "rating",
+                "Show a ladder rating for this season",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::User, "user", "Whose rating to show")
+                    .required(false),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "queue",
+            "Find an opponent near your rating",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "leave",
+            "Leave the ranked queue",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "bracket",
+            "Open a sign-up for a ranked bracket",
+        ))
+}
+
+/// A parsed `/ladder` or `$ladder` request.
+enum LadderAction {
+    Rating,
+    Queue,
+    Leave,
+    Bracket,
+}
+
+const USAGE: &str =
+    "Usage: `$ladder [rating] [@user]`, `$ladder queue`, `$ladder leave`, `$ladder bracket`";
+
+fn parse_action(sub: &str) -> Result<LadderAction, String> {
+    match sub {
+        "rating" | "rank" => Ok(LadderAction::Rating),
+        "queue" | "find" => Ok(LadderAction::Queue),
+        "leave" => Ok(LadderAction::Leave),
+        "bracket" => Ok(LadderAction::Bracket),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// What to tell the player: a line of text or their rating card.
+enum Reply {
+    Text(String),
+    Embed(Box<CreateEmbed>),
+}
+
+async fn build_rating(app_state: &AppState, target: &User) -> Result<CreateEmbed, String> {
+    let season = season_at(Utc::now());
+    let rating = rps_ladder::get_rating(&app_state.db, target.id, season)
+        .await
+        .map_err(|_| "Could not load the ladder rating.".to_string())?;
+    let rank = rps_ladder::rank(&app_state.db, target.id, season)
+        .await
+        .map_err(|_| "Could not load the ladder rating.".to_string())?;
+    Ok(create_rating_embed(&target.name, &rating, rank))
+}
+
+/// Posts a ranked match between a newly queued player and the opponent they were paired with,
+/// pointing the opponent to it if they queued somewhere else.
+async fn post_ranked_match(
+    ctx: &Context,
+    app_state: &AppState,
+    channel_id: ChannelId,
+    player: &User,
+    opponent: &RpsQueueEntry,
+) -> Result<String, String> {
+    let opponent_user = UserId::new(opponent.user_id as u64)
+        .to_user(&ctx.http)
+        .await
+        .map_err(|_| "Your opponent could not be found.".to_string())?;
+    let game = RpsGame {
+        state: GameState::rated(
+            Arc::new(opponent_user),
+            Arc::new(player.clone()),
+            MatchKind::Ranked,
+        ),
+    };
+    let (content, embed, components) = game.render();
+    let builder = CreateMessage::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+    let msg = channel_id
+        .send_message(&ctx.http, builder)
+        .await
+        .map_err(|_| "Failed to post the ranked match.".to_string())?;
+    app_state
+        .game_manager
+        .start_scheduled_game(channel_id, msg.id, Box::new(game));
+    let opponent_channel = ChannelId::new(opponent.channel_id as u64);
+    if opponent_channel != channel_id {
+        let ping = CreateMessage::new().content(format!(
+            "<@{}>, your ranked match against <@{}> is ready: {}",
+            opponent.user_id,
+            player.id,
+            msg.link()
+        ));
+        opponent_channel.send_message(&ctx.http, ping).await.ok();
+    }
+    Ok(format!(
+        "⚔️ Match found! You're up against <@{}> (rating {}).",
+        opponent.user_id, opponent.rating
+    ))
+}
+
+/// Opens a bracket lobby in the channel; it closes itself if the host never starts it.
+async fn open_bracket(
+    ctx: &Context,
+    app_state: &AppState,
+    channel_id: ChannelId,
+    host: &User,
+) -> Result<String, String> {
+    let lobby = BracketLobby::new(Arc::new(host.clone()));
+    let (content, embed, components) = lobby.render();
+    let builder = CreateMessage::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+    let msg = channel_id
+        .send_message(&ctx.http, builder)
+        .await
+        .map_err(|_| "Failed to open the bracket.".to_string())?;
+    let started = app_state
+        .game_manager
+        .start_game(channel_id, msg.id, Box::new(lobby));
+    if let Err(e) = started {
+        channel_id.delete_message(&ctx.http, msg.id).await.ok();
+        return Err(e);
+    }
+    Ok("🏟️ Bracket sign-up is open!".to_string())
+}
+
+async fn run_action(
+    ctx: &Context,
+    app_state: &AppState,
+    channel_id: ChannelId,
+    user: &User,
+    target: Option<User>,
+    action: LadderAction,
+) -> Reply {
+    let result = match action {
+        LadderAction::Rating => {
+            let target = target.unwrap_or_else(|| user.clone());
+            match build_rating(app_state, &target).await {
+                Ok(embed) => return Reply::Embed(Box::new(embed)),
+                Err(e) => Err(e),
+            }
+        }
+        LadderAction::Queue => {
+            match rps_ladder::join_queue(&app_state.db, user.id, channel_id).await {
+                Ok(QueueOutcome::Queued(rating)) => Ok(format!(
+                    "🔎 You're in the ranked queue at rating **{}**. You'll be pinged when an opponent near your rating queues; the search gets wider the longer you wait and stops after {} minutes.",
+                    rating, QUEUE_TIMEOUT_MINUTES
+                )),
+                Ok(QueueOutcome::Matched(opponent)) => {
+                    post_ranked_match(ctx, app_state, channel_id, user, &opponent).await
+                }
+                Err(e) => Err(e),
+            }
+        }
+        LadderAction::Leave => match rps_ladder::leave_queue(&app_state.db, user.id).await {
+            Ok(true) => Ok("You've left the ranked queue.".to_string()),
+            Ok(false) => Ok("You're not in the ranked queue.".to_string()),
+            Err(_) => Err("Failed to leave the queue.".to_string()),
+        },
+        LadderAction::Bracket => open_bracket(ctx, app_state, channel_id, user).await,
+    };
+    Reply::Text(result.unwrap_or_else(|e| e))
+}
+
+/// The `user` option of `/ladder rating`, if given.
+fn slash_target(interaction: &CommandInteraction) -> Option<User> {
+    let CommandDataOptionValue::SubCommand(nested) = &interaction.data.options.first()?.value
+    else {
+        return None;
+    };
+    let id = nested
+        .iter()
+        .find(|o| o.name == "user")?
+        .value
+        .as_user_id()?;
+    interaction.data.resolved.users.get(&id).cloned()
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    interaction.defer_ephemeral(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let sub = interaction
+        .data
+        .options
+        .first()
+        .map_or("rating", |o| o.name.as_str());
+    let builder = match parse_action(sub) {
+        Ok(action) => {
+            let target = slash_target(interaction);
+            match run_action(
+                ctx,
+                &app_state,
+                interaction.channel_id,
+                &interaction.user,
+                target,
+                action,
+            )
+            .await
+            {
+                Reply::Text(text) => EditInteractionResponse::new().content(text),
+                Reply::Embed(embed) => EditInteractionResponse::new().embed(*embed),
+            }
+        }
+        Err(e) => EditInteractionResponse::new().content(e),
+    };
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let sub = args
+        .first()
+        .filter(|arg| !arg.starts_with("<@"))
+        .map(|s| s.to_lowercase());
+    let builder = match parse_action(sub.as_deref().unwrap_or("rating")) {
+        Ok(action) => {
+            let target = msg.mentions.first().cloned();
+            match run_action(ctx, &app_state, msg.channel_id, &msg.author, target, action).await {
+                Reply::Text(text) => CreateMessage::new().content(text),
+                Reply::Embed(embed) => CreateMessage::new().embed(*embed),
+            }
+        }
+        Err(e) => CreateMessage::new().content(e),
+    };
+    msg.channel_id
+        .send_message(&ctx.http, builder.reference_message(msg))
+        .await
+        .ok();
+}
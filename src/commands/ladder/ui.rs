@@ -0,0 +1,48 @@
+//! Handles embed creation for the `/ladder` command.
+
+use crate::commands::rps::ladder::{PROVISIONAL_GAMES, season_ends};
+use crate::database::models::RpsRating;
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+pub fn create_rating_embed(name: &str, rating: &RpsRating, rank: Option<i64>) -> CreateEmbed {
+    let games = rating.games();
+    let win_rate = if games > 0 {
+        format!(" ({}%)", rating.wins * 100 / games)
+    } else {
+        String::new()
+    };
+    let mut description = format!(
+        "Season **{}** ends <t:{}:R>.",
+        rating.season,
+        season_ends(rating.season).timestamp()
+    );
+    if games < PROVISIONAL_GAMES {
+        description.push_str(&format!(
+            "\n*Provisional: ratings move faster for the first {} games of a season.*",
+            PROVISIONAL_GAMES
+        ));
+    }
+    CreateEmbed::new()
+        .title(format!("✊ RPS Ladder — {}", name))
+        .description(description)
+        .field(
+            "Rating",
+            format!("**{}** (peak {})", rating.rating, rating.peak),
+            true,
+        )
+        .field(
+            "Rank",
+            rank.map_or("Unranked".to_string(), |r| format!("#{}", r)),
+            true,
+        )
+        .field(
+            "Record",
+            format!("{}W – {}L{}", rating.wins, rating.losses, win_rate),
+            true,
+        )
+        .field("Brackets Won", rating.brackets_won.to_string(), true)
+        .color(0x5865F2)
+        .footer(CreateEmbedFooter::new(
+            "Find a match with /ladder queue · run a bracket with /ladder bracket",
+        ))
+}
@@ -71,5 +71,11 @@ pub fn create_leaderboard_buttons(current_board: LeaderboardType) -> CreateActio
         Btn::secondary("leaderboard_streak", "Work Streak")
     };
 
-    CreateActionRow::Buttons(vec![gm, wealth, streak])
+    let rps = if current_board == LeaderboardType::RpsLadder {
+        Btn::primary("leaderboard_rps", "RPS Ladder")
+    } else {
+        Btn::secondary("leaderboard_rps", "RPS Ladder")
+    };
+
+    CreateActionRow::Buttons(vec![gm, wealth, streak, rps])
 }
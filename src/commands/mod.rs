@@ -11,6 +11,8 @@ pub mod craft;
 pub mod economy;
 pub mod games;
 pub mod help;
+pub mod holdem;
+pub mod ladder;
 pub mod leaderboard;
 pub mod open;
 pub mod party;
@@ -24,4 +26,6 @@ pub mod research;
 pub mod rps;
 pub mod saga;
 pub mod tasks;
+pub mod tavern_games;
+pub mod tournament;
 pub mod train;
@@ -1,4 +1,5 @@
 //! Implements the `/open` command.
-//! This command will be used for items that can be opened, like geodes.
+//! Containers such as geodes roll their data-defined loot table when opened.
 
 pub mod run;
+pub mod ui;
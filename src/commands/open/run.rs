@@ -1,38 +1,177 @@
 //! Handles the command logic for `/open` and `$open`.
 
+use super::ui::{create_history_embed, create_result_embed, reveal_frames};
+use crate::AppState;
+use crate::commands::economy::core::registry::{ItemDef, registry};
+use crate::database::loot::{self, MAX_OPENS_PER_COMMAND, OpenOutcome};
 use serenity::builder::{
-    CreateCommand, CreateCommandOption, CreateInteractionResponse,
-    CreateInteractionResponseMessage, CreateMessage,
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage, EditInteractionResponse,
+    EditMessage,
 };
-use serenity::model::application::{CommandInteraction, CommandOptionType};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
+use serenity::model::id::UserId;
 use serenity::prelude::*;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Pause between reveal frames.
+const REVEAL_FRAME_MS: u64 = 900;
+/// Opens shown by `/open history`.
+const HISTORY_LIMIT: i64 = 10;
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("open")
         .description("Open an item from your inventory to see what's inside.")
         .add_option(
             CreateCommandOption::new(
-                CommandOptionType::String,
+                CommandOptionType::SubCommand,
                 "item",
-                "The item you want to open.",
+                "Open a container such as a Large Geode",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "item",
+                    "The item you want to open.",
+                )
+                .required(true),
             )
-            .required(true)
-            .add_string_choice("Large Geode", "large_geode"),
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "count", "How many to open")
+                    .required(false)
+                    .min_int_value(1)
+                    .max_int_value(MAX_OPENS_PER_COMMAND as u64),
+            ),
         )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "history",
+            "Show what you found in your recent opens",
+        ))
+}
+
+/// Opens the containers, returning the item and outcome or a user-facing error.
+async fn open(
+    db: &PgPool,
+    user_id: UserId,
+    item: &str,
+    count: i64,
+) -> Result<(ItemDef, OpenOutcome), String> {
+    let container = registry()
+        .resolve(item)
+        .cloned()
+        .ok_or_else(|| format!("'{}' is not a known item.", item))?;
+    let outcome = loot::open_containers(db, user_id, &container, count).await?;
+    Ok((container, outcome))
+}
+
+async fn history_embed(db: &PgPool, user_id: UserId) -> CreateEmbed {
+    let history = loot::recent_history(db, user_id, HISTORY_LIMIT)
+        .await
+        .unwrap_or_default();
+    let pity = loot::pity_status(db, user_id).await.unwrap_or_default();
+    create_history_embed(&history, &pity)
 }
 
 pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
-    let response_msg = CreateInteractionResponseMessage::new()
-        .content("This feature is coming soon! For now, your geode remains a mystery...")
-        .ephemeral(true);
-    let response = CreateInteractionResponse::Message(response_msg);
-    interaction.create_response(&ctx.http, response).await.ok();
+    interaction.defer(&ctx.http).await.ok();
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let user_id = interaction.user.id;
+    let Some(sub) = interaction.data.options.first() else {
+        return;
+    };
+    let CommandDataOptionValue::SubCommand(nested) = &sub.value else {
+        return;
+    };
+    if sub.name == "history" {
+        let builder = EditInteractionResponse::new().embed(history_embed(db, user_id).await);
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    }
+    let item = nested
+        .iter()
+        .find(|o| o.name == "item")
+        .and_then(|o| o.value.as_str())
+        .unwrap_or_default();
+    let count = nested
+        .iter()
+        .find(|o| o.name == "count")
+        .and_then(|o| o.value.as_i64())
+        .unwrap_or(1);
+
+    match open(db, user_id, item, count).await {
+        Ok((container, outcome)) => {
+            for frame in reveal_frames(&container, count) {
+                let builder = EditInteractionResponse::new().content(frame);
+                interaction.edit_response(&ctx.http, builder).await.ok();
+                tokio::time::sleep(Duration::from_millis(REVEAL_FRAME_MS)).await;
+            }
+            let builder = EditInteractionResponse::new()
+                .content("")
+                .embed(create_result_embed(&container, &outcome));
+            interaction.edit_response(&ctx.http, builder).await.ok();
+        }
+        Err(e) => {
+            let builder = EditInteractionResponse::new().content(e);
+            interaction.edit_response(&ctx.http, builder).await.ok();
+        }
+    }
 }
 
-pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
-    let builder = CreateMessage::new()
-        .content("This feature is coming soon! For now, your geode remains a mystery...")
-        .reference_message(msg);
-    msg.channel_id.send_message(&ctx.http, builder).await.ok();
+/// `$open <item> [count]` or `$open history`
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let db = &app_state.db;
+    let Some(&first) = args.first() else {
+        msg.reply(ctx, "Usage: `$open <item> [count]` or `$open history`")
+            .await
+            .ok();
+        return;
+    };
+    if first.eq_ignore_ascii_case("history") {
+        let builder = CreateMessage::new()
+            .embed(history_embed(db, msg.author.id).await)
+            .reference_message(msg);
+        msg.channel_id.send_message(&ctx.http, builder).await.ok();
+        return;
+    }
+    // A trailing number is the count; everything before it is the item name.
+    let (item_args, count) = match args.last().and_then(|a| a.parse::<i64>().ok()) {
+        Some(n) if args.len() > 1 => (&args[..args.len() - 1], n),
+        _ => (&args[..], 1),
+    };
+    let item = item_args.join(" ");
+
+    match open(db, msg.author.id, &item, count).await {
+        Ok((container, outcome)) => {
+            let frames = reveal_frames(&container, count);
+            let builder = CreateMessage::new()
+                .content(frames[0].clone())
+                .reference_message(msg);
+            let Ok(mut reply) = msg.channel_id.send_message(&ctx.http, builder).await else {
+                return;
+            };
+            for frame in &frames[1..] {
+                tokio::time::sleep(Duration::from_millis(REVEAL_FRAME_MS)).await;
+                reply
+                    .edit(&ctx.http, EditMessage::new().content(frame))
+                    .await
+                    .ok();
+            }
+            tokio::time::sleep(Duration::from_millis(REVEAL_FRAME_MS)).await;
+            let builder = EditMessage::new()
+                .content("")
+                .embed(create_result_embed(&container, &outcome));
+            reply.edit(&ctx.http, builder).await.ok();
+        }
+        Err(e) => {
+            msg.reply(ctx, e).await.ok();
+        }
+    }
 }
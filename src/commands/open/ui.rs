@@ -0,0 +1,133 @@
+//! Handles embed creation for the `/open` command.
+
+use crate::commands::economy::core::loot::{LootDrop, LootRoll};
+use crate::commands::economy::core::registry::{ItemDef, registry};
+use crate::database::loot::{LootHistoryEntry, OpenOutcome};
+use serenity::builder::{CreateEmbed, CreateEmbedFooter};
+
+const COLOR_OPEN: u32 = 0x95A5A6;
+const COLOR_RARE: u32 = 0xF1C40F;
+
+/// Message edits played before the loot is revealed.
+pub fn reveal_frames(container: &ItemDef, count: i64) -> [String; 3] {
+    let what = if count > 1 {
+        format!("{} x{}", container.name, count)
+    } else {
+        container.name.clone()
+    };
+    [
+        format!("⛏️ You set your **{}** on the anvil...", what),
+        "🔨 *Crack!* A fissure splits the stone...".to_string(),
+        "✨ Something glints inside...".to_string(),
+    ]
+}
+
+fn describe_drop(drop: &LootDrop) -> String {
+    let rare = if drop.is_rare { "🌟 " } else { "" };
+    match drop.item_id {
+        None => format!("{}💰 {}", rare, drop.quantity),
+        Some(id) => {
+            let items = registry();
+            let emoji = items.get(id).map(|d| d.emoji.clone()).unwrap_or_default();
+            format!("{}{} {} x{}", rare, emoji, items.name_of(id), drop.quantity)
+        }
+    }
+}
+
+fn describe_roll(roll: &LootRoll) -> String {
+    if roll.drops.is_empty() {
+        return "Just dust.".to_string();
+    }
+    roll.drops
+        .iter()
+        .map(describe_drop)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn create_result_embed(container: &ItemDef, outcome: &OpenOutcome) -> CreateEmbed {
+    let any_rare = outcome.rolls.iter().any(LootRoll::got_rare);
+    let lines: Vec<String> = outcome
+        .rolls
+        .iter()
+        .enumerate()
+        .map(|(i, roll)| {
+            let pity = if roll.pity_used { " *(pity)*" } else { "" };
+            if outcome.rolls.len() > 1 {
+                format!("**#{}** {}{}", i + 1, describe_roll(roll), pity)
+            } else {
+                format!("{}{}", describe_roll(roll), pity)
+            }
+        })
+        .collect();
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} {} Opened!", container.emoji, container.name))
+        .description(lines.join("\n"))
+        .color(if any_rare { COLOR_RARE } else { COLOR_OPEN });
+    if let Some(left) = outcome.opens_until_pity {
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "A rare find is guaranteed within {} more open(s).",
+            left
+        )));
+    }
+    embed
+}
+
+pub fn create_history_embed(history: &[LootHistoryEntry], pity: &[(i32, i32)]) -> CreateEmbed {
+    let items = registry();
+    let mut embed = CreateEmbed::new()
+        .title("📜 Open History")
+        .color(COLOR_OPEN);
+    if history.is_empty() {
+        embed = embed.description("You haven't opened anything yet. Try `/open item:geode`.");
+    } else {
+        let lines: Vec<String> = history
+            .iter()
+            .map(|h| {
+                let mut drops: Vec<LootDrop> = h
+                    .item_ids
+                    .iter()
+                    .zip(&h.quantities)
+                    .map(|(&id, &quantity)| LootDrop {
+                        item_id: Some(id),
+                        quantity,
+                        is_rare: false,
+                    })
+                    .collect();
+                if h.coins > 0 {
+                    drops.insert(
+                        0,
+                        LootDrop {
+                            item_id: None,
+                            quantity: h.coins,
+                            is_rare: false,
+                        },
+                    );
+                }
+                let container = h
+                    .container_item_id
+                    .map(|id| items.name_of(id))
+                    .unwrap_or_else(|| "Container".to_string());
+                format!(
+                    "<t:{}:R> **{}**{}: {}",
+                    h.opened_at.timestamp(),
+                    container,
+                    if h.got_rare { " 🌟" } else { "" },
+                    describe_roll(&LootRoll {
+                        drops,
+                        pity_used: h.pity_used,
+                    })
+                )
+            })
+            .collect();
+        embed = embed.description(lines.join("\n"));
+    }
+    for (container_id, left) in pity {
+        embed = embed.field(
+            format!("{} pity", items.name_of(*container_id)),
+            format!("Rare guaranteed within {} open(s)", left),
+            true,
+        );
+    }
+    embed
+}
@@ -1,17 +1,98 @@
 //! Implements the run logic for the `/party` command.
 
-use super::ui::create_party_view_with_bonds;
+use super::ui::{create_party_view_with_bonds, create_preset_view};
+use crate::database::presets::{self, PresetLoadOutcome};
 use crate::{AppState, services};
 use serenity::builder::{
-    CreateCommand, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
-    EditInteractionResponse,
+    CreateCommand, CreateCommandOption, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse,
 };
-use serenity::model::application::CommandInteraction;
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
+use serenity::model::id::UserId;
 use serenity::prelude::*;
 
 pub fn register() -> CreateCommand {
-    CreateCommand::new("party").description("Manage your active battle party and army.")
+    CreateCommand::new("party")
+        .description("Manage your active battle party and army.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "save",
+                "Save your current party (and bonds) as a named preset",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "load",
+                "Swap in a saved party preset by name",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "delete",
+                "Delete a saved party preset by name",
+            )
+            .required(false),
+        )
+}
+
+/// A preset action requested through slash options or prefix arguments.
+enum PresetAction {
+    Save(String),
+    Load(String),
+    Delete(String),
+    List,
+}
+
+/// Confirmation line shown after a successful preset swap.
+pub fn preset_loaded_line(outcome: &PresetLoadOutcome) -> String {
+    let bonds = if outcome.bonds_restored > 0 {
+        format!(" ({} bond(s) re-equipped)", outcome.bonds_restored)
+    } else {
+        String::new()
+    };
+    format!(
+        "📋 Loaded preset **{}**: {} unit(s) now in your party{}.",
+        outcome.name, outcome.members, bonds
+    )
+}
+
+/// Runs a preset action and returns (confirmation, show preset view instead of party view).
+async fn run_preset_action(
+    app_state: &AppState,
+    user_id: UserId,
+    action: PresetAction,
+) -> (String, bool) {
+    let db = &app_state.db;
+    match action {
+        PresetAction::Save(name) => {
+            match presets::save_current_party_as_preset(db, user_id, &name).await {
+                Ok(saved) => (
+                    format!("💾 Saved your current party as **{}**.", saved),
+                    true,
+                ),
+                Err(e) => (e, true),
+            }
+        }
+        PresetAction::Load(name) => match presets::load_party_preset(db, user_id, &name).await {
+            Ok(outcome) => {
+                app_state.invalidate_user_caches(user_id).await;
+                (preset_loaded_line(&outcome), false)
+            }
+            Err(e) => (e, true),
+        },
+        PresetAction::Delete(name) => match presets::delete_preset(db, user_id, &name).await {
+            Ok(true) => (format!("🗑️ Deleted preset **{}**.", name.trim()), true),
+            Ok(false) => (format!("No preset named `{}`.", name.trim()), true),
+            Err(_) => ("Failed to delete the preset.".to_string(), true),
+        },
+        PresetAction::List => (String::new(), true),
+    }
 }
 
 pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
@@ -45,16 +126,38 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         return;
     }
 
+    let preset_action = interaction.data.options.iter().find_map(|o| {
+        let CommandDataOptionValue::String(v) = &o.value else {
+            return None;
+        };
+        match o.name.as_str() {
+            "save" => Some(PresetAction::Save(v.clone())),
+            "load" => Some(PresetAction::Load(v.clone())),
+            "delete" => Some(PresetAction::Delete(v.clone())),
+            _ => None,
+        }
+    });
+
+    let (content, show_presets) = match preset_action {
+        Some(action) => run_preset_action(&app_state, interaction.user.id, action).await,
+        None => (String::new(), false),
+    };
+
     // Generate the UI with the fetched unit data.
-    let (embed, components) = create_party_view_with_bonds(&app_state, interaction.user.id).await;
+    let (embed, components) = if show_presets {
+        create_preset_view(&app_state, interaction.user.id).await
+    } else {
+        create_party_view_with_bonds(&app_state, interaction.user.id).await
+    };
     let builder = EditInteractionResponse::new()
+        .content(content)
         .embed(embed)
         .components(components);
 
     interaction.edit_response(&ctx.http, builder).await.ok();
 }
 
-pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
     let Some(app_state) = AppState::from_ctx(ctx).await else {
         return;
     };
@@ -67,10 +170,31 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
         return;
     }
 
-    let (embed, components) = create_party_view_with_bonds(&app_state, msg.author.id).await;
-    let builder = CreateMessage::new()
+    // `$party save|load|delete <name...>` or `$party presets`
+    let rest = args.get(1..).map(|a| a.join(" ")).unwrap_or_default();
+    let preset_action = match args.first().map(|a| a.to_lowercase()).as_deref() {
+        Some("save") => Some(PresetAction::Save(rest)),
+        Some("load") => Some(PresetAction::Load(rest)),
+        Some("delete") => Some(PresetAction::Delete(rest)),
+        Some("presets") => Some(PresetAction::List),
+        _ => None,
+    };
+    let (content, show_presets) = match preset_action {
+        Some(action) => run_preset_action(&app_state, msg.author.id, action).await,
+        None => (String::new(), false),
+    };
+
+    let (embed, components) = if show_presets {
+        create_preset_view(&app_state, msg.author.id).await
+    } else {
+        create_party_view_with_bonds(&app_state, msg.author.id).await
+    };
+    let mut builder = CreateMessage::new()
         .embed(embed)
         .components(components)
         .reference_message(msg);
+    if !content.is_empty() {
+        builder = builder.content(content);
+    }
     msg.channel_id.send_message(&ctx.http, builder).await.ok();
 }
@@ -2,9 +2,10 @@
 
 use crate::constants::rarity_icon;
 use crate::constants::{BOND_MAP_CACHE_TTL_SECS, EQUIP_BONUS_CACHE_TTL_SECS};
-use crate::database::models::{PlayerUnit, UnitRarity};
+use crate::database::models::{FormationRow, PlayerUnit, UnitRarity};
 use crate::model::AppState;
 use crate::model::{BondedEquippablesMap, EquipmentBonusMap};
+use crate::saga::upkeep::MORALE_DESERTION_WARNING;
 use crate::services::cache as cache_service;
 use crate::ui::buttons::Btn;
 use serenity::builder::{
@@ -127,10 +128,13 @@ pub fn create_party_view(units: &[PlayerUnit]) -> (CreateEmbed, Vec<CreateAction
         .placeholder("Dismiss a unit from your army...");
         components.push(CreateActionRow::SelectMenu(menu));
         // Add a bond management button row (links to /bond command UI via interaction custom id route)
-        components.push(CreateActionRow::Buttons(vec![Btn::secondary(
-            "bond_open",
-            "🔗 Manage Bonds",
-        )]));
+        components.push(CreateActionRow::Buttons(vec![
+            Btn::secondary("bond_open", "🔗 Manage Bonds"),
+            Btn::secondary("party_presets", "📋 Presets"),
+            Btn::secondary("party_formation", "🧭 Formation"),
+            Btn::secondary("party_upkeep", "🪙 Upkeep"),
+            Btn::secondary("party_gear", "🗡️ Gear"),
+        ]));
     }
 
     // Prepend Play row
@@ -260,10 +264,18 @@ pub async fn create_party_view_with_bonds(
         }
     );
 
+    let formation = crate::database::units::get_party_formation(pool, user_id)
+        .await
+        .unwrap_or_default();
+
     // Build enhanced party lines (replace existing Active Party field)
     let mut party_lines: Vec<String> = Vec::new();
     for p in units.iter().filter(|u| u.is_in_party) {
-        let mut line = format_pet_line(p);
+        let row = formation
+            .get(&p.player_unit_id)
+            .map(|(r, _)| *r)
+            .unwrap_or_default();
+        let mut line = format!("{} {}", row.icon(), format_pet_line(p));
         if let Some(b) = bonuses_map.get(&p.player_unit_id)
             && (b.0 > 0 || b.1 > 0 || b.2 > 0)
         {
@@ -346,5 +358,401 @@ pub async fn create_party_view_with_bonds(
         }
         embed = embed.field("🔗 Bonding Legend", "Bond bonuses are applied automatically in battles. Use the Bond Management button to equip or unequip special units.", false);
     }
+    let deserting: Vec<String> = crate::database::upkeep::list_human_upkeep(pool, user_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|h| h.morale <= MORALE_DESERTION_WARNING)
+        .map(|h| format!("{} ({} morale)", h.name, h.morale))
+        .collect();
+    if !deserting.is_empty() {
+        embed = embed.field(
+            "⚠️ Desertion Warning",
+            format!(
+                "{} may desert soon. Pay wages, rest them or send a gift (🪙 Upkeep).",
+                deserting.join(", ")
+            ),
+            false,
+        );
+    }
     (embed, components)
 }
+
+/// Builds the preset management view (saved party loadouts with load / delete menus).
+pub async fn create_preset_view(
+    app_state: &AppState,
+    user_id: UserId,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let pool: &PgPool = &app_state.db;
+    let presets = crate::database::presets::list_presets(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let members = crate::database::presets::list_preset_members(pool, user_id)
+        .await
+        .unwrap_or_default();
+
+    let mut embed = CreateEmbed::new()
+        .title("Party Presets")
+        .footer(CreateEmbedFooter::new(format!(
+            "Presets: {}/{} • Save your current party with /party save:<name>",
+            presets.len(),
+            crate::constants::MAX_PARTY_PRESETS
+        )))
+        .color(0x3498DB);
+    if presets.is_empty() {
+        embed = embed.description(
+            "You have no saved presets yet. Arrange your party, then use `/party save:<name>` (or `$party save <name>`).",
+        );
+    } else {
+        embed =
+            embed.description("Load a preset to swap your whole party (and its bonds) in one go.");
+        for preset in presets.iter().take(25) {
+            let lines = members
+                .iter()
+                .filter(|m| m.preset_id == preset.preset_id)
+                .map(|m| {
+                    let bond = if m.bond_id.is_some() { " 🔗" } else { "" };
+                    match &m.unit_name {
+                        Some(n) => format!("• {}{}", n, bond),
+                        None => format!("• ⚠️ Unit #{} (missing)", m.player_unit_id),
+                    }
+                })
+                .collect::<Vec<_>>();
+            let body = if lines.is_empty() {
+                "(empty)".to_string()
+            } else {
+                lines.join("\n")
+            };
+            embed = embed.field(format!("📋 {}", preset.name), body, true);
+        }
+    }
+
+    let mut rows = vec![crate::commands::saga::ui::global_nav_row("presets")];
+    if !presets.is_empty() {
+        let options = |prefix: &str| -> Vec<CreateSelectMenuOption> {
+            presets
+                .iter()
+                .take(25)
+                .map(|p| {
+                    CreateSelectMenuOption::new(
+                        format!("{} {}", prefix, p.name),
+                        p.preset_id.to_string(),
+                    )
+                })
+                .collect()
+        };
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "party_presetload",
+                CreateSelectMenuKind::String {
+                    options: options("Load"),
+                },
+            )
+            .placeholder("Load a preset as your party..."),
+        ));
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "party_presetdelete",
+                CreateSelectMenuKind::String {
+                    options: options("Delete"),
+                },
+            )
+            .placeholder("Delete a preset..."),
+        ));
+    }
+    (embed, rows)
+}
+
+/// Builds the formation view: front / back row listing plus a select to move party members.
+pub async fn create_formation_view(
+    app_state: &AppState,
+    user_id: UserId,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    use crate::saga::battle::logic::{BACK_ROW_MELEE_MULT, BACK_ROW_RANGED_MULT};
+    let pool: &PgPool = &app_state.db;
+    let party = crate::database::units::get_user_party(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let formation = crate::database::units::get_party_formation(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let placed: Vec<(&PlayerUnit, FormationRow, bool)> = party
+        .iter()
+        .map(|p| {
+            let (row, ranged) = formation
+                .get(&p.player_unit_id)
+                .copied()
+                .unwrap_or_default();
+            (p, row, ranged)
+        })
+        .collect();
+    let row_list = |row: FormationRow| -> String {
+        let lines = placed
+            .iter()
+            .filter(|(_, r, _)| *r == row)
+            .map(|(p, _, ranged)| {
+                let style = if *ranged {
+                    "🏹 ranged"
+                } else {
+                    "⚔️ melee"
+                };
+                format!(
+                    "{} **{}** ({})",
+                    rarity_icon(p.rarity),
+                    p.nickname.as_deref().unwrap_or(&p.name),
+                    style
+                )
+            })
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            "(empty)".to_string()
+        } else {
+            lines.join("\n")
+        }
+    };
+
+    let embed = CreateEmbed::new()
+        .title("Party Formation")
+        .description(format!(
+            "Enemies must defeat your **Front Row** before they can reach the **Back Row**.\nRanged units deal {:.0}% damage from the back; melee units only {:.0}%.",
+            BACK_ROW_RANGED_MULT * 100.0,
+            BACK_ROW_MELEE_MULT * 100.0
+        ))
+        .field(
+            format!("{} Front Row", FormationRow::Front.icon()),
+            row_list(FormationRow::Front),
+            true,
+        )
+        .field(
+            format!("{} Back Row", FormationRow::Back.icon()),
+            row_list(FormationRow::Back),
+            true,
+        )
+        .color(0x3498DB);
+
+    let mut rows = vec![crate::commands::saga::ui::global_nav_row("formation")];
+    if !placed.is_empty() {
+        let options: Vec<_> = placed
+            .iter()
+            .map(|(p, row, _)| {
+                CreateSelectMenuOption::new(
+                    format!(
+                        "Move {} to {} Row",
+                        p.nickname.as_deref().unwrap_or(&p.name),
+                        row.toggled().label()
+                    ),
+                    p.player_unit_id.to_string(),
+                )
+            })
+            .collect();
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("party_rowtoggle", CreateSelectMenuKind::String { options })
+                .placeholder("Move a party member to the other row..."),
+        ));
+    }
+    (embed, rows)
+}
+
+/// Builds the upkeep view: daily wages, morale per human and a gift menu.
+pub async fn create_upkeep_view(
+    app_state: &AppState,
+    user_id: UserId,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    use crate::saga::upkeep::{GIFT_COST, GIFT_MORALE, MORALE_MAX, morale_icon};
+    let humans = crate::database::upkeep::list_human_upkeep(&app_state.db, user_id)
+        .await
+        .unwrap_or_default();
+    let daily_total: i64 = humans.iter().map(|h| h.wage).sum();
+    let mut embed = CreateEmbed::new()
+        .title("Human Upkeep")
+        .description(format!(
+            "Hired humans draw a daily wage, paid automatically from your balance.\nUnpaid days lower morale; benched units recover while resting, and wins or gifts lift spirits.\n\n**Daily wages:** {} {}",
+            crate::ui::style::EMOJI_COIN,
+            daily_total
+        ))
+        .color(0x3498DB);
+    if humans.is_empty() {
+        embed = embed.field(
+            "No hired humans",
+            "Mercenaries and contracts appear here.",
+            false,
+        );
+    } else {
+        let lines = humans
+            .iter()
+            .map(|h| {
+                format!(
+                    "{} **{}** — morale {}/{} · {} coins/day{}",
+                    morale_icon(h.morale),
+                    h.name,
+                    h.morale,
+                    MORALE_MAX,
+                    h.wage,
+                    if h.is_in_party { "" } else { " · resting" }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("Humans", lines, false);
+    }
+
+    let mut rows = vec![crate::commands::saga::ui::global_nav_row("upkeep")];
+    let options: Vec<_> = humans
+        .iter()
+        .filter(|h| h.morale < MORALE_MAX)
+        .take(25)
+        .map(|h| {
+            CreateSelectMenuOption::new(
+                format!("Gift {} (+{} morale)", h.name, GIFT_MORALE),
+                h.player_unit_id.to_string(),
+            )
+        })
+        .collect();
+    if !options.is_empty() {
+        rows.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("party_gift", CreateSelectMenuKind::String { options })
+                .placeholder(format!("Send a gift ({} coins)...", GIFT_COST)),
+        ));
+    }
+    (embed, rows)
+}
+
+/// Builds the gear view: each unit's weapon / armor / trinket with durability, the gear in the
+/// player's bag, and menus to equip or unequip gear on the selected unit.
+pub async fn create_gear_view(
+    app_state: &AppState,
+    user_id: UserId,
+    selected: Option<i32>,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    use crate::commands::economy::core::registry::registry;
+    use crate::database::models::{GearSlot, UnitGear};
+    use crate::saga::gear::{WEAR_ON_DEFEAT, WEAR_PER_BATTLE, durability_label};
+    use std::collections::HashMap;
+    let pool: &PgPool = &app_state.db;
+    let items = registry();
+    let units = crate::database::units::get_player_units(pool, user_id)
+        .await
+        .unwrap_or_default();
+    let mut gear: HashMap<(i32, GearSlot), UnitGear> = HashMap::new();
+    for g in crate::database::gear::get_user_gear(pool, user_id)
+        .await
+        .unwrap_or_default()
+    {
+        gear.insert((g.player_unit_id, g.slot), g);
+    }
+    let owned = crate::database::gear::list_owned_gear(pool, user_id)
+        .await
+        .unwrap_or_default();
+
+    let mut embed = CreateEmbed::new()
+        .title("Unit Gear")
+        .description(format!(
+            "Each unit has a **Weapon**, **Armor** and **Trinket** slot. Gear loses {} durability per battle ({} on a defeat) and does nothing once broken.\nRepair gear at the Tavern's Small Arms shop; damaged gear must be repaired before it can be swapped out.",
+            WEAR_PER_BATTLE, WEAR_ON_DEFEAT
+        ))
+        .color(0x3498DB);
+    for unit in units.iter().take(10) {
+        let lines = GearSlot::ALL
+            .iter()
+            .map(|slot| match gear.get(&(unit.player_unit_id, *slot)) {
+                Some(g) => format!(
+                    "{} {} ({})",
+                    slot.icon(),
+                    items.name_of(g.item_id),
+                    durability_label(g)
+                ),
+                None => format!("{} —", slot.icon()),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let marker = if selected == Some(unit.player_unit_id) {
+            "▶ "
+        } else {
+            ""
+        };
+        embed = embed.field(
+            format!(
+                "{}{} {}",
+                marker,
+                rarity_icon(unit.rarity),
+                unit.nickname.as_deref().unwrap_or(&unit.name)
+            ),
+            lines,
+            true,
+        );
+    }
+    let bag = if owned.is_empty() {
+        "No gear in your bag. The Tavern's Small Arms shop stocks a few pieces each day."
+            .to_string()
+    } else {
+        owned
+            .iter()
+            .map(|o| {
+                format!(
+                    "{} {} x{} ({})",
+                    o.slot.icon(),
+                    items.name_of(o.item_id),
+                    o.quantity,
+                    o.slot.label()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    embed = embed.field("🎒 Gear in Bag", bag, false);
+
+    let mut rows = vec![crate::commands::saga::ui::global_nav_row("gear")];
+    if units.is_empty() {
+        return (embed, rows);
+    }
+    let unit_options: Vec<_> = units
+        .iter()
+        .take(25)
+        .map(|u| {
+            CreateSelectMenuOption::new(
+                u.nickname.as_deref().unwrap_or(&u.name),
+                u.player_unit_id.to_string(),
+            )
+            .default_selection(selected == Some(u.player_unit_id))
+        })
+        .collect();
+    rows.push(CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            "party_gearunit",
+            CreateSelectMenuKind::String {
+                options: unit_options,
+            },
+        )
+        .placeholder("Choose a unit to outfit..."),
+    ));
+
+    if let Some(unit_id) = selected {
+        let mut options: Vec<_> = owned
+            .iter()
+            .map(|o| {
+                CreateSelectMenuOption::new(
+                    format!("Equip {} ({})", items.name_of(o.item_id), o.slot.label()),
+                    o.item_id.to_string(),
+                )
+            })
+            .collect();
+        for slot in GearSlot::ALL {
+            if let Some(g) = gear.get(&(unit_id, slot)) {
+                options.push(CreateSelectMenuOption::new(
+                    format!("Unequip {} ({})", items.name_of(g.item_id), slot.label()),
+                    format!("slot:{}", slot.label()),
+                ));
+            }
+        }
+        if !options.is_empty() {
+            rows.push(CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    format!("party_gearequip_{}", unit_id),
+                    CreateSelectMenuKind::String { options },
+                )
+                .placeholder("Equip or unequip gear..."),
+            ));
+        }
+    }
+    (embed, rows)
+}
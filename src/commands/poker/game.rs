@@ -5,31 +5,28 @@ use super::hand_eval::evaluate_hand;
 use super::state::{GamePhase, HandRank, Player, PlayerStatus, PokerGame};
 use crate::commands::games::card::Rank;
 use crate::commands::games::deck::Deck;
-use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::commands::games::lifecycle::{SessionClock, TABLE_LOBBY_TIMEOUT};
+use crate::commands::games::{Game, GamePayout, GameUpdate, TABLE_BUY_IN_BETS};
 use serenity::async_trait;
 use serenity::builder::{
     CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
 };
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
 use serenity::model::user::User;
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// How long the table waits for an ante, a player's move, or the next round to be dealt.
+const TURN_TIMEOUT: Duration = Duration::from_secs(60);
+
 // This is the main entry point from the game engine. It delegates all work
 // to the appropriate handlers and renderers.
 #[async_trait]
 impl Game for PokerGame {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
     async fn handle_interaction(
         &mut self,
         ctx: &Context,
@@ -37,39 +34,29 @@ impl Game for PokerGame {
         db: &PgPool,
     ) -> GameUpdate {
         // Handle anti-stalling timeouts before processing any user clicks.
-        let now = Instant::now();
-        match self.phase {
-            GamePhase::Ante
-                if now.duration_since(self.last_action_time) > Duration::from_secs(60) =>
-            {
-                return GameUpdate::GameOver {
-                    message: "Game cancelled due to inactivity during ante phase.".to_string(),
-                    payouts: vec![],
-                };
-            }
-            GamePhase::PlayerTurns
-                if now.duration_since(self.last_action_time) > Duration::from_secs(60) =>
-            {
-                self.players[self.current_player_index].status = PlayerStatus::Folded;
-                self.advance_turn();
-            }
-            GamePhase::GameOver
-                if now.duration_since(self.last_action_time) > Duration::from_secs(60) =>
-            {
-                return GameUpdate::GameOver {
-                    message: "Game ended due to host inactivity.".to_string(),
-                    payouts: self.calculate_payouts().1,
-                };
-            }
-            _ => {}
+        if self.is_stalled()
+            && let Some(update) = self.resolve_stall(db, interaction.message.id).await
+        {
+            interaction.defer(&ctx.http).await.ok();
+            return update;
+        }
+
+        // Players may cash out whenever no round is being played.
+        if interaction.data.custom_id == "poker_leave"
+            && matches!(
+                self.phase,
+                GamePhase::WaitingForPlayers | GamePhase::Ante | GamePhase::GameOver
+            )
+        {
+            return self.leave_table(ctx, interaction, db).await;
         }
 
         // Delegate the actual interaction handling to the appropriate function.
         match self.phase {
             GamePhase::WaitingForPlayers => self.handle_lobby(ctx, interaction, db).await,
-            GamePhase::Ante => self.handle_ante_phase(ctx, interaction, db).await,
+            GamePhase::Ante => self.handle_ante_phase(ctx, interaction).await,
             GamePhase::PlayerTurns => self.handle_player_turn(ctx, interaction, db).await,
-            GamePhase::GameOver => self.handle_game_over(ctx, interaction).await,
+            GamePhase::GameOver => self.handle_game_over(ctx, interaction, db).await,
             // (✓) FIXED: Added the missing match arm for DealerTurn.
             GamePhase::DealerTurn => {
                 self.send_ephemeral_response(
@@ -120,11 +107,87 @@ impl Game for PokerGame {
         };
         (content, embed, components)
     }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.players.iter().map(|p| p.user.id).collect()
+    }
+
+    /// The lobby waits for the host to deal; after that the table moves on or closes whenever
+    /// nobody acts for `TURN_TIMEOUT`.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        match self.phase {
+            GamePhase::WaitingForPlayers => Some(clock.started + TABLE_LOBBY_TIMEOUT),
+            GamePhase::Ante | GamePhase::PlayerTurns | GamePhase::GameOver => {
+                Some(self.last_action_time + TURN_TIMEOUT)
+            }
+            GamePhase::DealerTurn => None,
+        }
+    }
+
+    async fn on_timeout(&mut self, db: &PgPool, message_id: MessageId) -> GameUpdate {
+        if self.phase == GamePhase::WaitingForPlayers {
+            let embed = CreateEmbed::new()
+                .title("Poker Lobby Expired")
+                .description("The game was not started by the host in time.")
+                .color(0xFF0000); // Red
+            return GameUpdate::Expired {
+                content: "**Poker Lobby Expired**".to_string(),
+                embed: Box::new(embed),
+                components: vec![],
+            };
+        }
+        self.resolve_stall(db, message_id)
+            .await
+            .unwrap_or(GameUpdate::ReRender)
+    }
 }
 
 // This block contains the core, non-async game logic and state manipulation.
 // (✓) FIXED: All methods are now in a single, consolidated impl block.
 impl PokerGame {
+    /// Whether the phase that is waiting on players has waited longer than `TURN_TIMEOUT`.
+    fn is_stalled(&self) -> bool {
+        matches!(
+            self.phase,
+            GamePhase::Ante | GamePhase::PlayerTurns | GamePhase::GameOver
+        ) && self.last_action_time.elapsed() > TURN_TIMEOUT
+    }
+
+    /// Moves a stalled table on: an unpaid ante or an idle table between rounds closes the
+    /// game, and a player who let their turn run out folds. `None` while play carries on.
+    async fn resolve_stall(&mut self, db: &PgPool, message_id: MessageId) -> Option<GameUpdate> {
+        match self.phase {
+            GamePhase::Ante => Some(GameUpdate::GameOver {
+                message: "Game cancelled due to inactivity during ante phase.".to_string(),
+                payouts: vec![],
+            }),
+            GamePhase::PlayerTurns => {
+                self.players[self.current_player_index].status = PlayerStatus::Folded;
+                self.advance_turn();
+                if self.phase == GamePhase::GameOver {
+                    Some(self.finish_round(db, message_id).await)
+                } else {
+                    None
+                }
+            }
+            // The last round was already settled against the stacks, which are cashed out as
+            // the table closes. One that never settled is void, so its stakes go back.
+            GamePhase::GameOver => {
+                if !self.round_settled {
+                    self.table_error = Some(
+                        "⚠️ This round's chips could not be settled, so it was voided and every stack returned."
+                            .to_string(),
+                    );
+                }
+                Some(GameUpdate::GameOver {
+                    message: "Game ended due to host inactivity.".to_string(),
+                    payouts: vec![],
+                })
+            }
+            _ => None,
+        }
+    }
+
     pub fn new(host: Arc<User>, min_bet: i64) -> Self {
         Self {
             host_id: host.id.get(),
@@ -134,6 +197,7 @@ impl PokerGame {
                 hand_rank: None,
                 ante_bet: 0,
                 play_bet: 0,
+                chips: min_bet * TABLE_BUY_IN_BETS,
                 status: PlayerStatus::Waiting,
             }],
             dealer_hand: Vec::new(),
@@ -146,12 +210,14 @@ impl PokerGame {
             ready_players: HashSet::new(),
             current_player_index: 0,
             last_action_time: Instant::now(),
+            round_settled: true,
+            table_error: None,
         }
     }
 
-    // (✓) ADDED: Helper function used by the timeout handler in run.rs.
-    pub fn is_in_lobby(&self) -> bool {
-        self.phase == GamePhase::WaitingForPlayers
+    /// Chips each player buys in for.
+    pub fn buy_in(&self) -> i64 {
+        self.min_bet * TABLE_BUY_IN_BETS
     }
 
     pub fn start_game(&mut self) {
@@ -182,8 +248,14 @@ impl PokerGame {
         self.last_action_time = Instant::now();
     }
 
+    /// Starts the next ante round. Players who can no longer cover the ante must have been
+    /// cashed out and removed by the caller.
     pub fn reset_for_next_round(&mut self) {
         self.ready_players.clear();
+        for player in self.players.iter_mut() {
+            player.ante_bet = 0;
+            player.play_bet = 0;
+        }
         self.pot = 0;
         self.round += 1;
         self.phase = GamePhase::Ante;
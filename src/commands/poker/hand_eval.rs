@@ -1,9 +1,33 @@
-//! Contains the logic for evaluating a 5-card poker hand.
+//! Contains the logic for evaluating a 5-card poker hand, and the best 5 of up to 7 cards
+//! for Texas Hold'em.
 
 use super::state::HandRank;
 use crate::commands::games::card::{Card, Rank};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// The strongest 5-card hand found among a player's cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestHand {
+    pub rank: HandRank,
+    /// Card ranks ordered by how many times they appear, then by rank, used to break ties
+    /// between equal `HandRank`s (e.g. the kickers behind a pair).
+    pub kickers: [u8; 5],
+    pub cards: Vec<Card>,
+}
+
+impl Ord for BestHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.rank, self.kickers).cmp(&(other.rank, other.kickers))
+    }
+}
+
+impl PartialOrd for BestHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Takes a slice of 5 cards and returns the best possible HandRank.
 pub fn evaluate_hand(hand: &[Card]) -> HandRank {
     let mut sorted_hand = hand.to_vec();
@@ -88,3 +112,53 @@ fn is_straight(sorted_hand: &[Card]) -> (bool, u8) {
         .all(|w| w[0].rank as u8 == w[1].rank as u8 + 1);
     (is_standard, sorted_hand[0].rank as u8)
 }
+
+/// Finds the best 5-card hand among `cards` (hole cards plus board, up to 7 cards).
+pub fn evaluate_best_hand(cards: &[Card]) -> BestHand {
+    if cards.len() <= 5 {
+        return best_hand_of(cards.to_vec());
+    }
+    let n = cards.len();
+    let mut best: Option<BestHand> = None;
+    // Every 5-card hand is the full set minus a choice of cards to leave out.
+    for mask in 0u32..(1 << n) {
+        if mask.count_ones() as usize != 5 {
+            continue;
+        }
+        let hand: Vec<Card> = (0..n)
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| cards[i])
+            .collect();
+        let candidate = best_hand_of(hand);
+        if best.as_ref().is_none_or(|b| candidate > *b) {
+            best = Some(candidate);
+        }
+    }
+    best.expect("at least one 5-card hand")
+}
+
+fn best_hand_of(cards: Vec<Card>) -> BestHand {
+    let rank = if cards.len() == 5 {
+        evaluate_hand(&cards)
+    } else {
+        HandRank::HighCard(cards.iter().map(|c| c.rank as u8).max().unwrap_or(0))
+    };
+    let mut grouped: Vec<(usize, u8)> = count_ranks(&cards)
+        .into_iter()
+        .map(|(rank, count)| (count, rank as u8))
+        .collect();
+    grouped.sort_by(|a, b| b.cmp(a));
+    let mut kickers = [0u8; 5];
+    for (slot, rank) in kickers.iter_mut().zip(
+        grouped
+            .iter()
+            .flat_map(|&(count, rank)| std::iter::repeat_n(rank, count)),
+    ) {
+        *slot = rank;
+    }
+    BestHand {
+        rank,
+        kickers,
+        cards,
+    }
+}
@@ -2,24 +2,15 @@
 
 use super::state::{GamePhase, Player, PlayerStatus, PokerGame}; // (✓) FIXED: Imported PokerGame, not BlackjackGame.
 use crate::commands::games::GameUpdate; // (✓) Using re-export from commands::games
+use crate::database::models::GambleGame;
+use crate::database::table_stacks;
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::MessageId;
 use serenity::prelude::Context;
 use sqlx::PgPool;
 use std::sync::Arc;
-
-async fn get_balance(db: &PgPool, user_id: serenity::model::id::UserId) -> i64 {
-    match sqlx::query!(
-        "SELECT balance FROM profiles WHERE user_id = $1",
-        user_id.get() as i64
-    )
-    .fetch_optional(db)
-    .await
-    {
-        Ok(Some(record)) => record.balance,
-        _ => 0,
-    }
-}
+use std::time::Instant;
 
 async fn send_ephemeral_response(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
     let builder = CreateInteractionResponseMessage::new()
@@ -48,17 +39,15 @@ impl PokerGame {
                     .iter()
                     .any(|p| p.user.id == interaction.user.id)
                 {
-                    let balance = get_balance(db, interaction.user.id).await;
-                    if balance < self.min_bet {
-                        send_ephemeral_response(
-                            ctx,
-                            interaction,
-                            &format!(
-                                "You cannot afford the ante of **💰{}** to join.",
-                                self.min_bet
-                            ),
-                        )
-                        .await;
+                    if let Err(e) = table_stacks::buy_in(
+                        db,
+                        interaction.message.id,
+                        interaction.user.id,
+                        self.buy_in(),
+                    )
+                    .await
+                    {
+                        send_ephemeral_response(ctx, interaction, &e).await;
                         return GameUpdate::NoOp;
                     }
                     self.players.push(Player {
@@ -67,6 +56,7 @@ impl PokerGame {
                         hand_rank: None,
                         ante_bet: 0,
                         play_bet: 0,
+                        chips: self.buy_in(),
                         status: PlayerStatus::Waiting,
                     });
                     interaction.defer(&ctx.http).await.ok();
@@ -109,7 +99,6 @@ impl PokerGame {
         &mut self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
-        db: &PgPool,
     ) -> GameUpdate {
         let player = match self
             .players
@@ -124,13 +113,12 @@ impl PokerGame {
             return GameUpdate::NoOp;
         }
 
-        let balance = get_balance(db, interaction.user.id).await;
-        if balance < self.min_bet {
+        if player.chips < self.min_bet {
             send_ephemeral_response(
                 ctx,
                 interaction,
                 &format!(
-                    "You can no longer afford the ante of **💰{}**.",
+                    "You no longer have the chips for the ante of **💰{}**.",
                     self.min_bet
                 ),
             )
@@ -166,10 +154,13 @@ impl PokerGame {
         match interaction.data.custom_id.as_str() {
             "poker_bet" => {
                 let play_bet_amount = player.ante_bet * 2;
-                let balance = get_balance(db, player.user.id).await;
-                if balance < player.ante_bet + play_bet_amount {
-                    send_ephemeral_response(ctx, interaction, "You cannot afford the play bet.")
-                        .await;
+                if player.chips < player.ante_bet + play_bet_amount {
+                    send_ephemeral_response(
+                        ctx,
+                        interaction,
+                        "You don't have the chips for the play bet.",
+                    )
+                    .await;
                     return GameUpdate::NoOp;
                 }
                 player.play_bet = play_bet_amount;
@@ -186,8 +177,7 @@ impl PokerGame {
         self.advance_turn();
 
         if self.phase == GamePhase::GameOver {
-            let (message, payouts) = self.calculate_payouts();
-            GameUpdate::GameOver { message, payouts }
+            self.finish_round(db, interaction.message.id).await
         } else {
             GameUpdate::ReRender
         }
@@ -197,17 +187,136 @@ impl PokerGame {
         &mut self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
+        db: &PgPool,
     ) -> GameUpdate {
-        if interaction.data.custom_id == "poker_next_round"
-            && interaction.user.id.get() == self.host_id
+        if interaction.data.custom_id != "poker_next_round"
+            || interaction.user.id.get() != self.host_id
         {
-            interaction.defer(&ctx.http).await.ok();
-            self.reset_for_next_round();
-            GameUpdate::ReRender
-        } else {
             send_ephemeral_response(ctx, interaction, "Only the host can start the next round.")
                 .await;
-            GameUpdate::NoOp
+            return GameUpdate::NoOp;
+        }
+        interaction.defer(&ctx.http).await.ok();
+        let table = interaction.message.id;
+        if !self.round_settled && !self.settle_stacks(db, table).await {
+            return GameUpdate::ReRender;
+        }
+        // Anyone who can no longer cover the ante leaves with what they have left. A player
+        // whose cash-out fails keeps their seat until it goes through.
+        let (busted, staying): (Vec<_>, Vec<_>) = std::mem::take(&mut self.players)
+            .into_iter()
+            .partition(|p| p.chips < self.min_bet);
+        self.players = staying;
+        let mut failed = Vec::new();
+        for player in busted {
+            if let Err(e) = table_stacks::cash_out(db, table, player.user.id).await {
+                failed.push(format!("<@{}> ({})", player.user.id, e));
+                self.players.push(player);
+            }
+        }
+        if !failed.is_empty() {
+            self.table_error = Some(format!(
+                "⚠️ Could not cash out {}. Press Next Round to try again.",
+                failed.join(", ")
+            ));
+            return GameUpdate::ReRender;
+        }
+        self.table_error = None;
+        if self.players.is_empty() {
+            return GameUpdate::GameOver {
+                message: "Every player has run out of chips.".to_string(),
+                payouts: vec![],
+            };
+        }
+        if !self.players.iter().any(|p| p.user.id.get() == self.host_id) {
+            self.host_id = self.players[0].user.id.get();
+        }
+        self.reset_for_next_round();
+        GameUpdate::ReRender
+    }
+
+    /// Settles a finished round against the players' stacks; the table then waits for the
+    /// host to deal the next one.
+    pub(super) async fn finish_round(&mut self, db: &PgPool, table: MessageId) -> GameUpdate {
+        self.settle_stacks(db, table).await;
+        self.last_action_time = Instant::now();
+        GameUpdate::ReRender
+    }
+
+    /// Applies the finished round to the players' chip stacks. If that fails the table says so
+    /// and the next round waits until the host retries it successfully.
+    async fn settle_stacks(&mut self, db: &PgPool, table: MessageId) -> bool {
+        let (_, payouts) = self.calculate_payouts();
+        match table_stacks::settle(db, table, GambleGame::Poker, &payouts).await {
+            Ok(stacks) => {
+                for player in self.players.iter_mut() {
+                    if let Some(&(_, chips)) = stacks.iter().find(|(u, _)| *u == player.user.id) {
+                        player.chips = chips;
+                    }
+                }
+                self.round_settled = true;
+                self.table_error = None;
+                true
+            }
+            Err(e) => {
+                tracing::error!(table = %table, error = ?e, "failed to settle poker round");
+                self.round_settled = false;
+                self.table_error = Some(
+                    "⚠️ This round's chips could not be settled. Press Next Round to try again."
+                        .to_string(),
+                );
+                false
+            }
+        }
+    }
+
+    /// Cashes a player out between rounds. The table closes once everyone has left, and the
+    /// next player in line takes over as host if the host leaves.
+    pub(super) async fn leave_table(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user_id = interaction.user.id;
+        let Some(index) = self.players.iter().position(|p| p.user.id == user_id) else {
+            send_ephemeral_response(ctx, interaction, "You are not at this table.").await;
+            return GameUpdate::NoOp;
+        };
+        if !self.round_settled {
+            send_ephemeral_response(
+                ctx,
+                interaction,
+                "This round's chips haven't been settled yet. Wait for the host to retry.",
+            )
+            .await;
+            return GameUpdate::NoOp;
         }
+        match table_stacks::cash_out(db, interaction.message.id, user_id).await {
+            Ok(chips) => {
+                let farewell = format!("You left the table and cashed out **💰{}**.", chips);
+                send_ephemeral_response(ctx, interaction, &farewell).await;
+            }
+            Err(e) => {
+                send_ephemeral_response(ctx, interaction, &e).await;
+                return GameUpdate::NoOp;
+            }
+        }
+        self.players.remove(index);
+        self.ready_players.remove(&user_id);
+        if self.players.is_empty() {
+            self.phase = GamePhase::GameOver;
+            return GameUpdate::GameOver {
+                message: "Everyone left the table.".to_string(),
+                payouts: vec![],
+            };
+        }
+        if user_id.get() == self.host_id {
+            self.host_id = self.players[0].user.id.get();
+        }
+        if self.phase == GamePhase::Ante && self.ready_players.len() == self.players.len() {
+            self.deal_new_round();
+        }
+        GameUpdate::ReRender
     }
 }
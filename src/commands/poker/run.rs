@@ -2,17 +2,17 @@
 
 use super::state::PokerGame;
 use crate::AppState;
-use crate::commands::games::{Game, GameManager};
+use crate::commands::games::{Game, open_table};
+use crate::database::gambling;
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponse,
-    CreateInteractionResponseMessage, CreateMessage, EditMessage,
+    CreateInteractionResponseMessage, CreateMessage,
 };
 use serenity::model::application::{CommandInteraction, CommandOptionType};
 use serenity::model::channel::Message;
+use serenity::model::id::UserId;
 use serenity::prelude::*;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
 use tracing::{instrument, warn};
 
 pub fn register() -> CreateCommand {
@@ -35,8 +35,6 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         warn!(command = "poker_slash", "missing_app_state");
         return;
     };
-    let game_manager_lock = app_state.game_manager.clone();
-
     let response = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
     if interaction
         .create_response(&ctx.http, response)
@@ -60,6 +58,12 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         })
         .unwrap_or(0); // This is safe because the option is required.
 
+    if let Err(e) = gambling::check_can_play(&app_state.db, interaction.user.id).await {
+        let builder = serenity::builder::EditInteractionResponse::new().content(e);
+        interaction.edit_response(&ctx.http, builder).await.ok();
+        return;
+    }
+
     let poker_game = PokerGame::new(Arc::new(interaction.user.clone()), ante);
     let (content, embed, components) = poker_game.render();
     let builder = serenity::builder::EditInteractionResponse::new()
@@ -68,11 +72,16 @@ pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
         .components(components);
 
     if let Ok(game_msg) = interaction.edit_response(&ctx.http, builder).await {
-        game_manager_lock
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(poker_game));
-        spawn_lobby_timeout_handler(ctx.clone(), game_manager_lock, game_msg);
+        let (host_id, buy_in) = (UserId::new(poker_game.host_id), poker_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(poker_game),
+        )
+        .await;
     }
 }
 
@@ -82,8 +91,6 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         warn!(command = "poker_prefix", "missing_app_state");
         return;
     };
-    let game_manager_lock = app_state.game_manager.clone();
-
     let ante = match args.first().and_then(|arg| arg.parse::<i64>().ok()) {
         Some(bet) if bet > 0 => bet,
         _ => {
@@ -97,6 +104,11 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         }
     };
 
+    if let Err(e) = gambling::check_can_play(&app_state.db, msg.author.id).await {
+        msg.reply(ctx, e).await.ok();
+        return;
+    }
+
     let poker_game = PokerGame::new(Arc::new(msg.author.clone()), ante);
     let (content, embed, components) = poker_game.render();
     let builder = CreateMessage::new()
@@ -106,38 +118,15 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
         .reference_message(msg);
 
     if let Ok(game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
-        game_manager_lock
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(poker_game));
-        spawn_lobby_timeout_handler(ctx.clone(), game_manager_lock, game_msg);
+        let (host_id, buy_in) = (UserId::new(poker_game.host_id), poker_game.buy_in());
+        open_table(
+            ctx,
+            &app_state,
+            game_msg,
+            host_id,
+            buy_in,
+            Box::new(poker_game),
+        )
+        .await;
     }
 }
-
-fn spawn_lobby_timeout_handler(
-    ctx: Context,
-    game_manager: Arc<RwLock<GameManager>>,
-    mut game_msg: Message,
-) {
-    tokio::spawn(async move {
-        tokio::time::sleep(Duration::from_secs(120)).await;
-        let mut manager = game_manager.write().await;
-
-        // (✓) FIXED: Collapsed the nested `if` statements into a single, more readable block.
-        if let Some(game_box) = manager.get_game_mut(&game_msg.id)
-            && let Some(poker_game) = game_box.as_any().downcast_ref::<PokerGame>()
-            && poker_game.is_in_lobby()
-        {
-            let embed = serenity::builder::CreateEmbed::new()
-                .title("Poker Lobby Expired")
-                .description("The game was not started by the host in time.")
-                .color(0xFF0000); // Red
-            let builder = EditMessage::new()
-                .content("**Poker Lobby Expired**")
-                .embed(embed)
-                .components(vec![]);
-            game_msg.edit(&ctx.http, builder).await.ok();
-            manager.remove_game(&game_msg.id);
-        }
-    });
-}
@@ -44,6 +44,8 @@ pub struct Player {
     pub hand_rank: Option<HandRank>,
     pub ante_bet: i64,
     pub play_bet: i64,
+    /// Chips bought in at the table; rounds are settled against these, not the wallet.
+    pub chips: i64,
     pub status: PlayerStatus,
 }
 
@@ -60,4 +62,8 @@ pub struct PokerGame {
     pub ready_players: HashSet<UserId>,
     pub current_player_index: usize,
     pub last_action_time: Instant,
+    /// False while the finished round's results still have to be applied to the chip stacks.
+    pub round_settled: bool,
+    /// Why the table could not settle the round or cash a player out, shown until it can.
+    pub table_error: Option<String>,
 }
@@ -15,8 +15,10 @@ impl PokerGame {
             .join("\n");
         let desc = if self.min_bet > 0 {
             format!(
-                "<@{}> has started a Five Card Poker table with an ante of **💰{}**!",
-                self.host_id, self.min_bet
+                "<@{}> has started a Five Card Poker table with an ante of **💰{}**!\nJoining buys you in for **💰{}** in chips; leave the table to cash out.",
+                self.host_id,
+                self.min_bet,
+                self.buy_in()
             )
         } else {
             format!(
@@ -41,6 +43,7 @@ impl PokerGame {
             Btn::success("poker_join", "Join"),
             Btn::danger("poker_cancel", "Cancel (Host)"),
             Btn::primary("poker_start", "Start Game (Host)"),
+            Btn::secondary("poker_leave", "Leave"),
         ];
         (embed, vec![CreateActionRow::Buttons(buttons)])
     }
@@ -55,7 +58,7 @@ impl PokerGame {
                 } else {
                     "🤔"
                 };
-                format!("{} <@{}>", status_icon, p.user.id)
+                format!("{} <@{}> — Chips: 💰{}", status_icon, p.user.id, p.chips)
             })
             .collect::<Vec<_>>()
             .join("\n");
@@ -72,10 +75,10 @@ impl PokerGame {
                 "Round starts when all are ready (60s timeout).",
             ));
 
-        let buttons = vec![Btn::success(
-            "poker_ante",
-            &format!("Place Ante (💰{})", self.min_bet),
-        )];
+        let buttons = vec![
+            Btn::success("poker_ante", &format!("Place Ante (💰{})", self.min_bet)),
+            Btn::secondary("poker_leave", "Cash Out & Leave"),
+        ];
         (embed, vec![CreateActionRow::Buttons(buttons)])
     }
 
@@ -163,7 +166,10 @@ impl PokerGame {
 
             let field_value = format!("{}\n{}", hand_str, status_text);
             embed = embed.field(
-                format!("{}👤 {}", turn_indicator, player.user.name),
+                format!(
+                    "{}👤 {} · 💰{}",
+                    turn_indicator, player.user.name, player.chips
+                ),
                 field_value,
                 true,
             );
@@ -171,7 +177,12 @@ impl PokerGame {
 
         if self.phase == GamePhase::GameOver {
             let (results_str, _) = self.calculate_payouts();
-            embed = embed.description(format!("**Final Results**\n\n{}", results_str));
+            let error = self
+                .table_error
+                .as_ref()
+                .map(|e| format!("\n\n{}", e))
+                .unwrap_or_default();
+            embed = embed.description(format!("**Final Results**\n\n{}{}", results_str, error));
             // Row: quick return to Tavern
             components.push(CreateActionRow::Buttons(vec![
                 crate::ui::buttons::Btn::secondary(
@@ -182,12 +193,12 @@ impl PokerGame {
             // Global nav row for consistency across mini-games
             components.push(crate::commands::saga::ui::global_nav_row("saga"));
             if self.min_bet > 0 {
-                components.push(CreateActionRow::Buttons(vec![Btn::primary(
-                    "poker_next_round",
-                    "Next Round (Host)",
-                )]));
+                components.push(CreateActionRow::Buttons(vec![
+                    Btn::primary("poker_next_round", "Next Round (Host)"),
+                    Btn::secondary("poker_leave", "Cash Out & Leave"),
+                ]));
                 embed = embed.footer(CreateEmbedFooter::new(
-                    "The host has 60 seconds to start the next round.",
+                    "The host has 60 seconds to start the next round. Players who can't cover the ante are cashed out.",
                 ));
             }
         } else {
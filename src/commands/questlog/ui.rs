@@ -1,6 +1,6 @@
 //! Contains the UI rendering logic for the `/questlog` command.
 
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use crate::database::models::PlayerQuestStatus;
 use crate::database::quests::QuestBoardEntry;
 use crate::ui::buttons::Btn;
@@ -31,6 +31,7 @@ pub fn create_questlog_embed(
         };
         embed = embed.description(description);
     } else {
+        let items = registry();
         for entry in quests {
             let mut reward_parts = Vec::new();
             for reward in &entry.rewards {
@@ -39,17 +40,11 @@ pub fn create_questlog_embed(
                 {
                     reward_parts.push(format!("💰 **{}** Coins", coins));
                 }
-                if let (Some(_item_id), Some(quantity), Some(item)) = (
-                    reward.reward_item_id,
-                    reward.reward_item_quantity,
-                    Item::from_i32(reward.reward_item_id.unwrap_or(0)),
-                ) {
-                    reward_parts.push(format!(
-                        "{} **{}x** {}",
-                        item.emoji(),
-                        quantity,
-                        item.display_name()
-                    ));
+                if let (Some(item_id), Some(quantity)) =
+                    (reward.reward_item_id, reward.reward_item_quantity)
+                    && let Some(item) = items.get(item_id)
+                {
+                    reward_parts.push(format!("{} **{}x** {}", item.emoji, quantity, item.name));
                 }
             }
             let reward_display = if reward_parts.is_empty() {
@@ -1,6 +1,6 @@
 //! Contains the UI rendering logic for the `/quests` command.
 
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use crate::database::models::PlayerQuestStatus;
 use crate::database::quests::QuestBoardEntry;
 use crate::ui::buttons::Btn;
@@ -27,6 +27,7 @@ pub fn create_quest_board_embed(quests: &[QuestBoardEntry]) -> (CreateEmbed, Vec
         embed =
             embed.description("There are no new quests available at the moment. Check back later!");
     } else {
+        let items = registry();
         for entry in quests {
             let mut reward_parts = Vec::new();
             for reward in &entry.rewards {
@@ -36,18 +37,11 @@ pub fn create_quest_board_embed(quests: &[QuestBoardEntry]) -> (CreateEmbed, Vec
                 {
                     reward_parts.push(format!("💰 **{}** Coins", coins));
                 }
-                // (✓) FIXED: Collapsed nested `if let` and ignored unused `_item_id`.
-                if let (Some(_item_id), Some(quantity), Some(item)) = (
-                    reward.reward_item_id,
-                    reward.reward_item_quantity,
-                    Item::from_i32(reward.reward_item_id.unwrap_or(0)),
-                ) {
-                    reward_parts.push(format!(
-                        "{} **{}x** {}",
-                        item.emoji(),
-                        quantity,
-                        item.display_name()
-                    ));
+                if let (Some(item_id), Some(quantity)) =
+                    (reward.reward_item_id, reward.reward_item_quantity)
+                    && let Some(item) = items.get(item_id)
+                {
+                    reward_parts.push(format!("{} **{}x** {}", item.emoji, quantity, item.name));
                 }
             }
             let reward_display = if reward_parts.is_empty() {
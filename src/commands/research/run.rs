@@ -1,12 +1,17 @@
 use crate::database;
+use crate::saga::research::{NodeStatus, RESEARCH_TREE, ResearchFamily};
 use crate::ui::buttons::Btn;
-use serenity::builder::{CreateActionRow, CreateCommand, CreateEmbed};
+use serenity::builder::{
+    CreateActionRow, CreateCommand, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind,
+    CreateSelectMenuOption,
+};
 use serenity::model::application::CommandInteraction;
 use serenity::model::channel::Message;
 use serenity::prelude::Context;
 
 pub fn register() -> CreateCommand {
-    CreateCommand::new("research").description("View pet research / taming progress")
+    CreateCommand::new("research")
+        .description("View pet research progress and unlock research tree passives")
 }
 
 pub async fn build_view_cached(
@@ -26,7 +31,7 @@ pub async fn build_view_cached(
         for u in units
             .into_iter()
             .filter(|u| matches!(u.kind, database::models::UnitKind::Pet))
-            .take(25)
+            .take(20)
         {
             let count = map.get(&u.unit_id).cloned().unwrap_or(0);
             let target = database::units::research_target_for_rarity(db, u.rarity).await;
@@ -60,19 +65,89 @@ pub async fn build_view_cached(
     embed
 }
 
+/// Full `/research` view: species progress, the research tree and an unlock menu.
+pub async fn build_research_view(
+    app_state: &crate::AppState,
+    user: serenity::model::id::UserId,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let mut embed = build_view_cached(app_state, user).await;
+    let mut components = vec![CreateActionRow::Buttons(vec![
+        Btn::secondary("bestiary_refresh", "📚 Bestiary"),
+        Btn::secondary("contracts_refresh", "📜 Contracts"),
+        Btn::secondary("research_refresh", "🔄 Refresh"),
+    ])];
+    let Ok(standing) = database::research::get_research_standing(&app_state.db, user).await else {
+        return (embed, components);
+    };
+    embed = embed.field(
+        "Research Points",
+        format!(
+            "{} available ({} earned, {} spent). Complete species research to earn more.",
+            standing.points_available(),
+            standing.points_earned,
+            standing.points_spent()
+        ),
+        false,
+    );
+    for family in ResearchFamily::ALL {
+        let open = standing.completed_families.contains(&family);
+        let lines = RESEARCH_TREE
+            .iter()
+            .filter(|n| n.family == family)
+            .map(|n| {
+                let status = standing.status(n);
+                let mut line = format!(
+                    "{} **{}** ({} pt) — {}",
+                    status.icon(),
+                    n.name,
+                    n.cost,
+                    n.passive.describe()
+                );
+                if let NodeStatus::MissingPrerequisite(req) = status {
+                    line.push_str(&format!("\n  ↳ requires {}", req));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let title = format!(
+            "{} {} Branch{}",
+            family.icon(),
+            family.label(),
+            if open {
+                ""
+            } else {
+                " (complete a species to open)"
+            }
+        );
+        embed = embed.field(title, lines, false);
+    }
+    let options: Vec<_> = RESEARCH_TREE
+        .iter()
+        .filter(|n| standing.status(n) == NodeStatus::Available)
+        .map(|n| {
+            CreateSelectMenuOption::new(format!("{} ({} pt)", n.name, n.cost), n.key)
+                .description(n.passive.describe())
+        })
+        .collect();
+    if !options.is_empty() {
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("research_unlock", CreateSelectMenuKind::String { options })
+                .placeholder("Unlock a research node..."),
+        ));
+    }
+    (embed, components)
+}
+
 pub async fn run_slash(ctx: &Context, interaction: &mut CommandInteraction) {
     interaction.defer_ephemeral(&ctx.http).await.ok();
     let Some(state) = crate::AppState::from_ctx(ctx).await else {
         return;
     };
-    let embed = build_view_cached(&state, interaction.user.id).await;
-    let row = CreateActionRow::Buttons(vec![
-        Btn::secondary("bestiary_refresh", "📚 Bestiary"),
-        Btn::secondary("contracts_refresh", "📜 Contracts"),
-    ]);
+    let (embed, components) = build_research_view(&state, interaction.user.id).await;
     let resp = serenity::builder::CreateInteractionResponseMessage::new()
         .embed(embed)
-        .components(vec![row]);
+        .components(components);
     let _ = interaction
         .create_response(
             &ctx.http,
@@ -85,18 +160,14 @@ pub async fn run_prefix(ctx: &Context, msg: &Message, _args: Vec<&str>) {
     let Some(state) = crate::AppState::from_ctx(ctx).await else {
         return;
     };
-    let embed = build_view_cached(&state, msg.author.id).await;
-    let row = CreateActionRow::Buttons(vec![
-        Btn::secondary("bestiary_refresh", "📚 Bestiary"),
-        Btn::secondary("contracts_refresh", "📜 Contracts"),
-    ]);
+    let (embed, components) = build_research_view(&state, msg.author.id).await;
     let _ = msg
         .channel_id
         .send_message(
             &ctx.http,
             serenity::builder::CreateMessage::new()
                 .embed(embed)
-                .components(vec![row]),
+                .components(components),
         )
         .await;
 }
@@ -0,0 +1,131 @@
+//! Pure single-elimination brackets: seeding with byes, reporting results and advancing winners.
+//! Nothing here touches Discord or the database.
+
+use serenity::model::id::UserId;
+
+pub const MIN_ENTRANTS: usize = 3;
+pub const MAX_ENTRANTS: usize = 32;
+
+/// Bracket positions for `size` seeds (a power of two), so seed 1 meets seed `size` first and the
+/// top two seeds can only meet in the final.
+pub fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let n = order.len() * 2;
+        order = order.iter().flat_map(|&s| [s, n - 1 - s]).collect();
+    }
+    order
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BracketMatch {
+    pub player1: Option<UserId>,
+    pub player2: Option<UserId>,
+    pub winner: Option<UserId>,
+}
+
+impl BracketMatch {
+    /// Both players are known and the match has not been played.
+    pub fn is_ready(&self) -> bool {
+        self.player1.is_some() && self.player2.is_some() && self.winner.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    /// Round by round, first round first; the last round is the final.
+    pub rounds: Vec<Vec<BracketMatch>>,
+}
+
+impl Bracket {
+    /// Builds a bracket from entrants ordered best seed first. Missing seeds become byes for the
+    /// top seeds, who go straight through to the second round.
+    pub fn new(seeds: &[UserId]) -> Self {
+        let size = seeds.len().next_power_of_two().max(2);
+        let slots: Vec<Option<UserId>> = seed_order(size)
+            .into_iter()
+            .map(|seed| seeds.get(seed).copied())
+            .collect();
+        let mut rounds = vec![
+            slots
+                .chunks(2)
+                .map(|pair| BracketMatch {
+                    player1: pair[0],
+                    player2: pair[1],
+                    winner: None,
+                })
+                .collect::<Vec<_>>(),
+        ];
+        let mut matches = size / 4;
+        while matches > 0 {
+            rounds.push(vec![BracketMatch::default(); matches]);
+            matches /= 2;
+        }
+        let mut bracket = Self { rounds };
+        for index in 0..bracket.rounds[0].len() {
+            let m = bracket.rounds[0][index];
+            if let (Some(player), None) | (None, Some(player)) = (m.player1, m.player2) {
+                bracket.advance(0, index, player);
+            }
+        }
+        bracket
+    }
+
+    fn advance(&mut self, round: usize, index: usize, winner: UserId) {
+        self.rounds[round][index].winner = Some(winner);
+        if let Some(next) = self.rounds.get_mut(round + 1) {
+            let slot = &mut next[index / 2];
+            if index.is_multiple_of(2) {
+                slot.player1 = Some(winner);
+            } else {
+                slot.player2 = Some(winner);
+            }
+        }
+    }
+
+    /// Records `winner` for a match and returns the match it unlocked, if the winner's next
+    /// opponent is already known. Unknown matches, finished matches and outsiders are ignored.
+    pub fn report(&mut self, round: usize, index: usize, winner: UserId) -> Option<(usize, usize)> {
+        let m = self.rounds.get(round)?.get(index)?;
+        if m.winner.is_some() || (m.player1 != Some(winner) && m.player2 != Some(winner)) {
+            return None;
+        }
+        self.advance(round, index, winner);
+        let next = (round + 1, index / 2);
+        self.get(next.0, next.1)
+            .is_some_and(|m| m.is_ready())
+            .then_some(next)
+    }
+
+    pub fn get(&self, round: usize, index: usize) -> Option<&BracketMatch> {
+        self.rounds.get(round)?.get(index)
+    }
+
+    /// Every match waiting to be played.
+    pub fn ready_matches(&self) -> Vec<(usize, usize)> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(round, matches)| {
+                matches
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| m.is_ready())
+                    .map(move |(index, _)| (round, index))
+            })
+            .collect()
+    }
+
+    pub fn champion(&self) -> Option<UserId> {
+        self.rounds.last()?.first()?.winner
+    }
+
+    pub fn round_name(&self, round: usize) -> String {
+        match self.rounds.len() - round {
+            1 => "Final".to_string(),
+            2 => "Semifinals".to_string(),
+            3 => "Quarterfinals".to_string(),
+            _ => format!("Round {}", round + 1),
+        }
+    }
+}
@@ -1,8 +1,13 @@
 //! This module contains the core implementation of the RPS game,
 //! adhering to the generic `Game` trait.
 
-use super::state::{GameState, Move, RoundOutcome};
+use super::lobby;
+use super::state::{GameState, MatchKind, Move, RoundOutcome};
+use crate::commands::games::lifecycle::SessionClock;
+use crate::commands::games::spectate::spectate_button;
 use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::database::models::GambleGame;
+use crate::database::{gambling, rps_ladder};
 use crate::ui::style::{COLOR_ALERT, COLOR_SAGA_TAVERN};
 use serenity::async_trait;
 use serenity::builder::{
@@ -12,10 +17,13 @@ use serenity::builder::{
 // narrow padding supplied by Btn::narrow
 use crate::ui::buttons::Btn;
 use serenity::model::application::ComponentInteraction;
-use serenity::model::id::UserId;
+use serenity::model::id::{MessageId, UserId};
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
+use std::time::{Duration, Instant};
+
+/// How long the challenged player has to accept.
+const CHALLENGE_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// This struct holds the state of an active RPS game and implements the `Game` trait.
 pub struct RpsGame {
@@ -24,31 +32,59 @@ pub struct RpsGame {
 
 #[async_trait]
 impl Game for RpsGame {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
-    /// Signature updated to match the new Game trait. The `_db` parameter is ignored.
+    /// Signature updated to match the new Game trait. The pool is only used to check the
+    /// opponent's gambling limits when they accept a bet.
     async fn handle_interaction(
         &mut self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
-        _db: &PgPool,
+        db: &PgPool,
     ) -> GameUpdate {
         let custom_id_parts: Vec<&str> = interaction.data.custom_id.split('_').collect();
         let action = custom_id_parts.get(1).unwrap_or(&"");
 
         match *action {
-            "accept" => self.handle_accept(ctx, interaction).await,
+            "accept" => self.handle_accept(ctx, interaction, db).await,
             "decline" => self.handle_decline(ctx, interaction).await,
-            "move" => self.handle_move(ctx, interaction).await,
+            "move" => self.handle_move(ctx, interaction, db).await,
             _ => GameUpdate::NoOp,
         }
     }
 
+    fn gamble_game(&self) -> Option<GambleGame> {
+        (self.state.bet > 0 && self.state.accepted && !self.state.declined)
+            .then_some(GambleGame::Rps)
+    }
+
+    /// Side bets are taken on an accepted duel until its first round is played.
+    fn side_bet_players(&self) -> Vec<UserId> {
+        let open = self.state.accepted
+            && !self.state.declined
+            && self.state.history.is_empty()
+            && !self.state.is_over();
+        if open {
+            vec![self.state.player1.id, self.state.player2.id]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn side_bets_rated(&self) -> bool {
+        true
+    }
+
+    /// Friendly duels pay nothing either way, so the winner comes from the score.
+    fn side_bet_winners(&self, _payouts: &[GamePayout]) -> Vec<UserId> {
+        if !self.state.is_over() {
+            return Vec::new();
+        }
+        if self.state.scores.p1 > self.state.scores.p2 {
+            vec![self.state.player1.id]
+        } else {
+            vec![self.state.player2.id]
+        }
+    }
+
     /// Checks for the declined state first to show the final message.
     fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
         if self.state.declined {
@@ -59,6 +95,24 @@ impl Game for RpsGame {
             self.render_active_game()
         }
     }
+
+    fn participants(&self) -> Vec<UserId> {
+        vec![self.state.player1.id, self.state.player2.id]
+    }
+
+    /// A challenge is called off if the opponent doesn't answer within `CHALLENGE_TIMEOUT`.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        (!self.state.accepted && !self.state.declined).then(|| clock.started + CHALLENGE_TIMEOUT)
+    }
+
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        let (content, embed, components) = Self::render_timeout_message(&self.state);
+        GameUpdate::Expired {
+            content,
+            embed: Box::new(embed),
+            components,
+        }
+    }
 }
 
 impl RpsGame {
@@ -83,6 +137,7 @@ impl RpsGame {
         &mut self,
         ctx: &Context,
         interaction: &ComponentInteraction,
+        db: &PgPool,
     ) -> GameUpdate {
         let p2_id: UserId = interaction
             .data
@@ -98,6 +153,12 @@ impl RpsGame {
                 .await;
             return GameUpdate::NoOp;
         }
+        if self.state.bet > 0
+            && let Err(e) = gambling::check_can_play(db, p2_id).await
+        {
+            self.send_ephemeral_response(ctx, interaction, &e).await;
+            return GameUpdate::NoOp;
+        }
 
         interaction.defer(&ctx.http).await.ok();
         self.state.accepted = true;
@@ -152,12 +213,17 @@ impl RpsGame {
         &mut self,
         ctx: &Context,
         interaction: &mut ComponentInteraction,
+        db: &PgPool,
     ) -> GameUpdate {
         if interaction.user.id != self.state.player1.id
             && interaction.user.id != self.state.player2.id
         {
-            self.send_ephemeral_response(ctx, interaction, "You are not a player in this game.")
-                .await;
+            self.send_ephemeral_response(
+                ctx,
+                interaction,
+                "You are not a player in this game. Press 👁 Spectate to watch or bet on it.",
+            )
+            .await;
             return GameUpdate::NoOp;
         }
         let player_move = match interaction.data.custom_id.split('_').nth(2) {
@@ -191,6 +257,9 @@ impl RpsGame {
             } else {
                 (self.state.player2.id, self.state.player1.id)
             };
+            if self.state.kind.is_rated() {
+                self.record_rated_result(ctx, db, winner_id, loser_id).await;
+            }
             GameUpdate::GameOver {
                 message: "The winner has been decided!".to_string(),
                 payouts: vec![
@@ -209,8 +278,65 @@ impl RpsGame {
         }
     }
 
+    /// Moves both ladder ratings and, for a bracket match, advances the winner.
+    async fn record_rated_result(
+        &mut self,
+        ctx: &Context,
+        db: &PgPool,
+        winner_id: UserId,
+        loser_id: UserId,
+    ) {
+        let bracket = matches!(self.state.kind, MatchKind::Bracket { .. });
+        match rps_ladder::record_match(db, winner_id, loser_id, bracket).await {
+            Ok(change) => self.state.rating_change = Some(change),
+            Err(e) => println!("[RPS] Failed to record ranked result: {:?}", e),
+        }
+        if let MatchKind::Bracket {
+            bracket,
+            round,
+            index,
+        } = &self.state.kind
+        {
+            lobby::report_result(ctx, db, bracket, *round, *index, winner_id).await;
+        }
+    }
+
     // --- Rendering Functions ---
 
+    /// The embed title: the format, plus what the duel counts for.
+    fn title(state: &GameState) -> String {
+        match &state.kind {
+            MatchKind::Casual => format!("Rock Paper Scissors | {}", state.format),
+            MatchKind::Ranked => format!("Ranked Rock Paper Scissors | {}", state.format),
+            MatchKind::Bracket { bracket, round, .. } => {
+                let stage = bracket
+                    .lock()
+                    .map(|b| b.bracket.round_name(*round))
+                    .unwrap_or_default();
+                format!("RPS Bracket · {} | {}", stage, state.format)
+            }
+        }
+    }
+
+    /// Both players' new ratings once a rated match has been recorded.
+    fn rating_line(&self) -> Option<String> {
+        let change = self.state.rating_change?;
+        let (winner, loser) = if self.state.scores.p1 > self.state.scores.p2 {
+            (&self.state.player1, &self.state.player2)
+        } else {
+            (&self.state.player2, &self.state.player1)
+        };
+        Some(format!(
+            "{} {} (+{}) · {} {} ({})",
+            winner.name,
+            change.winner_after,
+            change.winner_delta(),
+            loser.name,
+            change.loser_after,
+            change.loser_delta()
+        ))
+    }
+
     /// (✓) ADDED: A new helper function to build the dynamic header content.
     fn render_header_content(&self) -> String {
         let p1_id = self.state.player1.id;
@@ -254,7 +380,7 @@ impl RpsGame {
             self.state.player1.id, self.state.player2.id
         );
         let embed = CreateEmbed::new()
-            .title(format!("Rock Paper Scissors | {}", self.state.format))
+            .title(Self::title(&self.state))
             .color(COLOR_ALERT)
             .description(format!(
                 "**<@{}> declined the challenge.**",
@@ -279,7 +405,7 @@ impl RpsGame {
             state.player1.id, state.player2.id
         );
         let mut embed = CreateEmbed::new()
-            .title(format!("Rock Paper Scissors | {}", state.format))
+            .title(Self::title(state))
             .color(COLOR_ALERT)
             .field(state.player1.name.clone(), "Status: 👑", true)
             .field(
@@ -313,7 +439,7 @@ impl RpsGame {
             self.state.player1.id, self.state.player2.id
         );
         let mut embed = CreateEmbed::new()
-            .title(format!("Rock Paper Scissors | {}", self.state.format))
+            .title(Self::title(&self.state))
             .color(COLOR_SAGA_TAVERN)
             .field(self.state.player1.name.clone(), "Status: 🕰️ Waiting", true)
             .field("`0` vs `0`", "\u{200B}", true)
@@ -353,13 +479,15 @@ impl RpsGame {
         let (p1_status, p2_status) = self.get_player_statuses();
         let log_content = self.get_log_content();
         let footer_text = self.get_footer_text();
-        let bet_display = if self.state.bet > 0 {
+        let bet_display = if self.state.kind.is_rated() {
+            "📈 Rated".to_string()
+        } else if self.state.bet > 0 {
             format!("💰 **{}**", self.state.bet)
         } else {
             "\u{200B}".to_string()
         };
-        let embed = CreateEmbed::new()
-            .title(format!("Rock Paper Scissors | {}", self.state.format))
+        let mut embed = CreateEmbed::new()
+            .title(Self::title(&self.state))
             .color(if self.state.is_over() {
                 0x00FF00
             } else {
@@ -382,6 +510,9 @@ impl RpsGame {
             )
             .field("\u{200B}", log_content, false)
             .footer(CreateEmbedFooter::new(footer_text));
+        if let Some(line) = self.rating_line() {
+            embed = embed.field("📈 Ladder", line, false);
+        }
         let components = if self.state.is_over() {
             // Final-state navigation: quick Tavern return + global nav row
             vec![
@@ -396,6 +527,7 @@ impl RpsGame {
                 Btn::narrow("rps_move_rock", "✊ Rock").emoji('✊'),
                 Btn::narrow("rps_move_paper", "✋ Paper").emoji('✋'),
                 Btn::narrow("rps_move_scissors", "✌ Scissors").emoji('✌'),
+                spectate_button(),
             ])]
         };
         (content, embed, components)
@@ -0,0 +1,117 @@
+//! Pure ranked ladder rules: Elo rating changes, seasons and the matchmaking window. Nothing here
+//! touches Discord or the database.
+
+use super::state::DuelFormat;
+use crate::database::models::RpsQueueEntry;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+pub const STARTING_RATING: i32 = 1200;
+/// Ratings never drop below this, so a bad run can always be climbed back from.
+pub const RATING_FLOOR: i32 = 100;
+/// Games a player counts as provisional for; their rating moves faster until then.
+pub const PROVISIONAL_GAMES: i32 = 10;
+const PROVISIONAL_K: f64 = 40.0;
+const ESTABLISHED_K: f64 = 24.0;
+pub const SEASON_LENGTH_DAYS: i64 = 28;
+/// How long a queued player waits for an opponent before they are dropped from the queue.
+pub const QUEUE_TIMEOUT_MINUTES: i64 = 10;
+/// The largest rating gap matched straight away; it widens the longer someone has waited.
+const BASE_WINDOW: i32 = 100;
+const WINDOW_GROWTH_PER_MINUTE: i32 = 50;
+const MAX_WINDOW: i32 = 400;
+/// Every rated match, queued or in a bracket, is played as a best of three.
+pub const RANKED_FORMAT: DuelFormat = DuelFormat::BestOf(3);
+
+/// Season 1 began on Monday 6 January 2025; seasons follow back to back.
+fn season_epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap()
+}
+
+/// The season in progress at `now`.
+pub fn season_at(now: DateTime<Utc>) -> i32 {
+    (now - season_epoch())
+        .num_days()
+        .div_euclid(SEASON_LENGTH_DAYS) as i32
+        + 1
+}
+
+/// When `season` ends and the next one begins.
+pub fn season_ends(season: i32) -> DateTime<Utc> {
+    season_epoch() + Duration::days(season as i64 * SEASON_LENGTH_DAYS)
+}
+
+/// A player's first rating of a season: halfway between last season's rating and the start.
+pub fn seeded_rating(previous: Option<i32>) -> i32 {
+    match previous {
+        Some(rating) => STARTING_RATING + (rating - STARTING_RATING) / 2,
+        None => STARTING_RATING,
+    }
+}
+
+/// The chance `rating` beats `opponent`.
+pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) as f64 / 400.0))
+}
+
+pub fn k_factor(games_played: i32) -> f64 {
+    if games_played < PROVISIONAL_GAMES {
+        PROVISIONAL_K
+    } else {
+        ESTABLISHED_K
+    }
+}
+
+/// Both players' ratings before and after a rated match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatingChange {
+    pub winner_before: i32,
+    pub winner_after: i32,
+    pub loser_before: i32,
+    pub loser_after: i32,
+}
+
+impl RatingChange {
+    pub fn winner_delta(&self) -> i32 {
+        self.winner_after - self.winner_before
+    }
+
+    pub fn loser_delta(&self) -> i32 {
+        self.loser_after - self.loser_before
+    }
+}
+
+/// Rates a finished match. Each side uses its own K-factor, and a win is always worth a point.
+pub fn rate(winner: i32, winner_games: i32, loser: i32, loser_games: i32) -> RatingChange {
+    let expected = expected_score(winner, loser);
+    let gain = ((k_factor(winner_games) * (1.0 - expected)).round() as i32).max(1);
+    let loss = (k_factor(loser_games) * (1.0 - expected)).round() as i32;
+    RatingChange {
+        winner_before: winner,
+        winner_after: winner + gain,
+        loser_before: loser,
+        loser_after: (loser - loss).max(RATING_FLOOR),
+    }
+}
+
+/// The largest rating gap accepted for someone who has been queued for `waited`.
+pub fn match_window(waited: Duration) -> i32 {
+    let grown = waited.num_seconds().max(0) * WINDOW_GROWTH_PER_MINUTE as i64 / 60;
+    (BASE_WINDOW as i64 + grown).min(MAX_WINDOW as i64) as i32
+}
+
+/// Whether a queue entry has waited too long and should be dropped.
+pub fn queue_expired(entry: &RpsQueueEntry, now: DateTime<Utc>) -> bool {
+    now - entry.queued_at > Duration::minutes(QUEUE_TIMEOUT_MINUTES)
+}
+
+/// Picks the closest-rated opponent within their match window for a player rated `rating`,
+/// preferring whoever has waited longest on a tie. Expired entries are skipped.
+pub fn pick_opponent(rating: i32, queue: &[RpsQueueEntry], now: DateTime<Utc>) -> Option<usize> {
+    queue
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| !queue_expired(entry, now))
+        .filter(|(_, entry)| (entry.rating - rating).abs() <= match_window(now - entry.queued_at))
+        .min_by_key(|(_, entry)| ((entry.rating - rating).abs(), entry.queued_at))
+        .map(|(i, _)| i)
+}
@@ -0,0 +1,394 @@
+//! Ranked brackets. The sign-up lobby is a game in the `GameManager` until the host starts the
+//! bracket; from then on every match is its own rated `RpsGame`, and the matches share the
+//! bracket through `BracketState`.
+
+use super::bracket::{Bracket, MAX_ENTRANTS, MIN_ENTRANTS};
+use super::game::RpsGame;
+use super::ladder::{RANKED_FORMAT, season_at};
+use super::state::{GameState, MatchKind};
+use crate::AppState;
+use crate::commands::games::lifecycle::SessionClock;
+use crate::commands::games::{Game, GameUpdate};
+use crate::database::rps_ladder;
+use crate::ui::buttons::Btn;
+use crate::ui::style::{COLOR_ALERT, COLOR_SAGA_TAVERN};
+use chrono::Utc;
+use serenity::async_trait;
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditMessage,
+};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{ChannelId, MessageId, UserId};
+use serenity::model::user::User;
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long the lobby waits for the host to start the bracket.
+pub const LOBBY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// A bracket in progress, shared by all of its matches.
+pub struct BracketState {
+    pub bracket: Bracket,
+    pub players: HashMap<UserId, Arc<User>>,
+    pub channel_id: ChannelId,
+    /// The sign-up message, which shows the bracket as it fills in.
+    pub message_id: MessageId,
+}
+
+pub type SharedBracket = Arc<Mutex<BracketState>>;
+
+impl BracketState {
+    /// A rated game for a match whose players are both known.
+    fn match_game(shared: &SharedBracket, round: usize, index: usize) -> Option<RpsGame> {
+        let state = shared.lock().ok()?;
+        let m = state.bracket.get(round, index)?;
+        let player1 = state.players.get(&m.player1?)?.clone();
+        let player2 = state.players.get(&m.player2?)?.clone();
+        let kind = MatchKind::Bracket {
+            bracket: shared.clone(),
+            round,
+            index,
+        };
+        Some(RpsGame {
+            state: GameState::rated(player1, player2, kind),
+        })
+    }
+
+    fn render(&self) -> CreateEmbed {
+        let mention = |player: Option<UserId>| match player {
+            Some(id) => format!("<@{}>", id),
+            None => "TBD".to_string(),
+        };
+        let rounds = self
+            .bracket
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(round, matches)| {
+                let lines = matches
+                    .iter()
+                    .map(|m| match (m.player1, m.player2, m.winner) {
+                        (Some(p), None, Some(_)) | (None, Some(p), Some(_)) => {
+                            format!("<@{}> — bye", p)
+                        }
+                        (p1, p2, Some(winner)) => {
+                            format!("{} vs {} — 👑 <@{}>", mention(p1), mention(p2), winner)
+                        }
+                        (p1, p2, None) => format!("{} vs {}", mention(p1), mention(p2)),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("**{}**\n{}", self.bracket.round_name(round), lines)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let footer = match self.bracket.champion() {
+            Some(champion) => format!(
+                "Champion: {}",
+                self.players
+                    .get(&champion)
+                    .map_or("Unknown", |u| u.name.as_str())
+            ),
+            None => format!("{} · every match is rated", RANKED_FORMAT),
+        };
+        CreateEmbed::new()
+            .title("✊ Rock Paper Scissors Bracket")
+            .description(rounds)
+            .color(COLOR_SAGA_TAVERN)
+            .footer(CreateEmbedFooter::new(footer))
+    }
+}
+
+/// Posts matches as rated games. This runs in its own task because it is usually called while
+/// the match that unlocked them still holds its game's lock, which posting should not prolong.
+pub fn schedule(ctx: &Context, shared: SharedBracket, matches: Vec<(usize, usize)>) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let Some(app_state) = AppState::from_ctx(&ctx).await else {
+            return;
+        };
+        let Some(channel_id) = shared.lock().ok().map(|s| s.channel_id) else {
+            return;
+        };
+        for (round, index) in matches {
+            let Some(game) = BracketState::match_game(&shared, round, index) else {
+                continue;
+            };
+            let (content, embed, components) = game.render();
+            let builder = CreateMessage::new()
+                .content(content)
+                .embed(embed)
+                .components(components);
+            match channel_id.send_message(&ctx.http, builder).await {
+                Ok(msg) => {
+                    app_state
+                        .game_manager
+                        .start_scheduled_game(channel_id, msg.id, Box::new(game))
+                }
+                Err(e) => warn!(round, index, error = ?e, "failed to post bracket match"),
+            }
+        }
+    });
+}
+
+/// Records a finished bracket match: updates the bracket message, schedules the match it
+/// unlocked and crowns the champion once the final is decided.
+pub async fn report_result(
+    ctx: &Context,
+    db: &PgPool,
+    shared: &SharedBracket,
+    round: usize,
+    index: usize,
+    winner: UserId,
+) {
+    let Some((next, champion, channel_id, message_id, embed)) = shared.lock().ok().map(|mut s| {
+        let next = s.bracket.report(round, index, winner);
+        (
+            next,
+            s.bracket.champion(),
+            s.channel_id,
+            s.message_id,
+            s.render(),
+        )
+    }) else {
+        return;
+    };
+    if let Err(e) = channel_id
+        .edit_message(&ctx.http, message_id, EditMessage::new().embed(embed))
+        .await
+    {
+        warn!(error = ?e, "failed to update bracket message");
+    }
+    if let Some(next) = next {
+        schedule(ctx, shared.clone(), vec![next]);
+    }
+    if let Some(champion) = champion {
+        if let Err(e) = rps_ladder::record_bracket_win(db, champion).await {
+            warn!(error = ?e, "failed to record bracket win");
+        }
+        let builder = CreateMessage::new().content(format!(
+            "🏆 <@{}> has won the Rock Paper Scissors bracket!",
+            champion
+        ));
+        channel_id.send_message(&ctx.http, builder).await.ok();
+    }
+}
+
+/// The sign-up lobby for a bracket.
+pub struct BracketLobby {
+    pub host: Arc<User>,
+    pub entrants: Vec<Arc<User>>,
+    pub started: Option<SharedBracket>,
+    pub cancelled: bool,
+}
+
+impl BracketLobby {
+    pub fn new(host: Arc<User>) -> Self {
+        Self {
+            entrants: vec![host.clone()],
+            host,
+            started: None,
+            cancelled: false,
+        }
+    }
+
+    async fn reply(&self, ctx: &Context, interaction: &ComponentInteraction, content: &str) {
+        let response = CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true);
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await
+            .ok();
+    }
+
+    /// Seeds the entrants by ladder rating, best first, and schedules the first round.
+    async fn start(
+        &mut self,
+        ctx: &Context,
+        interaction: &ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let season = season_at(Utc::now());
+        let mut seeded = Vec::with_capacity(self.entrants.len());
+        for user in &self.entrants {
+            match rps_ladder::get_rating(db, user.id, season).await {
+                Ok(rating) => seeded.push((rating.rating, user.id)),
+                Err(_) => {
+                    self.reply(ctx, interaction, "Could not load ratings for seeding.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+            }
+        }
+        interaction.defer(&ctx.http).await.ok();
+        // The sort is stable, so equal ratings keep their sign-up order.
+        seeded.sort_by_key(|(rating, _)| std::cmp::Reverse(*rating));
+        let seeds: Vec<UserId> = seeded.into_iter().map(|(_, id)| id).collect();
+        let bracket = Bracket::new(&seeds);
+        let ready = bracket.ready_matches();
+        let shared = Arc::new(Mutex::new(BracketState {
+            bracket,
+            players: self.entrants.iter().map(|u| (u.id, u.clone())).collect(),
+            channel_id: interaction.channel_id,
+            message_id: interaction.message.id,
+        }));
+        schedule(ctx, shared.clone(), ready);
+        self.started = Some(shared);
+        GameUpdate::GameOver {
+            message: "The RPS bracket has started.".to_string(),
+            payouts: vec![],
+        }
+    }
+
+    pub fn render_expired(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let embed = CreateEmbed::new()
+            .title("✊ Rock Paper Scissors Bracket")
+            .description("The host did not start the bracket in time.")
+            .color(COLOR_ALERT);
+        (String::new(), embed, vec![])
+    }
+}
+
+#[async_trait]
+impl Game for BracketLobby {
+    async fn handle_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let user = &interaction.user;
+        let is_host = user.id == self.host.id;
+        let joined = self.entrants.iter().any(|u| u.id == user.id);
+        match interaction.data.custom_id.as_str() {
+            "rps_bracket_join" => {
+                if joined {
+                    self.reply(ctx, interaction, "You're already in the bracket.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                if self.entrants.len() >= MAX_ENTRANTS {
+                    self.reply(ctx, interaction, "The bracket is full.").await;
+                    return GameUpdate::NoOp;
+                }
+                interaction.defer(&ctx.http).await.ok();
+                self.entrants.push(Arc::new(user.clone()));
+                GameUpdate::ReRender
+            }
+            "rps_bracket_leave" => {
+                if is_host || !joined {
+                    let note = if is_host {
+                        "The host can't leave; cancel the bracket instead."
+                    } else {
+                        "You're not in the bracket."
+                    };
+                    self.reply(ctx, interaction, note).await;
+                    return GameUpdate::NoOp;
+                }
+                interaction.defer(&ctx.http).await.ok();
+                self.entrants.retain(|u| u.id != user.id);
+                GameUpdate::ReRender
+            }
+            "rps_bracket_start" => {
+                if !is_host {
+                    self.reply(ctx, interaction, "Only the host can start the bracket.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                if self.entrants.len() < MIN_ENTRANTS {
+                    let note = format!("A bracket needs at least {} players.", MIN_ENTRANTS);
+                    self.reply(ctx, interaction, &note).await;
+                    return GameUpdate::NoOp;
+                }
+                self.start(ctx, interaction, db).await
+            }
+            "rps_bracket_cancel" => {
+                if !is_host {
+                    self.reply(ctx, interaction, "Only the host can cancel the bracket.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                interaction.defer(&ctx.http).await.ok();
+                self.cancelled = true;
+                GameUpdate::GameOver {
+                    message: "The RPS bracket was cancelled.".to_string(),
+                    payouts: vec![],
+                }
+            }
+            _ => GameUpdate::NoOp,
+        }
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        if let Some(state) = self.started.as_ref().and_then(|s| s.lock().ok()) {
+            let content = format!(
+                "The bracket is under way with {} players! Matches are posted below as they come up.",
+                self.entrants.len()
+            );
+            return (content, state.render(), vec![]);
+        }
+        if self.cancelled {
+            let embed = CreateEmbed::new()
+                .title("✊ Rock Paper Scissors Bracket")
+                .description(format!("<@{}> cancelled the bracket.", self.host.id))
+                .color(COLOR_ALERT);
+            return (String::new(), embed, vec![]);
+        }
+        let entrants = self
+            .entrants
+            .iter()
+            .map(|u| format!("<@{}>", u.id))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let embed = CreateEmbed::new()
+            .title("✊ Rock Paper Scissors Bracket")
+            .description(format!(
+                "<@{}> is running a single-elimination bracket! Every match is a {} and counts toward your ladder rating; the best-rated players are seeded apart.\n\n**Entrants ({}/{}):**\n{}",
+                self.host.id,
+                RANKED_FORMAT,
+                self.entrants.len(),
+                MAX_ENTRANTS,
+                entrants
+            ))
+            .color(COLOR_SAGA_TAVERN)
+            .footer(CreateEmbedFooter::new(format!(
+                "Needs at least {} players. The lobby closes in 5 minutes.",
+                MIN_ENTRANTS
+            )));
+        let buttons = vec![
+            Btn::success("rps_bracket_join", "Join"),
+            Btn::secondary("rps_bracket_leave", "Leave"),
+            Btn::primary("rps_bracket_start", "Start (Host)"),
+            Btn::danger("rps_bracket_cancel", "Cancel (Host)"),
+        ];
+        (
+            String::new(),
+            embed,
+            vec![CreateActionRow::Buttons(buttons)],
+        )
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.entrants.iter().map(|u| u.id).collect()
+    }
+
+    /// Sign-up closes if the host hasn't started the bracket within `LOBBY_TIMEOUT`.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        Some(clock.started + LOBBY_TIMEOUT)
+    }
+
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        let (content, embed, components) = self.render_expired();
+        GameUpdate::Expired {
+            content,
+            embed: Box::new(embed),
+            components,
+        }
+    }
+}
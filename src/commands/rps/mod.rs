@@ -1,6 +1,13 @@
 //! This module contains the logic for the Rock, Paper, Scissors game.
 //! It is the first game to be implemented using the generic Game Engine.
+//!
+//! Besides casual challenges, duels can be ranked: `ladder` holds the rating rules, `bracket` the
+//! single-elimination brackets, and `lobby` the bracket sign-up game that schedules each match as
+//! an ordinary `RpsGame`.
 
+pub mod bracket;
 pub mod game;
+pub mod ladder;
+pub mod lobby;
 pub mod run;
 pub mod state;
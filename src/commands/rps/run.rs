@@ -2,7 +2,9 @@
 
 use super::game::RpsGame;
 use super::state::{DuelFormat, GameState};
+use crate::AppState;
 use crate::commands::games::{Game, GameManager};
+use crate::database::gambling;
 use serenity::builder::{
     CreateCommand, CreateCommandOption, CreateInteractionResponse,
     CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse, EditMessage,
@@ -14,8 +16,6 @@ use serenity::model::channel::Message;
 use serenity::model::user::User;
 use serenity::prelude::*;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
 
 // (✓) FIXED: The main prefix command logic is now correctly named `run_prefix`.
 // The incorrect placeholder function has been removed.
@@ -24,7 +24,7 @@ pub async fn run_prefix(
     ctx: &Context,
     msg: &Message,
     args: Vec<&str>,
-    game_manager: Arc<RwLock<GameManager>>,
+    game_manager: Arc<GameManager>,
 ) {
     let opponent = match msg.mentions.first() {
         Some(user) if user.id != msg.author.id && !user.bot => user.clone(),
@@ -41,6 +41,10 @@ pub async fn run_prefix(
 
     let duel_format = parse_duel_format_from_args(&args).unwrap_or_default();
     let bet = parse_bet_from_args(&args).unwrap_or(0);
+    if let Err(e) = check_limits(ctx, &msg.author, bet).await {
+        msg.reply(ctx, e).await.ok();
+        return;
+    }
 
     let game_state = GameState::new(
         Arc::new(msg.author.clone()),
@@ -57,12 +61,15 @@ pub async fn run_prefix(
         .components(components)
         .reference_message(msg);
 
-    if let Ok(game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
-        game_manager
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(rps_game));
-        spawn_timeout_handler(ctx.clone(), game_manager, game_msg);
+    if let Ok(mut game_msg) = msg.channel_id.send_message(&ctx.http, builder).await {
+        let started = game_manager.start_game(game_msg.channel_id, game_msg.id, Box::new(rps_game));
+        if let Err(e) = started {
+            let builder = EditMessage::new()
+                .content(e)
+                .embeds(vec![])
+                .components(vec![]);
+            game_msg.edit(&ctx.http, builder).await.ok();
+        }
     }
 }
 
@@ -97,7 +104,7 @@ pub fn register() -> CreateCommand {
 pub async fn run_slash(
     ctx: &Context,
     command: &CommandInteraction,
-    game_manager: Arc<RwLock<GameManager>>,
+    game_manager: Arc<GameManager>,
 ) {
     if let Err(e) = command
         .create_response(
@@ -120,6 +127,10 @@ pub async fn run_slash(
 
     let duel_format = get_format_from_options(&command.data.options).unwrap_or_default();
     let bet = get_bet_from_options(&command.data.options).unwrap_or(0);
+    if let Err(e) = check_limits(ctx, &command.user, bet).await {
+        send_ephemeral_error(ctx, command, &e).await;
+        return;
+    }
 
     let game_state = GameState::new(
         Arc::new(command.user.clone()),
@@ -135,40 +146,27 @@ pub async fn run_slash(
         .embed(embed)
         .components(components);
 
-    if let Ok(game_msg) = command.edit_response(&ctx.http, builder).await {
-        game_manager
-            .write()
-            .await
-            .start_game(game_msg.id, Box::new(rps_game));
-        spawn_timeout_handler(ctx.clone(), game_manager, game_msg);
+    if let Ok(mut game_msg) = command.edit_response(&ctx.http, builder).await {
+        let started = game_manager.start_game(game_msg.channel_id, game_msg.id, Box::new(rps_game));
+        if let Err(e) = started {
+            let builder = EditMessage::new()
+                .content(e)
+                .embeds(vec![])
+                .components(vec![]);
+            game_msg.edit(&ctx.http, builder).await.ok();
+        }
     }
 }
 
-/// Spawns a task to handle the 30-second challenge timeout.
-fn spawn_timeout_handler(
-    ctx: Context,
-    game_manager: Arc<RwLock<GameManager>>,
-    mut game_msg: Message,
-) {
-    tokio::spawn(async move {
-        tokio::time::sleep(Duration::from_secs(30)).await;
-        let mut manager = game_manager.write().await;
-
-        if let Some(game_box) = manager.get_game_mut(&game_msg.id)
-            && let Some(rps_game) = game_box.as_any().downcast_ref::<RpsGame>()
-            && !rps_game.state.accepted
-        {
-            let (content, embed, components) = RpsGame::render_timeout_message(&rps_game.state);
-            let builder = EditMessage::new()
-                .content(content)
-                .embed(embed)
-                .components(components);
-            if let Err(e) = game_msg.edit(&ctx.http, builder).await {
-                println!("[RPS] Error editing timeout message: {:?}", e);
-            }
-            manager.remove_game(&game_msg.id);
-        }
-    });
+/// Betting challenges are refused while the challenger is over their gambling limits.
+async fn check_limits(ctx: &Context, user: &User, bet: i64) -> Result<(), String> {
+    if bet == 0 {
+        return Ok(());
+    }
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return Ok(());
+    };
+    gambling::check_can_play(&app_state.db, user.id).await
 }
 
 /// Sends an ephemeral error message in response to a slash command.
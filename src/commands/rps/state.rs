@@ -1,3 +1,5 @@
+use super::ladder::{RANKED_FORMAT, RatingChange};
+use super::lobby::SharedBracket;
 use serenity::model::id::UserId;
 use serenity::model::user::User;
 use std::fmt;
@@ -60,6 +62,27 @@ pub enum RoundOutcome {
     Winner(UserId),
 }
 
+/// What a duel counts for.
+#[derive(Clone, Default)]
+pub enum MatchKind {
+    #[default]
+    Casual,
+    /// Paired by the ranked queue; the result moves both players' ratings.
+    Ranked,
+    /// A rated match in a bracket, identified by its round and position in that round.
+    Bracket {
+        bracket: SharedBracket,
+        round: usize,
+        index: usize,
+    },
+}
+
+impl MatchKind {
+    pub fn is_rated(&self) -> bool {
+        !matches!(self, MatchKind::Casual)
+    }
+}
+
 #[derive(Clone)]
 pub struct GameState {
     pub player1: Arc<User>,
@@ -73,6 +96,9 @@ pub struct GameState {
     pub round: u32,
     pub history: Vec<RoundRecord>,
     pub bet: i64,
+    pub kind: MatchKind,
+    /// Set once a rated match has been recorded on the ladder.
+    pub rating_change: Option<RatingChange>,
 }
 
 impl GameState {
@@ -89,6 +115,17 @@ impl GameState {
             round: 1,
             history: Vec::new(),
             bet,
+            kind: MatchKind::Casual,
+            rating_change: None,
+        }
+    }
+
+    /// A rated match. There is no challenge to accept: both players asked to be paired.
+    pub fn rated(player1: Arc<User>, player2: Arc<User>, kind: MatchKind) -> Self {
+        Self {
+            accepted: true,
+            kind,
+            ..Self::new(player1, player2, RANKED_FORMAT, 0)
         }
     }
 
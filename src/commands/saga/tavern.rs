@@ -36,6 +36,7 @@ pub const FAME_TIERS: [i32; 4] = [0, 50, 150, 400];
 
 // Daily Shop rotation sizing
 pub const SHOP_DAILY_COUNT: usize = 3; // number of Small Arms items shown per day
+pub const SHOP_DAILY_GEAR_COUNT: usize = 2; // unit gear offered alongside them
 
 /// Computes current fame tier index and progress (0..1) toward next tier.
 pub fn fame_tier(fame: i32) -> (usize, f32) {
@@ -78,11 +79,12 @@ pub async fn get_daily_recruits(pool: &sqlx::PgPool) -> Vec<Unit> {
     recruitable
 }
 
-/// Returns a stable, per-user daily rotation of Small Arms shop items.
+/// Returns a stable, per-user daily rotation of Small Arms shop items: `SHOP_DAILY_COUNT` goods
+/// followed by `SHOP_DAILY_GEAR_COUNT` pieces of unit gear.
 /// Deterministic over (user_id, UTC day). Keep the list small to encourage revisits.
 pub fn get_daily_shop_items(user: UserId) -> Vec<Item> {
-    // Full candidate pool for the Small Arms shop (expand over time)
-    let mut all: Vec<Item> = vec![
+    // Full candidate pools for the Small Arms shop (expand over time)
+    let goods: Vec<Item> = vec![
         Item::GreaterHealthPotion,
         Item::XpBooster,
         Item::ForestContractParchment,
@@ -92,18 +94,25 @@ pub fn get_daily_shop_items(user: UserId) -> Vec<Item> {
         Item::FocusTonic,
         Item::TamingLure,
     ];
+    let gear: Vec<Item> = vec![Item::IronSword, Item::LeatherArmor, Item::LuckyCharm];
+    let mut all = daily_rotation(user, goods, SHOP_DAILY_COUNT);
+    all.extend(daily_rotation(user, gear, SHOP_DAILY_GEAR_COUNT));
+    all
+}
+
+fn daily_rotation(user: UserId, mut pool: Vec<Item>, count: usize) -> Vec<Item> {
     let today = Utc::now().date_naive();
     // Stable deterministic shuffle key: user, date, item id combined via splitmix64
     let seed = user.get() ^ ((today.year() as u64) << 32) ^ (today.ordinal() as u64);
-    all.sort_by(|a, b| {
+    pool.sort_by(|a, b| {
         let ka = splitmix64(seed ^ ((*a as i32) as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
         let kb = splitmix64(seed ^ ((*b as i32) as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
         ka.cmp(&kb).then_with(|| (*a as i32).cmp(&(*b as i32)))
     });
-    if all.len() > SHOP_DAILY_COUNT {
-        all.truncate(SHOP_DAILY_COUNT);
+    if pool.len() > count {
+        pool.truncate(count);
     }
-    all
+    pool
 }
 
 /// Human-friendly time remaining until next UTC midnight reset, e.g., "5h 12m".
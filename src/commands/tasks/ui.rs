@@ -1,6 +1,6 @@
 //! Contains the UI rendering logic for the `/tasks` command.
 
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use crate::database::models::{PlayerTaskDetails, TaskType};
 use crate::ui::buttons::Btn;
 use serenity::builder::{CreateActionRow, CreateEmbed, CreateEmbedFooter};
@@ -14,6 +14,7 @@ pub fn create_tasks_embed(tasks: &[PlayerTaskDetails]) -> (CreateEmbed, Vec<Crea
     let mut daily_tasks_str = String::new();
     let mut weekly_tasks_str = String::new();
     let mut claim_buttons = Vec::new();
+    let items = registry();
 
     for task in tasks {
         let status_icon = if task.is_completed { "✅" } else { "⬜" };
@@ -26,17 +27,10 @@ pub fn create_tasks_embed(tasks: &[PlayerTaskDetails]) -> (CreateEmbed, Vec<Crea
         {
             reward_parts.push(format!("💰 **{}** coins", coins));
         }
-        if let (Some(_item_id), Some(quantity), Some(item)) = (
-            task.reward_item_id,
-            task.reward_item_quantity,
-            Item::from_i32(task.reward_item_id.unwrap_or(0)),
-        ) {
-            reward_parts.push(format!(
-                "{} **{}x** {}",
-                item.emoji(),
-                quantity,
-                item.display_name()
-            ));
+        if let (Some(item_id), Some(quantity)) = (task.reward_item_id, task.reward_item_quantity)
+            && let Some(item) = items.get(item_id)
+        {
+            reward_parts.push(format!("{} **{}x** {}", item.emoji, quantity, item.name));
         }
         let reward_display = if reward_parts.is_empty() {
             "No reward specified.".to_string()
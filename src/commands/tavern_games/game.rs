@@ -0,0 +1,911 @@
+//! The tavern contests as a `Game`. Either a player's unit takes on the house champion, or two
+//! players' units face off after a challenge is accepted; both can be played for a wager.
+
+use super::logic::{
+    BOUT_LENGTH, CHALLENGE_TIMEOUT, Effort, HOUSE_PURSE, MAX_STAMINA, PIN, STEADIES, THROWS,
+    Target, Wrestler, drift, exert, house_effort, house_stat, house_target, landing, score_at,
+    spread,
+};
+use crate::commands::games::lifecycle::SessionClock;
+use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::database::economy::get_or_create_profile;
+use crate::database::models::{GambleGame, PlayerUnit};
+use crate::database::{gambling, table_stacks, units};
+use crate::ui::buttons::Btn;
+use crate::ui::style::{COLOR_ALERT, COLOR_SAGA_MAP, COLOR_SAGA_TAVERN, EMOJI_COIN};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serenity::async_trait;
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
+use serenity::model::user::User;
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Lines of play-by-play kept on the embed.
+const LOG_LINES: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContestKind {
+    ArmWrestling,
+    Darts,
+}
+
+impl ContestKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ContestKind::ArmWrestling => "Arm Wrestling",
+            ContestKind::Darts => "Darts",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            ContestKind::ArmWrestling => "💪",
+            ContestKind::Darts => "🎯",
+        }
+    }
+
+    pub fn stat_name(self) -> &'static str {
+        match self {
+            ContestKind::ArmWrestling => "Strength",
+            ContestKind::Darts => "Dexterity",
+        }
+    }
+
+    /// The unit stat the contest tests: attack for strength, defense for dexterity.
+    pub fn stat(self, unit: &PlayerUnit) -> i32 {
+        match self {
+            ContestKind::ArmWrestling => unit.current_attack,
+            ContestKind::Darts => unit.current_defense,
+        }
+    }
+}
+
+/// One side of a contest: a player's unit, or the house champion when `user` is `None`.
+#[derive(Clone)]
+pub struct Contestant {
+    pub user: Option<Arc<User>>,
+    pub unit_name: String,
+    pub stat: i32,
+}
+
+impl Contestant {
+    pub fn from_unit(user: &User, unit: &PlayerUnit, kind: ContestKind) -> Self {
+        Self {
+            user: Some(Arc::new(user.clone())),
+            unit_name: unit.nickname.clone().unwrap_or_else(|| unit.name.clone()),
+            stat: kind.stat(unit),
+        }
+    }
+
+    /// Picks the named party member, or the one best suited to the contest.
+    pub async fn from_party(
+        db: &PgPool,
+        user: &User,
+        kind: ContestKind,
+        unit_name: Option<&str>,
+    ) -> Result<Self, String> {
+        let party = units::get_user_party(db, user.id)
+            .await
+            .map_err(|_| "Could not load your party.".to_string())?;
+        let unit = match unit_name {
+            Some(wanted) => party.iter().find(|u| {
+                u.name.eq_ignore_ascii_case(wanted)
+                    || u.nickname
+                        .as_deref()
+                        .is_some_and(|n| n.eq_ignore_ascii_case(wanted))
+            }),
+            None => party.iter().max_by_key(|u| kind.stat(u)),
+        };
+        match (unit, unit_name) {
+            (Some(unit), _) => Ok(Self::from_unit(user, unit, kind)),
+            (None, Some(wanted)) => Err(format!(
+                "{} has no party member named {}.",
+                user.name, wanted
+            )),
+            (None, None) => Err(format!("{} needs a party member to compete.", user.name)),
+        }
+    }
+
+    fn house(kind: ContestKind, player_stat: i32) -> Self {
+        Self {
+            user: None,
+            unit_name: format!("House {} Champion", kind.name()),
+            stat: house_stat(player_stat, rand::random()),
+        }
+    }
+
+    fn id(&self) -> Option<UserId> {
+        self.user.as_ref().map(|u| u.id)
+    }
+
+    fn label(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{} ({})", self.unit_name, user.name),
+            None => format!("🍺 {}", self.unit_name),
+        }
+    }
+}
+
+/// Refuses a wager the player can't cover or isn't allowed to make under their gambling limits,
+/// before the game is posted. The stake itself is taken once the game's message exists.
+pub async fn check_stake(db: &PgPool, user_id: UserId, bet: i64) -> Result<(), String> {
+    if bet == 0 {
+        return Ok(());
+    }
+    gambling::check_can_play(db, user_id).await?;
+    let profile = get_or_create_profile(db, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    if profile.balance < bet {
+        return Err(format!("You can't cover a wager of {}{}.", EMOJI_COIN, bet));
+    }
+    Ok(())
+}
+
+enum Contest {
+    Arm {
+        wrestlers: [Wrestler; 2],
+        /// The arm's position; positive favours the first side, and `PIN` either way ends it.
+        position: i32,
+        deadline: Option<DateTime<Utc>>,
+    },
+    Darts {
+        throws: [Vec<(i32, String)>; 2],
+        drift: [(f64, f64); 2],
+        steadies: [u8; 2],
+        turn: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Challenge,
+    Playing,
+    Declined,
+    Over,
+}
+
+pub struct TavernGame {
+    pub kind: ContestKind,
+    /// The challenger or house player first. Until a challenge is accepted the second side has no
+    /// unit yet.
+    pub sides: [Contestant; 2],
+    pub phase: Phase,
+    pub bet: i64,
+    contest: Contest,
+    log: Vec<String>,
+    result: Option<String>,
+    /// Set when the finished contest's wager could not be settled at the table.
+    wager_error: Option<String>,
+}
+
+/// A pair of independent standard normal draws (Box–Muller).
+fn normal_pair() -> (f64, f64) {
+    let mut rng = rand::rng();
+    let u1: f64 = rng.random_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.random();
+    let r = (-2.0 * u1.ln()).sqrt();
+    let theta = std::f64::consts::TAU * u2;
+    (r * theta.cos(), r * theta.sin())
+}
+
+impl TavernGame {
+    fn new(kind: ContestKind, sides: [Contestant; 2], phase: Phase, bet: i64) -> Self {
+        let contest = match kind {
+            ContestKind::ArmWrestling => Contest::Arm {
+                wrestlers: [Wrestler::new(sides[0].stat), Wrestler::new(sides[1].stat)],
+                position: 0,
+                deadline: None,
+            },
+            ContestKind::Darts => Contest::Darts {
+                throws: [Vec::new(), Vec::new()],
+                drift: [(0.0, 0.0); 2],
+                steadies: [STEADIES; 2],
+                turn: 0,
+            },
+        };
+        let mut game = Self {
+            kind,
+            sides,
+            phase,
+            bet,
+            contest,
+            log: Vec::new(),
+            result: None,
+            wager_error: None,
+        };
+        if phase == Phase::Playing {
+            game.begin();
+        }
+        game
+    }
+
+    /// A match against the house champion, who is about as good as the player's unit.
+    pub fn vs_house(kind: ContestKind, player: Contestant, bet: i64) -> Self {
+        let house = Contestant::house(kind, player.stat);
+        Self::new(kind, [player, house], Phase::Playing, bet)
+    }
+
+    /// A challenge to another player, whose unit is picked when they accept.
+    pub fn challenge(
+        kind: ContestKind,
+        challenger: Contestant,
+        opponent: Arc<User>,
+        bet: i64,
+    ) -> Self {
+        let pending = Contestant {
+            user: Some(opponent),
+            unit_name: String::new(),
+            stat: 0,
+        };
+        Self::new(kind, [challenger, pending], Phase::Challenge, bet)
+    }
+
+    fn begin(&mut self) {
+        self.phase = Phase::Playing;
+        match &mut self.contest {
+            Contest::Arm {
+                wrestlers,
+                deadline,
+                ..
+            } => {
+                *wrestlers = [
+                    Wrestler::new(self.sides[0].stat),
+                    Wrestler::new(self.sides[1].stat),
+                ];
+                *deadline = Some(Utc::now() + BOUT_LENGTH);
+            }
+            Contest::Darts { .. } => {}
+        }
+    }
+
+    /// Whether the contest is an arm-wrestling bout with a clock running.
+    fn is_timed(&self) -> bool {
+        matches!(self.contest, Contest::Arm { .. }) && self.phase == Phase::Playing
+    }
+
+    fn vs_house_game(&self) -> bool {
+        self.sides[1].user.is_none()
+    }
+
+    fn side_of(&self, user_id: UserId) -> Option<usize> {
+        self.sides.iter().position(|s| s.id() == Some(user_id))
+    }
+
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    /// The winning side, or `None` for a draw.
+    fn winner(&self) -> Option<usize> {
+        let (a, b) = match &self.contest {
+            Contest::Arm { position, .. } => (*position, 0),
+            Contest::Darts { throws, .. } => (total(&throws[0]), total(&throws[1])),
+        };
+        match a.cmp(&b) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// What each side wins or loses of the wager. These move the stakes held at the table.
+    fn wager_results(&self) -> Vec<GamePayout> {
+        let winner = self.winner();
+        self.sides
+            .iter()
+            .enumerate()
+            .filter_map(|(i, side)| {
+                let user_id = side.id()?;
+                let amount = match winner {
+                    Some(w) if w == i => self.bet,
+                    Some(_) => -self.bet,
+                    None => 0,
+                };
+                Some(GamePayout { user_id, amount })
+            })
+            .collect()
+    }
+
+    /// The house pays its purse for a free game. Wagers are settled at the table instead, so a
+    /// wagered game pays nothing here.
+    fn payouts(&self) -> Vec<GamePayout> {
+        if self.bet > 0 {
+            return Vec::new();
+        }
+        let winner = self.winner();
+        self.sides
+            .iter()
+            .enumerate()
+            .filter_map(|(i, side)| {
+                let user_id = side.id()?;
+                let amount = match winner {
+                    Some(w) if w == i && self.vs_house_game() => HOUSE_PURSE,
+                    _ => 0,
+                };
+                Some(GamePayout { user_id, amount })
+            })
+            .collect()
+    }
+
+    /// Moves the wager between the stakes taken at the table once the contest is over. The
+    /// stakes are returned as the table closes, so if this fails everyone gets their stake back.
+    async fn settle_wagers(&mut self, db: &PgPool, table: MessageId) {
+        if self.phase != Phase::Over || self.bet == 0 {
+            return;
+        }
+        let results = self.wager_results();
+        if let Err(e) = table_stacks::settle(db, table, GambleGame::Tavern, &results).await {
+            tracing::error!(table = %table, error = ?e, "failed to settle tavern wager");
+            self.wager_error =
+                Some("⚠️ The wager could not be settled, so every stake was returned.".to_string());
+        }
+    }
+
+    fn finish(&mut self, how: String) -> GameUpdate {
+        self.phase = Phase::Over;
+        self.result = Some(how);
+        GameUpdate::GameOver {
+            message: format!("{} finished.", self.kind.name()),
+            payouts: self.payouts(),
+        }
+    }
+
+    fn finish_on_time(&mut self) -> GameUpdate {
+        let how = match self.winner() {
+            Some(w) => format!(
+                "⏱️ Time! {} has the arm on their side and takes the bout.",
+                self.sides[w].unit_name
+            ),
+            None => "⏱️ Time! The arm is dead level — it's a draw.".to_string(),
+        };
+        self.finish(how)
+    }
+
+    // --- Arm wrestling ---
+
+    fn wrestle(&mut self, side: usize, effort: Effort) {
+        let Contest::Arm {
+            wrestlers,
+            position,
+            ..
+        } = &mut self.contest
+        else {
+            return;
+        };
+        let (first, second) = wrestlers.split_at_mut(1);
+        let (me, them) = if side == 0 {
+            (&mut first[0], &mut second[0])
+        } else {
+            (&mut second[0], &mut first[0])
+        };
+        let force = exert(me, them, effort);
+        *position = if side == 0 {
+            (*position + force).min(PIN)
+        } else {
+            (*position - force).max(-PIN)
+        };
+        let name = &self.sides[side].unit_name;
+        let line = match effort {
+            Effort::Push => format!("{} pushes (+{})", name, force),
+            Effort::Surge => format!("{} surges! (+{})", name, force),
+            Effort::Brace => format!("{} braces and catches their breath", name),
+        };
+        self.push_log(line);
+    }
+
+    fn handle_arm(&mut self, side: usize, action: &str) -> GameUpdate {
+        let deadline = match &self.contest {
+            Contest::Arm { deadline, .. } => *deadline,
+            _ => None,
+        };
+        if deadline.is_some_and(|d| Utc::now() > d) {
+            return self.finish_on_time();
+        }
+        let effort = match action {
+            "push" => Effort::Push,
+            "surge" => Effort::Surge,
+            "brace" => Effort::Brace,
+            _ => return GameUpdate::NoOp,
+        };
+        self.wrestle(side, effort);
+        if self.vs_house_game()
+            && let Contest::Arm {
+                wrestlers,
+                position,
+                ..
+            } = &self.contest
+        {
+            let answer = house_effort(&wrestlers[1], -*position, rand::random());
+            self.wrestle(1, answer);
+        }
+        match &self.contest {
+            Contest::Arm { position, .. } if position.abs() >= PIN => {
+                let (winner, loser) = if *position > 0 { (0, 1) } else { (1, 0) };
+                let how = format!(
+                    "📌 {} slams {}'s arm to the table!",
+                    self.sides[winner].unit_name, self.sides[loser].unit_name
+                );
+                self.finish(how)
+            }
+            _ => GameUpdate::ReRender,
+        }
+    }
+
+    // --- Darts ---
+
+    fn throw(&mut self, side: usize, target: Target) {
+        let spread = spread(self.sides[side].stat);
+        let Contest::Darts {
+            throws, drift: aim, ..
+        } = &mut self.contest
+        else {
+            return;
+        };
+        aim[side] = drift(aim[side], normal_pair());
+        let (x, y) = landing(target, aim[side], spread, normal_pair());
+        let (score, label) = score_at(x, y);
+        throws[side].push((score, label.clone()));
+        let line = format!(
+            "{} aims at {} — **{}** ({})",
+            self.sides[side].unit_name,
+            target.label(),
+            label,
+            score
+        );
+        self.push_log(line);
+    }
+
+    fn handle_darts(&mut self, side: usize, action: &str) -> Result<GameUpdate, &'static str> {
+        let Contest::Darts { turn, steadies, .. } = &self.contest else {
+            return Ok(GameUpdate::NoOp);
+        };
+        if *turn != side {
+            return Err("It's not your throw.");
+        }
+        let target = match action {
+            "bull" => Target::Bull,
+            "t20" => Target::Treble20,
+            "s20" => Target::Single20,
+            "steady" => {
+                if steadies[side] == 0 {
+                    return Err("You've no steadies left.");
+                }
+                if let Contest::Darts {
+                    drift, steadies, ..
+                } = &mut self.contest
+                {
+                    drift[side] = (0.0, 0.0);
+                    steadies[side] -= 1;
+                }
+                let line = format!("{} steadies their aim", self.sides[side].unit_name);
+                self.push_log(line);
+                return Ok(GameUpdate::ReRender);
+            }
+            _ => return Ok(GameUpdate::NoOp),
+        };
+        self.throw(side, target);
+        if self.vs_house_game() {
+            let (house, player) = match &self.contest {
+                Contest::Darts { throws, .. } => (total(&throws[1]), total(&throws[0])),
+                _ => (0, 0),
+            };
+            self.throw(1, house_target(house, player));
+        }
+        let (done, other_left) = match &self.contest {
+            Contest::Darts { throws, .. } => (
+                throws.iter().all(|t| t.len() >= THROWS),
+                throws[1 - side].len() < THROWS,
+            ),
+            _ => return Ok(GameUpdate::NoOp),
+        };
+        if done {
+            let how = match self.winner() {
+                Some(w) => format!("🎯 {} wins the match!", self.sides[w].unit_name),
+                None => "🎯 Level scores — it's a draw.".to_string(),
+            };
+            return Ok(self.finish(how));
+        }
+        // Players alternate darts; against the house the player keeps the throw.
+        if !self.vs_house_game()
+            && other_left
+            && let Contest::Darts { turn, .. } = &mut self.contest
+        {
+            *turn = 1 - side;
+        }
+        Ok(GameUpdate::ReRender)
+    }
+
+    // --- Interaction helpers ---
+
+    async fn reply(&self, ctx: &Context, interaction: &ComponentInteraction, content: &str) {
+        let response = CreateInteractionResponseMessage::new()
+            .content(content)
+            .ephemeral(true);
+        interaction
+            .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+            .await
+            .ok();
+    }
+
+    async fn handle_accept(
+        &mut self,
+        ctx: &Context,
+        interaction: &ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        if self.side_of(interaction.user.id) != Some(1) {
+            self.reply(ctx, interaction, "This challenge isn't for you.")
+                .await;
+            return GameUpdate::NoOp;
+        }
+        let contestant = match Contestant::from_party(db, &interaction.user, self.kind, None).await
+        {
+            Ok(contestant) => contestant,
+            Err(e) => {
+                self.reply(ctx, interaction, &e).await;
+                return GameUpdate::NoOp;
+            }
+        };
+        if self.bet > 0
+            && let Err(e) =
+                table_stacks::buy_in(db, interaction.message.id, interaction.user.id, self.bet)
+                    .await
+        {
+            self.reply(ctx, interaction, &e).await;
+            return GameUpdate::NoOp;
+        }
+        interaction.defer(&ctx.http).await.ok();
+        self.sides[1] = contestant;
+        self.begin();
+        GameUpdate::ReRender
+    }
+}
+
+fn total(throws: &[(i32, String)]) -> i32 {
+    throws.iter().map(|(score, _)| score).sum()
+}
+
+#[async_trait]
+impl Game for TavernGame {
+    async fn handle_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        let custom_id = interaction.data.custom_id.clone();
+        let action = custom_id.strip_prefix("tavern_").unwrap_or("");
+        match (self.phase, action) {
+            (Phase::Challenge, "accept") => self.handle_accept(ctx, interaction, db).await,
+            (Phase::Challenge, "decline") => {
+                if self.side_of(interaction.user.id).is_none() {
+                    self.reply(ctx, interaction, "This challenge isn't for you.")
+                        .await;
+                    return GameUpdate::NoOp;
+                }
+                interaction.defer(&ctx.http).await.ok();
+                self.phase = Phase::Declined;
+                GameUpdate::GameOver {
+                    message: format!("{} challenge declined.", self.kind.name()),
+                    payouts: vec![],
+                }
+            }
+            (Phase::Playing, action) => {
+                let Some(side) = self.side_of(interaction.user.id) else {
+                    self.reply(ctx, interaction, "You're not in this contest.")
+                        .await;
+                    return GameUpdate::NoOp;
+                };
+                let update = match self.kind {
+                    ContestKind::ArmWrestling => Ok(self.handle_arm(side, action)),
+                    ContestKind::Darts => self.handle_darts(side, action),
+                };
+                match update {
+                    Ok(update) => {
+                        self.settle_wagers(db, interaction.message.id).await;
+                        interaction.defer(&ctx.http).await.ok();
+                        update
+                    }
+                    Err(e) => {
+                        self.reply(ctx, interaction, e).await;
+                        GameUpdate::NoOp
+                    }
+                }
+            }
+            _ => GameUpdate::NoOp,
+        }
+    }
+
+    /// Wagered and house games count toward the tavern's gambling stats.
+    fn gamble_game(&self) -> Option<GambleGame> {
+        (self.phase == Phase::Over && (self.bet > 0 || self.vs_house_game()))
+            .then_some(GambleGame::Tavern)
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        match self.phase {
+            Phase::Challenge => self.render_challenge(),
+            Phase::Declined => self.render_declined(),
+            Phase::Playing | Phase::Over => self.render_contest(),
+        }
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.sides.iter().filter_map(|s| s.id()).collect()
+    }
+
+    /// A challenge waits `CHALLENGE_TIMEOUT` for an answer; an arm-wrestling bout runs on its
+    /// own clock.
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        match (self.phase, &self.contest) {
+            (Phase::Challenge, _) => Some(clock.started + CHALLENGE_TIMEOUT),
+            (
+                Phase::Playing,
+                Contest::Arm {
+                    deadline: Some(d), ..
+                },
+            ) => {
+                let left = (*d - Utc::now()).to_std().unwrap_or_default();
+                Some(Instant::now() + left)
+            }
+            _ => None,
+        }
+    }
+
+    async fn on_timeout(&mut self, db: &PgPool, message_id: MessageId) -> GameUpdate {
+        match self.phase {
+            Phase::Challenge => {
+                let (content, embed, components) = self.render_expired();
+                GameUpdate::Expired {
+                    content,
+                    embed: Box::new(embed),
+                    components,
+                }
+            }
+            Phase::Playing if self.is_timed() => {
+                let update = self.finish_on_time();
+                self.settle_wagers(db, message_id).await;
+                update
+            }
+            _ => GameUpdate::NoOp,
+        }
+    }
+}
+
+// --- Rendering ---
+
+fn stamina_bar(stamina: i32) -> String {
+    let filled = (stamina * 10 / MAX_STAMINA).clamp(0, 10) as usize;
+    format!(
+        "{}{} {}",
+        "▰".repeat(filled),
+        "▱".repeat(10 - filled),
+        stamina
+    )
+}
+
+/// The arm as a track between the two sides; the fist moves toward whoever is being pinned.
+fn arm_track(position: i32) -> String {
+    let slot = ((position + PIN) * 10 / (2 * PIN)).clamp(0, 10) as usize;
+    let track: String = (0..=10)
+        .map(|i| if i == slot { "✊" } else { "▬" })
+        .collect();
+    format!("◀ {} ▶", track)
+}
+
+impl TavernGame {
+    fn title(&self) -> String {
+        format!("{} Tavern {}", self.kind.emoji(), self.kind.name())
+    }
+
+    fn stake_line(&self) -> String {
+        if self.bet > 0 {
+            format!("Wager: **{}{}** each", EMOJI_COIN, self.bet)
+        } else if self.vs_house_game() {
+            format!(
+                "Beat the house for a purse of **{}{}**",
+                EMOJI_COIN, HOUSE_PURSE
+            )
+        } else {
+            "A friendly contest — nothing riding on it.".to_string()
+        }
+    }
+
+    fn mentions(&self) -> String {
+        self.sides
+            .iter()
+            .filter_map(|s| s.id())
+            .map(|id| format!("<@{}>", id))
+            .collect::<Vec<_>>()
+            .join(" vs ")
+    }
+
+    fn final_rows() -> Vec<CreateActionRow> {
+        vec![
+            CreateActionRow::Buttons(vec![Btn::secondary(
+                crate::interactions::ids::SAGA_TAVERN_HOME,
+                "🏰 Tavern",
+            )]),
+            crate::commands::saga::ui::tavern_saga_row(),
+        ]
+    }
+
+    fn render_challenge(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let challenger = &self.sides[0];
+        let embed = CreateEmbed::new()
+            .title(self.title())
+            .description(format!(
+                "{} puts up **{}** ({} {}) and challenges <@{}>!\n{}",
+                challenger
+                    .user
+                    .as_ref()
+                    .map_or(String::new(), |u| format!("<@{}>", u.id)),
+                challenger.unit_name,
+                self.kind.stat_name(),
+                challenger.stat,
+                self.sides[1].id().map_or(0, |id| id.get()),
+                self.stake_line()
+            ))
+            .color(COLOR_SAGA_TAVERN)
+            .footer(CreateEmbedFooter::new(format!(
+                "Your best {} unit steps up when you accept. The challenge expires in 60 seconds.",
+                self.kind.stat_name().to_lowercase()
+            )));
+        let buttons = vec![
+            Btn::success("tavern_accept", "✅ Accept"),
+            Btn::danger("tavern_decline", "❌ Decline"),
+        ];
+        (
+            self.mentions(),
+            embed,
+            vec![CreateActionRow::Buttons(buttons)],
+        )
+    }
+
+    fn render_declined(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let embed = CreateEmbed::new()
+            .title(self.title())
+            .description("The challenge was called off.")
+            .color(COLOR_ALERT);
+        (self.mentions(), embed, Self::final_rows())
+    }
+
+    /// Shown when a challenge goes unanswered.
+    fn render_expired(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let embed = CreateEmbed::new()
+            .title(self.title())
+            .description("The challenge was not accepted in time.")
+            .color(COLOR_ALERT);
+        (self.mentions(), embed, Self::final_rows())
+    }
+
+    fn render_contest(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let over = self.phase == Phase::Over;
+        let mut embed = CreateEmbed::new().title(self.title()).color(if over {
+            COLOR_SAGA_MAP
+        } else {
+            COLOR_SAGA_TAVERN
+        });
+        let mut rows = Vec::new();
+        let mut description = vec![self.stake_line()];
+        match &self.contest {
+            Contest::Arm {
+                wrestlers,
+                position,
+                deadline,
+            } => {
+                description.push(format!(
+                    "**{}** {} **{}**",
+                    self.sides[0].unit_name,
+                    arm_track(*position),
+                    self.sides[1].unit_name
+                ));
+                if let (false, Some(deadline)) = (over, deadline) {
+                    description.push(format!(
+                        "Drive the fist to your opponent's end! Time runs out <t:{}:R>.",
+                        deadline.timestamp()
+                    ));
+                }
+                for (side, wrestler) in self.sides.iter().zip(wrestlers) {
+                    embed = embed.field(
+                        side.label(),
+                        format!(
+                            "💪 {} · 🫁 {}{}",
+                            side.stat,
+                            stamina_bar(wrestler.stamina),
+                            if wrestler.braced { " · 🛡️" } else { "" }
+                        ),
+                        true,
+                    );
+                }
+                if !over {
+                    rows.push(CreateActionRow::Buttons(vec![
+                        Btn::primary("tavern_push", "💪 Push"),
+                        Btn::danger("tavern_surge", "⚡ Surge"),
+                        Btn::secondary("tavern_brace", "🛡️ Brace"),
+                    ]));
+                }
+                embed = embed.footer(CreateEmbedFooter::new(
+                    "Push is cheap, Surge hits twice as hard for three times the stamina, Brace recovers and blunts the next effort.",
+                ));
+            }
+            Contest::Darts {
+                throws,
+                drift,
+                steadies,
+                turn,
+            } => {
+                for (i, side) in self.sides.iter().enumerate() {
+                    let marks = throws[i]
+                        .iter()
+                        .map(|(_, label)| label.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" · ");
+                    let to_throw = if !over && *turn == i { "▶️ " } else { "" };
+                    embed = embed.field(
+                        format!("{}{}", to_throw, side.label()),
+                        format!(
+                            "**{}** pts ({}/{} darts)\n{}\n🎯 {} · drift {:.0}mm · steadies {}",
+                            total(&throws[i]),
+                            throws[i].len(),
+                            THROWS,
+                            if marks.is_empty() { "—" } else { &marks },
+                            side.stat,
+                            drift[i].0.hypot(drift[i].1),
+                            steadies[i]
+                        ),
+                        true,
+                    );
+                }
+                if !over {
+                    rows.push(CreateActionRow::Buttons(vec![
+                        Btn::primary("tavern_bull", "🎯 Bull"),
+                        Btn::danger("tavern_t20", "🔴 Treble 20"),
+                        Btn::secondary("tavern_s20", "⚪ Single 20"),
+                        Btn::secondary("tavern_steady", "🫲 Steady"),
+                    ]));
+                }
+                embed = embed.footer(CreateEmbedFooter::new(
+                    "Your aim drifts a little with every dart; steady it to start fresh. Better dexterity scatters less.",
+                ));
+            }
+        }
+        if !self.log.is_empty() {
+            embed = embed.field("Play by play", self.log.join("\n"), false);
+        }
+        if let Some(result) = &self.result {
+            description.push(format!("\n**{}**", result));
+            let results = if self.bet > 0 {
+                self.wager_results()
+            } else {
+                self.payouts()
+            };
+            let winnings = results
+                .iter()
+                .filter(|p| p.amount != 0)
+                .map(|p| format!("<@{}> {:+}{}", p.user_id, p.amount, EMOJI_COIN))
+                .collect::<Vec<_>>()
+                .join(" · ");
+            if let Some(error) = &self.wager_error {
+                description.push(error.clone());
+            } else if !winnings.is_empty() {
+                description.push(winnings);
+            }
+            rows = Self::final_rows();
+        }
+        embed = embed.description(description.join("\n"));
+        (self.mentions(), embed, rows)
+    }
+}
@@ -0,0 +1,220 @@
+//! Pure rules for the tavern contests: arm wrestling as a stamina duel and darts thrown at a real
+//! board with drifting aim. Nothing here touches Discord or the database; randomness comes in as
+//! arguments so outcomes can be tested.
+
+use std::time::Duration;
+
+/// Coins the house pays for beating its champion in a game without a wager.
+pub const HOUSE_PURSE: i64 = 25;
+/// How long a PvP challenge waits to be accepted.
+pub const CHALLENGE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A stat for the house champion close to the player's, so the contest is never a foregone
+/// conclusion. `roll` is any random number.
+pub fn house_stat(player_stat: i32, roll: u32) -> i32 {
+    (player_stat - 6 + (roll % 13) as i32).max(1)
+}
+
+// --- Arm wrestling ---
+
+/// How long a bout lasts; whoever has the arm on their side when time runs out wins.
+pub const BOUT_LENGTH: Duration = Duration::from_secs(20);
+/// How far the arm has to go to pin the other side.
+pub const PIN: i32 = 100;
+pub const MAX_STAMINA: i32 = 100;
+const PUSH_COST: i32 = 8;
+const SURGE_COST: i32 = 25;
+const BRACE_RECOVERY: i32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effort {
+    /// A steady push; cheap and reliable.
+    Push,
+    /// Twice the force of a push for three times the stamina.
+    Surge,
+    /// Recovers stamina and halves the opponent's next effort.
+    Brace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wrestler {
+    pub strength: i32,
+    pub stamina: i32,
+    /// Set by a brace; halves the next effort against this wrestler.
+    pub braced: bool,
+}
+
+impl Wrestler {
+    pub fn new(strength: i32) -> Self {
+        Self {
+            strength,
+            stamina: MAX_STAMINA,
+            braced: false,
+        }
+    }
+
+    pub fn can_surge(&self) -> bool {
+        self.stamina >= SURGE_COST
+    }
+
+    /// Force behind a push. Tired arms push with as little as 40% of their strength.
+    fn push_force(&self) -> i32 {
+        let base = 6 + self.strength / 4;
+        base * (40 + 60 * self.stamina / MAX_STAMINA) / 100
+    }
+}
+
+/// Applies one effort by `wrestler` against `opponent` and returns how far the arm moves in the
+/// wrestler's favour. Out of stamina, a push or surge does nothing.
+pub fn exert(wrestler: &mut Wrestler, opponent: &mut Wrestler, effort: Effort) -> i32 {
+    let mut force = match effort {
+        Effort::Push if wrestler.stamina >= PUSH_COST => {
+            let force = wrestler.push_force();
+            wrestler.stamina -= PUSH_COST;
+            force
+        }
+        Effort::Surge if wrestler.can_surge() => {
+            let force = wrestler.push_force() * 2;
+            wrestler.stamina -= SURGE_COST;
+            force
+        }
+        Effort::Brace => {
+            wrestler.stamina = (wrestler.stamina + BRACE_RECOVERY).min(MAX_STAMINA);
+            wrestler.braced = true;
+            0
+        }
+        _ => 0,
+    };
+    if force > 0 && opponent.braced {
+        force /= 2;
+        opponent.braced = false;
+    }
+    force
+}
+
+/// The house champion's answer to a player's effort. `position` is the arm's position from the
+/// house's side (positive is winning) and `roll` is any random number.
+pub fn house_effort(house: &Wrestler, position: i32, roll: u32) -> Effort {
+    if house.stamina < 20 || (house.stamina < 50 && roll.is_multiple_of(3)) {
+        Effort::Brace
+    } else if house.can_surge() && (position < -40 || roll.is_multiple_of(5)) {
+        Effort::Surge
+    } else {
+        Effort::Push
+    }
+}
+
+// --- Darts ---
+
+/// Darts each player throws.
+pub const THROWS: usize = 6;
+/// Times each player may steady their aim, cancelling the drift built up so far.
+pub const STEADIES: u8 = 2;
+/// How far (in mm) the aim wanders each throw, and how far it can wander in total.
+const DRIFT_STEP: f64 = 6.0;
+const MAX_DRIFT: f64 = 30.0;
+
+// Board radii in mm, from the centre out.
+const BULL: f64 = 6.35;
+const OUTER_BULL: f64 = 15.9;
+const TREBLE_INNER: f64 = 99.0;
+const TREBLE_OUTER: f64 = 107.0;
+const DOUBLE_INNER: f64 = 162.0;
+const DOUBLE_OUTER: f64 = 170.0;
+/// Sectors clockwise from the top.
+const SECTORS: [i32; 20] = [
+    20, 1, 18, 4, 13, 6, 10, 15, 2, 17, 3, 19, 7, 16, 8, 11, 14, 9, 12, 5,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Bull,
+    Treble20,
+    Single20,
+}
+
+impl Target {
+    /// Where a dart aimed at this target is pointed, in mm from the centre (y points up).
+    pub fn aim_point(self) -> (f64, f64) {
+        match self {
+            Target::Bull => (0.0, 0.0),
+            Target::Treble20 => (0.0, (TREBLE_INNER + TREBLE_OUTER) / 2.0),
+            Target::Single20 => (0.0, (TREBLE_OUTER + DOUBLE_INNER) / 2.0),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Target::Bull => "Bull",
+            Target::Treble20 => "Treble 20",
+            Target::Single20 => "Single 20",
+        }
+    }
+}
+
+/// Scores a dart landing at `(x, y)` mm from the centre, with a short label such as `T20`.
+pub fn score_at(x: f64, y: f64) -> (i32, String) {
+    let r = x.hypot(y);
+    if r <= BULL {
+        return (50, "Bull".to_string());
+    }
+    if r <= OUTER_BULL {
+        return (25, "25".to_string());
+    }
+    if r > DOUBLE_OUTER {
+        return (0, "Miss".to_string());
+    }
+    // Angle clockwise from straight up; each sector is 18° wide and centred on its number.
+    let degrees = x.atan2(y).to_degrees().rem_euclid(360.0);
+    let sector = SECTORS[(((degrees + 9.0) / 18.0) as usize) % 20];
+    if (TREBLE_INNER..=TREBLE_OUTER).contains(&r) {
+        (sector * 3, format!("T{}", sector))
+    } else if r >= DOUBLE_INNER {
+        (sector * 2, format!("D{}", sector))
+    } else {
+        (sector, sector.to_string())
+    }
+}
+
+/// How widely (one standard deviation, in mm) a thrower with this dexterity scatters.
+pub fn spread(dexterity: i32) -> f64 {
+    (36.0 - dexterity as f64 / 2.0).max(10.0)
+}
+
+/// Wanders the aim by one throw's worth of drift. `nudge` is a pair of standard normal draws.
+pub fn drift(current: (f64, f64), nudge: (f64, f64)) -> (f64, f64) {
+    let (x, y) = (
+        current.0 + nudge.0 * DRIFT_STEP,
+        current.1 + nudge.1 * DRIFT_STEP,
+    );
+    let len = x.hypot(y);
+    if len > MAX_DRIFT {
+        (x * MAX_DRIFT / len, y * MAX_DRIFT / len)
+    } else {
+        (x, y)
+    }
+}
+
+/// Where a dart lands: the target, shifted by the drifted aim and scattered by `spread`.
+/// `scatter` is a pair of standard normal draws.
+pub fn landing(
+    target: Target,
+    aim_drift: (f64, f64),
+    spread: f64,
+    scatter: (f64, f64),
+) -> (f64, f64) {
+    let (x, y) = target.aim_point();
+    (
+        x + aim_drift.0 + scatter.0 * spread,
+        y + aim_drift.1 + scatter.1 * spread,
+    )
+}
+
+/// What the house champion aims for: the treble when it's behind, the bull otherwise.
+pub fn house_target(house_score: i32, player_score: i32) -> Target {
+    if house_score < player_score {
+        Target::Treble20
+    } else {
+        Target::Bull
+    }
+}
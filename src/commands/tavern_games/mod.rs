@@ -0,0 +1,7 @@
+//! Implements `/contest`: the tavern's arm wrestling and darts. `logic` holds the rules of both
+//! contests, `game` plays them as a `Game` against the house champion or another player's unit,
+//! and `run` starts them from the command or the Saga tavern.
+
+pub mod game;
+pub mod logic;
+pub mod run;
@@ -0,0 +1,222 @@
+//! Handles the command logic for `/contest` and `$contest`, and starting a house game from the
+//! Saga tavern.
+
+use super::game::{ContestKind, Contestant, TavernGame, check_stake};
+use crate::AppState;
+use crate::commands::games::{Game, refuse_game};
+use crate::database::models::PlayerUnit;
+use crate::database::table_stacks;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditInteractionResponse,
+};
+use serenity::model::application::{
+    CommandDataOptionValue, CommandInteraction, CommandOptionType, ComponentInteraction,
+};
+use serenity::model::channel::Message;
+use serenity::model::user::User;
+use serenity::prelude::*;
+use std::sync::Arc;
+
+const USAGE: &str = "Usage: `$contest <arm|darts> [@user] [bet] [unit name]`";
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("contest")
+        .description("Arm wrestling or darts in the tavern, against the house or another player.")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "game", "Which contest")
+                .required(true)
+                .add_string_choice("Arm Wrestling", "arm")
+                .add_string_choice("Darts", "darts"),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "opponent",
+                "Challenge another player instead of the house",
+            )
+            .required(false),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "bet", "Coins to wager")
+                .required(false)
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "unit",
+                "Party member to send in. Defaults to your best for the contest.",
+            )
+            .required(false),
+        )
+}
+
+fn parse_kind(s: &str) -> Option<ContestKind> {
+    match s.to_lowercase().as_str() {
+        "arm" | "armwrestling" | "arm-wrestling" | "wrestle" => Some(ContestKind::ArmWrestling),
+        "darts" | "dart" => Some(ContestKind::Darts),
+        _ => None,
+    }
+}
+
+/// Builds the game for a request: a match against the house, or a challenge to `opponent`.
+async fn build_game(
+    app_state: &AppState,
+    user: &User,
+    kind: ContestKind,
+    opponent: Option<User>,
+    bet: i64,
+    unit_name: Option<&str>,
+) -> Result<TavernGame, String> {
+    let db = &app_state.db;
+    let contestant = Contestant::from_party(db, user, kind, unit_name).await?;
+    check_stake(db, user.id, bet).await?;
+    match opponent {
+        Some(opponent) if opponent.bot => Err("You cannot challenge a bot.".to_string()),
+        Some(opponent) if opponent.id == user.id => {
+            Err("You cannot challenge yourself.".to_string())
+        }
+        Some(opponent) => Ok(TavernGame::challenge(
+            kind,
+            contestant,
+            Arc::new(opponent),
+            bet,
+        )),
+        None => Ok(TavernGame::vs_house(kind, contestant, bet)),
+    }
+}
+
+/// Takes the challenger's stake and tracks a freshly posted game. The game manager's sweeper
+/// runs its clock, and whatever is staked goes back if the game never starts.
+async fn track_game(ctx: &Context, app_state: &AppState, message: &Message, game: TavernGame) {
+    if game.bet > 0
+        && let Some(&user_id) = game.participants().first()
+        && let Err(e) = table_stacks::buy_in(&app_state.db, message.id, user_id, game.bet).await
+    {
+        refuse_game(ctx, &app_state.db, message.channel_id, message.id, &e).await;
+        return;
+    }
+    let started = app_state
+        .game_manager
+        .start_game(message.channel_id, message.id, Box::new(game));
+    if let Err(e) = started {
+        refuse_game(ctx, &app_state.db, message.channel_id, message.id, &e).await;
+    }
+}
+
+pub async fn run_slash(ctx: &Context, command: &CommandInteraction) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let response = CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new());
+    if command.create_response(&ctx.http, response).await.is_err() {
+        return;
+    }
+
+    let mut kind = None;
+    let mut opponent = None;
+    let mut bet = 0;
+    let mut unit_name = None;
+    for option in &command.data.options {
+        match (option.name.as_str(), &option.value) {
+            ("game", CommandDataOptionValue::String(s)) => kind = parse_kind(s),
+            ("opponent", CommandDataOptionValue::User(id)) => {
+                opponent = command.data.resolved.users.get(id).cloned()
+            }
+            ("bet", CommandDataOptionValue::Integer(n)) => bet = *n,
+            ("unit", CommandDataOptionValue::String(s)) => unit_name = Some(s.as_str()),
+            _ => {}
+        }
+    }
+    let Some(kind) = kind else {
+        let builder = EditInteractionResponse::new().content(USAGE);
+        command.edit_response(&ctx.http, builder).await.ok();
+        return;
+    };
+
+    let game = match build_game(&app_state, &command.user, kind, opponent, bet, unit_name).await {
+        Ok(game) => game,
+        Err(e) => {
+            let builder = EditInteractionResponse::new().content(e);
+            command.edit_response(&ctx.http, builder).await.ok();
+            return;
+        }
+    };
+    let (content, embed, components) = game.render();
+    let builder = EditInteractionResponse::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+    if let Ok(message) = command.edit_response(&ctx.http, builder).await {
+        track_game(ctx, &app_state, &message, game).await;
+    }
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        return;
+    };
+    let Some(kind) = args.first().and_then(|s| parse_kind(s)) else {
+        msg.reply(ctx, USAGE).await.ok();
+        return;
+    };
+    let opponent = msg.mentions.first().cloned();
+    let mut bet = 0;
+    let mut name_words = Vec::new();
+    for arg in &args[1..] {
+        if arg.starts_with("<@") {
+            continue;
+        }
+        match arg.parse::<i64>() {
+            Ok(n) if n > 0 && bet == 0 => bet = n,
+            _ => name_words.push(*arg),
+        }
+    }
+    let unit_name = (!name_words.is_empty()).then(|| name_words.join(" "));
+
+    let game = match build_game(
+        &app_state,
+        &msg.author,
+        kind,
+        opponent,
+        bet,
+        unit_name.as_deref(),
+    )
+    .await
+    {
+        Ok(game) => game,
+        Err(e) => {
+            msg.reply(ctx, e).await.ok();
+            return;
+        }
+    };
+    let (content, embed, components) = game.render();
+    let builder = CreateMessage::new()
+        .content(content)
+        .embed(embed)
+        .components(components)
+        .reference_message(msg);
+    if let Ok(message) = msg.channel_id.send_message(&ctx.http, builder).await {
+        track_game(ctx, &app_state, &message, game).await;
+    }
+}
+
+/// Starts a free game against the house from the Saga tavern's contestant picker.
+pub async fn start_from_tavern(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    app_state: &AppState,
+    kind: ContestKind,
+    unit: &PlayerUnit,
+) {
+    let game = TavernGame::vs_house(kind, Contestant::from_unit(&component.user, unit, kind), 0);
+    let (content, embed, components) = game.render();
+    let builder = EditInteractionResponse::new()
+        .content(content)
+        .embed(embed)
+        .components(components);
+    if let Ok(message) = component.edit_response(&ctx.http, builder).await {
+        track_game(ctx, app_state, &message, game).await;
+    }
+}
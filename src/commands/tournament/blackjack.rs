@@ -0,0 +1,156 @@
+//! A single round of tournament blackjack. Everyone bets the current level's amount (or what
+//! they have left), plays their hand against the dealer in seat order, and the result moves
+//! tournament chips rather than coins.
+
+use crate::commands::blackjack::state::{Hand, HandStatus};
+use crate::commands::games::deck::Deck;
+use serenity::model::id::UserId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackMove {
+    Hit,
+    Stand,
+    Double,
+}
+
+pub struct BlackjackSeat {
+    pub user_id: UserId,
+    pub chips: i64,
+    pub hand: Hand,
+}
+
+pub struct BlackjackRound {
+    pub seats: Vec<BlackjackSeat>,
+    pub dealer: Hand,
+    pub to_act: usize,
+    deck: Deck,
+}
+
+impl BlackjackRound {
+    /// Takes each player's bet (capped at their chips) and deals two cards to everyone.
+    pub fn new(players: &[(UserId, i64)], bet: i64, mut deck: Deck) -> Self {
+        let mut seats: Vec<BlackjackSeat> = players
+            .iter()
+            .map(|&(user_id, chips)| BlackjackSeat {
+                user_id,
+                chips,
+                hand: Hand::new(bet.min(chips)),
+            })
+            .collect();
+        let mut dealer = Hand::new(0);
+        for _ in 0..2 {
+            for seat in &mut seats {
+                if let Some(card) = deck.deal_one() {
+                    seat.hand.add_card(card);
+                }
+            }
+            if let Some(card) = deck.deal_one() {
+                dealer.add_card(card);
+            }
+        }
+        for seat in &mut seats {
+            if seat.hand.score() == 21 {
+                seat.hand.status = HandStatus::Blackjack;
+            }
+        }
+        let mut round = Self {
+            seats,
+            dealer,
+            to_act: 0,
+            deck,
+        };
+        round.advance();
+        round
+    }
+
+    pub fn current_seat(&self) -> Option<&BlackjackSeat> {
+        self.seats.get(self.to_act)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.to_act >= self.seats.len()
+    }
+
+    pub fn apply(&mut self, user_id: UserId, action: BlackjackMove) -> Result<(), String> {
+        let Some(seat) = self.seats.get_mut(self.to_act) else {
+            return Err("This round is over.".into());
+        };
+        if seat.user_id != user_id {
+            return Err("It's not your turn.".into());
+        }
+        match action {
+            BlackjackMove::Stand => seat.hand.status = HandStatus::Stood,
+            BlackjackMove::Hit => {
+                if let Some(card) = self.deck.deal_one() {
+                    seat.hand.add_card(card);
+                }
+            }
+            BlackjackMove::Double => {
+                if !seat.hand.can_double_down() {
+                    return Err("You can only double down on your first two cards.".into());
+                }
+                if seat.chips < seat.hand.bet * 2 {
+                    return Err("You don't have enough chips to double down.".into());
+                }
+                seat.hand.bet *= 2;
+                if let Some(card) = self.deck.deal_one() {
+                    seat.hand.add_card(card);
+                }
+                seat.hand.status = HandStatus::Stood;
+            }
+        }
+        if seat.hand.score() > 21 {
+            seat.hand.status = HandStatus::Busted;
+        } else if seat.hand.score() == 21 && seat.hand.status == HandStatus::Playing {
+            seat.hand.status = HandStatus::Stood;
+        }
+        self.advance();
+        Ok(())
+    }
+
+    /// Chips each player ends the round with.
+    pub fn settle(&self) -> Vec<(UserId, i64)> {
+        let dealer_score = self.dealer.score();
+        let dealer_busted = dealer_score > 21;
+        let dealer_has_bj = dealer_score == 21 && self.dealer.cards.len() == 2;
+        self.seats
+            .iter()
+            .map(|seat| {
+                let hand = &seat.hand;
+                let net = match hand.status {
+                    HandStatus::Busted => -hand.bet,
+                    HandStatus::Blackjack if dealer_has_bj => 0,
+                    HandStatus::Blackjack => hand.bet * 3 / 2,
+                    _ if dealer_has_bj => -hand.bet,
+                    _ if dealer_busted || hand.score() > dealer_score => hand.bet,
+                    _ if hand.score() == dealer_score => 0,
+                    _ => -hand.bet,
+                };
+                (seat.user_id, seat.chips + net)
+            })
+            .collect()
+    }
+
+    /// Moves to the next hand still being played, or plays the dealer once everyone is done.
+    fn advance(&mut self) {
+        while self
+            .seats
+            .get(self.to_act)
+            .is_some_and(|s| s.hand.status != HandStatus::Playing)
+        {
+            self.to_act += 1;
+        }
+        if self.is_finished() {
+            let anyone_live = self
+                .seats
+                .iter()
+                .any(|s| s.hand.status == HandStatus::Stood);
+            while anyone_live && self.dealer.score() < 17 {
+                match self.deck.deal_one() {
+                    Some(card) => self.dealer.add_card(card),
+                    None => break,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Pure tournament rules: the blind and bet schedule, seating, table balancing and the prize
+//! split. Nothing here touches Discord or the database.
+
+use crate::commands::games::GamePayout;
+use crate::database::models::TournamentGame;
+use serenity::model::id::UserId;
+use std::time::Duration;
+
+/// Chips every entrant starts with, whatever the buy-in.
+pub const STARTING_CHIPS: i64 = 1_000;
+/// How long the registration window stays open before the tournament starts on its own.
+pub const REGISTRATION_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// How long each blind or bet level lasts.
+pub const LEVEL_DURATION: Duration = Duration::from_secs(5 * 60);
+pub const MIN_ENTRANTS: usize = 2;
+pub const MAX_ENTRANTS: usize = 30;
+
+/// Hold'em big blinds per level; the last level repeats until one player has every chip.
+const BIG_BLINDS: [i64; 10] = [20, 30, 50, 80, 100, 150, 200, 300, 500, 800];
+/// Blackjack bets per level. The tournament ends when the last level runs out.
+const BLACKJACK_BETS: [i64; 6] = [25, 50, 75, 100, 150, 200];
+
+/// The level in play after `elapsed` time.
+pub fn level_for(elapsed: Duration) -> usize {
+    (elapsed.as_secs() / LEVEL_DURATION.as_secs()) as usize
+}
+
+pub fn big_blind(level: usize) -> i64 {
+    BIG_BLINDS[level.min(BIG_BLINDS.len() - 1)]
+}
+
+pub fn blackjack_bet(level: usize) -> i64 {
+    BLACKJACK_BETS[level.min(BLACKJACK_BETS.len() - 1)]
+}
+
+/// Whether a blackjack tournament has played through its last bet level.
+pub fn blackjack_time_up(level: usize) -> bool {
+    level >= BLACKJACK_BETS.len()
+}
+
+pub fn seats_per_table(game: TournamentGame) -> usize {
+    match game {
+        TournamentGame::Holdem => 6,
+        TournamentGame::Blackjack => 5,
+    }
+}
+
+/// Splits entrants over as few tables as possible, dealing them out in turn so the tables
+/// differ by at most one player.
+pub fn assign_tables(players: &[UserId], max_seats: usize) -> Vec<Vec<UserId>> {
+    let count = players.len().div_ceil(max_seats).max(1);
+    let mut tables = vec![Vec::new(); count];
+    for (i, player) in players.iter().enumerate() {
+        tables[i % count].push(*player);
+    }
+    tables
+}
+
+/// Balances the tournament after table `from` finishes a hand, returning each moved player and
+/// their new table. Other tables may be mid-hand, so players are only taken from `from` or from
+/// a table left with a lone player: a lone player is moved whenever the field fits on fewer
+/// tables, `from` is broken up under the same condition when it is the shortest, and otherwise
+/// it gives players to the shortest table until it is at most one player larger.
+pub fn rebalance(
+    tables: &mut [Vec<UserId>],
+    from: usize,
+    max_seats: usize,
+) -> Vec<(UserId, usize)> {
+    let total: usize = tables.iter().map(Vec::len).sum();
+    let needed = total.div_ceil(max_seats).max(1);
+    let mut moves = Vec::new();
+    let active_count = |tables: &[Vec<UserId>]| tables.iter().filter(|t| !t.is_empty()).count();
+    while active_count(tables) > needed {
+        let Some(lone) = (0..tables.len()).find(|&i| i != from && tables[i].len() == 1) else {
+            break;
+        };
+        let Some(to) = (0..tables.len())
+            .filter(|&i| i != lone && !tables[i].is_empty() && tables[i].len() < max_seats)
+            .min_by_key(|&i| (tables[i].len(), i))
+        else {
+            break;
+        };
+        let player = tables[lone].remove(0);
+        tables[to].push(player);
+        moves.push((player, to));
+    }
+
+    let active: Vec<usize> = (0..tables.len())
+        .filter(|&i| !tables[i].is_empty())
+        .collect();
+    let shortest_other = |tables: &[Vec<UserId>]| {
+        active
+            .iter()
+            .copied()
+            .filter(|&i| i != from)
+            .min_by_key(|&i| (tables[i].len(), i))
+    };
+    let smallest = active.iter().map(|&i| tables[i].len()).min().unwrap_or(0);
+    if active.len() > needed && tables[from].len() == smallest {
+        while let Some(player) = tables[from].pop() {
+            let Some(to) = shortest_other(tables) else {
+                tables[from].push(player);
+                break;
+            };
+            tables[to].push(player);
+            moves.push((player, to));
+        }
+        return moves;
+    }
+    while let Some(to) = shortest_other(tables) {
+        if tables[from].len() <= tables[to].len() + 1 {
+            break;
+        }
+        let Some(player) = tables[from].pop() else {
+            break;
+        };
+        tables[to].push(player);
+        moves.push((player, to));
+    }
+    moves
+}
+
+/// Percentage of the prize pool for each paid place, by field size.
+pub fn prize_shares(entrants: usize) -> &'static [i64] {
+    match entrants {
+        0..=3 => &[100],
+        4..=6 => &[65, 35],
+        _ => &[50, 30, 20],
+    }
+}
+
+/// Prizes for the final standings (winner first). Rounding leftovers go to the winner.
+pub fn prize_payouts(prize_pool: i64, standings: &[UserId]) -> Vec<GamePayout> {
+    let shares = prize_shares(standings.len());
+    let mut payouts: Vec<GamePayout> = standings
+        .iter()
+        .zip(shares)
+        .map(|(&user_id, pct)| GamePayout {
+            user_id,
+            amount: prize_pool * pct / 100,
+        })
+        .collect();
+    let paid: i64 = payouts.iter().map(|p| p.amount).sum();
+    if let Some(first) = payouts.first_mut() {
+        first.amount += prize_pool - paid;
+    }
+    payouts
+}
+
+/// "1st", "2nd", "3rd", "4th", ...
+pub fn ordinal(place: usize) -> String {
+    let suffix = match (place % 10, place % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", place, suffix)
+}
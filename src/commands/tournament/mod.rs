@@ -0,0 +1,12 @@
+//! Poker and blackjack tournaments.
+//!
+//! Registration is a plain message handled by `interactions::tournament_handler`; once the
+//! tournament starts, every table is its own game in the `GameManager` and the tables share the
+//! tournament state in `state`.
+
+pub mod blackjack;
+pub mod logic;
+pub mod run;
+pub mod state;
+pub mod table;
+pub mod ui;
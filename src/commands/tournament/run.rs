@@ -0,0 +1,319 @@
+//! Handles `/tournament` and `$tournament`: opening registration, starting the tables and the
+//! tournament history.
+
+use super::logic::{self, REGISTRATION_WINDOW};
+use super::state::Tournament;
+use super::table::TournamentTable;
+use super::ui::{history_embed, lobby_view};
+use crate::AppState;
+use crate::commands::games::Game;
+use crate::database::models::TournamentGame;
+use crate::database::tournaments::{self, TournamentRecord};
+use rand::seq::SliceRandom;
+use serenity::builder::{
+    CreateCommand, CreateCommandOption, CreateEmbed, CreateMessage, EditInteractionResponse,
+    EditMessage,
+};
+use serenity::model::application::{CommandDataOptionValue, CommandInteraction, CommandOptionType};
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, MessageId, UserId};
+use serenity::prelude::*;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Finished tournaments shown by `/tournament history`.
+const HISTORY_ENTRIES: i64 = 5;
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("tournament")
+        .description("Run poker and blackjack tournaments with buy-ins and prize pools.")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "create",
+                "Open registration for a new tournament",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "game", "The game to play")
+                    .required(true)
+                    .add_string_choice("Texas Hold'em", "holdem")
+                    .add_string_choice("Blackjack", "blackjack"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "buy_in",
+                    "Coins each player pays into the prize pool",
+                )
+                .required(true)
+                .min_int_value(1),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "history",
+            "Show recently finished tournaments",
+        ))
+}
+
+/// A parsed `/tournament` or `$tournament` request.
+enum TournamentAction {
+    Create { game: TournamentGame, buy_in: i64 },
+    History,
+}
+
+fn parse_game(raw: &str) -> Result<TournamentGame, String> {
+    match raw.to_lowercase().as_str() {
+        "holdem" | "poker" | "th" => Ok(TournamentGame::Holdem),
+        "blackjack" | "bj" => Ok(TournamentGame::Blackjack),
+        _ => Err("Choose `holdem` or `blackjack`.".to_string()),
+    }
+}
+
+/// `$tournament create <holdem|blackjack> <buy_in> | history`
+fn parse_action(
+    sub: &str,
+    game: Option<&str>,
+    buy_in: Option<&str>,
+) -> Result<TournamentAction, String> {
+    const USAGE: &str =
+        "Usage: `$tournament create <holdem|blackjack> <buy_in>`, `$tournament history`";
+    match sub {
+        "history" => Ok(TournamentAction::History),
+        "create" => {
+            let game = parse_game(game.ok_or(USAGE)?)?;
+            let buy_in = match buy_in.and_then(|b| b.parse::<i64>().ok()) {
+                Some(b) if b > 0 => b,
+                _ => return Err("The buy-in must be a positive number of coins.".to_string()),
+            };
+            Ok(TournamentAction::Create { game, buy_in })
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn parse_slash(interaction: &CommandInteraction) -> Result<TournamentAction, String> {
+    let Some(sub) = interaction.data.options.first() else {
+        return Ok(TournamentAction::History);
+    };
+    let CommandDataOptionValue::SubCommand(nested) = &sub.value else {
+        return Err("Invalid tournament command.".to_string());
+    };
+    let game = nested
+        .iter()
+        .find(|o| o.name == "game")
+        .and_then(|o| o.value.as_str());
+    let buy_in = nested
+        .iter()
+        .find(|o| o.name == "buy_in")
+        .and_then(|o| o.value.as_i64())
+        .map(|b| b.to_string());
+    parse_action(sub.name.as_str(), game, buy_in.as_deref())
+}
+
+/// Unix time registration for `record` closes.
+pub fn registration_closes_at(record: &TournamentRecord) -> i64 {
+    record.created_at.timestamp() + REGISTRATION_WINDOW.as_secs() as i64
+}
+
+/// Opens a tournament and returns its registration view.
+async fn open_registration(
+    app_state: &AppState,
+    host: UserId,
+    channel_id: ChannelId,
+    game: TournamentGame,
+    buy_in: i64,
+) -> Result<(i32, CreateEmbed, Vec<serenity::builder::CreateActionRow>), String> {
+    let db = &app_state.db;
+    let tournament_id = tournaments::create_tournament(db, host, channel_id, game, buy_in)
+        .await
+        .map_err(|_| "Failed to create the tournament.".to_string())?;
+    // The host is registered straight away; if they can't cover the buy-in, nothing is opened.
+    let record = match tournaments::register(db, tournament_id, host).await {
+        Ok(record) => record,
+        Err(e) => {
+            tournaments::cancel(db, tournament_id).await.ok();
+            return Err(e);
+        }
+    };
+    let (embed, components) = lobby_view(&record, registration_closes_at(&record));
+    Ok((tournament_id, embed, components))
+}
+
+/// Closes registration, seats everyone and posts one message per table. Returns a status line
+/// for the registration message.
+pub async fn start_tournament(
+    ctx: &Context,
+    app_state: &AppState,
+    tournament_id: i32,
+) -> Result<String, String> {
+    let record = tournaments::start(&app_state.db, tournament_id).await?;
+    let mut entrants = record.entrants.clone();
+    entrants.shuffle(&mut rand::rng());
+    let tables = logic::assign_tables(&entrants, logic::seats_per_table(record.game));
+    let table_count = tables.len();
+    let shared = Arc::new(Mutex::new(Tournament::new(
+        record.tournament_id,
+        record.game,
+        record.prize_pool,
+        tables,
+    )));
+    let channel_id = ChannelId::new(record.channel_id as u64);
+    for table_no in 0..table_count {
+        let table = TournamentTable::new(shared.clone(), table_no);
+        let (content, embed, components) = table.render();
+        let builder = CreateMessage::new()
+            .content(content)
+            .embed(embed)
+            .components(components);
+        match channel_id.send_message(&ctx.http, builder).await {
+            Ok(msg) => {
+                app_state
+                    .game_manager
+                    .start_scheduled_game(channel_id, msg.id, Box::new(table))
+            }
+            Err(e) => warn!(tournament_id, table_no, error = ?e, "failed to post tournament table"),
+        }
+    }
+    Ok(format!(
+        "🏁 Tournament #{} has started with {} players at {} table(s)!",
+        tournament_id,
+        record.entrants.len(),
+        table_count
+    ))
+}
+
+/// Starts the tournament when registration closes, or cancels it if too few players joined.
+fn spawn_registration_timer(
+    ctx: Context,
+    app_state: Arc<AppState>,
+    tournament_id: i32,
+    channel_id: ChannelId,
+    lobby_id: MessageId,
+) {
+    tokio::spawn(async move {
+        tokio::time::sleep(REGISTRATION_WINDOW).await;
+        let db = &app_state.db;
+        let Ok(Some(record)) = tournaments::get_tournament(db, tournament_id).await else {
+            return;
+        };
+        if record.status != crate::database::models::TournamentStatus::Registering {
+            return;
+        }
+        let note = if record.entrants.len() >= logic::MIN_ENTRANTS {
+            start_tournament(&ctx, &app_state, tournament_id)
+                .await
+                .unwrap_or_else(|e| e)
+        } else {
+            match tournaments::cancel(db, tournament_id).await {
+                Ok(_) => "Not enough players registered; buy-ins were refunded.".to_string(),
+                Err(e) => e,
+            }
+        };
+        if let Ok(Some(record)) = tournaments::get_tournament(db, tournament_id).await {
+            let (embed, components) = lobby_view(&record, registration_closes_at(&record));
+            let builder = EditMessage::new()
+                .content(note)
+                .embed(embed)
+                .components(components);
+            channel_id
+                .edit_message(&ctx.http, lobby_id, builder)
+                .await
+                .ok();
+        }
+    });
+}
+
+pub async fn run_slash(ctx: &Context, interaction: &CommandInteraction) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        warn!(command = "tournament_slash", "missing_app_state");
+        return;
+    };
+    interaction.defer(&ctx.http).await.ok();
+    let builder = match parse_slash(interaction) {
+        Ok(TournamentAction::History) => {
+            match tournaments::recent_results(&app_state.db, HISTORY_ENTRIES).await {
+                Ok(results) => EditInteractionResponse::new().embed(history_embed(&results)),
+                Err(_) => {
+                    EditInteractionResponse::new().content("Could not load tournament history.")
+                }
+            }
+        }
+        Ok(TournamentAction::Create { game, buy_in }) => {
+            match open_registration(
+                &app_state,
+                interaction.user.id,
+                interaction.channel_id,
+                game,
+                buy_in,
+            )
+            .await
+            {
+                Ok((tournament_id, embed, components)) => {
+                    let builder = EditInteractionResponse::new()
+                        .embed(embed)
+                        .components(components);
+                    if let Ok(msg) = interaction.edit_response(&ctx.http, builder).await {
+                        spawn_registration_timer(
+                            ctx.clone(),
+                            app_state.clone(),
+                            tournament_id,
+                            msg.channel_id,
+                            msg.id,
+                        );
+                    }
+                    return;
+                }
+                Err(e) => EditInteractionResponse::new().content(e),
+            }
+        }
+        Err(e) => EditInteractionResponse::new().content(e),
+    };
+    interaction.edit_response(&ctx.http, builder).await.ok();
+}
+
+pub async fn run_prefix(ctx: &Context, msg: &Message, args: Vec<&str>) {
+    let Some(app_state) = AppState::from_ctx(ctx).await else {
+        warn!(command = "tournament_prefix", "missing_app_state");
+        return;
+    };
+    let sub = args.first().map(|s| s.to_lowercase());
+    let builder = match parse_action(
+        sub.as_deref().unwrap_or("history"),
+        args.get(1).copied(),
+        args.get(2).copied(),
+    ) {
+        Ok(TournamentAction::History) => {
+            match tournaments::recent_results(&app_state.db, HISTORY_ENTRIES).await {
+                Ok(results) => CreateMessage::new().embed(history_embed(&results)),
+                Err(_) => CreateMessage::new().content("Could not load tournament history."),
+            }
+        }
+        Ok(TournamentAction::Create { game, buy_in }) => {
+            match open_registration(&app_state, msg.author.id, msg.channel_id, game, buy_in).await {
+                Ok((tournament_id, embed, components)) => {
+                    let builder = CreateMessage::new()
+                        .embed(embed)
+                        .components(components)
+                        .reference_message(msg);
+                    if let Ok(lobby) = msg.channel_id.send_message(&ctx.http, builder).await {
+                        spawn_registration_timer(
+                            ctx.clone(),
+                            app_state.clone(),
+                            tournament_id,
+                            lobby.channel_id,
+                            lobby.id,
+                        );
+                    }
+                    return;
+                }
+                Err(e) => CreateMessage::new().content(e),
+            }
+        }
+        Err(e) => CreateMessage::new().content(e),
+    };
+    msg.channel_id
+        .send_message(&ctx.http, builder.reference_message(msg))
+        .await
+        .ok();
+}
@@ -0,0 +1,153 @@
+//! The live state of a running tournament, shared by all of its tables.
+//!
+//! Each table is its own game in the `GameManager`, so tables hold the tournament behind an
+//! `Arc<Mutex<_>>` and report to it between hands: chip counts, eliminations and the resulting
+//! table moves all go through here.
+
+use super::logic::{self, STARTING_CHIPS};
+use crate::database::models::TournamentGame;
+use serenity::model::id::UserId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+pub type SharedTournament = Arc<Mutex<Tournament>>;
+
+pub struct Tournament {
+    pub tournament_id: i32,
+    pub game: TournamentGame,
+    pub prize_pool: i64,
+    pub entrants: usize,
+    pub started_at: Instant,
+    /// Unix time the tournament started, for level countdowns.
+    pub started_unix: i64,
+    /// Who sits at each table; a closed table is left empty so table numbers stay stable.
+    pub tables: Vec<Vec<UserId>>,
+    pub chips: HashMap<UserId, i64>,
+    /// Busted players, first out first.
+    pub eliminated: Vec<UserId>,
+    /// Set once the final standings have been taken, so only one table pays out.
+    pub finished: bool,
+}
+
+/// What changed after a table reported a hand.
+#[derive(Debug, Default)]
+pub struct HandReport {
+    /// Busted players with their finishing place.
+    pub eliminated: Vec<(UserId, usize)>,
+    /// Players moved to another table, with its index.
+    pub moves: Vec<(UserId, usize)>,
+}
+
+impl Tournament {
+    pub fn new(
+        tournament_id: i32,
+        game: TournamentGame,
+        prize_pool: i64,
+        tables: Vec<Vec<UserId>>,
+    ) -> Self {
+        let chips: HashMap<UserId, i64> = tables
+            .iter()
+            .flatten()
+            .map(|&u| (u, STARTING_CHIPS))
+            .collect();
+        Self {
+            tournament_id,
+            game,
+            prize_pool,
+            entrants: chips.len(),
+            started_at: Instant::now(),
+            started_unix: chrono::Utc::now().timestamp(),
+            tables,
+            chips,
+            eliminated: Vec::new(),
+            finished: false,
+        }
+    }
+
+    pub fn level(&self) -> usize {
+        logic::level_for(self.started_at.elapsed())
+    }
+
+    /// Unix time the current level ends.
+    pub fn level_ends_at(&self) -> i64 {
+        self.started_unix + (self.level() as i64 + 1) * logic::LEVEL_DURATION.as_secs() as i64
+    }
+
+    pub fn chips_of(&self, user_id: UserId) -> i64 {
+        self.chips.get(&user_id).copied().unwrap_or(0)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.chips.values().filter(|&&c| c > 0).count()
+    }
+
+    /// The players seated at `table` with their chip counts.
+    pub fn roster(&self, table: usize) -> Vec<(UserId, i64)> {
+        self.tables
+            .get(table)
+            .map(|seats| seats.iter().map(|&u| (u, self.chips_of(u))).collect())
+            .unwrap_or_default()
+    }
+
+    /// Records the chip counts after a hand at `table`, eliminates busted players and
+    /// rebalances the tables.
+    pub fn report_hand(&mut self, table: usize, results: &[(UserId, i64)]) -> HandReport {
+        let mut busted: Vec<(UserId, i64)> = Vec::new();
+        for &(user_id, chips) in results {
+            let before = self.chips_of(user_id);
+            self.chips.insert(user_id, chips.max(0));
+            if chips <= 0 && before > 0 {
+                busted.push((user_id, before));
+            }
+        }
+        // Players busted in the same hand finish in order of the stacks they started it with.
+        busted.sort_by_key(|&(_, before)| before);
+        let mut report = HandReport::default();
+        for (user_id, _) in busted {
+            self.eliminated.push(user_id);
+            report
+                .eliminated
+                .push((user_id, self.entrants - self.eliminated.len() + 1));
+            for seats in &mut self.tables {
+                seats.retain(|&u| u != user_id);
+            }
+        }
+        if !self.is_over() {
+            let max_seats = logic::seats_per_table(self.game);
+            report.moves = logic::rebalance(&mut self.tables, table, max_seats);
+        }
+        report
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining() <= 1
+            || (self.game == TournamentGame::Blackjack && logic::blackjack_time_up(self.level()))
+    }
+
+    /// Final standings, winner first: players still in by chip count, then busted players
+    /// in reverse order of elimination.
+    pub fn standings(&self) -> Vec<UserId> {
+        let mut alive: Vec<(UserId, i64)> = self
+            .chips
+            .iter()
+            .filter(|&(_, &c)| c > 0)
+            .map(|(&u, &c)| (u, c))
+            .collect();
+        alive.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        alive
+            .into_iter()
+            .map(|(u, _)| u)
+            .chain(self.eliminated.iter().rev().copied())
+            .collect()
+    }
+
+    /// Takes the final standings the first time the tournament is found to be over.
+    pub fn take_standings(&mut self) -> Option<Vec<UserId>> {
+        if self.finished || !self.is_over() {
+            return None;
+        }
+        self.finished = true;
+        Some(self.standings())
+    }
+}
@@ -0,0 +1,380 @@
+//! One tournament table. It implements the `Game` trait so its buttons are routed by the
+//! `GameManager`, deals hand after hand at the current level, and reports every finished hand
+//! to the shared tournament state.
+
+use super::blackjack::{BlackjackMove, BlackjackRound};
+use super::logic::{self, ordinal};
+use super::state::SharedTournament;
+use crate::commands::games::deck::Deck;
+use crate::commands::games::lifecycle::SessionClock;
+use crate::commands::games::{Game, GamePayout, GameUpdate};
+use crate::commands::holdem::table::{Street, Table};
+use crate::commands::holdem::ui::{action_for, hole_cards_text};
+use crate::database::models::TournamentGame;
+use crate::database::tournaments;
+use serenity::async_trait;
+use serenity::builder::{
+    CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{MessageId, UserId};
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use std::time::{Duration, Instant};
+use tracing::error;
+
+/// How long the player to act has before they are checked, folded or stood automatically.
+const TURN_TIMEOUT: Duration = Duration::from_secs(60);
+/// Table event lines kept for the view.
+const NOTE_LINES: usize = 6;
+
+pub enum TableHand {
+    Holdem(Table),
+    Blackjack(BlackjackRound),
+}
+
+impl TableHand {
+    fn is_finished(&self) -> bool {
+        match self {
+            TableHand::Holdem(table) => table.is_finished(),
+            TableHand::Blackjack(round) => round.is_finished(),
+        }
+    }
+}
+
+pub struct TournamentTable {
+    pub tournament: SharedTournament,
+    pub table_no: usize,
+    pub hand: Option<TableHand>,
+    pub hands_played: u32,
+    /// Results, eliminations and table moves from recent hands.
+    pub notes: Vec<String>,
+    /// Why the table closed, once it has.
+    pub closed: Option<String>,
+    pub last_action_time: Instant,
+    /// Each player's chip result in the first Hold'em hand finished since spectators' side bets
+    /// were last settled.
+    pub round_outcome: Option<Vec<GamePayout>>,
+}
+
+async fn send_ephemeral_response(ctx: &Context, interaction: &ComponentInteraction, content: &str) {
+    let builder = CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true);
+    let response = CreateInteractionResponse::Message(builder);
+    interaction.create_response(&ctx.http, response).await.ok();
+}
+
+#[async_trait]
+impl Game for TournamentTable {
+    async fn handle_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: &mut ComponentInteraction,
+        db: &PgPool,
+    ) -> GameUpdate {
+        if self.closed.is_some() {
+            return GameUpdate::NoOp;
+        }
+        // Another table may have finished the tournament since this one last moved.
+        if self.tournament.lock().map(|t| t.finished).unwrap_or(true) {
+            interaction.defer(&ctx.http).await.ok();
+            return self.close("The tournament is over.");
+        }
+        if self.hand.is_some() && self.last_action_time.elapsed() > TURN_TIMEOUT {
+            self.apply_timeout();
+        }
+
+        let user_id = interaction.user.id;
+        let custom_id = interaction.data.custom_id.clone();
+        let Some(action) = custom_id.strip_prefix("tourney_") else {
+            return GameUpdate::NoOp;
+        };
+        let result = match (action, &mut self.hand) {
+            ("cards", Some(TableHand::Holdem(table))) => {
+                let content = hole_cards_text(table, user_id);
+                send_ephemeral_response(ctx, interaction, &content).await;
+                return GameUpdate::NoOp;
+            }
+            ("deal", None) => {
+                if let Some(closed) = self.deal_or_close() {
+                    interaction.defer(&ctx.http).await.ok();
+                    return closed;
+                }
+                if self.hand.is_none() {
+                    Err("Waiting for more players to be moved to this table.".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            ("hit", Some(TableHand::Blackjack(round))) => round.apply(user_id, BlackjackMove::Hit),
+            ("stand", Some(TableHand::Blackjack(round))) => {
+                round.apply(user_id, BlackjackMove::Stand)
+            }
+            ("double", Some(TableHand::Blackjack(round))) => {
+                round.apply(user_id, BlackjackMove::Double)
+            }
+            (action, Some(TableHand::Holdem(table))) => match action_for(action) {
+                Some(action) => table.apply(user_id, action),
+                None => return GameUpdate::NoOp,
+            },
+            _ => return GameUpdate::NoOp,
+        };
+        if let Err(e) = result {
+            send_ephemeral_response(ctx, interaction, &e).await;
+            return GameUpdate::NoOp;
+        }
+        self.last_action_time = Instant::now();
+        interaction.defer(&ctx.http).await.ok();
+        self.settle_finished_hands(db).await
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        self.render_table()
+    }
+
+    /// Spectators can back anyone in a Hold'em hand until the flop is dealt.
+    fn side_bet_players(&self) -> Vec<UserId> {
+        match &self.hand {
+            Some(TableHand::Holdem(table)) if table.street == Street::PreFlop => {
+                table.seats.iter().map(|s| s.user_id).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn take_round_outcome(&mut self) -> Option<Vec<GamePayout>> {
+        self.round_outcome.take()
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.tournament
+            .lock()
+            .map(|t| {
+                t.roster(self.table_no)
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The player to act has `TURN_TIMEOUT` before they are checked, folded or stood. A table
+    /// waiting for players looks for anyone moved to it as often, which also keeps the idle
+    /// sweep from closing it while the tournament runs.
+    fn deadline(&self, _clock: &SessionClock) -> Option<Instant> {
+        match self.closed {
+            None => Some(self.last_action_time + TURN_TIMEOUT),
+            Some(_) => None,
+        }
+    }
+
+    async fn on_timeout(&mut self, db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        if self.tournament.lock().map(|t| t.finished).unwrap_or(true) {
+            return self.close("The tournament is over.");
+        }
+        if self.hand.is_none() {
+            self.last_action_time = Instant::now();
+            if let Some(closed) = self.deal_or_close() {
+                return closed;
+            }
+            if self.hand.is_none() {
+                return GameUpdate::NoOp;
+            }
+        } else {
+            self.apply_timeout();
+        }
+        self.settle_finished_hands(db).await
+    }
+}
+
+impl TournamentTable {
+    /// Creates the table and deals its first hand.
+    pub fn new(tournament: SharedTournament, table_no: usize) -> Self {
+        let mut table = Self {
+            tournament,
+            table_no,
+            hand: None,
+            hands_played: 0,
+            notes: Vec::new(),
+            closed: None,
+            last_action_time: Instant::now(),
+            round_outcome: None,
+        };
+        table.start_hand();
+        table
+    }
+
+    /// Deals a new hand at the current level to everyone seated here, if at least two are.
+    pub(super) fn start_hand(&mut self) {
+        let Ok(tournament) = self.tournament.lock() else {
+            return;
+        };
+        let mut roster: Vec<_> = tournament
+            .roster(self.table_no)
+            .into_iter()
+            .filter(|&(_, chips)| chips > 0)
+            .collect();
+        if roster.len() < 2 {
+            self.hand = None;
+            return;
+        }
+        // Rotate the seats each hand so the button and the blinds move around the table.
+        let shift = self.hands_played as usize % roster.len();
+        roster.rotate_left(shift);
+        let level = tournament.level();
+        let mut deck = Deck::new();
+        deck.shuffle();
+        self.hand = Some(match tournament.game {
+            TournamentGame::Holdem => {
+                TableHand::Holdem(Table::new(roster, logic::big_blind(level), deck))
+            }
+            TournamentGame::Blackjack => TableHand::Blackjack(BlackjackRound::new(
+                &roster,
+                logic::blackjack_bet(level),
+                deck,
+            )),
+        });
+        self.hands_played += 1;
+        self.last_action_time = Instant::now();
+    }
+
+    fn apply_timeout(&mut self) {
+        match &mut self.hand {
+            Some(TableHand::Holdem(table)) => {
+                if let Some(user_id) = table.current_seat().map(|s| s.user_id) {
+                    let action = table.timeout_action();
+                    table.apply(user_id, action).ok();
+                }
+            }
+            Some(TableHand::Blackjack(round)) => {
+                if let Some(user_id) = round.current_seat().map(|s| s.user_id) {
+                    round.apply(user_id, BlackjackMove::Stand).ok();
+                }
+            }
+            None => {}
+        }
+        self.last_action_time = Instant::now();
+    }
+
+    /// Deals the next hand if enough players are seated, and closes the table once all of
+    /// them have been moved away.
+    fn deal_or_close(&mut self) -> Option<GameUpdate> {
+        self.start_hand();
+        let seated = self
+            .tournament
+            .lock()
+            .map(|t| t.roster(self.table_no).len())
+            .unwrap_or(0);
+        (seated == 0).then(|| self.close("This table has closed; its players were moved."))
+    }
+
+    fn push_note(&mut self, line: String) {
+        self.notes.push(line);
+        if self.notes.len() > NOTE_LINES {
+            self.notes.remove(0);
+        }
+    }
+
+    fn close(&mut self, reason: &str) -> GameUpdate {
+        self.closed = Some(reason.to_string());
+        self.hand = None;
+        GameUpdate::GameOver {
+            message: reason.to_string(),
+            payouts: vec![],
+        }
+    }
+
+    /// Reports finished hands to the tournament and deals the next one, until a hand needs a
+    /// player's input, the table runs short of players, or the tournament ends.
+    async fn settle_finished_hands(&mut self, db: &PgPool) -> GameUpdate {
+        loop {
+            let Some(hand) = self.hand.take_if(|h| h.is_finished()) else {
+                return GameUpdate::ReRender;
+            };
+            let results = match &hand {
+                TableHand::Holdem(table) => {
+                    let (pots, net) = table.settle();
+                    for pot in pots {
+                        let winners = pot
+                            .winners
+                            .iter()
+                            .map(|u| format!("<@{}>", u))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.push_note(format!("🏅 {} wins {} chips.", winners, pot.amount));
+                    }
+                    if self.round_outcome.is_none() {
+                        self.round_outcome = Some(
+                            net.iter()
+                                .map(|&(user_id, amount)| GamePayout { user_id, amount })
+                                .collect(),
+                        );
+                    }
+                    table
+                        .seats
+                        .iter()
+                        .zip(net)
+                        .map(|(seat, (user_id, net))| {
+                            (user_id, seat.stack + seat.contributed + net)
+                        })
+                        .collect::<Vec<_>>()
+                }
+                TableHand::Blackjack(round) => round.settle(),
+            };
+            let (report, standings, prize_pool, tournament_id) = {
+                let Ok(mut tournament) = self.tournament.lock() else {
+                    return GameUpdate::NoOp;
+                };
+                let report = tournament.report_hand(self.table_no, &results);
+                let standings = tournament.take_standings();
+                (
+                    report,
+                    standings,
+                    tournament.prize_pool,
+                    tournament.tournament_id,
+                )
+            };
+            for (user_id, place) in report.eliminated {
+                self.push_note(format!(
+                    "💀 <@{}> is out in {} place.",
+                    user_id,
+                    ordinal(place)
+                ));
+            }
+            for (user_id, to) in report.moves {
+                self.push_note(format!("➡️ <@{}> moves to Table {}.", user_id, to + 1));
+            }
+
+            if let Some(standings) = standings {
+                // Prizes are paid with the standings in one transaction, so the `GameOver`
+                // below carries no payouts of its own.
+                let payouts = logic::prize_payouts(prize_pool, &standings);
+                if let Err(e) = tournaments::finish(db, tournament_id, &standings, &payouts).await {
+                    // The tournament is still running in the database, so the restart refunds
+                    // every buy-in instead.
+                    error!(tournament_id, error = ?e, "failed to pay out tournament");
+                    return self.close(
+                        "🏆 **Tournament over!**\n⚠️ The prizes could not be paid out. The tournament will be cancelled and every buy-in refunded when the bot restarts.",
+                    );
+                }
+                let podium = payouts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| {
+                        format!("{}: <@{}> wins 💰{}", ordinal(i + 1), p.user_id, p.amount)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return self.close(&format!("🏆 **Tournament over!**\n{}", podium));
+            }
+
+            if let Some(closed) = self.deal_or_close() {
+                return closed;
+            }
+            if self.hand.is_none() {
+                return GameUpdate::ReRender;
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Embeds and buttons for tournament registration, tables and history.
+
+use super::logic::{self, LEVEL_DURATION, STARTING_CHIPS, ordinal};
+use super::table::{TableHand, TournamentTable};
+use crate::commands::blackjack::state::HandStatus;
+use crate::commands::holdem::ui::{action_rows, cards_text};
+use crate::database::models::{TournamentGame, TournamentStatus};
+use crate::database::tournaments::{TournamentRecord, TournamentResult};
+use crate::ui::buttons::Btn;
+use crate::ui::style::{COLOR_SAGA_MAP, COLOR_SAGA_TAVERN};
+use serenity::builder::{CreateActionRow, CreateEmbed, CreateEmbedFooter};
+
+pub fn game_label(game: TournamentGame) -> &'static str {
+    match game {
+        TournamentGame::Holdem => "Texas Hold'em",
+        TournamentGame::Blackjack => "Blackjack",
+    }
+}
+
+/// The registration message. `closes_at` is the unix time registration ends.
+pub fn lobby_view(
+    record: &TournamentRecord,
+    closes_at: i64,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let entrants = if record.entrants.is_empty() {
+        "No one yet.".to_string()
+    } else {
+        record
+            .entrants
+            .iter()
+            .map(|u| format!("<@{}>", u))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let shares = logic::prize_shares(record.entrants.len())
+        .iter()
+        .enumerate()
+        .map(|(i, pct)| format!("{} {}%", ordinal(i + 1), pct))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let levels = match record.game {
+        TournamentGame::Holdem => format!(
+            "Blinds start at {}/{} and rise every {} minutes until one player has every chip.",
+            logic::big_blind(0) / 2,
+            logic::big_blind(0),
+            LEVEL_DURATION.as_secs() / 60
+        ),
+        TournamentGame::Blackjack => format!(
+            "Bets start at {} and rise every {} minutes; the biggest stacks after the last level win.",
+            logic::blackjack_bet(0),
+            LEVEL_DURATION.as_secs() / 60
+        ),
+    };
+    let mut embed = CreateEmbed::new()
+        .title(format!(
+            "🏆 {} Tournament #{}",
+            game_label(record.game),
+            record.tournament_id
+        ))
+        .description(format!(
+            "<@{}> is hosting a tournament. Buy in for **💰{}** to get {} chips.\n{}",
+            record.host_id, record.buy_in, STARTING_CHIPS, levels
+        ))
+        .field("Prize Pool", format!("💰{}", record.prize_pool), true)
+        .field("Payouts", shares, true)
+        .field(
+            format!("Entrants ({})", record.entrants.len()),
+            entrants,
+            false,
+        )
+        .color(COLOR_SAGA_TAVERN);
+    let mut components = Vec::new();
+    match record.status {
+        TournamentStatus::Registering => {
+            embed = embed.field("Registration Closes", format!("<t:{}:R>", closes_at), true);
+            let id = record.tournament_id;
+            components.push(CreateActionRow::Buttons(vec![
+                Btn::success(
+                    &format!("tournament_join_{}", id),
+                    &format!("Register (💰{})", record.buy_in),
+                ),
+                Btn::primary(&format!("tournament_start_{}", id), "Start Now (Host)"),
+                Btn::danger(&format!("tournament_cancel_{}", id), "Cancel (Host)"),
+            ]));
+        }
+        TournamentStatus::Running => {
+            embed = embed.footer(CreateEmbedFooter::new(
+                "Registration closed. Good luck at the tables!",
+            ));
+        }
+        TournamentStatus::Finished | TournamentStatus::Cancelled => {
+            embed = embed.color(COLOR_SAGA_MAP);
+        }
+    }
+    (embed, components)
+}
+
+pub fn history_embed(results: &[TournamentResult]) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("🏆 Tournament History")
+        .color(COLOR_SAGA_MAP);
+    if results.is_empty() {
+        return embed.description("No tournaments have finished yet.");
+    }
+    for result in results {
+        let podium = result
+            .podium
+            .iter()
+            .map(|(user_id, place, prize)| {
+                format!("{} <@{}> 💰{}", ordinal(*place as usize), user_id, prize)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field(
+            format!(
+                "#{} {} · 💰{} buy-in",
+                result.tournament_id,
+                game_label(result.game),
+                result.buy_in
+            ),
+            format!(
+                "{} entrants · 💰{} pool · <t:{}:d>\n{}",
+                result.entrants,
+                result.prize_pool,
+                result.finished_at.timestamp(),
+                podium
+            ),
+            false,
+        );
+    }
+    embed
+}
+
+impl TournamentTable {
+    pub(super) fn render_table(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        let Ok(tournament) = self.tournament.lock() else {
+            return (String::new(), CreateEmbed::new(), vec![]);
+        };
+        let content = format!(
+            "🏆 Tournament #{} · Table {}",
+            tournament.tournament_id,
+            self.table_no + 1
+        );
+        let level = tournament.level();
+        let stakes = match tournament.game {
+            TournamentGame::Holdem => {
+                let bb = logic::big_blind(level);
+                format!("Blinds {}/{}", (bb / 2).max(1), bb)
+            }
+            TournamentGame::Blackjack => format!("Bet {}", logic::blackjack_bet(level)),
+        };
+        let mut embed = CreateEmbed::new()
+            .title(format!(
+                "🏆 {} Tournament · Table {}",
+                game_label(tournament.game),
+                self.table_no + 1
+            ))
+            .description(format!(
+                "Level {} · {} · next level <t:{}:R>\n{} of {} players remain.",
+                level + 1,
+                stakes,
+                tournament.level_ends_at(),
+                tournament.remaining(),
+                tournament.entrants
+            ))
+            .color(COLOR_SAGA_TAVERN);
+        let mut components = Vec::new();
+
+        match &self.hand {
+            Some(TableHand::Holdem(table)) => {
+                embed = embed
+                    .field("Board", format!("[ {} ]", cards_text(&table.board)), true)
+                    .field("Pot", table.pot_total().to_string(), true);
+                let seats = table
+                    .seats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, seat)| {
+                        let marker =
+                            if table.current_seat().map(|s| s.user_id) == Some(seat.user_id) {
+                                "▶️"
+                            } else {
+                                "▫️"
+                            };
+                        let dealer = if i == table.button { " (D)" } else { "" };
+                        let status = if seat.folded {
+                            "folded".to_string()
+                        } else if seat.is_all_in() {
+                            "all-in".to_string()
+                        } else {
+                            format!("{} chips", seat.stack)
+                        };
+                        format!(
+                            "{} <@{}>{} · {} · bet {}",
+                            marker, seat.user_id, dealer, status, seat.street_bet
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                embed = embed.field("Players", seats, false);
+                if !table.log.is_empty() {
+                    embed = embed.field("Action", table.log.join("\n"), false);
+                }
+                components.extend(action_rows(table, "tourney"));
+            }
+            Some(TableHand::Blackjack(round)) => {
+                let dealer = if round.is_finished() {
+                    round.dealer.display(0)
+                } else {
+                    format!(
+                        "[ {} ? ]",
+                        round
+                            .dealer
+                            .cards
+                            .first()
+                            .map(|c| c.to_string())
+                            .unwrap_or_default()
+                    )
+                };
+                embed = embed.field("🤵 Dealer", dealer, false);
+                let seats = round
+                    .seats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, seat)| {
+                        let marker = if i == round.to_act {
+                            "▶️"
+                        } else {
+                            "▫️"
+                        };
+                        let status = match seat.hand.status {
+                            HandStatus::Busted => " · busted",
+                            HandStatus::Blackjack => " · blackjack!",
+                            _ => "",
+                        };
+                        format!(
+                            "{} <@{}> · {} chips · bet {}\n{}{}",
+                            marker,
+                            seat.user_id,
+                            seat.chips,
+                            seat.hand.bet,
+                            seat.hand.display(0),
+                            status
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                embed = embed.field("Players", seats, false);
+                components.push(CreateActionRow::Buttons(vec![
+                    Btn::success("tourney_hit", "Hit"),
+                    Btn::secondary("tourney_stand", "Stand"),
+                    Btn::primary("tourney_double", "Double Down"),
+                ]));
+            }
+            None => {
+                let seated = tournament
+                    .roster(self.table_no)
+                    .iter()
+                    .map(|(u, chips)| format!("<@{}> · {} chips", u, chips))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if let Some(reason) = &self.closed {
+                    embed = embed.field("Table Closed", reason.clone(), false);
+                } else {
+                    embed = embed.field(
+                        "Waiting for Players",
+                        if seated.is_empty() {
+                            "No one is seated.".to_string()
+                        } else {
+                            seated
+                        },
+                        false,
+                    );
+                    components.push(CreateActionRow::Buttons(vec![Btn::primary(
+                        "tourney_deal",
+                        "Deal Next Hand",
+                    )]));
+                }
+            }
+        }
+        if !self.notes.is_empty() {
+            embed = embed.field("Table News", self.notes.join("\n"), false);
+        }
+        embed = embed.footer(CreateEmbedFooter::new(
+            "Players who don't act within 60 seconds check, fold or stand.",
+        ));
+        (content, embed, components)
+    }
+}
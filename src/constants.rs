@@ -3,6 +3,8 @@ pub const EQUIP_BONUS_CACHE_TTL_SECS: u64 = 5; // previously hard-coded in saga_
 pub const BOND_MAP_CACHE_TTL_SECS: u64 = 10; // cache lifetime for bonded mapping in party view
 pub const MAX_PARTY_SIZE: i64 = 5;
 pub const MAX_ARMY_SIZE: i64 = 10;
+pub const MAX_PARTY_PRESETS: i64 = 10; // fits comfortably in a single select menu
+pub const MAX_PRESET_NAME_LEN: usize = 32;
 // Feature flags / toggles (runtime constants). Flip to false during balancing sessions
 // to allow drafting human contracts without parchment consumption.
 pub const ENABLE_PARCHMENT_GATING: bool = true;
@@ -0,0 +1,573 @@
+//! Persistence for the bank (rules in `commands::economy::bank::logic`). Every change to a bank
+//! balance, and every wallet movement caused by the bank, is written to `bank_ledger` in the same
+//! transaction.
+
+use chrono::{Duration, Utc};
+use serenity::model::id::UserId;
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::instrument;
+
+use super::economy::{add_balance, get_or_create_profile};
+use super::models::{BankAccount, BankEntryKind, BankLedgerEntry, BankLoan, LedgerReason};
+use crate::commands::economy::bank::logic::{
+    BankRank, DEFAULT_CREDIT_BLOCK_DAYS, LoanTerms, MAX_MISSED_PAYMENTS, MIN_LOAN,
+    accrued_interest, installment_interval, missed_payment_penalty, split_payment,
+};
+
+/// Rank of an account today.
+pub fn account_rank(account: &BankAccount) -> BankRank {
+    BankRank::for_days((Utc::now() - account.rank_since).num_days())
+}
+
+/// Appends a ledger entry.
+async fn record(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    kind: BankEntryKind,
+    wallet_delta: i64,
+    bank_delta: i64,
+    bank_balance: i64,
+    loan_id: Option<i32>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO bank_ledger (user_id, kind, wallet_delta, bank_delta, bank_balance, loan_id) VALUES ($1, $2, $3, $4, $5, $6)",
+        user_id.get() as i64,
+        kind as BankEntryKind,
+        wallet_delta,
+        bank_delta,
+        bank_balance,
+        loan_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Opens the account if needed and locks it for the rest of the transaction.
+async fn lock_account(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+) -> Result<BankAccount, sqlx::Error> {
+    let uid = user_id.get() as i64;
+    sqlx::query!(
+        "INSERT INTO bank_accounts (user_id) VALUES ($1) ON CONFLICT (user_id) DO NOTHING",
+        uid
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query_as!(
+        BankAccount,
+        "SELECT balance, rank_since, interest_paid_on, credit_blocked_until FROM bank_accounts WHERE user_id = $1 FOR UPDATE",
+        uid
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+async fn set_bank_balance(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    balance: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE bank_accounts SET balance = $2 WHERE user_id = $1",
+        user_id.get() as i64,
+        balance
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+async fn wallet_balance(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT balance FROM profiles WHERE user_id = $1 FOR UPDATE",
+        user_id.get() as i64
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+async fn lock_active_loan(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+) -> Result<Option<BankLoan>, sqlx::Error> {
+    sqlx::query_as!(
+        BankLoan,
+        "SELECT loan_id, principal, total_due, repaid, installment, next_due_at, missed_payments FROM bank_loans WHERE user_id = $1 AND status = 'Active' FOR UPDATE",
+        user_id.get() as i64
+    )
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+/// Pays outstanding daily interest and collects every loan installment that has come due.
+/// Returns notices for the player (missed payments, defaults).
+async fn settle_locked(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    account: &mut BankAccount,
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut notices = Vec::new();
+    let today = Utc::now().date_naive();
+    let days = (today - account.interest_paid_on).num_days();
+    if days > 0 {
+        let interest = accrued_interest(account.balance, account_rank(account), days);
+        if interest > 0 {
+            account.balance += interest;
+            record(
+                tx,
+                user_id,
+                BankEntryKind::Interest,
+                0,
+                interest,
+                account.balance,
+                None,
+            )
+            .await?;
+        }
+        account.interest_paid_on = today;
+        sqlx::query!(
+            "UPDATE bank_accounts SET balance = $2, interest_paid_on = $3 WHERE user_id = $1",
+            user_id.get() as i64,
+            account.balance,
+            today
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let Some(mut loan) = lock_active_loan(tx, user_id).await? else {
+        return Ok(notices);
+    };
+    let now = Utc::now();
+    while loan.next_due_at <= now && loan.outstanding() > 0 {
+        let due = loan.installment.min(loan.outstanding());
+        let wallet = wallet_balance(tx, user_id).await?;
+        match split_payment(due, wallet, account.balance) {
+            Some((from_wallet, from_bank)) => {
+                add_balance(
+                    tx,
+                    user_id,
+                    -from_wallet,
+                    LedgerReason::Bank.with_ref("loan", loan.loan_id),
+                )
+                .await?;
+                account.balance -= from_bank;
+                set_bank_balance(tx, user_id, account.balance).await?;
+                loan.repaid += due;
+                record(
+                    tx,
+                    user_id,
+                    BankEntryKind::LoanPayment,
+                    -from_wallet,
+                    -from_bank,
+                    account.balance,
+                    Some(loan.loan_id),
+                )
+                .await?;
+            }
+            None => {
+                let penalty = missed_payment_penalty(loan.installment);
+                loan.total_due += penalty;
+                loan.missed_payments += 1;
+                record(
+                    tx,
+                    user_id,
+                    BankEntryKind::LoanPenalty,
+                    0,
+                    0,
+                    account.balance,
+                    Some(loan.loan_id),
+                )
+                .await?;
+                notices.push(format!(
+                    "⚠️ Missed a loan installment of {} coins; a {} coin penalty was added.",
+                    due, penalty
+                ));
+            }
+        }
+        loan.next_due_at += installment_interval();
+        if loan.missed_payments >= MAX_MISSED_PAYMENTS {
+            // Default: seize what the bank holds, write off the rest, reset the rank clock and
+            // block new credit.
+            let seized = loan.outstanding().min(account.balance);
+            account.balance -= seized;
+            loan.repaid += seized;
+            let blocked_until = now + Duration::days(DEFAULT_CREDIT_BLOCK_DAYS);
+            sqlx::query!(
+                "UPDATE bank_accounts SET balance = $2, rank_since = NOW(), credit_blocked_until = $3 WHERE user_id = $1",
+                user_id.get() as i64,
+                account.balance,
+                blocked_until
+            )
+            .execute(&mut **tx)
+            .await?;
+            account.rank_since = now;
+            account.credit_blocked_until = Some(blocked_until);
+            sqlx::query!(
+                "UPDATE bank_loans SET repaid = $2, total_due = $3, missed_payments = $4, status = 'Defaulted', closed_at = NOW() WHERE loan_id = $1",
+                loan.loan_id,
+                loan.repaid,
+                loan.total_due,
+                loan.missed_payments
+            )
+            .execute(&mut **tx)
+            .await?;
+            record(
+                tx,
+                user_id,
+                BankEntryKind::LoanDefault,
+                0,
+                -seized,
+                account.balance,
+                Some(loan.loan_id),
+            )
+            .await?;
+            notices.push(format!(
+                "🚫 Your loan defaulted. The bank seized {} coins, reset your account rank and will not lend to you for {} days.",
+                seized, DEFAULT_CREDIT_BLOCK_DAYS
+            ));
+            return Ok(notices);
+        }
+    }
+    let repaid_in_full = loan.outstanding() == 0;
+    sqlx::query!(
+        "UPDATE bank_loans SET repaid = $2, total_due = $3, missed_payments = $4, next_due_at = $5,
+            status = CASE WHEN $6 THEN 'Repaid'::loan_status ELSE status END,
+            closed_at = CASE WHEN $6 THEN NOW() ELSE closed_at END
+        WHERE loan_id = $1",
+        loan.loan_id,
+        loan.repaid,
+        loan.total_due,
+        loan.missed_payments,
+        loan.next_due_at,
+        repaid_in_full
+    )
+    .execute(&mut **tx)
+    .await?;
+    if repaid_in_full {
+        notices.push("✅ Your loan is fully repaid.".to_string());
+    }
+    Ok(notices)
+}
+
+/// Settles every account owed interest or holding a loan installment that is due. Used by the
+/// background bank worker so loans default even when their owner never opens `/bank`.
+pub async fn settle_due_accounts(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let due = sqlx::query_scalar!(
+        r#"SELECT user_id as "user_id!" FROM bank_accounts WHERE interest_paid_on < CURRENT_DATE
+        UNION
+        SELECT user_id as "user_id!" FROM bank_loans WHERE status = 'Active' AND next_due_at <= NOW()"#
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut settled = 0;
+    for uid in due {
+        let user_id = UserId::new(uid as u64);
+        let mut tx = pool.begin().await?;
+        let mut account = lock_account(&mut tx, user_id).await?;
+        settle_locked(&mut tx, user_id, &mut account).await?;
+        tx.commit().await?;
+        settled += 1;
+    }
+    Ok(settled)
+}
+
+/// The account, active loan and most recent ledger entries, after settling.
+pub struct BankStatement {
+    pub account: BankAccount,
+    pub wallet: i64,
+    pub loan: Option<BankLoan>,
+    pub recent: Vec<BankLedgerEntry>,
+    pub notices: Vec<String>,
+}
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_statement(
+    pool: &PgPool,
+    user_id: UserId,
+    recent: i64,
+) -> Result<BankStatement, sqlx::Error> {
+    get_or_create_profile(pool, user_id).await?;
+    let uid = user_id.get() as i64;
+    let mut tx = pool.begin().await?;
+    let mut account = lock_account(&mut tx, user_id).await?;
+    let notices = settle_locked(&mut tx, user_id, &mut account).await?;
+    let wallet = wallet_balance(&mut tx, user_id).await?;
+    let loan = lock_active_loan(&mut tx, user_id).await?;
+    let recent = sqlx::query_as!(
+        BankLedgerEntry,
+        r#"SELECT kind as "kind: BankEntryKind", wallet_delta, bank_delta, bank_balance, created_at
+        FROM bank_ledger WHERE user_id = $1 ORDER BY entry_id DESC LIMIT $2"#,
+        uid,
+        recent
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(BankStatement {
+        account,
+        wallet,
+        loan,
+        recent,
+        notices,
+    })
+}
+
+/// Moves `amount` (or the whole wallet with `None`) into the bank. Returns (amount deposited,
+/// new bank balance).
+#[instrument(level = "debug", skip(pool))]
+pub async fn deposit(
+    pool: &PgPool,
+    user_id: UserId,
+    amount: Option<i64>,
+) -> Result<(i64, i64), String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the deposit.".to_string())?;
+    let mut account = lock_account(&mut tx, user_id)
+        .await
+        .map_err(|_| "Bank account lookup failed.".to_string())?;
+    settle_locked(&mut tx, user_id, &mut account)
+        .await
+        .map_err(|_| "Failed to settle the account.".to_string())?;
+    let wallet = wallet_balance(&mut tx, user_id)
+        .await
+        .map_err(|_| "Wallet lookup failed.".to_string())?;
+    let amount = amount.unwrap_or(wallet);
+    if amount <= 0 {
+        return Err("Your wallet is empty.".into());
+    }
+    add_balance(&mut tx, user_id, -amount, LedgerReason::Bank)
+        .await
+        .map_err(|_| format!("Your wallet only holds {} coins.", wallet))?;
+    account.balance += amount;
+    set_bank_balance(&mut tx, user_id, account.balance)
+        .await
+        .map_err(|_| "Failed to record the deposit.".to_string())?;
+    record(
+        &mut tx,
+        user_id,
+        BankEntryKind::Deposit,
+        -amount,
+        amount,
+        account.balance,
+        None,
+    )
+    .await
+    .map_err(|_| "Failed to record the deposit.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the deposit.".to_string())?;
+    Ok((amount, account.balance))
+}
+
+/// Moves `amount` (or everything with `None`) from the bank into the wallet. Returns
+/// (amount withdrawn, new bank balance).
+#[instrument(level = "debug", skip(pool))]
+pub async fn withdraw(
+    pool: &PgPool,
+    user_id: UserId,
+    amount: Option<i64>,
+) -> Result<(i64, i64), String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the withdrawal.".to_string())?;
+    let mut account = lock_account(&mut tx, user_id)
+        .await
+        .map_err(|_| "Bank account lookup failed.".to_string())?;
+    settle_locked(&mut tx, user_id, &mut account)
+        .await
+        .map_err(|_| "Failed to settle the account.".to_string())?;
+    let amount = amount.unwrap_or(account.balance);
+    if amount <= 0 {
+        return Err("Your bank balance is empty.".into());
+    }
+    if amount > account.balance {
+        return Err(format!(
+            "Your bank balance is only {} coins.",
+            account.balance
+        ));
+    }
+    account.balance -= amount;
+    set_bank_balance(&mut tx, user_id, account.balance)
+        .await
+        .map_err(|_| "Failed to record the withdrawal.".to_string())?;
+    add_balance(&mut tx, user_id, amount, LedgerReason::Bank)
+        .await
+        .map_err(|_| "Failed to credit your wallet.".to_string())?;
+    record(
+        &mut tx,
+        user_id,
+        BankEntryKind::Withdrawal,
+        amount,
+        -amount,
+        account.balance,
+        None,
+    )
+    .await
+    .map_err(|_| "Failed to record the withdrawal.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the withdrawal.".to_string())?;
+    Ok((amount, account.balance))
+}
+
+/// Lends `principal` coins into the wallet on the standard installment terms.
+#[instrument(level = "debug", skip(pool))]
+pub async fn take_loan(pool: &PgPool, user_id: UserId, principal: i64) -> Result<BankLoan, String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the loan.".to_string())?;
+    let mut account = lock_account(&mut tx, user_id)
+        .await
+        .map_err(|_| "Bank account lookup failed.".to_string())?;
+    settle_locked(&mut tx, user_id, &mut account)
+        .await
+        .map_err(|_| "Failed to settle the account.".to_string())?;
+    if let Some(until) = account.credit_blocked_until
+        && until > Utc::now()
+    {
+        return Err(format!(
+            "After your default the bank won't lend to you until <t:{}:R>.",
+            until.timestamp()
+        ));
+    }
+    if lock_active_loan(&mut tx, user_id)
+        .await
+        .map_err(|_| "Loan lookup failed.".to_string())?
+        .is_some()
+    {
+        return Err("Repay your current loan before taking another.".into());
+    }
+    let rank = account_rank(&account);
+    if principal < MIN_LOAN || principal > rank.max_loan() {
+        return Err(format!(
+            "{} accounts can borrow between {} and {} coins.",
+            rank.label(),
+            MIN_LOAN,
+            rank.max_loan()
+        ));
+    }
+    let terms = LoanTerms::for_principal(principal);
+    let loan = sqlx::query_as!(
+        BankLoan,
+        "INSERT INTO bank_loans (user_id, principal, total_due, installment, next_due_at) VALUES ($1, $2, $3, $4, $5)
+        RETURNING loan_id, principal, total_due, repaid, installment, next_due_at, missed_payments",
+        user_id.get() as i64,
+        terms.principal,
+        terms.total_due,
+        terms.installment,
+        Utc::now() + installment_interval()
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| "Failed to open the loan.".to_string())?;
+    add_balance(
+        &mut tx,
+        user_id,
+        principal,
+        LedgerReason::Bank.with_ref("loan", loan.loan_id),
+    )
+    .await
+    .map_err(|_| "Failed to pay out the loan.".to_string())?;
+    record(
+        &mut tx,
+        user_id,
+        BankEntryKind::LoanIssued,
+        principal,
+        0,
+        account.balance,
+        Some(loan.loan_id),
+    )
+    .await
+    .map_err(|_| "Failed to record the loan.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the loan.".to_string())?;
+    Ok(loan)
+}
+
+/// Pays `amount` (or the whole outstanding debt with `None`) off the active loan from the
+/// wallet. Returns (amount paid, amount still owed).
+#[instrument(level = "debug", skip(pool))]
+pub async fn repay_loan(
+    pool: &PgPool,
+    user_id: UserId,
+    amount: Option<i64>,
+) -> Result<(i64, i64), String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the repayment.".to_string())?;
+    let mut account = lock_account(&mut tx, user_id)
+        .await
+        .map_err(|_| "Bank account lookup failed.".to_string())?;
+    settle_locked(&mut tx, user_id, &mut account)
+        .await
+        .map_err(|_| "Failed to settle the account.".to_string())?;
+    let loan = lock_active_loan(&mut tx, user_id)
+        .await
+        .map_err(|_| "Loan lookup failed.".to_string())?
+        .ok_or_else(|| "You have no active loan.".to_string())?;
+    let outstanding = loan.outstanding();
+    let amount = amount.unwrap_or(outstanding).min(outstanding);
+    if amount <= 0 {
+        return Err("Repay a positive amount.".into());
+    }
+    add_balance(
+        &mut tx,
+        user_id,
+        -amount,
+        LedgerReason::Bank.with_ref("loan", loan.loan_id),
+    )
+    .await
+    .map_err(|_| format!("You need {} coins in your wallet.", amount))?;
+    let remaining = outstanding - amount;
+    sqlx::query!(
+        "UPDATE bank_loans SET repaid = repaid + $2,
+            status = CASE WHEN $3 THEN 'Repaid'::loan_status ELSE status END,
+            closed_at = CASE WHEN $3 THEN NOW() ELSE closed_at END
+        WHERE loan_id = $1",
+        loan.loan_id,
+        amount,
+        remaining == 0
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record the repayment.".to_string())?;
+    record(
+        &mut tx,
+        user_id,
+        BankEntryKind::LoanPayment,
+        -amount,
+        0,
+        account.balance,
+        Some(loan.loan_id),
+    )
+    .await
+    .map_err(|_| "Failed to record the repayment.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the repayment.".to_string())?;
+    Ok((amount, remaining))
+}
@@ -1,5 +1,7 @@
 //! Battle resolution helpers extracted from saga/battle/game.rs for cleaner game loop.
 use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::loot::{CHANCE_SCALE, LootEntry, LootTable};
+use crate::commands::economy::core::registry::{ItemDef, registry};
 use crate::database;
 use crate::database::models::{PlayerUnit, UnitKind, UnitRarity};
 use rand::Rng;
@@ -20,6 +22,8 @@ pub struct ResolveVictoryInput {
     pub vitality_mitigated: i32,
     pub enemy_unit_ids: Vec<i32>,
     pub focus_active: bool,
+    /// Research tree multiplier for research drop chances (1.0 = no bonus).
+    pub research_drop_mult: f64,
 }
 
 /// Chance table for research drops based on rarity.
@@ -49,17 +53,27 @@ pub async fn resolve_node_victory(
     let rewards = database::world::get_rewards_for_node(db, input.node_id)
         .await
         .map_err(|_| "Reward lookup failed")?;
-    let mut dynamic_loot: Vec<(Item, i64)> = Vec::new();
+    let items = registry();
+    let mut dynamic_loot: Vec<(ItemDef, i64)> = Vec::new();
+    // Node drops roll through the loot-table engine as an independent-chance table.
+    let node_table = LootTable::independent(
+        &format!("node_{}", input.node_id),
+        rewards
+            .into_iter()
+            .map(|r| LootEntry {
+                item_id: Some(r.item_id),
+                min_quantity: r.quantity as i64,
+                max_quantity: r.quantity as i64,
+                weight: (r.drop_chance.clamp(0.0, 1.0) * CHANCE_SCALE as f32).round() as u32,
+                is_rare: false,
+            })
+            .collect(),
+    );
     // Use thread-local RNG; confined to this async function scope (no cross-await hold) so Send issues avoided.
-    {
-        let mut local_rng = rng();
-        for r in rewards.into_iter() {
-            let roll: f64 = local_rng.random();
-            if roll < r.drop_chance as f64
-                && let Some(it) = Item::from_i32(r.item_id)
-            {
-                dynamic_loot.push((it, r.quantity as i64));
-            }
+    let node_drops = node_table.roll(&mut rng(), 0).drops;
+    for drop in node_drops {
+        if let Some(it) = drop.item_id.and_then(|id| items.get(id)) {
+            dynamic_loot.push((it.clone(), drop.quantity));
         }
     }
     // Fetch enemy metas in one batch
@@ -95,11 +109,14 @@ pub async fn resolve_node_victory(
                     if input.focus_active {
                         chance = (chance * crate::constants::FOCUS_TONIC_BONUS_MULT).min(0.95);
                     }
+                    chance = (chance * input.research_drop_mult).min(0.95);
                     if chance > 0.0 {
                         let mut roll_rng = rng();
                         let roll: f64 = roll_rng.random();
-                        if roll < chance {
-                            dynamic_loot.push((research_item, 1));
+                        if roll < chance
+                            && let Some(def) = items.get(research_item.id())
+                        {
+                            dynamic_loot.push((def.clone(), 1));
                         }
                     }
                 }
@@ -129,12 +146,20 @@ pub async fn resolve_node_victory(
     } else {
         0
     };
+    // Contracted humans in the party take their negotiated share of the coin loot.
+    let loot_share_pct = database::human::party_loot_share_pct(db, input.user_id)
+        .await
+        .unwrap_or(0);
+    let loot_share_coins = scaled_coins * loot_share_pct as i64 / 100;
     // Apply rewards
     let results = database::units::apply_battle_rewards(
         db,
         input.user_id,
-        scaled_coins,
-        &dynamic_loot,
+        scaled_coins - loot_share_coins,
+        &dynamic_loot
+            .iter()
+            .map(|(def, qty)| (def.id, *qty))
+            .collect::<Vec<_>>(),
         &input.party_units,
         scaled_xp,
     )
@@ -143,6 +168,9 @@ pub async fn resolve_node_victory(
     database::saga::advance_story_progress(db, input.user_id, input.node_id)
         .await
         .ok();
+    database::upkeep::reward_party_morale(db, input.user_id)
+        .await
+        .ok();
     database::tasks::update_task_progress(
         db,
         input.user_id,
@@ -166,6 +194,12 @@ pub async fn resolve_node_victory(
             format!("💰 You earned **{}** coins.", scaled_coins)
         },
     ];
+    if loot_share_coins > 0 {
+        log.push(format!(
+            "🤝 Contract loot share: {} coins ({}%) paid to your hired humans.",
+            loot_share_coins, loot_share_pct
+        ));
+    }
     // Show Focus buff note when active to explain boosted research drop odds.
     if input.focus_active {
         let bonus = ((crate::constants::FOCUS_TONIC_BONUS_MULT - 1.0) * 100.0).round() as i32;
@@ -177,7 +211,7 @@ pub async fn resolve_node_victory(
     if !dynamic_loot.is_empty() {
         let loot_str = dynamic_loot
             .iter()
-            .map(|(i, q)| format!("`{}` {}", q, i.display_name()))
+            .map(|(i, q)| format!("`{}` {}", q, i.name))
             .collect::<Vec<_>>()
             .join(", ");
         log.push(format!("🎁 You found: **{}**!", loot_str));
@@ -39,7 +39,7 @@ pub async fn list_open_contracts(pool: &PgPool, user_id: UserId) -> Result<Vec<H
 #[instrument(level="debug", skip(pool))]
 pub async fn accept_contract(pool: &PgPool, user_id: UserId, unit_id: i32) -> Result<String, String> {
     use crate::database::economy::add_balance;
-    use crate::database::models::Profile;
+    use crate::database::models::{LedgerReason, Profile};
     let user_id_i64 = user_id.get() as i64;
     let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
     sqlx::query!("SELECT pg_advisory_xact_lock($1)", user_id_i64).execute(&mut *tx).await.map_err(|e| e.to_string())?;
@@ -48,9 +48,9 @@ pub async fn accept_contract(pool: &PgPool, user_id: UserId, unit_id: i32) -> Re
     if offer_row.accepted_at.is_some() { tx.rollback().await.ok(); return Err("Contract already accepted.".into()); }
     // Fetch unit meta for snapshot
     let unit_master = sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = $1", unit_id).fetch_one(&mut *tx).await.map_err(|_| "Unit missing.".to_string())?;
-    let profile = sqlx::query_as!(Profile, "SELECT balance, last_work, work_streak, fishing_xp, fishing_level, mining_xp, mining_level, coding_xp, coding_level FROM profiles WHERE user_id = $1 FOR UPDATE", user_id_i64).fetch_one(&mut *tx).await.map_err(|_| "Profile missing.".to_string())?;
+    let profile = sqlx::query_as!(Profile, "SELECT balance, last_work, work_streak FROM profiles WHERE user_id = $1 FOR UPDATE", user_id_i64).fetch_one(&mut *tx).await.map_err(|_| "Profile missing.".to_string())?;
     if profile.balance < offer_row.cost { tx.rollback().await.ok(); return Err("Not enough coins.".into()); }
-    add_balance(&mut tx, user_id, -offer_row.cost).await.map_err(|_| "Payment failed.".to_string())?;
+    add_balance(&mut tx, user_id, -offer_row.cost, LedgerReason::Contract.with_ref("unit", unit_id)).await.map_err(|_| "Payment failed.".to_string())?;
     // Insert player unit (Humans always eligible for party if space; reuse logic light)
     let party_size: i64 = sqlx::query_scalar!("SELECT COUNT(*) FROM player_units WHERE user_id = $1 AND is_in_party = TRUE", user_id_i64).fetch_one(&mut *tx).await.unwrap_or(Some(0)).unwrap_or(0);
     let mut is_in_party = false; if party_size < crate::constants::MAX_PARTY_SIZE { is_in_party = true; }
@@ -1,10 +1,13 @@
 //! Contains all database functions related to the crafting system.
 
-use super::economy::{add_to_inventory, get_inventory_item};
-use super::models::{Recipe, RecipeIngredient};
-use crate::commands::economy::core::item::Item;
+use super::economy::{add_to_inventory, get_inventory_item, get_or_create_profile};
+use super::models::{CraftingSkill, LedgerReason, QueuedCraft, Recipe, RecipeIngredient};
+use super::tasks::update_task_progress;
+use crate::commands::craft::profession::{MAX_BATCH, MAX_QUEUE, craft_duration, roll_batch};
+use crate::commands::economy::core::profile::handle_leveling;
+use crate::commands::economy::core::registry::registry;
 use serenity::model::id::UserId;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 
 /// Fetches all available crafting recipes from the database.
 pub async fn get_all_recipes(pool: &PgPool) -> Result<Vec<Recipe>, sqlx::Error> {
@@ -13,6 +16,20 @@ pub async fn get_all_recipes(pool: &PgPool) -> Result<Vec<Recipe>, sqlx::Error>
         .await
 }
 
+/// The recipe that produces `item_id`, if any.
+pub async fn find_recipe_for_output(
+    pool: &PgPool,
+    item_id: i32,
+) -> Result<Option<Recipe>, sqlx::Error> {
+    sqlx::query_as!(
+        Recipe,
+        "SELECT * FROM recipes WHERE output_item_id = $1 ORDER BY recipe_id LIMIT 1",
+        item_id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
 /// Fetches all ingredients required for a specific recipe.
 pub async fn get_ingredients_for_recipe(
     pool: &PgPool,
@@ -27,72 +44,274 @@ pub async fn get_ingredients_for_recipe(
     .await
 }
 
-/// A transaction to craft an item.
-/// Checks for ingredients, consumes them, and adds the crafted item to the inventory.
-/// Returns Ok(output_item) on success or Err(reason_string) on failure.
-pub async fn craft_item(pool: &PgPool, user_id: UserId, recipe_id: i32) -> Result<Item, String> {
-    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+/// The user's crafting level and XP (level 1 if they have never crafted).
+pub async fn get_crafting_skill(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<CraftingSkill, sqlx::Error> {
+    Ok(sqlx::query_as!(
+        CraftingSkill,
+        "SELECT level, xp FROM crafting_profiles WHERE user_id = $1",
+        user_id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or_default())
+}
+
+/// Locks the user's crafting row, creating it at level 1. Queueing and completing crafts both
+/// take this lock, so one user's queue changes one at a time.
+async fn lock_crafting_skill(
+    tx: &mut Transaction<'_, Postgres>,
+    user_i64: i64,
+) -> Result<CraftingSkill, sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO crafting_profiles (user_id) VALUES ($1) ON CONFLICT (user_id) DO NOTHING",
+        user_i64
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query_as!(
+        CraftingSkill,
+        "SELECT level, xp FROM crafting_profiles WHERE user_id = $1 FOR UPDATE",
+        user_i64
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
 
-    // 1. Get the recipe details.
+/// Unfinished crafts in queue order, followed by the `recent` most recently completed ones.
+pub async fn get_craft_queue(
+    pool: &PgPool,
+    user_id: UserId,
+    recent: i64,
+) -> Result<(Vec<QueuedCraft>, Vec<QueuedCraft>), sqlx::Error> {
+    let user_i64 = user_id.get() as i64;
+    let pending = sqlx::query_as!(
+        QueuedCraft,
+        r#"SELECT r.output_item_id, c.quantity, c.starts_at, c.completes_at,
+                  c.standard_count, c.fine_count, c.masterwork_count, c.saved_count, c.xp_gained
+           FROM craft_queue c JOIN recipes r ON r.recipe_id = c.recipe_id
+           WHERE c.user_id = $1 AND c.completed_at IS NULL ORDER BY c.completes_at"#,
+        user_i64
+    )
+    .fetch_all(pool)
+    .await?;
+    let done = sqlx::query_as!(
+        QueuedCraft,
+        r#"SELECT r.output_item_id, c.quantity, c.starts_at, c.completes_at,
+                  c.standard_count, c.fine_count, c.masterwork_count, c.saved_count, c.xp_gained
+           FROM craft_queue c JOIN recipes r ON r.recipe_id = c.recipe_id
+           WHERE c.user_id = $1 AND c.completed_at IS NOT NULL ORDER BY c.completed_at DESC LIMIT $2"#,
+        user_i64,
+        recent
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok((pending, done))
+}
+
+/// Queues `quantity` crafts of a recipe. The ingredients are taken now and the batch starts
+/// when the user's previous queued craft finishes. Returns the queued craft.
+pub async fn queue_craft(
+    pool: &PgPool,
+    user_id: UserId,
+    recipe_id: i32,
+    quantity: i32,
+) -> Result<QueuedCraft, String> {
+    if !(1..=MAX_BATCH).contains(&quantity) {
+        return Err(format!(
+            "You can craft between 1 and {} at a time.",
+            MAX_BATCH
+        ));
+    }
     let recipe = sqlx::query_as!(
         Recipe,
         "SELECT * FROM recipes WHERE recipe_id = $1",
         recipe_id
     )
-    .fetch_one(&mut *tx)
+    .fetch_optional(pool)
     .await
-    .map_err(|_| "That recipe does not exist.".to_string())?;
-
+    .map_err(|e| e.to_string())?
+    .ok_or("That recipe does not exist.")?;
     let ingredients = get_ingredients_for_recipe(pool, recipe_id)
         .await
         .map_err(|_| "Could not fetch recipe ingredients.".to_string())?;
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let items = registry();
+    let user_i64 = user_id.get() as i64;
 
-    // 2. Check if the player has all the required ingredients.
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let skill = lock_crafting_skill(&mut tx, user_i64)
+        .await
+        .map_err(|e| e.to_string())?;
+    if skill.level < recipe.required_level {
+        return Err(format!(
+            "**{}** needs crafting level {} (you are level {}).",
+            items.name_of(recipe.output_item_id),
+            recipe.required_level,
+            skill.level
+        ));
+    }
+    let queue = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!", MAX(completes_at) as last_completes_at, NOW() as "now!"
+           FROM craft_queue WHERE user_id = $1 AND completed_at IS NULL"#,
+        user_i64
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if queue.count >= MAX_QUEUE {
+        return Err(format!(
+            "Your crafting queue is full ({} crafts). Wait for one to finish.",
+            MAX_QUEUE
+        ));
+    }
+
+    // 1. Check and consume the ingredients for the whole batch.
     for ingredient in &ingredients {
-        let required_item =
-            Item::from_i32(ingredient.item_id).ok_or("Invalid item ID in recipe.")?;
-        let has_item = get_inventory_item(&mut tx, user_id, required_item)
+        let needed = ingredient.quantity as i64 * quantity as i64;
+        let has_item = get_inventory_item(&mut tx, user_id, ingredient.item_id)
             .await
             .map_err(|_| "Could not check your inventory.".to_string())?;
-
-        if has_item.is_none_or(|i| i.quantity < ingredient.quantity as i64) {
-            tx.rollback().await.ok();
+        if has_item.is_none_or(|i| i.quantity < needed) {
             return Err(format!(
-                "You don't have enough {}!",
-                required_item.display_name()
+                "You need `{}` {} to craft {}x {}.",
+                needed,
+                items.name_of(ingredient.item_id),
+                quantity,
+                items.name_of(recipe.output_item_id)
             ));
         }
-    }
-
-    // 3. Atomically consume the ingredients.
-    for ingredient in &ingredients {
-        let required_item = match Item::from_i32(ingredient.item_id) {
-            Some(it) => it,
-            None => {
-                tx.rollback().await.ok();
-                return Err("Invalid ingredient item id in recipe.".into());
-            }
-        }; // Safer conversion.
         add_to_inventory(
             &mut tx,
             user_id,
-            required_item,
-            -(ingredient.quantity as i64),
+            ingredient.item_id,
+            -needed,
+            LedgerReason::Crafting.with_ref("recipe", recipe_id),
         )
         .await
         .map_err(|_| "Failed to consume crafting materials.".to_string())?;
     }
 
-    // 4. Add the crafted item to the player's inventory.
-    let output_item =
-        Item::from_i32(recipe.output_item_id).ok_or("Invalid output item ID in recipe.")?;
-    add_to_inventory(&mut tx, user_id, output_item, recipe.output_quantity as i64)
-        .await
-        .map_err(|_| "Failed to add crafted item to your inventory.".to_string())?;
-
+    // 2. Queue the batch behind anything already in progress.
+    let starts_at = queue
+        .last_completes_at
+        .map_or(queue.now, |last| last.max(queue.now));
+    let completes_at = starts_at + craft_duration(recipe.craft_secs, skill.level, quantity);
+    sqlx::query!(
+        "INSERT INTO craft_queue (user_id, recipe_id, quantity, starts_at, completes_at) VALUES ($1, $2, $3, $4, $5)",
+        user_i64,
+        recipe_id,
+        quantity,
+        starts_at,
+        completes_at
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
     tx.commit()
         .await
         .map_err(|_| "Failed to finalize the transaction.".to_string())?;
 
-    Ok(output_item)
+    Ok(QueuedCraft {
+        output_item_id: recipe.output_item_id,
+        quantity,
+        starts_at,
+        completes_at,
+        standard_count: 0,
+        fine_count: 0,
+        masterwork_count: 0,
+        saved_count: 0,
+        xp_gained: 0,
+    })
+}
+
+/// Completes every craft that is due: rolls quality and saved ingredients at the crafter's
+/// current level, grants the outputs and XP, and records the result on the queue row. Runs from
+/// the background crafting worker and lazily from `/craft`. Returns how many crafts finished.
+pub async fn complete_due_crafts(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let mut completed = 0;
+    loop {
+        let mut tx = pool.begin().await?;
+        let Some(craft) = sqlx::query!(
+            "SELECT craft_id, user_id, recipe_id, quantity FROM craft_queue WHERE completed_at IS NULL AND completes_at <= NOW() ORDER BY completes_at LIMIT 1 FOR UPDATE SKIP LOCKED"
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.rollback().await.ok();
+            break;
+        };
+        let recipe = sqlx::query_as!(
+            Recipe,
+            "SELECT * FROM recipes WHERE recipe_id = $1",
+            craft.recipe_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let ingredients = sqlx::query_as!(
+            RecipeIngredient,
+            "SELECT item_id, quantity FROM recipe_ingredients WHERE recipe_id = $1",
+            craft.recipe_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let user_id = UserId::new(craft.user_id as u64);
+        let skill = lock_crafting_skill(&mut tx, craft.user_id).await?;
+
+        // Roll up front; the RNG must not be held across an await.
+        let outcome = roll_batch(skill.level, craft.quantity, &mut rand::rng());
+        let source = LedgerReason::Crafting.with_ref("craft", craft.craft_id);
+        for (item_id, quantity) in outcome.outputs(&recipe) {
+            add_to_inventory(&mut tx, user_id, item_id, quantity, source.clone()).await?;
+        }
+        if outcome.saved > 0 {
+            for ingredient in &ingredients {
+                add_to_inventory(
+                    &mut tx,
+                    user_id,
+                    ingredient.item_id,
+                    ingredient.quantity as i64 * outcome.saved as i64,
+                    source.clone(),
+                )
+                .await?;
+            }
+        }
+        let xp_gained = recipe.xp_reward * craft.quantity as i64;
+        let (level, xp, _) = handle_leveling(skill.level, skill.xp, xp_gained);
+        sqlx::query!(
+            "UPDATE crafting_profiles SET level = $2, xp = $3 WHERE user_id = $1",
+            craft.user_id,
+            level,
+            xp
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE craft_queue SET completed_at = NOW(), standard_count = $2, fine_count = $3, masterwork_count = $4, saved_count = $5, xp_gained = $6 WHERE craft_id = $1",
+            craft.craft_id,
+            outcome.standard,
+            outcome.fine,
+            outcome.masterwork,
+            outcome.saved,
+            xp_gained
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        completed += 1;
+
+        update_task_progress(
+            pool,
+            user_id,
+            &format!("CraftItem:{}", recipe.output_item_id), // e.g., "CraftItem:11"
+            outcome.total(),
+        )
+        .await
+        .ok();
+    }
+    Ok(completed)
 }
@@ -0,0 +1,168 @@
+//! Persistence for gambling stats and self-set limits (rules in
+//! `commands::economy::gambling::logic`).
+
+use chrono::{DateTime, Duration, Utc};
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+
+use super::economy::get_or_create_profile;
+use super::models::{GambleGame, GamblingLimits, GamblingStat};
+use crate::commands::economy::gambling::logic::{
+    LIMIT_RAISE_DELAY_HOURS, LimitChange, check_play, effective_loss_limit, limit_change,
+};
+use crate::commands::games::GamePayout;
+
+/// Records one hand per result: its net, whether it was won or lost, and the day's running net.
+pub async fn record(
+    pool: &PgPool,
+    game: GambleGame,
+    results: &[GamePayout],
+) -> Result<(), sqlx::Error> {
+    for result in results {
+        get_or_create_profile(pool, result.user_id).await?;
+    }
+    let mut tx = pool.begin().await?;
+    for result in results {
+        let uid = result.user_id.get() as i64;
+        sqlx::query!(
+            r#"INSERT INTO gambling_stats (user_id, game, hands_played, hands_won, hands_lost, net, biggest_win)
+               VALUES ($1, $2, 1, ($3::BIGINT > 0)::int, ($3::BIGINT < 0)::int, $3::BIGINT, GREATEST($3::BIGINT, 0))
+               ON CONFLICT (user_id, game) DO UPDATE SET
+                   hands_played = gambling_stats.hands_played + 1,
+                   hands_won = gambling_stats.hands_won + EXCLUDED.hands_won,
+                   hands_lost = gambling_stats.hands_lost + EXCLUDED.hands_lost,
+                   net = gambling_stats.net + EXCLUDED.net,
+                   biggest_win = GREATEST(gambling_stats.biggest_win, EXCLUDED.biggest_win)"#,
+            uid,
+            game as GambleGame,
+            result.amount
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO gambling_daily (user_id, day, net) VALUES ($1, CURRENT_DATE, $2)
+               ON CONFLICT (user_id, day) DO UPDATE SET net = gambling_daily.net + EXCLUDED.net"#,
+            uid,
+            result.amount
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// Per-game stats, biggest net winnings first.
+pub async fn get_stats(pool: &PgPool, user_id: UserId) -> Result<Vec<GamblingStat>, sqlx::Error> {
+    sqlx::query_as!(
+        GamblingStat,
+        r#"SELECT game AS "game: GambleGame", hands_played, hands_won, hands_lost, net, biggest_win
+           FROM gambling_stats WHERE user_id = $1 ORDER BY net DESC"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// The player's net result for the current day.
+pub async fn net_today(pool: &PgPool, user_id: UserId) -> Result<i64, sqlx::Error> {
+    Ok(sqlx::query_scalar!(
+        "SELECT net FROM gambling_daily WHERE user_id = $1 AND day = CURRENT_DATE",
+        user_id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(0))
+}
+
+pub async fn get_limits(pool: &PgPool, user_id: UserId) -> Result<GamblingLimits, sqlx::Error> {
+    Ok(sqlx::query_as!(
+        GamblingLimits,
+        "SELECT daily_loss_limit, pending_loss_limit, pending_from, cooldown_until FROM gambling_limits WHERE user_id = $1",
+        user_id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or_default())
+}
+
+/// Refuses to seat a player who is on a cooldown or has hit their daily loss limit.
+pub async fn check_can_play(pool: &PgPool, user_id: UserId) -> Result<(), String> {
+    let limits = get_limits(pool, user_id)
+        .await
+        .map_err(|_| "Could not check your gambling limits.".to_string())?;
+    let net = net_today(pool, user_id)
+        .await
+        .map_err(|_| "Could not check your gambling limits.".to_string())?;
+    check_play(&limits, net, Utc::now())
+}
+
+/// Sets (or with `None`, removes) the daily loss limit. Returns whether it applies now or
+/// after the delay.
+pub async fn set_loss_limit(
+    pool: &PgPool,
+    user_id: UserId,
+    limit: Option<i64>,
+) -> Result<LimitChange, String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let current = get_limits(pool, user_id)
+        .await
+        .map_err(|_| "Could not load your gambling limits.".to_string())?;
+    let now = Utc::now();
+    let change = limit_change(effective_loss_limit(&current, now), limit);
+    let uid = user_id.get() as i64;
+    let saved = match change {
+        LimitChange::Now => {
+            sqlx::query!(
+                r#"INSERT INTO gambling_limits (user_id, daily_loss_limit) VALUES ($1, $2)
+                   ON CONFLICT (user_id) DO UPDATE SET daily_loss_limit = $2,
+                       pending_loss_limit = NULL, pending_from = NULL"#,
+                uid,
+                limit
+            )
+            .execute(pool)
+            .await
+        }
+        LimitChange::Delayed => {
+            // Keep the limit in force until the change is due.
+            let pending_from = now + Duration::hours(LIMIT_RAISE_DELAY_HOURS);
+            sqlx::query!(
+                r#"UPDATE gambling_limits SET daily_loss_limit = $2, pending_loss_limit = $3,
+                       pending_from = $4 WHERE user_id = $1"#,
+                uid,
+                effective_loss_limit(&current, now),
+                limit,
+                pending_from
+            )
+            .execute(pool)
+            .await
+        }
+    };
+    saved.map_err(|_| "Failed to save your loss limit.".to_string())?;
+    Ok(change)
+}
+
+/// Blocks gambling for `length`. An existing, longer cooldown is kept. Returns when the
+/// cooldown ends.
+pub async fn start_cooldown(
+    pool: &PgPool,
+    user_id: UserId,
+    length: Duration,
+) -> Result<DateTime<Utc>, String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let until = Utc::now() + length;
+    sqlx::query_scalar!(
+        r#"INSERT INTO gambling_limits (user_id, cooldown_until) VALUES ($1, $2)
+           ON CONFLICT (user_id) DO UPDATE SET cooldown_until =
+               GREATEST(COALESCE(gambling_limits.cooldown_until, $2), $2)
+           RETURNING cooldown_until AS "cooldown_until!""#,
+        user_id.get() as i64,
+        until
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|_| "Failed to start your cooldown.".to_string())
+}
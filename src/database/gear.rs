@@ -0,0 +1,348 @@
+//! Persistence for unit gear (rules in `saga::gear`).
+
+use serenity::model::id::UserId;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::economy::{add_balance, add_to_inventory};
+use super::models::{GearSlot, LedgerReason, UnitGear};
+use crate::commands::economy::core::registry::registry;
+use crate::saga::gear::{GearBonus, repair_cost};
+
+/// A stack of equippable items in a player's inventory.
+#[derive(Debug, Clone)]
+pub struct OwnedGear {
+    pub item_id: i32,
+    pub slot: GearSlot,
+    pub quantity: i64,
+}
+
+/// All gear equipped on the player's units, ordered by unit and slot.
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_user_gear(pool: &PgPool, user_id: UserId) -> Result<Vec<UnitGear>, sqlx::Error> {
+    sqlx::query_as!(
+        UnitGear,
+        r#"SELECT g.player_unit_id, g.slot as "slot: GearSlot", g.item_id, g.durability,
+            gs.max_durability, gs.attack, gs.defense, gs.health
+        FROM player_unit_gear g
+        JOIN gear_stats gs ON gs.item_id = g.item_id
+        JOIN player_units pu ON pu.player_unit_id = g.player_unit_id
+        WHERE pu.user_id = $1
+        ORDER BY g.player_unit_id, g.slot"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Battle stats granted by each unit's working gear (player_unit_id -> bonus).
+pub async fn get_gear_bonuses(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<HashMap<i32, GearBonus>, sqlx::Error> {
+    let mut by_unit: HashMap<i32, Vec<UnitGear>> = HashMap::new();
+    for gear in get_user_gear(pool, user_id).await? {
+        by_unit.entry(gear.player_unit_id).or_default().push(gear);
+    }
+    Ok(by_unit
+        .into_iter()
+        .map(|(unit, gear)| (unit, GearBonus::from_gear(&gear)))
+        .filter(|(_, bonus)| !bonus.is_zero())
+        .collect())
+}
+
+/// Equippable items sitting in the player's inventory.
+pub async fn list_owned_gear(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<Vec<OwnedGear>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT inv.item_id, gs.slot as "slot: GearSlot", inv.quantity
+        FROM inventories inv JOIN gear_stats gs ON gs.item_id = inv.item_id
+        WHERE inv.user_id = $1 AND inv.quantity > 0
+        ORDER BY gs.slot, inv.item_id"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| OwnedGear {
+            item_id: r.item_id,
+            slot: r.slot,
+            quantity: r.quantity,
+        })
+        .collect())
+}
+
+/// Locks the unit row, failing when the unit is not in the player's army.
+async fn lock_owned_unit(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    player_unit_id: i32,
+) -> Result<String, String> {
+    sqlx::query_scalar!(
+        r#"SELECT COALESCE(pu.nickname, u.name) as "name!"
+        FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id
+        WHERE pu.player_unit_id = $1 AND pu.user_id = $2 FOR UPDATE OF pu"#,
+        player_unit_id,
+        user_id.get() as i64
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|_| "Unit lookup failed.".to_string())?
+    .ok_or_else(|| "That unit is not in your army.".to_string())
+}
+
+/// Takes one item out of the inventory and equips it, returning the slot's previous item to the
+/// inventory. Damaged gear must be repaired before it can be swapped out, so unequipping is never
+/// a free repair.
+#[instrument(level = "debug", skip(pool))]
+pub async fn equip_gear(
+    pool: &PgPool,
+    user_id: UserId,
+    player_unit_id: i32,
+    item_id: i32,
+) -> Result<String, String> {
+    let items = registry();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start equipping.".to_string())?;
+    let unit_name = lock_owned_unit(&mut tx, user_id, player_unit_id).await?;
+    let stats = sqlx::query!(
+        r#"SELECT slot as "slot: GearSlot", max_durability FROM gear_stats WHERE item_id = $1"#,
+        item_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Gear lookup failed.".to_string())?
+    .ok_or_else(|| format!("{} cannot be equipped.", items.name_of(item_id)))?;
+    add_to_inventory(
+        &mut tx,
+        user_id,
+        item_id,
+        -1,
+        LedgerReason::Gear.with_ref("unit", player_unit_id),
+    )
+    .await
+    .map_err(|_| format!("You don't have a {}.", items.name_of(item_id)))?;
+
+    let previous = sqlx::query!(
+        r#"SELECT g.item_id, g.durability, gs.max_durability
+        FROM player_unit_gear g JOIN gear_stats gs ON gs.item_id = g.item_id
+        WHERE g.player_unit_id = $1 AND g.slot = $2 FOR UPDATE OF g"#,
+        player_unit_id,
+        stats.slot as GearSlot
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Gear lookup failed.".to_string())?;
+    if let Some(prev) = &previous {
+        if prev.durability < prev.max_durability {
+            return Err(format!(
+                "{}'s {} is damaged. Repair it at the tavern before swapping it out.",
+                unit_name,
+                items.name_of(prev.item_id)
+            ));
+        }
+        add_to_inventory(
+            &mut tx,
+            user_id,
+            prev.item_id,
+            1,
+            LedgerReason::Gear.with_ref("unit", player_unit_id),
+        )
+        .await
+        .map_err(|_| "Failed to return the old gear.".to_string())?;
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO player_unit_gear (player_unit_id, slot, item_id, durability)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (player_unit_id, slot) DO UPDATE
+            SET item_id = EXCLUDED.item_id, durability = EXCLUDED.durability, equipped_at = NOW()"#,
+        player_unit_id,
+        stats.slot as GearSlot,
+        item_id,
+        stats.max_durability
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to equip the gear.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize equipping.".to_string())?;
+
+    let mut message = format!(
+        "{} equipped **{}** ({}).",
+        unit_name,
+        items.name_of(item_id),
+        stats.slot.label()
+    );
+    if let Some(prev) = previous {
+        message.push_str(&format!(
+            " {} returned to your inventory.",
+            items.name_of(prev.item_id)
+        ));
+    }
+    Ok(message)
+}
+
+/// Moves a unit's item in `slot` back to the inventory. Damaged gear must be repaired first.
+#[instrument(level = "debug", skip(pool))]
+pub async fn unequip_gear(
+    pool: &PgPool,
+    user_id: UserId,
+    player_unit_id: i32,
+    slot: GearSlot,
+) -> Result<String, String> {
+    let items = registry();
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start unequipping.".to_string())?;
+    let unit_name = lock_owned_unit(&mut tx, user_id, player_unit_id).await?;
+    let gear = sqlx::query!(
+        r#"SELECT g.item_id, g.durability, gs.max_durability
+        FROM player_unit_gear g JOIN gear_stats gs ON gs.item_id = g.item_id
+        WHERE g.player_unit_id = $1 AND g.slot = $2 FOR UPDATE OF g"#,
+        player_unit_id,
+        slot as GearSlot
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Gear lookup failed.".to_string())?
+    .ok_or_else(|| format!("{} has no {} equipped.", unit_name, slot.label()))?;
+    if gear.durability < gear.max_durability {
+        return Err(format!(
+            "{} is damaged. Repair it at the tavern before unequipping.",
+            items.name_of(gear.item_id)
+        ));
+    }
+    sqlx::query!(
+        "DELETE FROM player_unit_gear WHERE player_unit_id = $1 AND slot = $2",
+        player_unit_id,
+        slot as GearSlot
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to unequip the gear.".to_string())?;
+    add_to_inventory(
+        &mut tx,
+        user_id,
+        gear.item_id,
+        1,
+        LedgerReason::Gear.with_ref("unit", player_unit_id),
+    )
+    .await
+    .map_err(|_| "Failed to return the gear.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize unequipping.".to_string())?;
+    Ok(format!(
+        "{} unequipped **{}**.",
+        unit_name,
+        items.name_of(gear.item_id)
+    ))
+}
+
+/// Wears down every working item equipped on the given units and returns the worn gear with its
+/// new durability.
+#[instrument(level = "debug", skip(pool))]
+pub async fn wear_gear(
+    pool: &PgPool,
+    player_unit_ids: &[i32],
+    wear: i32,
+) -> Result<Vec<UnitGear>, sqlx::Error> {
+    if player_unit_ids.is_empty() || wear <= 0 {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as!(
+        UnitGear,
+        r#"WITH worn AS (
+            UPDATE player_unit_gear SET durability = GREATEST(durability - $2, 0)
+            WHERE player_unit_id = ANY($1) AND durability > 0
+            RETURNING player_unit_id, slot, item_id, durability
+        )
+        SELECT w.player_unit_id as "player_unit_id!", w.slot as "slot!: GearSlot",
+            w.item_id as "item_id!", w.durability as "durability!",
+            gs.max_durability, gs.attack, gs.defense, gs.health
+        FROM worn w JOIN gear_stats gs ON gs.item_id = w.item_id
+        ORDER BY w.player_unit_id, w.slot"#,
+        player_unit_ids,
+        wear
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Restores all of the player's damaged gear to full durability. Returns (items repaired, cost).
+#[instrument(level = "debug", skip(pool))]
+pub async fn repair_all_gear(pool: &PgPool, user_id: UserId) -> Result<(usize, i64), String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start repairs.".to_string())?;
+    let damaged = sqlx::query_as!(
+        UnitGear,
+        r#"SELECT g.player_unit_id, g.slot as "slot: GearSlot", g.item_id, g.durability,
+            gs.max_durability, gs.attack, gs.defense, gs.health
+        FROM player_unit_gear g
+        JOIN gear_stats gs ON gs.item_id = g.item_id
+        JOIN player_units pu ON pu.player_unit_id = g.player_unit_id
+        WHERE pu.user_id = $1 AND g.durability < gs.max_durability
+        FOR UPDATE OF g"#,
+        user_id.get() as i64
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|_| "Gear lookup failed.".to_string())?;
+    if damaged.is_empty() {
+        return Err("None of your gear needs repairs.".into());
+    }
+    let cost: i64 = damaged.iter().map(repair_cost).sum();
+    add_balance(&mut tx, user_id, -cost, LedgerReason::Gear)
+        .await
+        .map_err(|_| format!("Repairs cost {} coins.", cost))?;
+    let unit_ids: Vec<i32> = damaged.iter().map(|g| g.player_unit_id).collect();
+    sqlx::query!(
+        r#"UPDATE player_unit_gear g SET durability = gs.max_durability
+        FROM gear_stats gs
+        WHERE gs.item_id = g.item_id AND g.player_unit_id = ANY($1) AND g.durability < gs.max_durability"#,
+        &unit_ids
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record the repairs.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the repairs.".to_string())?;
+    Ok((damaged.len(), cost))
+}
+
+/// Strips a unit's gear back into the owner's inventory (used before the unit is removed).
+pub async fn return_unit_gear(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    player_unit_id: i32,
+) -> Result<(), sqlx::Error> {
+    let returned = sqlx::query_scalar!(
+        "DELETE FROM player_unit_gear WHERE player_unit_id = $1 RETURNING item_id",
+        player_unit_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    for item_id in returned {
+        add_to_inventory(
+            tx,
+            user_id,
+            item_id,
+            1,
+            LedgerReason::Gear.with_ref("unit", player_unit_id),
+        )
+        .await?;
+    }
+    Ok(())
+}
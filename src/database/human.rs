@@ -3,8 +3,12 @@ use sqlx::PgPool;
 use tracing::instrument;
 
 use crate::AppState;
-use crate::database::models::{DraftedHumanContract, HumanContractOffer};
+use crate::database::models::{DraftedHumanContract, HumanContractOffer, LedgerReason};
 use crate::database::models::{HumanEncounter, Unit, UnitKind, UnitRarity};
+use crate::saga::negotiation::{
+    ContractTerms, Counter, MAX_NEGOTIATION_ROUNDS, NegotiationOutcome, flexibility_pct,
+    opening_demands, resolve_counter,
+};
 use std::time::Duration;
 
 #[inline]
@@ -59,7 +63,7 @@ pub async fn list_human_progress(
 ) -> Result<Vec<(Unit, i32, i32)>, sqlx::Error> {
     // Returns (Unit, defeats, required)
     let uid = user_id.get() as i64;
-    let rows = sqlx::query!(r#"SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.kind as "kind: UnitKind", u.rarity as "rarity: UnitRarity", COALESCE(he.defeats,0) as defeats
+    let rows = sqlx::query!(r#"SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.is_ranged, u.kind as "kind: UnitKind", u.rarity as "rarity: UnitRarity", COALESCE(he.defeats,0) as defeats
         FROM units u LEFT JOIN human_encounters he ON he.user_id = $1 AND he.unit_id = u.unit_id
         WHERE u.kind = 'Human'"#, uid).fetch_all(pool).await?;
     let mut out = Vec::new();
@@ -73,6 +77,7 @@ pub async fn list_human_progress(
                 base_defense: r.base_defense,
                 base_health: r.base_health,
                 is_recruitable: r.is_recruitable,
+                is_ranged: r.is_ranged,
                 kind: r.kind,
                 rarity: r.rarity,
             },
@@ -116,6 +121,8 @@ pub async fn draft_contract(pool: &PgPool, user_id: UserId, unit_id: i32) -> Res
     if drafted_exists {
         return Err("Contract already drafted.".into());
     }
+    // The human opens negotiations with rarity-scaled demands (see saga::negotiation).
+    let demands = opening_demands(meta.rarity);
     // Parchment gating (feature-flagged). When enabled:
     //  - Rare humans need Forest parchment (14)
     //  - Epic+ need Frontier parchment (15)
@@ -155,18 +162,22 @@ pub async fn draft_contract(pool: &PgPool, user_id: UserId, unit_id: i32) -> Res
                 item.display_name()
             ));
         }
-        sqlx::query!(
-            "UPDATE inventories SET quantity = quantity - 1 WHERE user_id=$1 AND item_id=$2",
-            uid,
-            item as i32
+        crate::database::economy::add_to_inventory(
+            &mut tx,
+            user_id,
+            item,
+            -1,
+            LedgerReason::Contract.with_ref("unit", unit_id),
         )
-        .execute(&mut *tx)
         .await
         .map_err(|_| "Failed to consume parchment".to_string())?;
         sqlx::query!(
-            "INSERT INTO drafted_human_contracts (user_id, unit_id) VALUES ($1,$2)",
+            "INSERT INTO drafted_human_contracts (user_id, unit_id, wage, loot_share_pct, party_slot) VALUES ($1,$2,$3,$4,$5)",
             uid,
-            unit_id
+            unit_id,
+            demands.wage,
+            demands.loot_share_pct,
+            demands.party_slot
         )
         .execute(&mut *tx)
         .await
@@ -174,9 +185,12 @@ pub async fn draft_contract(pool: &PgPool, user_id: UserId, unit_id: i32) -> Res
         tx.commit().await.map_err(|_| "Commit fail".to_string())?;
     } else {
         sqlx::query!(
-            "INSERT INTO drafted_human_contracts (user_id, unit_id) VALUES ($1,$2)",
+            "INSERT INTO drafted_human_contracts (user_id, unit_id, wage, loot_share_pct, party_slot) VALUES ($1,$2,$3,$4,$5)",
             uid,
-            unit_id
+            unit_id,
+            demands.wage,
+            demands.loot_share_pct,
+            demands.party_slot
         )
         .execute(pool)
         .await
@@ -196,19 +210,18 @@ pub async fn accept_drafted_contract(
         .begin()
         .await
         .map_err(|_| "Tx start fail".to_string())?;
-    let drafted = sqlx::query_scalar!(
-        "SELECT 1 FROM drafted_human_contracts WHERE user_id=$1 AND unit_id=$2 AND consumed=FALSE",
+    let Some(drafted) = sqlx::query!(
+        "SELECT wage, loot_share_pct, party_slot FROM drafted_human_contracts WHERE user_id=$1 AND unit_id=$2 AND consumed=FALSE FOR UPDATE",
         uid,
         unit_id
     )
     .fetch_optional(&mut *tx)
     .await
     .map_err(|_| "Lookup failed".to_string())?
-    .is_some();
-    if !drafted {
+    else {
         tx.rollback().await.ok();
         return Err("No drafted contract.".into());
-    }
+    };
     // Reuse hire logic minimal: fetch unit
     let unit_master = sqlx::query!("SELECT unit_id, name, base_attack, base_defense, base_health, rarity as \"rarity: UnitRarity\", kind as \"kind: UnitKind\", is_recruitable, description FROM units WHERE unit_id=$1", unit_id)
         .fetch_one(&mut *tx).await.map_err(|_| "Unit not found".to_string())?;
@@ -224,11 +237,23 @@ pub async fn accept_drafted_contract(
     .await
     .unwrap_or(Some(0))
     .unwrap_or(0);
+    let terms = drafted_terms(
+        unit_master.rarity,
+        drafted.wage,
+        drafted.loot_share_pct,
+        drafted.party_slot,
+    );
     let mut is_in_party = false;
     if party_size < crate::constants::MAX_PARTY_SIZE {
         is_in_party = true;
+    } else if terms.party_slot {
+        tx.rollback().await.ok();
+        return Err(format!(
+            "{} demands a guaranteed party slot; free one up first.",
+            unit_master.name
+        ));
     }
-    sqlx::query!("INSERT INTO player_units (user_id, unit_id, nickname, current_attack, current_defense, current_health, rarity, is_in_party) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)", uid, unit_id, &unit_master.name, unit_master.base_attack, unit_master.base_defense, unit_master.base_health, unit_master.rarity as _, is_in_party)
+    sqlx::query!("INSERT INTO player_units (user_id, unit_id, nickname, current_attack, current_defense, current_health, rarity, is_in_party, contract_wage, contract_loot_share_pct, contract_party_slot) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)", uid, unit_id, &unit_master.name, unit_master.base_attack, unit_master.base_defense, unit_master.base_health, unit_master.rarity as _, is_in_party, terms.wage, terms.loot_share_pct, terms.party_slot)
         .execute(&mut *tx).await.map_err(|_| "Insert failed".to_string())?;
     sqlx::query!(
         "UPDATE drafted_human_contracts SET consumed=TRUE WHERE user_id=$1 AND unit_id=$2",
@@ -242,6 +267,156 @@ pub async fn accept_drafted_contract(
     Ok(unit_master.name)
 }
 
+/// Terms stored on a draft; drafts from before negotiation existed fall back to opening demands.
+fn drafted_terms(
+    rarity: UnitRarity,
+    wage: Option<i64>,
+    loot_share_pct: Option<i32>,
+    party_slot: Option<bool>,
+) -> ContractTerms {
+    let opening = opening_demands(rarity);
+    ContractTerms {
+        wage: wage.unwrap_or(opening.wage),
+        loot_share_pct: loot_share_pct.unwrap_or(opening.loot_share_pct),
+        party_slot: party_slot.unwrap_or(opening.party_slot),
+    }
+}
+
+/// Snapshot of an open negotiation for the negotiation view.
+#[derive(Debug, Clone)]
+pub struct NegotiationState {
+    pub unit_id: i32,
+    pub unit_name: String,
+    pub opening: ContractTerms,
+    pub current: ContractTerms,
+    pub rounds_used: i32,
+    pub fame_tier: usize,
+    pub extra_defeats: i32,
+    pub flexibility: i32,
+}
+
+impl NegotiationState {
+    pub fn rounds_left(&self) -> i32 {
+        (MAX_NEGOTIATION_ROUNDS - self.rounds_used).max(0)
+    }
+}
+
+async fn load_negotiation<'e, E>(
+    executor: E,
+    user_id: UserId,
+    unit_id: i32,
+    fame: i32,
+) -> Result<NegotiationState, String>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let uid = user_id.get() as i64;
+    let row = sqlx::query!(
+        r#"SELECT u.name, u.rarity as "rarity: UnitRarity", d.wage, d.loot_share_pct, d.party_slot, d.negotiation_rounds,
+            COALESCE(he.defeats, 0) as "defeats!"
+        FROM drafted_human_contracts d
+        JOIN units u ON u.unit_id = d.unit_id
+        LEFT JOIN human_encounters he ON he.user_id = d.user_id AND he.unit_id = d.unit_id
+        WHERE d.user_id = $1 AND d.unit_id = $2 AND d.consumed = FALSE"#,
+        uid,
+        unit_id
+    )
+    .fetch_optional(executor)
+    .await
+    .map_err(|_| "Negotiation lookup failed".to_string())?
+    .ok_or_else(|| "No drafted contract to negotiate.".to_string())?;
+    let fame_tier = crate::commands::saga::tavern::fame_tier(fame).0;
+    let extra_defeats = (row.defeats - defeats_required_for(row.rarity)).max(0);
+    Ok(NegotiationState {
+        unit_id,
+        unit_name: row.name,
+        opening: opening_demands(row.rarity),
+        current: drafted_terms(row.rarity, row.wage, row.loot_share_pct, row.party_slot),
+        rounds_used: row.negotiation_rounds,
+        fame_tier,
+        extra_defeats,
+        flexibility: flexibility_pct(fame_tier, extra_defeats),
+    })
+}
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_negotiation(
+    pool: &PgPool,
+    user_id: UserId,
+    unit_id: i32,
+) -> Result<NegotiationState, String> {
+    let (fame, _, _) = crate::database::tavern::get_or_create_fame(pool, user_id)
+        .await
+        .map_err(|_| "Fame lookup failed".to_string())?;
+    load_negotiation(pool, user_id, unit_id, fame).await
+}
+
+/// Submit one counter-offer. The draft row is locked so concurrent clicks cannot spend the same
+/// round twice.
+#[instrument(level = "info", skip(pool))]
+pub async fn counter_contract(
+    pool: &PgPool,
+    user_id: UserId,
+    unit_id: i32,
+    counter: Counter,
+) -> Result<(NegotiationOutcome, NegotiationState), String> {
+    let uid = user_id.get() as i64;
+    let (fame, _, _) = crate::database::tavern::get_or_create_fame(pool, user_id)
+        .await
+        .map_err(|_| "Fame lookup failed".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Tx start fail".to_string())?;
+    sqlx::query!(
+        "SELECT unit_id FROM drafted_human_contracts WHERE user_id=$1 AND unit_id=$2 AND consumed=FALSE FOR UPDATE",
+        uid,
+        unit_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Lookup failed".to_string())?
+    .ok_or_else(|| "No drafted contract to negotiate.".to_string())?;
+    let mut state = load_negotiation(&mut *tx, user_id, unit_id, fame).await?;
+    if state.rounds_left() == 0 {
+        tx.rollback().await.ok();
+        return Err(format!(
+            "{} has made their final offer. Accept it or walk away.",
+            state.unit_name
+        ));
+    }
+    let outcome = resolve_counter(&state.opening, &state.current, counter, state.flexibility);
+    let terms = outcome.terms(state.current);
+    sqlx::query!(
+        "UPDATE drafted_human_contracts SET wage=$3, loot_share_pct=$4, party_slot=$5, negotiation_rounds = negotiation_rounds + 1 WHERE user_id=$1 AND unit_id=$2",
+        uid,
+        unit_id,
+        terms.wage,
+        terms.loot_share_pct,
+        terms.party_slot
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record counter".to_string())?;
+    tx.commit().await.map_err(|_| "Commit failed".to_string())?;
+    state.current = terms;
+    state.rounds_used += 1;
+    Ok((outcome, state))
+}
+
+/// Combined coin loot share (percent, capped at 50) owed to contracted humans in the party.
+#[instrument(level = "debug", skip(pool))]
+pub async fn party_loot_share_pct(pool: &PgPool, user_id: UserId) -> Result<i32, sqlx::Error> {
+    let total = sqlx::query_scalar!(
+        "SELECT COALESCE(SUM(contract_loot_share_pct), 0)::INT FROM player_units WHERE user_id=$1 AND is_in_party=TRUE",
+        user_id.get() as i64
+    )
+    .fetch_one(pool)
+    .await?
+    .unwrap_or(0);
+    Ok(total.clamp(0, 50))
+}
+
 pub type ContractStatusRow = (
     Unit,
     i32,
@@ -258,7 +433,7 @@ pub async fn list_contract_status(
 ) -> Result<Vec<ContractStatusRow>, sqlx::Error> {
     // (Unit, defeats, required, drafted, recruited)
     let uid = user_id.get() as i64;
-    let rows = sqlx::query!(r#"SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.kind as "kind: UnitKind", u.rarity as "rarity: UnitRarity",
+    let rows = sqlx::query!(r#"SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.is_ranged, u.kind as "kind: UnitKind", u.rarity as "rarity: UnitRarity",
         COALESCE(he.defeats,0) as defeats, he.last_defeated_at,
         (SELECT 1 FROM drafted_human_contracts d2 WHERE d2.user_id=$1 AND d2.unit_id=u.unit_id AND d2.consumed=FALSE) as drafted_active,
         (SELECT 1 FROM player_units pu WHERE pu.user_id=$1 AND pu.unit_id=u.unit_id LIMIT 1) as recruited
@@ -275,6 +450,7 @@ pub async fn list_contract_status(
                 base_defense: r.base_defense,
                 base_health: r.base_health,
                 is_recruitable: r.is_recruitable,
+                is_ranged: r.is_ranged,
                 kind: r.kind,
                 rarity: r.rarity,
             },
@@ -295,7 +471,7 @@ pub async fn list_drafted_contracts(
     user_id: UserId,
 ) -> Result<Vec<DraftedHumanContract>, sqlx::Error> {
     let uid = user_id.get() as i64;
-    sqlx::query_as!(DraftedHumanContract, "SELECT user_id, unit_id, drafted_at, consumed FROM drafted_human_contracts WHERE user_id = $1 AND consumed = FALSE ORDER BY drafted_at DESC", uid).fetch_all(pool).await
+    sqlx::query_as!(DraftedHumanContract, "SELECT user_id, unit_id, drafted_at, consumed, wage, loot_share_pct FROM drafted_human_contracts WHERE user_id = $1 AND consumed = FALSE ORDER BY drafted_at DESC", uid).fetch_all(pool).await
 }
 
 /// Fetch legacy open contract offers (pre-drafting system). Activates HumanContractOffer struct for backward compatibility.
@@ -0,0 +1,55 @@
+//! Loads item definitions from the `items` table into an [`ItemRegistry`].
+
+use crate::commands::economy::core::item::{ItemCategory, Rarity};
+use crate::commands::economy::core::registry::{ItemDef, ItemRegistry};
+use sqlx::PgPool;
+
+/// Reads every item row. Rows with an unknown category or rarity are skipped with a warning
+/// so one bad edit cannot take the whole registry down.
+pub async fn load_item_registry(pool: &PgPool) -> Result<ItemRegistry, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT item_id, item_key, name, description, emoji, category, rarity, is_sellable,
+                  is_tradeable, buy_price, sell_price, aliases
+           FROM items ORDER BY item_id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut defs = Vec::with_capacity(rows.len());
+    for row in rows {
+        let (Some(category), Some(rarity)) = (
+            ItemCategory::parse(&row.category),
+            Rarity::parse(&row.rarity),
+        ) else {
+            tracing::warn!(target: "items", item_id = row.item_id, category = %row.category, rarity = %row.rarity, "Skipping item with unknown category or rarity");
+            continue;
+        };
+        defs.push(ItemDef {
+            id: row.item_id,
+            key: row.item_key,
+            name: row.name,
+            description: row.description.unwrap_or_default(),
+            emoji: row.emoji,
+            category,
+            rarity,
+            is_sellable: row.is_sellable,
+            is_tradeable: row.is_tradeable,
+            buy_price: row.buy_price,
+            sell_price: row.sell_price,
+            aliases: row.aliases,
+        });
+    }
+    Ok(ItemRegistry::new(defs))
+}
+
+/// Loads the registry from the database and installs it process-wide.
+/// Returns (previous item count, new item count). An empty table is treated as an error and
+/// keeps the current registry.
+pub async fn reload_item_registry(pool: &PgPool) -> Result<(usize, usize), sqlx::Error> {
+    let registry = load_item_registry(pool).await?;
+    if registry.is_empty() {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    let loaded = registry.len();
+    let previous = crate::commands::economy::core::registry::install(registry);
+    Ok((previous, loaded))
+}
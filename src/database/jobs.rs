@@ -0,0 +1,165 @@
+//! Database functions for data-defined jobs, job perks and per-player job progress.
+
+use super::economy::{add_balance, add_to_inventory};
+use super::models::{JobPerkKind, LedgerReason, WorkRewards};
+use crate::commands::economy::work::jobs::{JobDef, JobPerk, JobProgress};
+use chrono::Duration;
+use serenity::model::id::UserId;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+
+/// Every job, in display order, with its perk tree.
+pub async fn list_jobs(pool: &PgPool) -> Result<Vec<JobDef>, sqlx::Error> {
+    let mut perks: HashMap<String, Vec<JobPerk>> = HashMap::new();
+    let perk_rows = sqlx::query!(
+        r#"SELECT job_key, required_level, kind as "kind: JobPerkKind", value_pct, description
+           FROM job_perks ORDER BY job_key, required_level, perk_id"#
+    )
+    .fetch_all(pool)
+    .await?;
+    for row in perk_rows {
+        perks.entry(row.job_key).or_default().push(JobPerk {
+            required_level: row.required_level,
+            kind: row.kind,
+            value_pct: row.value_pct,
+            description: row.description,
+        });
+    }
+    let rows = sqlx::query!(
+        "SELECT job_key, display_name, emoji, min_payout, max_payout, cooldown_secs, xp_gain, resource_item_id, resource_min, resource_max, resource_level_divisor, rare_item_id, rare_chance_bp FROM jobs ORDER BY sort_order, job_key"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| JobDef {
+            perks: perks.remove(&r.job_key).unwrap_or_default(),
+            key: r.job_key,
+            display_name: r.display_name,
+            emoji: r.emoji,
+            min_payout: r.min_payout,
+            max_payout: r.max_payout,
+            cooldown: Duration::seconds(r.cooldown_secs as i64),
+            xp_gain: r.xp_gain,
+            resource_item_id: r.resource_item_id,
+            resource_min: r.resource_min,
+            resource_max: r.resource_max,
+            resource_level_divisor: r.resource_level_divisor,
+            rare_item_id: r.rare_item_id,
+            rare_chance_bp: r.rare_chance_bp.max(0) as u32,
+        })
+        .collect())
+}
+
+/// Finds a job by key or display name, ignoring case.
+pub async fn find_job(pool: &PgPool, name: &str) -> Result<Option<JobDef>, sqlx::Error> {
+    let name = name.trim();
+    Ok(list_jobs(pool)
+        .await?
+        .into_iter()
+        .find(|j| j.key.eq_ignore_ascii_case(name) || j.display_name.eq_ignore_ascii_case(name)))
+}
+
+/// The user's progress in every job they have worked, keyed by job key.
+pub async fn get_player_jobs(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<HashMap<String, JobProgress>, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT job_key, level, xp, last_worked_at FROM player_jobs WHERE user_id = $1",
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            (
+                r.job_key,
+                JobProgress {
+                    level: r.level,
+                    xp: r.xp,
+                    last_worked_at: r.last_worked_at,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Locks the user's row for a job (creating it at level 1) so two work commands at once cannot
+/// both pass the cooldown check. The profile must already exist.
+pub async fn lock_job_progress(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    job_key: &str,
+) -> Result<JobProgress, sqlx::Error> {
+    let user_i64 = user_id.get() as i64;
+    sqlx::query!(
+        "INSERT INTO player_jobs (user_id, job_key) VALUES ($1, $2) ON CONFLICT (user_id, job_key) DO NOTHING",
+        user_i64,
+        job_key
+    )
+    .execute(&mut **tx)
+    .await?;
+    let row = sqlx::query!(
+        "SELECT level, xp, last_worked_at FROM player_jobs WHERE user_id = $1 AND job_key = $2 FOR UPDATE",
+        user_i64,
+        job_key
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(JobProgress {
+        level: row.level,
+        xp: row.xp,
+        last_worked_at: row.last_worked_at,
+    })
+}
+
+/// Grants work rewards and saves the job's new level, XP and cooldown plus the daily streak.
+/// Expects the job row to be locked by `lock_job_progress` in the same transaction.
+pub async fn complete_work(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    job_key: &str,
+    rewards: &WorkRewards,
+    progress: JobProgress,
+    streak: i32,
+) -> Result<(), sqlx::Error> {
+    let user_i64 = user_id.get() as i64;
+    if rewards.coins != 0 {
+        add_balance(
+            tx,
+            user_id,
+            rewards.coins,
+            LedgerReason::Work.with_ref("job", job_key),
+        )
+        .await?;
+    }
+    for (item_id, quantity) in &rewards.items {
+        add_to_inventory(
+            tx,
+            user_id,
+            *item_id,
+            *quantity,
+            LedgerReason::Work.with_ref("job", job_key),
+        )
+        .await?;
+    }
+    sqlx::query!(
+        "UPDATE player_jobs SET level = $3, xp = $4, last_worked_at = NOW() WHERE user_id = $1 AND job_key = $2",
+        user_i64,
+        job_key,
+        progress.level,
+        progress.xp
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query!(
+        "UPDATE profiles SET last_work = NOW(), work_streak = $2 WHERE user_id = $1",
+        user_i64,
+        streak
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -75,3 +75,25 @@ pub async fn get_streak_leaderboard(
     .fetch_all(pool)
     .await
 }
+
+/// Fetches the top ranked Rock Paper Scissors ratings for `season`.
+pub async fn get_rps_leaderboard(
+    pool: &PgPool,
+    season: i32,
+    limit: i64,
+) -> Result<Vec<LeaderboardEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        LeaderboardEntry,
+        r#"
+        SELECT user_id, rating::BIGINT as "score!"
+        FROM rps_ratings
+        WHERE season = $1
+        ORDER BY rating DESC, wins DESC
+        LIMIT $2;
+        "#,
+        season,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,150 @@
+//! The economic ledger: an append-only audit trail of every coin and item movement.
+//!
+//! `economy::add_balance` and `economy::add_to_inventory` write an entry in the same transaction
+//! as the change they make, tagged with a `LedgerReason` and an optional source reference such as
+//! `listing:42`. Code that updates balances with its own query calls `record` instead.
+//! `find_mismatches` compares wallets, inventories and bank balances against their ledger sums.
+
+use super::models::{LedgerEntry, LedgerMismatch, LedgerReason};
+use serenity::model::id::UserId;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::fmt::Display;
+
+/// Why a balance or inventory changed and, optionally, what caused it (`kind:id`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerSource {
+    pub reason: LedgerReason,
+    pub source_ref: Option<String>,
+}
+
+impl From<LedgerReason> for LedgerSource {
+    fn from(reason: LedgerReason) -> Self {
+        Self {
+            reason,
+            source_ref: None,
+        }
+    }
+}
+
+impl LedgerReason {
+    /// Tags the reason with the record that caused it, e.g. `Market.with_ref("listing", 42)`.
+    pub fn with_ref(self, kind: &str, id: impl Display) -> LedgerSource {
+        LedgerSource {
+            reason: self,
+            source_ref: Some(format!("{}:{}", kind, id)),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LedgerReason::Opening => "opening balance",
+            LedgerReason::Work => "work",
+            LedgerReason::Shop => "shop",
+            LedgerReason::Sell => "sell",
+            LedgerReason::Give => "give",
+            LedgerReason::Trade => "trade",
+            LedgerReason::Market => "market",
+            LedgerReason::Tavern => "tavern",
+            LedgerReason::Recruit => "recruit",
+            LedgerReason::Bond => "bond",
+            LedgerReason::Battle => "battle",
+            LedgerReason::Quest => "quest",
+            LedgerReason::Task => "task",
+            LedgerReason::Contract => "contract",
+            LedgerReason::Crafting => "crafting",
+            LedgerReason::Loot => "loot",
+            LedgerReason::Gear => "gear",
+            LedgerReason::Upkeep => "upkeep",
+            LedgerReason::Bank => "bank",
+            LedgerReason::Game => "game",
+            LedgerReason::ItemUse => "item use",
+            LedgerReason::Tournament => "tournament",
+        }
+    }
+}
+
+/// Records a change already applied by the caller inside `tx`. `item_id` is `None` for coins.
+pub async fn record(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    item_id: Option<i32>,
+    delta: i64,
+    balance_after: i64,
+    source: &LedgerSource,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO ledger (user_id, item_id, delta, balance_after, reason, source_ref) VALUES ($1, $2, $3, $4, $5, $6)",
+        user_id.get() as i64,
+        item_id,
+        delta,
+        balance_after,
+        source.reason as LedgerReason,
+        source.source_ref
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Most recent entries for a user, newest first.
+pub async fn recent_entries(
+    pool: &PgPool,
+    user_id: UserId,
+    limit: i64,
+) -> Result<Vec<LedgerEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        LedgerEntry,
+        r#"SELECT item_id, delta, balance_after, reason as "reason: LedgerReason", source_ref, created_at
+        FROM ledger WHERE user_id = $1 ORDER BY entry_id DESC LIMIT $2"#,
+        user_id.get() as i64,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Wallets, inventory rows and bank balances that differ from their ledger sums, for one user or
+/// (with `None`) everyone.
+pub async fn find_mismatches(
+    pool: &PgPool,
+    user_id: Option<UserId>,
+) -> Result<Vec<LedgerMismatch>, sqlx::Error> {
+    let user_filter = user_id.map(|u| u.get() as i64);
+    sqlx::query_as!(
+        LedgerMismatch,
+        r#"WITH wallet_sums AS (
+            SELECT user_id, SUM(delta)::BIGINT AS total FROM ledger
+            WHERE item_id IS NULL AND ($1::BIGINT IS NULL OR user_id = $1)
+            GROUP BY user_id
+        ), item_sums AS (
+            SELECT user_id, item_id, SUM(delta)::BIGINT AS total FROM ledger
+            WHERE item_id IS NOT NULL AND ($1::BIGINT IS NULL OR user_id = $1)
+            GROUP BY user_id, item_id
+        ), inv AS (
+            SELECT user_id, item_id, quantity FROM inventories
+            WHERE $1::BIGINT IS NULL OR user_id = $1
+        ), bank_sums AS (
+            SELECT user_id, SUM(bank_delta)::BIGINT AS total FROM bank_ledger
+            WHERE $1::BIGINT IS NULL OR user_id = $1
+            GROUP BY user_id
+        )
+        SELECT p.user_id as "user_id!", NULL::INT as item_id, 'wallet' as "account!",
+            p.balance as "actual!", COALESCE(w.total, 0) as "expected!"
+        FROM profiles p LEFT JOIN wallet_sums w ON w.user_id = p.user_id
+        WHERE ($1::BIGINT IS NULL OR p.user_id = $1) AND p.balance <> COALESCE(w.total, 0)
+        UNION ALL
+        SELECT COALESCE(i.user_id, s.user_id), COALESCE(i.item_id, s.item_id), 'inventory',
+            COALESCE(i.quantity, 0), COALESCE(s.total, 0)
+        FROM inv i FULL OUTER JOIN item_sums s ON s.user_id = i.user_id AND s.item_id = i.item_id
+        WHERE COALESCE(i.quantity, 0) <> COALESCE(s.total, 0)
+            AND EXISTS (SELECT 1 FROM profiles p WHERE p.user_id = COALESCE(i.user_id, s.user_id))
+        UNION ALL
+        SELECT a.user_id, NULL::INT, 'bank', a.balance, COALESCE(b.total, 0)
+        FROM bank_accounts a LEFT JOIN bank_sums b ON b.user_id = a.user_id
+        WHERE ($1::BIGINT IS NULL OR a.user_id = $1) AND a.balance <> COALESCE(b.total, 0)
+        ORDER BY 1, 3, 2"#,
+        user_filter
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -0,0 +1,223 @@
+//! Database functions for container loot tables, pity counters and open history.
+
+use super::economy::{add_balance, add_to_inventory, get_or_create_profile};
+use super::models::LedgerReason;
+use crate::commands::economy::core::loot::{
+    LootEntry, LootMode, LootRoll, LootTable, next_pity_misses,
+};
+use crate::commands::economy::core::registry::ItemDef;
+use chrono::{DateTime, Utc};
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+
+/// Most containers opened by a single command.
+pub const MAX_OPENS_PER_COMMAND: i64 = 10;
+
+/// Loot table opened by consuming `item_id`, if the item is a container.
+pub async fn get_container_table(
+    pool: &PgPool,
+    item_id: i32,
+) -> Result<Option<LootTable>, sqlx::Error> {
+    let Some(table) = sqlx::query!(
+        "SELECT table_key, rolls, pity_threshold FROM loot_tables WHERE container_item_id = $1",
+        item_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let entries = sqlx::query!(
+        "SELECT item_id, min_quantity, max_quantity, weight, is_rare FROM loot_table_entries WHERE table_key = $1 ORDER BY entry_id",
+        table.table_key
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|e| LootEntry {
+        item_id: e.item_id,
+        min_quantity: e.min_quantity,
+        max_quantity: e.max_quantity,
+        weight: e.weight.max(0) as u32,
+        is_rare: e.is_rare,
+    })
+    .collect();
+    Ok(Some(LootTable {
+        key: table.table_key,
+        mode: LootMode::Weighted,
+        rolls: table.rolls.max(1) as u32,
+        pity_threshold: table.pity_threshold.map(|t| t.max(0) as u32),
+        entries,
+    }))
+}
+
+pub struct OpenOutcome {
+    pub rolls: Vec<LootRoll>,
+    /// Opens left until the pity guarantee, after this batch.
+    pub opens_until_pity: Option<u32>,
+}
+
+/// Consumes `count` containers and grants their loot in one transaction. The pity row is locked
+/// so two opens at once cannot both use the same guarantee.
+pub async fn open_containers(
+    pool: &PgPool,
+    user_id: UserId,
+    container: &ItemDef,
+    count: i64,
+) -> Result<OpenOutcome, String> {
+    if !(1..=MAX_OPENS_PER_COMMAND).contains(&count) {
+        return Err(format!(
+            "You can open between 1 and {} at a time.",
+            MAX_OPENS_PER_COMMAND
+        ));
+    }
+    let table = get_container_table(pool, container.id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("**{}** cannot be opened.", container.name))?;
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let user_i64 = user_id.get() as i64;
+
+    let source = LedgerReason::Loot.with_ref("table", &table.key);
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    if add_to_inventory(&mut tx, user_id, container.id, -count, source.clone())
+        .await
+        .is_err()
+    {
+        return Err(format!(
+            "You don't have `{}` **{}** to open.",
+            count, container.name
+        ));
+    }
+    sqlx::query!(
+        "INSERT INTO loot_pity (user_id, table_key) VALUES ($1, $2) ON CONFLICT (user_id, table_key) DO NOTHING",
+        user_i64,
+        table.key
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let misses = sqlx::query_scalar!(
+        "SELECT misses FROM loot_pity WHERE user_id = $1 AND table_key = $2 FOR UPDATE",
+        user_i64,
+        table.key
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .max(0) as u32;
+
+    // Roll everything up front; the RNG must not be held across an await.
+    let (rolls, misses) = {
+        let mut rng = rand::rng();
+        let mut misses = misses;
+        let mut rolls = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let roll = table.roll(&mut rng, misses);
+            misses = next_pity_misses(misses, &roll);
+            rolls.push(roll);
+        }
+        (rolls, misses)
+    };
+
+    let mut items: BTreeMap<i32, i64> = BTreeMap::new();
+    for roll in &rolls {
+        for drop in &roll.drops {
+            if let Some(id) = drop.item_id {
+                *items.entry(id).or_insert(0) += drop.quantity;
+            }
+        }
+        let (item_ids, quantities): (Vec<i32>, Vec<i64>) = roll
+            .drops
+            .iter()
+            .filter_map(|d| d.item_id.map(|id| (id, d.quantity)))
+            .unzip();
+        sqlx::query!(
+            "INSERT INTO loot_history (user_id, table_key, coins, item_ids, quantities, got_rare, pity_used) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            user_i64,
+            table.key,
+            roll.coins(),
+            &item_ids,
+            &quantities,
+            roll.got_rare(),
+            roll.pity_used
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    let coins: i64 = rolls.iter().map(LootRoll::coins).sum();
+    if coins > 0 {
+        add_balance(&mut tx, user_id, coins, source.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    for (item_id, quantity) in items {
+        add_to_inventory(&mut tx, user_id, item_id, quantity, source.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    sqlx::query!(
+        "UPDATE loot_pity SET misses = $3 WHERE user_id = $1 AND table_key = $2",
+        user_i64,
+        table.key,
+        misses as i32
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(OpenOutcome {
+        opens_until_pity: table.opens_until_pity(misses),
+        rolls,
+    })
+}
+
+pub struct LootHistoryEntry {
+    pub container_item_id: Option<i32>,
+    pub coins: i64,
+    pub item_ids: Vec<i32>,
+    pub quantities: Vec<i64>,
+    pub got_rare: bool,
+    pub pity_used: bool,
+    pub opened_at: DateTime<Utc>,
+}
+
+/// The user's most recent opens, newest first.
+pub async fn recent_history(
+    pool: &PgPool,
+    user_id: UserId,
+    limit: i64,
+) -> Result<Vec<LootHistoryEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        LootHistoryEntry,
+        r#"SELECT t.container_item_id as "container_item_id?", h.coins, h.item_ids, h.quantities, h.got_rare, h.pity_used, h.opened_at
+           FROM loot_history h LEFT JOIN loot_tables t ON t.table_key = h.table_key
+           WHERE h.user_id = $1 ORDER BY h.opened_at DESC, h.history_id DESC LIMIT $2"#,
+        user_id.get() as i64,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// (container item id, opens until the pity guarantee) for every table the user has opened.
+pub async fn pity_status(pool: &PgPool, user_id: UserId) -> Result<Vec<(i32, i32)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT t.container_item_id as "container_item_id!", t.pity_threshold as "pity_threshold!", p.misses
+           FROM loot_pity p JOIN loot_tables t ON t.table_key = p.table_key
+           WHERE p.user_id = $1 AND t.container_item_id IS NOT NULL AND t.pity_threshold IS NOT NULL
+           ORDER BY t.table_key"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.container_item_id, (r.pity_threshold - r.misses).max(1)))
+        .collect())
+}
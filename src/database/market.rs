@@ -0,0 +1,411 @@
+//! Database functions for the player marketplace.
+//!
+//! Listing an item moves it out of the seller's inventory into the listing (escrow). A buyout
+//! or auction settlement moves it to the buyer and pays the seller minus the market tax, all in
+//! one transaction. Auction bids are taken from the bidder's balance immediately and refunded
+//! when outbid, so a settled auction never fails for lack of funds.
+
+use super::economy::{add_balance, add_to_inventory, get_or_create_profile};
+use super::models::{LedgerReason, MarketListingStatus};
+use super::settings::get_config_value;
+use crate::commands::economy::core::registry::{ItemDef, registry};
+use crate::commands::economy::market::logic::{
+    self, LISTING_DURATION_HOURS, LISTINGS_PER_PAGE, MARKET_TAX_CONFIG_KEY, MAX_ACTIVE_LISTINGS,
+    MAX_AUCTION_HOURS, MIN_AUCTION_HOURS,
+};
+use chrono::{DateTime, Duration, Utc};
+use serenity::model::id::UserId;
+use sqlx::{PgPool, Postgres, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct MarketListing {
+    pub listing_id: i32,
+    pub seller_id: i64,
+    pub item_id: i32,
+    pub quantity: i64,
+    pub price: i64,
+    pub is_auction: bool,
+    pub high_bid: Option<i64>,
+    pub high_bidder_id: Option<i64>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl MarketListing {
+    pub fn min_next_bid(&self) -> i64 {
+        logic::min_next_bid(self.price, self.high_bid)
+    }
+}
+
+/// Result of `/market buy` on a listing.
+#[derive(Debug, Clone)]
+pub enum PurchaseOutcome {
+    Bought {
+        item_name: String,
+        quantity: i64,
+        price: i64,
+    },
+    BidPlaced {
+        item_name: String,
+        amount: i64,
+        expires_at: DateTime<Utc>,
+    },
+}
+
+/// Current market tax percentage (default when unset or invalid).
+pub async fn get_market_tax_pct(pool: &PgPool) -> i64 {
+    let raw = get_config_value(pool, MARKET_TAX_CONFIG_KEY)
+        .await
+        .ok()
+        .flatten();
+    logic::parse_tax_pct(raw.as_deref())
+}
+
+async fn lock_listing(
+    tx: &mut Transaction<'_, Postgres>,
+    listing_id: i32,
+) -> Result<Option<MarketListing>, sqlx::Error> {
+    sqlx::query_as!(
+        MarketListing,
+        "SELECT listing_id, seller_id, item_id, quantity, price, is_auction, high_bid, high_bidder_id, expires_at FROM market_listings WHERE listing_id = $1 AND status = 'Active' FOR UPDATE",
+        listing_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn close_listing(
+    tx: &mut Transaction<'_, Postgres>,
+    listing_id: i32,
+    status: MarketListingStatus,
+    buyer_id: Option<i64>,
+    sold_price: Option<i64>,
+    tax_paid: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE market_listings SET status = $2, buyer_id = $3, sold_price = $4, tax_paid = $5, closed_at = NOW() WHERE listing_id = $1",
+        listing_id,
+        status as _,
+        buyer_id,
+        sold_price,
+        tax_paid
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Settles one expired listing inside `tx`: auctions with a bid go to the high bidder,
+/// everything else returns to the seller.
+async fn settle_listing(
+    tx: &mut Transaction<'_, Postgres>,
+    listing: &MarketListing,
+    tax_pct: i64,
+) -> Result<(), sqlx::Error> {
+    let seller = UserId::new(listing.seller_id as u64);
+    let source = LedgerReason::Market.with_ref("listing", listing.listing_id);
+    match (listing.high_bid, listing.high_bidder_id) {
+        (Some(bid), Some(bidder)) if listing.is_auction => {
+            let tax = logic::market_tax(bid, tax_pct);
+            add_to_inventory(
+                tx,
+                UserId::new(bidder as u64),
+                listing.item_id,
+                listing.quantity,
+                source.clone(),
+            )
+            .await?;
+            add_balance(tx, seller, logic::seller_proceeds(bid, tax_pct), source).await?;
+            close_listing(
+                tx,
+                listing.listing_id,
+                MarketListingStatus::Sold,
+                Some(bidder),
+                Some(bid),
+                Some(tax),
+            )
+            .await
+        }
+        _ => {
+            add_to_inventory(tx, seller, listing.item_id, listing.quantity, source).await?;
+            close_listing(
+                tx,
+                listing.listing_id,
+                MarketListingStatus::Expired,
+                None,
+                None,
+                None,
+            )
+            .await
+        }
+    }
+}
+
+/// Settles every listing past its expiry. Called lazily before any market read or write, so
+/// no background scheduler is needed. Returns the number of listings settled.
+pub async fn settle_expired_listings(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let tax_pct = get_market_tax_pct(pool).await;
+    let mut settled = 0;
+    loop {
+        let mut tx = pool.begin().await?;
+        let Some(listing) = sqlx::query_as!(
+            MarketListing,
+            "SELECT listing_id, seller_id, item_id, quantity, price, is_auction, high_bid, high_bidder_id, expires_at FROM market_listings WHERE status = 'Active' AND expires_at <= NOW() ORDER BY expires_at LIMIT 1 FOR UPDATE SKIP LOCKED"
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.rollback().await.ok();
+            break;
+        };
+        settle_listing(&mut tx, &listing, tax_pct).await?;
+        tx.commit().await?;
+        settled += 1;
+    }
+    if settled > 0 {
+        tracing::info!(target: "market", settled, "Settled expired market listings");
+    }
+    Ok(settled)
+}
+
+pub async fn count_active_listings(pool: &PgPool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM market_listings WHERE status = 'Active' AND expires_at > NOW()"#
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// One browse page (0-based), soonest-ending first.
+pub async fn list_active_listings(
+    pool: &PgPool,
+    page: i64,
+) -> Result<Vec<MarketListing>, sqlx::Error> {
+    sqlx::query_as!(
+        MarketListing,
+        "SELECT listing_id, seller_id, item_id, quantity, price, is_auction, high_bid, high_bidder_id, expires_at FROM market_listings WHERE status = 'Active' AND expires_at > NOW() ORDER BY expires_at, listing_id LIMIT $1 OFFSET $2",
+        LISTINGS_PER_PAGE,
+        page.max(0) * LISTINGS_PER_PAGE
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Lists `quantity` of `item` at `price`. With `auction_hours` the listing is an auction and
+/// `price` is the starting bid. Returns the new listing id.
+pub async fn create_listing(
+    pool: &PgPool,
+    seller: UserId,
+    item: &ItemDef,
+    quantity: i64,
+    price: i64,
+    auction_hours: Option<i64>,
+) -> Result<i32, String> {
+    if !item.is_tradeable {
+        return Err(format!("**{}** cannot be traded.", item.name));
+    }
+    if quantity <= 0 || price <= 0 {
+        return Err("Quantity and price must be at least 1.".into());
+    }
+    if let Some(hours) = auction_hours
+        && !(MIN_AUCTION_HOURS..=MAX_AUCTION_HOURS).contains(&hours)
+    {
+        return Err(format!(
+            "Auctions run between {} and {} hours.",
+            MIN_AUCTION_HOURS, MAX_AUCTION_HOURS
+        ));
+    }
+    let seller_i64 = seller.get() as i64;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    // Lock the seller's profile so concurrent listings cannot exceed the cap.
+    sqlx::query!(
+        "SELECT user_id FROM profiles WHERE user_id = $1 FOR UPDATE",
+        seller_i64
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .ok_or("You need a profile before trading. Try `/work` first.")?;
+    let active = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM market_listings WHERE seller_id = $1 AND status = 'Active'"#,
+        seller_i64
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if active >= MAX_ACTIVE_LISTINGS {
+        return Err(format!(
+            "You already have {} active listings. Cancel one or wait for it to sell.",
+            MAX_ACTIVE_LISTINGS
+        ));
+    }
+    let expires_at = Utc::now() + Duration::hours(auction_hours.unwrap_or(LISTING_DURATION_HOURS));
+    let listing_id = sqlx::query_scalar!(
+        "INSERT INTO market_listings (seller_id, item_id, quantity, price, is_auction, expires_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING listing_id",
+        seller_i64,
+        item.id,
+        quantity,
+        price,
+        auction_hours.is_some(),
+        expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if add_to_inventory(
+        &mut tx,
+        seller,
+        item.id,
+        -quantity,
+        LedgerReason::Market.with_ref("listing", listing_id),
+    )
+    .await
+    .is_err()
+    {
+        return Err(format!(
+            "You don't have `{}` **{}** to list.",
+            quantity, item.name
+        ));
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(listing_id)
+}
+
+/// Buys a fixed-price listing outright, or places a bid on an auction (`bid` defaults to the
+/// minimum next bid).
+pub async fn buy_listing(
+    pool: &PgPool,
+    buyer: UserId,
+    listing_id: i32,
+    bid: Option<i64>,
+) -> Result<PurchaseOutcome, String> {
+    get_or_create_profile(pool, buyer)
+        .await
+        .map_err(|_| "Could not load your profile.".to_string())?;
+    let tax_pct = get_market_tax_pct(pool).await;
+    let buyer_i64 = buyer.get() as i64;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let listing = lock_listing(&mut tx, listing_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|l| l.expires_at > Utc::now())
+        .ok_or_else(|| format!("Listing #{} is no longer available.", listing_id))?;
+    if listing.seller_id == buyer_i64 {
+        return Err("You cannot buy your own listing.".into());
+    }
+    let item_name = registry().name_of(listing.item_id);
+    let source = LedgerReason::Market.with_ref("listing", listing.listing_id);
+
+    if !listing.is_auction {
+        if add_balance(&mut tx, buyer, -listing.price, source.clone())
+            .await
+            .is_err()
+        {
+            return Err(format!(
+                "You need **💰{}** to buy this listing.",
+                listing.price
+            ));
+        }
+        let tax = logic::market_tax(listing.price, tax_pct);
+        add_balance(
+            &mut tx,
+            UserId::new(listing.seller_id as u64),
+            logic::seller_proceeds(listing.price, tax_pct),
+            source.clone(),
+        )
+        .await
+        .map_err(|_| "Failed to pay the seller.".to_string())?;
+        add_to_inventory(&mut tx, buyer, listing.item_id, listing.quantity, source)
+            .await
+            .map_err(|_| "Failed to deliver the items.".to_string())?;
+        close_listing(
+            &mut tx,
+            listing.listing_id,
+            MarketListingStatus::Sold,
+            Some(buyer_i64),
+            Some(listing.price),
+            Some(tax),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        tx.commit().await.map_err(|e| e.to_string())?;
+        return Ok(PurchaseOutcome::Bought {
+            item_name,
+            quantity: listing.quantity,
+            price: listing.price,
+        });
+    }
+
+    let minimum = listing.min_next_bid();
+    let amount = bid.unwrap_or(minimum);
+    if amount < minimum {
+        return Err(format!("The minimum bid is **💰{}**.", minimum));
+    }
+    // Refund the previous high bid (possibly the buyer's own) before escrowing the new one.
+    if let (Some(prev_bid), Some(prev_bidder)) = (listing.high_bid, listing.high_bidder_id) {
+        add_balance(
+            &mut tx,
+            UserId::new(prev_bidder as u64),
+            prev_bid,
+            source.clone(),
+        )
+        .await
+        .map_err(|_| "Failed to refund the previous bidder.".to_string())?;
+    }
+    if add_balance(&mut tx, buyer, -amount, source).await.is_err() {
+        return Err(format!("You need **💰{}** to place this bid.", amount));
+    }
+    sqlx::query!(
+        "UPDATE market_listings SET high_bid = $2, high_bidder_id = $3 WHERE listing_id = $1",
+        listing.listing_id,
+        amount,
+        buyer_i64
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(PurchaseOutcome::BidPlaced {
+        item_name,
+        amount,
+        expires_at: listing.expires_at,
+    })
+}
+
+/// Cancels the seller's own listing and returns the escrowed items. Auctions with a bid can
+/// no longer be cancelled.
+pub async fn cancel_listing(
+    pool: &PgPool,
+    seller: UserId,
+    listing_id: i32,
+) -> Result<MarketListing, String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let listing = lock_listing(&mut tx, listing_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|l| l.seller_id == seller.get() as i64)
+        .ok_or_else(|| format!("You have no active listing #{}.", listing_id))?;
+    if listing.high_bid.is_some() {
+        return Err("This auction already has a bid and can no longer be cancelled.".into());
+    }
+    add_to_inventory(
+        &mut tx,
+        seller,
+        listing.item_id,
+        listing.quantity,
+        LedgerReason::Market.with_ref("listing", listing.listing_id),
+    )
+    .await
+    .map_err(|_| "Failed to return your items.".to_string())?;
+    close_listing(
+        &mut tx,
+        listing.listing_id,
+        MarketListingStatus::Cancelled,
+        None,
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(listing)
+}
@@ -4,16 +4,33 @@
 //!
 //! NOTE: Legacy `pets` module has been deprecated; all logic consolidated into `units`.
 
+pub mod bank;
 pub mod battle;
 pub mod crafting;
 pub mod economy;
+pub mod gambling;
+pub mod gear;
 pub mod human;
+pub mod items;
+pub mod jobs;
 pub mod leaderboard;
+pub mod ledger;
+pub mod loot;
+pub mod market;
 pub mod models;
+pub mod presets;
+pub mod pricing;
 pub mod quests;
+pub mod research;
+pub mod rps_ladder;
 pub mod saga;
 pub mod settings;
+pub mod side_bets;
+pub mod table_stacks;
 pub mod tasks;
 pub mod tavern;
+pub mod tournaments;
+pub mod trade;
 pub mod units; // final home
+pub mod upkeep;
 pub mod world;
@@ -1,20 +1,13 @@
 //! Contains all the data structures that map to database tables or query results.
 
-use crate::commands::economy::core::item::Item;
 use sqlx::Type;
-use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::types::chrono::{DateTime, NaiveDate, Utc};
 
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct Profile {
     pub balance: i64,
     pub last_work: Option<DateTime<Utc>>,
     pub work_streak: i32,
-    pub fishing_xp: i64,
-    pub fishing_level: i32,
-    pub mining_xp: i64,
-    pub mining_level: i32,
-    pub coding_xp: i64,
-    pub coding_level: i32,
 }
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct InventoryItem {
@@ -39,6 +32,8 @@ pub struct Unit {
     pub base_defense: i32,
     pub base_health: i32,
     pub is_recruitable: bool,
+    // Ranged attackers keep (and slightly improve) their damage from the back row.
+    pub is_ranged: bool,
     pub kind: UnitKind,
     // Rarity tier for the unit which gates equippable bonding & (for pets) party eligibility.
     pub rarity: UnitRarity,
@@ -62,6 +57,20 @@ pub struct PlayerUnit {
     pub rarity: UnitRarity,
 }
 
+// Named party preset (members stored in party_preset_members).
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PartyPreset {
+    pub preset_id: i32,
+    pub name: String,
+}
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct PartyPresetMember {
+    pub preset_id: i32,
+    pub player_unit_id: i32,
+    pub bond_id: Option<i32>,
+    pub unit_name: Option<String>, // None when the saved unit has since left the army
+}
+
 // -------------------------------------------------------------------------------------------------
 // Rarity & Equippables
 // -------------------------------------------------------------------------------------------------
@@ -87,6 +96,159 @@ pub enum UnitKind {
     Pet,
 }
 
+// Party formation row. Front row units are targeted first; back row units only once the front
+// row has fallen (see saga::battle::logic).
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[sqlx(type_name = "formation_row", rename_all = "PascalCase")]
+pub enum FormationRow {
+    #[default]
+    Front,
+    Back,
+}
+
+// Equipment slot on a player unit. Each unit holds at most one item per slot.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[sqlx(type_name = "gear_slot", rename_all = "PascalCase")]
+pub enum GearSlot {
+    Weapon,
+    Armor,
+    Trinket,
+}
+
+impl GearSlot {
+    pub const ALL: [GearSlot; 3] = [GearSlot::Weapon, GearSlot::Armor, GearSlot::Trinket];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GearSlot::Weapon => "Weapon",
+            GearSlot::Armor => "Armor",
+            GearSlot::Trinket => "Trinket",
+        }
+    }
+    pub fn icon(self) -> &'static str {
+        match self {
+            GearSlot::Weapon => "🗡️",
+            GearSlot::Armor => "🛡️",
+            GearSlot::Trinket => "🧿",
+        }
+    }
+}
+
+// An item equipped on a player unit, joined with its stats.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct UnitGear {
+    pub player_unit_id: i32,
+    pub slot: GearSlot,
+    pub item_id: i32,
+    pub durability: i32,
+    pub max_durability: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub health: i32,
+}
+
+// Lifecycle of a marketplace listing. Only Active listings hold items (and bids) in escrow.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "market_listing_status", rename_all = "PascalCase")]
+pub enum MarketListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+    Expired,
+}
+
+// Why a wallet balance or inventory row changed. See `database::ledger`.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "ledger_reason", rename_all = "PascalCase")]
+pub enum LedgerReason {
+    Opening,
+    Work,
+    Shop,
+    Sell,
+    Give,
+    Trade,
+    Market,
+    Tavern,
+    Recruit,
+    Bond,
+    Battle,
+    Quest,
+    Task,
+    Contract,
+    Crafting,
+    Loot,
+    Gear,
+    Upkeep,
+    Bank,
+    Game,
+    ItemUse,
+    Tournament,
+}
+
+// Which card game a tournament is played in.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "tournament_game", rename_all = "PascalCase")]
+pub enum TournamentGame {
+    Holdem,
+    Blackjack,
+}
+
+// Which game a gambling result is recorded under.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "gamble_game", rename_all = "PascalCase")]
+pub enum GambleGame {
+    Blackjack,
+    Poker,
+    Holdem,
+    Rps,
+    Tavern,
+    SideBet,
+}
+
+// Lifecycle of a tournament. Buy-ins stay in the prize pool until it is Finished or Cancelled.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "tournament_status", rename_all = "PascalCase")]
+pub enum TournamentStatus {
+    Registering,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+// What a bank ledger entry records. See `commands::economy::bank::logic` for the rules.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "bank_entry_kind", rename_all = "PascalCase")]
+pub enum BankEntryKind {
+    Deposit,
+    Withdrawal,
+    Interest,
+    LoanIssued,
+    LoanPayment,
+    LoanPenalty,
+    LoanDefault,
+}
+
+impl FormationRow {
+    pub fn toggled(self) -> Self {
+        match self {
+            FormationRow::Front => FormationRow::Back,
+            FormationRow::Back => FormationRow::Front,
+        }
+    }
+    pub fn label(self) -> &'static str {
+        match self {
+            FormationRow::Front => "Front",
+            FormationRow::Back => "Back",
+        }
+    }
+    pub fn icon(self) -> &'static str {
+        match self {
+            FormationRow::Front => "🛡️",
+            FormationRow::Back => "🏹",
+        }
+    }
+}
+
 // Represents a special unit that, once bonded, becomes an equippable augment to another (host) unit.
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct EquippableUnitBond {
@@ -123,6 +285,9 @@ pub struct DraftedHumanContract {
     pub unit_id: i32,
     pub drafted_at: DateTime<Utc>,
     pub consumed: bool,
+    // Currently offered terms (NULL for drafts made before negotiation existed).
+    pub wage: Option<i64>,
+    pub loot_share_pct: Option<i32>,
 }
 
 #[derive(sqlx::FromRow, Debug, Clone)]
@@ -146,25 +311,56 @@ pub struct NodeReward {
 pub struct WorkRewards {
     pub coins: i64,
     pub xp: i64,
-    pub items: Vec<(Item, i64)>,
+    /// (item id, quantity)
+    pub items: Vec<(i32, i64)>,
 }
-#[allow(dead_code)]
-pub struct ProgressionUpdate {
-    pub job_name: String,
-    pub new_level: i32,
-    pub new_xp: i64,
+// What a job perk does once the player reaches its level. See `work::jobs::PerkEffects`.
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "job_perk_kind", rename_all = "PascalCase")]
+pub enum JobPerkKind {
+    DoubleResource,
+    BonusCoins,
+    CooldownReduction,
+    RareChance,
 }
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct Recipe {
     pub recipe_id: i32,
     pub output_item_id: i32,
     pub output_quantity: i32,
+    pub craft_secs: i32,
+    pub xp_reward: i64,
+    pub required_level: i32,
+    pub masterwork_item_id: Option<i32>,
 }
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct RecipeIngredient {
     pub item_id: i32,
     pub quantity: i32,
 }
+#[derive(sqlx::FromRow, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CraftingSkill {
+    pub level: i32,
+    pub xp: i64,
+}
+impl Default for CraftingSkill {
+    fn default() -> Self {
+        Self { level: 1, xp: 0 }
+    }
+}
+// A queued (or recently completed) craft. The counts are filled in on completion.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct QueuedCraft {
+    pub output_item_id: i32,
+    pub quantity: i32,
+    pub starts_at: DateTime<Utc>,
+    pub completes_at: DateTime<Utc>,
+    pub standard_count: i32,
+    pub fine_count: i32,
+    pub masterwork_count: i32,
+    pub saved_count: i32,
+    pub xp_gained: i64,
+}
 
 // --- Task System ---
 #[derive(Debug, Clone, Copy, Type, PartialEq)]
@@ -259,3 +455,115 @@ pub struct QuestDetails {
     pub giver_name: String,
     pub difficulty: String,
 }
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct BankAccount {
+    pub balance: i64,
+    pub rank_since: DateTime<Utc>,
+    pub interest_paid_on: NaiveDate,
+    pub credit_blocked_until: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct BankLoan {
+    pub loan_id: i32,
+    pub principal: i64,
+    pub total_due: i64,
+    pub repaid: i64,
+    pub installment: i64,
+    pub next_due_at: DateTime<Utc>,
+    pub missed_payments: i32,
+}
+
+impl BankLoan {
+    pub fn outstanding(&self) -> i64 {
+        (self.total_due - self.repaid).max(0)
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct BankLedgerEntry {
+    pub kind: BankEntryKind,
+    pub wallet_delta: i64,
+    pub bank_delta: i64,
+    pub bank_balance: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct LedgerEntry {
+    pub item_id: Option<i32>,
+    pub delta: i64,
+    pub balance_after: i64,
+    pub reason: LedgerReason,
+    pub source_ref: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A wallet, inventory row or bank balance that no longer matches its ledger sum.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct LedgerMismatch {
+    pub user_id: i64,
+    /// Set for `inventory` mismatches only.
+    pub item_id: Option<i32>,
+    /// `"wallet"`, `"inventory"` or `"bank"`.
+    pub account: String,
+    pub actual: i64,
+    pub expected: i64,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct GamblingStat {
+    pub game: GambleGame,
+    pub hands_played: i64,
+    pub hands_won: i64,
+    pub hands_lost: i64,
+    pub net: i64,
+    pub biggest_win: i64,
+}
+
+/// Limits a player has set on their own gambling.
+#[derive(sqlx::FromRow, Debug, Clone, Default)]
+pub struct GamblingLimits {
+    pub daily_loss_limit: Option<i64>,
+    pub pending_loss_limit: Option<i64>,
+    /// When the pending limit replaces the current one; `None` if no change is queued.
+    pub pending_from: Option<DateTime<Utc>>,
+    pub cooldown_until: Option<DateTime<Utc>>,
+}
+
+/// A player's ranked Rock Paper Scissors record for one season.
+#[derive(sqlx::FromRow, Debug, Clone, Copy)]
+pub struct RpsRating {
+    pub season: i32,
+    pub rating: i32,
+    pub peak: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub brackets_won: i32,
+}
+
+impl RpsRating {
+    pub fn games(&self) -> i32 {
+        self.wins + self.losses
+    }
+}
+
+/// A player waiting in the ranked matchmaking queue.
+#[derive(sqlx::FromRow, Debug, Clone, Copy)]
+pub struct RpsQueueEntry {
+    pub user_id: i64,
+    pub channel_id: i64,
+    pub rating: i32,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// A spectator's open side bet on one player in a running game.
+#[derive(sqlx::FromRow, Debug, Clone, Copy)]
+pub struct SideBet {
+    pub user_id: i64,
+    pub backed_id: i64,
+    pub stake: i64,
+    /// Decimal odds in hundredths, fixed when the bet was placed.
+    pub odds: i32,
+}
@@ -230,7 +230,7 @@ pub async fn hire_unit(
 ) -> Result<String, String> {
     let user_id_i64 = user_id.get() as i64;
     let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
-    let profile = sqlx::query_as!(Profile, "SELECT balance, last_work, work_streak, fishing_xp, fishing_level, mining_xp, mining_level, coding_xp, coding_level FROM profiles WHERE user_id = $1 FOR UPDATE", user_id_i64).fetch_one(&mut *tx).await.map_err(|_| "Could not find your profile.".to_string())?;
+    let profile = sqlx::query_as!(Profile, "SELECT balance, last_work, work_streak FROM profiles WHERE user_id = $1 FOR UPDATE", user_id_i64).fetch_one(&mut *tx).await.map_err(|_| "Could not find your profile.".to_string())?;
     if profile.balance < cost {
         tx.rollback().await.ok();
         return Err("You don't have enough coins.".to_string());
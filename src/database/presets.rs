@@ -0,0 +1,363 @@
+//! Named party presets: save the current party (members + equipped bonds) under a name and swap
+//! it back in atomically later (including formation rows). Loading validates against the live
+//! army, training & bond state.
+
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use tracing::instrument;
+
+use super::models::{FormationRow, PartyPreset, PartyPresetMember, UnitRarity};
+use crate::constants::{MAX_PARTY_PRESETS, MAX_PARTY_SIZE, MAX_PRESET_NAME_LEN};
+
+/// Summary returned after a successful preset swap (used for the confirmation line).
+#[derive(Debug, Clone)]
+pub struct PresetLoadOutcome {
+    pub name: String,
+    pub members: usize,
+    pub bonds_restored: usize,
+}
+
+/// Trim and validate a user supplied preset name.
+pub fn normalize_preset_name(raw: &str) -> Result<String, String> {
+    let name = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err("Preset names cannot be empty.".into());
+    }
+    if name.chars().count() > MAX_PRESET_NAME_LEN {
+        return Err(format!(
+            "Preset names are limited to {} characters.",
+            MAX_PRESET_NAME_LEN
+        ));
+    }
+    Ok(name)
+}
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn list_presets(pool: &PgPool, user_id: UserId) -> Result<Vec<PartyPreset>, sqlx::Error> {
+    sqlx::query_as!(
+        PartyPreset,
+        "SELECT preset_id, name FROM party_presets WHERE user_id = $1 ORDER BY LOWER(name)",
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// All preset members for a user (grouped by the caller), including the current display name of
+/// each saved unit when it still exists in the army.
+#[instrument(level = "debug", skip(pool))]
+pub async fn list_preset_members(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<Vec<PartyPresetMember>, sqlx::Error> {
+    sqlx::query_as!(
+        PartyPresetMember,
+        r#"SELECT m.preset_id, m.player_unit_id, m.bond_id,
+            COALESCE(pu.nickname, u.name) as "unit_name?"
+        FROM party_preset_members m
+        JOIN party_presets p ON p.preset_id = m.preset_id
+        LEFT JOIN player_units pu ON pu.player_unit_id = m.player_unit_id AND pu.user_id = p.user_id
+        LEFT JOIN units u ON u.unit_id = pu.unit_id
+        WHERE p.user_id = $1
+        ORDER BY m.preset_id, m.slot"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Save (or overwrite) a preset from the user's current party and equipped bonds.
+#[instrument(level = "info", skip(pool))]
+pub async fn save_current_party_as_preset(
+    pool: &PgPool,
+    user_id: UserId,
+    raw_name: &str,
+) -> Result<String, String> {
+    let name = normalize_preset_name(raw_name)?;
+    let uid = user_id.get() as i64;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", uid)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    let members = sqlx::query!(
+        r#"SELECT pu.player_unit_id, pu.formation_row as "formation_row: FormationRow",
+            (SELECT b.bond_id FROM equippable_unit_bonds b
+                WHERE b.host_player_unit_id = pu.player_unit_id AND b.is_equipped = TRUE
+                ORDER BY b.bond_id LIMIT 1) as bond_id
+        FROM player_units pu
+        WHERE pu.user_id = $1 AND pu.is_in_party = TRUE
+        ORDER BY pu.player_unit_id"#,
+        uid
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|_| "Could not read your current party.".to_string())?;
+    if members.is_empty() {
+        tx.rollback().await.ok();
+        return Err("Your party is empty. Add units before saving a preset.".into());
+    }
+    let existing = sqlx::query_scalar!(
+        "SELECT preset_id FROM party_presets WHERE user_id = $1 AND LOWER(name) = LOWER($2) FOR UPDATE",
+        uid,
+        &name
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let preset_id = if let Some(id) = existing {
+        sqlx::query!(
+            "UPDATE party_presets SET name = $2, updated_at = $3 WHERE preset_id = $1",
+            id,
+            &name,
+            Utc::now()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+        sqlx::query!("DELETE FROM party_preset_members WHERE preset_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        id
+    } else {
+        let count: i64 =
+            sqlx::query_scalar!("SELECT COUNT(*) FROM party_presets WHERE user_id = $1", uid)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or(0);
+        if count >= MAX_PARTY_PRESETS {
+            tx.rollback().await.ok();
+            return Err(format!(
+                "You already have {}/{} presets. Delete one first.",
+                count, MAX_PARTY_PRESETS
+            ));
+        }
+        sqlx::query_scalar!(
+            "INSERT INTO party_presets (user_id, name) VALUES ($1, $2) RETURNING preset_id",
+            uid,
+            &name
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_| "Failed to create the preset.".to_string())?
+    };
+    for (slot, m) in members.iter().enumerate() {
+        sqlx::query!(
+            "INSERT INTO party_preset_members (preset_id, slot, player_unit_id, bond_id, formation_row) VALUES ($1, $2, $3, $4, $5)",
+            preset_id,
+            slot as i32,
+            m.player_unit_id,
+            m.bond_id,
+            m.formation_row as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| "Failed to store preset members.".to_string())?;
+    }
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the preset.".to_string())?;
+    Ok(name)
+}
+
+#[instrument(level = "info", skip(pool))]
+pub async fn delete_preset(
+    pool: &PgPool,
+    user_id: UserId,
+    name: &str,
+) -> Result<bool, sqlx::Error> {
+    let rows = sqlx::query!(
+        "DELETE FROM party_presets WHERE user_id = $1 AND LOWER(name) = LOWER($2)",
+        user_id.get() as i64,
+        name.trim()
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(rows > 0)
+}
+
+/// Resolve a preset id (from the party UI select menu) to its name for the given owner.
+pub async fn preset_name_by_id(
+    pool: &PgPool,
+    user_id: UserId,
+    preset_id: i32,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT name FROM party_presets WHERE preset_id = $1 AND user_id = $2",
+        preset_id,
+        user_id.get() as i64
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Swap a saved preset in as the active party. Every member is re-validated against the current
+/// army (ownership, active training, bond usage, pet rarity gate) and every saved bond must still
+/// exist on its host; any problem aborts the whole swap and is reported back to the player. Bonds
+/// the members picked up since the preset was saved are unequipped.
+#[instrument(level = "info", skip(pool))]
+pub async fn load_party_preset(
+    pool: &PgPool,
+    user_id: UserId,
+    name: &str,
+) -> Result<PresetLoadOutcome, String> {
+    let uid = user_id.get() as i64;
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", uid)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(preset) = sqlx::query!(
+        "SELECT preset_id, name FROM party_presets WHERE user_id = $1 AND LOWER(name) = LOWER($2)",
+        uid,
+        name.trim()
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    else {
+        tx.rollback().await.ok();
+        return Err(format!("No preset named `{}`.", name.trim()));
+    };
+    let rows = sqlx::query!(
+        r#"SELECT m.slot, m.player_unit_id, m.bond_id, m.formation_row as "formation_row: FormationRow",
+            pu.player_unit_id as "owned_id?", COALESCE(pu.nickname, u.name) as "unit_name?",
+            u.kind::text as kind_text, pu.rarity as "rarity?: UnitRarity",
+            pu.is_training as "is_training?", pu.training_ends_at,
+            EXISTS(SELECT 1 FROM equippable_unit_bonds eb
+                WHERE eb.equipped_player_unit_id = m.player_unit_id AND eb.is_equipped = TRUE) as "is_equipped_elsewhere!",
+            bd.host_player_unit_id as "bond_host?", eq.user_id as "bond_owner?"
+        FROM party_preset_members m
+        LEFT JOIN player_units pu ON pu.player_unit_id = m.player_unit_id AND pu.user_id = $2
+        LEFT JOIN units u ON u.unit_id = pu.unit_id
+        LEFT JOIN equippable_unit_bonds bd ON bd.bond_id = m.bond_id
+        LEFT JOIN player_units eq ON eq.player_unit_id = bd.equipped_player_unit_id
+        WHERE m.preset_id = $1
+        ORDER BY m.slot
+        FOR UPDATE OF m"#,
+        preset.preset_id,
+        uid
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    if rows.is_empty() {
+        tx.rollback().await.ok();
+        return Err("That preset has no members.".into());
+    }
+    if rows.len() as i64 > MAX_PARTY_SIZE {
+        tx.rollback().await.ok();
+        return Err(format!(
+            "That preset has {} members but the party limit is {}.",
+            rows.len(),
+            MAX_PARTY_SIZE
+        ));
+    }
+    let now = Utc::now();
+    let mut problems: Vec<String> = Vec::new();
+    let mut member_ids: Vec<i32> = Vec::with_capacity(rows.len());
+    let mut bond_ids: Vec<i32> = Vec::new();
+    for r in &rows {
+        let label = r
+            .unit_name
+            .clone()
+            .unwrap_or_else(|| format!("Unit #{}", r.player_unit_id));
+        if r.owned_id.is_none() {
+            problems.push(format!("{} is no longer in your army.", label));
+            continue;
+        }
+        if r.is_training.unwrap_or(false) && r.training_ends_at.map(|t| t > now).unwrap_or(true) {
+            let until = r
+                .training_ends_at
+                .map(|t| format!(" (ends <t:{}:R>)", t.timestamp()))
+                .unwrap_or_default();
+            problems.push(format!("{} is currently training{}.", label, until));
+        }
+        if r.is_equipped_elsewhere {
+            problems.push(format!("{} is bonded to another unit.", label));
+        }
+        if r.kind_text.as_deref() == Some("Pet")
+            && !matches!(
+                r.rarity,
+                Some(
+                    UnitRarity::Legendary
+                        | UnitRarity::Unique
+                        | UnitRarity::Mythical
+                        | UnitRarity::Fabled
+                )
+            )
+        {
+            problems.push(format!("{} is a pet below Legendary rarity.", label));
+        }
+        if let Some(bond_id) = r.bond_id {
+            if r.bond_host != Some(r.player_unit_id) || r.bond_owner != Some(uid) {
+                problems.push(format!("{}'s saved bond no longer exists.", label));
+            } else {
+                bond_ids.push(bond_id);
+            }
+        }
+        member_ids.push(r.player_unit_id);
+    }
+    if !problems.is_empty() {
+        tx.rollback().await.ok();
+        return Err(format!(
+            "Cannot load **{}**:\n• {}",
+            preset.name,
+            problems.join("\n• ")
+        ));
+    }
+    sqlx::query!(
+        "UPDATE player_units SET is_in_party = (player_unit_id = ANY($2)) WHERE user_id = $1",
+        uid,
+        &member_ids
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to swap party members.".to_string())?;
+    for r in &rows {
+        sqlx::query!(
+            "UPDATE player_units SET formation_row = $1 WHERE player_unit_id = $2 AND user_id = $3",
+            r.formation_row as _,
+            r.player_unit_id,
+            uid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| "Failed to restore the formation.".to_string())?;
+    }
+    // Members saved without a bond (or with a different one) drop what they hold now, so the
+    // party comes back exactly as it was saved.
+    sqlx::query!(
+        "UPDATE equippable_unit_bonds SET is_equipped = FALSE
+        WHERE host_player_unit_id = ANY($1) AND is_equipped = TRUE AND NOT (bond_id = ANY($2))",
+        &member_ids,
+        &bond_ids
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to clear unsaved bonds.".to_string())?;
+    let bonds_restored = if bond_ids.is_empty() {
+        0
+    } else {
+        sqlx::query!(
+            "UPDATE equippable_unit_bonds SET is_equipped = TRUE WHERE bond_id = ANY($1)",
+            &bond_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| "Failed to restore bonds.".to_string())?;
+        bond_ids.len()
+    };
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the party swap.".to_string())?;
+    Ok(PresetLoadOutcome {
+        name: preset.name,
+        members: member_ids.len(),
+        bonds_restored,
+    })
+}
@@ -0,0 +1,173 @@
+//! Database functions for supply/demand pricing. See `commands::economy::core::pricing` for
+//! the price model itself.
+
+use super::settings::get_config_value;
+use crate::commands::economy::core::pricing::{
+    MAX_ROLL_DAYS, PRICE_BAND_MAX_CONFIG_KEY, PRICE_BAND_MIN_CONFIG_KEY, PriceBand, PriceState,
+    close_multiplier, live_multiplier,
+};
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+
+pub async fn get_price_band(pool: &PgPool) -> PriceBand {
+    let min = get_config_value(pool, PRICE_BAND_MIN_CONFIG_KEY)
+        .await
+        .ok()
+        .flatten();
+    let max = get_config_value(pool, PRICE_BAND_MAX_CONFIG_KEY)
+        .await
+        .ok()
+        .flatten();
+    PriceBand::parse(min.as_deref(), max.as_deref())
+}
+
+/// Brings a locked `item_prices` row up to today. Any trade rolls the row first, so only the
+/// day it was last touched can have volume; later days just revert toward the base price.
+async fn roll_locked(
+    tx: &mut Transaction<'_, Postgres>,
+    item_id: i32,
+    open_bp: i64,
+    updated_on: NaiveDate,
+    today: NaiveDate,
+    band: PriceBand,
+) -> Result<i64, sqlx::Error> {
+    if updated_on >= today {
+        return Ok(open_bp);
+    }
+    let volume = sqlx::query!(
+        "SELECT bought, sold FROM item_market_volume WHERE item_id = $1 AND day = $2",
+        item_id,
+        updated_on
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    let (bought, sold) = volume.map(|v| (v.bought, v.sold)).unwrap_or((0, 0));
+    let mut bp = close_multiplier(open_bp, bought, sold, band);
+    let idle_days = ((today - updated_on).num_days() - 1).clamp(0, MAX_ROLL_DAYS);
+    for _ in 0..idle_days {
+        bp = close_multiplier(bp, 0, 0, band);
+    }
+    sqlx::query!(
+        "UPDATE item_prices SET open_bp = $2, updated_on = $3 WHERE item_id = $1",
+        item_id,
+        bp,
+        today
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(bp)
+}
+
+/// Rolls every price that has not been touched today. Like market settlement this runs lazily
+/// from the commands that show prices.
+pub async fn roll_stale_prices(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let band = get_price_band(pool).await;
+    let mut rolled = 0;
+    loop {
+        let mut tx = pool.begin().await?;
+        let Some(row) = sqlx::query!(
+            r#"SELECT item_id, open_bp, updated_on, CURRENT_DATE as "today!" FROM item_prices WHERE updated_on < CURRENT_DATE LIMIT 1 FOR UPDATE SKIP LOCKED"#
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            tx.rollback().await.ok();
+            break;
+        };
+        roll_locked(
+            &mut tx,
+            row.item_id,
+            row.open_bp,
+            row.updated_on,
+            row.today,
+            band,
+        )
+        .await?;
+        tx.commit().await?;
+        rolled += 1;
+    }
+    Ok(rolled)
+}
+
+/// Current price states for the given items. Items without a price row trade at base price.
+pub async fn get_price_states(pool: &PgPool, item_ids: &[i32]) -> HashMap<i32, PriceState> {
+    if let Err(e) = roll_stale_prices(pool).await {
+        tracing::warn!(target: "pricing", error = %e, "Failed to roll item prices");
+    }
+    let band = get_price_band(pool).await;
+    let rows = sqlx::query!(
+        r#"SELECT p.item_id, p.open_bp, COALESCE(v.bought, 0) as "bought!", COALESCE(v.sold, 0) as "sold!"
+           FROM item_prices p
+           LEFT JOIN item_market_volume v ON v.item_id = p.item_id AND v.day = CURRENT_DATE
+           WHERE p.item_id = ANY($1)"#,
+        item_ids
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+    rows.into_iter()
+        .map(|r| {
+            (
+                r.item_id,
+                PriceState {
+                    open_bp: r.open_bp,
+                    live_bp: live_multiplier(r.open_bp, r.bought, r.sold, band),
+                },
+            )
+        })
+        .collect()
+}
+
+pub async fn get_price_state(pool: &PgPool, item_id: i32) -> PriceState {
+    get_price_states(pool, &[item_id])
+        .await
+        .remove(&item_id)
+        .unwrap_or_default()
+}
+
+/// Records `bought`/`sold` units of an item inside a purchase or sale transaction and returns
+/// the price state before and after the trade. The price row stays locked until the caller's
+/// transaction ends, so concurrent trades of the same item are priced one after another.
+pub async fn record_trade(
+    tx: &mut Transaction<'_, Postgres>,
+    item_id: i32,
+    bought: i64,
+    sold: i64,
+    band: PriceBand,
+) -> Result<(PriceState, PriceState), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO item_prices (item_id) VALUES ($1) ON CONFLICT (item_id) DO NOTHING",
+        item_id
+    )
+    .execute(&mut **tx)
+    .await?;
+    let row = sqlx::query!(
+        r#"SELECT open_bp, updated_on, CURRENT_DATE as "today!" FROM item_prices WHERE item_id = $1 FOR UPDATE"#,
+        item_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    let open_bp = roll_locked(tx, item_id, row.open_bp, row.updated_on, row.today, band).await?;
+    let volume = sqlx::query!(
+        r#"INSERT INTO item_market_volume (item_id, day, bought, sold) VALUES ($1, $2, $3, $4)
+           ON CONFLICT (item_id, day) DO UPDATE
+           SET bought = item_market_volume.bought + EXCLUDED.bought, sold = item_market_volume.sold + EXCLUDED.sold
+           RETURNING bought, sold"#,
+        item_id,
+        row.today,
+        bought,
+        sold
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    let before = PriceState {
+        open_bp,
+        live_bp: live_multiplier(open_bp, volume.bought - bought, volume.sold - sold, band),
+    };
+    let after = PriceState {
+        open_bp,
+        live_bp: live_multiplier(open_bp, volume.bought, volume.sold, band),
+    };
+    Ok((before, after))
+}
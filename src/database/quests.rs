@@ -1,7 +1,10 @@
 //! Contains all database functions related to the player quest system.
 
-use super::models::{PlayerQuest, PlayerQuestStatus, Quest, QuestDetails, QuestReward, QuestType};
-use crate::{commands::economy::core::item::Item, database};
+use super::models::{
+    LedgerReason, PlayerQuest, PlayerQuestStatus, Quest, QuestDetails, QuestReward, QuestType,
+};
+use crate::commands::economy::core::registry::registry;
+use crate::database;
 use serenity::model::id::UserId;
 use sqlx::PgPool;
 
@@ -162,19 +165,28 @@ pub async fn complete_quest(
             if let Some(coins) = reward.reward_coins
                 && coins > 0
             {
-                database::economy::add_balance(&mut tx, user_id, coins)
-                    .await
-                    .map_err(|e| e.to_string())?;
+                database::economy::add_balance(
+                    &mut tx,
+                    user_id,
+                    coins,
+                    LedgerReason::Quest.with_ref("quest", reward.quest_id),
+                )
+                .await
+                .map_err(|e| e.to_string())?;
             }
-            // (✓) FIXED: Collapsed nested `if let` and ignored unused `_item_id`.
-            if let (Some(_item_id), Some(quantity), Some(item)) = (
-                reward.reward_item_id,
-                reward.reward_item_quantity,
-                Item::from_i32(reward.reward_item_id.unwrap_or(0)),
-            ) {
-                database::economy::add_to_inventory(&mut tx, user_id, item, quantity as i64)
-                    .await
-                    .map_err(|e| e.to_string())?;
+            if let (Some(item_id), Some(quantity)) =
+                (reward.reward_item_id, reward.reward_item_quantity)
+                && registry().get(item_id).is_some()
+            {
+                database::economy::add_to_inventory(
+                    &mut tx,
+                    user_id,
+                    item_id,
+                    quantity as i64,
+                    LedgerReason::Quest.with_ref("quest", reward.quest_id),
+                )
+                .await
+                .map_err(|e| e.to_string())?;
             }
         }
 
@@ -0,0 +1,138 @@
+//! Persistence for the research tree (see `saga::research`): derives a player's standing from
+//! species research progress and records node unlocks.
+
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+
+use super::models::UnitKind;
+use crate::saga::research::{
+    NodeStatus, ResearchFamily, ResearchNode, ResearchPerks, ResearchStanding, node_by_key,
+    points_for_rarity,
+};
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn list_unlocked_nodes(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT node_key FROM player_research_nodes WHERE user_id = $1 ORDER BY unlocked_at",
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Completed families and earned points from species research, plus persisted unlocks.
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_research_standing(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<ResearchStanding, sqlx::Error> {
+    let units = super::units::get_all_units(pool).await?;
+    let progress: HashMap<i32, i32> = super::units::list_research_progress(pool, user_id)
+        .await?
+        .into_iter()
+        .collect();
+    let mut standing = ResearchStanding {
+        unlocked: list_unlocked_nodes(pool, user_id).await?,
+        ..Default::default()
+    };
+    for u in units.iter().filter(|u| matches!(u.kind, UnitKind::Pet)) {
+        let target = super::units::research_target_for_rarity(pool, u.rarity).await;
+        let count = progress.get(&u.unit_id).copied().unwrap_or(0);
+        if target == 0 || count < target {
+            continue;
+        }
+        standing.points_earned += points_for_rarity(u.rarity);
+        if let Some(family) = ResearchFamily::for_unit_name(&u.name)
+            && !standing.completed_families.contains(&family)
+        {
+            standing.completed_families.push(family);
+        }
+    }
+    Ok(standing)
+}
+
+/// Passive bonuses for a player; falls back to no bonuses if the lookup fails.
+pub async fn get_research_perks(pool: &PgPool, user_id: UserId) -> ResearchPerks {
+    get_research_standing(pool, user_id)
+        .await
+        .map(|s| s.perks())
+        .unwrap_or_default()
+}
+
+/// Player unit ids of pets currently in the party (targets of the pet damage passive).
+#[instrument(level = "debug", skip(pool))]
+pub async fn list_party_pet_ids(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<HashSet<i32>, sqlx::Error> {
+    let rows = sqlx::query_scalar!(
+        "SELECT pu.player_unit_id FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id WHERE pu.user_id = $1 AND pu.is_in_party = TRUE AND u.kind = 'Pet'",
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Spend research points on a node. Validation runs under a profile row lock so concurrent
+/// unlocks cannot double-spend the same points.
+#[instrument(level = "info", skip(pool))]
+pub async fn unlock_research_node(
+    pool: &PgPool,
+    user_id: UserId,
+    node_key: &str,
+) -> Result<&'static ResearchNode, String> {
+    let node = node_by_key(node_key).ok_or_else(|| "Unknown research node.".to_string())?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start research unlock.".to_string())?;
+    sqlx::query!(
+        "SELECT user_id FROM profiles WHERE user_id = $1 FOR UPDATE",
+        user_id.get() as i64
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Failed to lock your profile.".to_string())?;
+    let standing = get_research_standing(pool, user_id)
+        .await
+        .map_err(|_| "Failed to load your research standing.".to_string())?;
+    match standing.status(node) {
+        NodeStatus::Available => {}
+        NodeStatus::Unlocked => return Err(format!("**{}** is already unlocked.", node.name)),
+        NodeStatus::FamilyIncomplete => {
+            return Err(format!(
+                "Complete research on a {} creature to open this branch.",
+                node.family.label()
+            ));
+        }
+        NodeStatus::MissingPrerequisite(name) => {
+            return Err(format!("Unlock **{}** first.", name));
+        }
+        NodeStatus::NotEnoughPoints => {
+            return Err(format!(
+                "**{}** costs {} research point(s); you have {}.",
+                node.name,
+                node.cost,
+                standing.points_available()
+            ));
+        }
+    }
+    sqlx::query!(
+        "INSERT INTO player_research_nodes (user_id, node_key) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id.get() as i64,
+        node.key
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to unlock the research node.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to commit research unlock.".to_string())?;
+    Ok(node)
+}
@@ -0,0 +1,250 @@
+//! Persistence for the ranked Rock Paper Scissors ladder: seasonal ratings, the matchmaking
+//! queue and match history (rules in `commands::rps::ladder`).
+
+use chrono::{Duration, Utc};
+use serenity::model::id::{ChannelId, UserId};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::economy::get_or_create_profile;
+use super::models::{RpsQueueEntry, RpsRating};
+use crate::commands::rps::ladder::{
+    QUEUE_TIMEOUT_MINUTES, RatingChange, pick_opponent, rate, season_at, seeded_rating,
+};
+
+/// What happened when a player joined the queue.
+pub enum QueueOutcome {
+    /// Nobody suitable was waiting; the player is queued at this rating.
+    Queued(i32),
+    /// The player was paired with this waiting player, who has left the queue.
+    Matched(RpsQueueEntry),
+}
+
+/// The player's rating in their most recent season before `season`.
+async fn previous_rating(
+    conn: &mut sqlx::PgConnection,
+    user_id: i64,
+    season: i32,
+) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT rating FROM rps_ratings WHERE user_id = $1 AND season < $2 ORDER BY season DESC LIMIT 1",
+        user_id,
+        season
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+/// The player's record for `season`. Players who haven't played yet get their seeded rating.
+pub async fn get_rating(
+    pool: &PgPool,
+    user_id: UserId,
+    season: i32,
+) -> Result<RpsRating, sqlx::Error> {
+    let uid = user_id.get() as i64;
+    let mut conn = pool.acquire().await?;
+    let existing = sqlx::query_as!(
+        RpsRating,
+        "SELECT season, rating, peak, wins, losses, brackets_won FROM rps_ratings WHERE user_id = $1 AND season = $2",
+        uid,
+        season
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    if let Some(rating) = existing {
+        return Ok(rating);
+    }
+    let rating = seeded_rating(previous_rating(&mut conn, uid, season).await?);
+    Ok(RpsRating {
+        season,
+        rating,
+        peak: rating,
+        wins: 0,
+        losses: 0,
+        brackets_won: 0,
+    })
+}
+
+/// Creates the player's row for `season` if needed and locks it.
+async fn lock_rating(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: UserId,
+    season: i32,
+) -> Result<RpsRating, sqlx::Error> {
+    let uid = user_id.get() as i64;
+    let seeded = seeded_rating(previous_rating(tx, uid, season).await?);
+    sqlx::query!(
+        r#"INSERT INTO rps_ratings (user_id, season, rating, peak) VALUES ($1, $2, $3, $3)
+           ON CONFLICT (user_id, season) DO NOTHING"#,
+        uid,
+        season,
+        seeded
+    )
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query_as!(
+        RpsRating,
+        r#"SELECT season, rating, peak, wins, losses, brackets_won FROM rps_ratings
+           WHERE user_id = $1 AND season = $2 FOR UPDATE"#,
+        uid,
+        season
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+/// Rates a finished match in the current season and logs it.
+pub async fn record_match(
+    pool: &PgPool,
+    winner: UserId,
+    loser: UserId,
+    bracket: bool,
+) -> Result<RatingChange, sqlx::Error> {
+    get_or_create_profile(pool, winner).await?;
+    get_or_create_profile(pool, loser).await?;
+    let season = season_at(Utc::now());
+    let mut tx = pool.begin().await?;
+    // Lock both rows in a fixed order so two matches between the same players can't deadlock.
+    let (w, l) = if winner < loser {
+        let w = lock_rating(&mut tx, winner, season).await?;
+        (w, lock_rating(&mut tx, loser, season).await?)
+    } else {
+        let l = lock_rating(&mut tx, loser, season).await?;
+        (lock_rating(&mut tx, winner, season).await?, l)
+    };
+    let change = rate(w.rating, w.games(), l.rating, l.games());
+    sqlx::query!(
+        r#"UPDATE rps_ratings SET rating = $3, peak = GREATEST(peak, $3), wins = wins + 1
+           WHERE user_id = $1 AND season = $2"#,
+        winner.get() as i64,
+        season,
+        change.winner_after
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "UPDATE rps_ratings SET rating = $3, losses = losses + 1 WHERE user_id = $1 AND season = $2",
+        loser.get() as i64,
+        season,
+        change.loser_after
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        r#"INSERT INTO rps_matches (season, winner_id, loser_id, winner_delta, loser_delta, bracket)
+           VALUES ($1, $2, $3, $4, $5, $6)"#,
+        season,
+        winner.get() as i64,
+        loser.get() as i64,
+        change.winner_delta(),
+        change.loser_delta(),
+        bracket
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(change)
+}
+
+/// Credits a bracket win to the champion's current season.
+pub async fn record_bracket_win(pool: &PgPool, user_id: UserId) -> Result<(), sqlx::Error> {
+    get_or_create_profile(pool, user_id).await?;
+    let season = season_at(Utc::now());
+    let mut tx = pool.begin().await?;
+    lock_rating(&mut tx, user_id, season).await?;
+    sqlx::query!(
+        "UPDATE rps_ratings SET brackets_won = brackets_won + 1 WHERE user_id = $1 AND season = $2",
+        user_id.get() as i64,
+        season
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// The player's position on the season's ladder, if they have played this season.
+pub async fn rank(pool: &PgPool, user_id: UserId, season: i32) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT (SELECT COUNT(*) FROM rps_ratings o WHERE o.season = r.season AND o.rating > r.rating) + 1 AS "rank!"
+           FROM rps_ratings r WHERE r.user_id = $1 AND r.season = $2"#,
+        user_id.get() as i64,
+        season
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Pairs the player with a waiting opponent, or queues them if nobody is in range.
+pub async fn join_queue(
+    pool: &PgPool,
+    user_id: UserId,
+    channel_id: ChannelId,
+) -> Result<QueueOutcome, String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    let now = Utc::now();
+    let rating = get_rating(pool, user_id, season_at(now))
+        .await
+        .map_err(|_| "Could not load your rating.".to_string())?
+        .rating;
+    let failed = |_| "Matchmaking failed, please try again.".to_string();
+    let uid = user_id.get() as i64;
+    let mut tx = pool.begin().await.map_err(failed)?;
+    // One matchmaker at a time, so a waiting player can't be handed to two newcomers.
+    sqlx::query!("LOCK TABLE rps_queue IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await
+        .map_err(failed)?;
+    sqlx::query!(
+        "DELETE FROM rps_queue WHERE queued_at < $1",
+        now - Duration::minutes(QUEUE_TIMEOUT_MINUTES)
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(failed)?;
+    let queue = sqlx::query_as!(
+        RpsQueueEntry,
+        "SELECT user_id, channel_id, rating, queued_at FROM rps_queue ORDER BY queued_at"
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(failed)?;
+    if queue.iter().any(|entry| entry.user_id == uid) {
+        return Err("You're already in the ranked queue.".to_string());
+    }
+    let outcome = match pick_opponent(rating, &queue, now) {
+        Some(i) => {
+            sqlx::query!("DELETE FROM rps_queue WHERE user_id = $1", queue[i].user_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(failed)?;
+            QueueOutcome::Matched(queue[i])
+        }
+        None => {
+            sqlx::query!(
+                "INSERT INTO rps_queue (user_id, channel_id, rating, queued_at) VALUES ($1, $2, $3, $4)",
+                uid,
+                channel_id.get() as i64,
+                rating,
+                now
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(failed)?;
+            QueueOutcome::Queued(rating)
+        }
+    };
+    tx.commit().await.map_err(failed)?;
+    Ok(outcome)
+}
+
+/// Takes the player out of the queue. Returns whether they were in it.
+pub async fn leave_queue(pool: &PgPool, user_id: UserId) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        "DELETE FROM rps_queue WHERE user_id = $1",
+        user_id.get() as i64
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -52,6 +52,17 @@ pub async fn update_and_get_saga_profile(
         tx.commit().await?;
     }
 
+    // Settle human wages / morale once per UTC day (no-op on repeat calls the same day).
+    if let Ok(Some(report)) = super::upkeep::settle_daily_upkeep(pool, user_id).await {
+        tracing::debug!(
+            target = "saga.upkeep",
+            user_id = user_id_i64,
+            days = report.days,
+            unpaid = report.unpaid_days,
+            spent = report.coins_spent
+        );
+    }
+
     // Next, update AP and TP in a single transaction.
     let mut tx = pool.begin().await?;
     // (✓) Robust UPSERT pattern: attempt insert and capture row with RETURNING. If the row
@@ -0,0 +1,143 @@
+//! Spectators' side bets on running games. A bet's stake leaves the wallet when it is placed and
+//! is held against the game's message until the round is settled: winning bets are paid at the
+//! odds they were placed at, and a round with no winner refunds every stake (rules in
+//! `commands::games::side_bets`).
+
+use serenity::model::id::{MessageId, UserId};
+use sqlx::PgPool;
+
+use super::economy::{add_balance, get_or_create_profile};
+use super::gambling;
+use super::ledger::LedgerSource;
+use super::models::{GambleGame, LedgerReason, SideBet};
+use crate::commands::games::GamePayout;
+use crate::commands::games::side_bets::winnings;
+
+fn bet_ref(game: MessageId) -> LedgerSource {
+    LedgerReason::Game.with_ref("side_bet", game)
+}
+
+/// How a settled bet turned out.
+#[derive(Debug, Clone, Copy)]
+pub struct SettledBet {
+    pub user_id: UserId,
+    pub backed_id: UserId,
+    /// Coins returned to the bettor: winnings, a refund, or nothing.
+    pub returned: i64,
+    pub stake: i64,
+}
+
+/// Takes `stake` from the bettor's wallet and records the bet on `backed` at `odds`, unless the
+/// bettor's gambling limits forbid it. One bet per spectator per round.
+pub async fn place(
+    pool: &PgPool,
+    game: MessageId,
+    user_id: UserId,
+    backed: UserId,
+    stake: i64,
+    odds: i64,
+) -> Result<(), String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    gambling::check_can_play(pool, user_id).await?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to place your bet.".to_string())?;
+    add_balance(&mut tx, user_id, -stake, bet_ref(game))
+        .await
+        .map_err(|_| format!("You can't afford a 💰{} bet.", stake))?;
+    let inserted = sqlx::query!(
+        "INSERT INTO side_bets (message_id, user_id, backed_id, stake, odds) VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+        game.get() as i64,
+        user_id.get() as i64,
+        backed.get() as i64,
+        stake,
+        odds as i32
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record your bet.".to_string())?;
+    if inserted.rows_affected() == 0 {
+        return Err("You already have a bet on this round.".into());
+    }
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to complete your bet.".to_string())
+}
+
+/// Every open bet on `game`.
+pub async fn open_bets(pool: &PgPool, game: MessageId) -> Result<Vec<SideBet>, sqlx::Error> {
+    sqlx::query_as!(
+        SideBet,
+        "SELECT user_id, backed_id, stake, odds FROM side_bets WHERE message_id = $1 ORDER BY placed_at",
+        game.get() as i64
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Settles every open bet on `game`: bets on one of `winners` are paid at their odds and the
+/// rest are lost. With no winners the round is void and every stake is refunded. Won and lost
+/// bets count toward the bettors' gambling stats.
+pub async fn settle(
+    pool: &PgPool,
+    game: MessageId,
+    winners: &[UserId],
+) -> Result<Vec<SettledBet>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let bets = sqlx::query_as!(
+        SideBet,
+        "DELETE FROM side_bets WHERE message_id = $1 RETURNING user_id, backed_id, stake, odds",
+        game.get() as i64
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let mut settled = Vec::with_capacity(bets.len());
+    for bet in bets {
+        let user_id = UserId::new(bet.user_id as u64);
+        let backed_id = UserId::new(bet.backed_id as u64);
+        let returned = if winners.is_empty() {
+            bet.stake
+        } else if winners.contains(&backed_id) {
+            winnings(bet.stake, bet.odds as i64)
+        } else {
+            0
+        };
+        add_balance(&mut tx, user_id, returned, bet_ref(game)).await?;
+        settled.push(SettledBet {
+            user_id,
+            backed_id,
+            returned,
+            stake: bet.stake,
+        });
+    }
+    tx.commit().await?;
+    if !winners.is_empty() && !settled.is_empty() {
+        let results: Vec<GamePayout> = settled
+            .iter()
+            .map(|b| GamePayout {
+                user_id: b.user_id,
+                amount: b.returned - b.stake,
+            })
+            .collect();
+        if let Err(e) = gambling::record(pool, GambleGame::SideBet, &results).await {
+            tracing::warn!(game = %game, error = ?e, "failed to record side bet stats");
+        }
+    }
+    Ok(settled)
+}
+
+/// Refunds every open bet. Games only live in memory, so this runs at startup for rounds a
+/// restart cut short.
+pub async fn refund_abandoned(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let games = sqlx::query_scalar!("SELECT DISTINCT message_id FROM side_bets")
+        .fetch_all(pool)
+        .await?;
+    let mut refunded = 0;
+    for game in games {
+        refunded += settle(pool, MessageId::new(game as u64), &[]).await?.len();
+    }
+    Ok(refunded)
+}
@@ -0,0 +1,159 @@
+//! Chip stacks for game tables. A player's buy-in moves from their wallet into a stack held
+//! against the table's message when they sit down. Each settled round moves the stack, and
+//! whatever is left goes back to the wallet when they cash out, so a losing round can never
+//! push a wallet below zero.
+
+use serenity::model::id::{MessageId, UserId};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::economy::{add_balance, get_or_create_profile};
+use super::gambling;
+use super::models::{GambleGame, LedgerReason};
+use crate::commands::games::GamePayout;
+
+fn table_ref(table: MessageId) -> super::ledger::LedgerSource {
+    LedgerReason::Game.with_ref("table", table)
+}
+
+/// Moves `chips` coins from the player's wallet into a stack at `table`, unless the player's
+/// gambling limits forbid it.
+pub async fn buy_in(
+    pool: &PgPool,
+    table: MessageId,
+    user_id: UserId,
+    chips: i64,
+) -> Result<(), String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    gambling::check_can_play(pool, user_id).await?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the buy-in.".to_string())?;
+    add_balance(&mut tx, user_id, -chips, table_ref(table))
+        .await
+        .map_err(|_| format!("You can't afford the 💰{} buy-in.", chips))?;
+    let inserted = sqlx::query!(
+        "INSERT INTO table_stacks (message_id, user_id, chips) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        table.get() as i64,
+        user_id.get() as i64,
+        chips
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record your buy-in.".to_string())?;
+    if inserted.rows_affected() == 0 {
+        return Err("You have already bought in at this table.".into());
+    }
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to complete the buy-in.".to_string())
+}
+
+/// Applies a round's net results to the stacks at `table`, records them in the players'
+/// gambling stats for `game`, and returns every stack afterwards.
+pub async fn settle(
+    pool: &PgPool,
+    table: MessageId,
+    game: GambleGame,
+    results: &[GamePayout],
+) -> Result<Vec<(UserId, i64)>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    for result in results.iter().filter(|r| r.amount != 0) {
+        sqlx::query!(
+            "UPDATE table_stacks SET chips = chips + $3 WHERE message_id = $1 AND user_id = $2",
+            table.get() as i64,
+            result.user_id.get() as i64,
+            result.amount
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    let stacks = sqlx::query!(
+        "SELECT user_id, chips FROM table_stacks WHERE message_id = $1",
+        table.get() as i64
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|r| (UserId::new(r.user_id as u64), r.chips))
+    .collect();
+    tx.commit().await?;
+    if let Err(e) = gambling::record(pool, game, results).await {
+        tracing::warn!(table = %table, error = ?e, "failed to record gambling stats");
+    }
+    Ok(stacks)
+}
+
+async fn cash_out_in(
+    tx: &mut Transaction<'_, Postgres>,
+    table: MessageId,
+    user_id: UserId,
+) -> Result<Option<i64>, sqlx::Error> {
+    let chips = sqlx::query_scalar!(
+        "DELETE FROM table_stacks WHERE message_id = $1 AND user_id = $2 RETURNING chips",
+        table.get() as i64,
+        user_id.get() as i64
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+    if let Some(chips) = chips {
+        add_balance(tx, user_id, chips, table_ref(table)).await?;
+    }
+    Ok(chips)
+}
+
+/// Returns the player's stack at `table` to their wallet. Returns the chips cashed out.
+pub async fn cash_out(pool: &PgPool, table: MessageId, user_id: UserId) -> Result<i64, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the cash-out.".to_string())?;
+    let chips = cash_out_in(&mut tx, table, user_id)
+        .await
+        .map_err(|_| "Failed to cash out your chips.".to_string())?
+        .ok_or("You have no chips at this table.")?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to complete the cash-out.".to_string())?;
+    Ok(chips)
+}
+
+/// Cashes out everyone still holding chips at `table`, e.g. when the table closes.
+pub async fn cash_out_table(
+    pool: &PgPool,
+    table: MessageId,
+) -> Result<Vec<(UserId, i64)>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let seated = sqlx::query_scalar!(
+        "SELECT user_id FROM table_stacks WHERE message_id = $1 FOR UPDATE",
+        table.get() as i64
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let mut cashed = Vec::with_capacity(seated.len());
+    for user_id in seated {
+        let user_id = UserId::new(user_id as u64);
+        if let Some(chips) = cash_out_in(&mut tx, table, user_id).await? {
+            cashed.push((user_id, chips));
+        }
+    }
+    tx.commit().await?;
+    Ok(cashed)
+}
+
+/// Cashes out every open stack. Tables only live in memory, so this runs at startup for
+/// tables a restart closed.
+pub async fn cash_out_abandoned(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let tables = sqlx::query_scalar!("SELECT DISTINCT message_id FROM table_stacks")
+        .fetch_all(pool)
+        .await?;
+    let mut cashed = 0;
+    for table in tables {
+        cashed += cash_out_table(pool, MessageId::new(table as u64))
+            .await?
+            .len();
+    }
+    Ok(cashed)
+}
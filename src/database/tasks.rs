@@ -1,7 +1,7 @@
 //! Contains all database functions related to the player task system.
 
-use super::models::{PlayerTaskDetails, Task, TaskType};
-use crate::commands::economy::core::item::Item;
+use super::models::{LedgerReason, PlayerTaskDetails, Task, TaskType};
+use crate::commands::economy::core::registry::registry;
 use crate::database::economy::{add_balance, add_to_inventory};
 use serenity::model::id::UserId;
 use sqlx::{PgPool, Postgres, Transaction};
@@ -143,16 +143,27 @@ pub async fn claim_task_reward(
     if let Some(task) = task_to_claim {
         let reward_coins = task.reward_coins.unwrap_or(0);
         if reward_coins > 0 {
-            add_balance(&mut tx, user_id, reward_coins)
-                .await
-                .map_err(|e| e.to_string())?;
+            add_balance(
+                &mut tx,
+                user_id,
+                reward_coins,
+                LedgerReason::Task.with_ref("task", player_task_id),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
         }
         if let (Some(item_id), Some(quantity)) = (task.reward_item_id, task.reward_item_quantity)
-            && let Some(item) = Item::from_i32(item_id)
+            && registry().get(item_id).is_some()
         {
-            add_to_inventory(&mut tx, user_id, item, quantity as i64)
-                .await
-                .map_err(|e| e.to_string())?;
+            add_to_inventory(
+                &mut tx,
+                user_id,
+                item_id,
+                quantity as i64,
+                LedgerReason::Task.with_ref("task", player_task_id),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
         }
         sqlx::query!(
             "UPDATE player_tasks SET claimed_at = NOW() WHERE player_task_id = $1",
@@ -119,7 +119,13 @@ pub async fn transactional_reroll(
     }
 
     // Deduct balance (will fail if insufficient)
-    crate::database::economy::add_balance(&mut tx, user, -cost).await?;
+    crate::database::economy::add_balance(
+        &mut tx,
+        user,
+        -cost,
+        crate::database::models::LedgerReason::Tavern.with_ref("reroll", today_date),
+    )
+    .await?;
 
     // Overwrite rotation for today
     sqlx::query(
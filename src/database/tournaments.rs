@@ -0,0 +1,362 @@
+//! Persistence for tournaments (rules in `commands::tournament::logic`). Buy-ins move from the
+//! wallet into the tournament's prize pool at registration and leave it either as prizes when
+//! the tournament finishes or as refunds when it is cancelled.
+
+use chrono::{DateTime, Utc};
+use serenity::model::id::{ChannelId, UserId};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::economy::{add_balance, get_or_create_profile};
+use super::models::{LedgerReason, TournamentGame, TournamentStatus};
+use crate::commands::games::GamePayout;
+use crate::commands::tournament::logic::{MAX_ENTRANTS, MIN_ENTRANTS};
+
+#[derive(Debug, Clone)]
+pub struct TournamentRecord {
+    pub tournament_id: i32,
+    pub host_id: i64,
+    pub channel_id: i64,
+    pub game: TournamentGame,
+    pub buy_in: i64,
+    pub prize_pool: i64,
+    pub status: TournamentStatus,
+    pub created_at: DateTime<Utc>,
+    /// Registered players in registration order.
+    pub entrants: Vec<UserId>,
+}
+
+/// A finished tournament for the history view.
+#[derive(Debug, Clone)]
+pub struct TournamentResult {
+    pub tournament_id: i32,
+    pub game: TournamentGame,
+    pub buy_in: i64,
+    pub prize_pool: i64,
+    pub entrants: i64,
+    pub finished_at: DateTime<Utc>,
+    /// Paid places as (user, place, prize), best first.
+    pub podium: Vec<(UserId, i32, i64)>,
+}
+
+struct TournamentRow {
+    tournament_id: i32,
+    host_id: i64,
+    channel_id: i64,
+    game: TournamentGame,
+    buy_in: i64,
+    prize_pool: i64,
+    status: TournamentStatus,
+    created_at: DateTime<Utc>,
+}
+
+async fn lock_tournament(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: i32,
+) -> Result<Option<TournamentRow>, sqlx::Error> {
+    sqlx::query_as!(
+        TournamentRow,
+        r#"SELECT tournament_id, host_id, channel_id, game AS "game: TournamentGame", buy_in, prize_pool, status AS "status: TournamentStatus", created_at FROM tournaments WHERE tournament_id = $1 FOR UPDATE"#,
+        tournament_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn entrants(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: i32,
+) -> Result<Vec<UserId>, sqlx::Error> {
+    let rows = sqlx::query_scalar!(
+        "SELECT user_id FROM tournament_entries WHERE tournament_id = $1 ORDER BY registered_at, user_id",
+        tournament_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows.into_iter().map(|u| UserId::new(u as u64)).collect())
+}
+
+async fn to_record(
+    tx: &mut Transaction<'_, Postgres>,
+    row: TournamentRow,
+) -> Result<TournamentRecord, sqlx::Error> {
+    Ok(TournamentRecord {
+        entrants: entrants(tx, row.tournament_id).await?,
+        tournament_id: row.tournament_id,
+        host_id: row.host_id,
+        channel_id: row.channel_id,
+        game: row.game,
+        buy_in: row.buy_in,
+        prize_pool: row.prize_pool,
+        status: row.status,
+        created_at: row.created_at,
+    })
+}
+
+pub async fn get_tournament(
+    pool: &PgPool,
+    tournament_id: i32,
+) -> Result<Option<TournamentRecord>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let record = match lock_tournament(&mut tx, tournament_id).await? {
+        Some(row) => Some(to_record(&mut tx, row).await?),
+        None => None,
+    };
+    tx.commit().await?;
+    Ok(record)
+}
+
+/// Opens registration for a new tournament.
+pub async fn create_tournament(
+    pool: &PgPool,
+    host_id: UserId,
+    channel_id: ChannelId,
+    game: TournamentGame,
+    buy_in: i64,
+) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        "INSERT INTO tournaments (host_id, channel_id, game, buy_in) VALUES ($1, $2, $3, $4) RETURNING tournament_id",
+        host_id.get() as i64,
+        channel_id.get() as i64,
+        game as TournamentGame,
+        buy_in
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Registers `user_id`, moving the buy-in from their wallet into the prize pool.
+pub async fn register(
+    pool: &PgPool,
+    tournament_id: i32,
+    user_id: UserId,
+) -> Result<TournamentRecord, String> {
+    get_or_create_profile(pool, user_id)
+        .await
+        .map_err(|_| "Profile lookup failed.".to_string())?;
+    super::gambling::check_can_play(pool, user_id).await?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start registration.".to_string())?;
+    let row = lock_tournament(&mut tx, tournament_id)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?
+        .ok_or("That tournament no longer exists.")?;
+    if row.status != TournamentStatus::Registering {
+        return Err("Registration for this tournament is closed.".into());
+    }
+    let registered = entrants(&mut tx, tournament_id)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?;
+    if registered.contains(&user_id) {
+        return Err("You are already registered.".into());
+    }
+    if registered.len() >= MAX_ENTRANTS {
+        return Err("This tournament is full.".into());
+    }
+    let source = LedgerReason::Tournament.with_ref("tournament", tournament_id);
+    add_balance(&mut tx, user_id, -row.buy_in, source)
+        .await
+        .map_err(|_| format!("You can't afford the 💰{} buy-in.", row.buy_in))?;
+    sqlx::query!(
+        "INSERT INTO tournament_entries (tournament_id, user_id) VALUES ($1, $2)",
+        tournament_id,
+        user_id.get() as i64
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record your entry.".to_string())?;
+    let row = sqlx::query_as!(
+        TournamentRow,
+        r#"UPDATE tournaments SET prize_pool = prize_pool + buy_in WHERE tournament_id = $1 RETURNING tournament_id, host_id, channel_id, game AS "game: TournamentGame", buy_in, prize_pool, status AS "status: TournamentStatus", created_at"#,
+        tournament_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| "Failed to update the prize pool.".to_string())?;
+    let record = to_record(&mut tx, row)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to complete registration.".to_string())?;
+    Ok(record)
+}
+
+/// Closes registration and marks the tournament as running. Needs at least two entrants.
+pub async fn start(pool: &PgPool, tournament_id: i32) -> Result<TournamentRecord, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start the tournament.".to_string())?;
+    let row = lock_tournament(&mut tx, tournament_id)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?
+        .ok_or("That tournament no longer exists.")?;
+    if row.status != TournamentStatus::Registering {
+        return Err("This tournament has already started or closed.".into());
+    }
+    let mut record = to_record(&mut tx, row)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?;
+    if record.entrants.len() < MIN_ENTRANTS {
+        return Err(format!(
+            "At least {} players must register before the tournament can start.",
+            MIN_ENTRANTS
+        ));
+    }
+    sqlx::query!(
+        "UPDATE tournaments SET status = 'Running', started_at = NOW() WHERE tournament_id = $1",
+        tournament_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to start the tournament.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to start the tournament.".to_string())?;
+    record.status = TournamentStatus::Running;
+    Ok(record)
+}
+
+/// Cancels a tournament that hasn't finished and refunds every buy-in.
+pub async fn cancel(pool: &PgPool, tournament_id: i32) -> Result<usize, String> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to cancel the tournament.".to_string())?;
+    let row = lock_tournament(&mut tx, tournament_id)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?
+        .ok_or("That tournament no longer exists.")?;
+    if matches!(
+        row.status,
+        TournamentStatus::Finished | TournamentStatus::Cancelled
+    ) {
+        return Err("This tournament is already closed.".into());
+    }
+    let registered = entrants(&mut tx, tournament_id)
+        .await
+        .map_err(|_| "Tournament lookup failed.".to_string())?;
+    let source = LedgerReason::Tournament.with_ref("tournament", tournament_id);
+    for user_id in &registered {
+        add_balance(&mut tx, *user_id, row.buy_in, source.clone())
+            .await
+            .map_err(|_| "Failed to refund a buy-in.".to_string())?;
+    }
+    sqlx::query!(
+        "UPDATE tournaments SET status = 'Cancelled', prize_pool = 0, finished_at = NOW() WHERE tournament_id = $1",
+        tournament_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to cancel the tournament.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to cancel the tournament.".to_string())?;
+    Ok(registered.len())
+}
+
+/// Records the final standings (winner first) and pays out the prizes from the pool.
+pub async fn finish(
+    pool: &PgPool,
+    tournament_id: i32,
+    standings: &[UserId],
+    payouts: &[GamePayout],
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(row) = lock_tournament(&mut tx, tournament_id).await? else {
+        return Err(sqlx::Error::RowNotFound);
+    };
+    if row.status != TournamentStatus::Running {
+        return Err(sqlx::Error::RowNotFound);
+    }
+    let source = LedgerReason::Tournament.with_ref("tournament", tournament_id);
+    for (place, user_id) in standings.iter().enumerate() {
+        let prize = payouts
+            .iter()
+            .find(|p| p.user_id == *user_id)
+            .map_or(0, |p| p.amount);
+        sqlx::query!(
+            "UPDATE tournament_entries SET finish_position = $3, prize = $4 WHERE tournament_id = $1 AND user_id = $2",
+            tournament_id,
+            user_id.get() as i64,
+            place as i32 + 1,
+            prize
+        )
+        .execute(&mut *tx)
+        .await?;
+        add_balance(&mut tx, *user_id, prize, source.clone()).await?;
+    }
+    sqlx::query!(
+        "UPDATE tournaments SET status = 'Finished', prize_pool = 0, finished_at = NOW() WHERE tournament_id = $1",
+        tournament_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+/// The most recently finished tournaments with their paid places.
+pub async fn recent_results(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<TournamentResult>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT t.tournament_id, t.game AS "game: TournamentGame", t.buy_in,
+                  COALESCE(SUM(e.prize), 0)::BIGINT AS "prize_pool!", COUNT(e.user_id) AS "entrants!",
+                  t.finished_at AS "finished_at!"
+           FROM tournaments t JOIN tournament_entries e ON e.tournament_id = t.tournament_id
+           WHERE t.status = 'Finished'
+           GROUP BY t.tournament_id
+           ORDER BY t.finished_at DESC
+           LIMIT $1"#,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+    let ids: Vec<i32> = rows.iter().map(|r| r.tournament_id).collect();
+    let podium = sqlx::query!(
+        r#"SELECT tournament_id, user_id, finish_position AS "finish_position!", prize
+           FROM tournament_entries
+           WHERE tournament_id = ANY($1) AND prize > 0
+           ORDER BY finish_position"#,
+        &ids
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| TournamentResult {
+            podium: podium
+                .iter()
+                .filter(|p| p.tournament_id == r.tournament_id)
+                .map(|p| (UserId::new(p.user_id as u64), p.finish_position, p.prize))
+                .collect(),
+            tournament_id: r.tournament_id,
+            game: r.game,
+            buy_in: r.buy_in,
+            prize_pool: r.prize_pool,
+            entrants: r.entrants,
+            finished_at: r.finished_at,
+        })
+        .collect())
+}
+
+/// Refunds every tournament still registering or running. Tables only live in memory, so this
+/// runs at startup for tournaments a restart cut short.
+pub async fn cancel_unfinished(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let ids = sqlx::query_scalar!(
+        "SELECT tournament_id FROM tournaments WHERE status IN ('Registering', 'Running')"
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut cancelled = 0;
+    for tournament_id in ids {
+        match cancel(pool, tournament_id).await {
+            Ok(_) => cancelled += 1,
+            Err(e) => tracing::warn!(tournament_id, error = %e, "failed to refund tournament"),
+        }
+    }
+    Ok(cancelled)
+}
@@ -0,0 +1,123 @@
+//! Database functions for direct player-to-player trades.
+
+use super::economy::{add_balance, add_to_inventory};
+use super::models::LedgerReason;
+use crate::commands::economy::core::registry::registry;
+use crate::commands::economy::trade::state::{Holdings, TradeOffer};
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+/// Current balance and item quantities for a trade participant (no locks; display and
+/// pre-checks only — settlement re-verifies under row locks).
+pub async fn get_holdings(pool: &PgPool, user_id: UserId) -> Result<Holdings, sqlx::Error> {
+    let user_id_i64 = user_id.get() as i64;
+    let balance = sqlx::query_scalar!(
+        "SELECT balance FROM profiles WHERE user_id = $1",
+        user_id_i64
+    )
+    .fetch_optional(pool)
+    .await?
+    .unwrap_or(0);
+    let items = sqlx::query!(
+        "SELECT item_id, quantity FROM inventories WHERE user_id = $1 AND quantity > 0",
+        user_id_i64
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| (r.item_id, r.quantity))
+    .collect();
+    Ok(Holdings { balance, items })
+}
+
+/// Swaps both offers in a single transaction. Both profiles and every inventory row of both
+/// players are locked (in user id order, so two trades between the same pair cannot deadlock)
+/// and the offers are re-checked against the locked rows before anything moves.
+pub async fn settle_trade(pool: &PgPool, offers: &[TradeOffer; 2]) -> Result<(), String> {
+    let mut ids: Vec<i64> = offers.iter().map(|o| o.user_id.get() as i64).collect();
+    ids.sort_unstable();
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+
+    let balances: HashMap<i64, i64> = sqlx::query!(
+        "SELECT user_id, balance FROM profiles WHERE user_id = ANY($1) ORDER BY user_id FOR UPDATE",
+        &ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| (r.user_id, r.balance))
+    .collect();
+    let stock: HashMap<(i64, i32), i64> = sqlx::query!(
+        "SELECT user_id, item_id, quantity FROM inventories WHERE user_id = ANY($1) ORDER BY user_id, item_id FOR UPDATE",
+        &ids
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?
+    .into_iter()
+    .map(|r| ((r.user_id, r.item_id), r.quantity))
+    .collect();
+
+    let items = registry();
+    for offer in offers {
+        let uid = offer.user_id.get() as i64;
+        let Some(&balance) = balances.get(&uid) else {
+            return Err(format!(
+                "**{}** needs a profile before trading. Try `/work` first.",
+                offer.name
+            ));
+        };
+        if balance < offer.coins {
+            return Err(format!(
+                "**{}** no longer has 💰{} to trade.",
+                offer.name, offer.coins
+            ));
+        }
+        for (&item_id, &quantity) in &offer.items {
+            if !items.get(item_id).is_some_and(|d| d.is_tradeable) {
+                return Err(format!("**{}** cannot be traded.", items.name_of(item_id)));
+            }
+            if stock.get(&(uid, item_id)).copied().unwrap_or(0) < quantity {
+                return Err(format!(
+                    "**{}** no longer has `{}` **{}**.",
+                    offer.name,
+                    quantity,
+                    items.name_of(item_id)
+                ));
+            }
+        }
+    }
+
+    let [a, b] = offers;
+    for (giver, receiver) in [(a, b), (b, a)] {
+        let sent = LedgerReason::Trade.with_ref("user", receiver.user_id);
+        let received = LedgerReason::Trade.with_ref("user", giver.user_id);
+        if giver.coins > 0 {
+            add_balance(&mut tx, giver.user_id, -giver.coins, sent.clone())
+                .await
+                .map_err(|e| e.to_string())?;
+            add_balance(&mut tx, receiver.user_id, giver.coins, received.clone())
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        for (&item_id, &quantity) in &giver.items {
+            add_to_inventory(&mut tx, giver.user_id, item_id, -quantity, sent.clone())
+                .await
+                .map_err(|e| e.to_string())?;
+            add_to_inventory(
+                &mut tx,
+                receiver.user_id,
+                item_id,
+                quantity,
+                received.clone(),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    tracing::info!(target: "trade", a = %a.user_id, b = %b.user_id, "Trade settled");
+    Ok(())
+}
@@ -7,8 +7,10 @@ use sqlx::{PgPool, Postgres, Transaction};
 use tracing::{instrument, warn};
 
 use super::economy::{add_balance, add_to_inventory, get_inventory_item};
+use super::ledger::LedgerSource;
 use crate::commands::economy::core::item::Item;
-pub use crate::database::models::{PlayerUnit, Profile, Unit, UnitKind, UnitRarity};
+use crate::database::models::LedgerReason;
+pub use crate::database::models::{FormationRow, PlayerUnit, Profile, Unit, UnitKind, UnitRarity};
 use crate::saga;
 use crate::saga::leveling::LevelUpResult;
 // TEMP: Re-export legacy bonding until pets.rs fully removed
@@ -61,14 +63,14 @@ pub async fn get_user_party(
 
 #[instrument(level = "debug", skip(pool, unit_ids), fields(count = unit_ids.len()))]
 pub async fn get_units_by_ids(pool: &PgPool, unit_ids: &[i32]) -> Result<Vec<Unit>, sqlx::Error> {
-    sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = ANY($1)", unit_ids)
+    sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, is_ranged, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = ANY($1)", unit_ids)
 		.fetch_all(pool)
 		.await
 }
 
 #[instrument(level = "debug", skip(pool))]
 pub async fn get_all_units(pool: &PgPool) -> Result<Vec<Unit>, sqlx::Error> {
-    sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units ORDER BY unit_id")
+    sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, is_ranged, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units ORDER BY unit_id")
         .fetch_all(pool)
         .await
 }
@@ -105,7 +107,14 @@ pub async fn hire_unit(
         .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
-    let profile = sqlx::query_as!(Profile, "SELECT balance, last_work, work_streak, fishing_xp, fishing_level, mining_xp, mining_level, coding_xp, coding_level FROM profiles WHERE user_id = $1 FOR UPDATE", user_id_i64).fetch_one(&mut *tx).await.map_err(|_| "Could not find your profile.".to_string())?;
+    let profile = sqlx::query_as!(
+        Profile,
+        "SELECT balance, last_work, work_streak FROM profiles WHERE user_id = $1 FOR UPDATE",
+        user_id_i64
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| "Could not find your profile.".to_string())?;
     if profile.balance < cost {
         tx.rollback().await.ok();
         return Err("You don't have enough coins.".to_string());
@@ -126,13 +135,18 @@ pub async fn hire_unit(
             crate::constants::MAX_ARMY_SIZE
         ));
     }
-    let unit_master = sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = $1", unit_id)
+    let unit_master = sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, is_ranged, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = $1", unit_id)
 		.fetch_one(&mut *tx)
 		.await
 		.map_err(|_| "This mercenary is no longer available.".to_string())?;
-    add_balance(&mut tx, user_id, -cost)
-        .await
-        .map_err(|_| "Failed to process payment.".to_string())?;
+    add_balance(
+        &mut tx,
+        user_id,
+        -cost,
+        LedgerReason::Recruit.with_ref("unit", unit_id),
+    )
+    .await
+    .map_err(|_| "Failed to process payment.".to_string())?;
     // Decide initial party inclusion:
     // Humans: auto-join party if space; Pets: must satisfy Legendary+ (handled elsewhere on explicit set) so default FALSE here.
     let mut is_in_party = false;
@@ -169,7 +183,7 @@ pub async fn attempt_recruit_unit(
         .execute(&mut *tx)
         .await
         .map_err(|e| e.to_string())?;
-    let unit_master = sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = $1", unit_id_to_recruit)
+    let unit_master = sqlx::query_as!(Unit, "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, is_ranged, kind as \"kind: UnitKind\", rarity as \"rarity: UnitRarity\" FROM units WHERE unit_id = $1", unit_id_to_recruit)
 		.fetch_one(&mut *tx)
 		.await
 		.map_err(|_| "Creature data not found.".to_string())?;
@@ -214,7 +228,14 @@ pub async fn attempt_recruit_unit(
     // PET TAMING MATERIALS (only pets reach this point). Research Data represents accumulated study from wild battles.
     if let Some(research_item) = Item::research_item_for_unit(&unit_master.name) {
         let requirements = [(Item::TamingLure, 1_i64), (research_item, 10_i64)];
-        if let Err(e) = verify_and_consume_items(&mut tx, user_id, &requirements).await {
+        if let Err(e) = verify_and_consume_items(
+            &mut tx,
+            user_id,
+            &requirements,
+            LedgerReason::Recruit.with_ref("unit", unit_id_to_recruit),
+        )
+        .await
+        {
             tx.rollback().await.ok();
             return Err(e);
         }
@@ -479,6 +500,47 @@ pub async fn set_unit_party_status(
     }
 }
 
+/// player_unit_id -> (formation row, master unit is ranged)
+pub type FormationMap = std::collections::HashMap<i32, (FormationRow, bool)>;
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_party_formation(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<FormationMap, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT pu.player_unit_id, pu.formation_row as "formation_row: FormationRow", u.is_ranged
+        FROM player_units pu JOIN units u ON u.unit_id = pu.unit_id
+        WHERE pu.user_id = $1"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.player_unit_id, (r.formation_row, r.is_ranged)))
+        .collect())
+}
+
+#[instrument(level = "info", skip(pool))]
+pub async fn set_unit_formation_row(
+    pool: &PgPool,
+    user_id: UserId,
+    player_unit_id: i32,
+    row: FormationRow,
+) -> Result<bool, sqlx::Error> {
+    let rows_affected = sqlx::query!(
+        "UPDATE player_units SET formation_row = $1 WHERE player_unit_id = $2 AND user_id = $3 AND is_in_party = TRUE",
+        row as _,
+        player_unit_id,
+        user_id.get() as i64
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
+
 // -------------------------------------------------------------------------------------------------
 // Research / Taming Progress (sub-Legendary tames accumulate research milestones)
 // -------------------------------------------------------------------------------------------------
@@ -588,16 +650,16 @@ pub async fn apply_battle_rewards(
     pool: &PgPool,
     user_id: UserId,
     coins: i64,
-    loot: &[(Item, i64)],
+    loot: &[(i32, i64)],
     units_in_battle: &[PlayerUnit],
     xp_per_unit: i32,
 ) -> Result<Vec<LevelUpResult>, sqlx::Error> {
     let mut tx = pool.begin().await?;
     if coins > 0 {
-        add_balance(&mut tx, user_id, coins).await?;
+        add_balance(&mut tx, user_id, coins, LedgerReason::Battle).await?;
     }
-    for (item, quantity) in loot {
-        add_to_inventory(&mut tx, user_id, *item, *quantity).await?;
+    for (item_id, quantity) in loot {
+        add_to_inventory(&mut tx, user_id, *item_id, *quantity, LedgerReason::Battle).await?;
     }
     let mut level_up_results = Vec::new();
     for unit in units_in_battle {
@@ -626,14 +688,28 @@ pub async fn dismiss_unit(
     player_unit_id: i32,
 ) -> Result<bool, sqlx::Error> {
     let user_id_i64 = user_id.get() as i64;
+    let mut tx = pool.begin().await?;
+    // Equipped gear goes back to the inventory rather than leaving with the unit.
+    let owned = sqlx::query_scalar!(
+        "SELECT player_unit_id FROM player_units WHERE player_unit_id = $1 AND user_id = $2 FOR UPDATE",
+        player_unit_id,
+        user_id_i64
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if owned.is_none() {
+        return Ok(false);
+    }
+    super::gear::return_unit_gear(&mut tx, user_id, player_unit_id).await?;
     let rows_affected = sqlx::query!(
         "DELETE FROM player_units WHERE player_unit_id = $1 AND user_id = $2",
         player_unit_id,
         user_id_i64
     )
-    .execute(pool)
+    .execute(&mut *tx)
     .await?
     .rows_affected();
+    tx.commit().await?;
     Ok(rows_affected > 0)
 }
 
@@ -813,6 +889,7 @@ async fn verify_and_consume_items(
     tx: &mut Transaction<'_, Postgres>,
     user_id: UserId,
     requirements: &[(Item, i64)],
+    source: LedgerSource,
 ) -> Result<(), String> {
     for (item, qty) in requirements.iter() {
         let inv = get_inventory_item(tx, user_id, *item)
@@ -823,7 +900,7 @@ async fn verify_and_consume_items(
         }
     }
     for (item, qty) in requirements.iter() {
-        add_to_inventory(tx, user_id, *item, -*qty)
+        add_to_inventory(tx, user_id, *item, -*qty, source.clone())
             .await
             .map_err(|_| "Failed to consume materials".to_string())?;
     }
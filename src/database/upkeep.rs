@@ -0,0 +1,233 @@
+//! Persistence for human upkeep & morale (rules in `saga::upkeep`).
+
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use sqlx::types::chrono::Utc;
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::models::{LedgerReason, UnitRarity};
+use crate::saga::upkeep::{
+    GIFT_COST, GIFT_MORALE, MAX_UPKEEP_CATCHUP_DAYS, MORALE_MAX, MORALE_WIN_GAIN, default_wage,
+    morale_after_settlement, settle_wages,
+};
+
+/// A human unit's upkeep line for the upkeep view.
+#[derive(Debug, Clone)]
+pub struct HumanUpkeep {
+    pub player_unit_id: i32,
+    pub name: String,
+    pub morale: i32,
+    pub wage: i64,
+    pub is_in_party: bool,
+}
+
+/// Summary of a lazy daily settlement.
+#[derive(Debug, Clone, Default)]
+pub struct UpkeepReport {
+    pub days: i64,
+    pub unpaid_days: i64,
+    pub coins_spent: i64,
+}
+
+#[instrument(level = "debug", skip(pool))]
+pub async fn list_human_upkeep(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<Vec<HumanUpkeep>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT pu.player_unit_id, COALESCE(pu.nickname, u.name) as "name!", pu.morale, pu.contract_wage,
+            pu.rarity as "rarity: UnitRarity", pu.is_in_party
+        FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id
+        WHERE pu.user_id = $1 AND u.kind = 'Human'
+        ORDER BY pu.is_in_party DESC, pu.morale ASC"#,
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| HumanUpkeep {
+            player_unit_id: r.player_unit_id,
+            name: r.name,
+            morale: r.morale,
+            wage: r.contract_wage.unwrap_or_else(|| default_wage(r.rarity)),
+            is_in_party: r.is_in_party,
+        })
+        .collect())
+}
+
+/// Morale of human units in the active party (player_unit_id -> morale).
+#[instrument(level = "debug", skip(pool))]
+pub async fn get_party_morale(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<HashMap<i32, i32>, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT pu.player_unit_id, pu.morale FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id WHERE pu.user_id = $1 AND pu.is_in_party = TRUE AND u.kind = 'Human'",
+        user_id.get() as i64
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.player_unit_id, r.morale))
+        .collect())
+}
+
+/// Pay outstanding daily wages (at most `MAX_UPKEEP_CATCHUP_DAYS`) and apply morale changes.
+/// Runs at most once per UTC day; the first call only starts the clock.
+#[instrument(level = "debug", skip(pool))]
+pub async fn settle_daily_upkeep(
+    pool: &PgPool,
+    user_id: UserId,
+) -> Result<Option<UpkeepReport>, sqlx::Error> {
+    let uid = user_id.get() as i64;
+    let today = Utc::now().date_naive();
+    let mut tx = pool.begin().await?;
+    let Some(last) = sqlx::query_scalar!(
+        "SELECT last_upkeep_date FROM player_saga_profile WHERE user_id = $1 FOR UPDATE",
+        uid
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let days = match last {
+        Some(last) => (today - last).num_days().min(MAX_UPKEEP_CATCHUP_DAYS),
+        None => 0,
+    };
+    if last.is_some() && days <= 0 {
+        return Ok(None);
+    }
+    sqlx::query!(
+        "UPDATE player_saga_profile SET last_upkeep_date = $2 WHERE user_id = $1",
+        uid,
+        today
+    )
+    .execute(&mut *tx)
+    .await?;
+    if days <= 0 {
+        tx.commit().await?;
+        return Ok(None);
+    }
+    let humans = sqlx::query!(
+        r#"SELECT pu.player_unit_id, pu.morale, pu.contract_wage, pu.rarity as "rarity: UnitRarity", pu.is_in_party
+        FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id
+        WHERE pu.user_id = $1 AND u.kind = 'Human' FOR UPDATE OF pu"#,
+        uid
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    if humans.is_empty() {
+        tx.commit().await?;
+        return Ok(None);
+    }
+    let daily_total: i64 = humans
+        .iter()
+        .map(|h| h.contract_wage.unwrap_or_else(|| default_wage(h.rarity)))
+        .sum();
+    let balance = sqlx::query_scalar!(
+        "SELECT balance FROM profiles WHERE user_id = $1 FOR UPDATE",
+        uid
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .unwrap_or(0);
+    let (_paid_days, unpaid_days, coins_spent) = settle_wages(balance, daily_total, days);
+    if coins_spent > 0 {
+        super::economy::add_balance(
+            &mut tx,
+            user_id,
+            -coins_spent,
+            LedgerReason::Upkeep.with_ref("wages", today),
+        )
+        .await?;
+    }
+    for h in &humans {
+        let rest_days = if h.is_in_party { 0 } else { days };
+        let morale = morale_after_settlement(h.morale, unpaid_days, rest_days);
+        if morale != h.morale {
+            sqlx::query!(
+                "UPDATE player_units SET morale = $2 WHERE player_unit_id = $1",
+                h.player_unit_id,
+                morale
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(Some(UpkeepReport {
+        days,
+        unpaid_days,
+        coins_spent,
+    }))
+}
+
+/// Battle wins lift the morale of every human in the party.
+#[instrument(level = "debug", skip(pool))]
+pub async fn reward_party_morale(pool: &PgPool, user_id: UserId) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE player_units pu SET morale = LEAST($2, pu.morale + $3) FROM units u WHERE pu.unit_id = u.unit_id AND pu.user_id = $1 AND pu.is_in_party = TRUE AND u.kind = 'Human'",
+        user_id.get() as i64,
+        MORALE_MAX,
+        MORALE_WIN_GAIN
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Spend `GIFT_COST` coins to raise one human's morale. Returns (name, new morale).
+#[instrument(level = "info", skip(pool))]
+pub async fn gift_unit(
+    pool: &PgPool,
+    user_id: UserId,
+    player_unit_id: i32,
+) -> Result<(String, i32), String> {
+    let uid = user_id.get() as i64;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|_| "Failed to start gift.".to_string())?;
+    let unit = sqlx::query!(
+        r#"SELECT COALESCE(pu.nickname, u.name) as "name!", pu.morale, u.kind::text as "kind!"
+        FROM player_units pu JOIN units u ON pu.unit_id = u.unit_id
+        WHERE pu.player_unit_id = $1 AND pu.user_id = $2 FOR UPDATE OF pu"#,
+        player_unit_id,
+        uid
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| "Unit lookup failed.".to_string())?
+    .ok_or_else(|| "That unit is not in your army.".to_string())?;
+    if unit.kind != "Human" {
+        return Err("Only hired humans care about gifts.".into());
+    }
+    if unit.morale >= MORALE_MAX {
+        return Err(format!("{}'s morale is already full.", unit.name));
+    }
+    super::economy::add_balance(
+        &mut tx,
+        user_id,
+        -GIFT_COST,
+        LedgerReason::Upkeep.with_ref("gift", player_unit_id),
+    )
+    .await
+    .map_err(|_| format!("A gift costs {} coins.", GIFT_COST))?;
+    let morale = (unit.morale + GIFT_MORALE).min(MORALE_MAX);
+    sqlx::query!(
+        "UPDATE player_units SET morale = $2 WHERE player_unit_id = $1",
+        player_unit_id,
+        morale
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| "Failed to record the gift.".to_string())?;
+    tx.commit()
+        .await
+        .map_err(|_| "Failed to finalize the gift.".to_string())?;
+    Ok((unit.name, morale))
+}
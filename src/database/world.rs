@@ -57,7 +57,7 @@ pub async fn get_full_node_bundle(
         None => return Err(sqlx::Error::RowNotFound),
     };
     let mut enemies = sqlx::query_as::<_, Unit>(
-        "SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.kind as kind, u.rarity as rarity FROM units u JOIN node_enemies ne ON u.unit_id = ne.unit_id WHERE ne.node_id = $1",
+        "SELECT u.unit_id, u.name, u.description, u.base_attack, u.base_defense, u.base_health, u.is_recruitable, u.is_ranged, u.kind as kind, u.rarity as rarity FROM units u JOIN node_enemies ne ON u.unit_id = ne.unit_id WHERE ne.node_id = $1",
     )
     .bind(node_id)
     .fetch_all(&mut *conn)
@@ -76,7 +76,7 @@ pub async fn get_full_node_bundle(
         };
         // Pick candidates: pets preferred, exclude humans for random generation
         let candidates = sqlx::query_as::<_, Unit>(
-            "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, kind as kind, rarity as rarity FROM units WHERE kind != 'Human'::unit_kind AND rarity >= $1 ORDER BY random() LIMIT 6",
+            "SELECT unit_id, name, description, base_attack, base_defense, base_health, is_recruitable, is_ranged, kind as kind, rarity as rarity FROM units WHERE kind != 'Human'::unit_kind AND rarity >= $1 ORDER BY random() LIMIT 6",
         )
         .bind(min_rarity)
         .fetch_all(&mut *conn)
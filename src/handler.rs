@@ -16,6 +16,12 @@ enum Command {
     Sell,
     Shop,
     Give,
+    Market,
+    Bank,
+    Gambling,
+    Ladder,
+    Contest,
+    Trade,
     Open,
     Saga,
     Leaderboard,
@@ -28,6 +34,8 @@ enum Command {
     Help,
     Blackjack,
     Poker,
+    Holdem,
+    Tournament,
     Unknown,
     Bond,
     Config,
@@ -51,6 +59,12 @@ impl FromStr for Command {
             "sell" => Ok(Command::Sell),
             "shop" => Ok(Command::Shop),
             "give" | "gift" => Ok(Command::Give),
+            "market" | "m" => Ok(Command::Market),
+            "bank" | "b" => Ok(Command::Bank),
+            "gambling" | "gamble" => Ok(Command::Gambling),
+            "ladder" | "ranked" => Ok(Command::Ladder),
+            "contest" | "ct" => Ok(Command::Contest),
+            "trade" => Ok(Command::Trade),
             "open" | "o" => Ok(Command::Open),
             "saga" | "play" => Ok(Command::Saga),
             "leaderboard" | "lb" => Ok(Command::Leaderboard),
@@ -63,6 +77,8 @@ impl FromStr for Command {
             "help" | "h" => Ok(Command::Help),
             "blackjack" | "bj" => Ok(Command::Blackjack),
             "poker" | "pk" => Ok(Command::Poker),
+            "holdem" | "th" => Ok(Command::Holdem),
+            "tournament" | "tourney" => Ok(Command::Tournament),
             "bond" => Ok(Command::Bond),
             "config" | "cfg" => Ok(Command::Config),
             "contracts" => Ok(Command::Contracts),
@@ -101,6 +117,12 @@ impl EventHandler for Handler {
                 "sell" => commands::economy::sell::run::run_slash(&ctx, command).await,
                 "shop" => commands::economy::shop::run::run_slash(&ctx, command).await,
                 "give" => commands::economy::give::run::run_slash(&ctx, command).await,
+                "market" => commands::economy::market::run::run_slash(&ctx, command).await,
+                "bank" => commands::economy::bank::run::run_slash(&ctx, command).await,
+                "gambling" => commands::economy::gambling::run::run_slash(&ctx, command).await,
+                "ladder" => commands::ladder::run::run_slash(&ctx, command).await,
+                "contest" => commands::tavern_games::run::run_slash(&ctx, command).await,
+                "trade" => commands::economy::trade::run::run_slash(&ctx, command).await,
                 "open" => commands::open::run::run_slash(&ctx, command).await,
                 "saga" => commands::saga::run::run_slash(&ctx, command).await,
                 "play" => commands::saga::run::run_slash(&ctx, command).await,
@@ -114,6 +136,8 @@ impl EventHandler for Handler {
                 "help" => commands::help::run_slash(&ctx, command).await,
                 "blackjack" => commands::blackjack::run::run_slash(&ctx, command).await,
                 "poker" => commands::poker::run::run_slash(&ctx, command).await,
+                "holdem" => commands::holdem::run::run_slash(&ctx, command).await,
+                "tournament" => commands::tournament::run::run_slash(&ctx, command).await,
                 "bond" => commands::bond::run::run_slash(&ctx, command).await,
                 "config" => commands::config::run_slash(&ctx, command).await,
                 "contracts" => commands::contracts::run::run_slash(&ctx, command).await,
@@ -137,7 +161,8 @@ impl EventHandler for Handler {
                 command_family = target; // e.g. saga / party / train
             }
             match command_family {
-                "rps" | "bj" | "poker" | "shop" | "battle" => {
+                "rps" | "bj" | "poker" | "holdem" | "tourney" | "shop" | "battle" | "trade"
+                | "tavern" | "spectate" => {
                     interactions::game_handler::handle(&ctx, component, app_state).await
                 }
                 "help" => commands::help::handle_interaction(&ctx, component).await,
@@ -163,6 +188,10 @@ impl EventHandler for Handler {
                 "research" => {
                     interactions::research_handler::handle(&ctx, component, app_state).await
                 }
+                "market" => interactions::market_handler::handle(&ctx, component, app_state).await,
+                "tournament" => {
+                    interactions::tournament_handler::handle(&ctx, component, app_state).await
+                }
                 other => {
                     tracing::debug!(target="component.unhandled", id=%original_id, family=%other, "No handler mapped for component family");
                 }
@@ -209,6 +238,16 @@ impl EventHandler for Handler {
             Command::Sell => commands::economy::sell::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Shop => commands::economy::shop::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Give => commands::economy::give::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Market => {
+                commands::economy::market::run::run_prefix(&ctx, &msg, args_vec).await
+            }
+            Command::Bank => commands::economy::bank::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Gambling => {
+                commands::economy::gambling::run::run_prefix(&ctx, &msg, args_vec).await
+            }
+            Command::Ladder => commands::ladder::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Contest => commands::tavern_games::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Trade => commands::economy::trade::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Open => commands::open::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Saga => commands::saga::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Leaderboard => {
@@ -224,6 +263,10 @@ impl EventHandler for Handler {
             Command::Help => commands::help::run_prefix(&ctx, &msg, args_vec).await,
             Command::Blackjack => commands::blackjack::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Poker => commands::poker::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Holdem => commands::holdem::run::run_prefix(&ctx, &msg, args_vec).await,
+            Command::Tournament => {
+                commands::tournament::run::run_prefix(&ctx, &msg, args_vec).await
+            }
             Command::Bond => commands::bond::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Contracts => commands::contracts::run::run_prefix(&ctx, &msg, args_vec).await,
             Command::Bestiary => commands::bestiary::run::run_prefix(&ctx, &msg, args_vec).await,
@@ -246,6 +289,7 @@ impl EventHandler for Handler {
 
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected and ready!", ready.user.name);
+        commands::games::lifecycle::spawn_sweeper(&ctx);
         let commands_to_register = vec![
             // (✓) DEFINITIVE FIX: Use the full, correct path to each `register` function.
             commands::ping::register(),
@@ -256,6 +300,12 @@ impl EventHandler for Handler {
             commands::economy::sell::run::register(),
             commands::economy::shop::run::register(),
             commands::economy::give::run::register(),
+            commands::economy::market::run::register(),
+            commands::economy::bank::run::register(),
+            commands::economy::gambling::run::register(),
+            commands::ladder::run::register(),
+            commands::tavern_games::run::register(),
+            commands::economy::trade::run::register(),
             commands::open::run::register(),
             commands::saga::run::register(),
             commands::saga::run::register_play(),
@@ -268,6 +318,8 @@ impl EventHandler for Handler {
             commands::questlog::register(),
             commands::blackjack::run::register(),
             commands::poker::run::register(),
+            commands::holdem::run::register(),
+            commands::tournament::run::register(),
             commands::rps::run::register(),
             commands::bond::run::register(),
             commands::contracts::run::register(),
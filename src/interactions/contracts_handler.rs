@@ -1,5 +1,6 @@
 //! Handles interactive contract drafting / acceptance via component interactions.
 use super::util::{defer_component, edit_component, handle_global_nav};
+use crate::saga::negotiation::{Counter, NegotiationOutcome};
 use crate::{AppState, database};
 use serenity::builder::EditInteractionResponse;
 use serenity::model::application::ComponentInteraction;
@@ -38,6 +39,28 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
         }
         return;
     }
+    if cid == "contracts_negotiate_select" {
+        if let Some(id) = parse_first_select(component) {
+            negotiate(ctx, component, &app_state, id, None).await;
+        }
+        return;
+    }
+    if let Some(rest) = cid.strip_prefix("contracts_counter_") {
+        // contracts_counter_<unit_id>_<counter>
+        if let Some((id, key)) = rest.split_once('_')
+            && let Ok(id) = id.parse::<i32>()
+            && let Some(counter) = Counter::from_key(key)
+        {
+            counter_offer(ctx, component, &app_state, id, counter).await;
+        }
+        return;
+    }
+    if let Some(rest) = cid.strip_prefix("contracts_sign_") {
+        if let Ok(id) = rest.parse::<i32>() {
+            accept(ctx, component, &app_state, id).await;
+        }
+        return;
+    }
     if let Some(rest) = cid.strip_prefix("contracts_page_") {
         if let Ok(page) = rest.parse::<usize>() {
             page_nav(ctx, component, &app_state, page).await;
@@ -168,6 +191,74 @@ async fn accept(
     .await;
 }
 
+async fn negotiate(
+    ctx: &Context,
+    component: &mut ComponentInteraction,
+    app_state: &AppState,
+    unit_id: i32,
+    notice: Option<String>,
+) {
+    match database::human::get_negotiation(&app_state.db, component.user.id, unit_id).await {
+        Ok(state) => {
+            let (embed, comps) =
+                crate::commands::contracts::run::build_negotiation_view(&state, notice.as_deref());
+            edit_component(
+                ctx,
+                component,
+                "contracts.negotiate",
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(comps),
+            )
+            .await;
+        }
+        Err(e) => {
+            let (_desc, embed, comps) = load_embed(app_state, component.user.id, 0).await;
+            edit_component(
+                ctx,
+                component,
+                "contracts.negotiate_err",
+                EditInteractionResponse::new()
+                    .embed(embed.description(format!("Negotiation failed: {}", e)))
+                    .components(comps),
+            )
+            .await;
+        }
+    }
+}
+async fn counter_offer(
+    ctx: &Context,
+    component: &mut ComponentInteraction,
+    app_state: &AppState,
+    unit_id: i32,
+    counter: Counter,
+) {
+    let notice =
+        match database::human::counter_contract(&app_state.db, component.user.id, unit_id, counter)
+            .await
+        {
+            Ok((outcome, state)) => match outcome {
+                NegotiationOutcome::Agreed(_) => {
+                    format!("🤝 {} agrees to your counter.", state.unit_name)
+                }
+                NegotiationOutcome::Compromise(_) => {
+                    format!("⚖️ {} meets you halfway.", state.unit_name)
+                }
+                NegotiationOutcome::Offended(_) => format!(
+                    "😠 {} is insulted and raises their demands.",
+                    state.unit_name
+                ),
+                NegotiationOutcome::Refused => format!(
+                    "🚫 {} refuses to give up their party slot.",
+                    state.unit_name
+                ),
+            },
+            Err(e) => format!("⚠️ {}", e),
+        };
+    app_state.invalidate_user_caches(component.user.id).await;
+    negotiate(ctx, component, app_state, unit_id, Some(notice)).await;
+}
+
 async fn load_embed(
     app_state: &AppState,
     user: serenity::model::id::UserId,
@@ -1,62 +1,49 @@
 //! Handles all component interactions for the `craft` command family.
+//! `craft_select_recipe` picks a recipe, `craft_make_{recipe}_{count}` queues a batch and
+//! `craft_refresh` redraws the queue.
 
-use crate::{AppState, database};
+use super::util::{defer_component, edit_component};
+use crate::AppState;
+use crate::commands::craft::run::{build_craft_view, queue_and_describe};
 use serenity::builder::EditInteractionResponse;
-use serenity::model::application::ComponentInteraction;
+use serenity::model::application::{ComponentInteraction, ComponentInteractionDataKind};
 use serenity::prelude::Context;
 use std::sync::Arc;
 
 pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_state: Arc<AppState>) {
     let db = &app_state.db;
-    component.defer_ephemeral(&ctx.http).await.ok();
-
-    // Get the recipe ID that the user selected from the dropdown.
-    let recipe_id_str =
-        if let serenity::model::application::ComponentInteractionDataKind::StringSelect { values } =
-            &component.data.kind
-        {
-            &values[0]
-        } else {
-            return;
+    defer_component(ctx, component).await;
+    let user_id = component.user.id;
+    let custom_id = component.data.custom_id.clone();
+
+    let (selected, notice) = if custom_id == "craft_select_recipe" {
+        // Get the recipe ID that the user selected from the dropdown.
+        let selected = match &component.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => {
+                values.first().and_then(|v| v.parse::<i32>().ok())
+            }
+            _ => None,
         };
-
-    // Safely parse the recipe ID.
-    let recipe_id: i32 = match recipe_id_str.parse() {
-        Ok(id) => id,
-        Err(_) => {
-            let builder =
-                EditInteractionResponse::new().content("❌ Error: Invalid recipe ID selected.");
-            component.edit_response(&ctx.http, builder).await.ok();
+        (selected, None)
+    } else if let Some(rest) = custom_id.strip_prefix("craft_make_") {
+        let Some((recipe_id, count)) = rest
+            .split_once('_')
+            .and_then(|(r, c)| Some((r.parse::<i32>().ok()?, c.parse::<i32>().ok()?)))
+        else {
             return;
-        }
+        };
+        (
+            Some(recipe_id),
+            Some(queue_and_describe(db, user_id, recipe_id, count).await),
+        )
+    } else {
+        (None, None)
     };
 
-    // Call the database function to attempt the craft.
-    let result = database::crafting::craft_item(db, component.user.id, recipe_id).await;
-
-    let mut builder = EditInteractionResponse::new().components(vec![]); // Clear components after crafting.
-
-    match result {
-        Ok(item) => {
-            // (✓) FIXED: Use the correct function signature with a formatted objective key.
-            database::tasks::update_task_progress(
-                db,
-                component.user.id,
-                &format!("CraftItem:{}", item.id()), // e.g., "CraftItem:11"
-                1,
-            )
-            .await
-            .ok();
-
-            builder = builder.content(format!(
-                "✅ You successfully crafted **1x {}**!",
-                item.display_name()
-            ));
-        }
-        Err(e) => {
-            builder = builder.content(format!("❌ Crafting failed: {}", e));
-        }
-    }
-
-    component.edit_response(&ctx.http, builder).await.ok();
+    let (embed, components) = build_craft_view(db, user_id, selected).await;
+    let builder = EditInteractionResponse::new()
+        .content(notice.unwrap_or_default())
+        .embed(embed)
+        .components(components);
+    edit_component(ctx, component, "craft", builder).await;
 }
@@ -1,5 +1,6 @@
 //! Handles all component interactions that are managed by the generic `GameManager`.
-//! This includes RPS, Blackjack, Poker, the Shop, and Battles.
+//! This includes RPS, Blackjack, Poker, the tavern contests, the Shop, Battles, Trades, and the
+//! spectator view with its side bets.
 
 use crate::AppState;
 use crate::commands::games::{Game, GameManager};
@@ -7,12 +8,13 @@ use serenity::builder::EditInteractionResponse;
 use serenity::model::application::ComponentInteraction;
 use serenity::prelude::Context;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_state: Arc<AppState>) {
     let db = &app_state.db;
-    let mut game_manager = app_state.game_manager.write().await;
-    game_manager.on_interaction(ctx, component, db).await;
+    app_state
+        .game_manager
+        .on_interaction(ctx, component, db)
+        .await;
 }
 
 // (✓) NEW: Add the missing `start_new_game` function required by quest_handler.
@@ -20,7 +22,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
 pub async fn start_new_game(
     ctx: &Context,
     component: &mut ComponentInteraction,
-    game_manager: Arc<RwLock<GameManager>>,
+    game_manager: Arc<GameManager>,
     game: Box<dyn Game + Send + Sync>,
     initial_content: &str,
 ) {
@@ -32,6 +34,13 @@ pub async fn start_new_game(
         .components(components);
 
     if let Ok(msg) = component.edit_response(&ctx.http, builder).await {
-        game_manager.write().await.start_game(msg.id, game);
+        let started = game_manager.start_game(msg.channel_id, msg.id, game);
+        if let Err(e) = started {
+            let builder = EditInteractionResponse::new()
+                .content(e)
+                .embeds(vec![])
+                .components(vec![]);
+            component.edit_response(&ctx.http, builder).await.ok();
+        }
     }
 }
@@ -30,6 +30,7 @@ pub const SAGA_TAVERN_BUY_PREFIX: &str = "saga_tavern_buy_"; // followed by item
 pub const SAGA_TAVERN_SHOP_BUY_PREFIX: &str = "saga_tavern_shop_buy_"; // followed by item id
 pub const SAGA_TAVERN_SHOP_BUY_CONFIRM_PREFIX: &str = "saga_tavern_shop_buy_confirm_"; // followed by item id
 pub const SAGA_TAVERN_SHOP_BUY_CANCEL: &str = "saga_tavern_shop_buy_cancel";
+pub const SAGA_TAVERN_SHOP_REPAIR: &str = "saga_tavern_shop_repair";
 pub const SAGA_TAVERN_USE_PREFIX: &str = "saga_tavern_use_"; // followed by item id
 pub const SAGA_TAVERN_BUY_CONFIRM_PREFIX: &str = "saga_tavern_buy_confirm_"; // followed by item id
 pub const SAGA_TAVERN_BUY_CANCEL: &str = "saga_tavern_buy_cancel";
@@ -15,6 +15,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
     let board_type = match component.data.custom_id.as_str() {
         "leaderboard_wealth" => LeaderboardType::Wealth,
         "leaderboard_streak" => LeaderboardType::WorkStreak,
+        "leaderboard_rps" => LeaderboardType::RpsLadder,
         _ => LeaderboardType::Gamemaster, // Default to the main leaderboard.
     };
 
@@ -25,6 +26,10 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
         }
         LeaderboardType::Wealth => database::leaderboard::get_wealth_leaderboard(&db, 10).await,
         LeaderboardType::WorkStreak => database::leaderboard::get_streak_leaderboard(&db, 10).await,
+        LeaderboardType::RpsLadder => {
+            let season = commands::rps::ladder::season_at(chrono::Utc::now());
+            database::leaderboard::get_rps_leaderboard(&db, season, 10).await
+        }
     }
     .unwrap_or_default();
 
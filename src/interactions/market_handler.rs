@@ -0,0 +1,30 @@
+//! Handles marketplace browse pagination (`market_page_{n}`).
+use super::util::{defer_component, edit_component};
+use crate::AppState;
+use serenity::builder::EditInteractionResponse;
+use serenity::model::application::ComponentInteraction;
+use serenity::prelude::Context;
+use std::sync::Arc;
+
+pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_state: Arc<AppState>) {
+    defer_component(ctx, component).await;
+    let Some(page) = component
+        .data
+        .custom_id
+        .strip_prefix("market_page_")
+        .and_then(|p| p.parse::<i64>().ok())
+    else {
+        return;
+    };
+    let (embed, components) =
+        crate::commands::economy::market::run::build_browse_view(&app_state.db, page).await;
+    edit_component(
+        ctx,
+        component,
+        "market_page",
+        EditInteractionResponse::new()
+            .embed(embed)
+            .components(components),
+    )
+    .await;
+}
@@ -12,11 +12,13 @@ pub mod craft_handler;
 pub mod game_handler;
 pub mod ids;
 pub mod leaderboard_handler;
+pub mod market_handler;
 pub mod party_handler;
 pub mod quest_handler;
 pub mod questlog_handler;
 pub mod research_handler;
 pub mod saga_handler;
 pub mod task_handler;
+pub mod tournament_handler;
 pub mod train_handler;
 pub mod util; // shared helpers for defer/edit // central custom_id constants
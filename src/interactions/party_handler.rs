@@ -16,6 +16,88 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
 
     let action = component.data.custom_id.split('_').nth(1).unwrap_or("");
 
+    if action == "presets" {
+        let (embed, components) =
+            commands::party::ui::create_preset_view(&app_state, component.user.id).await;
+        edit_component(
+            ctx,
+            component,
+            "party.presets",
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+                .content(""),
+        )
+        .await;
+        return;
+    }
+
+    if action == "formation" || action == "rowtoggle" {
+        let mut message = String::new();
+        if action == "rowtoggle" {
+            message = toggle_formation_row(component, &app_state).await;
+        }
+        let (embed, components) =
+            commands::party::ui::create_formation_view(&app_state, component.user.id).await;
+        edit_component(
+            ctx,
+            component,
+            "party.formation",
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+                .content(message),
+        )
+        .await;
+        return;
+    }
+
+    if action == "upkeep" || action == "gift" {
+        let mut message = String::new();
+        if action == "gift" {
+            message = gift_unit(component, &app_state).await;
+        }
+        let (embed, components) =
+            commands::party::ui::create_upkeep_view(&app_state, component.user.id).await;
+        edit_component(
+            ctx,
+            component,
+            "party.upkeep",
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+                .content(message),
+        )
+        .await;
+        return;
+    }
+
+    if action == "gear" || action == "gearunit" || action == "gearequip" {
+        let (selected, message) = match action {
+            "gearunit" => (selected_unit(component), String::new()),
+            "gearequip" => change_gear(component, &app_state).await,
+            _ => (None, String::new()),
+        };
+        let (embed, components) =
+            commands::party::ui::create_gear_view(&app_state, component.user.id, selected).await;
+        edit_component(
+            ctx,
+            component,
+            "party.gear",
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+                .content(message),
+        )
+        .await;
+        return;
+    }
+
+    if action == "presetload" || action == "presetdelete" {
+        handle_preset_select(ctx, component, &app_state, action).await;
+        return;
+    }
+
     let unit_id_str =
         if let serenity::model::application::ComponentInteractionDataKind::StringSelect { values } =
             &component.data.kind
@@ -87,3 +169,197 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
     )
     .await;
 }
+
+/// Load / delete a preset chosen from the preset view select menus.
+async fn handle_preset_select(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    app_state: &Arc<AppState>,
+    action: &str,
+) {
+    let db = &app_state.db;
+    let preset_id = match &component.data.kind {
+        serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().and_then(|v| v.parse::<i32>().ok())
+        }
+        _ => None,
+    };
+    let name = match preset_id {
+        Some(id) => database::presets::preset_name_by_id(db, component.user.id, id)
+            .await
+            .ok()
+            .flatten(),
+        None => None,
+    };
+    let Some(name) = name else {
+        edit_component(
+            ctx,
+            component,
+            "party.preset_missing",
+            EditInteractionResponse::new().content("That preset no longer exists."),
+        )
+        .await;
+        return;
+    };
+
+    if action == "presetdelete" {
+        let message = match database::presets::delete_preset(db, component.user.id, &name).await {
+            Ok(true) => format!("🗑️ Deleted preset **{}**.", name),
+            _ => "Failed to delete the preset.".to_string(),
+        };
+        let (embed, components) =
+            commands::party::ui::create_preset_view(app_state, component.user.id).await;
+        edit_component(
+            ctx,
+            component,
+            "party.preset_delete",
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components)
+                .content(message),
+        )
+        .await;
+        return;
+    }
+
+    match database::presets::load_party_preset(db, component.user.id, &name).await {
+        Ok(outcome) => {
+            app_state.invalidate_user_caches(component.user.id).await;
+            let (embed, components) =
+                commands::party::ui::create_party_view_with_bonds(app_state, component.user.id)
+                    .await;
+            edit_component(
+                ctx,
+                component,
+                "party.preset_load",
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(components)
+                    .content(commands::party::run::preset_loaded_line(&outcome)),
+            )
+            .await;
+        }
+        Err(e) => {
+            let (embed, components) =
+                commands::party::ui::create_preset_view(app_state, component.user.id).await;
+            edit_component(
+                ctx,
+                component,
+                "party.preset_load_err",
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(components)
+                    .content(e),
+            )
+            .await;
+        }
+    }
+}
+
+/// Flip the selected party member between the front and back row; returns a status line.
+async fn toggle_formation_row(
+    component: &ComponentInteraction,
+    app_state: &Arc<AppState>,
+) -> String {
+    let unit_id = match &component.data.kind {
+        serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().and_then(|v| v.parse::<i32>().ok())
+        }
+        _ => None,
+    };
+    let Some(unit_id) = unit_id else {
+        return "Invalid unit selected.".to_string();
+    };
+    let formation = database::units::get_party_formation(&app_state.db, component.user.id)
+        .await
+        .unwrap_or_default();
+    let current = formation.get(&unit_id).map(|(r, _)| *r).unwrap_or_default();
+    match database::units::set_unit_formation_row(
+        &app_state.db,
+        component.user.id,
+        unit_id,
+        current.toggled(),
+    )
+    .await
+    {
+        Ok(true) => format!("Moved to the {} Row.", current.toggled().label()),
+        _ => "Only active party members can be arranged.".to_string(),
+    }
+}
+
+/// Spend coins on a gift for the selected human; returns a status line.
+async fn gift_unit(component: &ComponentInteraction, app_state: &Arc<AppState>) -> String {
+    let unit_id = match &component.data.kind {
+        serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().and_then(|v| v.parse::<i32>().ok())
+        }
+        _ => None,
+    };
+    let Some(unit_id) = unit_id else {
+        return "Invalid unit selected.".to_string();
+    };
+    match database::upkeep::gift_unit(&app_state.db, component.user.id, unit_id).await {
+        Ok((name, morale)) => {
+            app_state.invalidate_user_caches(component.user.id).await;
+            format!(
+                "🎁 {} appreciates the gift. Morale is now {}.",
+                name, morale
+            )
+        }
+        Err(e) => e,
+    }
+}
+
+/// The unit picked in the gear view's unit menu.
+fn selected_unit(component: &ComponentInteraction) -> Option<i32> {
+    match &component.data.kind {
+        serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().and_then(|v| v.parse::<i32>().ok())
+        }
+        _ => None,
+    }
+}
+
+/// Equip the chosen item on (or unequip a slot from) the unit named in the custom id
+/// (`party_gearequip_{unit}`); returns the unit to keep selected and a status line.
+async fn change_gear(
+    component: &ComponentInteraction,
+    app_state: &Arc<AppState>,
+) -> (Option<i32>, String) {
+    use crate::database::models::GearSlot;
+    let Some(unit_id) = component
+        .data
+        .custom_id
+        .rsplit('_')
+        .next()
+        .and_then(|v| v.parse::<i32>().ok())
+    else {
+        return (None, "Invalid unit selected.".to_string());
+    };
+    let choice = match &component.data.kind {
+        serenity::model::application::ComponentInteractionDataKind::StringSelect { values } => {
+            values.first().cloned().unwrap_or_default()
+        }
+        _ => String::new(),
+    };
+    let result = if let Some(label) = choice.strip_prefix("slot:") {
+        match GearSlot::ALL.into_iter().find(|s| s.label() == label) {
+            Some(slot) => {
+                database::gear::unequip_gear(&app_state.db, component.user.id, unit_id, slot).await
+            }
+            None => Err("Unknown gear slot.".to_string()),
+        }
+    } else {
+        match choice.parse::<i32>() {
+            Ok(item_id) => {
+                database::gear::equip_gear(&app_state.db, component.user.id, unit_id, item_id).await
+            }
+            Err(_) => Err("Invalid gear selected.".to_string()),
+        }
+    };
+    let message = match result {
+        Ok(line) => format!("🗡️ {}", line),
+        Err(e) => e,
+    };
+    (Some(unit_id), message)
+}
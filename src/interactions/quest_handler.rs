@@ -3,13 +3,13 @@
 use crate::commands::games::GameManager;
 use crate::database;
 use crate::saga::battle::game::BattleGame;
+use crate::saga::battle::modifiers::PartyModifiers;
 use crate::saga::battle::state::{BattleSession, BattleUnit};
 use crate::{AppState, interactions};
 use serenity::all::EditInteractionResponse;
 use serenity::model::application::ComponentInteraction;
 use serenity::prelude::Context;
 use std::sync::Arc;
-use tokio::sync::RwLock;
 
 /// The main entry point for quest-related component interactions.
 pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_state: Arc<AppState>) {
@@ -70,8 +70,7 @@ async fn start_quest_battle(
     ctx: &Context,
     component: &mut ComponentInteraction,
     quest: database::quests::AcceptedQuest,
-    // (✓) FIXED: Use the correct GameManager type: Arc<RwLock<GameManager>> to fix E0308.
-    game_manager: Arc<RwLock<GameManager>>,
+    game_manager: Arc<GameManager>,
 ) {
     let db = if let Some(app) = AppState::from_ctx(ctx).await {
         app.db.clone()
@@ -116,14 +115,16 @@ async fn start_quest_battle(
     let bonuses = database::units::get_equipment_bonuses(&db, component.user.id)
         .await
         .unwrap_or_default();
+    let modifiers = PartyModifiers::load(&db, component.user.id).await;
     let player_units: Vec<BattleUnit> = player_party_db
         .iter()
         .map(|u| {
-            if let Some(b) = bonuses.get(&u.player_unit_id) {
+            let unit = if let Some(b) = bonuses.get(&u.player_unit_id) {
                 BattleUnit::from_player_unit_with_bonus(u, *b)
             } else {
                 BattleUnit::from_player_unit(u)
-            }
+            };
+            modifiers.apply(u.player_unit_id, unit)
         })
         .collect();
     let enemy_units: Vec<BattleUnit> = enemy_pets_db.iter().map(BattleUnit::from_unit).collect();
@@ -137,6 +138,7 @@ async fn start_quest_battle(
         can_afford_recruit: false,
         player_quest_id: Some(quest.player_quest_id),
         claimed: false,
+        gear_worn: false,
     };
 
     interactions::game_handler::start_new_game(
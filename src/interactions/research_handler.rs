@@ -1,8 +1,8 @@
-//! Handles research refresh component.
+//! Handles research view components (refresh + research tree unlocks).
 use super::util::{defer_component, handle_global_nav};
 use crate::AppState;
 use serenity::builder::EditInteractionResponse;
-use serenity::model::application::ComponentInteraction;
+use serenity::model::application::{ComponentInteraction, ComponentInteractionDataKind};
 use serenity::prelude::Context;
 use std::sync::Arc;
 use tracing::instrument;
@@ -17,16 +17,49 @@ pub async fn handle(
     if handle_global_nav(ctx, component, &_app_state, "saga").await {
         return;
     }
-    if component.data.custom_id != "research_refresh" {
-        return;
-    }
     let Some(state) = AppState::from_ctx(ctx).await else {
         return;
     };
+    let notice = match component.data.custom_id.as_str() {
+        "research_refresh" => String::new(),
+        "research_unlock" => {
+            let key = match &component.data.kind {
+                ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
+                _ => None,
+            };
+            match key {
+                Some(key) => {
+                    match crate::database::research::unlock_research_node(
+                        &state.db,
+                        component.user.id,
+                        &key,
+                    )
+                    .await
+                    {
+                        Ok(node) => format!(
+                            "🔬 Unlocked **{}**: {}.",
+                            node.name,
+                            node.passive.describe()
+                        ),
+                        Err(e) => e,
+                    }
+                }
+                None => "Invalid research node selected.".to_string(),
+            }
+        }
+        _ => return,
+    };
     // rebuild research view using cached variant
-    let embed = crate::commands::research::run::build_view_cached(&state, component.user.id).await;
+    let (embed, components) =
+        crate::commands::research::run::build_research_view(&state, component.user.id).await;
     component
-        .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .content(notice)
+                .embed(embed)
+                .components(components),
+        )
         .await
         .ok();
 }
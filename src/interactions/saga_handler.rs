@@ -4,7 +4,10 @@ use crate::commands::games::Game;
 use crate::saga::battle::game::BattleGame;
 // (✓) FIXED: Import the specific structs needed, removing the unused `BattlePhase`.
 use super::util::{defer_component, edit_component, handle_global_nav, handle_saga_back_refresh};
+use crate::commands::economy::core::registry::{ItemDef, registry};
 use crate::constants::EQUIP_BONUS_CACHE_TTL_SECS;
+use crate::database::models::LedgerReason;
+use crate::saga::battle::modifiers::PartyModifiers;
 use crate::saga::battle::state::{BattleSession, BattleUnit};
 use crate::saga::view::{SagaView, push_and_render};
 use crate::services::cache as cache_service;
@@ -35,10 +38,19 @@ async fn render_tavern_games_view(
         .field("Poker", "Five Card Draw.", true)
         .field(
             "Arm Wrestling",
-            "Pick a party member. Tests Strength.",
+            "A stamina duel with the house champion. Tests Strength.",
             true,
         )
-        .field("Darts", "Pick a party member. Tests Dexterity.", true)
+        .field(
+            "Darts",
+            "Six darts each against the house. Tests Dexterity.",
+            true,
+        )
+        .field(
+            "Challenges",
+            "Use `/contest` to take on another player's unit, with or without a wager.",
+            false,
+        )
         .color(crate::ui::style::COLOR_SAGA_TAVERN);
     let mut rows = Vec::with_capacity(4);
     // Row 1: Card games
@@ -83,19 +95,43 @@ async fn render_tavern_games_view(
 // Local cache helpers removed (centralized in services::saga).
 
 // Centralized Tavern pricing for Goods and Small Arms menus.
-fn tavern_price(item: crate::commands::economy::core::item::Item) -> Option<i64> {
+fn tavern_price(item: &ItemDef) -> Option<i64> {
     use crate::commands::economy::core::item::Item as I;
-    match item {
-        I::HealthPotion => Some(50),
-        I::FocusTonic => Some(125),
-        I::StaminaDraft => Some(125),
-        I::TamingLure => Some(200),
-        I::GreaterHealthPotion => Some(150),
-        I::XpBooster => item.properties().buy_price.or(Some(2000)),
-        I::ForestContractParchment => Some(300),
-        I::FrontierContractParchment => Some(500),
-        _ => None,
+    if item.id == I::XpBooster.id() {
+        return item.buy_price.or(Some(2000));
     }
+    [
+        (I::HealthPotion, 50),
+        (I::FocusTonic, 125),
+        (I::StaminaDraft, 125),
+        (I::TamingLure, 200),
+        (I::GreaterHealthPotion, 150),
+        (I::ForestContractParchment, 300),
+        (I::FrontierContractParchment, 500),
+        (I::IronSword, 250),
+        (I::LeatherArmor, 250),
+        (I::LuckyCharm, 400),
+    ]
+    .into_iter()
+    .find(|(handle, _)| handle.id() == item.id)
+    .map(|(_, price)| price)
+}
+
+// Final Tavern cost after the fame discount; Taming Lures also take the research tree discount.
+// The list price moves with supply and demand before any discount applies.
+fn tavern_cost(
+    item: &ItemDef,
+    shop_disc: f32,
+    lure_disc: f32,
+    price: crate::commands::economy::core::pricing::PriceState,
+) -> i64 {
+    let base = price.price(tavern_price(item).unwrap_or(1_000_000));
+    let rate = if item.id == crate::commands::economy::core::item::Item::TamingLure.id() {
+        (shop_disc + lure_disc).min(0.9)
+    } else {
+        shop_disc
+    };
+    crate::commands::saga::tavern::apply_shop_discount(base, rate)
 }
 
 // Small helper: render the Tavern Goods view consistently, with optional notice line.
@@ -128,12 +164,6 @@ async fn render_tavern_goods_view(
             balance: 0,
             last_work: None,
             work_streak: 0,
-            fishing_xp: 0,
-            fishing_level: 1,
-            mining_xp: 0,
-            mining_level: 1,
-            coding_xp: 0,
-            coding_level: 1,
         });
     let (_, meta_tmp) =
         crate::commands::saga::tavern::build_tavern_state_cached(app_state, component.user.id)
@@ -185,15 +215,27 @@ async fn render_tavern_goods_view(
             true,
         )
         .color(crate::ui::style::COLOR_SAGA_TAVERN);
+    let lure_disc = database::research::get_research_perks(db, component.user.id)
+        .await
+        .lure_discount;
+    let catalog_ids: Vec<i32> = catalog.iter().map(|i| i.id()).collect();
+    let prices = database::pricing::get_price_states(db, &catalog_ids).await;
     let mut buy_buttons = Vec::new();
-    for item in &catalog {
-        let base = tavern_price(*item).unwrap_or(1_000_000);
-        let cost = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
-        let label = format!("{} {}", item.emoji(), item.display_name());
-        let desc = item.properties().description;
+    let items = registry();
+    for item in catalog.iter().filter_map(|i| items.get(i.id())) {
+        let price = prices.get(&item.id).copied().unwrap_or_default();
+        let cost = tavern_cost(item, shop_disc, lure_disc, price);
+        let label = format!("{} {}", item.emoji, item.name);
+        let desc = &item.description;
         embed = embed.field(
             label,
-            format!("{}\nCost: {} {}", desc, crate::ui::style::EMOJI_COIN, cost),
+            format!(
+                "{}\nCost: {} {} {}",
+                desc,
+                crate::ui::style::EMOJI_COIN,
+                cost,
+                price.trend_label()
+            ),
             true,
         );
         buy_buttons.push(
@@ -201,9 +243,9 @@ async fn render_tavern_goods_view(
                 &format!(
                     "{}{}",
                     crate::interactions::ids::SAGA_TAVERN_BUY_PREFIX,
-                    item.id()
+                    item.id
                 ),
-                &format!("Buy {} {}", item.emoji(), item.display_name()),
+                &format!("Buy {} {}", item.emoji, item.name),
             )
             .disabled(profile.balance < cost),
         );
@@ -219,41 +261,26 @@ async fn render_tavern_goods_view(
             .flatten()
             .map(|i| i.quantity)
             .unwrap_or(0);
+        let name = items.name_of(use_item.id());
+        let emoji = items
+            .get(use_item.id())
+            .map(|d| d.emoji.as_str())
+            .unwrap_or_default();
         let label = match use_item {
             Item::FocusTonic => {
                 if let Some((active, remaining)) = &focus_state {
                     if *active {
                         let mins = remaining.as_secs() / 60;
                         let secs = remaining.as_secs() % 60;
-                        format!(
-                            "{} Focus active ({:02}:{:02})",
-                            use_item.emoji(),
-                            mins,
-                            secs
-                        )
+                        format!("{} Focus active ({:02}:{:02})", emoji, mins, secs)
                     } else {
-                        format!(
-                            "Use {} {} ({} in bag)",
-                            use_item.emoji(),
-                            use_item.display_name(),
-                            qty
-                        )
+                        format!("Use {} {} ({} in bag)", emoji, name, qty)
                     }
                 } else {
-                    format!(
-                        "Use {} {} ({} in bag)",
-                        use_item.emoji(),
-                        use_item.display_name(),
-                        qty
-                    )
+                    format!("Use {} {} ({} in bag)", emoji, name, qty)
                 }
             }
-            Item::StaminaDraft => format!(
-                "Use {} {} ({} in bag)",
-                use_item.emoji(),
-                use_item.display_name(),
-                qty
-            ),
+            Item::StaminaDraft => format!("Use {} {} ({} in bag)", emoji, name, qty),
             _ => String::new(),
         };
         use_buttons.push(
@@ -309,12 +336,6 @@ async fn render_tavern_shop_view(
             balance: 0,
             last_work: None,
             work_streak: 0,
-            fishing_xp: 0,
-            fishing_level: 1,
-            mining_xp: 0,
-            mining_level: 1,
-            coding_xp: 0,
-            coding_level: 1,
         });
     let (_, meta_tmp) =
         crate::commands::saga::tavern::build_tavern_state_cached(app_state, component.user.id)
@@ -354,15 +375,27 @@ async fn render_tavern_shop_view(
             true,
         )
         .color(crate::ui::style::COLOR_SAGA_TAVERN);
+    let lure_disc = database::research::get_research_perks(db, component.user.id)
+        .await
+        .lure_discount;
+    let catalog_ids: Vec<i32> = catalog.iter().map(|i| i.id()).collect();
+    let prices = database::pricing::get_price_states(db, &catalog_ids).await;
     let mut buy_buttons = Vec::new();
-    for item in &catalog {
-        let base = tavern_price(*item).unwrap_or(1_000_000);
-        let cost = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
-        let label = format!("{} {}", item.emoji(), item.display_name());
-        let desc = item.properties().description;
+    let items = registry();
+    for item in catalog.iter().filter_map(|i| items.get(i.id())) {
+        let price = prices.get(&item.id).copied().unwrap_or_default();
+        let cost = tavern_cost(item, shop_disc, lure_disc, price);
+        let label = format!("{} {}", item.emoji, item.name);
+        let desc = &item.description;
         embed = embed.field(
             label,
-            format!("{}\nCost: {} {}", desc, crate::ui::style::EMOJI_COIN, cost),
+            format!(
+                "{}\nCost: {} {} {}",
+                desc,
+                crate::ui::style::EMOJI_COIN,
+                cost,
+                price.trend_label()
+            ),
             true,
         );
         buy_buttons.push(
@@ -370,14 +403,53 @@ async fn render_tavern_shop_view(
                 &format!(
                     "{}{}",
                     crate::interactions::ids::SAGA_TAVERN_SHOP_BUY_PREFIX,
-                    *item as i32
+                    item.id
                 ),
-                &format!("Buy {} {}", item.emoji(), item.display_name()),
+                &format!("Buy {} {}", item.emoji, item.name),
             )
             .disabled(profile.balance < cost),
         );
     }
     let mut rows = vec![CreateActionRow::Buttons(buy_buttons)];
+    // Gear repairs for everything equipped on the player's units
+    let gear = database::gear::get_user_gear(db, component.user.id)
+        .await
+        .unwrap_or_default();
+    let damaged: Vec<_> = gear
+        .iter()
+        .filter(|g| g.durability < g.max_durability)
+        .collect();
+    let repair_total: i64 = damaged
+        .iter()
+        .map(|g| crate::saga::gear::repair_cost(g))
+        .sum();
+    let repair_text = if gear.is_empty() {
+        "No gear equipped. Equip gear from `/party` → Gear.".to_string()
+    } else if damaged.is_empty() {
+        "All equipped gear is in good shape.".to_string()
+    } else {
+        let broken = damaged
+            .iter()
+            .filter(|g| crate::saga::gear::is_broken(g))
+            .count();
+        format!(
+            "{} damaged item(s), {} broken. Full repair: {} {}",
+            damaged.len(),
+            broken,
+            crate::ui::style::EMOJI_COIN,
+            repair_total
+        )
+    };
+    embed = embed.field("🔧 Gear Repairs", repair_text, false);
+    if !damaged.is_empty() {
+        rows.push(CreateActionRow::Buttons(vec![
+            crate::ui::buttons::Btn::primary(
+                crate::interactions::ids::SAGA_TAVERN_SHOP_REPAIR,
+                &format!("🔧 Repair All ({})", repair_total),
+            )
+            .disabled(profile.balance < repair_total),
+        ]));
+    }
     // Home + Recruitment row
     rows.push(CreateActionRow::Buttons(vec![
         crate::ui::buttons::Btn::secondary(crate::interactions::ids::SAGA_TAVERN_HOME, "🏰 Tavern"),
@@ -424,7 +496,7 @@ async fn render_tavern_home_view(
         )
         .field(
             "5) Small Arms & Petty Equipment",
-            "Basic gear until you find better shops",
+            "Basic gear until you find better shops, plus gear repairs",
             false,
         );
     let buttons = vec![
@@ -651,7 +723,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            let Some(item) = Item::from_i32(item_id) else {
+            if registry().get(item_id).is_none() {
                 edit_component(
                     ctx,
                     component,
@@ -660,9 +732,9 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 )
                 .await;
                 return;
-            };
-            match item {
-                Item::FocusTonic => {
+            }
+            match item_id {
+                id if id == Item::FocusTonic.id() => {
                     // Atomically decrement inventory; on success, set buff cache
                     let mut tx = match db.begin().await {
                         Ok(t) => t,
@@ -684,6 +756,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                         component.user.id,
                         Item::FocusTonic,
                         -1,
+                        LedgerReason::ItemUse,
                     )
                     .await
                     {
@@ -720,7 +793,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                         }
                     }
                 }
-                Item::StaminaDraft => {
+                id if id == Item::StaminaDraft.id() => {
                     // Attempt to restore AP if not full; else restore TP up to a small amount.
                     // Start a transaction to deduct the item, then update saga profile accordingly.
                     let mut tx = match db.begin().await {
@@ -752,6 +825,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                                 component.user.id,
                                 Item::StaminaDraft,
                                 -1,
+                                LedgerReason::ItemUse,
                             )
                             .await
                             {
@@ -1206,7 +1280,6 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
             }
         }
         Some(&"tavern") if raw_id.starts_with(crate::interactions::ids::SAGA_TAVERN_BUY_PREFIX) => {
-            use crate::commands::economy::core::item::Item;
             use serenity::builder::{CreateActionRow, CreateEmbed};
             let id_str =
                 raw_id.trim_start_matches(crate::interactions::ids::SAGA_TAVERN_BUY_PREFIX);
@@ -1220,7 +1293,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            let Some(item) = Item::from_i32(item_id) else {
+            let Some(item) = registry().get(item_id).cloned() else {
                 edit_component(
                     ctx,
                     component,
@@ -1254,17 +1327,21 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 },
             ));
             let (shop_disc, _, _) = crate::commands::saga::tavern::fame_perks(meta_tmp.fame_tier);
-            let base = tavern_price(item).unwrap_or(1_000_000);
-            let cost: i64 = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
+            let lure_disc =
+                database::research::get_research_perks(&app_state.db, component.user.id)
+                    .await
+                    .lure_discount;
+            let price = database::pricing::get_price_state(db, item.id).await;
+            let cost: i64 = tavern_cost(&item, shop_disc, lure_disc, price);
             let mut embed = CreateEmbed::new()
                 .title("Confirm Purchase")
                 .description(format!(
                     "Buy {} {} for {} {}?\n{}",
-                    item.emoji(),
-                    item.display_name(),
+                    item.emoji,
+                    item.name,
                     crate::ui::style::EMOJI_COIN,
                     cost,
-                    item.properties().description
+                    item.description
                 ))
                 .field(
                     "Your Balance",
@@ -1319,7 +1396,6 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
         Some(&"tavern")
             if raw_id.starts_with(crate::interactions::ids::SAGA_TAVERN_BUY_CONFIRM_PREFIX) =>
         {
-            use crate::commands::economy::core::item::Item;
             let id_str =
                 raw_id.trim_start_matches(crate::interactions::ids::SAGA_TAVERN_BUY_CONFIRM_PREFIX);
             let Some(item_id) = id_str.parse::<i32>().ok() else {
@@ -1332,7 +1408,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            let Some(item) = Item::from_i32(item_id) else {
+            let Some(item) = registry().get(item_id).cloned() else {
                 edit_component(
                     ctx,
                     component,
@@ -1367,8 +1443,12 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 },
             ));
             let (shop_disc, _, _) = crate::commands::saga::tavern::fame_perks(meta_tmp.fame_tier);
-            let base = tavern_price(item).unwrap_or(1_000_000);
-            let cost: i64 = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
+            let lure_disc =
+                database::research::get_research_perks(&app_state.db, component.user.id)
+                    .await
+                    .lure_discount;
+            let price = database::pricing::get_price_state(db, item.id).await;
+            let cost: i64 = tavern_cost(&item, shop_disc, lure_disc, price);
             // Atomic purchase
             let mut tx = match db.begin().await {
                 Ok(t) => t,
@@ -1384,15 +1464,27 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     return;
                 }
             };
-            let res = {
-                match crate::database::economy::add_balance(&mut tx, component.user.id, -cost).await
+            let band = database::pricing::get_price_band(db).await;
+            let res = if let Err(e) =
+                database::pricing::record_trade(&mut tx, item.id, 1, 0, band).await
+            {
+                Err(e)
+            } else {
+                match crate::database::economy::add_balance(
+                    &mut tx,
+                    component.user.id,
+                    -cost,
+                    LedgerReason::Tavern.with_ref("item", item.id),
+                )
+                .await
                 {
                     Ok(()) => {
                         crate::database::economy::add_to_inventory(
                             &mut tx,
                             component.user.id,
-                            item,
+                            item.id,
                             1,
+                            LedgerReason::Tavern,
                         )
                         .await
                     }
@@ -1415,7 +1507,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     component.data.custom_id = crate::interactions::ids::SAGA_TAVERN_GOODS.into();
                     let notice = Some(format!(
                         "You bought {} for {} {}.",
-                        item.display_name(),
+                        item.name,
                         crate::ui::style::EMOJI_COIN,
                         cost
                     ));
@@ -1442,28 +1534,40 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
             // Start a friendly (no-ante) Blackjack table immediately
             use crate::commands::blackjack::state::BlackjackGame;
             let game = BlackjackGame::new(Arc::new(component.user.clone()), 0);
-            let mut gm = app_state.game_manager.write().await;
+            let gm = &app_state.game_manager;
             let (content, embed, components) = game.render();
             let builder = EditInteractionResponse::new()
                 .content(content)
                 .embed(embed)
                 .components(components);
-            if let Ok(msg) = component.edit_response(&ctx.http, builder).await {
-                gm.start_game(msg.id, Box::new(game));
+            if let Ok(msg) = component.edit_response(&ctx.http, builder).await
+                && let Err(e) = gm.start_game(msg.channel_id, msg.id, Box::new(game))
+            {
+                let builder = EditInteractionResponse::new()
+                    .content(e)
+                    .embeds(vec![])
+                    .components(vec![]);
+                component.edit_response(&ctx.http, builder).await.ok();
             }
         }
         Some(&"tavern") if raw_id == crate::interactions::ids::SAGA_TAVERN_GAMES_POKER => {
             // Start a friendly (no-ante) Poker table immediately
             use crate::commands::poker::state::PokerGame;
             let game = PokerGame::new(Arc::new(component.user.clone()), 0);
-            let mut gm = app_state.game_manager.write().await;
+            let gm = &app_state.game_manager;
             let (content, embed, components) = game.render();
             let builder = EditInteractionResponse::new()
                 .content(content)
                 .embed(embed)
                 .components(components);
-            if let Ok(msg) = component.edit_response(&ctx.http, builder).await {
-                gm.start_game(msg.id, Box::new(game));
+            if let Ok(msg) = component.edit_response(&ctx.http, builder).await
+                && let Err(e) = gm.start_game(msg.channel_id, msg.id, Box::new(game))
+            {
+                let builder = EditInteractionResponse::new()
+                    .content(e)
+                    .embeds(vec![])
+                    .components(vec![]);
+                component.edit_response(&ctx.http, builder).await.ok();
             }
         }
         Some(&"tavern")
@@ -1519,7 +1623,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
         Some(&"tavern")
             if raw_id.starts_with(crate::interactions::ids::SAGA_TAVERN_GAMES_PLAY_PREFIX) =>
         {
-            // Resolve the mini-game with a simple stat check
+            // Start the chosen contest with the picked unit
             let parts: Vec<&str> = raw_id.split('_').collect();
             let game = parts.get(4).copied().unwrap_or("arm");
             let unit_id = parts.get(5).and_then(|s| s.parse::<i32>().ok());
@@ -1546,59 +1650,14 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            // Stat check: stat bonus + d20 vs. DC (seeded for fairness)
-            let (stat, dc, label, stat_name) = if game == "arm" {
-                (unit.current_attack, 13, "Arm Wrestling", "Strength")
+            // Play the contest for real against the house champion.
+            let kind = if game == "arm" {
+                crate::commands::tavern_games::game::ContestKind::ArmWrestling
             } else {
-                (unit.current_defense, 13, "Darts", "Dexterity")
+                crate::commands::tavern_games::game::ContestKind::Darts
             };
-            let today = chrono::Utc::now().date_naive();
-            let seed = (component.user.id.get())
-                ^ (((today.year() as u64) << 32) ^ (today.ordinal() as u64))
-                ^ (unit_id as u64);
-            let roll = (crate::commands::saga::tavern::splitmix64(seed) % 20 + 1) as i32; // d20
-            let score = stat / 10 + roll; // stat bonus scaled
-            let success = score >= dc;
-            let mut embed = serenity::builder::CreateEmbed::new()
-                .title(format!("{} — Result", label))
-                .description(format!(
-                    "{} used {} ({}), rolled d20 = {} ➜ total {} vs DC {} → {}",
-                    unit.name,
-                    stat_name,
-                    stat,
-                    roll,
-                    score,
-                    dc,
-                    if success { "Win" } else { "Loss" }
-                ))
-                .color(crate::ui::style::COLOR_SAGA_TAVERN);
-            if success {
-                embed = embed.field(
-                    "Winnings",
-                    format!("{} {}", crate::ui::style::EMOJI_COIN, 25),
-                    true,
-                );
-                if let Ok(mut tx) = db.begin().await {
-                    let _ =
-                        crate::database::economy::add_balance(&mut tx, component.user.id, 25).await;
-                    let _ = tx.commit().await;
-                }
-            }
-            // Provide quick return to Tavern
-            let rows = vec![
-                serenity::builder::CreateActionRow::Buttons(vec![
-                    crate::ui::buttons::Btn::secondary(
-                        crate::interactions::ids::SAGA_TAVERN_HOME,
-                        "🏰 Tavern",
-                    ),
-                ]),
-                crate::commands::saga::ui::tavern_saga_row(),
-            ];
-            edit_component(
-                ctx,
-                component,
-                "tavern.games.result",
-                EditInteractionResponse::new().embed(embed).components(rows),
+            crate::commands::tavern_games::run::start_from_tavern(
+                ctx, component, &app_state, kind, &unit,
             )
             .await;
         }
@@ -1648,10 +1707,22 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
         Some(&"tavern") if raw_id == crate::interactions::ids::SAGA_TAVERN_SHOP => {
             render_tavern_shop_view(ctx, component, &app_state, None).await;
         }
+        Some(&"tavern") if raw_id == crate::interactions::ids::SAGA_TAVERN_SHOP_REPAIR => {
+            let notice = match database::gear::repair_all_gear(db, component.user.id).await {
+                Ok((repaired, cost)) => format!(
+                    "🔧 Repaired {} item(s) for {} {}.",
+                    repaired,
+                    crate::ui::style::EMOJI_COIN,
+                    cost
+                ),
+                Err(e) => e,
+            };
+            component.data.custom_id = crate::interactions::ids::SAGA_TAVERN_SHOP.into();
+            render_tavern_shop_view(ctx, component, &app_state, Some(notice)).await;
+        }
         Some(&"tavern")
             if raw_id.starts_with(crate::interactions::ids::SAGA_TAVERN_SHOP_BUY_PREFIX) =>
         {
-            use crate::commands::economy::core::item::Item;
             use serenity::builder::{CreateActionRow, CreateEmbed};
             let id_str =
                 raw_id.trim_start_matches(crate::interactions::ids::SAGA_TAVERN_SHOP_BUY_PREFIX);
@@ -1665,7 +1736,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            let Some(item) = Item::from_i32(item_id) else {
+            let Some(item) = registry().get(item_id).cloned() else {
                 edit_component(
                     ctx,
                     component,
@@ -1699,17 +1770,21 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 },
             ));
             let (shop_disc, _, _) = crate::commands::saga::tavern::fame_perks(meta_tmp.fame_tier);
-            let base = tavern_price(item).unwrap_or(1_000_000);
-            let cost: i64 = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
+            let lure_disc =
+                database::research::get_research_perks(&app_state.db, component.user.id)
+                    .await
+                    .lure_discount;
+            let price = database::pricing::get_price_state(db, item.id).await;
+            let cost: i64 = tavern_cost(&item, shop_disc, lure_disc, price);
             let mut embed = CreateEmbed::new()
                 .title("Confirm Purchase — Small Arms")
                 .description(format!(
                     "Buy {} {} for {} {}?\n{}",
-                    item.emoji(),
-                    item.display_name(),
+                    item.emoji,
+                    item.name,
                     crate::ui::style::EMOJI_COIN,
                     cost,
-                    item.properties().description
+                    item.description
                 ))
                 .field(
                     "Your Balance",
@@ -1763,7 +1838,6 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
             if raw_id
                 .starts_with(crate::interactions::ids::SAGA_TAVERN_SHOP_BUY_CONFIRM_PREFIX) =>
         {
-            use crate::commands::economy::core::item::Item;
             let id_str = raw_id
                 .trim_start_matches(crate::interactions::ids::SAGA_TAVERN_SHOP_BUY_CONFIRM_PREFIX);
             let Some(item_id) = id_str.parse::<i32>().ok() else {
@@ -1776,7 +1850,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 .await;
                 return;
             };
-            let Some(item) = Item::from_i32(item_id) else {
+            let Some(item) = registry().get(item_id).cloned() else {
                 edit_component(
                     ctx,
                     component,
@@ -1811,8 +1885,12 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 },
             ));
             let (shop_disc, _, _) = crate::commands::saga::tavern::fame_perks(meta_tmp.fame_tier);
-            let base = tavern_price(item).unwrap_or(1_000_000);
-            let cost: i64 = crate::commands::saga::tavern::apply_shop_discount(base, shop_disc);
+            let lure_disc =
+                database::research::get_research_perks(&app_state.db, component.user.id)
+                    .await
+                    .lure_discount;
+            let price = database::pricing::get_price_state(db, item.id).await;
+            let cost: i64 = tavern_cost(&item, shop_disc, lure_disc, price);
             let mut tx = match db.begin().await {
                 Ok(t) => t,
                 Err(e) => {
@@ -1827,15 +1905,27 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     return;
                 }
             };
-            let res = {
-                match crate::database::economy::add_balance(&mut tx, component.user.id, -cost).await
+            let band = database::pricing::get_price_band(db).await;
+            let res = if let Err(e) =
+                database::pricing::record_trade(&mut tx, item.id, 1, 0, band).await
+            {
+                Err(e)
+            } else {
+                match crate::database::economy::add_balance(
+                    &mut tx,
+                    component.user.id,
+                    -cost,
+                    LedgerReason::Tavern.with_ref("item", item.id),
+                )
+                .await
                 {
                     Ok(()) => {
                         crate::database::economy::add_to_inventory(
                             &mut tx,
                             component.user.id,
-                            item,
+                            item.id,
                             1,
+                            LedgerReason::Tavern,
                         )
                         .await
                     }
@@ -1858,8 +1948,8 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     component.data.custom_id = crate::interactions::ids::SAGA_TAVERN_SHOP.into();
                     let notice = Some(format!(
                         "You bought {} {} for {} {}.",
-                        item.emoji(),
-                        item.display_name(),
+                        item.emoji,
+                        item.name,
                         crate::ui::style::EMOJI_COIN,
                         cost
                     ));
@@ -2308,11 +2398,12 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     .await;
                     fresh
                 };
+                let modifiers = PartyModifiers::load(db, component.user.id).await;
                 let mut synergy_log: Vec<String> = Vec::new();
                 let player_units: Vec<BattleUnit> = player_party_units
                     .iter()
                     .map(|u| {
-                        if let Some(b) = bonuses.get(&u.player_unit_id) {
+                        let unit = if let Some(b) = bonuses.get(&u.player_unit_id) {
                             if b.0 > 0 || b.1 > 0 || b.2 > 0 {
                                 synergy_log.push(format!(
                                     "🔗 {} gains +{} Atk / +{} Def / +{} HP from bonded unit(s).",
@@ -2325,7 +2416,17 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                             BattleUnit::from_player_unit_with_bonus(u, *b)
                         } else {
                             BattleUnit::from_player_unit(u)
+                        };
+                        if let Some(g) = modifiers.gear_bonus(u.player_unit_id) {
+                            synergy_log.push(format!(
+                                "🗡️ {} gains +{} Atk / +{} Def / +{} HP from gear.",
+                                u.nickname.as_deref().unwrap_or(&u.name),
+                                g.attack,
+                                g.defense,
+                                g.health
+                            ));
                         }
+                        modifiers.apply(u.player_unit_id, unit)
                     })
                     .collect();
                 // Dynamic enemy scaling: if player's story progress greatly exceeds node requirement, slightly buff enemies
@@ -2367,6 +2468,7 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     can_afford_recruit,
                     player_quest_id: None,
                     claimed: false,
+                    gear_worn: false,
                 };
                 let (content, embed, components) = battle_game.render();
                 let builder = EditInteractionResponse::new()
@@ -2374,11 +2476,18 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                     .embed(embed)
                     .components(components);
                 if let Ok(msg) = component.edit_response(&ctx.http, builder).await {
-                    app_state
-                        .game_manager
-                        .write()
-                        .await
-                        .start_game(msg.id, Box::new(battle_game));
+                    let started = app_state.game_manager.start_game(
+                        msg.channel_id,
+                        msg.id,
+                        Box::new(battle_game),
+                    );
+                    if let Err(e) = started {
+                        let builder = EditInteractionResponse::new()
+                            .content(e)
+                            .embeds(vec![])
+                            .components(vec![]);
+                        component.edit_response(&ctx.http, builder).await.ok();
+                    }
                 }
             } else {
                 edit_component(
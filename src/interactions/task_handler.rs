@@ -1,7 +1,7 @@
 //! Handles all component interactions for the `/tasks` command family.
 
 use super::util::{defer_component, edit_component, handle_global_nav};
-use crate::commands::economy::core::item::Item;
+use crate::commands::economy::core::registry::registry;
 use crate::commands::tasks::ui;
 use crate::{AppState, database};
 use serenity::builder::EditInteractionResponse;
@@ -44,14 +44,9 @@ pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_sta
                 rewards.push(format!("💰 **{}** coins", coins));
             }
             if let (Some(id), Some(qty)) = (item_id, quantity)
-                && let Some(item) = Item::from_i32(id)
+                && let Some(item) = registry().get(id)
             {
-                rewards.push(format!(
-                    "{} **{}x** {}",
-                    item.emoji(),
-                    qty,
-                    item.display_name()
-                ));
+                rewards.push(format!("{} **{}x** {}", item.emoji, qty, item.name));
             }
             // Add a default message if for some reason there are no rewards.
             if rewards.is_empty() {
@@ -0,0 +1,94 @@
+//! Handles tournament registration buttons (`tournament_{join|start|cancel}_{id}`).
+use super::util::{defer_component, edit_component};
+use crate::AppState;
+use crate::commands::tournament::logic::MIN_ENTRANTS;
+use crate::commands::tournament::run::{registration_closes_at, start_tournament};
+use crate::commands::tournament::ui::lobby_view;
+use crate::database::tournaments;
+use serenity::builder::{
+    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
+};
+use serenity::model::application::ComponentInteraction;
+use serenity::prelude::Context;
+use std::sync::Arc;
+
+async fn send_ephemeral(ctx: &Context, component: &ComponentInteraction, content: &str) {
+    let builder = CreateInteractionResponseMessage::new()
+        .content(content)
+        .ephemeral(true);
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Message(builder))
+        .await
+        .ok();
+}
+
+pub async fn handle(ctx: &Context, component: &mut ComponentInteraction, app_state: Arc<AppState>) {
+    let Some((action, id)) = component
+        .data
+        .custom_id
+        .strip_prefix("tournament_")
+        .and_then(|rest| rest.split_once('_'))
+        .and_then(|(action, id)| id.parse::<i32>().ok().map(|id| (action.to_string(), id)))
+    else {
+        return;
+    };
+    let db = &app_state.db;
+    let Ok(Some(record)) = tournaments::get_tournament(db, id).await else {
+        send_ephemeral(ctx, component, "That tournament no longer exists.").await;
+        return;
+    };
+    let is_host = record.host_id == component.user.id.get() as i64;
+
+    let note = match action.as_str() {
+        "join" => match tournaments::register(db, id, component.user.id).await {
+            Ok(_) => format!("<@{}> registered.", component.user.id),
+            Err(e) => {
+                send_ephemeral(ctx, component, &e).await;
+                return;
+            }
+        },
+        "start" | "cancel" if !is_host => {
+            send_ephemeral(ctx, component, "Only the host can do that.").await;
+            return;
+        }
+        "start" => {
+            if record.entrants.len() < MIN_ENTRANTS {
+                let msg = format!("At least {} players must register first.", MIN_ENTRANTS);
+                send_ephemeral(ctx, component, &msg).await;
+                return;
+            }
+            defer_component(ctx, component).await;
+            match start_tournament(ctx, &app_state, id).await {
+                Ok(note) => note,
+                Err(e) => e,
+            }
+        }
+        "cancel" => match tournaments::cancel(db, id).await {
+            Ok(refunded) => format!(
+                "Tournament cancelled; {} buy-in(s) were refunded.",
+                refunded
+            ),
+            Err(e) => {
+                send_ephemeral(ctx, component, &e).await;
+                return;
+            }
+        },
+        _ => return,
+    };
+
+    defer_component(ctx, component).await;
+    let Ok(Some(record)) = tournaments::get_tournament(db, id).await else {
+        return;
+    };
+    let (embed, components) = lobby_view(&record, registration_closes_at(&record));
+    edit_component(
+        ctx,
+        component,
+        "tournament_lobby",
+        EditInteractionResponse::new()
+            .content(note)
+            .embed(embed)
+            .components(components),
+    )
+    .await;
+}
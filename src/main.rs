@@ -50,6 +50,14 @@ async fn serenity(
         .expect("Failed to run database migrations.");
     tracing::info!(target: "setup", "Database migrations run successfully");
 
+    // 1b. Load item definitions into the in-memory registry (built-in items as fallback).
+    match crate::database::items::reload_item_registry(&pool).await {
+        Ok((_, loaded)) => tracing::info!(target: "setup", items = loaded, "Item registry loaded"),
+        Err(e) => {
+            tracing::warn!(target: "setup", error = %e, "Item registry load failed; using built-in items")
+        }
+    }
+
     // 2. Load secrets from the Shuttle secret store.
     let token = secrets
         .get("DISCORD_TOKEN")
@@ -73,8 +81,8 @@ async fn serenity(
         };
 
     let app_state = Arc::new(AppState {
-        // GameManager is wrapped for interior mutability across async tasks.
-        game_manager: Arc::new(RwLock::new(GameManager::new())),
+        // GameManager locks each game on its own, so it needs no outer lock.
+        game_manager: Arc::new(GameManager::new()),
         // PgPool is already thread-safe (it's an Arc internally).
         db: pool,
         // The prefix needs to be mutable at runtime by admins.
@@ -113,6 +121,38 @@ async fn serenity(
         }
     }
 
+    // 3d. Start background workers (crafting queue completion, bank interest and loans).
+    crate::services::crafting::spawn_craft_worker(app_state.db.clone());
+    crate::services::bank::spawn_bank_worker(app_state.db.clone());
+    crate::services::ledger::spawn_reconciliation_worker(app_state.db.clone());
+
+    // 3e. Tournament tables only live in memory, so refund any tournament a restart cut short.
+    match crate::database::tournaments::cancel_unfinished(&app_state.db).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(target: "setup", count, "Refunded unfinished tournaments"),
+        Err(err) => {
+            tracing::error!(target: "setup", error = %err, "Failed to refund unfinished tournaments")
+        }
+    }
+
+    // 3f. Game tables only live in memory too, so return any chips still bought in at one.
+    match crate::database::table_stacks::cash_out_abandoned(&app_state.db).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(target: "setup", count, "Cashed out abandoned table stacks"),
+        Err(err) => {
+            tracing::error!(target: "setup", error = %err, "Failed to cash out abandoned table stacks")
+        }
+    }
+
+    // 3g. Side bets are held against in-memory games as well, so refund any still open.
+    match crate::database::side_bets::refund_abandoned(&app_state.db).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(target: "setup", count, "Refunded abandoned side bets"),
+        Err(err) => {
+            tracing::error!(target: "setup", error = %err, "Failed to refund abandoned side bets")
+        }
+    }
+
     // 4. Set gateway intents required for the bot's functionality.
     let intents =
         GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
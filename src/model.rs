@@ -58,8 +58,9 @@ impl TypeMapKey for ShardManagerContainer {
 /// from any command or event handler.
 pub struct AppState {
     /// The manager for all active game instances, such as Blackjack or Poker.
-    /// This is the single point of entry for all game-related logic.
-    pub game_manager: Arc<RwLock<GameManager>>,
+    /// This is the single point of entry for all game-related logic. It locks each game on its
+    /// own, so it is shared without an outer lock.
+    pub game_manager: Arc<GameManager>,
     /// The connection pool for the PostgreSQL database.
     pub db: PgPool,
     /// The current command prefix, which can be changed at runtime by administrators.
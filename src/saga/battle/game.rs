@@ -1,16 +1,18 @@
 //! Implements the `Game` trait for a battle session.
 
+use crate::commands::economy::core::registry::registry;
 use crate::commands::games::{Game, GameUpdate};
 use crate::database;
 use crate::database::battle;
-use crate::database::models::UnitKind;
+use crate::database::models::{LedgerReason, UnitKind};
 use crate::saga::battle::{logic, state::*, ui};
+use crate::saga::gear;
 use serenity::async_trait;
 use serenity::builder::{CreateActionRow, CreateEmbed};
 use serenity::model::application::ComponentInteraction;
+use serenity::model::id::UserId;
 use serenity::prelude::Context;
 use sqlx::PgPool;
-use std::any::Any;
 use tokio::time::Duration;
 
 pub struct BattleGame {
@@ -22,15 +24,76 @@ pub struct BattleGame {
     // (✓) NEW: Add a field to track if this battle is for a quest.
     pub player_quest_id: Option<i32>,
     pub claimed: bool,
+    /// Set once equipped gear has lost durability for this battle.
+    pub gear_worn: bool,
+}
+
+impl BattleGame {
+    /// Wears down the party's gear once the battle is decided; returns log lines for items that
+    /// broke or are running low.
+    async fn wear_gear(&mut self, db: &PgPool, defeat: bool) -> Vec<String> {
+        if self.gear_worn {
+            return Vec::new();
+        }
+        self.gear_worn = true;
+        let unit_ids: Vec<i32> = self
+            .party_members
+            .iter()
+            .map(|p| p.player_unit_id)
+            .collect();
+        let worn = match database::gear::wear_gear(db, &unit_ids, gear::battle_wear(defeat)).await {
+            Ok(worn) => worn,
+            Err(e) => {
+                tracing::warn!(target: "gear", error = %e, "Failed to wear party gear");
+                return Vec::new();
+            }
+        };
+        let items = registry();
+        worn.iter()
+            .filter_map(|g| {
+                let unit = self
+                    .party_members
+                    .iter()
+                    .find(|p| p.player_unit_id == g.player_unit_id)?;
+                let name = unit.nickname.as_deref().unwrap_or(&unit.name);
+                if gear::is_broken(g) {
+                    Some(format!(
+                        "💥 {}'s {} broke! Repair it at the tavern.",
+                        name,
+                        items.name_of(g.item_id)
+                    ))
+                } else if g.durability * 100 <= g.max_durability * gear::LOW_DURABILITY_PCT {
+                    Some(format!(
+                        "⚠️ {}'s {} is wearing thin ({}).",
+                        name,
+                        items.name_of(g.item_id),
+                        gear::durability_label(g)
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    async fn end_battle(&mut self, db: &PgPool, phase: BattlePhase, summary: &str) {
+        self.session.phase = phase;
+        self.session.log.push("---".to_string());
+        self.session.log.push(summary.to_string());
+        let lines = self.wear_gear(db, phase == BattlePhase::Defeat).await;
+        self.session.log.extend(lines);
+    }
 }
 
 #[async_trait]
 impl Game for BattleGame {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
+    /// A battle is played by whoever owns the party.
+    fn participants(&self) -> Vec<UserId> {
+        self.party_members
+            .first()
+            .map(|unit| UserId::new(unit.user_id as u64))
+            .into_iter()
+            .collect()
     }
 
     fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
@@ -72,22 +135,16 @@ impl Game for BattleGame {
         match interaction.data.custom_id.as_str() {
             "battle_attack" => {
                 if logic::process_player_turn(&mut self.session) == BattleOutcome::PlayerVictory {
-                    self.session.phase = BattlePhase::Victory;
-                    self.session.log.push("---".to_string());
-                    self.session
-                        .log
-                        .push("You have defeated all enemies!".to_string());
+                    self.end_battle(db, BattlePhase::Victory, "You have defeated all enemies!")
+                        .await;
                     return GameUpdate::ReRender;
                 }
 
                 tokio::time::sleep(Duration::from_millis(750)).await;
 
                 if logic::process_enemy_turn(&mut self.session) == BattleOutcome::PlayerDefeat {
-                    self.session.phase = BattlePhase::Defeat;
-                    self.session.log.push("---".to_string());
-                    self.session
-                        .log
-                        .push("Your party has been defeated.".to_string());
+                    self.end_battle(db, BattlePhase::Defeat, "Your party has been defeated.")
+                        .await;
                     return GameUpdate::ReRender;
                 }
 
@@ -223,15 +280,17 @@ impl Game for BattleGame {
                     }
                 };
                 use crate::commands::economy::core::item::Item;
-                let Some(item) = Item::from_i32(item_id) else {
+                let Some(item) = registry().get(item_id).cloned() else {
                     self.session.log.push("⚠️ Unknown item.".to_string());
                     return GameUpdate::ReRender;
                 };
                 // Only allow health potions in-battle for now
-                let heal_amount = match item {
-                    Item::HealthPotion => 30,
-                    Item::GreaterHealthPotion => 80,
-                    _ => 0,
+                let heal_amount = if item.id == Item::HealthPotion.id() {
+                    30
+                } else if item.id == Item::GreaterHealthPotion.id() {
+                    80
+                } else {
+                    0
                 };
                 if heal_amount == 0 {
                     self.session
@@ -261,15 +320,16 @@ impl Game for BattleGame {
                         return GameUpdate::ReRender;
                     }
                 };
-                match database::economy::get_inventory_item(&mut tx, interaction.user.id, item)
+                match database::economy::get_inventory_item(&mut tx, interaction.user.id, item.id)
                     .await
                 {
                     Ok(Some(it)) if it.quantity > 0 => {
                         if database::economy::add_to_inventory(
                             &mut tx,
                             interaction.user.id,
-                            item,
+                            item.id,
                             -1,
+                            LedgerReason::ItemUse,
                         )
                         .await
                         .is_err()
@@ -307,9 +367,7 @@ impl Game for BattleGame {
                     let healed = unit.current_hp - before;
                     self.session.log.push(format!(
                         "🧪 Used {} on {} (+{} HP).",
-                        item.display_name(),
-                        unit.name,
-                        healed
+                        item.name, unit.name, healed
                     ));
                 } else {
                     self.session
@@ -318,19 +376,23 @@ impl Game for BattleGame {
                 }
                 // Advance flow: end player phase and let enemy act
                 if logic::process_enemy_turn(&mut self.session) == BattleOutcome::PlayerDefeat {
-                    self.session.phase = BattlePhase::Defeat;
-                    self.session.log.push("---".to_string());
-                    self.session
-                        .log
-                        .push("Your party has been defeated.".to_string());
+                    self.end_battle(db, BattlePhase::Defeat, "Your party has been defeated.")
+                        .await;
                     return GameUpdate::ReRender;
                 }
                 GameUpdate::ReRender
             }
-            "battle_flee" => GameUpdate::GameOver {
-                message: "You fled from the battle.".to_string(),
-                payouts: vec![],
-            },
+            "battle_flee" => {
+                let mut message = "You fled from the battle.".to_string();
+                for line in self.wear_gear(db, false).await {
+                    message.push('\n');
+                    message.push_str(&line);
+                }
+                GameUpdate::GameOver {
+                    message,
+                    payouts: vec![],
+                }
+            }
             "battle_claim_rewards" => {
                 if self.claimed {
                     return GameUpdate::ReRender;
@@ -376,6 +438,10 @@ impl Game for BattleGame {
                     } else {
                         false
                     };
+                    let research_drop_mult =
+                        crate::database::research::get_research_perks(db, interaction.user.id)
+                            .await
+                            .research_drop_mult;
                     match battle::resolve_node_victory(
                         db,
                         crate::database::battle::ResolveVictoryInput {
@@ -391,6 +457,7 @@ impl Game for BattleGame {
                                 .map(|e| e.unit_id)
                                 .collect::<Vec<_>>(),
                             focus_active,
+                            research_drop_mult,
                         },
                     )
                     .await
@@ -1,9 +1,43 @@
 //! Contains the core, stateful logic for processing battle turns.
 
 use super::state::{BattleOutcome, BattlePhase, BattleSession, BattleUnit};
+use crate::database::models::FormationRow;
 use rand::prelude::IteratorRandom;
 use rand::rng;
 
+/// Damage multiplier for ranged units attacking from the back row.
+pub const BACK_ROW_RANGED_MULT: f32 = 1.10;
+/// Damage multiplier for melee units stuck in the back row (they have to reach past allies).
+pub const BACK_ROW_MELEE_MULT: f32 = 0.5;
+
+/// Attack multiplier derived from a unit's formation row and attack style.
+pub fn row_attack_multiplier(unit: &BattleUnit) -> f32 {
+    match (unit.row, unit.is_ranged) {
+        (FormationRow::Front, _) => 1.0,
+        (FormationRow::Back, true) => BACK_ROW_RANGED_MULT,
+        (FormationRow::Back, false) => BACK_ROW_MELEE_MULT,
+    }
+}
+
+/// Indices of living units that may currently be targeted: the front row while anyone in it is
+/// still standing, otherwise the back row.
+pub fn targetable_indices(party: &[BattleUnit]) -> Vec<usize> {
+    let living = |row: FormationRow| -> Vec<usize> {
+        party
+            .iter()
+            .enumerate()
+            .filter(|(_, u)| u.current_hp > 0 && u.row == row)
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let front = living(FormationRow::Front);
+    if front.is_empty() {
+        living(FormationRow::Back)
+    } else {
+        front
+    }
+}
+
 // (✓) NEW: A private helper function to handle the core attack logic for any party.
 // This eliminates code duplication between the player and enemy turn functions.
 fn process_party_attack(
@@ -16,16 +50,15 @@ fn process_party_attack(
     // Use thread-local RNG each call; scope-limited so no Send issues.
     let mut rng = rng();
     for attacker in attacking_party.iter().filter(|a| a.current_hp > 0) {
-        // Find a random, living target in the defending party.
-        if let Some(target_idx) = defending_party
-            .iter()
-            .enumerate()
-            .filter(|(_, d)| d.current_hp > 0)
-            .map(|(i, _)| i)
+        // Find a random, living target in the defending party (front row first).
+        if let Some(target_idx) = targetable_indices(defending_party)
+            .into_iter()
             .choose(&mut rng)
         {
             let defender = &mut defending_party[target_idx];
-            let effective_attack = attacker.attack + attacker.bonus_attack;
+            let effective_attack = ((attacker.attack + attacker.bonus_attack) as f32
+                * row_attack_multiplier(attacker))
+            .round() as i32;
             let mut effective_defense = defender.defense + defender.bonus_defense;
             // NEW: Leverage bonus_health as a minor mitigation factor (each 10 bonus HP = +1 defense virtual).
             let mut mitigated = 0;
@@ -2,5 +2,6 @@
 
 pub mod game;
 pub mod logic;
+pub mod modifiers;
 pub mod state;
 pub mod ui;
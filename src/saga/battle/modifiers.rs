@@ -0,0 +1,65 @@
+//! Per-player modifiers applied to party units when a battle starts: equipped gear, formation
+//! rows, research passives and human morale.
+
+use super::state::BattleUnit;
+use crate::database;
+use crate::database::units::FormationMap;
+use crate::saga::gear::GearBonus;
+use crate::saga::research::ResearchPerks;
+use crate::saga::upkeep::morale_stat_multiplier;
+use serenity::model::id::UserId;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Default)]
+pub struct PartyModifiers {
+    formation: FormationMap,
+    research: ResearchPerks,
+    pet_ids: HashSet<i32>,
+    morale: HashMap<i32, i32>,
+    gear: HashMap<i32, GearBonus>,
+}
+
+impl PartyModifiers {
+    /// Loads every modifier source; lookups that fail simply contribute nothing.
+    pub async fn load(db: &PgPool, user_id: UserId) -> Self {
+        Self {
+            formation: database::units::get_party_formation(db, user_id)
+                .await
+                .unwrap_or_default(),
+            research: database::research::get_research_perks(db, user_id).await,
+            pet_ids: database::research::list_party_pet_ids(db, user_id)
+                .await
+                .unwrap_or_default(),
+            morale: database::upkeep::get_party_morale(db, user_id)
+                .await
+                .unwrap_or_default(),
+            gear: database::gear::get_gear_bonuses(db, user_id)
+                .await
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Stats added by the unit's working gear, if any.
+    pub fn gear_bonus(&self, player_unit_id: i32) -> Option<GearBonus> {
+        self.gear.get(&player_unit_id).copied()
+    }
+
+    pub fn apply(&self, player_unit_id: i32, unit: BattleUnit) -> BattleUnit {
+        let mut unit = unit;
+        // Gear first so that morale and research multipliers scale the equipped stats too.
+        if let Some(gear) = self.gear.get(&player_unit_id) {
+            unit = unit.with_gear(*gear);
+        }
+        if self.pet_ids.contains(&player_unit_id) {
+            unit = unit.with_attack_multiplier(self.research.pet_damage_mult);
+        }
+        if let Some(morale) = self.morale.get(&player_unit_id) {
+            unit = unit.with_morale(morale_stat_multiplier(*morale));
+        }
+        match self.formation.get(&player_unit_id) {
+            Some((row, ranged)) => unit.with_formation(*row, *ranged),
+            None => unit,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Defines the data structures for a battle session.
 
-use crate::database::models::{PlayerUnit, Unit};
+use crate::database::models::{FormationRow, PlayerUnit, Unit};
+use crate::saga::gear::GearBonus;
 
 #[derive(Debug, Clone)]
 pub struct BattleUnit {
@@ -17,6 +18,9 @@ pub struct BattleUnit {
     pub bonus_defense: i32,
     pub bonus_health: i32,
     pub owner_user_id: Option<i64>, // new: original owner when derived from PlayerUnit
+    // Formation: front row is targeted first; back row damage depends on attack style.
+    pub row: FormationRow,
+    pub is_ranged: bool,
 }
 
 // (✓) NEW: Add explicit constructors to resolve compiler errors.
@@ -37,6 +41,8 @@ impl BattleUnit {
             bonus_defense: 0,
             bonus_health: 0,
             owner_user_id: Some(unit.user_id),
+            row: FormationRow::Front,
+            is_ranged: false,
         }
     }
 
@@ -54,6 +60,8 @@ impl BattleUnit {
             bonus_defense: bonus.1,
             bonus_health: bonus.2,
             owner_user_id: Some(unit.user_id),
+            row: FormationRow::Front,
+            is_ranged: false,
         }
     }
 
@@ -71,8 +79,49 @@ impl BattleUnit {
             bonus_defense: 0,
             bonus_health: 0,
             owner_user_id: None,
+            // Wild ranged enemies hang back behind their melee companions.
+            row: if unit.is_ranged {
+                FormationRow::Back
+            } else {
+                FormationRow::Front
+            },
+            is_ranged: unit.is_ranged,
         }
     }
+
+    /// Apply the owner's formation placement (player units default to the front row).
+    pub fn with_formation(mut self, row: FormationRow, is_ranged: bool) -> Self {
+        self.row = row;
+        self.is_ranged = is_ranged;
+        self
+    }
+
+    /// Adds flat stats from equipped gear (broken items are already excluded from the bonus).
+    pub fn with_gear(mut self, gear: GearBonus) -> Self {
+        self.attack += gear.attack;
+        self.defense += gear.defense;
+        self.max_hp += gear.health;
+        self.current_hp += gear.health;
+        self
+    }
+
+    /// Scales attack and defense down for low morale (multiplier <= 1.0).
+    pub fn with_morale(mut self, mult: f32) -> Self {
+        if mult < 1.0 {
+            self.attack = ((self.attack as f32 * mult).round() as i32).max(1);
+            self.defense = (self.defense as f32 * mult).round() as i32;
+        }
+        self
+    }
+
+    /// Scales attack by a passive multiplier (e.g. research pet damage); rounds up so small
+    /// bonuses still count.
+    pub fn with_attack_multiplier(mut self, mult: f32) -> Self {
+        if mult > 1.0 {
+            self.attack = (self.attack as f32 * mult).ceil() as i32;
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
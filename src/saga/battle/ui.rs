@@ -148,7 +148,12 @@ fn format_party_hp(party: &[BattleUnit]) -> String {
             } else {
                 ("❤️", format!("{}/{}", unit.current_hp, unit.max_hp))
             };
-            format!("{} {} [{}]", icon, unit.name, hp_part)
+            let row = if unit.row == crate::database::models::FormationRow::Back {
+                " · back"
+            } else {
+                ""
+            };
+            format!("{} {} [{}]{}", icon, unit.name, hp_part, row)
         })
         .collect::<Vec<_>>()
         .join("\n")
@@ -0,0 +1,70 @@
+//! Equipment rules for player units.
+//!
+//! Gear is bought as items (see the tavern's Small Arms shop) and equipped into a unit's weapon,
+//! armor or trinket slot. Equipped gear adds flat stats when a battle starts and loses durability
+//! once the battle ends (more on a defeat). Broken gear stays equipped but adds nothing until it
+//! is repaired at the tavern, which costs coins per missing durability point.
+
+use crate::database::models::UnitGear;
+
+/// Durability lost by every equipped item when a battle ends.
+pub const WEAR_PER_BATTLE: i32 = 1;
+/// Durability lost instead when the party is defeated.
+pub const WEAR_ON_DEFEAT: i32 = 2;
+/// Coins per durability point restored at the tavern.
+pub const REPAIR_COST_PER_POINT: i64 = 4;
+/// At or below this share of max durability an item is flagged as worn.
+pub const LOW_DURABILITY_PCT: i32 = 25;
+
+/// Flat stats a unit's gear contributes in battle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GearBonus {
+    pub attack: i32,
+    pub defense: i32,
+    pub health: i32,
+}
+
+impl GearBonus {
+    /// Sums the stats of all equipped items that are not broken.
+    pub fn from_gear<'a>(gear: impl IntoIterator<Item = &'a UnitGear>) -> Self {
+        gear.into_iter()
+            .filter(|g| !is_broken(g))
+            .fold(Self::default(), |acc, g| Self {
+                attack: acc.attack + g.attack,
+                defense: acc.defense + g.defense,
+                health: acc.health + g.health,
+            })
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+pub fn is_broken(gear: &UnitGear) -> bool {
+    gear.durability <= 0
+}
+
+pub fn battle_wear(defeat: bool) -> i32 {
+    if defeat {
+        WEAR_ON_DEFEAT
+    } else {
+        WEAR_PER_BATTLE
+    }
+}
+
+/// Coins needed to restore an item to full durability.
+pub fn repair_cost(gear: &UnitGear) -> i64 {
+    (gear.max_durability - gear.durability).max(0) as i64 * REPAIR_COST_PER_POINT
+}
+
+/// Durability shown next to an item, e.g. `18/30`, `⚠️ 5/30` or `💥 broken`.
+pub fn durability_label(gear: &UnitGear) -> String {
+    if is_broken(gear) {
+        "💥 broken".to_string()
+    } else if gear.durability * 100 <= gear.max_durability * LOW_DURABILITY_PCT {
+        format!("⚠️ {}/{}", gear.durability, gear.max_durability)
+    } else {
+        format!("{}/{}", gear.durability, gear.max_durability)
+    }
+}
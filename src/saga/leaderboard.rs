@@ -5,6 +5,8 @@ pub enum LeaderboardType {
     Gamemaster,
     Wealth,
     WorkStreak,
+    /// This season's ranked Rock Paper Scissors ratings.
+    RpsLadder,
 }
 
 impl LeaderboardType {
@@ -13,6 +15,7 @@ impl LeaderboardType {
             Self::Gamemaster => "🏆 Gamemaster Score",
             Self::Wealth => "💰 Wealth",
             Self::WorkStreak => "📈 Work Streak",
+            Self::RpsLadder => "✊ RPS Ladder",
         }
     }
 
@@ -21,6 +24,7 @@ impl LeaderboardType {
             Self::Gamemaster => "Score",
             Self::Wealth => "Coins",
             Self::WorkStreak => "Days",
+            Self::RpsLadder => "Rating",
         }
     }
 }
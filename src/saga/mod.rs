@@ -2,6 +2,10 @@
 
 pub mod battle;
 pub mod core;
+pub mod gear;
 pub mod leaderboard;
 pub mod leveling;
+pub mod negotiation;
+pub mod research;
+pub mod upkeep;
 pub mod view;
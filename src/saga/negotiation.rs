@@ -0,0 +1,176 @@
+//! Contract negotiation for drafted human recruits.
+//!
+//! A drafted human opens with demands scaled by rarity (daily wage, a share of battle coin loot,
+//! and for Epic+ a guaranteed party slot). The player may counter a limited number of times; how
+//! far the human bends depends on the player's tavern fame tier and how often they have beaten
+//! this human beyond the required defeats.
+
+use crate::database::models::UnitRarity;
+
+/// Counters allowed per drafted contract before the human's offer becomes final.
+pub const MAX_NEGOTIATION_ROUNDS: i32 = 3;
+/// Flexibility needed before a human agrees to give up a guaranteed party slot.
+const WAIVE_SLOT_FLEXIBILITY: i32 = 20;
+/// Counters within this many points above flexibility end in a compromise instead of offence.
+const COMPROMISE_WINDOW: i32 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContractTerms {
+    pub wage: i64,
+    pub loot_share_pct: i32,
+    pub party_slot: bool,
+}
+
+impl ContractTerms {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} coins/day · {}% loot share{}",
+            self.wage,
+            self.loot_share_pct,
+            if self.party_slot {
+                " · guaranteed party slot"
+            } else {
+                ""
+            }
+        )
+    }
+
+    fn cut_from(opening: &ContractTerms, cut_pct: i32, party_slot: bool) -> Self {
+        Self {
+            wage: (opening.wage * (100 - cut_pct) as i64 / 100).max(1),
+            loot_share_pct: opening.loot_share_pct * (100 - cut_pct) / 100,
+            party_slot,
+        }
+    }
+}
+
+/// Opening demands for a freshly drafted human of the given rarity.
+pub fn opening_demands(rarity: UnitRarity) -> ContractTerms {
+    let (wage, loot_share_pct) = match rarity {
+        UnitRarity::Common => (20, 5),
+        UnitRarity::Rare => (45, 8),
+        UnitRarity::Epic => (90, 12),
+        UnitRarity::Legendary => (160, 15),
+        UnitRarity::Unique => (240, 18),
+        UnitRarity::Mythical => (350, 20),
+        UnitRarity::Fabled => (500, 25),
+    };
+    ContractTerms {
+        wage,
+        loot_share_pct,
+        party_slot: !matches!(rarity, UnitRarity::Common | UnitRarity::Rare),
+    }
+}
+
+/// How far (in percent) a human will bend: fame tier dominates, extra defeats add respect.
+pub fn flexibility_pct(fame_tier: usize, extra_defeats: i32) -> i32 {
+    5 + fame_tier.min(3) as i32 * 10 + extra_defeats.clamp(0, 5) * 3
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    Modest,
+    Firm,
+    Lowball,
+    WaiveSlot,
+}
+
+impl Counter {
+    pub const ALL: [Counter; 4] = [
+        Counter::Modest,
+        Counter::Firm,
+        Counter::Lowball,
+        Counter::WaiveSlot,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Modest => "modest",
+            Self::Firm => "firm",
+            Self::Lowball => "lowball",
+            Self::WaiveSlot => "slot",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Modest => "Counter -15%",
+            Self::Firm => "Counter -30%",
+            Self::Lowball => "Lowball -50%",
+            Self::WaiveSlot => "Waive Party Slot",
+        }
+    }
+
+    /// Cut (percent off the opening demands) proposed by this counter.
+    fn cut_pct(&self) -> i32 {
+        match self {
+            Self::Modest => 15,
+            Self::Firm => 30,
+            Self::Lowball => 50,
+            Self::WaiveSlot => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegotiationOutcome {
+    /// The human agreed to the counter as proposed.
+    Agreed(ContractTerms),
+    /// The human met the player halfway.
+    Compromise(ContractTerms),
+    /// The counter was insulting; demands went up.
+    Offended(ContractTerms),
+    /// The human refused to waive the party slot; terms unchanged.
+    Refused,
+}
+
+impl NegotiationOutcome {
+    pub fn terms(&self, current: ContractTerms) -> ContractTerms {
+        match self {
+            Self::Agreed(t) | Self::Compromise(t) | Self::Offended(t) => *t,
+            Self::Refused => current,
+        }
+    }
+}
+
+/// Resolve one counter. Cuts are always measured against the opening demands so repeated
+/// counters cannot compound.
+pub fn resolve_counter(
+    opening: &ContractTerms,
+    current: &ContractTerms,
+    counter: Counter,
+    flexibility: i32,
+) -> NegotiationOutcome {
+    if counter == Counter::WaiveSlot {
+        return if !current.party_slot {
+            NegotiationOutcome::Agreed(*current)
+        } else if flexibility >= WAIVE_SLOT_FLEXIBILITY {
+            NegotiationOutcome::Agreed(ContractTerms {
+                party_slot: false,
+                ..*current
+            })
+        } else {
+            NegotiationOutcome::Refused
+        };
+    }
+    let cut = counter.cut_pct();
+    if cut <= flexibility {
+        NegotiationOutcome::Agreed(ContractTerms::cut_from(opening, cut, current.party_slot))
+    } else if cut <= flexibility + COMPROMISE_WINDOW {
+        NegotiationOutcome::Compromise(ContractTerms::cut_from(
+            opening,
+            cut / 2,
+            current.party_slot,
+        ))
+    } else {
+        NegotiationOutcome::Offended(ContractTerms {
+            wage: current.wage + (current.wage / 10).max(1),
+            loot_share_pct: current.loot_share_pct + 1,
+            party_slot: current.party_slot,
+        })
+    }
+}
@@ -0,0 +1,250 @@
+//! The research tree: passive nodes unlocked by spending research points.
+//!
+//! Completing research for any species of a creature family opens that family's branch;
+//! every completed species also awards research points (scaled by rarity). The tree itself is
+//! static game data, only the unlocked node keys are persisted per player.
+
+use crate::database::models::UnitRarity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResearchFamily {
+    Beast,
+    Arcane,
+    Draconic,
+}
+
+impl ResearchFamily {
+    pub const ALL: [ResearchFamily; 3] = [
+        ResearchFamily::Beast,
+        ResearchFamily::Arcane,
+        ResearchFamily::Draconic,
+    ];
+
+    /// Maps a creature name to its research family, if it belongs to one.
+    pub fn for_unit_name(name: &str) -> Option<Self> {
+        match name {
+            "Forest Wolf" | "Wolf" | "Alpha Wolf" | "Boar" | "Bear" | "Stone Turtle"
+            | "Giant Spider" | "Slime" => Some(Self::Beast),
+            "Temporal Sprite" | "Aether Serpent" | "Mythic Kitsune" => Some(Self::Arcane),
+            "Ember Drake" | "Celestial Griffin" => Some(Self::Draconic),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Beast => "Beast",
+            Self::Arcane => "Arcane",
+            Self::Draconic => "Draconic",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Beast => "🐺",
+            Self::Arcane => "✨",
+            Self::Draconic => "🐉",
+        }
+    }
+}
+
+/// Passive effect granted by an unlocked node (values are whole percents).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResearchPassive {
+    PetDamage(u8),
+    LureDiscount(u8),
+    ResearchDrop(u8),
+}
+
+impl ResearchPassive {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::PetDamage(p) => format!("+{}% pet damage", p),
+            Self::LureDiscount(p) => format!("-{}% Taming Lure cost", p),
+            Self::ResearchDrop(p) => format!("+{}% research drop chance", p),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ResearchNode {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub family: ResearchFamily,
+    pub cost: i32,
+    pub requires: &'static [&'static str],
+    pub passive: ResearchPassive,
+}
+
+pub const RESEARCH_TREE: &[ResearchNode] = &[
+    ResearchNode {
+        key: "beast_pack_tactics",
+        name: "Pack Tactics",
+        family: ResearchFamily::Beast,
+        cost: 1,
+        requires: &[],
+        passive: ResearchPassive::PetDamage(5),
+    },
+    ResearchNode {
+        key: "beast_lure_craft",
+        name: "Lure Crafting",
+        family: ResearchFamily::Beast,
+        cost: 1,
+        requires: &[],
+        passive: ResearchPassive::LureDiscount(10),
+    },
+    ResearchNode {
+        key: "beast_alpha_instinct",
+        name: "Alpha Instinct",
+        family: ResearchFamily::Beast,
+        cost: 2,
+        requires: &["beast_pack_tactics"],
+        passive: ResearchPassive::PetDamage(5),
+    },
+    ResearchNode {
+        key: "arcane_field_notes",
+        name: "Field Notes",
+        family: ResearchFamily::Arcane,
+        cost: 1,
+        requires: &[],
+        passive: ResearchPassive::ResearchDrop(5),
+    },
+    ResearchNode {
+        key: "arcane_scrying_lens",
+        name: "Scrying Lens",
+        family: ResearchFamily::Arcane,
+        cost: 2,
+        requires: &["arcane_field_notes"],
+        passive: ResearchPassive::ResearchDrop(10),
+    },
+    ResearchNode {
+        key: "draconic_fury",
+        name: "Draconic Fury",
+        family: ResearchFamily::Draconic,
+        cost: 2,
+        requires: &["beast_pack_tactics"],
+        passive: ResearchPassive::PetDamage(5),
+    },
+    ResearchNode {
+        key: "draconic_hoard_sense",
+        name: "Hoard Sense",
+        family: ResearchFamily::Draconic,
+        cost: 2,
+        requires: &["beast_lure_craft"],
+        passive: ResearchPassive::LureDiscount(10),
+    },
+];
+
+pub fn node_by_key(key: &str) -> Option<&'static ResearchNode> {
+    RESEARCH_TREE.iter().find(|n| n.key == key)
+}
+
+/// Research points awarded for completing one species of the given rarity.
+pub fn points_for_rarity(rarity: UnitRarity) -> i32 {
+    match rarity {
+        UnitRarity::Common => 1,
+        UnitRarity::Rare => 2,
+        UnitRarity::Epic => 3,
+        UnitRarity::Legendary | UnitRarity::Unique | UnitRarity::Mythical | UnitRarity::Fabled => 0,
+    }
+}
+
+/// A player's position in the tree: which branches are open, points, and unlocked nodes.
+#[derive(Debug, Clone, Default)]
+pub struct ResearchStanding {
+    pub completed_families: Vec<ResearchFamily>,
+    pub points_earned: i32,
+    pub unlocked: Vec<String>,
+}
+
+impl ResearchStanding {
+    pub fn is_unlocked(&self, key: &str) -> bool {
+        self.unlocked.iter().any(|k| k == key)
+    }
+
+    pub fn points_spent(&self) -> i32 {
+        self.unlocked
+            .iter()
+            .filter_map(|k| node_by_key(k))
+            .map(|n| n.cost)
+            .sum()
+    }
+
+    pub fn points_available(&self) -> i32 {
+        (self.points_earned - self.points_spent()).max(0)
+    }
+
+    pub fn status(&self, node: &ResearchNode) -> NodeStatus {
+        if self.is_unlocked(node.key) {
+            return NodeStatus::Unlocked;
+        }
+        if !self.completed_families.contains(&node.family) {
+            return NodeStatus::FamilyIncomplete;
+        }
+        if let Some(missing) = node.requires.iter().find(|r| !self.is_unlocked(r)) {
+            return NodeStatus::MissingPrerequisite(
+                node_by_key(missing).map(|n| n.name).unwrap_or(missing),
+            );
+        }
+        if self.points_available() < node.cost {
+            return NodeStatus::NotEnoughPoints;
+        }
+        NodeStatus::Available
+    }
+
+    /// Aggregated passives from every unlocked node.
+    pub fn perks(&self) -> ResearchPerks {
+        let mut pet_damage = 0u32;
+        let mut lure_discount = 0u32;
+        let mut research_drop = 0u32;
+        for node in self.unlocked.iter().filter_map(|k| node_by_key(k)) {
+            match node.passive {
+                ResearchPassive::PetDamage(p) => pet_damage += p as u32,
+                ResearchPassive::LureDiscount(p) => lure_discount += p as u32,
+                ResearchPassive::ResearchDrop(p) => research_drop += p as u32,
+            }
+        }
+        ResearchPerks {
+            pet_damage_mult: 1.0 + pet_damage as f32 / 100.0,
+            lure_discount: (lure_discount.min(50)) as f32 / 100.0,
+            research_drop_mult: 1.0 + research_drop as f64 / 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    Unlocked,
+    Available,
+    FamilyIncomplete,
+    MissingPrerequisite(&'static str),
+    NotEnoughPoints,
+}
+
+impl NodeStatus {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Unlocked => "✅",
+            Self::Available => "🟢",
+            Self::FamilyIncomplete | Self::MissingPrerequisite(_) | Self::NotEnoughPoints => "🔒",
+        }
+    }
+}
+
+/// Passive bonuses applied by gameplay systems.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResearchPerks {
+    pub pet_damage_mult: f32,
+    pub lure_discount: f32,
+    pub research_drop_mult: f64,
+}
+
+impl Default for ResearchPerks {
+    fn default() -> Self {
+        Self {
+            pet_damage_mult: 1.0,
+            lure_discount: 0.0,
+            research_drop_mult: 1.0,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Upkeep and morale rules for human units.
+//!
+//! Humans draw a daily wage (their negotiated contract wage, or a rarity default for mercenaries
+//! hired with coins). Wages are settled lazily once per UTC day when the saga profile refreshes.
+//! Unpaid days cost morale; resting on the bench, battle wins and gifts restore it. Low morale
+//! weakens a unit in battle and eventually raises desertion warnings.
+
+use crate::database::models::UnitRarity;
+
+pub const MORALE_MAX: i32 = 100;
+/// At or below this morale a unit is shown as about to desert.
+pub const MORALE_DESERTION_WARNING: i32 = 20;
+pub const MORALE_UNPAID_PENALTY: i32 = 15;
+/// Morale regained per day spent outside the active party.
+pub const MORALE_REST_GAIN: i32 = 10;
+pub const MORALE_WIN_GAIN: i32 = 3;
+pub const GIFT_COST: i64 = 75;
+pub const GIFT_MORALE: i32 = 20;
+/// Days settled at most when a player returns after a long absence.
+pub const MAX_UPKEEP_CATCHUP_DAYS: i64 = 7;
+
+/// Daily wage for humans without negotiated terms (e.g. hired through the tavern).
+pub fn default_wage(rarity: UnitRarity) -> i64 {
+    match rarity {
+        UnitRarity::Common => 10,
+        UnitRarity::Rare => 25,
+        UnitRarity::Epic => 50,
+        UnitRarity::Legendary => 90,
+        UnitRarity::Unique => 130,
+        UnitRarity::Mythical => 190,
+        UnitRarity::Fabled => 270,
+    }
+}
+
+/// Attack/defense multiplier for a given morale.
+pub fn morale_stat_multiplier(morale: i32) -> f32 {
+    match morale {
+        m if m >= 60 => 1.0,
+        m if m >= 40 => 0.95,
+        m if m > MORALE_DESERTION_WARNING => 0.85,
+        _ => 0.75,
+    }
+}
+
+pub fn morale_icon(morale: i32) -> &'static str {
+    match morale {
+        m if m >= 60 => "😊",
+        m if m >= 40 => "😐",
+        m if m > MORALE_DESERTION_WARNING => "😟",
+        _ => "⚠️",
+    }
+}
+
+/// Outcome of paying `days` of wages from `balance`: (days paid, days unpaid, coins spent).
+/// Each day is paid in full or not at all.
+pub fn settle_wages(balance: i64, daily_total: i64, days: i64) -> (i64, i64, i64) {
+    if daily_total <= 0 {
+        return (days, 0, 0);
+    }
+    let paid = (balance.max(0) / daily_total).min(days);
+    (paid, days - paid, paid * daily_total)
+}
+
+/// New morale after settlement for one unit.
+pub fn morale_after_settlement(morale: i32, unpaid_days: i64, rest_days: i64) -> i32 {
+    let delta = rest_days as i32 * MORALE_REST_GAIN - unpaid_days as i32 * MORALE_UNPAID_PENALTY;
+    (morale + delta).clamp(0, MORALE_MAX)
+}
@@ -0,0 +1,25 @@
+//! Background worker that pays daily bank interest and collects due loan installments.
+
+use crate::database::bank::settle_due_accounts;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// How often the worker looks for accounts to settle.
+pub const BANK_SWEEP_INTERVAL_SECS: u64 = 300;
+
+/// Spawns the settlement loop. Call once at startup.
+pub fn spawn_bank_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(BANK_SWEEP_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match settle_due_accounts(&pool).await {
+                Ok(0) => {}
+                Ok(n) => tracing::debug!(target: "bank", settled = n, "Settled bank accounts"),
+                Err(e) => {
+                    tracing::warn!(target: "bank", error = %e, "Bank settlement sweep failed")
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,27 @@
+//! Background worker that completes queued crafts once their timer runs out.
+
+use crate::database::crafting::complete_due_crafts;
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// How often the worker checks for finished crafts.
+pub const CRAFT_SWEEP_INTERVAL_SECS: u64 = 15;
+
+/// Spawns the completion loop. Call once at startup.
+pub fn spawn_craft_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(CRAFT_SWEEP_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match complete_due_crafts(&pool).await {
+                Ok(0) => {}
+                Ok(n) => {
+                    tracing::debug!(target: "crafting", completed = n, "Completed queued crafts")
+                }
+                Err(e) => {
+                    tracing::warn!(target: "crafting", error = %e, "Craft completion sweep failed")
+                }
+            }
+        }
+    });
+}
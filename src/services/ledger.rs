@@ -0,0 +1,60 @@
+//! Nightly reconciliation: checks every wallet, inventory row and bank balance against the sum of
+//! its ledger entries and logs anything that drifted.
+
+use crate::database::ledger::find_mismatches;
+use chrono::{DateTime, Days, Utc};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// UTC hour the reconciliation runs at.
+pub const RECONCILE_HOUR_UTC: u32 = 4;
+
+/// Time from `now` until the next reconciliation run.
+pub fn until_next_run(now: DateTime<Utc>) -> Duration {
+    let today = now
+        .date_naive()
+        .and_hms_opt(RECONCILE_HOUR_UTC, 0, 0)
+        .expect("valid reconciliation hour")
+        .and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + Days::new(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// Spawns the nightly reconciliation loop. Call once at startup.
+pub fn spawn_reconciliation_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(until_next_run(Utc::now())).await;
+            match find_mismatches(&pool, None).await {
+                Ok(mismatches) if mismatches.is_empty() => {
+                    tracing::info!(target: "ledger", "Ledger reconciliation passed");
+                }
+                Ok(mismatches) => {
+                    for m in &mismatches {
+                        tracing::error!(
+                            target: "ledger",
+                            user_id = m.user_id,
+                            account = %m.account,
+                            item_id = ?m.item_id,
+                            actual = m.actual,
+                            expected = m.expected,
+                            "Balance does not match the ledger"
+                        );
+                    }
+                    tracing::error!(
+                        target: "ledger",
+                        mismatches = mismatches.len(),
+                        "Ledger reconciliation failed"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(target: "ledger", error = %e, "Ledger reconciliation query failed")
+                }
+            }
+        }
+    });
+}
@@ -1,2 +1,5 @@
+pub mod bank;
 pub mod cache;
+pub mod crafting;
+pub mod ledger;
 pub mod saga; // generic TTL cache helpers
@@ -0,0 +1,45 @@
+use gamemaster_bot::commands::economy::bank::logic::{
+    BankRank, LoanTerms, MAX_INTEREST_CATCHUP_DAYS, accrued_interest, daily_interest, parse_amount,
+    split_payment,
+};
+
+#[test]
+fn rank_follows_days_in_good_standing() {
+    assert_eq!(BankRank::for_days(0), BankRank::Copper);
+    assert_eq!(BankRank::for_days(7), BankRank::Silver);
+    assert_eq!(BankRank::for_days(29), BankRank::Silver);
+    assert_eq!(BankRank::for_days(400), BankRank::Platinum);
+    assert_eq!(BankRank::Gold.next(), Some(BankRank::Platinum));
+    assert_eq!(BankRank::Platinum.next(), None);
+}
+
+#[test]
+fn interest_is_capped_per_day_and_catchup_is_limited() {
+    assert_eq!(daily_interest(10_000, BankRank::Copper), 10);
+    assert_eq!(daily_interest(1_000_000, BankRank::Copper), 50);
+    assert_eq!(daily_interest(-5, BankRank::Gold), 0);
+    // Compounds: the second day earns on the first day's interest too.
+    assert_eq!(accrued_interest(100_000, BankRank::Silver, 2), 150 + 150);
+    assert_eq!(accrued_interest(10_000, BankRank::Gold, 1), 20);
+    assert_eq!(
+        accrued_interest(1_000_000, BankRank::Copper, 30),
+        50 * MAX_INTEREST_CATCHUP_DAYS
+    );
+}
+
+#[test]
+fn loan_terms_and_payments() {
+    let terms = LoanTerms::for_principal(1_000);
+    assert_eq!(terms.total_due, 1_100);
+    assert_eq!(terms.installment, 275);
+    assert_eq!(LoanTerms::for_principal(101).installment, 28);
+
+    assert_eq!(split_payment(275, 300, 0), Some((275, 0)));
+    assert_eq!(split_payment(275, 100, 200), Some((100, 175)));
+    assert_eq!(split_payment(275, 100, 100), None);
+
+    assert_eq!(parse_amount("all"), Ok(None));
+    assert_eq!(parse_amount("1,500"), Ok(Some(1_500)));
+    assert!(parse_amount("0").is_err());
+    assert!(parse_amount("lots").is_err());
+}
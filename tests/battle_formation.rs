@@ -0,0 +1,44 @@
+use gamemaster_bot::database::models::{FormationRow, Unit, UnitKind, UnitRarity};
+use gamemaster_bot::saga::battle::logic::{
+    process_enemy_turn, row_attack_multiplier, targetable_indices,
+};
+use gamemaster_bot::saga::battle::state::{BattleSession, BattleUnit};
+
+fn unit(name: &str, is_ranged: bool) -> BattleUnit {
+    BattleUnit::from_unit(&Unit {
+        unit_id: 1,
+        name: name.into(),
+        description: None,
+        base_attack: 20,
+        base_defense: 2,
+        base_health: 100,
+        is_recruitable: false,
+        is_ranged,
+        kind: UnitKind::Human,
+        rarity: UnitRarity::Common,
+    })
+}
+
+#[test]
+fn front_row_is_targeted_before_back_row() {
+    let front = unit("Squire", false).with_formation(FormationRow::Front, false);
+    let back = unit("Mage", true).with_formation(FormationRow::Back, true);
+    let mut session = BattleSession::new(vec![front, back], vec![unit("Brute", false)]);
+    process_enemy_turn(&mut session);
+    assert!(session.player_party[0].current_hp < 100);
+    assert_eq!(session.player_party[1].current_hp, 100);
+
+    // Once the front row falls the back row becomes targetable.
+    session.player_party[0].current_hp = 0;
+    assert_eq!(targetable_indices(&session.player_party), vec![1]);
+}
+
+#[test]
+fn back_row_modifiers_depend_on_attack_style() {
+    let ranged = unit("Ranger", true).with_formation(FormationRow::Back, true);
+    let melee = unit("Brawler", false).with_formation(FormationRow::Back, false);
+    let front = unit("Militia", false);
+    assert!(row_attack_multiplier(&ranged) > 1.0);
+    assert!(row_attack_multiplier(&melee) < 1.0);
+    assert_eq!(row_attack_multiplier(&front), 1.0);
+}
@@ -0,0 +1,104 @@
+use gamemaster_bot::commands::blackjack::rules::{HouseRules, MAX_HANDS};
+use gamemaster_bot::commands::blackjack::state::Hand;
+use gamemaster_bot::commands::games::card::{Card, Rank, Suit};
+use gamemaster_bot::commands::games::shoe::Shoe;
+
+fn hand(ranks: &[Rank]) -> Hand {
+    let mut hand = Hand::new(10);
+    for &rank in ranks {
+        hand.add_card(Card {
+            suit: Suit::Spades,
+            rank,
+        });
+    }
+    hand
+}
+
+#[test]
+fn shoe_reaches_cut_card_at_penetration() {
+    let mut shoe = Shoe::new(2, 75);
+    assert_eq!(shoe.size(), 104);
+    assert_eq!(shoe.cards_remaining(), 104);
+    for _ in 0..77 {
+        shoe.deal_one().unwrap();
+    }
+    assert!(!shoe.cut_card_reached());
+    shoe.deal_one().unwrap();
+    assert!(shoe.cut_card_reached());
+
+    shoe.reshuffle();
+    assert_eq!(shoe.cards_remaining(), 104);
+    assert!(!shoe.cut_card_reached());
+}
+
+#[test]
+fn empty_shoe_refills_from_discards_only() {
+    let mut shoe = Shoe::new(1, 100);
+    let mut in_play = Vec::new();
+    while let Some(card) = shoe.deal_one() {
+        in_play.push(card);
+    }
+    // Nothing has been discarded yet, so there is nothing to refill from.
+    assert_eq!(shoe.cards_remaining(), 0);
+    shoe.discard(in_play.drain(..10));
+    for _ in 0..10 {
+        assert!(shoe.deal_one().is_some());
+    }
+    assert!(shoe.deal_one().is_none());
+}
+
+#[test]
+fn soft_hands_and_dealer_soft_17() {
+    let soft_17 = hand(&[Rank::Ace, Rank::Six]);
+    let hard_17 = hand(&[Rank::Ten, Rank::Seven]);
+    assert!(soft_17.is_soft());
+    assert!(!hard_17.is_soft());
+    assert!(!hand(&[Rank::Ace, Rank::Six, Rank::Ten]).is_soft());
+
+    let vegas = HouseRules::vegas();
+    let friendly = HouseRules::friendly();
+    assert!(vegas.dealer_hits(&soft_17));
+    assert!(!friendly.dealer_hits(&soft_17));
+    assert!(!vegas.dealer_hits(&hard_17));
+    assert!(friendly.dealer_hits(&hand(&[Rank::Ten, Rank::Six])));
+}
+
+#[test]
+fn split_hands_follow_house_rules() {
+    let mut vegas = HouseRules::vegas();
+    let friendly = HouseRules::friendly();
+
+    let mut aces = hand(&[Rank::Ace, Rank::Ace]);
+    aces.from_split = true;
+    assert!(!vegas.can_hit(&aces));
+    assert!(!vegas.can_split(&aces, 2));
+    assert!(friendly.can_split(&aces, 2));
+    assert!(!friendly.can_split(&aces, MAX_HANDS));
+
+    let mut split_nine = hand(&[Rank::Nine, Rank::Two]);
+    split_nine.from_split = true;
+    assert!(vegas.can_double(&split_nine));
+    assert!(!friendly.can_surrender(&split_nine));
+    vegas.double_after_split = false;
+    assert!(!vegas.can_double(&split_nine));
+
+    let opening = hand(&[Rank::Ten, Rank::Six]);
+    assert!(friendly.can_surrender(&opening));
+    assert!(!HouseRules::vegas().can_surrender(&opening));
+}
+
+#[test]
+fn rules_parse_presets_and_flags() {
+    assert_eq!(HouseRules::from_args([]).unwrap(), HouseRules::friendly());
+    assert_eq!(HouseRules::from_args(["vegas"]).unwrap().name(), "Vegas");
+
+    let custom = HouseRules::from_args(["vegas", "s17", "8d", "3:2"]).unwrap();
+    assert_eq!(custom.name(), "Custom");
+    assert!(!custom.dealer_hits_soft_17);
+    assert_eq!(custom.decks, 8);
+    assert_eq!(custom.blackjack_payout(10), 15);
+    assert_eq!(HouseRules::vegas().blackjack_payout(10), 12);
+
+    assert!(HouseRules::from_args(["9d"]).is_err());
+    assert!(HouseRules::from_args(["hit-everything"]).is_err());
+}
@@ -0,0 +1,57 @@
+use gamemaster_bot::database::models::UnitRarity;
+use gamemaster_bot::saga::negotiation::{
+    Counter, NegotiationOutcome, flexibility_pct, opening_demands, resolve_counter,
+};
+
+#[test]
+fn leverage_grows_with_fame_and_extra_defeats() {
+    assert_eq!(flexibility_pct(0, 0), 5);
+    assert!(flexibility_pct(2, 0) > flexibility_pct(1, 0));
+    assert!(flexibility_pct(1, 3) > flexibility_pct(1, 0));
+    // Extra defeats are capped so grinding cannot replace fame entirely.
+    assert_eq!(flexibility_pct(0, 5), flexibility_pct(0, 50));
+}
+
+#[test]
+fn counters_resolve_against_opening_demands() {
+    let opening = opening_demands(UnitRarity::Epic);
+    assert!(opening.party_slot);
+    // Low leverage: a lowball offends and raises demands.
+    match resolve_counter(&opening, &opening, Counter::Lowball, flexibility_pct(0, 0)) {
+        NegotiationOutcome::Offended(t) => assert!(t.wage > opening.wage),
+        other => panic!("expected offence, got {:?}", other),
+    }
+    // High leverage: the same counter is agreed outright.
+    match resolve_counter(&opening, &opening, Counter::Lowball, flexibility_pct(3, 5)) {
+        NegotiationOutcome::Agreed(t) => assert_eq!(t.wage, opening.wage / 2),
+        other => panic!("expected agreement, got {:?}", other),
+    }
+    // Just outside flexibility ends in a compromise at half the requested cut.
+    match resolve_counter(&opening, &opening, Counter::Firm, flexibility_pct(1, 0)) {
+        NegotiationOutcome::Compromise(t) => assert_eq!(t.wage, opening.wage * 85 / 100),
+        other => panic!("expected compromise, got {:?}", other),
+    }
+}
+
+#[test]
+fn party_slot_waiver_needs_leverage() {
+    let opening = opening_demands(UnitRarity::Legendary);
+    assert_eq!(
+        resolve_counter(
+            &opening,
+            &opening,
+            Counter::WaiveSlot,
+            flexibility_pct(0, 0)
+        ),
+        NegotiationOutcome::Refused
+    );
+    match resolve_counter(
+        &opening,
+        &opening,
+        Counter::WaiveSlot,
+        flexibility_pct(2, 0),
+    ) {
+        NegotiationOutcome::Agreed(t) => assert!(!t.party_slot),
+        other => panic!("expected waiver, got {:?}", other),
+    }
+}
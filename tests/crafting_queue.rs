@@ -0,0 +1,62 @@
+use chrono::Duration;
+use gamemaster_bot::commands::craft::profession::{
+    CraftOutcome, MIN_DURATION_PCT, craft_duration, masterwork_chance_pct, roll_batch,
+    save_chance_pct,
+};
+use gamemaster_bot::database::models::Recipe;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn potion_recipe(masterwork_item_id: Option<i32>) -> Recipe {
+    Recipe {
+        recipe_id: 1,
+        output_item_id: 11,
+        output_quantity: 1,
+        craft_secs: 60,
+        xp_reward: 10,
+        required_level: 1,
+        masterwork_item_id,
+    }
+}
+
+#[test]
+fn batch_duration_scales_with_count_and_level() {
+    assert_eq!(craft_duration(60, 1, 10), Duration::minutes(10));
+    assert_eq!(craft_duration(60, 6, 1), Duration::seconds(54));
+    assert_eq!(
+        craft_duration(60, 100, 1),
+        Duration::seconds(60 * MIN_DURATION_PCT / 100)
+    );
+}
+
+#[test]
+fn novices_never_roll_masterwork_or_save() {
+    assert_eq!(masterwork_chance_pct(1), 0);
+    assert_eq!(save_chance_pct(1), 0);
+    let mut rng = StdRng::seed_from_u64(3);
+    let outcome = roll_batch(1, 10, &mut rng);
+    assert_eq!(outcome.total(), 10);
+    assert_eq!(outcome.masterwork, 0);
+    assert_eq!(outcome.saved, 0);
+
+    let veteran = roll_batch(30, 200, &mut rng);
+    assert_eq!(veteran.total(), 200);
+    assert!(veteran.masterwork > 0 && veteran.fine > 0 && veteran.saved > 0);
+}
+
+#[test]
+fn outputs_follow_quality_tiers() {
+    let outcome = CraftOutcome {
+        standard: 2,
+        fine: 1,
+        masterwork: 1,
+        saved: 0,
+    };
+    let upgraded = outcome.outputs(&potion_recipe(Some(17)));
+    assert_eq!(upgraded.get(&11), Some(&4));
+    assert_eq!(upgraded.get(&17), Some(&1));
+
+    let doubled = outcome.outputs(&potion_recipe(None));
+    assert_eq!(doubled.get(&11), Some(&6));
+    assert_eq!(doubled.len(), 1);
+}
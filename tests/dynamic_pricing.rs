@@ -0,0 +1,60 @@
+use gamemaster_bot::commands::economy::core::pricing::{
+    BASE_BP, PriceBand, PriceState, Trend, apply_multiplier, close_multiplier, live_multiplier,
+    quote_total,
+};
+
+#[test]
+fn selling_pressure_lowers_the_price_within_the_band() {
+    let band = PriceBand::default();
+    let dumped = live_multiplier(BASE_BP, 0, 1_000, band);
+    assert!(dumped < BASE_BP);
+    // A month of dumping cannot push the price below the band floor.
+    let mut bp = BASE_BP;
+    for _ in 0..30 {
+        bp = close_multiplier(bp, 0, 10_000, band);
+    }
+    assert!(bp >= band.min_pct * 100);
+    assert_eq!(live_multiplier(bp, 0, 10_000, band), band.min_pct * 100);
+    assert!(live_multiplier(BASE_BP, 1_000_000, 0, band) <= band.max_pct * 100);
+}
+
+#[test]
+fn quiet_days_drift_back_to_base() {
+    let band = PriceBand::default();
+    let mut bp = 15_000;
+    for _ in 0..60 {
+        bp = close_multiplier(bp, 0, 0, band);
+    }
+    assert!((bp - BASE_BP).abs() < 50);
+    // Balanced volume does not move the price.
+    assert_eq!(live_multiplier(BASE_BP, 40, 40, band), BASE_BP);
+}
+
+#[test]
+fn large_orders_pay_for_their_own_impact() {
+    let band = PriceBand::default();
+    let after = live_multiplier(BASE_BP, 0, 100, band);
+    let total = quote_total(250, BASE_BP, after, 100);
+    assert!(total < 250 * 100);
+    assert!(total > apply_multiplier(250, after) * 100);
+    assert_eq!(apply_multiplier(1, band.min_pct * 100), 1);
+}
+
+#[test]
+fn trend_arrows_and_band_config() {
+    let up = PriceState {
+        open_bp: BASE_BP,
+        live_bp: 11_200,
+    };
+    assert_eq!(up.trend(), Trend::Up);
+    assert_eq!(up.trend_label(), "▲ +12%");
+    assert_eq!(PriceState::default().trend_label(), "▬");
+    assert_eq!(
+        PriceBand::parse(Some("80"), Some("150")),
+        PriceBand::new(80, 150).unwrap()
+    );
+    assert_eq!(
+        PriceBand::parse(Some("120"), Some("150")),
+        PriceBand::default()
+    );
+}
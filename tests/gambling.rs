@@ -0,0 +1,84 @@
+use chrono::{Duration, Utc};
+use gamemaster_bot::commands::economy::gambling::logic::{
+    LimitChange, check_play, effective_loss_limit, limit_change, parse_cooldown, parse_limit,
+    totals,
+};
+use gamemaster_bot::database::models::{GambleGame, GamblingLimits, GamblingStat};
+
+fn stat(game: GambleGame, hands: i64, net: i64, biggest_win: i64) -> GamblingStat {
+    GamblingStat {
+        game,
+        hands_played: hands,
+        hands_won: hands / 2,
+        hands_lost: hands - hands / 2,
+        net,
+        biggest_win,
+    }
+}
+
+#[test]
+fn totals_sum_games_and_keep_the_biggest_win() {
+    let stats = [
+        stat(GambleGame::Blackjack, 10, 150, 90),
+        stat(GambleGame::Rps, 3, -40, 20),
+    ];
+    let total = totals(&stats);
+    assert_eq!(total.hands_played, 13);
+    assert_eq!(total.hands_won, 6);
+    assert_eq!(total.net, 110);
+    assert_eq!(total.biggest_win, 90);
+}
+
+#[test]
+fn tightening_applies_now_and_loosening_waits() {
+    assert_eq!(limit_change(None, Some(500)), LimitChange::Now);
+    assert_eq!(limit_change(Some(500), Some(200)), LimitChange::Now);
+    assert_eq!(limit_change(Some(500), Some(800)), LimitChange::Delayed);
+    assert_eq!(limit_change(Some(500), None), LimitChange::Delayed);
+}
+
+#[test]
+fn pending_limit_takes_over_once_due() {
+    let now = Utc::now();
+    let mut limits = GamblingLimits {
+        daily_loss_limit: Some(100),
+        pending_loss_limit: None,
+        pending_from: Some(now + Duration::hours(1)),
+        cooldown_until: None,
+    };
+    assert_eq!(effective_loss_limit(&limits, now), Some(100));
+    limits.pending_from = Some(now - Duration::hours(1));
+    assert_eq!(effective_loss_limit(&limits, now), None);
+}
+
+#[test]
+fn play_is_blocked_by_cooldowns_and_loss_limits() {
+    let now = Utc::now();
+    let limited = GamblingLimits {
+        daily_loss_limit: Some(300),
+        ..Default::default()
+    };
+    assert!(check_play(&limited, -299, now).is_ok());
+    assert!(check_play(&limited, -300, now).is_err());
+    assert!(check_play(&GamblingLimits::default(), -10_000, now).is_ok());
+
+    let resting = GamblingLimits {
+        cooldown_until: Some(now + Duration::minutes(5)),
+        ..Default::default()
+    };
+    assert!(check_play(&resting, 0, now).is_err());
+    assert!(check_play(&resting, 0, now + Duration::minutes(6)).is_ok());
+}
+
+#[test]
+fn parses_limits_and_cooldowns() {
+    assert_eq!(parse_limit("1,000"), Ok(Some(1000)));
+    assert_eq!(parse_limit("off"), Ok(None));
+    assert!(parse_limit("-5").is_err());
+
+    assert_eq!(parse_cooldown("12h"), Ok(Duration::hours(12)));
+    assert_eq!(parse_cooldown("3d"), Ok(Duration::days(3)));
+    assert_eq!(parse_cooldown("6"), Ok(Duration::hours(6)));
+    assert!(parse_cooldown("31d").is_err());
+    assert!(parse_cooldown("soon").is_err());
+}
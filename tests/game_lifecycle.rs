@@ -0,0 +1,210 @@
+use gamemaster_bot::commands::games::lifecycle::{IDLE_LIMIT, MAX_GAMES_PER_USER, SessionClock};
+use gamemaster_bot::commands::games::{Game, GameManager, GamePayout, GameUpdate};
+use serenity::async_trait;
+use serenity::builder::{CreateActionRow, CreateEmbed};
+use serenity::http::{Http, HttpBuilder};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::id::{ChannelId, MessageId, UserId};
+use serenity::prelude::Context;
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+struct Stub {
+    players: Vec<UserId>,
+    timeout: Option<Duration>,
+    abandoned: Arc<AtomicBool>,
+    /// What the game says it owes its players when it is abandoned.
+    owed: Vec<GamePayout>,
+}
+
+#[async_trait]
+impl Game for Stub {
+    async fn handle_interaction(
+        &mut self,
+        _ctx: &Context,
+        _interaction: &mut ComponentInteraction,
+        _db: &PgPool,
+    ) -> GameUpdate {
+        GameUpdate::NoOp
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        (String::new(), CreateEmbed::new(), vec![])
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        self.players.clone()
+    }
+
+    fn deadline(&self, clock: &SessionClock) -> Option<Instant> {
+        self.timeout.map(|t| clock.started + t)
+    }
+
+    async fn on_abandon(&mut self, _db: &PgPool, _message_id: MessageId) -> Vec<GamePayout> {
+        self.abandoned.store(true, Ordering::SeqCst);
+        self.owed.clone()
+    }
+}
+
+fn stub(players: &[u64], timeout: Option<Duration>) -> Box<dyn Game> {
+    Box::new(Stub {
+        players: players.iter().map(|&id| UserId::new(id)).collect(),
+        timeout,
+        abandoned: Arc::default(),
+        owed: Vec::new(),
+    })
+}
+
+/// Discord and the database both refuse straight away, so closing a game only logs.
+fn offline() -> (Http, PgPool) {
+    let http = HttpBuilder::new("")
+        .proxy("http://127.0.0.1:9")
+        .ratelimiter_disabled(true)
+        .build();
+    let db = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_lazy("postgres://127.0.0.1:9/unused")
+        .unwrap();
+    (http, db)
+}
+
+const CHANNEL: ChannelId = ChannelId::new(1);
+
+#[test]
+fn players_at_the_cap_cannot_open_another_game() {
+    let manager = GameManager::new();
+    for i in 0..MAX_GAMES_PER_USER as u64 {
+        assert!(
+            manager
+                .start_game(CHANNEL, MessageId::new(10 + i), stub(&[1], None))
+                .is_ok()
+        );
+    }
+    assert_eq!(manager.games_of(UserId::new(1)), MAX_GAMES_PER_USER);
+
+    // A challenge counts against both players, so the busy one blocks it.
+    let refused = manager.start_game(CHANNEL, MessageId::new(20), stub(&[2, 1], None));
+    assert!(refused.unwrap_err().contains("<@1>"));
+    assert!(
+        manager
+            .start_game(CHANNEL, MessageId::new(21), stub(&[2], None))
+            .is_ok()
+    );
+
+    // Games the bot schedules itself are never refused.
+    manager.start_scheduled_game(CHANNEL, MessageId::new(22), stub(&[1, 2], None));
+    assert_eq!(manager.games_of(UserId::new(1)), MAX_GAMES_PER_USER + 1);
+
+    let totals = manager.metrics(Instant::now()).totals;
+    assert_eq!(totals.started, MAX_GAMES_PER_USER as u64 + 2);
+    assert_eq!(totals.refused, 1);
+}
+
+#[test]
+fn the_sweeper_finds_expired_clocks_and_idle_games() {
+    let manager = GameManager::new();
+    let now = Instant::now();
+    manager.start_scheduled_game(
+        CHANNEL,
+        MessageId::new(1),
+        stub(&[1], Some(Duration::from_secs(30))),
+    );
+    manager.start_scheduled_game(CHANNEL, MessageId::new(2), stub(&[2], None));
+
+    assert!(manager.timed_out(now).is_empty());
+    assert_eq!(
+        manager.timed_out(now + Duration::from_secs(31)),
+        vec![MessageId::new(1)]
+    );
+
+    assert!(manager.idle(now + Duration::from_secs(60)).is_empty());
+    let mut idle = manager.idle(now + IDLE_LIMIT + Duration::from_secs(1));
+    idle.sort();
+    assert_eq!(idle, vec![MessageId::new(1), MessageId::new(2)]);
+}
+
+#[test]
+fn metrics_describe_the_open_sessions() {
+    let manager = GameManager::new();
+    assert_eq!(manager.metrics(Instant::now()).active, 0);
+    assert!(manager.metrics(Instant::now()).busiest.is_none());
+
+    manager.start_scheduled_game(CHANNEL, MessageId::new(1), stub(&[1, 2], None));
+    manager.start_scheduled_game(CHANNEL, MessageId::new(2), stub(&[2, 3], None));
+    manager.start_scheduled_game(CHANNEL, MessageId::new(3), stub(&[2], None));
+
+    let later = Instant::now() + Duration::from_secs(90);
+    let metrics = manager.metrics(later);
+    assert_eq!(metrics.active, 3);
+    assert_eq!(metrics.players, 3);
+    assert_eq!(metrics.busiest, Some((UserId::new(2), 3)));
+    assert_eq!(metrics.quiet, 3);
+    assert!(
+        metrics
+            .oldest
+            .is_some_and(|age| age >= Duration::from_secs(90))
+    );
+    assert!(metrics.lines()[0].starts_with("Active games: 3 with 3 player(s)"));
+
+    manager.remove_game(&MessageId::new(3));
+    assert_eq!(manager.games_of(UserId::new(2)), 2);
+}
+
+#[tokio::test]
+async fn the_idle_sweep_abandons_a_game_through_its_hook() {
+    let manager = GameManager::new();
+    let abandoned = Arc::new(AtomicBool::new(false));
+    let game = Stub {
+        players: vec![UserId::new(1)],
+        timeout: None,
+        abandoned: abandoned.clone(),
+        owed: Vec::new(),
+    };
+    manager.start_scheduled_game(CHANNEL, MessageId::new(1), Box::new(game));
+    let (http, db) = offline();
+
+    manager
+        .sweep_at(&http, &db, Instant::now() + Duration::from_secs(60))
+        .await;
+    assert!(!abandoned.load(Ordering::SeqCst));
+    assert_eq!(manager.games_of(UserId::new(1)), 1);
+
+    manager
+        .sweep_at(
+            &http,
+            &db,
+            Instant::now() + IDLE_LIMIT + Duration::from_secs(1),
+        )
+        .await;
+    assert!(abandoned.load(Ordering::SeqCst));
+    assert_eq!(manager.games_of(UserId::new(1)), 0);
+    assert_eq!(manager.metrics(Instant::now()).totals.abandoned, 1);
+}
+
+#[tokio::test]
+async fn a_game_closes_even_when_its_payouts_fail() {
+    let manager = GameManager::new();
+    let game = Stub {
+        players: vec![UserId::new(1)],
+        timeout: None,
+        abandoned: Arc::default(),
+        owed: vec![GamePayout {
+            user_id: UserId::new(1),
+            amount: 50,
+        }],
+    };
+    manager.start_scheduled_game(CHANNEL, MessageId::new(1), Box::new(game));
+    let (http, db) = offline();
+
+    // The database is unreachable, so the refund can't be paid, but the game is still closed
+    // rather than left for the next sweep.
+    let later = Instant::now() + IDLE_LIMIT + Duration::from_secs(1);
+    manager.sweep_at(&http, &db, later).await;
+    assert_eq!(manager.games_of(UserId::new(1)), 0);
+    assert_eq!(manager.metrics(Instant::now()).active, 0);
+    manager.sweep_at(&http, &db, later).await;
+    assert_eq!(manager.metrics(Instant::now()).totals.abandoned, 1);
+}
@@ -0,0 +1,322 @@
+use gamemaster_bot::commands::games::lifecycle::SessionClock;
+use gamemaster_bot::commands::games::{Game, GameManager, GameUpdate};
+use serenity::async_trait;
+use serenity::builder::{CreateActionRow, CreateEmbed};
+use serenity::cache::Cache;
+use serenity::gateway::{
+    Shard, ShardManager, ShardManagerOptions, ShardMessenger, ShardRunner, ShardRunnerOptions,
+};
+use serenity::http::{Http, HttpBuilder};
+use serenity::json::{from_value, json};
+use serenity::model::application::ComponentInteraction;
+use serenity::model::gateway::{GatewayIntents, ShardInfo};
+use serenity::model::id::{ChannelId, MessageId, ShardId, UserId};
+use serenity::prelude::{Context, TypeMap};
+use sqlx::PgPool;
+use sqlx::postgres::PgPoolOptions;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, RwLock, watch};
+
+/// How long a click keeps its game busy when nothing lets it go, like a battle resolving a turn.
+const TURN: Duration = Duration::from_millis(50);
+const GAMES: u64 = 16;
+
+/// Counts how many games are busy at once. A busy game holds on until the test opens the gate
+/// (or `hold` runs out), so a test can catch every game that can be busy at the same time.
+struct Load {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+    gate: watch::Sender<bool>,
+    hold: Option<Duration>,
+}
+
+impl Load {
+    fn new(hold: Option<Duration>) -> Arc<Self> {
+        Arc::new(Self {
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            gate: watch::Sender::new(false),
+            hold,
+        })
+    }
+
+    async fn busy(&self) {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        let mut gate = self.gate.subscribe();
+        let open = gate.wait_for(|open| *open);
+        match self.hold {
+            Some(hold) => drop(tokio::time::timeout(hold, open).await),
+            None => drop(open.await),
+        }
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Waits until `games` are busy at once, then lets them all finish.
+    async fn release_when_busy(&self, games: usize) {
+        let waited = tokio::time::timeout(Duration::from_secs(5), async {
+            while self.in_flight.load(Ordering::SeqCst) < games {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await;
+        self.gate.send_replace(true);
+        assert!(
+            waited.is_ok(),
+            "only {} of {} games were ever busy at once",
+            self.peak.load(Ordering::SeqCst),
+            games
+        );
+    }
+}
+
+/// A game whose every click is slow, and whose clock may already have run out. Its timeout is
+/// as slow and then stops the clock, so the manager has nothing to post.
+struct Slow {
+    player: UserId,
+    load: Arc<Load>,
+    due: Option<Instant>,
+}
+
+#[async_trait]
+impl Game for Slow {
+    async fn handle_interaction(
+        &mut self,
+        _ctx: &Context,
+        _interaction: &mut ComponentInteraction,
+        _db: &PgPool,
+    ) -> GameUpdate {
+        self.load.busy().await;
+        GameUpdate::NoOp
+    }
+
+    fn render(&self) -> (String, CreateEmbed, Vec<CreateActionRow>) {
+        (String::new(), CreateEmbed::new(), vec![])
+    }
+
+    fn participants(&self) -> Vec<UserId> {
+        vec![self.player]
+    }
+
+    fn deadline(&self, _clock: &SessionClock) -> Option<Instant> {
+        self.due
+    }
+
+    async fn on_timeout(&mut self, _db: &PgPool, _message_id: MessageId) -> GameUpdate {
+        self.load.busy().await;
+        self.due = None;
+        GameUpdate::NoOp
+    }
+}
+
+fn slow(player: u64, load: &Arc<Load>, due: bool) -> Box<dyn Game> {
+    Box::new(Slow {
+        player: UserId::new(player),
+        load: load.clone(),
+        due: due.then(Instant::now),
+    })
+}
+
+fn manager_with_games(load: &Arc<Load>) -> GameManager {
+    let manager = GameManager::new();
+    for id in 1..=GAMES {
+        manager.start_scheduled_game(ChannelId::new(1), MessageId::new(id), slow(id, load, false));
+    }
+    manager
+}
+
+/// Nothing in these tests reaches Discord or the database: requests go to a closed port and
+/// fail at once.
+fn offline() -> (Arc<Http>, PgPool) {
+    let http = HttpBuilder::new("")
+        .proxy("http://127.0.0.1:9")
+        .ratelimiter_disabled(true)
+        .build();
+    let db = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_lazy("postgres://127.0.0.1:9/unused")
+        .unwrap();
+    (Arc::new(http), db)
+}
+
+/// A `Context` like the one the bot hands to `on_interaction`. Its shard is connected to a
+/// local socket that accepts the connection and never says anything.
+async fn offline_context(http: Arc<Http>) -> Context {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = Arc::new(Mutex::new(format!(
+        "ws://{}",
+        listener.local_addr().unwrap()
+    )));
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let _gateway = tokio_tungstenite::accept_async(stream).await;
+                std::future::pending::<()>().await;
+            });
+        }
+    });
+    let data = Arc::new(serenity::prelude::RwLock::new(TypeMap::new()));
+    let cache = Arc::new(Cache::new());
+    let (manager, _) = ShardManager::new(ShardManagerOptions {
+        data: data.clone(),
+        event_handlers: vec![],
+        raw_event_handlers: vec![],
+        shard_index: 0,
+        shard_init: 0,
+        shard_total: 1,
+        ws_url: ws_url.clone(),
+        cache: cache.clone(),
+        http: http.clone(),
+        intents: GatewayIntents::empty(),
+        presence: None,
+    });
+    let info = ShardInfo {
+        id: ShardId(0),
+        total: 1,
+    };
+    let shard = Shard::new(ws_url, "", info, GatewayIntents::empty(), None)
+        .await
+        .unwrap();
+    let runner = ShardRunner::new(ShardRunnerOptions {
+        data: data.clone(),
+        event_handlers: vec![],
+        raw_event_handlers: vec![],
+        manager,
+        shard,
+        cache: cache.clone(),
+        http: http.clone(),
+    });
+    Context {
+        data,
+        shard: ShardMessenger::new(&runner),
+        shard_id: ShardId(0),
+        http,
+        cache,
+    }
+}
+
+/// A player pressing a button on the game posted as `message`.
+fn click(message: u64, player: u64) -> ComponentInteraction {
+    from_value(json!({
+        "id": "1",
+        "application_id": "1",
+        "type": 3,
+        "data": { "custom_id": "slow_move", "component_type": 2 },
+        "channel_id": "1",
+        "user": { "id": player.to_string(), "username": "player" },
+        "token": "token",
+        "version": 1,
+        "message": {
+            "id": message.to_string(),
+            "channel_id": "1",
+            "author": { "id": "1", "username": "bot" },
+            "content": "",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0
+        },
+        "locale": "en-US",
+        "attachment_size_limit": 0,
+        "entitlements": []
+    }))
+    .unwrap()
+}
+
+/// Every player clicks their own game at once.
+async fn click_all<F, Fut>(press: F)
+where
+    F: Fn(ComponentInteraction) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let clicks: Vec<_> = (1..=GAMES)
+        .map(|id| tokio::spawn(press(click(id, id))))
+        .collect();
+    for click in clicks {
+        click.await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn clicks_on_different_games_run_side_by_side() {
+    let (http, db) = offline();
+    let ctx = offline_context(http).await;
+
+    let load = Load::new(None);
+    let manager = Arc::new(manager_with_games(&load));
+    let clicks = click_all(|mut interaction| {
+        let (manager, ctx, db) = (manager.clone(), ctx.clone(), db.clone());
+        async move { manager.on_interaction(&ctx, &mut interaction, &db).await }
+    });
+    tokio::join!(clicks, load.release_when_busy(GAMES as usize));
+    assert_eq!(load.peak.load(Ordering::SeqCst), GAMES as usize);
+    assert_eq!(manager.metrics(Instant::now()).active, GAMES as usize);
+
+    // The same clicks behind one manager-wide lock, which is how every click reached its game
+    // before each game had its own lock, are handled one at a time.
+    let load = Load::new(Some(TURN));
+    let global = Arc::new(RwLock::new(manager_with_games(&load)));
+    click_all(|mut interaction| {
+        let (global, ctx, db) = (global.clone(), ctx.clone(), db.clone());
+        async move {
+            let manager = global.write().await;
+            manager.on_interaction(&ctx, &mut interaction, &db).await
+        }
+    })
+    .await;
+    assert_eq!(load.peak.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn a_busy_game_does_not_hold_up_the_manager() {
+    let load = Load::new(None);
+    let manager = Arc::new(GameManager::new());
+    manager.start_scheduled_game(ChannelId::new(1), MessageId::new(1), slow(1, &load, true));
+    for id in 2..=GAMES {
+        manager.start_scheduled_game(
+            ChannelId::new(1),
+            MessageId::new(id),
+            slow(id, &load, false),
+        );
+    }
+
+    let (http, db) = offline();
+    let busy = {
+        let (manager, http, db) = (manager.clone(), http.clone(), db.clone());
+        tokio::spawn(async move { manager.sweep(&http, &db).await })
+    };
+    while load.in_flight.load(Ordering::SeqCst) == 0 {
+        tokio::task::yield_now().await;
+    }
+
+    // While game 1 is in its timeout, the rest of the manager answers straight away, and another
+    // sweep leaves the busy game for later instead of waiting on it.
+    assert!(
+        manager
+            .start_game(
+                ChannelId::new(1),
+                MessageId::new(100),
+                slow(1, &load, false)
+            )
+            .is_ok()
+    );
+    assert_eq!(manager.games_of(UserId::new(1)), 2);
+    assert_eq!(manager.metrics(Instant::now()).active, GAMES as usize + 1);
+    manager.sweep(&http, &db).await;
+    assert_eq!(load.in_flight.load(Ordering::SeqCst), 1);
+    assert_eq!(load.peak.load(Ordering::SeqCst), 1);
+
+    load.gate.send_replace(true);
+    busy.await.unwrap();
+    let now = Instant::now();
+    assert_eq!(manager.metrics(now).totals.timed_out, 1);
+    assert!(manager.timed_out(now).is_empty());
+}
@@ -0,0 +1,137 @@
+use gamemaster_bot::commands::games::card::{Card, Rank, Suit};
+use gamemaster_bot::commands::games::deck::Deck;
+use gamemaster_bot::commands::holdem::table::{HoldemAction, Pot, Seat, Street, Table, side_pots};
+use gamemaster_bot::commands::poker::hand_eval::evaluate_best_hand;
+use gamemaster_bot::commands::poker::state::HandRank;
+use serenity::model::id::UserId;
+
+fn card(rank: Rank, suit: Suit) -> Card {
+    Card { suit, rank }
+}
+
+fn seat(id: u64, contributed: i64, folded: bool) -> Seat {
+    Seat {
+        user_id: UserId::new(id),
+        stack: 0,
+        street_bet: 0,
+        contributed,
+        hole: Vec::new(),
+        folded,
+        acted: true,
+    }
+}
+
+#[test]
+fn best_five_of_seven_uses_kickers() {
+    let board = [
+        card(Rank::King, Suit::Hearts),
+        card(Rank::King, Suit::Clubs),
+        card(Rank::Nine, Suit::Spades),
+        card(Rank::Five, Suit::Diamonds),
+        card(Rank::Two, Suit::Hearts),
+    ];
+    let with = |a: Card, b: Card| {
+        let mut cards = board.to_vec();
+        cards.extend([a, b]);
+        evaluate_best_hand(&cards)
+    };
+    let ace_kicker = with(
+        card(Rank::Ace, Suit::Spades),
+        card(Rank::Three, Suit::Clubs),
+    );
+    let queen_kicker = with(
+        card(Rank::Queen, Suit::Spades),
+        card(Rank::Three, Suit::Diamonds),
+    );
+    assert_eq!(ace_kicker.rank, HandRank::OnePair(13));
+    assert_eq!(ace_kicker.cards.len(), 5);
+    assert!(ace_kicker > queen_kicker);
+
+    // Four hearts are no flush, but a fifth makes one even though two cards don't play.
+    let four_hearts = with(
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Four, Suit::Hearts),
+    );
+    assert_eq!(four_hearts.rank, HandRank::OnePair(13));
+    let mut hearts = board.to_vec();
+    hearts.extend([
+        card(Rank::Seven, Suit::Hearts),
+        card(Rank::Four, Suit::Hearts),
+        card(Rank::Three, Suit::Hearts),
+    ]);
+    hearts.remove(1);
+    assert_eq!(evaluate_best_hand(&hearts).rank, HandRank::Flush(13));
+}
+
+#[test]
+fn side_pots_cap_what_short_stacks_can_win() {
+    let seats = [
+        seat(1, 50, false),
+        seat(2, 100, false),
+        seat(3, 100, false),
+        seat(4, 30, true),
+    ];
+    assert_eq!(
+        side_pots(&seats),
+        vec![
+            Pot {
+                amount: 180,
+                eligible: vec![UserId::new(1), UserId::new(2), UserId::new(3)],
+            },
+            Pot {
+                amount: 100,
+                eligible: vec![UserId::new(2), UserId::new(3)],
+            },
+        ]
+    );
+}
+
+#[test]
+fn heads_up_betting_runs_through_the_streets() {
+    let (a, b) = (UserId::new(1), UserId::new(2));
+    // An unshuffled deck deals from the top of the spades: A♠K♠ to the button, Q♠J♠ to the
+    // big blind, and a spade board, so the button's ace-high flush wins.
+    let mut table = Table::new(vec![(a, 1000), (b, 600)], 20, Deck::new());
+    assert_eq!(table.pot_total(), 30);
+    assert_eq!(table.current_seat().map(|s| s.user_id), Some(a));
+    assert!(table.apply(b, HoldemAction::CheckCall).is_err());
+
+    table.apply(a, HoldemAction::CheckCall).unwrap();
+    // The big blind still gets the option to raise.
+    assert_eq!(table.street, Street::PreFlop);
+    table.apply(b, HoldemAction::CheckCall).unwrap();
+    assert_eq!(table.street, Street::Flop);
+    assert_eq!(table.board.len(), 3);
+    assert_eq!(table.current_seat().map(|s| s.user_id), Some(b));
+
+    table.apply(b, HoldemAction::AllIn).unwrap();
+    assert_eq!(table.to_call(), 580);
+    table.apply(a, HoldemAction::CheckCall).unwrap();
+    assert!(table.is_finished());
+    assert_eq!(table.board.len(), 5);
+
+    let (results, net) = table.settle();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].winners, vec![a]);
+    assert_eq!(net, vec![(a, 600), (b, -600)]);
+}
+
+#[test]
+fn short_all_in_only_wins_the_main_pot() {
+    let (a, b, c) = (UserId::new(1), UserId::new(2), UserId::new(3));
+    let mut table = Table::new(vec![(a, 1000), (b, 1000), (c, 100)], 20, Deck::new());
+    // Three-handed the button acts first before the flop.
+    table.apply(a, HoldemAction::AllIn).unwrap();
+    assert!(table.apply(b, HoldemAction::MinRaise).is_err());
+    table.apply(b, HoldemAction::CheckCall).unwrap();
+    table.apply(c, HoldemAction::AllIn).unwrap();
+    assert!(table.is_finished());
+
+    let (results, net) = table.settle();
+    assert_eq!(
+        results.iter().map(|r| r.amount).collect::<Vec<_>>(),
+        vec![300, 1800]
+    );
+    assert_eq!(net.iter().map(|(_, n)| n).sum::<i64>(), 0);
+    assert_eq!(net, vec![(a, 1100), (b, -1000), (c, -100)]);
+}
@@ -0,0 +1,31 @@
+use gamemaster_bot::saga::upkeep::{
+    MORALE_MAX, MORALE_REST_GAIN, MORALE_UNPAID_PENALTY, morale_after_settlement,
+    morale_stat_multiplier, settle_wages,
+};
+
+#[test]
+fn wages_are_paid_per_full_day_until_coins_run_out() {
+    // 3 days owed at 40/day with 100 coins: two days paid, one unpaid.
+    assert_eq!(settle_wages(100, 40, 3), (2, 1, 80));
+    assert_eq!(settle_wages(0, 40, 2), (0, 2, 0));
+    // No humans on the payroll costs nothing.
+    assert_eq!(settle_wages(0, 0, 4), (4, 0, 0));
+}
+
+#[test]
+fn morale_drops_when_unpaid_and_recovers_while_resting() {
+    assert_eq!(
+        morale_after_settlement(80, 1, 0),
+        80 - MORALE_UNPAID_PENALTY
+    );
+    assert_eq!(morale_after_settlement(50, 0, 1), 50 + MORALE_REST_GAIN);
+    assert_eq!(morale_after_settlement(95, 0, 3), MORALE_MAX);
+    assert_eq!(morale_after_settlement(10, 5, 0), 0);
+}
+
+#[test]
+fn low_morale_weakens_units() {
+    assert_eq!(morale_stat_multiplier(MORALE_MAX), 1.0);
+    assert!(morale_stat_multiplier(45) < 1.0);
+    assert!(morale_stat_multiplier(10) < morale_stat_multiplier(45));
+}
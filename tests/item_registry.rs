@@ -0,0 +1,87 @@
+use gamemaster_bot::commands::economy::core::item::{Item, ItemCategory, Rarity};
+use gamemaster_bot::commands::economy::core::registry::{ItemDef, ItemRegistry};
+
+fn custom_item(id: i32, key: &str, name: &str, aliases: &[&str]) -> ItemDef {
+    ItemDef {
+        id,
+        key: key.to_string(),
+        name: name.to_string(),
+        description: String::new(),
+        emoji: "🧩".to_string(),
+        category: ItemCategory::Resource,
+        rarity: Rarity::Common,
+        is_sellable: true,
+        is_tradeable: true,
+        buy_price: Some(40),
+        sell_price: Some(15),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+    }
+}
+
+#[test]
+fn builtin_registry_covers_every_item() {
+    let registry = ItemRegistry::builtin();
+    assert_eq!(registry.len(), Item::ALL.len());
+    for item in Item::ALL {
+        let def = registry.get(item.id()).expect("builtin item missing");
+        assert_eq!(def.name, item.display_name());
+        assert_eq!(
+            registry.resolve(&item.to_string()).map(|d| d.id),
+            Some(item.id())
+        );
+    }
+}
+
+#[test]
+fn resolve_accepts_keys_names_and_aliases() {
+    let registry = ItemRegistry::builtin();
+    let golden = Item::GoldenFish.id();
+    assert_eq!(registry.resolve("goldenfish").map(|d| d.id), Some(golden));
+    assert_eq!(registry.resolve("Golden Fish").map(|d| d.id), Some(golden));
+    assert_eq!(registry.resolve("golden").map(|d| d.id), Some(golden));
+    assert_eq!(
+        registry.resolve("XP_BOOSTER").map(|d| d.id),
+        Some(Item::XpBooster.id())
+    );
+    assert!(registry.resolve("dragon scale").is_none());
+}
+
+#[test]
+fn new_rows_need_no_code_changes() {
+    let mut defs: Vec<ItemDef> = Item::ALL.into_iter().map(ItemDef::builtin).collect();
+    defs.push(custom_item(50, "moonpearl", "Moon Pearl", &["pearl"]));
+    let registry = ItemRegistry::new(defs);
+    let pearl = registry.resolve("pearl").expect("alias resolves");
+    assert_eq!(pearl.id, 50);
+    assert!(
+        registry
+            .purchasable(ItemCategory::Resource)
+            .iter()
+            .any(|d| d.id == 50)
+    );
+    assert_eq!(registry.name_of(99), "Item #99");
+}
+
+#[test]
+fn keys_take_priority_over_aliases() {
+    // A later item claiming an existing key as alias must not shadow it.
+    let registry = ItemRegistry::new(vec![
+        custom_item(1, "fish", "Fish", &[]),
+        custom_item(2, "bait", "Bait", &["fish"]),
+    ]);
+    assert_eq!(registry.resolve("fish").map(|d| d.id), Some(1));
+}
+
+#[test]
+fn shop_lists_only_priced_items_in_category() {
+    let registry = ItemRegistry::builtin();
+    let resources: Vec<i32> = registry
+        .purchasable(ItemCategory::Resource)
+        .iter()
+        .map(|d| d.id)
+        .collect();
+    assert_eq!(
+        resources,
+        vec![Item::Fish.id(), Item::Ore.id(), Item::Gem.id()]
+    );
+}
@@ -0,0 +1,97 @@
+use chrono::{Duration, Utc};
+use gamemaster_bot::commands::economy::work::jobs::{
+    JobDef, JobPerk, JobProgress, PerkEffects, roll_work,
+};
+use gamemaster_bot::database::models::JobPerkKind;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn perk(required_level: i32, kind: JobPerkKind, value_pct: i32) -> JobPerk {
+    JobPerk {
+        required_level,
+        kind,
+        value_pct,
+        description: String::new(),
+    }
+}
+
+fn mining() -> JobDef {
+    JobDef {
+        key: "mining".into(),
+        display_name: "Mining".into(),
+        emoji: "⛏️".into(),
+        min_payout: 100,
+        max_payout: 300,
+        cooldown: Duration::hours(2),
+        xp_gain: 25,
+        resource_item_id: 2,
+        resource_min: 5,
+        resource_max: 15,
+        resource_level_divisor: 1,
+        rare_item_id: Some(5),
+        rare_chance_bp: 200,
+        perks: vec![
+            perk(5, JobPerkKind::CooldownReduction, 10),
+            perk(10, JobPerkKind::DoubleResource, 100),
+        ],
+    }
+}
+
+#[test]
+fn perks_unlock_at_their_level() {
+    let job = mining();
+    assert_eq!(job.effects_at(4), PerkEffects::default());
+    assert_eq!(job.effects_at(9).cooldown_reduction_pct, 10);
+    assert_eq!(job.effects_at(9).double_resource_pct, 0);
+    assert_eq!(job.effects_at(10).double_resource_pct, 100);
+    assert_eq!(job.cooldown_at(5), Duration::minutes(108));
+    assert_eq!(job.perks_unlocked(8, 11).len(), 1);
+    assert_eq!(job.next_perk(5).map(|p| p.required_level), Some(10));
+    assert!(job.next_perk(10).is_none());
+
+    // Stacked reductions are capped.
+    let capped = PerkEffects::at_level(
+        &[
+            perk(2, JobPerkKind::CooldownReduction, 40),
+            perk(3, JobPerkKind::CooldownReduction, 40),
+        ],
+        3,
+    );
+    assert_eq!(capped.cooldown_reduction_pct, 50);
+}
+
+#[test]
+fn double_resource_perk_doubles_the_haul() {
+    let job = mining();
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..50 {
+        let low = roll_work(&job, 9, 1, &mut rng);
+        assert!(!low.resource_doubled);
+        assert!((5 + 9..=15 + 9).contains(&low.resource));
+        let high = roll_work(&job, 10, 1, &mut rng);
+        assert!(high.resource_doubled);
+        assert!((2 * (5 + 10)..=2 * (15 + 10)).contains(&high.resource));
+        assert_eq!(high.rewards(&job).items[0], (2, high.resource));
+    }
+}
+
+#[test]
+fn cooldowns_are_per_job_progress() {
+    let job = mining();
+    let now = Utc::now();
+    let fresh = JobProgress::default();
+    assert!(fresh.cooldown_remaining(&job, now).is_none());
+    let worked = JobProgress {
+        last_worked_at: Some(now - Duration::hours(1)),
+        ..JobProgress::default()
+    };
+    assert_eq!(
+        worked.cooldown_remaining(&job, now),
+        Some(Duration::hours(1))
+    );
+    let veteran = JobProgress { level: 5, ..worked };
+    assert_eq!(
+        veteran.cooldown_remaining(&job, now),
+        Some(Duration::minutes(48))
+    );
+}
@@ -0,0 +1,26 @@
+use chrono::{TimeZone, Utc};
+use gamemaster_bot::database::ledger::LedgerSource;
+use gamemaster_bot::database::models::LedgerReason;
+use gamemaster_bot::services::ledger::{RECONCILE_HOUR_UTC, until_next_run};
+
+#[test]
+fn source_refs_are_kind_and_id() {
+    let source = LedgerReason::Market.with_ref("listing", 42);
+    assert_eq!(source.reason, LedgerReason::Market);
+    assert_eq!(source.source_ref.as_deref(), Some("listing:42"));
+    let plain: LedgerSource = LedgerReason::Battle.into();
+    assert_eq!(plain.source_ref, None);
+}
+
+#[test]
+fn reconciliation_runs_once_a_night() {
+    let before = Utc.with_ymd_and_hms(2025, 9, 23, 1, 30, 0).unwrap();
+    assert_eq!(
+        until_next_run(before).as_secs(),
+        (RECONCILE_HOUR_UTC as u64 - 1) * 3600 - 30 * 60
+    );
+    let at = Utc
+        .with_ymd_and_hms(2025, 9, 23, RECONCILE_HOUR_UTC, 0, 0)
+        .unwrap();
+    assert_eq!(until_next_run(at).as_secs(), 24 * 3600);
+}
@@ -0,0 +1,74 @@
+use gamemaster_bot::commands::economy::core::loot::{
+    CHANCE_SCALE, LootEntry, LootMode, LootTable, next_pity_misses,
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn entry(item_id: Option<i32>, weight: u32, is_rare: bool) -> LootEntry {
+    LootEntry {
+        item_id,
+        min_quantity: 1,
+        max_quantity: 3,
+        weight,
+        is_rare,
+    }
+}
+
+fn geode() -> LootTable {
+    LootTable {
+        key: "test_geode".into(),
+        mode: LootMode::Weighted,
+        rolls: 2,
+        pity_threshold: Some(5),
+        entries: vec![
+            entry(None, 90, false),
+            entry(Some(3), 10, false),
+            entry(Some(6), 1, true),
+        ],
+    }
+}
+
+#[test]
+fn weighted_rolls_respect_counts_and_quantities() {
+    let table = geode();
+    let mut rng = StdRng::seed_from_u64(7);
+    for _ in 0..200 {
+        let roll = table.roll(&mut rng, 0);
+        assert_eq!(roll.drops.len(), 2);
+        assert!(roll.drops.iter().all(|d| (1..=3).contains(&d.quantity)));
+    }
+}
+
+#[test]
+fn pity_guarantees_a_rare_after_a_dry_streak() {
+    let table = geode();
+    let mut rng = StdRng::seed_from_u64(1);
+    assert!(!table.pity_due(3));
+    assert!(table.pity_due(4));
+    let roll = table.roll(&mut rng, 4);
+    assert!(roll.pity_used && roll.got_rare());
+    assert_eq!(next_pity_misses(4, &roll), 0);
+    assert_eq!(table.opens_until_pity(2), Some(3));
+    // Simulated opens never go more than the threshold without a rare.
+    let mut misses = 0;
+    for _ in 0..500 {
+        let roll = table.roll(&mut rng, misses);
+        misses = next_pity_misses(misses, &roll);
+        assert!(misses < 5);
+    }
+}
+
+#[test]
+fn independent_tables_use_per_entry_chances() {
+    let always = LootEntry {
+        weight: CHANCE_SCALE,
+        ..entry(Some(1), 0, false)
+    };
+    let never = entry(Some(2), 0, false);
+    let table = LootTable::independent("node_1", vec![always, never]);
+    let mut rng = StdRng::seed_from_u64(3);
+    let roll = table.roll(&mut rng, 0);
+    assert_eq!(roll.drops.len(), 1);
+    assert_eq!(roll.drops[0].item_id, Some(1));
+    assert_eq!(table.opens_until_pity(0), None);
+}
@@ -0,0 +1,39 @@
+use gamemaster_bot::commands::economy::market::logic::{
+    DEFAULT_MARKET_TAX_PCT, LISTINGS_PER_PAGE, MAX_MARKET_TAX_PCT, market_tax, min_next_bid,
+    page_count, parse_tax_pct, seller_proceeds,
+};
+
+#[test]
+fn tax_is_taken_from_seller_proceeds() {
+    assert_eq!(market_tax(1000, 5), 50);
+    assert_eq!(seller_proceeds(1000, 5), 950);
+    // Small sales round the tax down rather than charging more than the rate.
+    assert_eq!(market_tax(19, 5), 0);
+    assert_eq!(seller_proceeds(19, 5), 19);
+    // Rates outside the allowed range are clamped.
+    assert_eq!(market_tax(100, 90), MAX_MARKET_TAX_PCT);
+}
+
+#[test]
+fn bids_must_beat_the_current_high_bid() {
+    assert_eq!(min_next_bid(200, None), 200);
+    assert_eq!(min_next_bid(200, Some(200)), 210);
+    // Cheap auctions still require at least one extra coin.
+    assert_eq!(min_next_bid(5, Some(5)), 6);
+}
+
+#[test]
+fn configured_tax_falls_back_to_default() {
+    assert_eq!(parse_tax_pct(Some("12")), 12);
+    assert_eq!(parse_tax_pct(Some("0")), 0);
+    assert_eq!(parse_tax_pct(Some("-3")), DEFAULT_MARKET_TAX_PCT);
+    assert_eq!(parse_tax_pct(Some("lots")), DEFAULT_MARKET_TAX_PCT);
+    assert_eq!(parse_tax_pct(None), DEFAULT_MARKET_TAX_PCT);
+}
+
+#[test]
+fn empty_market_still_has_one_page() {
+    assert_eq!(page_count(0), 1);
+    assert_eq!(page_count(LISTINGS_PER_PAGE), 1);
+    assert_eq!(page_count(LISTINGS_PER_PAGE + 1), 2);
+}
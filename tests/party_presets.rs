@@ -0,0 +1,19 @@
+use gamemaster_bot::constants::MAX_PRESET_NAME_LEN;
+use gamemaster_bot::database::presets::normalize_preset_name;
+
+#[test]
+fn preset_names_are_trimmed_and_collapsed() {
+    assert_eq!(
+        normalize_preset_name("  Forest   team ").unwrap(),
+        "Forest team"
+    );
+}
+
+#[test]
+fn preset_names_reject_empty_and_overlong() {
+    assert!(normalize_preset_name("   ").is_err());
+    let long = "x".repeat(MAX_PRESET_NAME_LEN + 1);
+    assert!(normalize_preset_name(&long).is_err());
+    let max = "x".repeat(MAX_PRESET_NAME_LEN);
+    assert!(normalize_preset_name(&max).is_ok());
+}
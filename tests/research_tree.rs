@@ -0,0 +1,65 @@
+use gamemaster_bot::saga::research::{
+    NodeStatus, RESEARCH_TREE, ResearchFamily, ResearchStanding, node_by_key,
+};
+
+fn standing(families: &[ResearchFamily], points: i32, unlocked: &[&str]) -> ResearchStanding {
+    ResearchStanding {
+        completed_families: families.to_vec(),
+        points_earned: points,
+        unlocked: unlocked.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+#[test]
+fn tree_prerequisites_reference_existing_nodes() {
+    for node in RESEARCH_TREE {
+        for req in node.requires {
+            assert!(
+                node_by_key(req).is_some(),
+                "{} requires unknown {}",
+                node.key,
+                req
+            );
+        }
+    }
+}
+
+#[test]
+fn node_status_checks_family_prerequisites_and_points() {
+    let alpha = node_by_key("beast_alpha_instinct").unwrap();
+    assert_eq!(
+        standing(&[], 5, &[]).status(alpha),
+        NodeStatus::FamilyIncomplete
+    );
+    assert_eq!(
+        standing(&[ResearchFamily::Beast], 5, &[]).status(alpha),
+        NodeStatus::MissingPrerequisite("Pack Tactics")
+    );
+    // Pack Tactics (1) spent leaves 1 point, Alpha Instinct costs 2.
+    assert_eq!(
+        standing(&[ResearchFamily::Beast], 2, &["beast_pack_tactics"]).status(alpha),
+        NodeStatus::NotEnoughPoints
+    );
+    assert_eq!(
+        standing(&[ResearchFamily::Beast], 3, &["beast_pack_tactics"]).status(alpha),
+        NodeStatus::Available
+    );
+}
+
+#[test]
+fn perks_stack_from_unlocked_nodes() {
+    let s = standing(
+        &[ResearchFamily::Beast],
+        10,
+        &[
+            "beast_pack_tactics",
+            "beast_alpha_instinct",
+            "beast_lure_craft",
+        ],
+    );
+    let perks = s.perks();
+    assert!((perks.pet_damage_mult - 1.10).abs() < 1e-6);
+    assert!((perks.lure_discount - 0.10).abs() < 1e-6);
+    assert_eq!(perks.research_drop_mult, 1.0);
+    assert_eq!(s.points_available(), 6);
+}
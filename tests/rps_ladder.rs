@@ -0,0 +1,89 @@
+use chrono::{Duration, TimeZone, Utc};
+use gamemaster_bot::commands::rps::bracket::{Bracket, seed_order};
+use gamemaster_bot::commands::rps::ladder::{
+    STARTING_RATING, match_window, pick_opponent, rate, season_at, season_ends, seeded_rating,
+};
+use gamemaster_bot::database::models::RpsQueueEntry;
+use serenity::model::id::UserId;
+
+fn queued(user_id: i64, rating: i32, minutes_ago: i64) -> RpsQueueEntry {
+    RpsQueueEntry {
+        user_id,
+        channel_id: 1,
+        rating,
+        queued_at: Utc::now() - Duration::minutes(minutes_ago),
+    }
+}
+
+#[test]
+fn upsets_move_ratings_more_and_provisional_players_move_faster() {
+    let even = rate(1200, 20, 1200, 20);
+    assert_eq!(even.winner_delta(), 12);
+    assert_eq!(even.loser_delta(), -12);
+
+    let upset = rate(1000, 20, 1400, 20);
+    let expected = rate(1400, 20, 1000, 20);
+    assert!(upset.winner_delta() > expected.winner_delta());
+
+    let provisional = rate(1200, 0, 1200, 20);
+    assert_eq!(provisional.winner_delta(), 20);
+    assert_eq!(provisional.loser_delta(), -12);
+}
+
+#[test]
+fn seasons_roll_over_and_soft_reset_ratings() {
+    let start = Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap();
+    assert_eq!(season_at(start), 1);
+    assert_eq!(season_at(start + Duration::days(27)), 1);
+    assert_eq!(season_at(start + Duration::days(28)), 2);
+    assert_eq!(season_ends(1), start + Duration::days(28));
+
+    assert_eq!(seeded_rating(None), STARTING_RATING);
+    assert_eq!(seeded_rating(Some(1600)), 1400);
+    assert_eq!(seeded_rating(Some(1000)), 1100);
+}
+
+#[test]
+fn matchmaking_prefers_the_closest_rating_within_a_widening_window() {
+    assert_eq!(match_window(Duration::zero()), 100);
+    assert_eq!(match_window(Duration::minutes(2)), 200);
+    assert_eq!(match_window(Duration::minutes(30)), 400);
+
+    let now = Utc::now();
+    let queue = [queued(1, 1500, 0), queued(2, 1260, 1), queued(3, 1230, 0)];
+    assert_eq!(pick_opponent(1200, &queue, now), Some(2));
+    // Nobody close enough yet, until someone has waited long enough to widen their window.
+    assert_eq!(pick_opponent(1200, &[queued(1, 1450, 0)], now), None);
+    assert_eq!(pick_opponent(1200, &[queued(1, 1450, 4)], now), Some(0));
+    // Stale entries are never matched.
+    assert_eq!(pick_opponent(1200, &[queued(1, 1200, 11)], now), None);
+}
+
+#[test]
+fn brackets_seed_top_players_apart_and_give_them_byes() {
+    assert_eq!(seed_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+
+    let players: Vec<UserId> = (1..=5).map(UserId::new).collect();
+    let bracket = Bracket::new(&players);
+    assert_eq!(bracket.rounds.len(), 3);
+    // Seeds 1-3 have byes: seeds 4 and 5 play in the first round, while seeds 2 and 3 go
+    // straight into their semifinal.
+    assert_eq!(bracket.ready_matches(), vec![(0, 1), (1, 1)]);
+    assert_eq!(bracket.rounds[1][0].player1, Some(players[0]));
+    assert_eq!(bracket.round_name(2), "Final");
+}
+
+#[test]
+fn reporting_results_advances_winners_to_a_champion() {
+    let players: Vec<UserId> = (1..=4).map(UserId::new).collect();
+    let mut bracket = Bracket::new(&players);
+    assert_eq!(bracket.ready_matches(), vec![(0, 0), (0, 1)]);
+
+    // An outsider can't be reported as the winner.
+    assert_eq!(bracket.report(0, 0, UserId::new(99)), None);
+    assert_eq!(bracket.report(0, 0, players[0]), None);
+    assert_eq!(bracket.report(0, 1, players[2]), Some((1, 0)));
+    assert_eq!(bracket.champion(), None);
+    assert_eq!(bracket.report(1, 0, players[2]), None);
+    assert_eq!(bracket.champion(), Some(players[2]));
+}
@@ -0,0 +1,53 @@
+use gamemaster_bot::commands::games::GamePayout;
+use gamemaster_bot::commands::games::side_bets::{
+    MAX_ODDS, MIN_ODDS, even_odds, odds_for, odds_from_ratings, top_earners, win_chances, winnings,
+};
+use serenity::model::id::UserId;
+
+fn payout(user_id: u64, amount: i64) -> GamePayout {
+    GamePayout {
+        user_id: UserId::new(user_id),
+        amount,
+    }
+}
+
+#[test]
+fn even_ratings_split_the_chances_and_pay_under_evens() {
+    let chances = win_chances(&[1200, 1200]);
+    assert!((chances[0] - 0.5).abs() < 1e-9);
+    assert_eq!(odds_from_ratings(&[1200, 1200]), vec![190, 190]);
+
+    let table = win_chances(&[1200, 1200, 1200, 1200]);
+    assert!((table.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert_eq!(odds_for(table[0]), 380);
+}
+
+#[test]
+fn a_hand_before_the_flop_is_priced_at_even_odds() {
+    assert_eq!(even_odds(4), vec![380; 4]);
+    assert_eq!(even_odds(2), odds_from_ratings(&[1200, 1200]));
+    assert!(even_odds(0).is_empty());
+}
+
+#[test]
+fn the_underdog_pays_more_within_bounds() {
+    let odds = odds_from_ratings(&[1600, 1200]);
+    assert!(odds[1] > odds[0]);
+    // A 400 point gap makes the favourite ten times as likely to win.
+    assert_eq!(odds, vec![MIN_ODDS, MAX_ODDS]);
+    assert_eq!(odds_for(0.0), MAX_ODDS);
+    assert_eq!(odds_for(1.0), MIN_ODDS);
+    assert_eq!(winnings(200, 185), 370);
+}
+
+#[test]
+fn the_top_earner_wins_and_a_round_with_no_winner_is_void() {
+    let duel = [payout(1, 50), payout(2, -50)];
+    assert_eq!(top_earners(&duel), vec![UserId::new(1)]);
+
+    let split = [payout(1, 30), payout(2, 30), payout(3, -60)];
+    assert_eq!(top_earners(&split), vec![UserId::new(1), UserId::new(2)]);
+
+    assert!(top_earners(&[payout(1, 0), payout(2, 0)]).is_empty());
+    assert!(top_earners(&[]).is_empty());
+}
@@ -0,0 +1,76 @@
+use gamemaster_bot::commands::tavern_games::logic::{
+    Effort, MAX_STAMINA, Target, Wrestler, drift, exert, house_effort, house_stat, landing,
+    score_at, spread,
+};
+
+#[test]
+fn surges_hit_harder_but_tire_the_arm() {
+    let mut a = Wrestler::new(40);
+    let mut b = Wrestler::new(40);
+    let push = exert(&mut a, &mut b, Effort::Push);
+    let surge = exert(&mut a, &mut b, Effort::Surge);
+    assert!(surge > push);
+    assert_eq!(a.stamina, MAX_STAMINA - 8 - 25);
+
+    let mut tired = Wrestler::new(40);
+    tired.stamina = 10;
+    assert_eq!(exert(&mut tired, &mut b, Effort::Surge), 0);
+    assert!(exert(&mut tired, &mut b, Effort::Push) < push);
+}
+
+#[test]
+fn a_brace_recovers_and_blunts_the_next_effort() {
+    let mut a = Wrestler::new(40);
+    let mut b = Wrestler::new(40);
+    a.stamina = 50;
+    assert_eq!(exert(&mut a, &mut b, Effort::Brace), 0);
+    assert_eq!(a.stamina, 70);
+    assert!(a.braced);
+
+    let full = exert(&mut Wrestler::new(40), &mut Wrestler::new(40), Effort::Push);
+    assert_eq!(exert(&mut b, &mut a, Effort::Push), full / 2);
+    assert!(!a.braced);
+}
+
+#[test]
+fn the_house_rests_when_tired_and_surges_when_losing() {
+    let mut tired = Wrestler::new(30);
+    tired.stamina = 15;
+    assert_eq!(house_effort(&tired, 0, 1), Effort::Brace);
+    assert_eq!(house_effort(&Wrestler::new(30), -60, 1), Effort::Surge);
+    assert_eq!(house_effort(&Wrestler::new(30), 10, 1), Effort::Push);
+    for roll in 0..13 {
+        assert!((house_stat(30, roll) - 30).abs() <= 6);
+    }
+    assert_eq!(house_stat(1, 0), 1);
+}
+
+#[test]
+fn darts_score_like_a_real_board() {
+    assert_eq!(score_at(0.0, 0.0), (50, "Bull".to_string()));
+    assert_eq!(score_at(10.0, 0.0), (25, "25".to_string()));
+    assert_eq!(score_at(0.0, 103.0), (60, "T20".to_string()));
+    assert_eq!(score_at(0.0, 166.0), (40, "D20".to_string()));
+    assert_eq!(score_at(0.0, 130.0), (20, "20".to_string()));
+    // Straight right is 6, straight down is 3.
+    assert_eq!(score_at(130.0, 0.0).0, 6);
+    assert_eq!(score_at(0.0, -130.0).0, 3);
+    assert_eq!(score_at(0.0, 200.0), (0, "Miss".to_string()));
+}
+
+#[test]
+fn aim_drifts_within_bounds_and_dexterity_tightens_throws() {
+    let mut aim = (0.0, 0.0);
+    for _ in 0..20 {
+        aim = drift(aim, (2.0, 2.0));
+    }
+    assert!(aim.0.hypot(aim.1) <= 30.0 + 1e-9);
+
+    assert!(spread(60) < spread(10));
+    assert_eq!(spread(1000), 10.0);
+
+    let (x, y) = landing(Target::Treble20, (0.0, 0.0), spread(40), (0.0, 0.0));
+    assert_eq!(score_at(x, y).0, 60);
+    let (x, y) = landing(Target::Bull, (5.0, 0.0), spread(40), (0.0, 0.5));
+    assert_eq!(score_at(x, y).0, 25);
+}
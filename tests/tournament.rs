@@ -0,0 +1,119 @@
+use gamemaster_bot::commands::games::lifecycle::SessionClock;
+use gamemaster_bot::commands::games::{Game, GameUpdate};
+use gamemaster_bot::commands::tournament::logic::{
+    STARTING_CHIPS, assign_tables, prize_payouts, rebalance,
+};
+use gamemaster_bot::commands::tournament::state::Tournament;
+use gamemaster_bot::commands::tournament::table::TournamentTable;
+use gamemaster_bot::database::models::TournamentGame;
+use serenity::model::id::{MessageId, UserId};
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+fn users(ids: std::ops::RangeInclusive<u64>) -> Vec<UserId> {
+    ids.map(UserId::new).collect()
+}
+
+#[test]
+fn entrants_are_spread_evenly_over_the_fewest_tables() {
+    let tables = assign_tables(&users(1..=13), 6);
+    let sizes: Vec<usize> = tables.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![5, 4, 4]);
+}
+
+#[test]
+fn rebalance_breaks_the_shortest_table_and_evens_out_the_rest() {
+    // Eight players fit on two tables of six, so the reporting table of two is broken up.
+    let mut tables = vec![users(1..=2), users(3..=5), users(6..=8)];
+    let moves = rebalance(&mut tables, 0, 6);
+    assert_eq!(moves.len(), 2);
+    assert!(tables[0].is_empty());
+    assert_eq!(tables[1].len() + tables[2].len(), 8);
+
+    // A table three players larger than another gives one up.
+    let mut tables = vec![users(1..=6), users(7..=9)];
+    let moves = rebalance(&mut tables, 0, 6);
+    assert_eq!(moves, vec![(UserId::new(6), 1)]);
+
+    // A lone player waiting elsewhere is moved even when another table reports.
+    let mut tables = vec![users(1..=3), users(4..=4)];
+    let moves = rebalance(&mut tables, 0, 6);
+    assert_eq!(moves, vec![(UserId::new(4), 0)]);
+    assert!(tables[1].is_empty());
+}
+
+#[test]
+fn prizes_follow_the_field_size_and_sum_to_the_pool() {
+    let standings = users(1..=7);
+    let payouts = prize_payouts(701, &standings);
+    let amounts: Vec<i64> = payouts.iter().map(|p| p.amount).collect();
+    assert_eq!(amounts, vec![351, 210, 140]);
+    assert_eq!(payouts[0].user_id, UserId::new(1));
+
+    let payouts = prize_payouts(300, &users(1..=3));
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(payouts[0].amount, 300);
+}
+
+#[test]
+fn eliminations_set_finishing_places_and_standings() {
+    let mut tournament = Tournament::new(1, TournamentGame::Holdem, 400, vec![users(1..=4)]);
+    // Players 3 and 4 bust in the same hand; 4 started it shorter, so finishes 4th.
+    tournament.chips.insert(UserId::new(4), 200);
+    let report = tournament.report_hand(
+        0,
+        &[
+            (UserId::new(1), 2 * STARTING_CHIPS),
+            (UserId::new(2), STARTING_CHIPS + 200),
+            (UserId::new(3), 0),
+            (UserId::new(4), 0),
+        ],
+    );
+    assert_eq!(
+        report.eliminated,
+        vec![(UserId::new(4), 4), (UserId::new(3), 3)]
+    );
+    assert_eq!(tournament.remaining(), 2);
+    assert!(tournament.take_standings().is_none());
+
+    tournament.report_hand(0, &[(UserId::new(1), 3200), (UserId::new(2), 0)]);
+    let standings = tournament.take_standings().expect("tournament is over");
+    assert_eq!(
+        standings,
+        vec![
+            UserId::new(1),
+            UserId::new(2),
+            UserId::new(3),
+            UserId::new(4)
+        ]
+    );
+    assert!(tournament.take_standings().is_none());
+}
+
+#[tokio::test]
+async fn a_waiting_table_keeps_its_clock_and_deals_once_players_arrive() {
+    let tournament = Tournament::new(
+        1,
+        TournamentGame::Holdem,
+        400,
+        vec![users(1..=3), users(4..=4)],
+    );
+    let tournament = Arc::new(Mutex::new(tournament));
+    let mut table = TournamentTable::new(tournament.clone(), 1);
+    assert!(table.hand.is_none());
+    // A table with nobody to play against still has a deadline, so the idle sweep never
+    // mistakes it for an abandoned game.
+    assert!(table.deadline(&SessionClock::new(Instant::now())).is_some());
+
+    {
+        let mut tournament = tournament.lock().unwrap();
+        tournament.tables[0].retain(|&u| u != UserId::new(3));
+        tournament.tables[1].push(UserId::new(3));
+    }
+    // Nothing is settled, so the database is never reached.
+    let db = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let update = table.on_timeout(&db, MessageId::new(1)).await;
+    assert!(matches!(update, GameUpdate::ReRender));
+    assert!(table.hand.is_some());
+}
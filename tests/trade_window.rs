@@ -0,0 +1,57 @@
+use gamemaster_bot::commands::economy::core::item::Item;
+use gamemaster_bot::commands::economy::core::registry::ItemDef;
+use gamemaster_bot::commands::economy::trade::state::{TradeSession, TradeStatus};
+use serenity::model::id::UserId;
+
+const ALICE: UserId = UserId::new(1);
+const BOB: UserId = UserId::new(2);
+
+fn session() -> TradeSession {
+    let mut s = TradeSession::new((ALICE, "alice".into()), (BOB, "bob".into()));
+    s.holdings[0].balance = 500;
+    s.holdings[0].items.insert(Item::Gem.id(), 1);
+    s.holdings[1].items.insert(Item::Ore.id(), 3);
+    s
+}
+
+#[test]
+fn any_change_resets_both_confirmations() {
+    let mut s = session();
+    let gem = ItemDef::builtin(Item::Gem);
+    let ore = ItemDef::builtin(Item::Ore);
+    s.add_item(ALICE, &gem, 1).unwrap();
+    s.add_item(BOB, &ore, 3).unwrap();
+    assert_eq!(s.confirm(ALICE), Ok(false));
+    // Bob sweetening (or stripping) his offer withdraws Alice's confirmation.
+    s.clear(BOB).unwrap();
+    assert!(!s.offers[0].confirmed);
+    s.add_item(BOB, &ore, 3).unwrap();
+    assert_eq!(s.confirm(ALICE), Ok(false));
+    assert_eq!(s.confirm(BOB), Ok(true));
+    s.add_coins(ALICE, 100).unwrap();
+    assert!(!s.both_confirmed());
+}
+
+#[test]
+fn offers_are_capped_by_holdings() {
+    let mut s = session();
+    let ore = ItemDef::builtin(Item::Ore);
+    assert!(s.add_item(ALICE, &ore, 1).is_err());
+    s.add_item(BOB, &ore, 2).unwrap();
+    assert!(s.add_item(BOB, &ore, 2).is_err());
+    assert!(s.add_coins(ALICE, 600).is_err());
+    s.add_coins(ALICE, 500).unwrap();
+    assert!(s.add_coins(BOB, 1).is_err());
+}
+
+#[test]
+fn outsiders_and_closed_trades_are_rejected() {
+    let mut s = session();
+    assert!(s.add_coins(UserId::new(3), 1).is_err());
+    assert!(
+        s.confirm(ALICE).is_err(),
+        "empty trades cannot be confirmed"
+    );
+    s.status = TradeStatus::Expired;
+    assert!(s.add_coins(ALICE, 1).is_err());
+}
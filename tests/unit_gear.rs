@@ -0,0 +1,59 @@
+use gamemaster_bot::database::models::{GearSlot, UnitGear};
+use gamemaster_bot::saga::gear::{
+    GearBonus, REPAIR_COST_PER_POINT, WEAR_ON_DEFEAT, WEAR_PER_BATTLE, battle_wear,
+    durability_label, repair_cost,
+};
+
+fn gear(slot: GearSlot, durability: i32, stats: (i32, i32, i32)) -> UnitGear {
+    UnitGear {
+        player_unit_id: 1,
+        slot,
+        item_id: 22,
+        durability,
+        max_durability: 30,
+        attack: stats.0,
+        defense: stats.1,
+        health: stats.2,
+    }
+}
+
+#[test]
+fn broken_gear_adds_no_stats() {
+    let loadout = [
+        gear(GearSlot::Weapon, 12, (4, 0, 0)),
+        gear(GearSlot::Armor, 0, (0, 3, 10)),
+        gear(GearSlot::Trinket, 30, (1, 1, 5)),
+    ];
+    assert_eq!(
+        GearBonus::from_gear(&loadout),
+        GearBonus {
+            attack: 5,
+            defense: 1,
+            health: 5
+        }
+    );
+    assert!(GearBonus::from_gear(&loadout[1..2]).is_zero());
+}
+
+#[test]
+fn defeats_wear_gear_faster() {
+    assert_eq!(battle_wear(false), WEAR_PER_BATTLE);
+    assert_eq!(battle_wear(true), WEAR_ON_DEFEAT);
+}
+
+#[test]
+fn repairs_cost_per_missing_point() {
+    assert_eq!(repair_cost(&gear(GearSlot::Weapon, 30, (4, 0, 0))), 0);
+    assert_eq!(
+        repair_cost(&gear(GearSlot::Weapon, 20, (4, 0, 0))),
+        10 * REPAIR_COST_PER_POINT
+    );
+    assert_eq!(
+        durability_label(&gear(GearSlot::Weapon, 0, (4, 0, 0))),
+        "💥 broken"
+    );
+    assert_eq!(
+        durability_label(&gear(GearSlot::Weapon, 5, (4, 0, 0))),
+        "⚠️ 5/30"
+    );
+}